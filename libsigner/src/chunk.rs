@@ -0,0 +1,534 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The envelope of the data written to stacker-db chunks.
+//!
+//! Raw chunks hold a serialized message as is, and so start with the message's type prefix.
+//! Compressed chunks start with `CHUNK_ENVELOPE_ZSTD`, which is not a valid type prefix,
+//! followed by the zstd-compressed message. Chunks written by signers without compression
+//! support are therefore still decoded as raw chunks.
+//!
+//! Messages that are still too large for a single chunk are split into fragments, written one
+//! after the other to the same slot. Fragment chunks start with `CHUNK_ENVELOPE_FRAGMENT` and
+//! are reassembled by a `FragmentBuffer` from the stream of chunk events.
+
+use std::time::{Duration, Instant};
+
+use hashbrown::HashMap;
+use libstackerdb::StackerDBChunkData;
+use stacks_common::codec::{read_next, write_next, Error as CodecError, StacksMessageCodec};
+use stacks_common::util::hash::Sha512Trunc256Sum;
+
+/// The first byte of a zstd-compressed chunk
+pub const CHUNK_ENVELOPE_ZSTD: u8 = 0xff;
+/// The first byte of a chunk holding a fragment of a larger message
+pub const CHUNK_ENVELOPE_FRAGMENT: u8 = 0xfe;
+/// The number of bytes a fragment adds to its payload: the envelope byte, the message ID,
+/// the index, the total and the payload length
+pub const FRAGMENT_OVERHEAD: usize = 1 + 8 + 4 + 4 + 4;
+/// The maximum number of fragments a message may be split into
+pub const MAX_FRAGMENTS: u32 = 64;
+/// How long an incomplete message is kept waiting for its missing fragments
+pub const DEFAULT_FRAGMENT_TIMEOUT: Duration = Duration::from_secs(60);
+/// The default size in bytes above which serialized messages are compressed
+pub const DEFAULT_CHUNK_COMPRESSION_THRESHOLD: usize = 64 * 1024;
+/// The maximum size in bytes of a decompressed chunk. Guards against decompression bombs.
+const MAX_DECOMPRESSED_CHUNK_LEN: usize = 16 * 1024 * 1024;
+/// The zstd compression level
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
+/// The encoding of the data of a stacker-db chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkEnvelope {
+    /// The serialized message, as is
+    Raw,
+    /// The zstd-compressed serialized message
+    Zstd,
+    /// A fragment of a larger chunk
+    Fragment,
+}
+
+impl ChunkEnvelope {
+    /// The envelope of the given chunk data
+    pub fn of(data: &[u8]) -> Self {
+        match data.first() {
+            Some(&CHUNK_ENVELOPE_ZSTD) => Self::Zstd,
+            Some(&CHUNK_ENVELOPE_FRAGMENT) => Self::Fragment,
+            _ => Self::Raw,
+        }
+    }
+}
+
+/// Wrap a serialized message into chunk data. The message is compressed if it is larger than
+/// `compression_threshold` and compression actually shrinks it.
+/// If `compression_threshold` is `None`, the message is always written raw.
+pub fn encode_chunk(message_bytes: Vec<u8>, compression_threshold: Option<usize>) -> Vec<u8> {
+    let Some(threshold) = compression_threshold else {
+        return message_bytes;
+    };
+    if message_bytes.len() <= threshold {
+        return message_bytes;
+    }
+    let compressed = match zstd::bulk::compress(&message_bytes, ZSTD_COMPRESSION_LEVEL) {
+        Ok(compressed) => compressed,
+        Err(e) => {
+            warn!("Failed to compress chunk data, writing it raw: {e:?}");
+            return message_bytes;
+        }
+    };
+    if compressed.len() + 1 >= message_bytes.len() {
+        return message_bytes;
+    }
+    let mut data = Vec::with_capacity(compressed.len() + 1);
+    data.push(CHUNK_ENVELOPE_ZSTD);
+    data.extend(compressed);
+    data
+}
+
+/// Decode a message from chunk data, whether it was written raw or compressed
+pub fn decode_chunk<T: StacksMessageCodec>(data: &[u8]) -> Result<T, CodecError> {
+    match ChunkEnvelope::of(data) {
+        ChunkEnvelope::Raw => read_next::<T, _>(&mut &data[..]),
+        ChunkEnvelope::Zstd => {
+            let message_bytes = zstd::bulk::decompress(&data[1..], MAX_DECOMPRESSED_CHUNK_LEN)
+                .map_err(|e| {
+                    CodecError::DeserializeError(format!("Failed to decompress chunk: {e:?}"))
+                })?;
+            read_next::<T, _>(&mut &message_bytes[..])
+        }
+        ChunkEnvelope::Fragment => Err(CodecError::DeserializeError(
+            "Chunk holds a fragment, which must be reassembled before decoding".into(),
+        )),
+    }
+}
+
+/// A piece of chunk data too large to be written as a single chunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fragment {
+    /// Identifies the fragments of the same chunk data
+    pub message_id: u64,
+    /// The position of this fragment
+    pub index: u32,
+    /// The number of fragments the chunk data was split into
+    pub total: u32,
+    /// The bytes of the chunk data carried by this fragment
+    pub payload: Vec<u8>,
+}
+
+impl StacksMessageCodec for Fragment {
+    fn consensus_serialize<W: std::io::Write>(&self, fd: &mut W) -> Result<(), CodecError> {
+        write_next(fd, &CHUNK_ENVELOPE_FRAGMENT)?;
+        write_next(fd, &self.message_id)?;
+        write_next(fd, &self.index)?;
+        write_next(fd, &self.total)?;
+        write_next(fd, &self.payload)?;
+        Ok(())
+    }
+
+    fn consensus_deserialize<R: std::io::Read>(fd: &mut R) -> Result<Self, CodecError> {
+        let envelope = read_next::<u8, _>(fd)?;
+        if envelope != CHUNK_ENVELOPE_FRAGMENT {
+            return Err(CodecError::DeserializeError(format!(
+                "Not a fragment: unexpected envelope {envelope}"
+            )));
+        }
+        let message_id = read_next::<u64, _>(fd)?;
+        let index = read_next::<u32, _>(fd)?;
+        let total = read_next::<u32, _>(fd)?;
+        if total == 0 || total > MAX_FRAGMENTS || index >= total {
+            return Err(CodecError::DeserializeError(format!(
+                "Invalid fragment {index} of {total}"
+            )));
+        }
+        let payload = read_next::<Vec<u8>, _>(fd)?;
+        Ok(Self {
+            message_id,
+            index,
+            total,
+            payload,
+        })
+    }
+}
+
+/// Split chunk data into fragment chunks of at most `max_chunk_size` bytes.
+/// Chunk data that already fits is returned as is.
+pub fn fragment_chunk(data: Vec<u8>, max_chunk_size: usize) -> Result<Vec<Vec<u8>>, CodecError> {
+    if data.len() <= max_chunk_size {
+        return Ok(vec![data]);
+    }
+    let payload_size = max_chunk_size.saturating_sub(FRAGMENT_OVERHEAD);
+    if payload_size == 0 {
+        return Err(CodecError::SerializeError(format!(
+            "Chunk size {max_chunk_size} cannot hold a fragment"
+        )));
+    }
+    let total = u32::try_from(data.len().div_ceil(payload_size))
+        .ok()
+        .filter(|total| *total <= MAX_FRAGMENTS)
+        .ok_or_else(|| {
+            CodecError::SerializeError(format!(
+                "Chunk data of {} bytes needs more than {MAX_FRAGMENTS} fragments",
+                data.len()
+            ))
+        })?;
+    // Identical chunk data gets the same ID, so resent fragments are recognized as duplicates
+    let hash = Sha512Trunc256Sum::from_data(&data);
+    let mut message_id_bytes = [0u8; 8];
+    message_id_bytes.copy_from_slice(&hash.as_bytes()[..8]);
+    let message_id = u64::from_be_bytes(message_id_bytes);
+    Ok(data
+        .chunks(payload_size)
+        .zip(0..total)
+        .map(|(payload, index)| {
+            Fragment {
+                message_id,
+                index,
+                total,
+                payload: payload.to_vec(),
+            }
+            .serialize_to_vec()
+        })
+        .collect())
+}
+
+/// The fragments received so far of a chunk
+#[derive(Debug)]
+struct PartialChunk {
+    /// The received fragment payloads, by index
+    payloads: Vec<Option<Vec<u8>>>,
+    /// When the first fragment was received
+    first_seen: Instant,
+}
+
+/// Reassembles fragmented chunk data from the chunks written to stacker-db slots.
+/// Fragments may arrive out of order or more than once. Incomplete chunk data is
+/// dropped once `timeout` has passed since its first fragment was received.
+#[derive(Debug)]
+pub struct FragmentBuffer {
+    /// The incomplete chunk data, keyed by slot ID and message ID
+    partial_chunks: HashMap<(u32, u64), PartialChunk>,
+    /// How long incomplete chunk data is kept
+    timeout: Duration,
+}
+
+impl Default for FragmentBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_FRAGMENT_TIMEOUT)
+    }
+}
+
+impl FragmentBuffer {
+    /// Create an empty buffer dropping incomplete chunk data after `timeout`
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            partial_chunks: HashMap::new(),
+            timeout,
+        }
+    }
+
+    /// Add a fragment read from the given slot at `now`.
+    /// Returns the reassembled chunk data once all of its fragments were received.
+    pub fn add_fragment(
+        &mut self,
+        slot_id: u32,
+        fragment: Fragment,
+        now: Instant,
+    ) -> Option<Vec<u8>> {
+        self.prune(now);
+        let key = (slot_id, fragment.message_id);
+        let partial = self
+            .partial_chunks
+            .entry(key)
+            .or_insert_with(|| PartialChunk {
+                payloads: vec![None; fragment.total as usize],
+                first_seen: now,
+            });
+        if partial.payloads.len() != fragment.total as usize {
+            warn!(
+                "Dropping fragment {} of message {} in slot {slot_id}: expected {} fragments, got {}",
+                fragment.index,
+                fragment.message_id,
+                partial.payloads.len(),
+                fragment.total
+            );
+            return None;
+        }
+        let Some(payload) = partial.payloads.get_mut(fragment.index as usize) else {
+            return None;
+        };
+        if payload.is_none() {
+            *payload = Some(fragment.payload);
+        }
+        if partial.payloads.iter().any(Option::is_none) {
+            return None;
+        }
+        let partial = self.partial_chunks.remove(&key)?;
+        Some(partial.payloads.into_iter().flatten().flatten().collect())
+    }
+
+    /// Replace the fragments among the given chunks with the chunk data they complete.
+    /// Fragments that do not complete any chunk data are buffered and left out.
+    pub fn reassemble_chunks(
+        &mut self,
+        chunks: Vec<StackerDBChunkData>,
+        now: Instant,
+    ) -> Vec<StackerDBChunkData> {
+        let mut reassembled = Vec::with_capacity(chunks.len());
+        for mut chunk in chunks {
+            if ChunkEnvelope::of(&chunk.data) != ChunkEnvelope::Fragment {
+                reassembled.push(chunk);
+                continue;
+            }
+            let fragment = match read_next::<Fragment, _>(&mut &chunk.data[..]) {
+                Ok(fragment) => fragment,
+                Err(e) => {
+                    warn!(
+                        "Dropping malformed fragment in slot {}: {e:?}",
+                        chunk.slot_id
+                    );
+                    continue;
+                }
+            };
+            if let Some(data) = self.add_fragment(chunk.slot_id, fragment, now) {
+                chunk.data = data;
+                reassembled.push(chunk);
+            }
+        }
+        reassembled
+    }
+
+    /// Drop the incomplete chunk data that timed out at `now`
+    pub fn prune(&mut self, now: Instant) {
+        let timeout = self.timeout;
+        self.partial_chunks
+            .retain(|(slot_id, message_id), partial| {
+                let expired = now.saturating_duration_since(partial.first_seen) >= timeout;
+                if expired {
+                    debug!("Dropping incomplete message {message_id} in slot {slot_id}");
+                }
+                !expired
+            });
+    }
+
+    /// The number of chunks waiting for more fragments
+    pub fn len(&self) -> usize {
+        self.partial_chunks.len()
+    }
+
+    /// Whether no chunk is waiting for more fragments
+    pub fn is_empty(&self) -> bool {
+        self.partial_chunks.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use blockstack_lib::chainstate::nakamoto::signer_set::NakamotoSigners;
+    use blockstack_lib::chainstate::stacks::events::StackerDBChunksEvent;
+    use blockstack_lib::chainstate::stacks::{
+        StacksTransaction, TransactionAnchorMode, TransactionAuth, TransactionPayload,
+        TransactionPostConditionMode, TransactionSmartContract, TransactionVersion,
+    };
+    use blockstack_lib::util_lib::strings::StacksString;
+    use libstackerdb::StackerDBChunkData;
+    use stacks_common::consts::CHAIN_ID_TESTNET;
+    use stacks_common::types::chainstate::StacksPrivateKey;
+
+    use super::*;
+    use crate::v1::messages::SignerMessage;
+    use crate::SignerEvent;
+
+    /// A transactions message large enough to be compressed
+    fn large_transactions_message() -> SignerMessage {
+        let sk = StacksPrivateKey::new();
+        let txs = (0..1000)
+            .map(|i| StacksTransaction {
+                version: TransactionVersion::Testnet,
+                chain_id: CHAIN_ID_TESTNET,
+                auth: TransactionAuth::from_p2pkh(&sk).unwrap(),
+                anchor_mode: TransactionAnchorMode::Any,
+                post_condition_mode: TransactionPostConditionMode::Allow,
+                post_conditions: vec![],
+                payload: TransactionPayload::SmartContract(
+                    TransactionSmartContract {
+                        name: format!("test-contract-{i}").as_str().into(),
+                        code_body: StacksString::from_str("(/ 1 0)").unwrap(),
+                    },
+                    None,
+                ),
+            })
+            .collect();
+        SignerMessage::Transactions(txs)
+    }
+
+    #[test]
+    fn large_message_should_round_trip_compressed() {
+        let message = large_transactions_message();
+        let message_bytes = message.serialize_to_vec();
+        assert!(message_bytes.len() > DEFAULT_CHUNK_COMPRESSION_THRESHOLD);
+
+        let data = encode_chunk(
+            message_bytes.clone(),
+            Some(DEFAULT_CHUNK_COMPRESSION_THRESHOLD),
+        );
+        assert_eq!(ChunkEnvelope::of(&data), ChunkEnvelope::Zstd);
+        assert!(data.len() < message_bytes.len());
+        assert_eq!(decode_chunk::<SignerMessage>(&data).unwrap(), message);
+
+        // Compression can be disabled
+        let data = encode_chunk(message_bytes.clone(), None);
+        assert_eq!(data, message_bytes);
+        assert_eq!(decode_chunk::<SignerMessage>(&data).unwrap(), message);
+    }
+
+    #[test]
+    fn small_message_should_stay_raw() {
+        let message = SignerMessage::Transactions(vec![]);
+        let message_bytes = message.serialize_to_vec();
+        let data = encode_chunk(
+            message_bytes.clone(),
+            Some(DEFAULT_CHUNK_COMPRESSION_THRESHOLD),
+        );
+        assert_eq!(data, message_bytes);
+        assert_eq!(ChunkEnvelope::of(&data), ChunkEnvelope::Raw);
+        assert_eq!(decode_chunk::<SignerMessage>(&data).unwrap(), message);
+    }
+
+    #[test]
+    fn corrupt_compressed_chunk_should_fail() {
+        let data = vec![CHUNK_ENVELOPE_ZSTD, 1, 2, 3];
+        assert!(decode_chunk::<SignerMessage>(&data).is_err());
+    }
+
+    /// Split the serialized message into fragments of at most `max_chunk_size` bytes
+    fn fragments_of(message: &SignerMessage, max_chunk_size: usize) -> Vec<Fragment> {
+        fragment_chunk(message.serialize_to_vec(), max_chunk_size)
+            .unwrap()
+            .into_iter()
+            .map(|data| {
+                assert!(data.len() <= max_chunk_size);
+                assert_eq!(ChunkEnvelope::of(&data), ChunkEnvelope::Fragment);
+                read_next::<Fragment, _>(&mut &data[..]).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fragments_should_reassemble_out_of_order_with_duplicates() {
+        let message = large_transactions_message();
+        let max_chunk_size = message.serialize_to_vec().len() / 3;
+        let fragments = fragments_of(&message, max_chunk_size);
+        assert_eq!(fragments.len(), 4);
+
+        let mut buffer = FragmentBuffer::default();
+        let now = Instant::now();
+        for index in [2, 0, 2, 3, 0] {
+            assert!(buffer
+                .add_fragment(1, fragments[index].clone(), now)
+                .is_none());
+        }
+        // Fragments of the same message in another slot are kept apart
+        assert!(buffer.add_fragment(2, fragments[1].clone(), now).is_none());
+        assert_eq!(buffer.len(), 2);
+
+        let data = buffer
+            .add_fragment(1, fragments[1].clone(), now)
+            .expect("Expected the reassembled message");
+        assert_eq!(decode_chunk::<SignerMessage>(&data).unwrap(), message);
+        assert_eq!(buffer.len(), 1);
+    }
+
+    #[test]
+    fn incomplete_fragments_should_time_out() {
+        let message = large_transactions_message();
+        let max_chunk_size = message.serialize_to_vec().len() / 2;
+        let fragments = fragments_of(&message, max_chunk_size);
+        let timeout = Duration::from_secs(10);
+        let mut buffer = FragmentBuffer::new(timeout);
+        let now = Instant::now();
+        assert!(buffer.add_fragment(0, fragments[0].clone(), now).is_none());
+        buffer.prune(now + timeout);
+        assert!(buffer.is_empty());
+
+        // The missing fragments alone no longer complete the message
+        for fragment in &fragments[1..] {
+            assert!(buffer
+                .add_fragment(0, fragment.clone(), now + timeout)
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn small_chunk_should_not_be_fragmented() {
+        let data = SignerMessage::Transactions(vec![]).serialize_to_vec();
+        assert_eq!(
+            fragment_chunk(data.clone(), data.len()).unwrap(),
+            vec![data.clone()]
+        );
+        assert!(fragment_chunk(data, FRAGMENT_OVERHEAD).is_err());
+    }
+
+    #[test]
+    fn invalid_fragments_should_fail_to_decode() {
+        let fragment = Fragment {
+            message_id: 1,
+            index: 2,
+            total: 2,
+            payload: vec![1, 2, 3],
+        };
+        assert!(read_next::<Fragment, _>(&mut &fragment.serialize_to_vec()[..]).is_err());
+        let fragment = Fragment {
+            index: 0,
+            total: MAX_FRAGMENTS + 1,
+            ..fragment
+        };
+        assert!(read_next::<Fragment, _>(&mut &fragment.serialize_to_vec()[..]).is_err());
+        assert!(decode_chunk::<SignerMessage>(&[CHUNK_ENVELOPE_FRAGMENT]).is_err());
+    }
+
+    #[test]
+    fn signer_event_should_decode_mixed_raw_and_compressed_slots() {
+        let small_message = SignerMessage::Transactions(vec![]);
+        let large_message = large_transactions_message();
+        let contract_id = NakamotoSigners::make_signers_db_contract_id(0, 0, false);
+        let chunks = [
+            encode_chunk(small_message.serialize_to_vec(), None),
+            encode_chunk(
+                large_message.serialize_to_vec(),
+                Some(DEFAULT_CHUNK_COMPRESSION_THRESHOLD),
+            ),
+            // a signer without compression support writes large messages raw
+            encode_chunk(large_message.serialize_to_vec(), None),
+        ];
+        let event = StackerDBChunksEvent {
+            contract_id,
+            modified_slots: chunks
+                .into_iter()
+                .enumerate()
+                .map(|(slot_id, data)| StackerDBChunkData::new(slot_id as u32, 1, data))
+                .collect(),
+        };
+        let SignerEvent::SignerMessages(_, messages) =
+            SignerEvent::<SignerMessage>::try_from(event).unwrap()
+        else {
+            panic!("Expected signer messages");
+        };
+        assert_eq!(
+            messages,
+            vec![small_message, large_message.clone(), large_message]
+        );
+    }
+}
@@ -39,6 +39,12 @@ pub enum RPCError {
     /// HTTP error
     #[error("HTTP code {0}")]
     HttpError(u32),
+    /// TLS error, e.g. a failed handshake or certificate verification
+    #[error("TLS error: {0}")]
+    Tls(String),
+    /// The response exceeded the maximum allowed size in bytes
+    #[error("Response exceeded the maximum size of {0} bytes")]
+    ResponseTooLarge(u64),
 }
 
 /// Errors originating from receiving event data from the Stacks node
@@ -20,6 +20,7 @@ use std::net::{SocketAddr, TcpListener, TcpStream};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
+use std::time::Instant;
 
 use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
 use blockstack_lib::chainstate::stacks::boot::{MINERS_NAME, SIGNERS_NAME};
@@ -51,7 +52,9 @@ use wsts::net::{
 };
 use wsts::state_machine::signer;
 
+use crate::chunk::{decode_chunk, FragmentBuffer};
 use crate::http::{decode_http_body, decode_http_request};
+use crate::signer_set::SignerSlotOwners;
 use crate::EventError;
 
 /// Define the trait for the event processor
@@ -184,6 +187,10 @@ pub struct SignerEventReceiver<T: SignerEventTrait> {
     stop_signal: Arc<AtomicBool>,
     /// Whether the receiver is running on mainnet
     is_mainnet: bool,
+    /// The fragments of signer messages still waiting to be reassembled
+    fragment_buffer: FragmentBuffer,
+    /// The owners of the signers stacker-db slots, whose signatures chunks are checked against
+    slot_owners: SignerSlotOwners,
 }
 
 impl<T: SignerEventTrait> SignerEventReceiver<T> {
@@ -196,13 +203,20 @@ impl<T: SignerEventTrait> SignerEventReceiver<T> {
             out_channels: vec![],
             stop_signal: Arc::new(AtomicBool::new(false)),
             is_mainnet,
+            fragment_buffer: FragmentBuffer::default(),
+            slot_owners: SignerSlotOwners::default(),
         }
     }
 
+    /// Drop the signer messages not signed by the owner of the slot they were written to
+    pub fn set_slot_owners(&mut self, slot_owners: SignerSlotOwners) {
+        self.slot_owners = slot_owners;
+    }
+
     /// Do something with the socket
     pub fn with_server<F, R>(&mut self, todo: F) -> Result<R, EventError>
     where
-        F: FnOnce(&SignerEventReceiver<T>, &mut HttpServer, bool) -> R,
+        F: FnOnce(&mut SignerEventReceiver<T>, &mut HttpServer, bool) -> R,
     {
         let mut server = if let Some(s) = self.http_server.take() {
             s
@@ -294,7 +308,12 @@ impl<T: SignerEventTrait> EventReceiver<T> for SignerEventReceiver<T> {
                 )));
             }
             if request.url() == "/stackerdb_chunks" {
-                process_stackerdb_event(event_receiver.local_addr, request)
+                process_stackerdb_event(
+                    event_receiver.local_addr,
+                    &event_receiver.slot_owners,
+                    &mut event_receiver.fragment_buffer,
+                    request,
+                )
                     .map_err(|e| {
                         error!("Error processing stackerdb_chunks message"; "err" => ?e);
                         e
@@ -376,8 +395,12 @@ fn ack_dispatcher(request: HttpRequest) {
 }
 
 /// Process a stackerdb event from the node
+/// Signer chunks not signed by the owner of their slot are dropped. Fragments of signer
+/// messages are buffered until the whole message was received.
 fn process_stackerdb_event<T: SignerEventTrait>(
     local_addr: Option<SocketAddr>,
+    slot_owners: &SignerSlotOwners,
+    fragment_buffer: &mut FragmentBuffer,
     mut request: HttpRequest,
 ) -> Result<SignerEvent<T>, EventError> {
     debug!("Got stackerdb_chunks event");
@@ -391,9 +414,19 @@ fn process_stackerdb_event<T: SignerEventTrait>(
         )));
     }
 
-    let event: StackerDBChunksEvent = serde_json::from_slice(body.as_bytes())
+    let mut event: StackerDBChunksEvent = serde_json::from_slice(body.as_bytes())
         .map_err(|e| EventError::Deserialize(format!("Could not decode body to JSON: {:?}", &e)))?;
 
+    if event.contract_id.name.starts_with(SIGNERS_NAME) && event.contract_id.is_boot() {
+        let mut chunks = std::mem::take(&mut event.modified_slots);
+        if let Some((signer_set, _)) =
+            get_signers_db_signer_set_message_id(event.contract_id.name.as_str())
+        {
+            chunks = slot_owners.filter_chunks(signer_set, chunks);
+        }
+        event.modified_slots = fragment_buffer.reassemble_chunks(chunks, Instant::now());
+    }
+
     let event_contract_id = event.contract_id.clone();
 
     let signer_event = match SignerEvent::try_from(event) {
@@ -446,7 +479,7 @@ impl<T: SignerEventTrait> TryFrom<StackerDBChunksEvent> for SignerEvent<T> {
             let signer_messages: Vec<T> = event
                 .modified_slots
                 .iter()
-                .filter_map(|chunk| read_next::<T, _>(&mut &chunk.data[..]).ok())
+                .filter_map(|chunk| decode_chunk::<T>(&chunk.data).ok())
                 .collect();
             SignerEvent::SignerMessages(signer_set, signer_messages)
         } else {
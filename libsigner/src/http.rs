@@ -229,6 +229,7 @@ pub fn run_http_request<S: Read + Write>(
     path: &str,
     content_type: Option<&str>,
     payload: &[u8],
+    max_response_bytes: u64,
 ) -> Result<Vec<u8>, RPCError> {
     let content_length_hdr = if !payload.is_empty() {
         format!("Content-Length: {}\r\n", payload.len())
@@ -254,7 +255,14 @@ pub fn run_http_request<S: Read + Write>(
 
     let mut buf = vec![];
 
-    sock.read_to_end(&mut buf)?;
+    // Read one byte past the limit so that an oversized response can be told apart from one
+    // that is exactly at the limit, without ever buffering more than that.
+    sock.by_ref()
+        .take(max_response_bytes.saturating_add(1))
+        .read_to_end(&mut buf)?;
+    if buf.len() as u64 > max_response_bytes {
+        return Err(RPCError::ResponseTooLarge(max_response_bytes));
+    }
 
     let (headers, body_offset) = decode_http_response(&buf)?;
     if body_offset >= buf.len() {
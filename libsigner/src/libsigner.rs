@@ -36,17 +36,24 @@ extern crate libc;
 #[cfg(test)]
 mod tests;
 
+mod chunk;
 mod error;
 mod events;
 mod http;
 mod runloop;
 mod session;
 mod signer_set;
+mod tls;
 /// v0 signer related code
 pub mod v0;
 /// v1 signer related code
 pub mod v1;
 
+pub use crate::chunk::{
+    decode_chunk, encode_chunk, fragment_chunk, ChunkEnvelope, Fragment, FragmentBuffer,
+    CHUNK_ENVELOPE_FRAGMENT, CHUNK_ENVELOPE_ZSTD, DEFAULT_CHUNK_COMPRESSION_THRESHOLD,
+    DEFAULT_FRAGMENT_TIMEOUT, FRAGMENT_OVERHEAD, MAX_FRAGMENTS,
+};
 pub use crate::error::{EventError, RPCError};
 pub use crate::events::{
     BlockProposal, EventReceiver, EventStopSignaler, SignerEvent, SignerEventReceiver,
@@ -54,4 +61,5 @@ pub use crate::events::{
 };
 pub use crate::runloop::{RunningSigner, Signer, SignerRunLoop};
 pub use crate::session::{SignerSession, StackerDBSession};
-pub use crate::signer_set::{Error as ParseSignerEntriesError, SignerEntries};
+pub use crate::signer_set::{Error as ParseSignerEntriesError, SignerEntries, SignerSlotOwners};
+pub use crate::tls::{is_tls_error, NodeStream, NodeTransport};
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::net::{SocketAddr, TcpStream};
+use std::net::SocketAddr;
 use std::str;
 
 use clarity::vm::types::QualifiedContractIdentifier;
@@ -27,6 +27,7 @@ use stacks_common::codec::StacksMessageCodec;
 
 use crate::error::RPCError;
 use crate::http::run_http_request;
+use crate::tls::{NodeStream, NodeTransport};
 
 /// Trait for connecting to and querying a signer Stacker DB replica
 pub trait SignerSession {
@@ -78,6 +79,36 @@ pub trait SignerSession {
         Ok(latest_chunks.swap_remove(0))
     }
 
+    /// Get the latest chunks of the given slots along with their signatures, so that they can be
+    /// checked against the slots' owners. Each chunk is fetched at the version listed in the
+    /// slot metadata; a slot written to in the meantime is returned as `None`.
+    fn get_latest_signed_chunks(
+        &mut self,
+        slot_ids: &[u32],
+    ) -> Result<Vec<Option<StackerDBChunkData>>, RPCError> {
+        let slots_metadata = self.list_chunks()?;
+        let mut chunks = Vec::with_capacity(slot_ids.len());
+        for slot_id in slot_ids {
+            let Some(slot_metadata) = slots_metadata
+                .iter()
+                .find(|slot_metadata| slot_metadata.slot_id == *slot_id)
+            else {
+                chunks.push(None);
+                continue;
+            };
+            let chunk = self
+                .get_chunk(*slot_id, slot_metadata.slot_version)?
+                .map(|data| StackerDBChunkData {
+                    slot_id: *slot_id,
+                    slot_version: slot_metadata.slot_version,
+                    sig: slot_metadata.signature,
+                    data,
+                });
+            chunks.push(chunk);
+        }
+        Ok(chunks)
+    }
+
     /// Get a single latest chunk from the StackerDB and deserialize into `T` using the
     /// StacksMessageCodec.
     fn get_latest<T: StacksMessageCodec>(&mut self, slot_id: u32) -> Result<Option<T>, RPCError> {
@@ -101,8 +132,12 @@ pub struct StackerDBSession {
     pub host: String,
     /// contract we're talking to
     pub stackerdb_contract_id: QualifiedContractIdentifier,
+    /// how to connect to the replica
+    pub transport: NodeTransport,
+    /// the maximum size of a response (headers included) read from the replica
+    pub max_response_bytes: u64,
     /// connection to the replica
-    sock: Option<TcpStream>,
+    sock: Option<NodeStream>,
 }
 
 impl StackerDBSession {
@@ -111,21 +146,29 @@ impl StackerDBSession {
         StackerDBSession {
             host: host.to_owned(),
             stackerdb_contract_id,
+            transport: NodeTransport::Plain,
+            max_response_bytes: u64::MAX,
             sock: None,
         }
     }
 
+    /// use the given transport for all connections to the replica
+    pub fn with_transport(mut self, transport: NodeTransport) -> StackerDBSession {
+        self.transport = transport;
+        self
+    }
+
     /// connect or reconnect to the node
     fn connect_or_reconnect(&mut self) -> Result<(), RPCError> {
         debug!("connect to {}", &self.host);
-        self.sock = Some(TcpStream::connect(&self.host)?);
+        self.sock = Some(self.transport.connect(&self.host)?);
         Ok(())
     }
 
     /// Do something with the connected socket
     fn with_socket<F, R>(&mut self, todo: F) -> Result<R, RPCError>
     where
-        F: FnOnce(&mut StackerDBSession, &mut TcpStream) -> R,
+        F: FnOnce(&mut StackerDBSession, &mut NodeStream) -> R,
     {
         // TODO: fix this so we can use persistent connection
         // See https://github.com/stacks-network/stacks-blockchain/issues/3922
@@ -154,7 +197,15 @@ impl StackerDBSession {
         payload: &[u8],
     ) -> Result<Vec<u8>, RPCError> {
         self.with_socket(|session, sock| {
-            run_http_request(sock, &session.host, verb, path, content_type, payload)
+            run_http_request(
+                sock,
+                &session.host,
+                verb,
+                path,
+                content_type,
+                payload,
+                session.max_response_bytes,
+            )
         })?
     }
 }
@@ -13,8 +13,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::sync::{Arc, RwLock};
+
 use blockstack_lib::chainstate::stacks::boot::NakamotoSignerEntry;
 use hashbrown::{HashMap, HashSet};
+use libstackerdb::StackerDBChunkData;
 use stacks_common::types::chainstate::{StacksAddress, StacksPublicKey};
 use wsts::curve::ecdsa;
 use wsts::curve::point::{Compressed, Point};
@@ -135,3 +138,81 @@ impl SignerEntries {
         Ok((num_keys as f64 * 9_f64 / 10_f64).ceil() as u32)
     }
 }
+
+/// The owners of the signers stacker-db slots, by signer set.
+/// Clones share the same table, so that the signer runloop can record the owners of the slots
+/// as it learns the signer sets, while the event receiver checks chunks against them.
+#[derive(Debug, Clone, Default)]
+pub struct SignerSlotOwners {
+    /// The address of each slot's owner, keyed by signer set and then slot ID
+    owners: Arc<RwLock<HashMap<u32, HashMap<u32, StacksAddress>>>>,
+    /// Called for every chunk dropped because it was not signed by its slot's owner
+    on_rejected_chunk: Option<fn()>,
+}
+
+impl SignerSlotOwners {
+    /// Call `on_rejected_chunk` for every chunk dropped because it was not signed by its slot's owner
+    pub fn with_rejected_chunk_callback(mut self, on_rejected_chunk: fn()) -> Self {
+        self.on_rejected_chunk = Some(on_rejected_chunk);
+        self
+    }
+
+    /// Record the owners of the slots of the given signer set, replacing those of any
+    /// previous reward cycle using the same signer set
+    pub fn set_signer_set(&self, signer_set: u32, owners: HashMap<u32, StacksAddress>) {
+        self.owners
+            .write()
+            .expect("FATAL: signer slot owners lock poisoned")
+            .insert(signer_set, owners);
+    }
+
+    /// Whether the owners of the slots of the given signer set are known
+    pub fn knows_signer_set(&self, signer_set: u32) -> bool {
+        self.owners
+            .read()
+            .expect("FATAL: signer slot owners lock poisoned")
+            .contains_key(&signer_set)
+    }
+
+    /// Whether the chunk was signed by the owner of its slot in the given signer set.
+    /// Chunks of a signer set whose owners are unknown cannot be checked and are accepted.
+    pub fn is_signed_by_owner(&self, signer_set: u32, chunk: &StackerDBChunkData) -> bool {
+        let owners = self
+            .owners
+            .read()
+            .expect("FATAL: signer slot owners lock poisoned");
+        let Some(signer_set_owners) = owners.get(&signer_set) else {
+            return true;
+        };
+        let Some(owner) = signer_set_owners.get(&chunk.slot_id) else {
+            return false;
+        };
+        chunk.verify(owner).unwrap_or(false)
+    }
+
+    /// Drop the chunks that were not signed by the owner of their slot in the given signer set
+    pub fn filter_chunks(
+        &self,
+        signer_set: u32,
+        chunks: Vec<StackerDBChunkData>,
+    ) -> Vec<StackerDBChunkData> {
+        chunks
+            .into_iter()
+            .filter(|chunk| {
+                if self.is_signed_by_owner(signer_set, chunk) {
+                    return true;
+                }
+                warn!(
+                    "Dropping stackerdb chunk not signed by the owner of its slot";
+                    "signer_set" => signer_set,
+                    "slot_id" => chunk.slot_id,
+                    "slot_version" => chunk.slot_version,
+                );
+                if let Some(on_rejected_chunk) = self.on_rejected_chunk {
+                    on_rejected_chunk();
+                }
+                false
+            })
+            .collect()
+    }
+}
@@ -269,6 +269,7 @@ fn test_run_http_request_with_body() {
             path,
             content_type,
             &payload,
+            u64::MAX,
         )
         .unwrap();
         assert_eq!(result_chunked, body_bytes);
@@ -280,6 +281,7 @@ fn test_run_http_request_with_body() {
             path,
             content_type,
             &payload,
+            u64::MAX,
         )
         .unwrap();
         assert_eq!(result_plain, body_bytes);
@@ -326,6 +328,7 @@ fn test_run_http_request_no_body() {
             path,
             content_type,
             &payload,
+            u64::MAX,
         )
         .unwrap();
         let result_plain = run_http_request(
@@ -335,6 +338,7 @@ fn test_run_http_request_no_body() {
             path,
             content_type,
             &payload,
+            u64::MAX,
         )
         .unwrap();
 
@@ -342,3 +346,39 @@ fn test_run_http_request_no_body() {
         assert_eq!(result_plain.len(), 0);
     }
 }
+
+#[test]
+fn test_run_http_request_response_too_large() {
+    let reply =
+        "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-type: text/plain\r\n\r\nhello world";
+    let max_response_bytes = reply.len() as u64;
+
+    let mut msock = MockHTTPSocket::new(reply.to_string());
+    let result = run_http_request(
+        &mut msock,
+        "127.0.0.1:20443",
+        "GET",
+        "/test-response-at-limit",
+        None,
+        &[],
+        max_response_bytes,
+    )
+    .unwrap();
+    assert_eq!(result, "hello world".as_bytes());
+
+    let mut msock = MockHTTPSocket::new(reply.to_string());
+    let result = run_http_request(
+        &mut msock,
+        "127.0.0.1:20443",
+        "GET",
+        "/test-response-over-limit",
+        None,
+        &[],
+        max_response_bytes - 1,
+    );
+    assert!(
+        matches!(result, Err(RPCError::ResponseTooLarge(limit)) if limit == max_response_bytes - 1)
+    );
+    // Nothing past the limit was read from the socket
+    assert_eq!(msock.ptr as u64, max_response_bytes);
+}
@@ -0,0 +1,196 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use lazy_static::lazy_static;
+use rustls::client::{ServerCertVerified, ServerCertVerifier};
+use rustls::{
+    Certificate, ClientConfig, ClientConnection, OwnedTrustAnchor, RootCertStore, ServerName,
+    StreamOwned,
+};
+
+use crate::error::RPCError;
+
+lazy_static! {
+    /// TLS client config that verifies the node's certificate against the webpki roots
+    static ref VERIFIED_TLS_CONFIG: Arc<ClientConfig> = Arc::new(verified_tls_config());
+    /// TLS client config that accepts any certificate
+    static ref UNVERIFIED_TLS_CONFIG: Arc<ClientConfig> = {
+        let mut config = verified_tls_config();
+        config
+            .dangerous()
+            .set_certificate_verifier(Arc::new(NoCertificateVerification));
+        Arc::new(config)
+    };
+}
+
+/// How connections to the stacks node are made
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NodeTransport {
+    /// Plain HTTP over TCP
+    #[default]
+    Plain,
+    /// HTTP over TLS
+    Tls {
+        /// Accept any certificate presented by the node.
+        /// Only use this for test setups with self-signed certificates.
+        skip_verify: bool,
+    },
+}
+
+impl NodeTransport {
+    /// The URL scheme for this transport
+    pub fn scheme(&self) -> &'static str {
+        match self {
+            Self::Plain => "http",
+            Self::Tls { .. } => "https",
+        }
+    }
+
+    /// Open a connection to `host` (a `host:port` string).
+    /// For TLS, the handshake is completed before returning so that certificate problems are
+    /// reported as `RPCError::Tls` rather than surfacing later as I/O errors.
+    pub fn connect(&self, host: &str) -> Result<NodeStream, RPCError> {
+        let mut sock = TcpStream::connect(host)?;
+        let Self::Tls { skip_verify } = self else {
+            return Ok(NodeStream::Plain(sock));
+        };
+        let config = if *skip_verify {
+            UNVERIFIED_TLS_CONFIG.clone()
+        } else {
+            VERIFIED_TLS_CONFIG.clone()
+        };
+        let mut conn = ClientConnection::new(config, server_name(host)?)
+            .map_err(|e| RPCError::Tls(e.to_string()))?;
+        while conn.is_handshaking() {
+            conn.complete_io(&mut sock).map_err(|e| {
+                if is_tls_error(&e) {
+                    RPCError::Tls(e.to_string())
+                } else {
+                    RPCError::IO(e)
+                }
+            })?;
+        }
+        Ok(NodeStream::Tls(Box::new(StreamOwned::new(conn, sock))))
+    }
+}
+
+/// A connection to the stacks node
+#[derive(Debug)]
+pub enum NodeStream {
+    /// Plain TCP connection
+    Plain(TcpStream),
+    /// TLS connection
+    Tls(Box<StreamOwned<ClientConnection, TcpStream>>),
+}
+
+impl Read for NodeStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(sock) => sock.read(buf),
+            Self::Tls(stream) => match stream.read(buf) {
+                // Some TLS terminators close the connection without sending close_notify.
+                // Requests are sent with `Connection: close`, so treat this as the end of the response.
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(0),
+                res => res,
+            },
+        }
+    }
+}
+
+impl Write for NodeStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(sock) => sock.write(buf),
+            Self::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Plain(sock) => sock.flush(),
+            Self::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+/// Whether the error, or any error in its source chain, was raised by the TLS layer
+pub fn is_tls_error(err: &(dyn std::error::Error + 'static)) -> bool {
+    let mut next = Some(err);
+    while let Some(err) = next {
+        if err.is::<rustls::Error>() {
+            return true;
+        }
+        if let Some(io_err) = err.downcast_ref::<io::Error>() {
+            if io_err
+                .get_ref()
+                .map_or(false, |inner| inner.is::<rustls::Error>())
+            {
+                return true;
+            }
+        }
+        next = err.source();
+    }
+    false
+}
+
+/// Extract the TLS server name from a `host:port` string
+fn server_name(host: &str) -> Result<ServerName, RPCError> {
+    let hostname = host
+        .rsplit_once(':')
+        .map_or(host, |(hostname, _port)| hostname)
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    ServerName::try_from(hostname)
+        .map_err(|_| RPCError::Tls(format!("Invalid TLS server name: {hostname}")))
+}
+
+/// Build a TLS client config that verifies certificates against the webpki roots
+fn verified_tls_config() -> ClientConfig {
+    let mut root_store = RootCertStore::empty();
+    root_store.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(
+            ta.subject,
+            ta.spki,
+            ta.name_constraints,
+        )
+    }));
+    ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(root_store)
+        .with_no_client_auth()
+}
+
+/// Certificate verifier used when `skip_verify` is set
+struct NoCertificateVerification;
+
+impl ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
@@ -48,6 +48,7 @@ use stacks_common::codec::{
     StacksMessageCodec,
 };
 use stacks_common::consts::SIGNER_SLOTS_PER_USER;
+use stacks_common::types::chainstate::{BurnchainHeaderHash, ConsensusHash};
 use stacks_common::util::hash::Sha512Trunc256Sum;
 use tiny_http::{
     Method as HttpMethod, Request as HttpRequest, Response as HttpResponse, Server as HttpServer,
@@ -97,7 +98,17 @@ MessageSlotID {
     /// DKG Results
     DkgResults = 12,
     /// Persisted encrypted signer state containing DKG shares
-    EncryptedSignerState = 13
+    EncryptedSignerState = 13,
+    /// Notifications of updates to a signer's slots, stored in the `SignatureShareResponse` slot
+    StackerDbSlotUpdate = 14,
+    /// NACKs of broken signing rounds, stored in the `SignatureShareResponse` slot
+    Nack = 15,
+    /// Periodic summaries of the NACKs a signer has received, stored in the
+    /// `SignatureShareResponse` slot
+    NackReport = 16,
+    /// Conflicting aggregate keys voted for in the same reward cycle, stored in the
+    /// `SignatureShareResponse` slot
+    DkgConflict = 17
 });
 
 define_u8_enum!(
@@ -112,7 +123,15 @@ SignerMessageTypePrefix {
     /// The results of a successful DKG
     DkgResults = 3,
     /// The encrypted state of the signer to be persisted
-    EncryptedSignerState = 4
+    EncryptedSignerState = 4,
+    /// A notification that a signer has written to one of its slots
+    StackerDbSlotUpdate = 5,
+    /// A NACK of a broken signing round
+    Nack = 6,
+    /// A summary of the NACKs a signer has received
+    NackReport = 7,
+    /// A conflict between the aggregate keys of two DKG rounds
+    DkgConflict = 8
 });
 
 #[cfg_attr(test, mutants::skip)]
@@ -123,7 +142,24 @@ impl MessageSlotID {
         mainnet: bool,
         reward_cycle: u64,
     ) -> QualifiedContractIdentifier {
-        NakamotoSigners::make_signers_db_contract_id(reward_cycle, self.to_u32(), mainnet)
+        NakamotoSigners::make_signers_db_contract_id(
+            reward_cycle,
+            self.storage_slot().to_u32(),
+            mainnet,
+        )
+    }
+
+    /// Return the message slot whose contract stores messages of this type.
+    /// Notifications between signers are only ever observed through stacker-db events, so they
+    /// are written to the event-only `SignatureShareResponse` slot rather than to a contract of
+    /// their own past the `SIGNER_SLOTS_PER_USER` slots allocated to each signer.
+    pub fn storage_slot(&self) -> MessageSlotID {
+        match self {
+            Self::StackerDbSlotUpdate | Self::Nack | Self::NackReport | Self::DkgConflict => {
+                Self::SignatureShareResponse
+            }
+            _ => *self,
+        }
     }
 
     /// Return the u32 identifier for the message slot (used to index the contract that stores it)
@@ -156,6 +192,12 @@ impl From<&SignerMessage> for SignerMessageTypePrefix {
             SignerMessage::Transactions(_) => SignerMessageTypePrefix::Transactions,
             SignerMessage::DkgResults { .. } => SignerMessageTypePrefix::DkgResults,
             SignerMessage::EncryptedSignerState(_) => SignerMessageTypePrefix::EncryptedSignerState,
+            SignerMessage::StackerDbSlotUpdate { .. } => {
+                SignerMessageTypePrefix::StackerDbSlotUpdate
+            }
+            SignerMessage::Nack(_) => SignerMessageTypePrefix::Nack,
+            SignerMessage::NackReport { .. } => SignerMessageTypePrefix::NackReport,
+            SignerMessage::DkgConflict { .. } => SignerMessageTypePrefix::DkgConflict,
         }
     }
 }
@@ -227,7 +269,27 @@ RejectCodeTypePrefix {
     /// Nonce timeout
     NonceTimeout = 5,
     /// Aggregator error
-    AggregatorError = 6
+    AggregatorError = 6,
+    /// Validation timeout
+    ValidationTimeout = 7,
+    /// Excessive fee rate
+    ExcessiveFeeRate = 8,
+    /// No vote
+    NoVote = 9,
+    /// Conflicting proposal
+    ConflictingProposal = 10,
+    /// Signing timeout
+    SigningTimeout = 11,
+    /// Validation attempts exceeded
+    ValidationAttemptsExceeded = 12,
+    /// Unknown parent
+    UnknownParent = 13,
+    /// Filtered proposal
+    FilteredProposal = 14,
+    /// Invalid coinbase
+    InvalidCoinbase = 15,
+    /// Unknown miner
+    UnknownMiner = 16
 });
 
 impl TryFrom<u8> for RejectCodeTypePrefix {
@@ -249,6 +311,18 @@ impl From<&RejectCode> for RejectCodeTypePrefix {
             RejectCode::ConnectivityIssues => RejectCodeTypePrefix::ConnectivityIssues,
             RejectCode::NonceTimeout(_) => RejectCodeTypePrefix::NonceTimeout,
             RejectCode::AggregatorError(_) => RejectCodeTypePrefix::AggregatorError,
+            RejectCode::ValidationTimeout => RejectCodeTypePrefix::ValidationTimeout,
+            RejectCode::ExcessiveFeeRate(_) => RejectCodeTypePrefix::ExcessiveFeeRate,
+            RejectCode::NoVote => RejectCodeTypePrefix::NoVote,
+            RejectCode::ConflictingProposal => RejectCodeTypePrefix::ConflictingProposal,
+            RejectCode::SigningTimeout => RejectCodeTypePrefix::SigningTimeout,
+            RejectCode::ValidationAttemptsExceeded => {
+                RejectCodeTypePrefix::ValidationAttemptsExceeded
+            }
+            RejectCode::UnknownParent => RejectCodeTypePrefix::UnknownParent,
+            RejectCode::FilteredProposal => RejectCodeTypePrefix::FilteredProposal,
+            RejectCode::InvalidCoinbase => RejectCodeTypePrefix::InvalidCoinbase,
+            RejectCode::UnknownMiner => RejectCodeTypePrefix::UnknownMiner,
         }
     }
 }
@@ -271,6 +345,41 @@ pub enum SignerMessage {
     },
     /// The encrypted state of the signer to be persisted
     EncryptedSignerState(Vec<u8>),
+    /// A notification that a signer has written a new chunk to its slot
+    StackerDbSlotUpdate {
+        /// The id of the signer that wrote the chunk
+        signer_id: u32,
+        /// The version of the chunk that was written
+        slot_version: u32,
+    },
+    /// A notification that a signing round is broken, for other signers to observe
+    Nack(NackMessage),
+    /// A periodic summary of the NACKs a signer has received, so that other signers can gauge
+    /// how many signers disagree with the chain view without collecting every NACK themselves
+    NackReport {
+        /// The id of the signer sending the report
+        sender_id: u32,
+        /// The burn chain view of the sender when it sent the report
+        coordinator_metadata: CoordinatorMetadata,
+        /// The number of distinct signers the sender received a NACK from recently
+        nacked_signer_count: usize,
+    },
+    /// A notification that two DKG rounds of the same reward cycle produced different aggregate
+    /// keys that both received votes, so that other signers abandon both and run a fresh round
+    DkgConflict {
+        /// The id of the signer that detected the conflict
+        sender_id: u32,
+        /// The reward cycle of the conflicting DKG rounds
+        reward_cycle: u64,
+        /// The voting round of the earlier aggregate key
+        previous_round: u64,
+        /// The earlier aggregate key
+        previous_key: Point,
+        /// The voting round of the later aggregate key
+        conflicting_round: u64,
+        /// The later aggregate key
+        conflicting_key: Point,
+    },
 }
 
 impl Debug for SignerMessage {
@@ -296,6 +405,41 @@ impl Debug for SignerMessage {
             Self::EncryptedSignerState(s) => {
                 f.debug_tuple("EncryptedSignerState").field(s).finish()
             }
+            Self::StackerDbSlotUpdate {
+                signer_id,
+                slot_version,
+            } => f
+                .debug_struct("StackerDbSlotUpdate")
+                .field("signer_id", signer_id)
+                .field("slot_version", slot_version)
+                .finish(),
+            Self::Nack(n) => Debug::fmt(n, f),
+            Self::NackReport {
+                sender_id,
+                coordinator_metadata,
+                nacked_signer_count,
+            } => f
+                .debug_struct("NackReport")
+                .field("sender_id", sender_id)
+                .field("coordinator_metadata", coordinator_metadata)
+                .field("nacked_signer_count", nacked_signer_count)
+                .finish(),
+            Self::DkgConflict {
+                sender_id,
+                reward_cycle,
+                previous_round,
+                previous_key,
+                conflicting_round,
+                conflicting_key,
+            } => f
+                .debug_struct("DkgConflict")
+                .field("sender_id", sender_id)
+                .field("reward_cycle", reward_cycle)
+                .field("previous_round", previous_round)
+                .field("previous_key", &previous_key.to_string())
+                .field("conflicting_round", conflicting_round)
+                .field("conflicting_key", &conflicting_key.to_string())
+                .finish(),
         }
     }
 }
@@ -321,6 +465,10 @@ impl SignerMessage {
             Self::Transactions(_) => MessageSlotID::Transactions,
             Self::DkgResults { .. } => MessageSlotID::DkgResults,
             Self::EncryptedSignerState(_) => MessageSlotID::EncryptedSignerState,
+            Self::StackerDbSlotUpdate { .. } => MessageSlotID::StackerDbSlotUpdate,
+            Self::Nack(_) => MessageSlotID::Nack,
+            Self::NackReport { .. } => MessageSlotID::NackReport,
+            Self::DkgConflict { .. } => MessageSlotID::DkgConflict,
         }
     }
 }
@@ -391,6 +539,42 @@ impl StacksMessageCodec for SignerMessage {
             SignerMessage::EncryptedSignerState(encrypted_state) => {
                 write_next(fd, encrypted_state)?;
             }
+            SignerMessage::StackerDbSlotUpdate {
+                signer_id,
+                slot_version,
+            } => {
+                write_next(fd, signer_id)?;
+                write_next(fd, slot_version)?;
+            }
+            SignerMessage::Nack(nack) => {
+                write_next(fd, nack)?;
+            }
+            SignerMessage::NackReport {
+                sender_id,
+                coordinator_metadata,
+                nacked_signer_count,
+            } => {
+                write_next(fd, sender_id)?;
+                write_next(fd, coordinator_metadata)?;
+                let nacked_signer_count = u64::try_from(*nacked_signer_count)
+                    .expect("FATAL: nacked signer count exceeds u64::MAX");
+                write_next(fd, &nacked_signer_count)?;
+            }
+            SignerMessage::DkgConflict {
+                sender_id,
+                reward_cycle,
+                previous_round,
+                previous_key,
+                conflicting_round,
+                conflicting_key,
+            } => {
+                write_next(fd, sender_id)?;
+                write_next(fd, reward_cycle)?;
+                write_next(fd, previous_round)?;
+                previous_key.inner_consensus_serialize(fd)?;
+                write_next(fd, conflicting_round)?;
+                conflicting_key.inner_consensus_serialize(fd)?;
+            }
         };
         Ok(())
     }
@@ -439,6 +623,49 @@ impl StacksMessageCodec for SignerMessage {
                 let encrypted_state = read_next::<_, _>(&mut bound_reader)?;
                 SignerMessage::EncryptedSignerState(encrypted_state)
             }
+            SignerMessageTypePrefix::StackerDbSlotUpdate => {
+                let signer_id = read_next::<u32, _>(fd)?;
+                let slot_version = read_next::<u32, _>(fd)?;
+                SignerMessage::StackerDbSlotUpdate {
+                    signer_id,
+                    slot_version,
+                }
+            }
+            SignerMessageTypePrefix::Nack => {
+                let nack = read_next::<NackMessage, _>(fd)?;
+                SignerMessage::Nack(nack)
+            }
+            SignerMessageTypePrefix::NackReport => {
+                let sender_id = read_next::<u32, _>(fd)?;
+                let coordinator_metadata = read_next::<CoordinatorMetadata, _>(fd)?;
+                let nacked_signer_count =
+                    usize::try_from(read_next::<u64, _>(fd)?).map_err(|_| {
+                        CodecError::DeserializeError(
+                            "NACKed signer count exceeds usize::MAX".into(),
+                        )
+                    })?;
+                SignerMessage::NackReport {
+                    sender_id,
+                    coordinator_metadata,
+                    nacked_signer_count,
+                }
+            }
+            SignerMessageTypePrefix::DkgConflict => {
+                let sender_id = read_next::<u32, _>(fd)?;
+                let reward_cycle = read_next::<u64, _>(fd)?;
+                let previous_round = read_next::<u64, _>(fd)?;
+                let previous_key = Point::inner_consensus_deserialize(fd)?;
+                let conflicting_round = read_next::<u64, _>(fd)?;
+                let conflicting_key = Point::inner_consensus_deserialize(fd)?;
+                SignerMessage::DkgConflict {
+                    sender_id,
+                    reward_cycle,
+                    previous_round,
+                    previous_key,
+                    conflicting_round,
+                    conflicting_key,
+                }
+            }
         };
         Ok(message)
     }
@@ -1284,6 +1511,115 @@ impl From<BlockValidateReject> for BlockRejection {
     }
 }
 
+define_u8_enum!(
+/// The reason a signer NACKed a signing round
+NackReason {
+    /// The coordinator requested signature shares for a message the signer never saw a
+    /// NonceRequest for
+    UnexpectedCoordinator = 0,
+    /// The coordinator requested signature shares for a message that is not a block vote
+    MalformedPacket = 1
+});
+
+impl TryFrom<u8> for NackReason {
+    type Error = CodecError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_u8(value)
+            .ok_or_else(|| CodecError::DeserializeError(format!("Unknown NACK reason: {value}")))
+    }
+}
+
+impl StacksMessageCodec for NackReason {
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), CodecError> {
+        write_next(fd, &self.to_u8())
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<Self, CodecError> {
+        Self::try_from(read_next::<u8, _>(fd)?)
+    }
+}
+
+/// A signer's notification that a signing round is broken, so that other signers do not have to
+/// wait for it to time out
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NackMessage {
+    /// The id of the signer sending the NACK
+    pub signer_id: u32,
+    /// The id of the signing round
+    pub sign_id: u64,
+    /// The iteration of the signing round
+    pub sign_iter_id: u64,
+    /// The id of the coordinator of the signing round
+    pub coordinator_id: u32,
+    /// The reason the round is broken
+    pub reason: NackReason,
+}
+
+impl StacksMessageCodec for NackMessage {
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), CodecError> {
+        write_next(fd, &self.signer_id)?;
+        write_next(fd, &self.sign_id)?;
+        write_next(fd, &self.sign_iter_id)?;
+        write_next(fd, &self.coordinator_id)?;
+        write_next(fd, &self.reason)?;
+        Ok(())
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<Self, CodecError> {
+        let signer_id = read_next::<u32, _>(fd)?;
+        let sign_id = read_next::<u64, _>(fd)?;
+        let sign_iter_id = read_next::<u64, _>(fd)?;
+        let coordinator_id = read_next::<u32, _>(fd)?;
+        let reason = read_next::<NackReason, _>(fd)?;
+        Ok(Self {
+            signer_id,
+            sign_id,
+            sign_iter_id,
+            coordinator_id,
+            reason,
+        })
+    }
+}
+
+/// The burn chain state a coordinator is selected against
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CoordinatorMetadata {
+    /// The canonical burn block height
+    pub burn_block_height: u64,
+    /// The canonical burn header hash, if known
+    pub burn_header_hash: Option<BurnchainHeaderHash>,
+    /// The pox consensus hash used to order the coordinators
+    pub pox_consensus_hash: ConsensusHash,
+}
+
+impl StacksMessageCodec for CoordinatorMetadata {
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), CodecError> {
+        write_next(fd, &self.burn_block_height)?;
+        write_next(fd, &(self.burn_header_hash.is_some() as u8))?;
+        if let Some(burn_header_hash) = &self.burn_header_hash {
+            write_next(fd, burn_header_hash)?;
+        }
+        write_next(fd, &self.pox_consensus_hash)?;
+        Ok(())
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<Self, CodecError> {
+        let burn_block_height = read_next::<u64, _>(fd)?;
+        let has_burn_header_hash = read_next::<u8, _>(fd)? != 0;
+        let burn_header_hash = if has_burn_header_hash {
+            Some(read_next::<BurnchainHeaderHash, _>(fd)?)
+        } else {
+            None
+        };
+        let pox_consensus_hash = read_next::<ConsensusHash, _>(fd)?;
+        Ok(Self {
+            burn_block_height,
+            burn_header_hash,
+            pox_consensus_hash,
+        })
+    }
+}
+
 /// This enum is used to supply a `reason_code` for block rejections
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum RejectCode {
@@ -1301,6 +1637,26 @@ pub enum RejectCode {
     MissingTransactions(Vec<StacksTransaction>),
     /// The block was rejected due to connectivity issues with the signer
     ConnectivityIssues,
+    /// The stacks node did not respond to the block validation request in time
+    ValidationTimeout,
+    /// The median transaction fee of the block (in microstacks) exceeds the acceptable maximum
+    ExcessiveFeeRate(u64),
+    /// The signer was asked to sign a block it never cast a vote for
+    NoVote,
+    /// The miner proposed a different block at the same height of the same tenure
+    ConflictingProposal,
+    /// The signing round did not accumulate enough signatures in time
+    SigningTimeout,
+    /// The block was submitted to the stacks node for validation too many times
+    ValidationAttemptsExceeded,
+    /// The parent of the block is not known to the stacks node
+    UnknownParent,
+    /// The block was declined by the signer's block proposal filter
+    FilteredProposal,
+    /// The block starts a tenure with a malformed coinbase, or was not mined by the current miner
+    InvalidCoinbase,
+    /// The block was not mined by the miner of the tenure's other blocks
+    UnknownMiner,
 }
 
 impl From<&SignError> for RejectCode {
@@ -1330,6 +1686,16 @@ impl StacksMessageCodec for RejectCode {
             }
             RejectCode::AggregatorError(reason) => write_next(fd, &reason.as_bytes().to_vec())?,
             RejectCode::ConnectivityIssues => write_next(fd, &4u8)?,
+            RejectCode::ValidationTimeout => {}
+            RejectCode::ExcessiveFeeRate(median_fee) => write_next(fd, median_fee)?,
+            RejectCode::NoVote
+            | RejectCode::ConflictingProposal
+            | RejectCode::SigningTimeout
+            | RejectCode::ValidationAttemptsExceeded
+            | RejectCode::UnknownParent
+            | RejectCode::FilteredProposal
+            | RejectCode::InvalidCoinbase
+            | RejectCode::UnknownMiner => {}
         };
         Ok(())
     }
@@ -1359,6 +1725,20 @@ impl StacksMessageCodec for RejectCode {
                 RejectCode::NonceTimeout(read_next::<Vec<u32>, _>(fd)?)
             }
             RejectCodeTypePrefix::ConnectivityIssues => RejectCode::ConnectivityIssues,
+            RejectCodeTypePrefix::ValidationTimeout => RejectCode::ValidationTimeout,
+            RejectCodeTypePrefix::ExcessiveFeeRate => {
+                RejectCode::ExcessiveFeeRate(read_next::<u64, _>(fd)?)
+            }
+            RejectCodeTypePrefix::NoVote => RejectCode::NoVote,
+            RejectCodeTypePrefix::ConflictingProposal => RejectCode::ConflictingProposal,
+            RejectCodeTypePrefix::SigningTimeout => RejectCode::SigningTimeout,
+            RejectCodeTypePrefix::ValidationAttemptsExceeded => {
+                RejectCode::ValidationAttemptsExceeded
+            }
+            RejectCodeTypePrefix::UnknownParent => RejectCode::UnknownParent,
+            RejectCodeTypePrefix::FilteredProposal => RejectCode::FilteredProposal,
+            RejectCodeTypePrefix::InvalidCoinbase => RejectCode::InvalidCoinbase,
+            RejectCodeTypePrefix::UnknownMiner => RejectCode::UnknownMiner,
             RejectCodeTypePrefix::AggregatorError => {
                 let reason_bytes = read_next::<Vec<u8>, _>(fd)?;
                 let reason = String::from_utf8(reason_bytes).map_err(|e| {
@@ -1400,6 +1780,46 @@ impl std::fmt::Display for RejectCode {
                 f,
                 "The block was rejected due to connectivity issues with the signer."
             ),
+            RejectCode::ValidationTimeout => write!(
+                f,
+                "The block was rejected as it was not validated by the stacks node in time."
+            ),
+            RejectCode::ExcessiveFeeRate(median_fee) => write!(
+                f,
+                "The block was rejected as its median transaction fee ({median_fee} uSTX) is excessive."
+            ),
+            RejectCode::NoVote => write!(
+                f,
+                "The block was rejected as the signer never voted on it."
+            ),
+            RejectCode::ConflictingProposal => write!(
+                f,
+                "The block was rejected as the miner proposed a different block at the same height of its tenure."
+            ),
+            RejectCode::SigningTimeout => write!(
+                f,
+                "The block was rejected as its signing round did not gather enough signatures in time."
+            ),
+            RejectCode::ValidationAttemptsExceeded => write!(
+                f,
+                "The block was rejected as the stacks node could not be asked to validate it within the allowed attempts."
+            ),
+            RejectCode::UnknownParent => write!(
+                f,
+                "The block was rejected as its parent block is unknown."
+            ),
+            RejectCode::FilteredProposal => write!(
+                f,
+                "The block was rejected by the signer's block proposal filter."
+            ),
+            RejectCode::InvalidCoinbase => write!(
+                f,
+                "The block was rejected as its coinbase is malformed or it was not mined by the current miner."
+            ),
+            RejectCode::UnknownMiner => write!(
+                f,
+                "The block was rejected as it was not mined by the miner of its tenure."
+            ),
             RejectCode::AggregatorError(reason) => write!(
                 f,
                 "An internal error occurred in the signer when aggregating the signaure: {:?}",
@@ -1461,6 +1881,20 @@ mod test {
         );
     }
 
+    #[test]
+    fn sent_message_slots_should_be_stored_in_allocated_slots() {
+        for msg_id in MessageSlotID::ALL {
+            // The encrypted signer state is never written to stacker-db
+            if *msg_id == MessageSlotID::EncryptedSignerState {
+                continue;
+            }
+            assert!(
+                msg_id.storage_slot().to_u32() < SIGNER_SLOTS_PER_USER,
+                "{msg_id} is stored past the {SIGNER_SLOTS_PER_USER} slots allocated to each signer"
+            );
+        }
+    }
+
     #[test]
     fn serde_reject_code() {
         let code = RejectCode::ValidationFailed(ValidateRejectCode::InvalidBlock);
@@ -1493,6 +1927,66 @@ mod test {
             .expect("Failed to deserialize RejectCode");
         assert_eq!(code, deserialized_code);
 
+        let code = RejectCode::ValidationTimeout;
+        let serialized_code = code.serialize_to_vec();
+        let deserialized_code = read_next::<RejectCode, _>(&mut &serialized_code[..])
+            .expect("Failed to deserialize RejectCode");
+        assert_eq!(code, deserialized_code);
+
+        let code = RejectCode::ExcessiveFeeRate(1_000_000);
+        let serialized_code = code.serialize_to_vec();
+        let deserialized_code = read_next::<RejectCode, _>(&mut &serialized_code[..])
+            .expect("Failed to deserialize RejectCode");
+        assert_eq!(code, deserialized_code);
+
+        let code = RejectCode::NoVote;
+        let serialized_code = code.serialize_to_vec();
+        let deserialized_code = read_next::<RejectCode, _>(&mut &serialized_code[..])
+            .expect("Failed to deserialize RejectCode");
+        assert_eq!(code, deserialized_code);
+
+        let code = RejectCode::ConflictingProposal;
+        let serialized_code = code.serialize_to_vec();
+        let deserialized_code = read_next::<RejectCode, _>(&mut &serialized_code[..])
+            .expect("Failed to deserialize RejectCode");
+        assert_eq!(code, deserialized_code);
+
+        let code = RejectCode::SigningTimeout;
+        let serialized_code = code.serialize_to_vec();
+        let deserialized_code = read_next::<RejectCode, _>(&mut &serialized_code[..])
+            .expect("Failed to deserialize RejectCode");
+        assert_eq!(code, deserialized_code);
+
+        let code = RejectCode::ValidationAttemptsExceeded;
+        let serialized_code = code.serialize_to_vec();
+        let deserialized_code = read_next::<RejectCode, _>(&mut &serialized_code[..])
+            .expect("Failed to deserialize RejectCode");
+        assert_eq!(code, deserialized_code);
+
+        let code = RejectCode::UnknownParent;
+        let serialized_code = code.serialize_to_vec();
+        let deserialized_code = read_next::<RejectCode, _>(&mut &serialized_code[..])
+            .expect("Failed to deserialize RejectCode");
+        assert_eq!(code, deserialized_code);
+
+        let code = RejectCode::FilteredProposal;
+        let serialized_code = code.serialize_to_vec();
+        let deserialized_code = read_next::<RejectCode, _>(&mut &serialized_code[..])
+            .expect("Failed to deserialize RejectCode");
+        assert_eq!(code, deserialized_code);
+
+        let code = RejectCode::InvalidCoinbase;
+        let serialized_code = code.serialize_to_vec();
+        let deserialized_code = read_next::<RejectCode, _>(&mut &serialized_code[..])
+            .expect("Failed to deserialize RejectCode");
+        assert_eq!(code, deserialized_code);
+
+        let code = RejectCode::UnknownMiner;
+        let serialized_code = code.serialize_to_vec();
+        let deserialized_code = read_next::<RejectCode, _>(&mut &serialized_code[..])
+            .expect("Failed to deserialize RejectCode");
+        assert_eq!(code, deserialized_code);
+
         let sk = StacksPrivateKey::new();
         let tx = StacksTransaction {
             version: TransactionVersion::Testnet,
@@ -1850,5 +2344,61 @@ mod test {
             read_next::<SignerMessage, _>(&mut &serialized_signer_message[..])
                 .expect("Failed to deserialize SignerMessage");
         assert_eq!(signer_message, deserialized_signer_message);
+
+        let signer_message = SignerMessage::StackerDbSlotUpdate {
+            signer_id: 3,
+            slot_version: 42,
+        };
+        let serialized_signer_message = signer_message.serialize_to_vec();
+        let deserialized_signer_message =
+            read_next::<SignerMessage, _>(&mut &serialized_signer_message[..])
+                .expect("Failed to deserialize SignerMessage");
+        assert_eq!(signer_message, deserialized_signer_message);
+
+        for reason in NackReason::ALL {
+            let signer_message = SignerMessage::Nack(NackMessage {
+                signer_id: 2,
+                sign_id: 7,
+                sign_iter_id: 1,
+                coordinator_id: 4,
+                reason: *reason,
+            });
+            let serialized_signer_message = signer_message.serialize_to_vec();
+            let deserialized_signer_message =
+                read_next::<SignerMessage, _>(&mut &serialized_signer_message[..])
+                    .expect("Failed to deserialize SignerMessage");
+            assert_eq!(signer_message, deserialized_signer_message);
+        }
+
+        for burn_header_hash in [None, Some(BurnchainHeaderHash([3; 32]))] {
+            let signer_message = SignerMessage::NackReport {
+                sender_id: 1,
+                coordinator_metadata: CoordinatorMetadata {
+                    burn_block_height: 100,
+                    burn_header_hash,
+                    pox_consensus_hash: ConsensusHash([4; 20]),
+                },
+                nacked_signer_count: 3,
+            };
+            let serialized_signer_message = signer_message.serialize_to_vec();
+            let deserialized_signer_message =
+                read_next::<SignerMessage, _>(&mut &serialized_signer_message[..])
+                    .expect("Failed to deserialize SignerMessage");
+            assert_eq!(signer_message, deserialized_signer_message);
+        }
+
+        let signer_message = SignerMessage::DkgConflict {
+            sender_id: 3,
+            reward_cycle: 8,
+            previous_round: 1,
+            previous_key: Point::from(Scalar::random(&mut OsRng)),
+            conflicting_round: 2,
+            conflicting_key: Point::from(Scalar::random(&mut OsRng)),
+        };
+        let serialized_signer_message = signer_message.serialize_to_vec();
+        let deserialized_signer_message =
+            read_next::<SignerMessage, _>(&mut &serialized_signer_message[..])
+                .expect("Failed to deserialize SignerMessage");
+        assert_eq!(signer_message, deserialized_signer_message);
     }
 }
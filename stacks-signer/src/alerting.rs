@@ -0,0 +1,319 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_derive::{Deserialize, Serialize};
+use slog::{slog_debug, slog_error, slog_warn};
+use stacks_common::util::get_epoch_time_secs;
+use stacks_common::{debug, error, warn};
+
+/// The most alerts waiting to be delivered. Alerts raised while the queue is full are dropped.
+const ALERT_QUEUE_CAPACITY: usize = 64;
+/// The minimum time between two alerts of the same event for the same signer
+const ALERT_RATE_LIMIT: Duration = Duration::from_secs(60);
+/// How many times the delivery of an alert is attempted
+const ALERT_DELIVERY_ATTEMPTS: u32 = 3;
+/// The time between two attempts at delivering an alert
+const ALERT_RETRY_DELAY: Duration = Duration::from_secs(2);
+/// How long to wait for the webhook to answer
+const ALERT_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The events that threaten the liveness of the signer
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertEvent {
+    /// A DKG round failed
+    DkgFailure,
+    /// A signing round did not complete by its deadline
+    SignRoundTimeout,
+    /// The signer started backing off, as enough of the signer set is NACKing
+    NackBackOff,
+    /// The stacks node could not be reached for longer than the health check timeout
+    NodeUnreachable,
+    /// A miner proposed conflicting blocks
+    ConflictingProposal,
+    /// Two DKG rounds produced different aggregate keys that both received votes
+    DkgConflict,
+}
+
+impl AlertEvent {
+    /// How urgently operators should act on the event
+    pub fn severity(&self) -> AlertSeverity {
+        match self {
+            Self::DkgFailure
+            | Self::NodeUnreachable
+            | Self::ConflictingProposal
+            | Self::DkgConflict => AlertSeverity::Critical,
+            Self::SignRoundTimeout | Self::NackBackOff => AlertSeverity::Warning,
+        }
+    }
+}
+
+/// How urgently operators should act on an alert
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    /// The signer may recover on its own
+    Warning,
+    /// The signer needs attention
+    Critical,
+}
+
+/// The JSON payload posted to the webhook
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Alert {
+    /// The event alerted of
+    pub event: AlertEvent,
+    /// How urgently operators should act on the event
+    pub severity: AlertSeverity,
+    /// The signer that raised the alert, or None if it concerns the whole signer process
+    pub signer_id: Option<u32>,
+    /// The reward cycle of the signer that raised the alert
+    pub reward_cycle: Option<u64>,
+    /// A human readable description of the event
+    pub details: String,
+    /// The time, in seconds since the epoch, at which the alert was raised
+    pub timestamp: u64,
+}
+
+/// Posts alerts to a webhook from a background thread. Raising an alert never blocks: alerts
+/// are dropped when the queue is full or the same alert was raised recently.
+/// Clones share the queue and the rate limit.
+#[derive(Debug, Clone)]
+pub struct AlertNotifier {
+    /// Queues alerts for the delivery thread
+    sender: SyncSender<Alert>,
+    /// When each event was last alerted of, per signer
+    last_alerts: Arc<Mutex<HashMap<(AlertEvent, Option<u32>), Instant>>>,
+    /// The minimum time between two alerts of the same event for the same signer
+    rate_limit: Duration,
+}
+
+impl AlertNotifier {
+    /// Spawn the thread delivering alerts to the webhook at the given URL
+    pub fn spawn(webhook_url: &str) -> Self {
+        Self::spawn_with(webhook_url, ALERT_RATE_LIMIT, ALERT_RETRY_DELAY)
+    }
+
+    fn spawn_with(webhook_url: &str, rate_limit: Duration, retry_delay: Duration) -> Self {
+        let (sender, receiver) = sync_channel(ALERT_QUEUE_CAPACITY);
+        let webhook_url = webhook_url.to_string();
+        thread::Builder::new()
+            .name("signer-alerts".into())
+            .spawn(move || deliver_alerts(&webhook_url, receiver, retry_delay))
+            .expect("FATAL: failed to spawn the alert delivery thread");
+        Self {
+            sender,
+            last_alerts: Arc::new(Mutex::new(HashMap::new())),
+            rate_limit,
+        }
+    }
+
+    /// Queue an alert for delivery, unless the same event was alerted of for the same signer
+    /// within the rate limit
+    pub fn notify(
+        &self,
+        event: AlertEvent,
+        signer_id: Option<u32>,
+        reward_cycle: Option<u64>,
+        details: String,
+    ) {
+        let now = Instant::now();
+        {
+            let Ok(mut last_alerts) = self.last_alerts.lock() else {
+                warn!("Alert rate limit lock poisoned. Dropping alert."; "event" => ?event);
+                return;
+            };
+            if last_alerts
+                .get(&(event, signer_id))
+                .is_some_and(|last_alert| {
+                    now.saturating_duration_since(*last_alert) < self.rate_limit
+                })
+            {
+                debug!("Rate limiting alert"; "event" => ?event, "signer_id" => ?signer_id);
+                return;
+            }
+            last_alerts.insert((event, signer_id), now);
+        }
+        let alert = Alert {
+            event,
+            severity: event.severity(),
+            signer_id,
+            reward_cycle,
+            details,
+            timestamp: get_epoch_time_secs(),
+        };
+        match self.sender.try_send(alert) {
+            Ok(()) => {}
+            Err(TrySendError::Full(alert)) => {
+                warn!("Alert queue is full. Dropping alert."; "event" => ?alert.event);
+            }
+            Err(TrySendError::Disconnected(alert)) => {
+                error!("Alert delivery thread is gone. Dropping alert."; "event" => ?alert.event);
+            }
+        }
+    }
+}
+
+/// Post each queued alert to the webhook, retrying failed deliveries, until every notifier is
+/// dropped
+fn deliver_alerts(webhook_url: &str, receiver: Receiver<Alert>, retry_delay: Duration) {
+    let client = match reqwest::blocking::Client::builder()
+        .timeout(ALERT_REQUEST_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Failed to build the alert webhook client. No alerts will be sent: {e:?}");
+            return;
+        }
+    };
+    for alert in receiver {
+        for attempt in 1..=ALERT_DELIVERY_ATTEMPTS {
+            match client.post(webhook_url).json(&alert).send() {
+                Ok(response) if response.status().is_success() => {
+                    debug!("Delivered alert"; "event" => ?alert.event);
+                    break;
+                }
+                Ok(response) => warn!(
+                    "Alert webhook rejected alert";
+                    "event" => ?alert.event,
+                    "status" => %response.status(),
+                    "attempt" => attempt,
+                ),
+                Err(e) => warn!(
+                    "Failed to deliver alert";
+                    "event" => ?alert.event,
+                    "error" => ?e,
+                    "attempt" => attempt,
+                ),
+            }
+            if attempt < ALERT_DELIVERY_ATTEMPTS {
+                thread::sleep(retry_delay);
+            } else {
+                error!("Giving up on delivering alert"; "event" => ?alert.event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+
+    use super::*;
+
+    /// Accept a single webhook request on the listener, answer it with the given status line
+    /// and return the alert it posted
+    pub fn receive_alert(listener: &TcpListener, status: &str) -> Alert {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).unwrap();
+        respond(stream, status);
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    fn respond(mut stream: TcpStream, status: &str) {
+        stream
+            .write_all(
+                format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                    .as_bytes(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn alerts_should_be_posted_as_json() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/alerts", listener.local_addr().unwrap());
+        let notifier = AlertNotifier::spawn(&url);
+        notifier.notify(
+            AlertEvent::NodeUnreachable,
+            None,
+            None,
+            "no answer for 60s".into(),
+        );
+        let alert = receive_alert(&listener, "200 OK");
+        assert_eq!(alert.event, AlertEvent::NodeUnreachable);
+        assert_eq!(alert.severity, AlertSeverity::Critical);
+        assert_eq!(alert.signer_id, None);
+        assert_eq!(alert.details, "no answer for 60s");
+        assert!(alert.timestamp > 0);
+    }
+
+    #[test]
+    fn alerts_should_be_rate_limited_per_event_and_signer() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let notifier = AlertNotifier::spawn(&url);
+        notifier.notify(AlertEvent::DkgFailure, Some(0), Some(1), "first".into());
+        notifier.notify(AlertEvent::DkgFailure, Some(0), Some(1), "repeated".into());
+        notifier.notify(
+            AlertEvent::DkgFailure,
+            Some(1),
+            Some(1),
+            "other signer".into(),
+        );
+        notifier.notify(
+            AlertEvent::NackBackOff,
+            Some(0),
+            Some(1),
+            "other event".into(),
+        );
+        let details: Vec<_> = (0..3)
+            .map(|_| receive_alert(&listener, "200 OK").details)
+            .collect();
+        assert_eq!(details, vec!["first", "other signer", "other event"]);
+    }
+
+    #[test]
+    fn failed_deliveries_should_be_retried() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let notifier = AlertNotifier::spawn_with(&url, ALERT_RATE_LIMIT, Duration::from_millis(10));
+        notifier.notify(
+            AlertEvent::SignRoundTimeout,
+            Some(2),
+            Some(3),
+            "timeout".into(),
+        );
+        let failed = receive_alert(&listener, "500 Internal Server Error");
+        let retried = receive_alert(&listener, "200 OK");
+        assert_eq!(failed, retried);
+        assert_eq!(retried.severity, AlertSeverity::Warning);
+    }
+}
@@ -26,6 +26,7 @@ use stacks_common::address::{
     C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
 };
 use stacks_common::types::chainstate::StacksPrivateKey;
+use stacks_common::util::hash::Sha512Trunc256Sum;
 
 extern crate alloc;
 
@@ -55,6 +56,15 @@ pub enum Command {
     GenerateStackingSignature(GenerateStackingSignatureArgs),
     /// Check a configuration file and output config information
     CheckConfig(RunSignerArgs),
+    /// Inspect the signer database
+    Db(DbArgs),
+    /// Run DKG and a signing round with a simulated signer set in memory
+    Simulate(SimulateArgs),
+    /// Print a session recorded with `record_events_path`
+    Dump(DumpArgs),
+    /// Check that the signer can run: that its node is reachable, that it is registered in the
+    /// upcoming reward set and that it can use its stacker-db slot and database
+    Check(CheckArgs),
 }
 
 /// Basic arguments for all cyrptographic and stacker-db functionality
@@ -123,6 +133,107 @@ pub struct RunSignerArgs {
     pub config: PathBuf,
 }
 
+#[derive(Parser, Debug, Clone)]
+/// Arguments for the db command
+pub struct DbArgs {
+    /// Path to the signer database
+    #[arg(long, value_name = "FILE")]
+    pub db_path: PathBuf,
+    /// Output information in JSON format
+    #[arg(long, global = true, action=ArgAction::SetTrue, required=false)]
+    pub json: bool,
+    /// The query to run against the database
+    #[command(subcommand)]
+    pub command: DbCommand,
+}
+
+/// Subcommands for the db command. All but `prune` and `migrate-json-state` open the database
+/// read-only, so they are safe to run against the database of a running signer.
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum DbCommand {
+    /// List the blocks of a reward cycle
+    ListBlocks(RewardCycleArgs),
+    /// Show a block, given its signer signature hash
+    ShowBlock(ShowBlockArgs),
+    /// List the votes on the blocks of a reward cycle
+    Votes(RewardCycleArgs),
+    /// Summarize the blocks and DKG rounds of a reward cycle
+    Stats(RewardCycleArgs),
+    /// Remove the blocks and signing state of all but the latest reward cycles
+    Prune(PruneArgs),
+    /// Import the blocks of a legacy JSON signer state dump
+    MigrateJsonState(MigrateJsonStateArgs),
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Arguments for the simulate command
+pub struct SimulateArgs {
+    /// The number of signers
+    #[arg(long, default_value_t = 5)]
+    pub signers: u32,
+    /// The number of key IDs, split as evenly as possible across the signers
+    #[arg(long, default_value_t = 20)]
+    pub keys: u32,
+    /// The percentage of the key IDs needed to sign
+    #[arg(long, default_value_t = 70)]
+    pub threshold_percent: u32,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Arguments for the dump command
+pub struct DumpArgs {
+    /// The recorded session
+    #[arg(long, value_name = "FILE")]
+    pub events: PathBuf,
+    /// Path to the config file of the signer that recorded the session
+    #[arg(long, short, value_name = "FILE")]
+    pub config: PathBuf,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Arguments for the check command
+pub struct CheckArgs {
+    /// Path to config file
+    #[arg(long, short, value_name = "FILE")]
+    pub config: PathBuf,
+    /// Also write to the signer's stacker-db slot, overwriting its transactions with an empty
+    /// message. Do not use while the signer is running.
+    #[arg(long)]
+    pub write_test: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Arguments for the db subcommands of a reward cycle
+pub struct RewardCycleArgs {
+    /// The reward cycle to query
+    #[arg(long)]
+    pub reward_cycle: u64,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Arguments for the db show-block command
+pub struct ShowBlockArgs {
+    /// The signer signature hash of the block in hexadecimal format
+    #[arg(value_parser = parse_sighash)]
+    pub sighash: Sha512Trunc256Sum,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Arguments for the db prune command
+pub struct PruneArgs {
+    /// The number of latest reward cycles to keep
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub keep: u64,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Arguments for the db migrate-json-state command
+pub struct MigrateJsonStateArgs {
+    /// The JSON file holding an array of blocks
+    #[arg(long, value_name = "FILE")]
+    pub path: PathBuf,
+}
+
 #[derive(Clone, Debug)]
 /// Wrapper around `Pox4SignatureTopic` to implement `ValueEnum`
 pub struct StackingSignatureMethod(Pox4SignatureTopic);
@@ -228,6 +339,12 @@ fn parse_private_key(private_key: &str) -> Result<StacksPrivateKey, String> {
     StacksPrivateKey::from_hex(private_key).map_err(|e| format!("Invalid private key: {}", e))
 }
 
+/// Parse the hexadecimal signer signature hash of a block
+fn parse_sighash(sighash: &str) -> Result<Sha512Trunc256Sum, String> {
+    let sighash = sighash.strip_prefix("0x").unwrap_or(sighash);
+    Sha512Trunc256Sum::from_hex(sighash).map_err(|e| format!("Invalid signer signature hash: {e}"))
+}
+
 /// Parse the input data
 fn parse_data(data: &str) -> Result<Vec<u8>, String> {
     let encoded_data = if data == "-" {
@@ -14,6 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+/// The token bucket rate limiter for requests to the stacks node
+mod rate_limiter;
+/// Limits on the size of responses read from the stacks node
+mod response_limits;
+/// The mapping of signer message types to stacker-db slots
+mod slot_layout;
 /// The stacker db module for communicating with the stackerdb contract
 mod stackerdb;
 /// The stacks node client module for communicating with the stacks node
@@ -23,8 +29,13 @@ use std::time::Duration;
 
 use clarity::vm::errors::Error as ClarityError;
 use clarity::vm::types::serialization::SerializationError;
+use libsigner::v1::messages::MessageSlotID;
+use libsigner::{is_tls_error, NodeTransport, RPCError};
 use libstackerdb::Error as StackerDBError;
+pub use rate_limiter::*;
+pub use response_limits::*;
 use slog::slog_debug;
+pub use slot_layout::*;
 pub use stackerdb::*;
 pub use stacks_client::*;
 use stacks_common::codec::Error as CodecError;
@@ -34,6 +45,16 @@ use stacks_common::debug;
 const BACKOFF_INITIAL_INTERVAL: u64 = 128;
 /// Backoff timer max interval in milliseconds
 const BACKOFF_MAX_INTERVAL: u64 = 16384;
+/// Backoff timer multiplier applied to the interval after each failed attempt
+const BACKOFF_MULTIPLIER: f64 = 1.5;
+/// Backoff timer max elapsed time in milliseconds before giving up
+const BACKOFF_MAX_ELAPSED_TIME: u64 = 900_000;
+/// Backoff timer randomization (jitter) factor applied to each interval
+const BACKOFF_JITTER: f64 = 0.5;
+/// Stacker-db write backoff timer max interval in milliseconds
+const STACKERDB_WRITE_BACKOFF_MAX_INTERVAL: u64 = 2_000;
+/// Stacker-db write backoff timer max elapsed time in milliseconds before giving up
+const STACKERDB_WRITE_BACKOFF_MAX_ELAPSED_TIME: u64 = 30_000;
 
 #[derive(thiserror::Error, Debug)]
 /// Client error type
@@ -86,13 +107,153 @@ pub enum ClientError {
     /// Invalid response from the stacks node
     #[error("Invalid response from the stacks node: {0}")]
     InvalidResponse(String),
+    /// Timed out waiting for the rate limiter to allow a request
+    #[error("Timed out waiting to send a request to the stacks node")]
+    Timeout,
+    /// Failed to open a TCP connection to the stacks node
+    #[error("Failed to connect to the stacks node: {0}")]
+    ConnectionError(String),
+    /// The TLS handshake with the stacks node failed, e.g. because its certificate could not be verified
+    #[error("TLS connection to the stacks node failed: {0}")]
+    TlsError(String),
+    /// A stacker-db RPC request failed
+    #[error("Stacker-db request failed: {0}")]
+    StackerDBRequestFailure(RPCError),
+    /// The stacks node sent a response larger than allowed for its endpoint
+    #[error("Stacks node response exceeded the maximum size of {0} bytes")]
+    ResponseTooLarge(u64),
+    /// Failed to deserialize a JSON response from the stacks node
+    #[error("Failed to deserialize response: {0}")]
+    JsonDeserializationError(#[from] serde_json::Error),
+    /// Stacker-db kept rejecting our chunk for a stale slot version
+    #[error("Stacker-db slot version conflict persisted after {0} attempts")]
+    SlotVersionConflict(u32),
+    /// Someone else wrote to our stacker-db slot. Our signing key may be compromised or shared.
+    #[error("Stacker-db slot {0} was overwritten by another writer at version {1}")]
+    ForeignSlotWrite(u32, u32),
+    /// The stacker-db slot layout does not map the message type to any slot
+    #[error("No stacker-db slot is mapped for message slot {0}")]
+    UnmappedMessageSlot(MessageSlotID),
 }
 
-/// Retry a function F with an exponential backoff and notification on transient failure
-pub fn retry_with_exponential_backoff<F, E, T>(request_fn: F) -> Result<T, ClientError>
+impl From<RPCError> for ClientError {
+    fn from(e: RPCError) -> Self {
+        match e {
+            RPCError::Tls(msg) => Self::TlsError(msg),
+            RPCError::ResponseTooLarge(max_bytes) => Self::ResponseTooLarge(max_bytes),
+            e => Self::StackerDBRequestFailure(e),
+        }
+    }
+}
+
+impl ClientError {
+    /// Classify a failed request to the stacks node, separating TLS and TCP connection failures
+    pub fn from_request_error(e: reqwest::Error) -> Self {
+        if is_tls_error(&e) {
+            Self::TlsError(e.to_string())
+        } else if e.is_connect() {
+            Self::ConnectionError(e.to_string())
+        } else {
+            Self::ReqwestError(e)
+        }
+    }
+}
+
+/// Build the HTTP client used to talk to the stacks node over the given transport
+pub fn build_http_client(transport: NodeTransport) -> reqwest::blocking::Client {
+    let skip_verify = matches!(transport, NodeTransport::Tls { skip_verify: true });
+    reqwest::blocking::Client::builder()
+        .danger_accept_invalid_certs(skip_verify)
+        .build()
+        .expect("FATAL: failed to build the stacks node HTTP client")
+}
+
+/// Map a stacker-db RPC error to a backoff error.
+/// TLS failures and oversized responses will not resolve on retry.
+pub fn backoff_rpc_error(e: RPCError) -> backoff::Error<RPCError> {
+    if matches!(e, RPCError::Tls(_) | RPCError::ResponseTooLarge(_)) {
+        backoff::Error::permanent(e)
+    } else {
+        backoff::Error::transient(e)
+    }
+}
+
+/// The exponential backoff parameters used when retrying requests to the stacks node
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryConfig {
+    /// The interval to wait before the first retry
+    pub initial_interval: Duration,
+    /// The factor the interval is multiplied by after each failed attempt
+    pub multiplier: f64,
+    /// The upper bound on the interval between two attempts
+    pub max_interval: Duration,
+    /// The total time after which retries are abandoned. If None, retry forever.
+    pub max_elapsed_time: Option<Duration>,
+    /// The randomization factor in [0, 1] applied to each interval to spread out retries
+    /// of several signers hitting the same node
+    pub jitter: f64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            initial_interval: Duration::from_millis(BACKOFF_INITIAL_INTERVAL),
+            multiplier: BACKOFF_MULTIPLIER,
+            max_interval: Duration::from_millis(BACKOFF_MAX_INTERVAL),
+            max_elapsed_time: Some(Duration::from_millis(BACKOFF_MAX_ELAPSED_TIME)),
+            jitter: BACKOFF_JITTER,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// The default backoff policy for stacker-db writes. A write that only succeeds long after
+    /// we gave up on it advances our slot version behind our back, so writes are abandoned much
+    /// sooner than node reads.
+    pub fn stackerdb_write_default() -> Self {
+        Self {
+            max_interval: Duration::from_millis(STACKERDB_WRITE_BACKOFF_MAX_INTERVAL),
+            max_elapsed_time: Some(Duration::from_millis(
+                STACKERDB_WRITE_BACKOFF_MAX_ELAPSED_TIME,
+            )),
+            ..Self::default()
+        }
+    }
+
+    /// Return a copy of this config whose retries are abandoned after at most `deadline`.
+    /// Used by latency sensitive callers that cannot afford to block for the full elapsed time.
+    pub fn with_deadline(&self, deadline: Duration) -> Self {
+        let max_elapsed_time = match self.max_elapsed_time {
+            Some(max_elapsed_time) => max_elapsed_time.min(deadline),
+            None => deadline,
+        };
+        Self {
+            max_elapsed_time: Some(max_elapsed_time),
+            ..*self
+        }
+    }
+
+    /// Build the exponential backoff timer described by this config
+    pub fn build_backoff(&self) -> backoff::ExponentialBackoff {
+        backoff::ExponentialBackoffBuilder::new()
+            .with_initial_interval(self.initial_interval)
+            .with_multiplier(self.multiplier)
+            .with_max_interval(self.max_interval)
+            .with_max_elapsed_time(self.max_elapsed_time)
+            .with_randomization_factor(self.jitter)
+            .build()
+    }
+}
+
+/// Retry a function F with the exponential backoff described by the provided config
+/// and notification on transient failure
+pub fn retry_with_exponential_backoff<F, E, T>(
+    retry_config: &RetryConfig,
+    request_fn: F,
+) -> Result<T, ClientError>
 where
     F: FnMut() -> Result<T, backoff::Error<E>>,
-    E: std::fmt::Debug,
+    E: std::fmt::Debug + Into<ClientError>,
 {
     let notify = |err, dur| {
         debug!(
@@ -100,32 +261,38 @@ where
         );
     };
 
-    let backoff_timer = backoff::ExponentialBackoffBuilder::new()
-        .with_initial_interval(Duration::from_millis(BACKOFF_INITIAL_INTERVAL))
-        .with_max_interval(Duration::from_millis(BACKOFF_MAX_INTERVAL))
-        .build();
+    let backoff_timer = retry_config.build_backoff();
 
-    backoff::retry_notify(backoff_timer, request_fn, notify).map_err(|_| ClientError::RetryTimeout)
+    backoff::retry_notify(backoff_timer, request_fn, notify).map_err(|e| match e {
+        // Permanent errors will not resolve on retry, so report them as is
+        backoff::Error::Permanent(err) => err.into(),
+        backoff::Error::Transient { .. } => ClientError::RetryTimeout,
+    })
 }
 
 #[cfg(test)]
 pub(crate) mod tests {
     use std::io::{Read, Write};
     use std::net::{SocketAddr, TcpListener};
+    use std::sync::Arc;
 
-    use blockstack_lib::chainstate::stacks::boot::POX_4_NAME;
+    use blockstack_lib::chainstate::stacks::address::PoxAddress;
+    use blockstack_lib::chainstate::stacks::boot::{
+        NakamotoSignerEntry, PoxStartCycleInfo, RewardSet, POX_4_NAME,
+    };
     use blockstack_lib::net::api::getaccount::AccountEntryResponse;
     use blockstack_lib::net::api::getinfo::RPCPeerInfoData;
     use blockstack_lib::net::api::getpoxinfo::{
         RPCPoxCurrentCycleInfo, RPCPoxEpoch, RPCPoxInfoData, RPCPoxNextCycleInfo,
     };
+    use blockstack_lib::net::api::getstackers::GetStackersResponse;
     use blockstack_lib::net::api::postfeerate::{RPCFeeEstimate, RPCFeeEstimateResponse};
     use blockstack_lib::util_lib::boot::boot_code_id;
     use clarity::vm::costs::ExecutionCost;
     use clarity::vm::types::TupleData;
     use clarity::vm::Value as ClarityValue;
     use hashbrown::{HashMap, HashSet};
-    use libsigner::SignerEntries;
+    use libsigner::{SignerEntries, SignerSlotOwners};
     use rand::distributions::Standard;
     use rand::{thread_rng, Rng};
     use rand_core::{OsRng, RngCore};
@@ -164,6 +331,14 @@ pub(crate) mod tests {
             }
         }
 
+        /// Construct a new MockServerClient on a random port whose client connects over TLS
+        pub fn new_tls(skip_verify: bool) -> Self {
+            let mut mock = Self::new();
+            mock.config.node_transport = NodeTransport::Tls { skip_verify };
+            mock.client = StacksClient::from(&mock.config);
+            mock
+        }
+
         /// Construct a new MockServerClient on the port specified in the config
         pub fn from_config(config: GlobalConfig) -> Self {
             let server = mock_server_from_config(&config);
@@ -203,6 +378,42 @@ pub(crate) mod tests {
         request_bytes
     }
 
+    /// Build a TLS server config with a freshly generated self-signed certificate for `localhost`
+    pub fn mock_tls_server_config() -> Arc<rustls::ServerConfig> {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let cert_der = rustls::Certificate(cert.serialize_der().unwrap());
+        let key_der = rustls::PrivateKey(cert.serialize_private_key_der());
+        let server_config = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(vec![cert_der], key_der)
+            .unwrap();
+        Arc::new(server_config)
+    }
+
+    /// Write a response to the mock server over TLS and return the request bytes.
+    /// If the client aborts the handshake, nothing is written and the request bytes are empty.
+    pub fn write_tls_response(
+        mock_server: TcpListener,
+        server_config: Arc<rustls::ServerConfig>,
+        bytes: &[u8],
+    ) -> [u8; 1024] {
+        debug!("Writing a TLS response...");
+        let mut request_bytes = [0u8; 1024];
+        {
+            let sock = mock_server.accept().unwrap().0;
+            let conn = rustls::ServerConnection::new(server_config).unwrap();
+            let mut stream = rustls::StreamOwned::new(conn, sock);
+            // The first read drives the handshake
+            if stream.read(&mut request_bytes).is_ok() {
+                stream.write_all(bytes).unwrap();
+                stream.conn.send_close_notify();
+                stream.flush().unwrap();
+            }
+        }
+        request_bytes
+    }
+
     pub fn generate_random_consensus_hash() -> ConsensusHash {
         let rng = rand::thread_rng();
         let bytes: Vec<u8> = rng.sample_iter(Standard).take(20).collect();
@@ -218,6 +429,23 @@ pub(crate) mod tests {
         build_read_only_response(&value)
     }
 
+    /// Build a response for the get_reward_set_signers request
+    pub fn build_get_reward_set_response(signers: Vec<NakamotoSignerEntry>) -> String {
+        let stackers_response = GetStackersResponse {
+            stacker_set: RewardSet {
+                rewarded_addresses: vec![PoxAddress::standard_burn_address(false)],
+                start_cycle_state: PoxStartCycleInfo {
+                    missed_reward_slots: vec![],
+                },
+                signers: Some(signers),
+                pox_ustx_threshold: None,
+            },
+        };
+        let stackers_response_json = serde_json::to_string(&stackers_response)
+            .expect("Failed to serialize get stacker response");
+        format!("HTTP/1.1 200 OK\n\n{stackers_response_json}")
+    }
+
     /// Build a response for the get_account_nonce request
     pub fn build_account_nonce_response(nonce: u64) -> String {
         let account_nonce_entry = AccountEntryResponse {
@@ -328,27 +556,53 @@ pub(crate) mod tests {
         build_read_only_response(&clarity_value)
     }
 
-    /// Build a response for the get_approved_aggregate_key request
+    /// Build a response for the get_vote_for_aggregate_public_key request
     pub fn build_get_vote_for_aggregate_key_response(point: Option<Point>) -> String {
         let clarity_value = if let Some(point) = point {
-            ClarityValue::some(ClarityValue::Tuple(
-                TupleData::from_data(vec![
-                    (
-                        "aggregate-public-key".into(),
-                        ClarityValue::buff_from(point.compress().as_bytes().to_vec())
-                            .expect("BUG: Failed to create clarity value from point"),
-                    ),
-                    ("signer-weight".into(), ClarityValue::UInt(1)), // fixed for testing purposes
-                ])
-                .expect("BUG: Failed to create clarity value from tuple data"),
-            ))
-            .expect("BUG: Failed to create clarity value from tuple data")
+            ClarityValue::some(
+                ClarityValue::buff_from(point.compress().as_bytes().to_vec())
+                    .expect("BUG: Failed to create clarity value from point"),
+            )
+            .expect("BUG: Failed to create clarity value from point")
         } else {
             ClarityValue::none()
         };
         build_read_only_response(&clarity_value)
     }
 
+    /// Build a response for the get_aggregate_key_tally request
+    pub fn build_get_aggregate_key_tally_response(weight: Option<u128>) -> String {
+        let clarity_value = if let Some(weight) = weight {
+            ClarityValue::some(ClarityValue::UInt(weight))
+                .expect("BUG: Failed to create clarity value from weight")
+        } else {
+            ClarityValue::none()
+        };
+        build_read_only_response(&clarity_value)
+    }
+
+    /// Build a response for the get_candidate_info request
+    pub fn build_get_candidate_info_response(
+        candidate_weight: u128,
+        total_weight: Option<u128>,
+    ) -> String {
+        let total_weight = total_weight.map_or_else(ClarityValue::none, |weight| {
+            ClarityValue::some(ClarityValue::UInt(weight))
+                .expect("BUG: Failed to create clarity value from weight")
+        });
+        let clarity_value = ClarityValue::Tuple(
+            TupleData::from_data(vec![
+                (
+                    "candidate-weight".into(),
+                    ClarityValue::UInt(candidate_weight),
+                ),
+                ("total-weight".into(), total_weight),
+            ])
+            .expect("BUG: Failed to create clarity value from tuple data"),
+        );
+        build_read_only_response(&clarity_value)
+    }
+
     /// Build a response for the get_peer_info_with_retry request with a specific stacks tip height and consensus hash
     pub fn build_get_peer_info_response(
         burn_block_height: Option<u64>,
@@ -536,6 +790,7 @@ pub(crate) mod tests {
         SignerConfig {
             reward_cycle,
             signer_id: 0,
+            signer_name: "signer-0".to_string(),
             signer_slot_id: SignerSlotID(rand::thread_rng().gen_range(0..num_signers)), // Give a random signer slot id between 0 and num_signers
             key_ids: signer_key_ids.get(&0).cloned().unwrap_or_default(),
             signer_entries: SignerEntries {
@@ -546,18 +801,38 @@ pub(crate) mod tests {
                 signer_public_keys,
             },
             signer_slot_ids,
+            slot_owners: SignerSlotOwners::default(),
             ecdsa_private_key: config.ecdsa_private_key,
             stacks_private_key: config.stacks_private_key,
             node_host: config.node_host.to_string(),
-            mainnet: config.network.is_mainnet(),
+            node_transport: config.node_transport,
+            network: config.network_profile,
             dkg_end_timeout: config.dkg_end_timeout,
             dkg_private_timeout: config.dkg_private_timeout,
             dkg_public_timeout: config.dkg_public_timeout,
             nonce_timeout: config.nonce_timeout,
             sign_timeout: config.sign_timeout,
+            block_validation_timeout: config.block_validation_timeout,
+            dkg_round_stall_timeout: config.dkg_round_stall_timeout,
+            packet_future_tolerance: config.packet_future_tolerance,
             tx_fee_ustx: config.tx_fee_ustx,
             max_tx_fee_ustx: config.max_tx_fee_ustx,
+            max_acceptable_median_fee_rate: config.max_acceptable_median_fee_rate,
+            max_validation_attempts: config.max_validation_attempts,
+            max_rejected_requests_per_round: config.max_rejected_requests_per_round,
+            slot_layout: SlotLayout::default(),
+            clear_stackerdb_on_tenure_end: config.clear_stackerdb_on_tenure_end,
+            parallel_validation: config.parallel_validation,
+            missing_transaction_policy: config.missing_transaction_policy,
+            stale_node_nack_policy: config.stale_node_nack_policy,
+            event_recorder: None,
+            alert_notifier: None,
+            block_proposal_filter: None,
             db_path: config.db_path.clone(),
+            retry_config: config.retry_config,
+            stackerdb_write_retry_config: config.stackerdb_write_retry_config,
+            response_limits: config.response_limits,
+            chunk_compression_threshold: config.chunk_compression_threshold,
         }
     }
 
@@ -584,4 +859,58 @@ pub(crate) mod tests {
         let clarity_value = ClarityValue::UInt(threshold as u128);
         build_read_only_response(&clarity_value)
     }
+
+    #[test]
+    fn backoff_intervals_respect_configured_bounds() {
+        use backoff::backoff::Backoff;
+
+        let retry_config = RetryConfig {
+            initial_interval: Duration::from_millis(100),
+            multiplier: 2.0,
+            max_interval: Duration::from_millis(400),
+            max_elapsed_time: None,
+            jitter: 0.1,
+        };
+        let mut backoff_timer = retry_config.build_backoff();
+        let mut expected_interval = retry_config.initial_interval;
+        for _ in 0..10 {
+            let interval = backoff_timer
+                .next_backoff()
+                .expect("Backoff should never give up without a max elapsed time");
+            // Allow for a small rounding error in the randomized interval
+            let tolerance = Duration::from_millis(1);
+            assert!(interval + tolerance >= expected_interval.mul_f64(1.0 - retry_config.jitter));
+            assert!(interval <= expected_interval.mul_f64(1.0 + retry_config.jitter) + tolerance);
+            expected_interval = expected_interval
+                .mul_f64(retry_config.multiplier)
+                .min(retry_config.max_interval);
+        }
+    }
+
+    #[test]
+    fn retry_deadline_stops_retries() {
+        let retry_config = RetryConfig {
+            initial_interval: Duration::from_millis(10),
+            multiplier: 1.0,
+            max_interval: Duration::from_millis(10),
+            max_elapsed_time: Some(Duration::from_secs(60)),
+            jitter: 0.0,
+        }
+        .with_deadline(Duration::from_millis(100));
+        assert_eq!(
+            retry_config.max_elapsed_time,
+            Some(Duration::from_millis(100))
+        );
+
+        let mut attempts = 0;
+        let start = std::time::Instant::now();
+        let result: Result<(), ClientError> = retry_with_exponential_backoff(&retry_config, || {
+            attempts += 1;
+            Err(backoff::Error::transient(ClientError::NotConnected))
+        });
+        assert!(matches!(result, Err(ClientError::RetryTimeout)));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(attempts > 1);
+        assert!(attempts <= 12);
+    }
 }
@@ -0,0 +1,208 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use reqwest::header::RETRY_AFTER;
+use reqwest::StatusCode;
+
+use super::ClientError;
+
+/// Default number of requests per second allowed to the stacks node
+const RATE_LIMIT_REQUESTS_PER_SECOND: f64 = 50.0;
+/// Default max time in milliseconds a request may wait for the rate limiter before failing
+const RATE_LIMIT_MAX_QUEUE_WAIT: u64 = 30_000;
+
+/// The parameters of the token bucket used to rate limit requests to the stacks node
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitConfig {
+    /// The sustained number of requests per second. This is also the size of an allowed burst.
+    pub requests_per_second: f64,
+    /// The maximum time a request may wait for the rate limiter before failing with a timeout
+    pub max_queue_wait: Duration,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_second: RATE_LIMIT_REQUESTS_PER_SECOND,
+            max_queue_wait: Duration::from_millis(RATE_LIMIT_MAX_QUEUE_WAIT),
+        }
+    }
+}
+
+/// The mutable state of the token bucket
+#[derive(Debug)]
+struct TokenBucket {
+    /// The number of requests that may currently be sent without waiting
+    tokens: f64,
+    /// The last time tokens were added to the bucket
+    last_refill: Instant,
+    /// No request may be sent before this time (set when the node asks us to back off)
+    paused_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    /// Take a token from the bucket if one is available.
+    /// Otherwise, return how long to wait before trying again.
+    fn try_take(&mut self, config: &RateLimitConfig, now: Instant) -> Option<Duration> {
+        if let Some(paused_until) = self.paused_until {
+            if paused_until > now {
+                return Some(paused_until - now);
+            }
+            self.paused_until = None;
+        }
+        let capacity = config.requests_per_second.max(1.0);
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens =
+            (self.tokens + elapsed.as_secs_f64() * config.requests_per_second).min(capacity);
+        self.last_refill = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return None;
+        }
+        Some(Duration::from_secs_f64(
+            (1.0 - self.tokens) / config.requests_per_second,
+        ))
+    }
+}
+
+/// A token bucket rate limiter shared by all clones of a `StacksClient`
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    /// The rate limiting parameters
+    config: RateLimitConfig,
+    /// The shared token bucket
+    bucket: Arc<Mutex<TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Create a new rate limiter with a full bucket
+    pub fn new(config: RateLimitConfig) -> Self {
+        let bucket = TokenBucket {
+            tokens: config.requests_per_second.max(1.0),
+            last_refill: Instant::now(),
+            paused_until: None,
+        };
+        Self {
+            config,
+            bucket: Arc::new(Mutex::new(bucket)),
+        }
+    }
+
+    /// The rate limiting parameters
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Block until a request may be sent to the stacks node.
+    /// Fails with `ClientError::Timeout` if that would take longer than the configured max queue wait.
+    pub fn acquire(&self) -> Result<(), ClientError> {
+        let deadline = Instant::now() + self.config.max_queue_wait;
+        loop {
+            let now = Instant::now();
+            let Some(wait) = self
+                .bucket
+                .lock()
+                .expect("FATAL: rate limiter lock poisoned")
+                .try_take(&self.config, now)
+            else {
+                return Ok(());
+            };
+            if wait > deadline.saturating_duration_since(now) {
+                return Err(ClientError::Timeout);
+            }
+            std::thread::sleep(wait);
+        }
+    }
+
+    /// Hold back all requests for the given duration
+    pub fn pause_for(&self, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut bucket = self
+            .bucket
+            .lock()
+            .expect("FATAL: rate limiter lock poisoned");
+        if bucket.paused_until.map_or(true, |paused| paused < until) {
+            bucket.paused_until = Some(until);
+        }
+    }
+
+    /// How long requests are still held back at the stacks node's request, if they are
+    pub fn remaining_pause(&self) -> Option<Duration> {
+        let bucket = self
+            .bucket
+            .lock()
+            .expect("FATAL: rate limiter lock poisoned");
+        bucket
+            .paused_until
+            .and_then(|paused_until| paused_until.checked_duration_since(Instant::now()))
+            .filter(|remaining| !remaining.is_zero())
+    }
+}
+
+/// Whether the stacks node is asking us to slow down
+pub fn is_backpressure_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::SERVICE_UNAVAILABLE
+}
+
+/// Parse the delay requested by a `Retry-After` header.
+/// Only the delay-seconds form is supported. HTTP dates are ignored.
+pub fn parse_retry_after(response: &reqwest::blocking::Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn acquire_should_pace_requests() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            requests_per_second: 10.0,
+            max_queue_wait: Duration::from_secs(5),
+        });
+        let start = Instant::now();
+        // The first 10 requests are served from the initial burst. The next 5 take ~100ms each.
+        for _ in 0..15 {
+            limiter.acquire().expect("Failed to acquire rate limiter");
+        }
+        assert!(start.elapsed() >= Duration::from_millis(450));
+    }
+
+    #[test]
+    fn acquire_should_time_out() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            requests_per_second: 1.0,
+            max_queue_wait: Duration::from_millis(100),
+        });
+        limiter.acquire().expect("Failed to acquire rate limiter");
+        assert!(matches!(limiter.acquire(), Err(ClientError::Timeout)));
+
+        let limiter = RateLimiter::new(RateLimitConfig::default());
+        limiter.pause_for(Duration::from_secs(60));
+        assert!(matches!(limiter.acquire(), Err(ClientError::Timeout)));
+    }
+}
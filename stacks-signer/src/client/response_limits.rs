@@ -0,0 +1,192 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::{self, Read};
+
+use serde::de::DeserializeOwned;
+
+use super::ClientError;
+
+/// Default max size in bytes of account, nonce and fee lookups
+const MAX_RESPONSE_BYTES_ACCOUNT: u64 = 64 * 1024;
+/// Default max size in bytes of other RPC responses (node info, pox info, read-only calls, ...)
+const MAX_RESPONSE_BYTES_RPC: u64 = 4 * 1024 * 1024;
+/// Default max size in bytes of stacker-db responses. Leaves room for the HTTP headers of a
+/// maximum size signer chunk.
+const MAX_RESPONSE_BYTES_STACKERDB: u64 = 3 * 1024 * 1024;
+
+/// The kinds of stacks node endpoints, grouped by the size of their expected responses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseCategory {
+    /// Account, nonce and fee lookups
+    Account,
+    /// Any other RPC endpoint
+    Rpc,
+    /// Stacker-db chunks and metadata
+    StackerDB,
+}
+
+/// The maximum number of bytes read from a stacks node response, per endpoint category
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResponseLimits {
+    /// The limit for account, nonce and fee lookups
+    pub account: u64,
+    /// The limit for any other RPC endpoint
+    pub rpc: u64,
+    /// The limit for stacker-db responses
+    pub stackerdb: u64,
+}
+
+impl Default for ResponseLimits {
+    fn default() -> Self {
+        Self {
+            account: MAX_RESPONSE_BYTES_ACCOUNT,
+            rpc: MAX_RESPONSE_BYTES_RPC,
+            stackerdb: MAX_RESPONSE_BYTES_STACKERDB,
+        }
+    }
+}
+
+impl ResponseLimits {
+    /// The limit for the given endpoint category
+    pub fn max_bytes(&self, category: ResponseCategory) -> u64 {
+        match category {
+            ResponseCategory::Account => self.account,
+            ResponseCategory::Rpc => self.rpc,
+            ResponseCategory::StackerDB => self.stackerdb,
+        }
+    }
+}
+
+/// A reader that fails once more than a fixed number of bytes are read from the inner reader
+struct BoundedReader<R> {
+    /// The reader being limited
+    inner: R,
+    /// The number of bytes that may still be read
+    remaining: u64,
+    /// Whether the inner reader had more data than allowed
+    exceeded: bool,
+}
+
+impl<R: Read> BoundedReader<R> {
+    fn new(inner: R, max_bytes: u64) -> Self {
+        Self {
+            inner,
+            remaining: max_bytes,
+            exceeded: false,
+        }
+    }
+}
+
+impl<R: Read> Read for BoundedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.remaining == 0 {
+            // Only fail if there actually is more data
+            if self.inner.read(&mut [0u8; 1])? == 0 {
+                return Ok(0);
+            }
+            self.exceeded = true;
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "response exceeds the maximum size",
+            ));
+        }
+        let max_len = usize::try_from(self.remaining)
+            .unwrap_or(usize::MAX)
+            .min(buf.len());
+        let read = self.inner.read(&mut buf[..max_len])?;
+        self.remaining = self.remaining.saturating_sub(read as u64);
+        Ok(read)
+    }
+}
+
+/// Deserialize a JSON value straight from `reader`, reading at most `max_bytes`
+pub fn read_json_bounded<T, R>(reader: R, max_bytes: u64) -> Result<T, ClientError>
+where
+    T: DeserializeOwned,
+    R: Read,
+{
+    let mut reader = BoundedReader::new(reader, max_bytes);
+    let result = serde_json::from_reader(&mut reader);
+    if reader.exceeded {
+        return Err(ClientError::ResponseTooLarge(max_bytes));
+    }
+    result.map_err(ClientError::from)
+}
+
+/// Deserialize the JSON body of a stacks node response, reading at most `max_bytes`.
+/// Responses that announce a larger body are rejected without reading it.
+pub fn read_json_response<T: DeserializeOwned>(
+    response: reqwest::blocking::Response,
+    max_bytes: u64,
+) -> Result<T, ClientError> {
+    if response
+        .content_length()
+        .map_or(false, |len| len > max_bytes)
+    {
+        return Err(ClientError::ResponseTooLarge(max_bytes));
+    }
+    read_json_bounded(response, max_bytes)
+}
+
+/// Read the raw body of a stacks node response, reading at most `max_bytes`.
+/// Responses that announce a larger body are rejected without reading it.
+pub fn read_bytes_response(
+    response: reqwest::blocking::Response,
+    max_bytes: u64,
+) -> Result<Vec<u8>, ClientError> {
+    if response
+        .content_length()
+        .map_or(false, |len| len > max_bytes)
+    {
+        return Err(ClientError::ResponseTooLarge(max_bytes));
+    }
+    let mut reader = BoundedReader::new(response, max_bytes);
+    let mut body = vec![];
+    let result = reader.read_to_end(&mut body);
+    if reader.exceeded {
+        return Err(ClientError::ResponseTooLarge(max_bytes));
+    }
+    result.map_err(|e| ClientError::InvalidResponse(e.to_string()))?;
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_json_bounded_should_enforce_limit() {
+        let body = br#"{"nonce": 1, "balance": "0x00"}"#;
+        let value: serde_json::Value =
+            read_json_bounded(&body[..], body.len() as u64).expect("Failed to read json");
+        assert_eq!(value["nonce"], 1);
+
+        assert!(matches!(
+            read_json_bounded::<serde_json::Value, _>(&body[..], body.len() as u64 - 1),
+            Err(ClientError::ResponseTooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn read_json_bounded_should_stop_reading_endless_response() {
+        // An endless stream of whitespace would exhaust memory if it were buffered
+        let max_bytes = 1024;
+        let mut source = io::repeat(b' ');
+        let result = read_json_bounded::<serde_json::Value, _>(&mut source, max_bytes);
+        assert!(matches!(result, Err(ClientError::ResponseTooLarge(limit)) if limit == max_bytes));
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+
+use blockstack_lib::chainstate::nakamoto::signer_set::NakamotoSigners;
+use clarity::vm::types::QualifiedContractIdentifier;
+use libsigner::v1::messages::MessageSlotID;
+
+/// The message types the signer never writes to stacker-db, which a layout may leave unmapped
+pub const UNSENT_MESSAGE_SLOTS: &[MessageSlotID] = &[MessageSlotID::EncryptedSignerState];
+
+/// An error in the stacker-db slot layout
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum SlotLayoutError {
+    /// A message type is mapped past the slots the contract allocates to each signer
+    #[error("Message slot {0} is mapped to offset {1}, but the contract only allocates {2} slots per signer")]
+    OffsetOutOfRange(MessageSlotID, u32, u32),
+    /// Two message types are mapped to the same slot
+    #[error("Message slots {0} and {1} are both mapped to offset {2}")]
+    SharedOffset(MessageSlotID, MessageSlotID, u32),
+    /// A message type stored in the slot of another message type is mapped to an offset
+    #[error("Message slot {0} is stored in the slot of {1} and cannot be mapped on its own")]
+    StoredInSharedSlot(MessageSlotID, MessageSlotID),
+    /// A message type the signer writes to stacker-db is not mapped to any slot
+    #[error("Message slot {0} is written by the signer, but is not mapped to any slot")]
+    UnmappedMessageSlot(MessageSlotID),
+}
+
+/// The stacker-db slot layout of a signer set: the offset within a signer's slot range, i.e. the
+/// index of the message contract (X in signers-N-X), each type of signer message is written to and
+/// read from. Message types without an offset are never written to or read from stacker-db.
+/// Message types stored in the slot of another type (see `MessageSlotID::storage_slot`) follow
+/// the offset of that type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotLayout {
+    /// The slot offset of each mapped message type
+    offsets: BTreeMap<MessageSlotID, u32>,
+}
+
+impl Default for SlotLayout {
+    /// Map each message type stored in its own slot to the offset of its own ID
+    fn default() -> Self {
+        Self {
+            offsets: MessageSlotID::ALL
+                .iter()
+                .filter(|msg_id| msg_id.storage_slot() == **msg_id)
+                .map(|msg_id| (*msg_id, msg_id.to_u32()))
+                .collect(),
+        }
+    }
+}
+
+impl SlotLayout {
+    /// Create a layout from explicit message type offsets.
+    /// Fails if two message types share an offset, or if a message type stored in the slot of
+    /// another type is given an offset of its own.
+    pub fn new(offsets: BTreeMap<MessageSlotID, u32>) -> Result<Self, SlotLayoutError> {
+        let mut owners = BTreeMap::new();
+        for (msg_id, offset) in offsets.iter() {
+            let storage_slot = msg_id.storage_slot();
+            if storage_slot != *msg_id {
+                return Err(SlotLayoutError::StoredInSharedSlot(*msg_id, storage_slot));
+            }
+            if let Some(owner) = owners.insert(*offset, *msg_id) {
+                return Err(SlotLayoutError::SharedOffset(owner, *msg_id, *offset));
+            }
+        }
+        Ok(Self { offsets })
+    }
+
+    /// The default layout, restricted to the message types whose offset is within the
+    /// `slots_per_signer` slots the contract allocates to each signer
+    pub fn default_for_slots(slots_per_signer: u32) -> Self {
+        let mut layout = Self::default();
+        layout
+            .offsets
+            .retain(|_, offset| *offset < slots_per_signer);
+        layout
+    }
+
+    /// Check that every mapped offset lies within the `slots_per_signer` slots the contract
+    /// allocates to each signer, and that every message type the signer writes is mapped
+    pub fn validate(&self, slots_per_signer: u32) -> Result<(), SlotLayoutError> {
+        for (msg_id, offset) in self.offsets.iter() {
+            if *offset >= slots_per_signer {
+                return Err(SlotLayoutError::OffsetOutOfRange(
+                    *msg_id,
+                    *offset,
+                    slots_per_signer,
+                ));
+            }
+        }
+        if let Some(msg_id) = self
+            .unmapped()
+            .into_iter()
+            .find(|msg_id| !UNSENT_MESSAGE_SLOTS.contains(msg_id))
+        {
+            return Err(SlotLayoutError::UnmappedMessageSlot(msg_id));
+        }
+        Ok(())
+    }
+
+    /// The slot offset of the given message type, if it is mapped
+    pub fn offset(&self, msg_id: &MessageSlotID) -> Option<u32> {
+        self.offsets.get(&msg_id.storage_slot()).copied()
+    }
+
+    /// The message types not mapped to any slot
+    pub fn unmapped(&self) -> Vec<MessageSlotID> {
+        MessageSlotID::ALL
+            .iter()
+            .filter(|msg_id| self.offset(msg_id).is_none())
+            .copied()
+            .collect()
+    }
+
+    /// The StackerDB contract messages of the given type are written to, if it is mapped
+    pub fn stacker_db_contract(
+        &self,
+        msg_id: &MessageSlotID,
+        mainnet: bool,
+        reward_cycle: u64,
+    ) -> Option<QualifiedContractIdentifier> {
+        let offset = self.offset(msg_id)?;
+        Some(NakamotoSigners::make_signers_db_contract_id(
+            reward_cycle,
+            offset,
+            mainnet,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use stacks_common::consts::SIGNER_SLOTS_PER_USER;
+
+    use super::*;
+
+    #[test]
+    fn default_layout_should_map_message_ids_to_their_storage_slot_offset() {
+        let layout = SlotLayout::default();
+        for msg_id in MessageSlotID::ALL {
+            assert_eq!(layout.offset(msg_id), Some(msg_id.storage_slot().to_u32()));
+        }
+        assert!(layout.unmapped().is_empty());
+        assert_eq!(
+            layout.stacker_db_contract(&MessageSlotID::BlockResponse, false, 3),
+            Some(MessageSlotID::BlockResponse.stacker_db_contract(false, 3))
+        );
+    }
+
+    #[test]
+    fn default_layout_should_be_restricted_to_allocated_slots() {
+        let layout = SlotLayout::default_for_slots(SIGNER_SLOTS_PER_USER);
+        assert!(layout.validate(SIGNER_SLOTS_PER_USER).is_ok());
+        let is_allocated =
+            |msg_id: &MessageSlotID| msg_id.storage_slot().to_u32() < SIGNER_SLOTS_PER_USER;
+        let expected_unmapped: Vec<_> = MessageSlotID::ALL
+            .iter()
+            .filter(|msg_id| !is_allocated(msg_id))
+            .copied()
+            .collect();
+        assert_eq!(layout.unmapped(), expected_unmapped);
+        for msg_id in MessageSlotID::ALL {
+            assert_eq!(
+                layout.stacker_db_contract(msg_id, false, 3),
+                is_allocated(msg_id).then(|| msg_id.stacker_db_contract(false, 3))
+            );
+        }
+    }
+
+    #[test]
+    fn layout_should_reject_offsets_past_allocated_slots() {
+        let layout = SlotLayout::default();
+        let last_slot = MessageSlotID::ALL
+            .iter()
+            .map(MessageSlotID::storage_slot)
+            .max_by_key(MessageSlotID::to_u32)
+            .unwrap();
+        let slots_per_signer = last_slot.to_u32();
+        assert_eq!(
+            layout.validate(slots_per_signer),
+            Err(SlotLayoutError::OffsetOutOfRange(
+                last_slot,
+                slots_per_signer,
+                slots_per_signer
+            ))
+        );
+        assert!(layout.validate(slots_per_signer + 1).is_ok());
+    }
+
+    #[test]
+    fn layout_should_reject_unmapped_sent_message_types() {
+        let layout = SlotLayout::new(BTreeMap::from([(MessageSlotID::BlockResponse, 0)])).unwrap();
+        let first_unmapped = MessageSlotID::ALL
+            .iter()
+            .find(|msg_id| **msg_id != MessageSlotID::BlockResponse)
+            .unwrap();
+        assert_eq!(
+            layout.validate(SIGNER_SLOTS_PER_USER),
+            Err(SlotLayoutError::UnmappedMessageSlot(*first_unmapped))
+        );
+
+        // Only the message types the signer never writes may be left unmapped
+        let layout = SlotLayout::new(
+            MessageSlotID::ALL
+                .iter()
+                .filter(|msg_id| {
+                    msg_id.storage_slot() == **msg_id && !UNSENT_MESSAGE_SLOTS.contains(msg_id)
+                })
+                .map(|msg_id| (*msg_id, msg_id.to_u32()))
+                .collect(),
+        )
+        .unwrap();
+        assert_eq!(layout.unmapped(), UNSENT_MESSAGE_SLOTS);
+        assert!(layout.validate(SIGNER_SLOTS_PER_USER).is_ok());
+    }
+
+    #[test]
+    fn layout_should_reject_shared_offsets() {
+        let offsets = BTreeMap::from([
+            (MessageSlotID::BlockResponse, 0),
+            (MessageSlotID::Transactions, 1),
+            (MessageSlotID::DkgResults, 0),
+        ]);
+        assert_eq!(
+            SlotLayout::new(offsets),
+            Err(SlotLayoutError::SharedOffset(
+                MessageSlotID::BlockResponse,
+                MessageSlotID::DkgResults,
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn layout_should_reject_offsets_of_message_types_stored_in_shared_slots() {
+        let offsets = BTreeMap::from([
+            (MessageSlotID::SignatureShareResponse, 0),
+            (MessageSlotID::StackerDbSlotUpdate, 1),
+        ]);
+        assert_eq!(
+            SlotLayout::new(offsets),
+            Err(SlotLayoutError::StoredInSharedSlot(
+                MessageSlotID::StackerDbSlotUpdate,
+                MessageSlotID::SignatureShareResponse
+            ))
+        );
+
+        let layout =
+            SlotLayout::new(BTreeMap::from([(MessageSlotID::SignatureShareResponse, 4)])).unwrap();
+        assert_eq!(layout.offset(&MessageSlotID::StackerDbSlotUpdate), Some(4));
+    }
+}
@@ -14,21 +14,51 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
 use blockstack_lib::chainstate::stacks::StacksTransaction;
 use blockstack_lib::net::api::poststackerdbchunk::StackerDBErrorCodes;
 use hashbrown::HashMap;
 use libsigner::v1::messages::{MessageSlotID, SignerMessage};
-use libsigner::{SignerSession, StackerDBSession};
-use libstackerdb::{StackerDBChunkAckData, StackerDBChunkData};
+use libsigner::{
+    decode_chunk, encode_chunk, fragment_chunk, ChunkEnvelope, NodeTransport, SignerSession,
+    SignerSlotOwners, StackerDBSession, DEFAULT_CHUNK_COMPRESSION_THRESHOLD,
+};
+use libstackerdb::{
+    SlotMetadata, StackerDBChunkAckData, StackerDBChunkData, SIGNERS_STACKERDB_CHUNK_SIZE,
+};
 use slog::{slog_debug, slog_error, slog_warn};
-use stacks_common::codec::{read_next, StacksMessageCodec};
+use stacks_common::codec::StacksMessageCodec;
 use stacks_common::types::chainstate::StacksPrivateKey;
+use stacks_common::util::hash::Sha512Trunc256Sum;
 use stacks_common::{debug, error, warn};
 use wsts::net::Packet;
 
 use super::ClientError;
-use crate::client::retry_with_exponential_backoff;
+use crate::client::{backoff_rpc_error, retry_with_exponential_backoff, RetryConfig, SlotLayout};
 use crate::config::SignerConfig;
+use crate::monitoring::StackerDBAckOutcome;
+
+/// The minimum time between two slot update notifications for the same signer
+const SLOT_UPDATE_MIN_INTERVAL: Duration = Duration::from_millis(100);
+/// The maximum number of stale slot version rejections tolerated for a single message
+const SLOT_VERSION_CONFLICT_MAX_ATTEMPTS: u32 = 5;
+/// The maximum number of undelivered messages held in the outbound queue
+const OUTBOUND_QUEUE_MAX_LEN: usize = 64;
+/// The message slots wsts packets are written to
+const PACKET_SLOT_IDS: &[MessageSlotID] = &[
+    MessageSlotID::DkgBegin,
+    MessageSlotID::DkgPrivateBegin,
+    MessageSlotID::DkgEndBegin,
+    MessageSlotID::DkgEnd,
+    MessageSlotID::DkgPublicShares,
+    MessageSlotID::DkgPrivateShares,
+    MessageSlotID::NonceRequest,
+    MessageSlotID::NonceResponse,
+    MessageSlotID::SignatureShareRequest,
+    MessageSlotID::SignatureShareResponse,
+];
 
 /// The signer StackerDB slot ID, purposefully wrapped to prevent conflation with SignerID
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy, PartialOrd, Ord)]
@@ -40,6 +70,85 @@ impl std::fmt::Display for SignerSlotID {
     }
 }
 
+/// A chunk read back from a stacker-db slot
+#[derive(Debug, Clone, PartialEq)]
+struct SlotChunk {
+    /// The slot the chunk was read from
+    slot_id: u32,
+    /// The version of the chunk, if the replica reported it
+    slot_version: Option<u32>,
+    /// The chunk data
+    data: Vec<u8>,
+}
+
+/// The last chunk of a slot delivered to the signer. Identified by its version when known, since a
+/// slot can legitimately be rewritten with identical bytes, and by its data hash otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeliveredChunk {
+    /// The version of the delivered chunk
+    Version(u32),
+    /// The hash of the delivered chunk's data
+    DataHash(Sha512Trunc256Sum),
+}
+
+impl From<&SlotChunk> for DeliveredChunk {
+    fn from(chunk: &SlotChunk) -> Self {
+        match chunk.slot_version {
+            Some(version) => Self::Version(version),
+            None => Self::DataHash(Sha512Trunc256Sum::from_data(&chunk.data)),
+        }
+    }
+}
+
+/// The outcome of writing a message to stackerdb
+#[derive(Debug, Clone, PartialEq)]
+pub enum SendOutcome {
+    /// The node acknowledged storing the message
+    Acked(StackerDBChunkAckData),
+    /// The node definitively refused to store the message
+    Rejected(String),
+    /// No acknowledgement was received in time: the message may or may not have been stored
+    Unknown,
+}
+
+impl SendOutcome {
+    /// Classify the result of a chunk write. Errors raised before the chunk could reach the node
+    /// are returned as is.
+    fn from_write_result(
+        result: Result<StackerDBChunkAckData, ClientError>,
+    ) -> Result<Self, ClientError> {
+        match result {
+            Ok(chunk_ack) => Ok(Self::Acked(chunk_ack)),
+            Err(ClientError::PutChunkRejected(reason)) => Ok(Self::Rejected(reason)),
+            Err(e @ (ClientError::SlotVersionConflict(_) | ClientError::ForeignSlotWrite(..))) => {
+                Ok(Self::Rejected(e.to_string()))
+            }
+            Err(ClientError::RetryTimeout | ClientError::ResponseTooLarge(_)) => Ok(Self::Unknown),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The ack of the written message, for callers that treat any other outcome as a failure
+    pub fn into_ack(self) -> Result<StackerDBChunkAckData, ClientError> {
+        match self {
+            Self::Acked(chunk_ack) => Ok(chunk_ack),
+            Self::Rejected(reason) => Err(ClientError::PutChunkRejected(reason)),
+            Self::Unknown => Err(ClientError::RetryTimeout),
+        }
+    }
+}
+
+/// A message that could not be written to stackerdb, waiting to be sent again
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuedMessage {
+    /// The ID the message is persisted under in the signer db, if it could be persisted
+    pub id: Option<i64>,
+    /// The message to send
+    pub message: SignerMessage,
+    /// The time (in seconds since the epoch) after which the message is no longer worth sending
+    pub expires_at: u64,
+}
+
 /// The StackerDB client for communicating with the .signers contract
 #[derive(Debug)]
 pub struct StackerDB {
@@ -48,97 +157,357 @@ pub struct StackerDB {
     signers_message_stackerdb_sessions: HashMap<MessageSlotID, StackerDBSession>,
     /// The private key used in all stacks node communications
     stacks_private_key: StacksPrivateKey,
-    /// A map of a message ID to last chunk version for each session
+    /// A map of a storage slot to last chunk version for each session
     slot_versions: HashMap<MessageSlotID, HashMap<SignerSlotID, u32>>,
+    /// The version and data hash of the last chunk we wrote to our slot for each storage slot
+    last_written_chunks: HashMap<MessageSlotID, (u32, Sha512Trunc256Sum)>,
     /// The signer slot ID -- the index into the signer list for this signer daemon's signing key.
     signer_slot_id: SignerSlotID,
     /// The reward cycle of the connecting signer
     reward_cycle: u64,
     /// The stacker-db transaction msg session for the NEXT reward cycle
     next_transaction_session: StackerDBSession,
+    /// The backoff policy used when retrying stacker-db reads
+    retry_config: RetryConfig,
+    /// The backoff policy used when retrying stacker-db writes and the slot version refreshes
+    /// between them
+    write_retry_config: RetryConfig,
+    /// The signer ID to announce in slot update notifications. Notifications are not sent if unset.
+    signer_id: Option<u32>,
+    /// The last time a slot update notification was sent for each signer ID
+    last_slot_update_sent: HashMap<u32, Instant>,
+    /// Messages that exhausted their retries, in the order they must be delivered
+    outbound_queue: VecDeque<QueuedMessage>,
+    /// The size in bytes above which written messages are compressed. Disabled if None.
+    chunk_compression_threshold: Option<usize>,
+    /// The size in bytes above which written messages are split into fragments
+    max_chunk_size: usize,
+    /// The owners of the signers stacker-db slots, which chunks read back must be signed by
+    slot_owners: SignerSlotOwners,
+    /// The last chunk delivered from each signer's slot for each message ID
+    delivered_chunks: HashMap<MessageSlotID, HashMap<u32, DeliveredChunk>>,
+    /// The number of chunks not delivered because they were already delivered by an earlier read
+    suppressed_duplicate_chunks: u64,
 }
 
 impl From<&SignerConfig> for StackerDB {
     fn from(config: &SignerConfig) -> Self {
-        Self::new(
+        let mut stackerdb = Self::new_with_slot_layout(
             &config.node_host,
             config.stacks_private_key,
-            config.mainnet,
+            config.network.mainnet,
             config.reward_cycle,
             config.signer_slot_id,
-        )
+            &config.slot_layout,
+        );
+        stackerdb.retry_config = config.retry_config;
+        stackerdb.write_retry_config = config.stackerdb_write_retry_config;
+        stackerdb.signer_id = Some(config.signer_id);
+        stackerdb.set_node_transport(config.node_transport);
+        stackerdb.set_max_response_bytes(config.response_limits.stackerdb);
+        stackerdb.chunk_compression_threshold = config.chunk_compression_threshold;
+        stackerdb.slot_owners = config.slot_owners.clone();
+        stackerdb
     }
 }
 impl StackerDB {
-    /// Create a new StackerDB client
+    /// Create a new StackerDB client using the default slot layout
     pub fn new(
         host: &str,
         stacks_private_key: StacksPrivateKey,
         is_mainnet: bool,
         reward_cycle: u64,
         signer_slot_id: SignerSlotID,
+    ) -> Self {
+        Self::new_with_slot_layout(
+            host,
+            stacks_private_key,
+            is_mainnet,
+            reward_cycle,
+            signer_slot_id,
+            &SlotLayout::default(),
+        )
+    }
+
+    /// Create a new StackerDB client that writes and reads each message type to and from the
+    /// slot given by `slot_layout`. Message types the layout does not map get no session.
+    pub fn new_with_slot_layout(
+        host: &str,
+        stacks_private_key: StacksPrivateKey,
+        is_mainnet: bool,
+        reward_cycle: u64,
+        signer_slot_id: SignerSlotID,
+        slot_layout: &SlotLayout,
     ) -> Self {
         let mut signers_message_stackerdb_sessions = HashMap::new();
         for msg_id in MessageSlotID::ALL {
-            signers_message_stackerdb_sessions.insert(
-                *msg_id,
-                StackerDBSession::new(host, msg_id.stacker_db_contract(is_mainnet, reward_cycle)),
-            );
+            let Some(contract_id) =
+                slot_layout.stacker_db_contract(msg_id, is_mainnet, reward_cycle)
+            else {
+                continue;
+            };
+            signers_message_stackerdb_sessions
+                .insert(*msg_id, StackerDBSession::new(host, contract_id));
         }
         let next_transaction_session = StackerDBSession::new(
             host,
-            MessageSlotID::Transactions
-                .stacker_db_contract(is_mainnet, reward_cycle.wrapping_add(1)),
+            slot_layout
+                .stacker_db_contract(
+                    &MessageSlotID::Transactions,
+                    is_mainnet,
+                    reward_cycle.wrapping_add(1),
+                )
+                .unwrap_or_else(|| {
+                    MessageSlotID::Transactions
+                        .stacker_db_contract(is_mainnet, reward_cycle.wrapping_add(1))
+                }),
         );
 
         Self {
             signers_message_stackerdb_sessions,
             stacks_private_key,
             slot_versions: HashMap::new(),
+            last_written_chunks: HashMap::new(),
             signer_slot_id,
             reward_cycle,
             next_transaction_session,
+            retry_config: RetryConfig::default(),
+            write_retry_config: RetryConfig::stackerdb_write_default(),
+            signer_id: None,
+            last_slot_update_sent: HashMap::new(),
+            outbound_queue: VecDeque::new(),
+            chunk_compression_threshold: Some(DEFAULT_CHUNK_COMPRESSION_THRESHOLD),
+            max_chunk_size: SIGNERS_STACKERDB_CHUNK_SIZE,
+            slot_owners: SignerSlotOwners::default(),
+            delivered_chunks: HashMap::new(),
+            suppressed_duplicate_chunks: 0,
+        }
+    }
+
+    /// Connect to the stacker-db replica at the given host from now on
+    pub fn set_node_host(&mut self, host: &str) {
+        for session in self.signers_message_stackerdb_sessions.values_mut() {
+            session.host = host.to_string();
+        }
+        self.next_transaction_session.host = host.to_string();
+    }
+
+    /// Use the given backoff policies when retrying reads from and writes to stacker-db
+    pub fn set_retry_configs(
+        &mut self,
+        retry_config: RetryConfig,
+        write_retry_config: RetryConfig,
+    ) {
+        self.retry_config = retry_config;
+        self.write_retry_config = write_retry_config;
+    }
+
+    /// Use the given transport for all connections to the stacker-db replica
+    pub fn set_node_transport(&mut self, transport: NodeTransport) {
+        for session in self.signers_message_stackerdb_sessions.values_mut() {
+            session.transport = transport;
         }
+        self.next_transaction_session.transport = transport;
     }
 
-    /// Sends messages to the .signers stacker-db with an exponential backoff retry
+    /// Limit the size of responses read from the stacker-db replica
+    pub fn set_max_response_bytes(&mut self, max_bytes: u64) {
+        for session in self.signers_message_stackerdb_sessions.values_mut() {
+            session.max_response_bytes = max_bytes;
+        }
+        self.next_transaction_session.max_response_bytes = max_bytes;
+    }
+
+    /// Sends messages to the .signers stacker-db with an exponential backoff retry.
+    /// Messages larger than the compression threshold are written compressed.
+    /// Messages that still exceed the maximum chunk size are written as successive fragments,
+    /// which only event observers can reassemble. Returns whether the node acked the last chunk
+    /// written, rejected a chunk, or never answered. Errors are only returned for messages
+    /// that could not be written at all.
     pub fn send_message_with_retry(
         &mut self,
         message: SignerMessage,
-    ) -> Result<StackerDBChunkAckData, ClientError> {
+    ) -> Result<SendOutcome, ClientError> {
         let msg_id = message.msg_id();
-        let message_bytes = message.serialize_to_vec();
-        self.send_message_bytes_with_retry(&msg_id, message_bytes)
+        let message_bytes =
+            encode_chunk(message.serialize_to_vec(), self.chunk_compression_threshold);
+        let chunks = fragment_chunk(message_bytes, self.max_chunk_size)?;
+        if chunks.len() > 1 {
+            debug!(
+                "Splitting message with ID {msg_id} into {} fragments",
+                chunks.len()
+            );
+        }
+        let mut outcome = None;
+        for chunk in chunks {
+            let chunk_outcome =
+                SendOutcome::from_write_result(self.send_message_bytes_with_retry(&msg_id, chunk))?;
+            if !matches!(chunk_outcome, SendOutcome::Acked(_)) {
+                return Ok(chunk_outcome);
+            }
+            outcome = Some(chunk_outcome);
+        }
+        let outcome = outcome.expect("infallible: a message is written as at least one chunk");
+        if !matches!(message, SignerMessage::StackerDbSlotUpdate { .. }) {
+            self.notify_slot_update(&msg_id);
+        }
+        Ok(outcome)
+    }
+
+    /// Queue a message to be sent again by `flush_outbound_queue`.
+    /// If the queue is full, its oldest message is dropped and returned.
+    pub fn queue_message(&mut self, queued: QueuedMessage) -> Option<QueuedMessage> {
+        let dropped = if self.outbound_queue.len() >= OUTBOUND_QUEUE_MAX_LEN {
+            self.outbound_queue.pop_front()
+        } else {
+            None
+        };
+        self.outbound_queue.push_back(queued);
+        dropped
+    }
+
+    /// Whether there are messages waiting to be sent again
+    pub fn has_queued_messages(&self) -> bool {
+        !self.outbound_queue.is_empty()
+    }
+
+    /// The number of messages waiting to be sent again
+    pub fn num_queued_messages(&self) -> usize {
+        self.outbound_queue.len()
+    }
+
+    /// Try to send the queued messages in order, dropping those that expired at `now`.
+    /// Stops at the first message that still cannot reach the node to preserve delivery order.
+    /// Returns the messages removed from the queue, whether they were delivered or dropped.
+    pub fn flush_outbound_queue(&mut self, now: u64) -> Vec<QueuedMessage> {
+        let mut removed = vec![];
+        while let Some(queued) = self.outbound_queue.front().cloned() {
+            if queued.expires_at <= now {
+                debug!("Dropping expired queued message: {:?}", queued.message);
+            } else {
+                match self.send_message_with_retry(queued.message.clone()) {
+                    Ok(SendOutcome::Acked(ack)) => debug!("Delivered queued message: {ack:?}"),
+                    Ok(SendOutcome::Unknown) => {
+                        warn!(
+                            "Stacks node is still unreachable. {} message(s) remain queued",
+                            self.outbound_queue.len()
+                        );
+                        break;
+                    }
+                    Ok(SendOutcome::Rejected(reason)) => {
+                        warn!("Dropping queued message rejected by stacker-db: {reason}")
+                    }
+                    Err(e) => warn!("Dropping queued message that cannot be written: {e:?}"),
+                }
+            }
+            removed.extend(self.outbound_queue.pop_front());
+        }
+        removed
+    }
+
+    /// Overwrite the chunks this signer left in stackerdb so that they are not mistaken for
+    /// current messages once its tenure has ended: the transactions slot is overwritten with an
+    /// empty transactions message and the packet slots we wrote to with empty chunks.
+    /// Each write is retried for at most `deadline`, and clearing stops at the first failed write.
+    pub fn clear_slots(&mut self, deadline: Duration) -> Result<(), ClientError> {
+        let write_retry_config = self.write_retry_config;
+        self.write_retry_config = write_retry_config.with_deadline(deadline);
+        let result = self.write_empty_chunks();
+        self.write_retry_config = write_retry_config;
+        result
+    }
+
+    /// Write an empty chunk over each of the slots cleared by `clear_slots`
+    fn write_empty_chunks(&mut self) -> Result<(), ClientError> {
+        let empty_transactions = encode_chunk(
+            SignerMessage::Transactions(vec![]).serialize_to_vec(),
+            self.chunk_compression_threshold,
+        );
+        self.send_message_bytes_with_retry(&MessageSlotID::Transactions, empty_transactions)?;
+        for msg_id in PACKET_SLOT_IDS {
+            if !self.last_written_chunks.contains_key(msg_id) {
+                continue;
+            }
+            self.send_message_bytes_with_retry(msg_id, vec![])?;
+        }
+        Ok(())
+    }
+
+    /// Notify the other signers that we wrote a new chunk to the given message slot
+    fn notify_slot_update(&mut self, msg_id: &MessageSlotID) {
+        let Some(signer_id) = self.signer_id else {
+            return;
+        };
+        if !self
+            .signers_message_stackerdb_sessions
+            .contains_key(&MessageSlotID::StackerDbSlotUpdate)
+        {
+            debug!("No stacker-db slot is mapped for slot update notifications. Not notifying.");
+            return;
+        }
+        if !self.should_notify_slot_update(signer_id, Instant::now()) {
+            debug!("Rate limiting slot update notification for signer {signer_id}");
+            return;
+        }
+        // The stored version is always one past the version of the chunk we just wrote
+        let slot_version = self
+            .slot_versions
+            .get(&msg_id.storage_slot())
+            .and_then(|versions| versions.get(&self.signer_slot_id))
+            .map(|version| version.saturating_sub(1))
+            .unwrap_or(0);
+        let notification = SignerMessage::StackerDbSlotUpdate {
+            signer_id,
+            slot_version,
+        };
+        match self.send_message_with_retry(notification) {
+            Ok(SendOutcome::Acked(_)) => {}
+            Ok(outcome) => {
+                warn!("Failed to send slot update notification for signer {signer_id}: {outcome:?}")
+            }
+            Err(e) => {
+                warn!("Failed to send slot update notification for signer {signer_id}: {e:?}")
+            }
+        }
+    }
+
+    /// Check whether a slot update notification for the given signer may be sent at `now`,
+    /// recording it as sent if so. At most one notification is sent per `SLOT_UPDATE_MIN_INTERVAL`.
+    fn should_notify_slot_update(&mut self, signer_id: u32, now: Instant) -> bool {
+        if let Some(last_sent) = self.last_slot_update_sent.get(&signer_id) {
+            if now.saturating_duration_since(*last_sent) < SLOT_UPDATE_MIN_INTERVAL {
+                return false;
+            }
+        }
+        self.last_slot_update_sent.insert(signer_id, now);
+        true
     }
 
     /// Sends message (as a raw msg ID and bytes) to the .signers stacker-db with an
-    /// exponential backoff retry
+    /// exponential backoff retry. Slot versions are tracked per storage slot, since message types
+    /// stored in the same slot share its versions.
+    /// If the node rejects the chunk for a stale slot version, the chunk is re-signed with a
+    /// version past the node's and sent again, up to `SLOT_VERSION_CONFLICT_MAX_ATTEMPTS` times.
     pub fn send_message_bytes_with_retry(
         &mut self,
         msg_id: &MessageSlotID,
         message_bytes: Vec<u8>,
     ) -> Result<StackerDBChunkAckData, ClientError> {
         let slot_id = self.signer_slot_id;
+        let storage_slot = msg_id.storage_slot();
+        let mut slot_version = self
+            .slot_versions
+            .get(&storage_slot)
+            .and_then(|versions| versions.get(&slot_id))
+            .copied()
+            .unwrap_or(1);
+        let mut conflicts = 0;
         loop {
-            let mut slot_version = if let Some(versions) = self.slot_versions.get_mut(msg_id) {
-                if let Some(version) = versions.get(&slot_id) {
-                    *version
-                } else {
-                    versions.insert(slot_id, 0);
-                    1
-                }
-            } else {
-                let mut versions = HashMap::new();
-                versions.insert(slot_id, 0);
-                self.slot_versions.insert(*msg_id, versions);
-                1
-            };
-
             let mut chunk = StackerDBChunkData::new(slot_id.0, slot_version, message_bytes.clone());
             chunk.sign(&self.stacks_private_key)?;
 
             let Some(session) = self.signers_message_stackerdb_sessions.get_mut(msg_id) else {
-                panic!("FATAL: would loop forever trying to send a message with ID {}, for which we don't have a session", msg_id);
+                return Err(ClientError::UnmappedMessageSlot(*msg_id));
             };
 
             debug!(
@@ -146,80 +515,242 @@ impl StackerDB {
                 &session.stackerdb_contract_id
             );
 
-            let send_request = || session.put_chunk(&chunk).map_err(backoff::Error::transient);
-            let chunk_ack: StackerDBChunkAckData = retry_with_exponential_backoff(send_request)?;
-
-            if let Some(versions) = self.slot_versions.get_mut(msg_id) {
-                // NOTE: per the above, this is always executed
-                versions.insert(slot_id, slot_version.saturating_add(1));
-            } else {
-                return Err(ClientError::NotConnected);
+            let send_request = || session.put_chunk(&chunk).map_err(backoff_rpc_error);
+            let timer = crate::monitoring::new_stackerdb_send_timer(msg_id);
+            let send_result =
+                retry_with_exponential_backoff(&self.write_retry_config, send_request);
+            timer.stop_and_record();
+            if send_result.is_err() {
+                crate::monitoring::increment_stackerdb_acks(msg_id, StackerDBAckOutcome::Failure);
             }
+            let chunk_ack: StackerDBChunkAckData = send_result?;
 
             if chunk_ack.accepted {
                 debug!("Chunk accepted by stackerdb: {chunk_ack:?}");
+                crate::monitoring::increment_stackerdb_acks(msg_id, StackerDBAckOutcome::Success);
+                crate::monitoring::update_stackerdb_last_chunk_size(msg_id, chunk.data.len());
+                self.record_written_chunk(msg_id, slot_version, chunk.data_hash());
                 return Ok(chunk_ack);
+            }
+            warn!("Chunk rejected by stackerdb: {chunk_ack:?}");
+            let is_version_conflict = chunk_ack.code.and_then(StackerDBErrorCodes::from_code)
+                == Some(StackerDBErrorCodes::DataAlreadyExists);
+            if is_version_conflict {
+                crate::monitoring::increment_stackerdb_acks(
+                    msg_id,
+                    StackerDBAckOutcome::VersionConflict,
+                );
             } else {
-                warn!("Chunk rejected by stackerdb: {chunk_ack:?}");
+                crate::monitoring::increment_stackerdb_acks(msg_id, StackerDBAckOutcome::Failure);
+                return Err(ClientError::PutChunkRejected(
+                    chunk_ack
+                        .reason
+                        .unwrap_or_else(|| "No reason given".to_string()),
+                ));
             }
-            if let Some(code) = chunk_ack.code {
-                match StackerDBErrorCodes::from_code(code) {
-                    Some(StackerDBErrorCodes::DataAlreadyExists) => {
-                        if let Some(slot_metadata) = chunk_ack.metadata {
-                            warn!("Failed to send message to stackerdb due to wrong version number. Attempted {}. Expected {}. Retrying...", slot_version, slot_metadata.slot_version);
-                            slot_version = slot_metadata.slot_version;
-                        } else {
-                            warn!("Failed to send message to stackerdb due to wrong version number. Attempted {}. Expected unknown version number. Incrementing and retrying...", slot_version);
-                        }
-                        if let Some(versions) = self.slot_versions.get_mut(msg_id) {
-                            // NOTE: per the above, this is always executed
-                            versions.insert(slot_id, slot_version.saturating_add(1));
-                        } else {
-                            return Err(ClientError::NotConnected);
-                        }
-                    }
-                    _ => {
-                        warn!("Failed to send message to stackerdb: {:?}", chunk_ack);
-                        return Err(ClientError::PutChunkRejected(
-                            chunk_ack
-                                .reason
-                                .unwrap_or_else(|| "No reason given".to_string()),
-                        ));
-                    }
+
+            let slot_metadata = match chunk_ack.metadata {
+                Some(slot_metadata) => Some(slot_metadata),
+                None => self.get_slot_metadata(msg_id)?,
+            };
+            let node_version = if let Some(slot_metadata) = slot_metadata {
+                if slot_metadata.slot_version == slot_version
+                    && slot_metadata.data_hash == chunk.data_hash()
+                {
+                    // An earlier attempt was stored, but its ack was lost
+                    debug!("Chunk with version {slot_version} is already stored by stackerdb");
+                    crate::monitoring::update_stackerdb_last_chunk_size(msg_id, chunk.data.len());
+                    self.record_written_chunk(msg_id, slot_version, slot_metadata.data_hash);
+                    return Ok(StackerDBChunkAckData {
+                        accepted: true,
+                        reason: None,
+                        metadata: Some(slot_metadata),
+                        code: None,
+                    });
+                }
+                if self.is_foreign_write(msg_id, &slot_metadata) {
+                    error!(
+                        "Stackerdb slot {slot_id} for message ID {msg_id} was overwritten by another writer at version {}. Is our signing key in use elsewhere?",
+                        slot_metadata.slot_version
+                    );
+                    return Err(ClientError::ForeignSlotWrite(
+                        slot_id.0,
+                        slot_metadata.slot_version,
+                    ));
                 }
+                slot_metadata.slot_version
+            } else {
+                slot_version
+            };
+            slot_version = node_version.saturating_add(1);
+            self.slot_versions
+                .entry(storage_slot)
+                .or_default()
+                .insert(slot_id, slot_version);
+
+            conflicts += 1;
+            if conflicts >= SLOT_VERSION_CONFLICT_MAX_ATTEMPTS {
+                return Err(ClientError::SlotVersionConflict(conflicts));
             }
+            warn!("Failed to send message to stackerdb due to a stale slot version. Node has version {node_version}. Retrying with version {slot_version}...");
         }
     }
 
-    /// Get all signer messages from stackerdb for the given slot IDs
-    fn get_messages(
+    /// Remember the version and content of the last chunk we wrote to the storage slot of `msg_id`
+    fn record_written_chunk(
+        &mut self,
+        msg_id: &MessageSlotID,
+        slot_version: u32,
+        data_hash: Sha512Trunc256Sum,
+    ) {
+        let storage_slot = msg_id.storage_slot();
+        self.slot_versions
+            .entry(storage_slot)
+            .or_default()
+            .insert(self.signer_slot_id, slot_version.saturating_add(1));
+        self.last_written_chunks
+            .insert(storage_slot, (slot_version, data_hash));
+    }
+
+    /// Whether the node's copy of our slot was written by someone else since our last write.
+    /// We are the only legitimate writer of our slot, so any newer version, or different content
+    /// at the version we wrote, did not come from us.
+    /// Without a previous write (e.g. right after a restart), newer versions are expected.
+    fn is_foreign_write(&self, msg_id: &MessageSlotID, slot_metadata: &SlotMetadata) -> bool {
+        let Some((written_version, written_hash)) =
+            self.last_written_chunks.get(&msg_id.storage_slot())
+        else {
+            return false;
+        };
+        slot_metadata.slot_version > *written_version
+            || (slot_metadata.slot_version == *written_version
+                && slot_metadata.data_hash != *written_hash)
+    }
+
+    /// Fetch the node's current metadata of our slot for the given message ID
+    fn get_slot_metadata(
+        &mut self,
+        msg_id: &MessageSlotID,
+    ) -> Result<Option<SlotMetadata>, ClientError> {
+        let slot_id = self.signer_slot_id;
+        let Some(session) = self.signers_message_stackerdb_sessions.get_mut(msg_id) else {
+            return Err(ClientError::UnmappedMessageSlot(*msg_id));
+        };
+        let send_request = || session.list_chunks().map_err(backoff_rpc_error);
+        let slots_metadata =
+            retry_with_exponential_backoff(&self.write_retry_config, send_request)?;
+        Ok(slots_metadata
+            .into_iter()
+            .find(|slot_metadata| slot_metadata.slot_id == slot_id.0))
+    }
+
+    /// Get the latest chunks from stackerdb for the given slot IDs.
+    /// If the owners of the signer set's slots are known, chunks not signed by their slot's
+    /// owner are dropped.
+    fn get_latest_slot_chunks(
         session: &mut StackerDBSession,
         slot_ids: &[u32],
-    ) -> Result<Vec<SignerMessage>, ClientError> {
-        let mut messages = vec![];
+        retry_config: &RetryConfig,
+        slot_owners: &SignerSlotOwners,
+        signer_set: u32,
+    ) -> Result<Vec<SlotChunk>, ClientError> {
+        if slot_owners.knows_signer_set(signer_set) {
+            let send_request = || {
+                session
+                    .get_latest_signed_chunks(slot_ids)
+                    .map_err(backoff_rpc_error)
+            };
+            let chunks = retry_with_exponential_backoff(retry_config, send_request)?
+                .into_iter()
+                .flatten()
+                .collect();
+            return Ok(slot_owners
+                .filter_chunks(signer_set, chunks)
+                .into_iter()
+                .map(|chunk| SlotChunk {
+                    slot_id: chunk.slot_id,
+                    slot_version: Some(chunk.slot_version),
+                    data: chunk.data,
+                })
+                .collect());
+        }
         let send_request = || {
             session
                 .get_latest_chunks(slot_ids)
-                .map_err(backoff::Error::transient)
+                .map_err(backoff_rpc_error)
         };
-        let chunk_ack = retry_with_exponential_backoff(send_request)?;
-        for (i, chunk) in chunk_ack.iter().enumerate() {
-            let Some(data) = chunk else {
+        let chunks = retry_with_exponential_backoff(retry_config, send_request)?;
+        Ok(slot_ids
+            .iter()
+            .zip(chunks)
+            .filter_map(|(slot_id, data)| {
+                Some(SlotChunk {
+                    slot_id: *slot_id,
+                    slot_version: None,
+                    data: data?,
+                })
+            })
+            .collect())
+    }
+
+    /// Decode the signer messages carried by the given chunks, skipping undecodable chunks
+    fn decode_messages(chunks: &[SlotChunk]) -> Vec<SignerMessage> {
+        let mut messages = vec![];
+        for SlotChunk { slot_id, data, .. } in chunks {
+            if ChunkEnvelope::of(data) == ChunkEnvelope::Fragment {
+                // Only the last fragment of a message remains in its slot
+                debug!(
+                    "slot #{slot_id}: Skipping fragment of a message too large for a single chunk"
+                );
                 continue;
-            };
-            let Ok(message) = read_next::<SignerMessage, _>(&mut &data[..]) else {
+            }
+            let Ok(message) = decode_chunk::<SignerMessage>(data) else {
                 if !data.is_empty() {
                     warn!("Failed to deserialize chunk data into a SignerMessage");
-                    debug!("slot #{i}: Failed chunk ({}): {data:?}", &data.len(),);
+                    debug!("slot #{slot_id}: Failed chunk ({}): {data:?}", &data.len(),);
                 }
                 continue;
             };
             messages.push(message);
         }
-        Ok(messages)
+        messages
+    }
+
+    /// Drop the chunks already delivered from their slot for the given message ID,
+    /// and remember the remaining ones as delivered
+    fn retain_undelivered_chunks(
+        &mut self,
+        msg_id: &MessageSlotID,
+        mut chunks: Vec<SlotChunk>,
+    ) -> Vec<SlotChunk> {
+        let delivered_chunks = self.delivered_chunks.entry(*msg_id).or_default();
+        let num_chunks = chunks.len();
+        chunks.retain(|chunk| {
+            let delivered_chunk = DeliveredChunk::from(chunk);
+            delivered_chunks.insert(chunk.slot_id, delivered_chunk) != Some(delivered_chunk)
+        });
+        let suppressed = num_chunks - chunks.len();
+        if suppressed > 0 {
+            debug!(
+                "Suppressed {suppressed} chunks of {msg_id} already delivered by an earlier read"
+            );
+            self.suppressed_duplicate_chunks = self
+                .suppressed_duplicate_chunks
+                .saturating_add(suppressed as u64);
+            for _ in 0..suppressed {
+                crate::monitoring::increment_suppressed_duplicate_chunks();
+            }
+        }
+        chunks
+    }
+
+    /// The number of chunks not delivered because they were already delivered by an earlier read
+    pub fn suppressed_duplicate_chunks(&self) -> u64 {
+        self.suppressed_duplicate_chunks
     }
 
     /// Get the ordered DKG packets from stackerdb for the signer slot IDs.
+    /// Packets whose chunk was already delivered by an earlier call are skipped.
     pub fn get_dkg_packets(
         &mut self,
         signer_ids: &[SignerSlotID],
@@ -233,14 +764,23 @@ impl StackerDB {
             MessageSlotID::DkgEnd,
         ];
         let slot_ids = signer_ids.iter().map(|id| id.0).collect::<Vec<_>>();
+        let signer_set = self.get_signer_set();
         let mut packets = vec![];
         for packet_slot in packet_slots {
             let session = self
                 .signers_message_stackerdb_sessions
                 .get_mut(packet_slot)
-                .ok_or(ClientError::NotConnected)?;
-            let messages = Self::get_messages(session, &slot_ids)?;
-            for message in messages {
+                .ok_or(ClientError::UnmappedMessageSlot(*packet_slot))?;
+            let chunks = Self::get_latest_slot_chunks(
+                session,
+                &slot_ids,
+                &self.retry_config,
+                &self.slot_owners,
+                signer_set,
+            )?;
+            crate::monitoring::increment_stackerdb_chunks_received(packet_slot, chunks.len());
+            let chunks = self.retain_undelivered_chunks(packet_slot, chunks);
+            for message in Self::decode_messages(&chunks) {
                 let SignerMessage::Packet(packet) = message else {
                     warn!("Found an unexpected type in a packet slot {packet_slot}");
                     continue;
@@ -255,11 +795,24 @@ impl StackerDB {
     fn get_transactions(
         transactions_session: &mut StackerDBSession,
         signer_ids: &[SignerSlotID],
+        retry_config: &RetryConfig,
+        slot_owners: &SignerSlotOwners,
+        signer_set: u32,
     ) -> Result<Vec<StacksTransaction>, ClientError> {
         let slot_ids = signer_ids.iter().map(|id| id.0).collect::<Vec<_>>();
-        let messages = Self::get_messages(transactions_session, &slot_ids)?;
+        let chunks = Self::get_latest_slot_chunks(
+            transactions_session,
+            &slot_ids,
+            retry_config,
+            slot_owners,
+            signer_set,
+        )?;
+        crate::monitoring::increment_stackerdb_chunks_received(
+            &MessageSlotID::Transactions,
+            chunks.len(),
+        );
         let mut transactions = vec![];
-        for message in messages {
+        for message in Self::decode_messages(&chunks) {
             let SignerMessage::Transactions(chunk_transactions) = message else {
                 warn!("Signer wrote an unexpected type to the transactions slot");
                 continue;
@@ -271,13 +824,22 @@ impl StackerDB {
 
     /// Get this signer's latest transactions from stackerdb
     pub fn get_current_transactions(&mut self) -> Result<Vec<StacksTransaction>, ClientError> {
+        let signer_set = self.get_signer_set();
         let Some(transactions_session) = self
             .signers_message_stackerdb_sessions
             .get_mut(&MessageSlotID::Transactions)
         else {
-            return Err(ClientError::NotConnected);
+            return Err(ClientError::UnmappedMessageSlot(
+                MessageSlotID::Transactions,
+            ));
         };
-        Self::get_transactions(transactions_session, &[self.signer_slot_id])
+        Self::get_transactions(
+            transactions_session,
+            &[self.signer_slot_id],
+            &self.retry_config,
+            &self.slot_owners,
+            signer_set,
+        )
     }
 
     /// Get the latest signer transactions from signer ids for the next reward cycle
@@ -286,7 +848,15 @@ impl StackerDB {
         signer_ids: &[SignerSlotID],
     ) -> Result<Vec<StacksTransaction>, ClientError> {
         debug!("Getting latest chunks from stackerdb for the following signers: {signer_ids:?}",);
-        Self::get_transactions(&mut self.next_transaction_session, signer_ids)
+        let next_signer_set = u32::try_from(self.reward_cycle.wrapping_add(1) % 2)
+            .expect("FATAL: reward cycle % 2 exceeds u32::MAX");
+        Self::get_transactions(
+            &mut self.next_transaction_session,
+            signer_ids,
+            &self.retry_config,
+            &self.slot_owners,
+            next_signer_set,
+        )
     }
 
     /// Get the encrypted state for the given signer
@@ -299,21 +869,23 @@ impl StackerDB {
             .signers_message_stackerdb_sessions
             .get_mut(&MessageSlotID::EncryptedSignerState)
         else {
-            return Err(ClientError::NotConnected);
+            return Err(ClientError::UnmappedMessageSlot(
+                MessageSlotID::EncryptedSignerState,
+            ));
         };
 
         let send_request = || {
             state_session
                 .get_latest_chunks(&[signer_id.0])
-                .map_err(backoff::Error::transient)
+                .map_err(backoff_rpc_error)
         };
 
-        let Some(chunk) = retry_with_exponential_backoff(send_request)?.pop().ok_or(
-            ClientError::UnexpectedResponseFormat(format!(
+        let Some(chunk) = retry_with_exponential_backoff(&self.retry_config, send_request)?
+            .pop()
+            .ok_or(ClientError::UnexpectedResponseFormat(format!(
                 "Missing response for state session request for signer {}",
                 signer_id
-            )),
-        )?
+            )))?
         else {
             debug!("No persisted state for signer {signer_id}");
             return Ok(None);
@@ -324,8 +896,7 @@ impl StackerDB {
             return Ok(None);
         }
 
-        let SignerMessage::EncryptedSignerState(state) =
-            read_next::<SignerMessage, _>(&mut chunk.as_slice())?
+        let SignerMessage::EncryptedSignerState(state) = decode_chunk::<SignerMessage>(&chunk)?
         else {
             error!("Wrong message type stored in signer state slot for signer {signer_id}");
             return Ok(None);
@@ -340,24 +911,40 @@ impl StackerDB {
     }
 
     /// Retrieve the signer slot ID
-    pub fn get_signer_slot_id(&mut self) -> SignerSlotID {
+    pub fn get_signer_slot_id(&self) -> SignerSlotID {
         self.signer_slot_id
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicU64, Ordering};
     use std::thread::spawn;
     use std::time::Duration;
 
+    use blockstack_lib::chainstate::nakamoto::signer_set::NakamotoSigners;
     use blockstack_lib::chainstate::stacks::{
         TransactionAnchorMode, TransactionAuth, TransactionPayload, TransactionPostConditionMode,
         TransactionSmartContract, TransactionVersion,
     };
     use blockstack_lib::util_lib::strings::StacksString;
+    use libsigner::v1::messages::{
+        BlockRejection, BlockResponse, CoordinatorMetadata, NackMessage, NackReason, RejectCode,
+    };
+    use libsigner::{Fragment, FragmentBuffer};
+    use stacks_common::codec::read_next;
+    use stacks_common::types::chainstate::{ConsensusHash, StacksAddress, StacksPublicKey};
+    use wsts::curve::point::Point;
+    use wsts::net::{DkgBegin, Message};
 
     use super::*;
-    use crate::client::tests::{generate_signer_config, mock_server_from_config, write_response};
+    use crate::client::tests::{
+        generate_signer_config, mock_server_from_config, mock_server_random,
+        mock_tls_server_config, write_response, write_tls_response,
+    };
     use crate::config::GlobalConfig;
 
     #[test]
@@ -403,6 +990,276 @@ mod tests {
         assert_eq!(transactions, vec![tx]);
     }
 
+    /// A transaction large enough that a message carrying it gets compressed
+    fn large_transaction() -> StacksTransaction {
+        let sk = StacksPrivateKey::new();
+        let code_body = "(/ 1 0)".repeat(DEFAULT_CHUNK_COMPRESSION_THRESHOLD / 4);
+        StacksTransaction {
+            version: TransactionVersion::Testnet,
+            chain_id: 0,
+            auth: TransactionAuth::from_p2pkh(&sk).unwrap(),
+            anchor_mode: TransactionAnchorMode::Any,
+            post_condition_mode: TransactionPostConditionMode::Allow,
+            post_conditions: vec![],
+            payload: TransactionPayload::SmartContract(
+                TransactionSmartContract {
+                    name: "test-contract".into(),
+                    code_body: StacksString::from_str(&code_body).unwrap(),
+                },
+                None,
+            ),
+        }
+    }
+
+    #[test]
+    fn get_signer_transactions_should_decode_mixed_chunks() {
+        let (mut stackerdb, mock_server) = mock_stackerdb();
+        let raw_tx = large_transaction();
+        let compressed_tx = large_transaction();
+
+        let h = spawn(move || stackerdb.get_next_transactions(&[SignerSlotID(0), SignerSlotID(1)]));
+        // A signer without compression support writes its transactions raw
+        let raw_chunk = encode_chunk(
+            SignerMessage::Transactions(vec![raw_tx.clone()]).serialize_to_vec(),
+            None,
+        );
+        let compressed_chunk = encode_chunk(
+            SignerMessage::Transactions(vec![compressed_tx.clone()]).serialize_to_vec(),
+            Some(DEFAULT_CHUNK_COMPRESSION_THRESHOLD),
+        );
+        assert_eq!(ChunkEnvelope::of(&raw_chunk), ChunkEnvelope::Raw);
+        assert_eq!(ChunkEnvelope::of(&compressed_chunk), ChunkEnvelope::Zstd);
+        for chunk in [raw_chunk, compressed_chunk] {
+            let mut response_bytes = b"HTTP/1.1 200 OK\n\n".to_vec();
+            response_bytes.extend(chunk);
+            serve_response(&mock_server, &response_bytes);
+        }
+
+        let transactions = h.join().unwrap().unwrap();
+        assert_eq!(transactions, vec![raw_tx, compressed_tx]);
+    }
+
+    #[test]
+    fn unchanged_chunks_should_not_be_delivered_twice() {
+        let (mut stackerdb, mock_server) = mock_stackerdb();
+        let packet = Packet {
+            msg: Message::DkgBegin(DkgBegin { dkg_id: 1 }),
+            sig: vec![],
+        };
+        let mut packet_response = b"HTTP/1.1 200 OK\n\n".to_vec();
+        packet_response.extend(SignerMessage::Packet(packet.clone()).serialize_to_vec());
+
+        let mut delivered = vec![];
+        for _ in 0..2 {
+            let h = spawn(move || {
+                let packets = stackerdb.get_dkg_packets(&[SignerSlotID(0)]);
+                (stackerdb, packets)
+            });
+            // Only the DKG begin slot holds a chunk
+            serve_response(&mock_server, &packet_response);
+            for _ in 0..5 {
+                serve_response(&mock_server, b"HTTP/1.1 404 Not Found\n\n");
+            }
+            let (returned_stackerdb, packets) = h.join().unwrap();
+            stackerdb = returned_stackerdb;
+            delivered.push(packets.unwrap());
+        }
+        assert_eq!(delivered, vec![vec![packet], vec![]]);
+        assert_eq!(stackerdb.suppressed_duplicate_chunks(), 1);
+    }
+
+    #[test]
+    fn rewritten_chunk_with_identical_bytes_should_be_delivered() {
+        let (mut stackerdb, _mock_server) = mock_stackerdb();
+        let msg_id = MessageSlotID::DkgBegin;
+        let chunk = |slot_version| SlotChunk {
+            slot_id: 0,
+            slot_version: Some(slot_version),
+            data: vec![1, 2, 3],
+        };
+        assert_eq!(
+            stackerdb.retain_undelivered_chunks(&msg_id, vec![chunk(1)]),
+            vec![chunk(1)]
+        );
+        assert!(stackerdb
+            .retain_undelivered_chunks(&msg_id, vec![chunk(1)])
+            .is_empty());
+        assert_eq!(
+            stackerdb.retain_undelivered_chunks(&msg_id, vec![chunk(2)]),
+            vec![chunk(2)]
+        );
+        assert_eq!(stackerdb.suppressed_duplicate_chunks(), 1);
+    }
+
+    static REJECTED_CHUNKS: AtomicU64 = AtomicU64::new(0);
+
+    #[test]
+    fn chunk_signed_by_wrong_key_should_be_rejected() {
+        let (mut stackerdb, mock_server) = mock_stackerdb();
+        let owner_keys = [StacksPrivateKey::new(), StacksPrivateKey::new()];
+        let next_signer_set = u32::try_from((stackerdb.reward_cycle + 1) % 2).unwrap();
+        stackerdb.slot_owners = SignerSlotOwners::default().with_rejected_chunk_callback(|| {
+            REJECTED_CHUNKS.fetch_add(1, Ordering::SeqCst);
+        });
+        stackerdb.slot_owners.set_signer_set(
+            next_signer_set,
+            owner_keys
+                .iter()
+                .enumerate()
+                .map(|(slot_id, sk)| {
+                    (
+                        slot_id as u32,
+                        StacksAddress::p2pkh(false, &StacksPublicKey::from_private(sk)),
+                    )
+                })
+                .collect(),
+        );
+        let owner_tx = large_transaction();
+        let forged_tx = large_transaction();
+        let mut owner_chunk = StackerDBChunkData::new(
+            0,
+            1,
+            SignerMessage::Transactions(vec![owner_tx.clone()]).serialize_to_vec(),
+        );
+        owner_chunk.sign(&owner_keys[0]).unwrap();
+        // Slot 1 holds content the node substituted, signed by someone other than its owner
+        let mut forged_chunk = StackerDBChunkData::new(
+            1,
+            3,
+            SignerMessage::Transactions(vec![forged_tx]).serialize_to_vec(),
+        );
+        forged_chunk.sign(&StacksPrivateKey::new()).unwrap();
+
+        let h = spawn(move || stackerdb.get_next_transactions(&[SignerSlotID(0), SignerSlotID(1)]));
+        let slots_metadata = vec![
+            owner_chunk.get_slot_metadata(),
+            forged_chunk.get_slot_metadata(),
+        ];
+        serve_response(&mock_server, &build_json_response(&slots_metadata));
+        for chunk in [owner_chunk, forged_chunk] {
+            let mut response_bytes = b"HTTP/1.1 200 OK\n\n".to_vec();
+            response_bytes.extend(chunk.data);
+            serve_response(&mock_server, &response_bytes);
+        }
+
+        let transactions = h.join().unwrap().unwrap();
+        assert_eq!(transactions, vec![owner_tx]);
+        assert_eq!(REJECTED_CHUNKS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn large_message_should_be_written_compressed() {
+        let ack = StackerDBChunkAckData {
+            accepted: true,
+            reason: None,
+            metadata: None,
+            code: None,
+        };
+        let message = SignerMessage::Transactions(vec![large_transaction()]);
+        for compression_threshold in [Some(DEFAULT_CHUNK_COMPRESSION_THRESHOLD), None] {
+            let (mut stackerdb, mock_server) = mock_stackerdb();
+            stackerdb.signer_id = None;
+            stackerdb.chunk_compression_threshold = compression_threshold;
+
+            let thread_message = message.clone();
+            let h = spawn(move || stackerdb.send_message_with_retry(thread_message));
+            let chunk =
+                serve_response(&mock_server, &build_json_response(&ack)).expect("Expected a chunk");
+            assert_eq!(h.join().unwrap().unwrap(), SendOutcome::Acked(ack.clone()));
+
+            let expected_envelope = if compression_threshold.is_some() {
+                ChunkEnvelope::Zstd
+            } else {
+                ChunkEnvelope::Raw
+            };
+            assert_eq!(ChunkEnvelope::of(&chunk.data), expected_envelope);
+            assert_eq!(decode_chunk::<SignerMessage>(&chunk.data).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn oversized_message_should_be_written_in_fragments() {
+        let ack = StackerDBChunkAckData {
+            accepted: true,
+            reason: None,
+            metadata: None,
+            code: None,
+        };
+        let message = SignerMessage::Transactions(vec![large_transaction()]);
+        let message_len = message.serialize_to_vec().len();
+        let (mut stackerdb, mock_server) = mock_stackerdb();
+        stackerdb.signer_id = None;
+        stackerdb.chunk_compression_threshold = None;
+        stackerdb.max_chunk_size = message_len / 3;
+
+        let thread_message = message.clone();
+        let h = spawn(move || stackerdb.send_message_with_retry(thread_message));
+        let mut chunks = vec![];
+        loop {
+            let chunk =
+                serve_response(&mock_server, &build_json_response(&ack)).expect("Expected a chunk");
+            let fragment =
+                read_next::<Fragment, _>(&mut &chunk.data[..]).expect("Expected a fragment");
+            chunks.push(chunk);
+            if fragment.index + 1 == fragment.total {
+                break;
+            }
+        }
+        assert_eq!(h.join().unwrap().unwrap(), SendOutcome::Acked(ack));
+        assert!(chunks.len() > 3);
+        for (version, chunk) in chunks.iter().enumerate() {
+            assert!(chunk.data.len() <= message_len / 3);
+            assert_eq!(ChunkEnvelope::of(&chunk.data), ChunkEnvelope::Fragment);
+            // Each fragment replaces the previous one in our slot
+            assert_eq!(chunk.slot_version, version as u32 + 1);
+        }
+
+        // Simulate the node delivering the fragments out of order and more than once
+        let mut delivered = chunks.clone();
+        delivered.reverse();
+        delivered.insert(1, chunks[chunks.len() - 1].clone());
+        let mut fragment_buffer = FragmentBuffer::default();
+        let now = Instant::now();
+        let (last, rest) = delivered.split_last().unwrap();
+        assert!(fragment_buffer
+            .reassemble_chunks(rest.to_vec(), now)
+            .is_empty());
+        let reassembled = fragment_buffer.reassemble_chunks(vec![last.clone()], now);
+        assert_eq!(reassembled.len(), 1);
+        assert_eq!(
+            decode_chunk::<SignerMessage>(&reassembled[0].data).unwrap(),
+            message
+        );
+        assert!(fragment_buffer.is_empty());
+    }
+
+    #[test]
+    fn tls_session_should_verify_certificate() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let server_config = mock_tls_server_config();
+        let message = SignerMessage::Transactions(vec![]).serialize_to_vec();
+        let mut response_bytes = b"HTTP/1.1 200 OK\n\n".to_vec();
+        response_bytes.extend(message);
+
+        // The self-signed certificate is only accepted if verification is skipped
+        for skip_verify in [true, false] {
+            let (mock_server, mock_server_addr) = mock_server_random();
+            let mut signer_config = generate_signer_config(&config, 5, 20);
+            signer_config.node_host = mock_server_addr.to_string();
+            signer_config.node_transport = NodeTransport::Tls { skip_verify };
+            let mut stackerdb = StackerDB::from(&signer_config);
+
+            let h = spawn(move || stackerdb.get_next_transactions(&[SignerSlotID(0)]));
+            write_tls_response(mock_server, server_config.clone(), &response_bytes);
+            let result = h.join().unwrap();
+            if skip_verify {
+                assert_eq!(result.unwrap(), vec![]);
+            } else {
+                assert!(matches!(result, Err(ClientError::TlsError(_))));
+            }
+        }
+    }
+
     #[test]
     fn send_signer_message_should_succeed() {
         let config = GlobalConfig::load_from_file("./src/tests/conf/signer-1.toml").unwrap();
@@ -440,6 +1297,579 @@ mod tests {
         response_bytes.extend(payload.as_bytes());
         std::thread::sleep(Duration::from_millis(500));
         write_response(mock_server, response_bytes.as_slice());
+        // The successful write is followed by a slot update notification
+        let mock_server = mock_server_from_config(&config);
+        write_response(mock_server, response_bytes.as_slice());
+        assert_eq!(SendOutcome::Acked(ack), h.join().unwrap().unwrap());
+    }
+
+    /// Build an HTTP response carrying the given JSON payload
+    fn build_json_response<T: serde::Serialize>(payload: &T) -> Vec<u8> {
+        let mut response_bytes = b"HTTP/1.1 200 OK\n\n".to_vec();
+        let payload = serde_json::to_string(payload).expect("Failed to serialize payload");
+        response_bytes.extend(payload.as_bytes());
+        response_bytes
+    }
+
+    /// Build the ack the node sends when a chunk's version is not newer than the stored one
+    fn build_stale_version_ack(slot_metadata: Option<SlotMetadata>) -> StackerDBChunkAckData {
+        let code = StackerDBErrorCodes::DataAlreadyExists;
+        StackerDBChunkAckData {
+            accepted: false,
+            reason: Some(code.reason().to_string()),
+            metadata: slot_metadata,
+            code: Some(code.code()),
+        }
+    }
+
+    #[test]
+    fn messages_should_be_routed_to_their_layout_slot() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        // Reverse the default layout so that no message type keeps its own offset
+        let max_offset = u32::try_from(MessageSlotID::ALL.len() - 1).unwrap();
+        signer_config.slot_layout = SlotLayout::new(
+            MessageSlotID::ALL
+                .iter()
+                .filter(|msg_id| msg_id.storage_slot() == **msg_id)
+                .map(|msg_id| (*msg_id, max_offset - msg_id.to_u32()))
+                .collect(),
+        )
+        .unwrap();
+        let stackerdb = StackerDB::from(&signer_config);
+
+        let messages = [
+            SignerMessage::Packet(Packet {
+                msg: Message::DkgBegin(DkgBegin { dkg_id: 1 }),
+                sig: vec![],
+            }),
+            SignerMessage::BlockResponse(BlockResponse::Rejected(BlockRejection::new(
+                Sha512Trunc256Sum([0; 32]),
+                RejectCode::ConnectivityIssues,
+            ))),
+            SignerMessage::Transactions(vec![]),
+            SignerMessage::DkgResults {
+                aggregate_key: Point::new(),
+                party_polynomials: vec![],
+            },
+            SignerMessage::EncryptedSignerState(vec![1, 2, 3]),
+            SignerMessage::StackerDbSlotUpdate {
+                signer_id: 0,
+                slot_version: 1,
+            },
+            SignerMessage::Nack(NackMessage {
+                signer_id: 0,
+                sign_id: 1,
+                sign_iter_id: 1,
+                coordinator_id: 2,
+                reason: NackReason::UnexpectedCoordinator,
+            }),
+            SignerMessage::NackReport {
+                sender_id: 0,
+                coordinator_metadata: CoordinatorMetadata {
+                    burn_block_height: 1,
+                    burn_header_hash: None,
+                    pox_consensus_hash: ConsensusHash([0; 20]),
+                },
+                nacked_signer_count: 1,
+            },
+            SignerMessage::DkgConflict {
+                sender_id: 0,
+                reward_cycle: 1,
+                previous_round: 1,
+                previous_key: Point::new(),
+                conflicting_round: 2,
+                conflicting_key: Point::new(),
+            },
+        ];
+        for message in messages {
+            let msg_id = message.msg_id();
+            let offset = signer_config.slot_layout.offset(&msg_id).unwrap();
+            let session = stackerdb
+                .signers_message_stackerdb_sessions
+                .get(&msg_id)
+                .expect("Missing session for a mapped message slot");
+            assert_eq!(
+                session.stackerdb_contract_id,
+                NakamotoSigners::make_signers_db_contract_id(
+                    signer_config.reward_cycle,
+                    offset,
+                    signer_config.network.mainnet
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn unmapped_message_should_not_be_written() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.slot_layout =
+            SlotLayout::new(BTreeMap::from([(MessageSlotID::BlockResponse, 0)])).unwrap();
+        let mut stackerdb = StackerDB::from(&signer_config);
+        assert_eq!(stackerdb.signers_message_stackerdb_sessions.len(), 1);
+
+        assert!(matches!(
+            stackerdb.send_message_with_retry(SignerMessage::Transactions(vec![])),
+            Err(ClientError::UnmappedMessageSlot(
+                MessageSlotID::Transactions
+            ))
+        ));
+        assert!(matches!(
+            stackerdb.get_current_transactions(),
+            Err(ClientError::UnmappedMessageSlot(
+                MessageSlotID::Transactions
+            ))
+        ));
+    }
+
+    #[test]
+    fn unanswered_write_should_have_unknown_outcome() {
+        let (mut stackerdb, mock_server) = mock_stackerdb();
+        stackerdb.signer_id = None;
+        stackerdb.write_retry_config = stackerdb
+            .write_retry_config
+            .with_deadline(Duration::from_millis(200));
+
+        let h =
+            spawn(move || stackerdb.send_message_with_retry(SignerMessage::Transactions(vec![])));
+        // The node receives every attempt, but its ack never makes it back
+        mock_server.set_nonblocking(true).unwrap();
+        let mut attempts = 0;
+        while !h.is_finished() {
+            match mock_server.accept() {
+                Ok((mut stream, _)) => {
+                    stream.set_nonblocking(false).unwrap();
+                    let mut request = [0u8; 1024];
+                    let _ = stream.read(&mut request).unwrap();
+                    attempts += 1;
+                }
+                Err(_) => std::thread::sleep(Duration::from_millis(10)),
+            }
+        }
+        assert!(attempts > 0);
+        assert_eq!(h.join().unwrap().unwrap(), SendOutcome::Unknown);
+    }
+
+    #[test]
+    fn rejected_write_should_have_rejected_outcome() {
+        let (mut stackerdb, mock_server) = mock_stackerdb();
+        stackerdb.signer_id = None;
+
+        let h =
+            spawn(move || stackerdb.send_message_with_retry(SignerMessage::Transactions(vec![])));
+        let ack = StackerDBChunkAckData {
+            accepted: false,
+            reason: Some("Bad signature".to_string()),
+            metadata: None,
+            code: None,
+        };
+        serve_response(&mock_server, &build_json_response(&ack)).expect("Expected a chunk");
+        assert_eq!(
+            h.join().unwrap().unwrap(),
+            SendOutcome::Rejected("Bad signature".to_string())
+        );
+    }
+
+    #[cfg(feature = "monitoring_prom")]
+    #[test]
+    fn stackerdb_writes_should_update_metrics() {
+        use crate::monitoring::prometheus::{
+            STACKERDB_ACKS, STACKERDB_LAST_CHUNK_SIZE, STACKERDB_SEND_LATENCIES_HISTOGRAM,
+        };
+
+        let msg_id = MessageSlotID::DkgEndBegin;
+        let label = format!("{msg_id:?}");
+        let acks = |outcome: &str| STACKERDB_ACKS.with_label_values(&[&label, outcome]).get();
+        let sends = || {
+            STACKERDB_SEND_LATENCIES_HISTOGRAM
+                .with_label_values(&[&label])
+                .get_sample_count()
+        };
+        let (successes, failures, conflicts, sent) = (
+            acks("success"),
+            acks("failure"),
+            acks("version_conflict"),
+            sends(),
+        );
+
+        let (mut stackerdb, mock_server) = mock_stackerdb();
+        let slot_id = stackerdb.signer_slot_id.0;
+        let h = spawn(move || {
+            let accepted = stackerdb.send_message_bytes_with_retry(&msg_id, vec![1, 2, 3]);
+            let rejected = stackerdb.send_message_bytes_with_retry(&msg_id, vec![4, 5]);
+            (accepted, rejected)
+        });
+        let slot_metadata = SlotMetadata::new_unsigned(slot_id, 3, Sha512Trunc256Sum([0u8; 32]));
+        serve_response(
+            &mock_server,
+            &build_json_response(&build_stale_version_ack(Some(slot_metadata))),
+        );
+        let ack = StackerDBChunkAckData {
+            accepted: true,
+            reason: None,
+            metadata: None,
+            code: None,
+        };
+        serve_response(&mock_server, &build_json_response(&ack));
+        let rejection = StackerDBChunkAckData {
+            accepted: false,
+            reason: Some("Bad signature".to_string()),
+            metadata: None,
+            code: None,
+        };
+        serve_response(&mock_server, &build_json_response(&rejection));
+        let (accepted, rejected) = h.join().unwrap();
+        assert!(accepted.unwrap().accepted);
+        assert!(rejected.is_err());
+
+        // Other tests may write to the same slot concurrently, so only a lower bound is known
+        assert!(acks("success") > successes);
+        assert!(acks("failure") > failures);
+        assert!(acks("version_conflict") > conflicts);
+        assert!(sends() >= sent + 3);
+        assert!(STACKERDB_LAST_CHUNK_SIZE.with_label_values(&[&label]).get() > 0);
+    }
+
+    #[test]
+    fn cleared_slots_should_hold_no_transactions_or_packets() {
+        let (mut stackerdb, mock_server) = mock_stackerdb();
+        let packet = SignerMessage::Packet(Packet {
+            msg: Message::DkgBegin(DkgBegin { dkg_id: 1 }),
+            sig: vec![],
+        });
+
+        let h = spawn(move || {
+            stackerdb.send_message_bytes_with_retry(
+                &MessageSlotID::DkgBegin,
+                packet.serialize_to_vec(),
+            )?;
+            stackerdb.clear_slots(Duration::from_secs(5))?;
+            stackerdb.get_current_transactions()
+        });
+        let ack = StackerDBChunkAckData {
+            accepted: true,
+            reason: None,
+            metadata: None,
+            code: None,
+        };
+        serve_response(&mock_server, &build_json_response(&ack)).expect("Expected a chunk");
+
+        let transactions_chunk =
+            serve_response(&mock_server, &build_json_response(&ack)).expect("Expected a chunk");
+        assert_eq!(
+            decode_chunk::<SignerMessage>(&transactions_chunk.data).unwrap(),
+            SignerMessage::Transactions(vec![])
+        );
+        // Only the packet slot we wrote to is cleared
+        let packet_chunk =
+            serve_response(&mock_server, &build_json_response(&ack)).expect("Expected a chunk");
+        assert!(packet_chunk.data.is_empty());
+        assert_eq!(packet_chunk.slot_version, 2);
+
+        // Serve the cleared transactions chunk back
+        let mut response_bytes = b"HTTP/1.1 200 OK\n\n".to_vec();
+        response_bytes.extend(transactions_chunk.data);
+        serve_response(&mock_server, &response_bytes);
+        assert!(h.join().unwrap().unwrap().is_empty());
+    }
+
+    /// Build a StackerDB client talking to a mock server on a random port
+    fn mock_stackerdb() -> (StackerDB, TcpListener) {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        let signer_config = generate_signer_config(&config, 5, 20);
+        (StackerDB::from(&signer_config), mock_server)
+    }
+
+    /// Answer the next request to the mock server with `response_bytes`.
+    /// Returns the chunk carried by the request, if it posted one.
+    fn serve_response(
+        mock_server: &TcpListener,
+        response_bytes: &[u8],
+    ) -> Option<StackerDBChunkData> {
+        let mut stream = mock_server.accept().unwrap().0;
+        let mut request = vec![];
+        let mut buf = [0u8; 1024];
+        let body_start = loop {
+            let read = stream.read(&mut buf).unwrap();
+            assert!(read > 0, "Request ended before its headers");
+            request.extend_from_slice(&buf[..read]);
+            if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+        };
+        let content_length = String::from_utf8_lossy(&request[..body_start])
+            .to_lowercase()
+            .lines()
+            .find_map(|line| line.strip_prefix("content-length:").map(str::to_string))
+            .map_or(0, |len| len.trim().parse::<usize>().unwrap());
+        while request.len() < body_start + content_length {
+            let read = stream.read(&mut buf).unwrap();
+            assert!(read > 0, "Request ended before its body");
+            request.extend_from_slice(&buf[..read]);
+        }
+        stream.write_all(response_bytes).unwrap();
+        serde_json::from_slice(&request[body_start..]).ok()
+    }
+
+    #[test]
+    fn stale_slot_version_should_refetch_and_retry() {
+        let (mut stackerdb, mock_server) = mock_stackerdb();
+        let slot_id = stackerdb.signer_slot_id.0;
+
+        let h = spawn(move || {
+            stackerdb.send_message_bytes_with_retry(&MessageSlotID::Transactions, vec![1, 2, 3])
+        });
+        // The first write is rejected without the node's metadata, so it is refetched
+        let chunk = serve_response(
+            &mock_server,
+            &build_json_response(&build_stale_version_ack(None)),
+        )
+        .expect("Expected a chunk");
+        assert_eq!(chunk.slot_version, 1);
+        let slots_metadata: Vec<_> = (0..=slot_id)
+            .map(|id| {
+                let version = if id == slot_id { 7 } else { 0 };
+                SlotMetadata::new_unsigned(id, version, Sha512Trunc256Sum([0u8; 32]))
+            })
+            .collect();
+        assert!(serve_response(&mock_server, &build_json_response(&slots_metadata)).is_none());
+
+        let ack = StackerDBChunkAckData {
+            accepted: true,
+            reason: None,
+            metadata: None,
+            code: None,
+        };
+        let chunk =
+            serve_response(&mock_server, &build_json_response(&ack)).expect("Expected a chunk");
+        assert_eq!(chunk.slot_version, 8);
+        assert_eq!(chunk.data, vec![1, 2, 3]);
+        assert_eq!(ack, h.join().unwrap().unwrap());
+    }
+
+    #[test]
+    fn persistent_slot_version_conflict_should_fail() {
+        let (mut stackerdb, mock_server) = mock_stackerdb();
+        let slot_id = stackerdb.signer_slot_id.0;
+
+        let h = spawn(move || {
+            stackerdb.send_message_bytes_with_retry(&MessageSlotID::Transactions, vec![1, 2, 3])
+        });
+        // The node's version keeps moving ahead of ours
+        for attempt in 0..SLOT_VERSION_CONFLICT_MAX_ATTEMPTS {
+            let node_version = 10 * (attempt + 1);
+            let slot_metadata =
+                SlotMetadata::new_unsigned(slot_id, node_version, Sha512Trunc256Sum([0u8; 32]));
+            let chunk = serve_response(
+                &mock_server,
+                &build_json_response(&build_stale_version_ack(Some(slot_metadata))),
+            )
+            .expect("Expected a chunk");
+            let expected_version = if attempt == 0 { 1 } else { 10 * attempt + 1 };
+            assert_eq!(chunk.slot_version, expected_version);
+        }
+        assert!(matches!(
+            h.join().unwrap(),
+            Err(ClientError::SlotVersionConflict(
+                SLOT_VERSION_CONFLICT_MAX_ATTEMPTS
+            ))
+        ));
+    }
+
+    #[test]
+    fn foreign_slot_write_should_fail() {
+        let (mut stackerdb, mock_server) = mock_stackerdb();
+        let slot_id = stackerdb.signer_slot_id.0;
+
+        let h = spawn(move || {
+            let ack = stackerdb
+                .send_message_bytes_with_retry(&MessageSlotID::Transactions, vec![1, 2, 3])?;
+            assert!(ack.accepted);
+            stackerdb.send_message_bytes_with_retry(&MessageSlotID::Transactions, vec![4, 5, 6])
+        });
+        let ack = StackerDBChunkAckData {
+            accepted: true,
+            reason: None,
+            metadata: None,
+            code: None,
+        };
+        serve_response(&mock_server, &build_json_response(&ack));
+
+        // Someone else already wrote version 2 of our slot
+        let slot_metadata =
+            SlotMetadata::new_unsigned(slot_id, 2, Sha512Trunc256Sum::from_data(&[7, 8, 9]));
+        let chunk = serve_response(
+            &mock_server,
+            &build_json_response(&build_stale_version_ack(Some(slot_metadata))),
+        )
+        .expect("Expected a chunk");
+        assert_eq!(chunk.slot_version, 2);
+        assert!(matches!(
+            h.join().unwrap(),
+            Err(ClientError::ForeignSlotWrite(id, 2)) if id == slot_id
+        ));
+    }
+
+    #[test]
+    fn message_types_stored_in_the_same_slot_should_share_its_versions() {
+        let (mut stackerdb, mock_server) = mock_stackerdb();
+
+        let h = spawn(move || {
+            let ack = stackerdb.send_message_bytes_with_retry(
+                &MessageSlotID::SignatureShareResponse,
+                vec![1, 2, 3],
+            )?;
+            assert!(ack.accepted);
+            stackerdb
+                .send_message_bytes_with_retry(&MessageSlotID::StackerDbSlotUpdate, vec![4, 5, 6])
+        });
+        let ack = StackerDBChunkAckData {
+            accepted: true,
+            reason: None,
+            metadata: None,
+            code: None,
+        };
+        let chunk =
+            serve_response(&mock_server, &build_json_response(&ack)).expect("Expected a chunk");
+        assert_eq!(chunk.slot_version, 1);
+        // The notification is written over the share response, so it must not reuse its version
+        let chunk =
+            serve_response(&mock_server, &build_json_response(&ack)).expect("Expected a chunk");
+        assert_eq!(chunk.slot_version, 2);
+        assert_eq!(chunk.data, vec![4, 5, 6]);
         assert_eq!(ack, h.join().unwrap().unwrap());
     }
+
+    #[test]
+    fn already_stored_chunk_should_be_accepted() {
+        let (mut stackerdb, mock_server) = mock_stackerdb();
+        let slot_id = stackerdb.signer_slot_id.0;
+
+        let h = spawn(move || {
+            stackerdb.send_message_bytes_with_retry(&MessageSlotID::Transactions, vec![1, 2, 3])
+        });
+        // The node already stored this exact chunk, e.g. from an attempt whose ack was lost
+        let slot_metadata =
+            SlotMetadata::new_unsigned(slot_id, 1, Sha512Trunc256Sum::from_data(&[1, 2, 3]));
+        serve_response(
+            &mock_server,
+            &build_json_response(&build_stale_version_ack(Some(slot_metadata))),
+        );
+        let ack = h.join().unwrap().unwrap();
+        assert!(ack.accepted);
+    }
+
+    #[test]
+    fn queued_messages_should_be_delivered_in_order() {
+        let (mut stackerdb, mock_server) = mock_stackerdb();
+        let mock_server_addr = mock_server.local_addr().unwrap();
+        // Only expect the queued messages, without slot update notifications
+        stackerdb.signer_id = None;
+        stackerdb.write_retry_config = stackerdb
+            .write_retry_config
+            .with_deadline(Duration::from_millis(200));
+
+        let messages: Vec<_> = (0..3u8)
+            .map(|i| {
+                SignerMessage::BlockResponse(BlockResponse::Rejected(BlockRejection::new(
+                    Sha512Trunc256Sum([i; 32]),
+                    RejectCode::ConnectivityIssues,
+                )))
+            })
+            .collect();
+
+        // Take the node down
+        drop(mock_server);
+        assert_eq!(
+            stackerdb
+                .send_message_with_retry(messages[0].clone())
+                .unwrap(),
+            SendOutcome::Unknown
+        );
+        for (i, message) in messages.iter().enumerate() {
+            let queued = QueuedMessage {
+                id: Some(i as i64),
+                message: message.clone(),
+                expires_at: 100,
+            };
+            assert!(stackerdb.queue_message(queued).is_none());
+        }
+        let expired = QueuedMessage {
+            id: Some(3),
+            message: messages[0].clone(),
+            expires_at: 10,
+        };
+        stackerdb.queue_message(expired);
+
+        // Nothing is delivered while the node is down
+        assert!(stackerdb.flush_outbound_queue(50).is_empty());
+        assert!(stackerdb.has_queued_messages());
+
+        // Bring the node back
+        let mock_server = TcpListener::bind(mock_server_addr).unwrap();
+        let h = spawn(move || {
+            let removed = stackerdb.flush_outbound_queue(50);
+            (stackerdb, removed)
+        });
+        let ack = StackerDBChunkAckData {
+            accepted: true,
+            reason: None,
+            metadata: None,
+            code: None,
+        };
+        for message in messages.iter() {
+            let chunk =
+                serve_response(&mock_server, &build_json_response(&ack)).expect("Expected a chunk");
+            assert_eq!(chunk.data, message.serialize_to_vec());
+        }
+        let (stackerdb, removed) = h.join().unwrap();
+        // The expired message is dropped without being sent
+        assert_eq!(
+            removed.iter().map(|queued| queued.id).collect::<Vec<_>>(),
+            vec![Some(0), Some(1), Some(2), Some(3)]
+        );
+        assert!(!stackerdb.has_queued_messages());
+    }
+
+    #[test]
+    fn outbound_queue_should_be_bounded() {
+        let (mut stackerdb, _mock_server) = mock_stackerdb();
+        let message = SignerMessage::Transactions(vec![]);
+        for id in 0..OUTBOUND_QUEUE_MAX_LEN as i64 {
+            let queued = QueuedMessage {
+                id: Some(id),
+                message: message.clone(),
+                expires_at: 100,
+            };
+            assert!(stackerdb.queue_message(queued).is_none());
+        }
+        let queued = QueuedMessage {
+            id: None,
+            message,
+            expires_at: 100,
+        };
+        let dropped = stackerdb
+            .queue_message(queued)
+            .expect("Expected a dropped message");
+        assert_eq!(dropped.id, Some(0));
+        assert_eq!(stackerdb.outbound_queue.len(), OUTBOUND_QUEUE_MAX_LEN);
+    }
+
+    #[test]
+    fn slot_update_notifications_are_rate_limited() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let signer_config = generate_signer_config(&config, 5, 20);
+        let mut stackerdb = StackerDB::from(&signer_config);
+
+        let now = Instant::now();
+        assert!(stackerdb.should_notify_slot_update(0, now));
+        assert!(!stackerdb.should_notify_slot_update(0, now + Duration::from_millis(50)));
+        // Other signers are rate limited independently
+        assert!(stackerdb.should_notify_slot_update(1, now + Duration::from_millis(50)));
+        assert!(stackerdb.should_notify_slot_update(0, now + SLOT_UPDATE_MIN_INTERVAL));
+        assert!(!stackerdb.should_notify_slot_update(0, now + Duration::from_millis(150)));
+    }
 }
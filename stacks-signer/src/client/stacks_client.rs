@@ -14,11 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use blockstack_lib::burnchains::Txid;
 use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
 use blockstack_lib::chainstate::stacks::boot::{
-    NakamotoSignerEntry, SIGNERS_VOTING_FUNCTION_NAME, SIGNERS_VOTING_NAME,
+    NakamotoSignerEntry, MINERS_NAME, SIGNERS_NAME, SIGNERS_VOTING_BATCH_FUNCTION_NAME,
+    SIGNERS_VOTING_FUNCTION_NAME, SIGNERS_VOTING_NAME,
 };
 use blockstack_lib::chainstate::stacks::{
     StacksTransaction, StacksTransactionSigner, TransactionAnchorMode, TransactionAuth,
@@ -32,23 +35,76 @@ use blockstack_lib::net::api::getpoxinfo::RPCPoxInfoData;
 use blockstack_lib::net::api::getstackers::GetStackersResponse;
 use blockstack_lib::net::api::postblock_proposal::NakamotoBlockProposal;
 use blockstack_lib::net::api::postfeerate::{FeeRateEstimateRequestBody, RPCFeeEstimateResponse};
+use blockstack_lib::net::stackerdb::MINER_SLOT_COUNT;
 use blockstack_lib::util_lib::boot::{boot_code_addr, boot_code_id};
 use clarity::util::hash::to_hex;
-use clarity::vm::types::{PrincipalData, QualifiedContractIdentifier};
+use clarity::vm::types::{PrincipalData, QualifiedContractIdentifier, TupleData};
 use clarity::vm::{ClarityName, ContractName, Value as ClarityValue};
+use libsigner::v1::messages::{BlockResponse, MessageSlotID, SignerMessage};
+use libsigner::BlockProposal;
+use libstackerdb::stackerdb_get_chunk_path;
 use reqwest::header::AUTHORIZATION;
+use serde::de::DeserializeOwned;
 use serde_json::json;
 use slog::slog_debug;
-use stacks_common::codec::StacksMessageCodec;
+use stacks_common::codec::{read_next, StacksMessageCodec};
 use stacks_common::consts::{CHAIN_ID_MAINNET, CHAIN_ID_TESTNET};
 use stacks_common::debug;
-use stacks_common::types::chainstate::{StacksAddress, StacksPrivateKey, StacksPublicKey};
+use stacks_common::types::chainstate::{
+    ConsensusHash, StacksAddress, StacksBlockId, StacksPrivateKey, StacksPublicKey,
+};
 use stacks_common::types::StacksEpochId;
 use wsts::curve::point::{Compressed, Point};
+use wsts::net::Message;
 
-use crate::client::{retry_with_exponential_backoff, ClientError};
+use crate::client::{
+    build_http_client, is_backpressure_status, parse_retry_after, read_bytes_response,
+    read_json_response, retry_with_exponential_backoff, ClientError, RateLimitConfig, RateLimiter,
+    ResponseCategory, ResponseLimits, RetryConfig,
+};
 use crate::config::GlobalConfig;
 use crate::runloop::RewardCycleInfo;
+use crate::v1::coordinator::CoordinatorMetadata;
+
+/// The sortition info of the canonical burn chain tip as reported by the stacks node's `/v2/info`
+#[derive(Clone, Debug, PartialEq)]
+pub struct SortitionInfo {
+    /// The canonical burn block height
+    pub burn_block_height: u64,
+    /// The consensus hash of the canonical burn chain tip
+    pub pox_consensus_hash: ConsensusHash,
+}
+
+/// The vote tallies of a DKG round as recorded by the signers voting contract
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundInfo {
+    /// The number of signers that voted in the round
+    pub votes_count: u128,
+    /// The total weight of the votes cast in the round, across all candidate keys
+    pub votes_weight: u128,
+    /// The aggregate key approved for the reward cycle, if any candidate reached the threshold
+    pub approved_aggregate_key: Option<Point>,
+}
+
+/// The weight voted for a candidate aggregate key in a DKG round, as recorded by the signers
+/// voting contract
+#[derive(Clone, Debug, PartialEq)]
+pub struct CandidateInfo {
+    /// The weight of the votes cast for the candidate
+    pub candidate_weight: u128,
+    /// The total weight of the signers of the reward cycle. None until a vote was cast in the
+    /// reward cycle.
+    pub total_weight: Option<u128>,
+}
+
+impl From<&RPCPeerInfoData> for SortitionInfo {
+    fn from(peer_info: &RPCPeerInfoData) -> Self {
+        Self {
+            burn_block_height: peer_info.burn_block_height,
+            pox_consensus_hash: peer_info.pox_consensus,
+        }
+    }
+}
 
 /// The Stacks signer client used to communicate with the stacks node
 #[derive(Clone, Debug)]
@@ -69,6 +125,16 @@ pub struct StacksClient {
     stacks_node_client: reqwest::blocking::Client,
     /// the auth password for the stacks node
     auth_password: String,
+    /// The backoff policy used when retrying requests to the stacks node
+    retry_config: RetryConfig,
+    /// The last retrieved sortition info, reused until the next burn block is processed
+    sortition_info_cache: Arc<Mutex<Option<SortitionInfo>>>,
+    /// The rate limiter pacing requests to the stacks node
+    rate_limiter: RateLimiter,
+    /// The maximum size of responses read from the stacks node
+    response_limits: ResponseLimits,
+    /// When the stacks node last answered a request
+    last_node_contact: Arc<Mutex<Option<Instant>>>,
 }
 
 impl From<&GlobalConfig> for StacksClient {
@@ -76,12 +142,17 @@ impl From<&GlobalConfig> for StacksClient {
         Self {
             stacks_private_key: config.stacks_private_key,
             stacks_address: config.stacks_address,
-            http_origin: format!("http://{}", config.node_host),
-            tx_version: config.network.to_transaction_version(),
-            chain_id: config.network.to_chain_id(),
-            stacks_node_client: reqwest::blocking::Client::new(),
-            mainnet: config.network.is_mainnet(),
+            http_origin: format!("{}://{}", config.node_transport.scheme(), config.node_host),
+            tx_version: config.network_profile.transaction_version,
+            chain_id: config.network_profile.chain_id,
+            stacks_node_client: build_http_client(config.node_transport),
+            mainnet: config.network_profile.mainnet,
             auth_password: config.auth_password.clone(),
+            retry_config: config.retry_config,
+            sortition_info_cache: Arc::new(Mutex::new(None)),
+            rate_limiter: RateLimiter::new(config.rate_limit_config),
+            response_limits: config.response_limits,
+            last_node_contact: Arc::new(Mutex::new(None)),
         }
     }
 }
@@ -115,9 +186,107 @@ impl StacksClient {
             stacks_node_client: reqwest::blocking::Client::new(),
             mainnet,
             auth_password,
+            retry_config: RetryConfig::default(),
+            sortition_info_cache: Arc::new(Mutex::new(None)),
+            rate_limiter: RateLimiter::new(RateLimitConfig::default()),
+            response_limits: ResponseLimits::default(),
+            last_node_contact: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// The chain id of the transactions the client builds
+    pub fn get_chain_id(&self) -> u32 {
+        self.chain_id
+    }
+
+    /// Apply the stacks node connection settings of the given configuration that can change
+    /// while the signer runs: the node endpoint, its authorization, and how requests are paced,
+    /// retried and bounded. The rate limiter keeps its state unless its parameters changed.
+    pub fn apply_node_config(&mut self, config: &GlobalConfig) {
+        let http_origin = format!("{}://{}", config.node_transport.scheme(), config.node_host);
+        if http_origin != self.http_origin {
+            self.http_origin = http_origin;
+            self.stacks_node_client = build_http_client(config.node_transport);
+            self.sortition_info_cache = Arc::new(Mutex::new(None));
+        }
+        self.auth_password = config.auth_password.clone();
+        self.retry_config = config.retry_config;
+        if *self.rate_limiter.config() != config.rate_limit_config {
+            self.rate_limiter = RateLimiter::new(config.rate_limit_config);
+        }
+        self.response_limits = config.response_limits;
+    }
+
+    /// Return a copy of this client whose requests stop retrying after at most `deadline`.
+    /// Use for latency sensitive calls that must not block the event loop for the full retry period.
+    pub fn with_retry_deadline(&self, deadline: Duration) -> Self {
+        let mut client = self.clone();
+        client.retry_config = self.retry_config.with_deadline(deadline);
+        client
+    }
+
+    /// Deserialize the JSON body of a response, enforcing the size limit of its endpoint category
+    fn read_json<T: DeserializeOwned>(
+        &self,
+        response: reqwest::blocking::Response,
+        category: ResponseCategory,
+    ) -> Result<T, ClientError> {
+        read_json_response(response, self.response_limits.max_bytes(category))
+    }
+
+    /// Send the request built by `build_request` to the stacks node, retrying on transient failures.
+    /// Requests are paced by the rate limiter, and 429/503 responses are retried after the delay
+    /// requested by their `Retry-After` header if present.
+    fn send_with_retry<F>(
+        &self,
+        build_request: F,
+    ) -> Result<reqwest::blocking::Response, ClientError>
+    where
+        F: Fn() -> reqwest::blocking::RequestBuilder,
+    {
+        let send_request = || {
+            self.rate_limiter
+                .acquire()
+                .map_err(backoff::Error::permanent)?;
+            let response = build_request().send().map_err(|e| {
+                match ClientError::from_request_error(e) {
+                    // A failed handshake or certificate verification will not resolve on retry
+                    e @ ClientError::TlsError(_) => backoff::Error::permanent(e),
+                    e => backoff::Error::transient(e),
+                }
+            })?;
+            *self
+                .last_node_contact
+                .lock()
+                .expect("FATAL: last node contact lock poisoned") = Some(Instant::now());
+            let status = response.status();
+            if is_backpressure_status(status) {
+                if let Some(retry_after) = parse_retry_after(&response) {
+                    debug!("Stacks node responded with {status}. Holding back requests for {retry_after:?}");
+                    self.rate_limiter.pause_for(retry_after);
+                }
+                return Err(backoff::Error::transient(ClientError::RequestFailure(
+                    status,
+                )));
+            }
+            Ok(response)
+        };
+        retry_with_exponential_backoff(&self.retry_config, send_request)
+    }
+
+    /// When the stacks node last answered a request, whatever its response
+    pub fn last_node_contact(&self) -> Option<Instant> {
+        *self
+            .last_node_contact
+            .lock()
+            .expect("FATAL: last node contact lock poisoned")
+    }
+
+    /// How long requests to the stacks node are still held back at its request, if they are
+    pub fn node_backoff_remaining(&self) -> Option<Duration> {
+        self.rate_limiter.remaining_pause()
+    }
+
     /// Get our signer address
     pub const fn get_signer_address(&self) -> &StacksAddress {
         &self.stacks_address
@@ -186,16 +355,13 @@ impl StacksClient {
         )?;
         // Return value is of type:
         // ```clarity
-        // (option { aggregate-public-key: (buff 33), signer-weight: uint })
+        // (optional (buff 33))
         // ```
         let inner_data = value.expect_optional()?;
-        if let Some(inner_data) = inner_data {
-            let tuple = inner_data.expect_tuple()?;
-            let key_value = tuple.get_owned("aggregate-public-key")?;
-            self.parse_aggregate_public_key(key_value)
-        } else {
-            Ok(None)
-        }
+        inner_data.map_or_else(
+            || Ok(None),
+            |key_value| self.parse_aggregate_public_key(key_value),
+        )
     }
 
     /// Retrieve the medium estimated transaction fee in uSTX from the stacks node for the given transaction
@@ -209,20 +375,18 @@ impl StacksClient {
         };
         let timer =
             crate::monitoring::new_rpc_call_timer(&self.fees_transaction_path(), &self.http_origin);
-        let send_request = || {
+        let response = self.send_with_retry(|| {
             self.stacks_node_client
                 .post(self.fees_transaction_path())
                 .header("Content-Type", "application/json")
                 .json(&request)
-                .send()
-                .map_err(backoff::Error::transient)
-        };
-        let response = retry_with_exponential_backoff(send_request)?;
+        })?;
         if !response.status().is_success() {
             return Err(ClientError::RequestFailure(response.status()));
         }
         timer.stop_and_record();
-        let fee_estimate_response = response.json::<RPCFeeEstimateResponse>()?;
+        let fee_estimate_response =
+            self.read_json::<RPCFeeEstimateResponse>(response, ResponseCategory::Account)?;
         let fee = fee_estimate_response
             .estimations
             .get(1)
@@ -273,17 +437,13 @@ impl StacksClient {
         };
         let timer =
             crate::monitoring::new_rpc_call_timer(&self.block_proposal_path(), &self.http_origin);
-        let send_request = || {
+        let response = self.send_with_retry(|| {
             self.stacks_node_client
                 .post(self.block_proposal_path())
                 .header("Content-Type", "application/json")
                 .header(AUTHORIZATION, self.auth_password.clone())
                 .json(&block_proposal)
-                .send()
-                .map_err(backoff::Error::transient)
-        };
-
-        let response = retry_with_exponential_backoff(send_request)?;
+        })?;
         timer.stop_and_record();
         if !response.status().is_success() {
             return Err(ClientError::RequestFailure(response.status()));
@@ -291,6 +451,120 @@ impl StacksClient {
         Ok(())
     }
 
+    /// Retrieve the block proposals of the given reward cycle that the miners last wrote to the .miners stacker-db.
+    /// Lets the signer catch proposals whose stacker-db events it never received.
+    pub fn get_pending_block_proposals(
+        &self,
+        reward_cycle: u64,
+    ) -> Result<Vec<BlockProposal>, ClientError> {
+        let mut block_proposals = vec![];
+        for slot_id in 0..MINER_SLOT_COUNT {
+            let path = self.miner_chunk_path(slot_id);
+            let timer = crate::monitoring::new_rpc_call_timer(&path, &self.http_origin);
+            let response = self.send_with_retry(|| self.stacks_node_client.get(&path))?;
+            timer.stop_and_record();
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                // The miner has not written to its slot yet
+                continue;
+            }
+            if !response.status().is_success() {
+                return Err(ClientError::RequestFailure(response.status()));
+            }
+            let data = read_bytes_response(
+                response,
+                self.response_limits.max_bytes(ResponseCategory::StackerDB),
+            )?;
+            let Ok(SignerMessage::Packet(packet)) = read_next::<SignerMessage, _>(&mut &data[..])
+            else {
+                continue;
+            };
+            let Message::NonceRequest(nonce_request) = packet.msg else {
+                continue;
+            };
+            let Ok(block_proposal) =
+                BlockProposal::consensus_deserialize(&mut nonce_request.message.as_slice())
+            else {
+                debug!("Miner slot {slot_id} holds a nonce request that is not a block proposal");
+                continue;
+            };
+            if block_proposal.reward_cycle == reward_cycle {
+                block_proposals.push(block_proposal);
+            }
+        }
+        Ok(block_proposals)
+    }
+
+    /// Retrieve the block responses that the signers of the given reward cycle last wrote to
+    /// their block response slots in the .signers stacker-db
+    pub fn get_block_responses(
+        &self,
+        reward_cycle: u64,
+    ) -> Result<Vec<BlockResponse>, ClientError> {
+        let signer_set =
+            u32::try_from(reward_cycle % 2).expect("FATAL: reward_cycle % 2 exceeds u32::MAX");
+        let signer_slots =
+            self.get_stackerdb_signer_slots(&boot_code_id(SIGNERS_NAME, self.mainnet), signer_set)?;
+        let contract_id =
+            MessageSlotID::BlockResponse.stacker_db_contract(self.mainnet, reward_cycle);
+        let mut block_responses = vec![];
+        for slot_id in 0..signer_slots.len() {
+            let slot_id =
+                u32::try_from(slot_id).expect("FATAL: number of signers exceeds u32::MAX");
+            let path = format!(
+                "{}{}",
+                self.http_origin,
+                stackerdb_get_chunk_path(contract_id.clone(), slot_id, None)
+            );
+            let timer = crate::monitoring::new_rpc_call_timer(&path, &self.http_origin);
+            let response = self.send_with_retry(|| self.stacks_node_client.get(&path))?;
+            timer.stop_and_record();
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                // The signer has not responded to a block yet
+                continue;
+            }
+            if !response.status().is_success() {
+                return Err(ClientError::RequestFailure(response.status()));
+            }
+            let data = read_bytes_response(
+                response,
+                self.response_limits.max_bytes(ResponseCategory::StackerDB),
+            )?;
+            match read_next::<SignerMessage, _>(&mut &data[..]) {
+                Ok(SignerMessage::BlockResponse(block_response)) => {
+                    block_responses.push(block_response)
+                }
+                _ => debug!("Signer slot {slot_id} does not hold a block response"),
+            }
+        }
+        Ok(block_responses)
+    }
+
+    /// Retrieve the Nakamoto block with the given ID from the stacks node, or None if the node
+    /// does not know the block
+    pub fn get_block_by_id(
+        &self,
+        block_id: &StacksBlockId,
+    ) -> Result<Option<NakamotoBlock>, ClientError> {
+        debug!("Getting block {block_id}...");
+        let path = self.block_path(block_id);
+        let timer = crate::monitoring::new_rpc_call_timer(&path, &self.http_origin);
+        let response = self.send_with_retry(|| self.stacks_node_client.get(&path))?;
+        timer.stop_and_record();
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(ClientError::RequestFailure(response.status()));
+        }
+        let data = read_bytes_response(
+            response,
+            self.response_limits.max_bytes(ResponseCategory::Rpc),
+        )?;
+        let block = read_next::<NakamotoBlock, _>(&mut &data[..])
+            .map_err(|e| ClientError::UnexpectedResponseFormat(format!("Malformed block: {e}")))?;
+        Ok(Some(block))
+    }
+
     /// Retrieve the approved DKG aggregate public key for the given reward cycle
     pub fn get_approved_aggregate_key(
         &self,
@@ -339,6 +613,115 @@ impl StacksClient {
         Ok(Some(votes_weight))
     }
 
+    /// Retrieve the vote tallies of the given reward cycle and DKG round from the signers voting contract,
+    /// along with the aggregate key approved for the reward cycle. Returns None if no votes were cast in the round.
+    pub fn get_round_info(
+        &self,
+        reward_cycle: u64,
+        round: u64,
+    ) -> Result<Option<RoundInfo>, ClientError> {
+        debug!("Getting round info for reward cycle {reward_cycle} and round {round}...");
+        let function_name = ClarityName::from("get-round-info");
+        let voting_contract_id = boot_code_id(SIGNERS_VOTING_NAME, self.mainnet);
+        let function_args = &[
+            ClarityValue::UInt(reward_cycle as u128),
+            ClarityValue::UInt(round as u128),
+        ];
+        let value = self.read_only_contract_call(
+            &voting_contract_id.issuer.into(),
+            &voting_contract_id.name,
+            &function_name,
+            function_args,
+        )?;
+        // Return value is of type:
+        // ```clarity
+        // (option { votes-count: uint, votes-weight: uint })
+        // ```
+        let Some(inner_data) = value.expect_optional()? else {
+            return Ok(None);
+        };
+        let round_data = inner_data.expect_tuple()?;
+        let votes_count = round_data.get("votes-count")?.to_owned().expect_u128()?;
+        let votes_weight = round_data.get("votes-weight")?.to_owned().expect_u128()?;
+        let approved_aggregate_key = self.get_approved_aggregate_key(reward_cycle)?;
+        Ok(Some(RoundInfo {
+            votes_count,
+            votes_weight,
+            approved_aggregate_key,
+        }))
+    }
+
+    /// Retrieve the weight voted for the given aggregate key in the given reward cycle and DKG round.
+    /// Returns None if no signer voted for the key in the round.
+    pub fn get_aggregate_key_tally(
+        &self,
+        reward_cycle: u64,
+        round: u64,
+        aggregate_key: &Point,
+    ) -> Result<Option<u128>, ClientError> {
+        debug!("Getting the tally of aggregate key {aggregate_key} in reward cycle {reward_cycle} and round {round}...");
+        let function_name = ClarityName::from("get-tally");
+        let voting_contract_id = boot_code_id(SIGNERS_VOTING_NAME, self.mainnet);
+        let function_args = &[
+            ClarityValue::UInt(reward_cycle as u128),
+            ClarityValue::UInt(round as u128),
+            ClarityValue::buff_from(aggregate_key.compress().data.to_vec())?,
+        ];
+        let value = self.read_only_contract_call(
+            &voting_contract_id.issuer.into(),
+            &voting_contract_id.name,
+            &function_name,
+            function_args,
+        )?;
+        let Some(tally) = value.expect_optional()? else {
+            return Ok(None);
+        };
+        Ok(Some(tally.expect_u128()?))
+    }
+
+    /// Retrieve the weight voted for the given candidate aggregate key in the given reward cycle
+    /// and DKG round, along with the total weight of the reward cycle's signers
+    pub fn get_candidate_info(
+        &self,
+        reward_cycle: u64,
+        round: u64,
+        candidate: &Point,
+    ) -> Result<CandidateInfo, ClientError> {
+        debug!("Getting the info of candidate {candidate} in reward cycle {reward_cycle} and round {round}...");
+        let function_name = ClarityName::from("get-candidate-info");
+        let voting_contract_id = boot_code_id(SIGNERS_VOTING_NAME, self.mainnet);
+        let function_args = &[
+            ClarityValue::UInt(reward_cycle as u128),
+            ClarityValue::UInt(round as u128),
+            ClarityValue::buff_from(candidate.compress().data.to_vec())?,
+        ];
+        let value = self.read_only_contract_call(
+            &voting_contract_id.issuer.into(),
+            &voting_contract_id.name,
+            &function_name,
+            function_args,
+        )?;
+        // Return value is of type:
+        // ```clarity
+        // { candidate-weight: uint, total-weight: (optional uint) }
+        // ```
+        let candidate_data = value.expect_tuple()?;
+        let candidate_weight = candidate_data
+            .get("candidate-weight")?
+            .to_owned()
+            .expect_u128()?;
+        let total_weight = candidate_data
+            .get("total-weight")?
+            .to_owned()
+            .expect_optional()?
+            .map(|weight| weight.expect_u128())
+            .transpose()?;
+        Ok(CandidateInfo {
+            candidate_weight,
+            total_weight,
+        })
+    }
+
     /// Retrieve the weight threshold required to approve a DKG vote
     pub fn get_vote_threshold_weight(&self, reward_cycle: u64) -> Result<u128, ClientError> {
         let function_name = ClarityName::from("get-threshold-weight");
@@ -363,21 +746,49 @@ impl StacksClient {
         debug!("Getting stacks node info...");
         let timer =
             crate::monitoring::new_rpc_call_timer(&self.core_info_path(), &self.http_origin);
-        let send_request = || {
-            self.stacks_node_client
-                .get(self.core_info_path())
-                .send()
-                .map_err(backoff::Error::transient)
-        };
-        let response = retry_with_exponential_backoff(send_request)?;
+        let response =
+            self.send_with_retry(|| self.stacks_node_client.get(self.core_info_path()))?;
         timer.stop_and_record();
         if !response.status().is_success() {
             return Err(ClientError::RequestFailure(response.status()));
         }
-        let peer_info_data = response.json::<RPCPeerInfoData>()?;
+        let peer_info_data = self.read_json::<RPCPeerInfoData>(response, ResponseCategory::Rpc)?;
         Ok(peer_info_data)
     }
 
+    /// Retrieve the sortition info of the canonical burn chain tip from `/v2/info`.
+    /// The info is cached until `clear_sortition_info_cache` is called on the next burn block.
+    pub fn get_sortition_info(&self) -> Result<SortitionInfo, ClientError> {
+        if let Some(cached) = self
+            .sortition_info_cache
+            .lock()
+            .expect("FATAL: sortition info cache lock poisoned")
+            .as_ref()
+        {
+            return Ok(cached.clone());
+        }
+        let sortition_info = SortitionInfo::from(&self.get_peer_info()?);
+        *self
+            .sortition_info_cache
+            .lock()
+            .expect("FATAL: sortition info cache lock poisoned") = Some(sortition_info.clone());
+        Ok(sortition_info)
+    }
+
+    /// Forget the cached sortition info, so that the next call to `get_sortition_info` reads the
+    /// canonical burn chain tip again. Clones of this client share the cache.
+    pub fn clear_sortition_info_cache(&self) {
+        *self
+            .sortition_info_cache
+            .lock()
+            .expect("FATAL: sortition info cache lock poisoned") = None;
+    }
+
+    /// Assemble the coordinator metadata from the current canonical burn chain tip
+    pub fn get_current_coordinator_metadata(&self) -> Result<CoordinatorMetadata, ClientError> {
+        self.get_sortition_info().map(CoordinatorMetadata::from)
+    }
+
     /// Retrieve the last DKG vote round number for the current reward cycle
     pub fn get_last_round(&self, reward_cycle: u64) -> Result<Option<u64>, ClientError> {
         debug!("Getting the last DKG vote round of reward cycle {reward_cycle}...");
@@ -405,6 +816,66 @@ impl StacksClient {
         Ok(round)
     }
 
+    /// Retrieve the number of burn blocks after its first vote a DKG vote round expires
+    pub fn get_round_expiry_blocks(&self) -> Result<u64, ClientError> {
+        let function_name = ClarityName::from("get-round-expiry-blocks");
+        let voting_contract_id = boot_code_id(SIGNERS_VOTING_NAME, self.mainnet);
+        let value = self.read_only_contract_call(
+            &voting_contract_id.issuer.into(),
+            &voting_contract_id.name,
+            &function_name,
+            &[],
+        )?;
+        u64::try_from(value.expect_u128()?).map_err(|e| {
+            ClientError::MalformedContractData(format!(
+                "Failed to convert round expiry to u64: {e}"
+            ))
+        })
+    }
+
+    /// Retrieve the burn block height at which the given DKG vote round received its first vote.
+    /// Returns None if no signer voted in the round yet.
+    pub fn get_round_start_height(
+        &self,
+        reward_cycle: u64,
+        round: u64,
+    ) -> Result<Option<u64>, ClientError> {
+        debug!("Getting the start height of round {round} of reward cycle {reward_cycle}...");
+        let function_name = ClarityName::from("get-round-start-height");
+        let voting_contract_id = boot_code_id(SIGNERS_VOTING_NAME, self.mainnet);
+        let function_args = &[
+            ClarityValue::UInt(reward_cycle as u128),
+            ClarityValue::UInt(round as u128),
+        ];
+        let value = self.read_only_contract_call(
+            &voting_contract_id.issuer.into(),
+            &voting_contract_id.name,
+            &function_name,
+            function_args,
+        )?;
+        value
+            .expect_optional()?
+            .map(|height| {
+                u64::try_from(height.expect_u128()?).map_err(|e| {
+                    ClientError::MalformedContractData(format!(
+                        "Failed to convert round start height to u64: {e}"
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    /// Check whether the given DKG vote round expired as of the current burn block height.
+    /// An expired round no longer accepts votes, so a new DKG round can safely be started.
+    pub fn is_round_expired(&self, reward_cycle: u64, round: u64) -> Result<bool, ClientError> {
+        let Some(start_height) = self.get_round_start_height(reward_cycle, round)? else {
+            return Ok(false);
+        };
+        let expiry_blocks = self.get_round_expiry_blocks()?;
+        let burn_block_height = self.get_burn_block_height()?;
+        Ok(burn_block_height >= start_height.saturating_add(expiry_blocks))
+    }
+
     /// Get the reward set signers from the stacks node for the given reward cycle
     pub fn get_reward_set_signers(
         &self,
@@ -415,18 +886,16 @@ impl StacksClient {
             &self.reward_set_path(reward_cycle),
             &self.http_origin,
         );
-        let send_request = || {
+        let response = self.send_with_retry(|| {
             self.stacks_node_client
                 .get(self.reward_set_path(reward_cycle))
-                .send()
-                .map_err(backoff::Error::transient)
-        };
-        let response = retry_with_exponential_backoff(send_request)?;
+        })?;
         timer.stop_and_record();
         if !response.status().is_success() {
             return Err(ClientError::RequestFailure(response.status()));
         }
-        let stackers_response = response.json::<GetStackersResponse>()?;
+        let stackers_response =
+            self.read_json::<GetStackersResponse>(response, ResponseCategory::Rpc)?;
         Ok(stackers_response.stacker_set.signers)
     }
 
@@ -435,19 +904,13 @@ impl StacksClient {
         debug!("Getting pox data...");
         #[cfg(feature = "monitoring_prom")]
         let timer = crate::monitoring::new_rpc_call_timer(&self.pox_path(), &self.http_origin);
-        let send_request = || {
-            self.stacks_node_client
-                .get(self.pox_path())
-                .send()
-                .map_err(backoff::Error::transient)
-        };
-        let response = retry_with_exponential_backoff(send_request)?;
+        let response = self.send_with_retry(|| self.stacks_node_client.get(self.pox_path()))?;
         #[cfg(feature = "monitoring_prom")]
         timer.stop_and_record();
         if !response.status().is_success() {
             return Err(ClientError::RequestFailure(response.status()));
         }
-        let pox_info_data = response.json::<RPCPoxInfoData>()?;
+        let pox_info_data = self.read_json::<RPCPoxInfoData>(response, ResponseCategory::Rpc)?;
         Ok(pox_info_data)
     }
 
@@ -483,18 +946,14 @@ impl StacksClient {
         debug!("Getting account info...");
         let timer =
             crate::monitoring::new_rpc_call_timer(&self.accounts_path(address), &self.http_origin);
-        let send_request = || {
-            self.stacks_node_client
-                .get(self.accounts_path(address))
-                .send()
-                .map_err(backoff::Error::transient)
-        };
-        let response = retry_with_exponential_backoff(send_request)?;
+        let response =
+            self.send_with_retry(|| self.stacks_node_client.get(self.accounts_path(address)))?;
         timer.stop_and_record();
         if !response.status().is_success() {
             return Err(ClientError::RequestFailure(response.status()));
         }
-        let account_entry = response.json::<AccountEntryResponse>()?;
+        let account_entry =
+            self.read_json::<AccountEntryResponse>(response, ResponseCategory::Account)?;
         Ok(account_entry)
     }
 
@@ -553,24 +1012,63 @@ impl StacksClient {
         Ok(unsigned_tx)
     }
 
+    /// Helper function to create a stacks transaction voting for the aggregate public key of
+    /// several rounds at once
+    pub fn build_unsigned_vote_for_aggregate_public_keys(
+        &self,
+        signer_index: u32,
+        votes: &[(u64, Point)],
+        reward_cycle: u64,
+        nonce: u64,
+    ) -> Result<StacksTransaction, ClientError> {
+        debug!("Building {SIGNERS_VOTING_BATCH_FUNCTION_NAME} transaction...");
+        let contract_address = boot_code_addr(self.mainnet);
+        let contract_name = ContractName::from(SIGNERS_VOTING_NAME);
+        let function_name = ClarityName::from(SIGNERS_VOTING_BATCH_FUNCTION_NAME);
+        let votes = votes
+            .iter()
+            .map(|(round, dkg_public_key)| {
+                let vote = TupleData::from_data(vec![
+                    ("round".into(), ClarityValue::UInt(*round as u128)),
+                    (
+                        "key".into(),
+                        ClarityValue::buff_from(dkg_public_key.compress().data.to_vec())?,
+                    ),
+                ])?;
+                Ok(ClarityValue::Tuple(vote))
+            })
+            .collect::<Result<Vec<_>, ClientError>>()?;
+        let function_args = vec![
+            ClarityValue::UInt(signer_index as u128),
+            ClarityValue::cons_list_unsanitized(votes)?,
+            ClarityValue::UInt(reward_cycle as u128),
+        ];
+
+        let unsigned_tx = Self::build_unsigned_contract_call_transaction(
+            &contract_address,
+            contract_name,
+            function_name,
+            &function_args,
+            &self.stacks_private_key,
+            self.tx_version,
+            self.chain_id,
+            nonce,
+        )?;
+        Ok(unsigned_tx)
+    }
+
     /// Helper function to submit a transaction to the Stacks mempool
     pub fn submit_transaction(&self, tx: &StacksTransaction) -> Result<Txid, ClientError> {
         let txid = tx.txid();
         let tx = tx.serialize_to_vec();
         let timer =
             crate::monitoring::new_rpc_call_timer(&self.transaction_path(), &self.http_origin);
-        let send_request = || {
+        let response = self.send_with_retry(|| {
             self.stacks_node_client
                 .post(self.transaction_path())
                 .header("Content-Type", "application/octet-stream")
                 .body(tx.clone())
-                .send()
-                .map_err(|e| {
-                    debug!("Failed to submit transaction to the Stacks node: {e:?}");
-                    backoff::Error::transient(e)
-                })
-        };
-        let response = retry_with_exponential_backoff(send_request)?;
+        })?;
         timer.stop_and_record();
         if !response.status().is_success() {
             return Err(ClientError::RequestFailure(response.status()));
@@ -601,17 +1099,20 @@ impl StacksClient {
             json!({"sender": self.stacks_address.to_string(), "arguments": args}).to_string();
         let path = self.read_only_path(contract_addr, contract_name, function_name);
         let timer = crate::monitoring::new_rpc_call_timer(&path, &self.http_origin);
+        self.rate_limiter.acquire()?;
         let response = self
             .stacks_node_client
             .post(path)
             .header("Content-Type", "application/json")
             .body(body)
-            .send()?;
+            .send()
+            .map_err(ClientError::from_request_error)?;
         timer.stop_and_record();
         if !response.status().is_success() {
             return Err(ClientError::RequestFailure(response.status()));
         }
-        let call_read_only_response = response.json::<CallReadOnlyResponse>()?;
+        let call_read_only_response =
+            self.read_json::<CallReadOnlyResponse>(response, ResponseCategory::Rpc)?;
         if !call_read_only_response.okay {
             return Err(ClientError::ReadOnlyFailure(format!(
                 "{function_name}: {}",
@@ -661,6 +1162,19 @@ impl StacksClient {
         format!("{}/v2/stacker_set/{reward_cycle}", self.http_origin)
     }
 
+    fn block_path(&self, block_id: &StacksBlockId) -> String {
+        format!("{}/v3/blocks/{block_id}", self.http_origin)
+    }
+
+    fn miner_chunk_path(&self, slot_id: u32) -> String {
+        let miners_contract_id = boot_code_id(MINERS_NAME, self.mainnet);
+        format!(
+            "{}{}",
+            self.http_origin,
+            stackerdb_get_chunk_path(miners_contract_id, slot_id, None)
+        )
+    }
+
     fn fees_transaction_path(&self) -> String {
         format!("{}/v2/fees/transaction", self.http_origin)
     }
@@ -723,10 +1237,12 @@ impl StacksClient {
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
-    use std::io::{BufWriter, Write};
+    use std::io::{BufWriter, Read, Write};
     use std::thread::spawn;
+    use std::time::Instant;
 
     use blockstack_lib::burnchains::Address;
+    use blockstack_lib::chainstate::nakamoto::signer_set::NakamotoSigners;
     use blockstack_lib::chainstate::nakamoto::NakamotoBlockHeader;
     use blockstack_lib::chainstate::stacks::address::PoxAddress;
     use blockstack_lib::chainstate::stacks::boot::{
@@ -736,18 +1252,23 @@ mod tests {
         ListData, ListTypeData, ResponseData, SequenceData, TupleData, TupleTypeSignature,
         TypeSignature,
     };
+    use libsigner::v1::messages::{BlockRejection, RejectCode};
     use rand::thread_rng;
     use rand_core::RngCore;
     use stacks_common::consts::{CHAIN_ID_TESTNET, SIGNER_SLOTS_PER_USER};
+    use stacks_common::util::hash::Sha512Trunc256Sum;
     use wsts::curve::scalar::Scalar;
+    use wsts::net::{NonceRequest, Packet};
 
     use super::*;
     use crate::client::tests::{
-        build_account_nonce_response, build_get_approved_aggregate_key_response,
+        build_account_nonce_response, build_get_aggregate_key_tally_response,
+        build_get_approved_aggregate_key_response, build_get_candidate_info_response,
         build_get_last_round_response, build_get_medium_estimated_fee_ustx_response,
         build_get_peer_info_response, build_get_pox_data_response, build_get_round_info_response,
         build_get_vote_for_aggregate_key_response, build_get_weight_threshold_response,
-        build_read_only_response, write_response, MockServerClient,
+        build_read_only_response, mock_tls_server_config, write_response, write_tls_response,
+        MockServerClient,
     };
 
     #[test]
@@ -873,7 +1394,42 @@ mod tests {
             b"HTTP/1.1 200 Ok\n\n{\"current_cycle\":{\"id\":\"fake id\", \"is_pox_active\":false}}",
         );
         let res = h.join().unwrap();
-        assert!(matches!(res, Err(ClientError::ReqwestError(_))));
+        assert!(matches!(res, Err(ClientError::JsonDeserializationError(_))));
+    }
+
+    #[test]
+    fn announced_oversized_response_should_fail() {
+        let mock = MockServerClient::new();
+        let max_bytes = mock.config.response_limits.account;
+        let h = spawn(move || mock.client.get_account_nonce(&mock.client.stacks_address));
+        let response = format!("HTTP/1.1 200 OK\nContent-Length: {}\n\n", max_bytes + 1);
+        write_response(mock.server, response.as_bytes());
+        let res = h.join().unwrap();
+        assert!(matches!(res, Err(ClientError::ResponseTooLarge(limit)) if limit == max_bytes));
+    }
+
+    #[test]
+    fn endless_response_should_fail_without_buffering() {
+        let mock = MockServerClient::new();
+        let max_bytes = mock.config.response_limits.account;
+        let h = spawn(move || mock.client.get_account_nonce(&mock.client.stacks_address));
+        let mut stream = mock.server.accept().unwrap().0;
+        let _ = stream.read(&mut [0u8; 1024]).unwrap();
+        stream
+            .write_all(b"HTTP/1.1 200 OK\n\n{\"nonce\": 0, \"balance\": \"0x")
+            .unwrap();
+        // Keep streaming until the client gives up. If it buffered the whole body, it never would.
+        let chunk = [b'0'; 4096];
+        let mut written = 0u64;
+        while !h.is_finished() && written < 1024 * max_bytes {
+            if stream.write_all(&chunk).is_err() {
+                break;
+            }
+            written += chunk.len() as u64;
+        }
+        drop(stream);
+        let res = h.join().unwrap();
+        assert!(matches!(res, Err(ClientError::ResponseTooLarge(limit)) if limit == max_bytes));
     }
 
     #[test]
@@ -988,6 +1544,36 @@ mod tests {
         assert!(h.join().unwrap().is_ok());
     }
 
+    #[test]
+    fn build_vote_for_aggregate_public_keys_should_succeed() {
+        let mock = MockServerClient::new();
+        let votes: Vec<_> = (0..3)
+            .map(|round| (round, Point::from(Scalar::random(&mut rand::thread_rng()))))
+            .collect();
+        let nonce = thread_rng().next_u64();
+        let signer_index = thread_rng().next_u32();
+        let reward_cycle = thread_rng().next_u64();
+
+        let unsigned_tx = mock
+            .client
+            .build_unsigned_vote_for_aggregate_public_keys(
+                signer_index,
+                &votes,
+                reward_cycle,
+                nonce,
+            )
+            .unwrap();
+        let tx = mock.client.sign_transaction(unsigned_tx).unwrap();
+        let params = NakamotoSigners::parse_votes_for_aggregate_public_keys(&tx).unwrap();
+        assert_eq!(params.len(), votes.len());
+        for (params, (round, point)) in params.iter().zip(votes.iter()) {
+            assert_eq!(params.signer_index, u64::from(signer_index));
+            assert_eq!(params.voting_round, *round);
+            assert_eq!(params.aggregate_key, *point);
+            assert_eq!(params.reward_cycle, reward_cycle);
+        }
+    }
+
     #[test]
     fn broadcast_vote_for_aggregate_public_key_should_succeed() {
         let mock = MockServerClient::new();
@@ -1256,6 +1842,111 @@ mod tests {
         assert_eq!(h.join().unwrap().unwrap(), peer_info);
     }
 
+    #[test]
+    fn get_peer_info_over_tls_should_succeed() {
+        let mock = MockServerClient::new_tls(true);
+        let (response, peer_info) = build_get_peer_info_response(None, None);
+        let h = spawn(move || mock.client.get_peer_info());
+        write_tls_response(mock.server, mock_tls_server_config(), response.as_bytes());
+        assert_eq!(h.join().unwrap().unwrap(), peer_info);
+    }
+
+    #[test]
+    fn untrusted_certificate_should_fail_with_tls_error() {
+        let mock = MockServerClient::new_tls(false);
+        let (response, _) = build_get_peer_info_response(None, None);
+        let h = spawn(move || mock.client.get_peer_info());
+        write_tls_response(mock.server, mock_tls_server_config(), response.as_bytes());
+        // The failed verification is not retried
+        assert!(matches!(h.join().unwrap(), Err(ClientError::TlsError(_))));
+    }
+
+    #[test]
+    fn requests_should_be_paced_by_rate_limiter() {
+        let mut mock = MockServerClient::new();
+        mock.config.rate_limit_config = RateLimitConfig {
+            requests_per_second: 2.0,
+            max_queue_wait: Duration::from_secs(5),
+        };
+        let client = StacksClient::from(&mock.config);
+        let (response, _) = build_get_peer_info_response(None, None);
+        let h = spawn(move || {
+            let start = Instant::now();
+            for _ in 0..4 {
+                client.get_peer_info().expect("Failed to get peer info");
+            }
+            start.elapsed()
+        });
+        for _ in 0..4 {
+            write_response(mock.server.try_clone().unwrap(), response.as_bytes());
+        }
+        // The first two requests are served by the initial burst. The last two wait ~500ms each.
+        assert!(h.join().unwrap() >= Duration::from_millis(900));
+    }
+
+    #[test]
+    fn applying_the_node_config_should_keep_the_rate_limiter_state() {
+        let mock = MockServerClient::new();
+        let mut client = StacksClient::from(&mock.config);
+        client.rate_limiter.pause_for(Duration::from_secs(60));
+
+        let mut config = mock.config.clone();
+        client.apply_node_config(&config);
+        assert!(client.node_backoff_remaining().is_some());
+
+        config.rate_limit_config.requests_per_second += 1.0;
+        client.apply_node_config(&config);
+        assert!(client.node_backoff_remaining().is_none());
+    }
+
+    #[test]
+    fn retry_after_should_be_honored() {
+        let mock = MockServerClient::new();
+        let (response, peer_info) = build_get_peer_info_response(None, None);
+        let h = spawn(move || {
+            let start = Instant::now();
+            let peer_info = mock.client.get_peer_info();
+            (peer_info, start.elapsed())
+        });
+        write_response(
+            mock.server.try_clone().unwrap(),
+            b"HTTP/1.1 429 Too Many Requests\nRetry-After: 1\n\n",
+        );
+        write_response(mock.server, response.as_bytes());
+        let (result, elapsed) = h.join().unwrap();
+        assert_eq!(result.unwrap(), peer_info);
+        assert!(elapsed >= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn get_sortition_info_should_be_cached_until_cleared() {
+        let mock = MockServerClient::new();
+        let (peer_response, peer_info) = build_get_peer_info_response(None, None);
+        // Clones share the sortition info cache
+        let cached_client = mock.client.clone();
+        let h = spawn(move || mock.client.get_current_coordinator_metadata());
+        write_response(mock.server, peer_response.as_bytes());
+        let metadata = h.join().unwrap().unwrap();
+        assert_eq!(metadata.burn_block_height, peer_info.burn_block_height);
+        assert_eq!(metadata.pox_consensus_hash, peer_info.pox_consensus);
+        assert!(metadata.burn_header_hash.is_none());
+
+        // Served from the cache without contacting the stacks node
+        assert_eq!(
+            cached_client.get_sortition_info().unwrap(),
+            SortitionInfo::from(&peer_info)
+        );
+
+        // A new burn block at the same height, e.g. after a fork, replaces the cached info
+        cached_client.clear_sortition_info_cache();
+        let (peer_response, peer_info) =
+            build_get_peer_info_response(Some(peer_info.burn_block_height), None);
+        let mock = MockServerClient::from_config(mock.config);
+        let h = spawn(move || cached_client.get_sortition_info());
+        write_response(mock.server, peer_response.as_bytes());
+        assert_eq!(h.join().unwrap().unwrap(), SortitionInfo::from(&peer_info));
+    }
+
     #[test]
     fn get_last_round_should_succeed() {
         let mock = MockServerClient::new();
@@ -1267,6 +1958,43 @@ mod tests {
         assert_eq!(h.join().unwrap().unwrap().unwrap(), round);
     }
 
+    #[test]
+    fn is_round_expired_should_compare_the_burn_height_to_the_round_expiry() {
+        let expired_at = |burn_block_height: u64| {
+            let mock = MockServerClient::new();
+            let h = spawn(move || mock.client.is_round_expired(0, 0));
+            write_response(
+                mock.server,
+                build_read_only_response(&ClarityValue::some(ClarityValue::UInt(100)).unwrap())
+                    .as_bytes(),
+            );
+            let mock = MockServerClient::from_config(mock.config);
+            write_response(
+                mock.server,
+                build_read_only_response(&ClarityValue::UInt(6)).as_bytes(),
+            );
+            let mock = MockServerClient::from_config(mock.config);
+            write_response(
+                mock.server,
+                build_get_peer_info_response(Some(burn_block_height), None)
+                    .0
+                    .as_bytes(),
+            );
+            h.join().unwrap().unwrap()
+        };
+        assert!(!expired_at(105));
+        assert!(expired_at(106));
+
+        // A round without votes never expires
+        let mock = MockServerClient::new();
+        let h = spawn(move || mock.client.is_round_expired(0, 1));
+        write_response(
+            mock.server,
+            build_read_only_response(&ClarityValue::none()).as_bytes(),
+        );
+        assert!(!h.join().unwrap().unwrap());
+    }
+
     #[test]
     fn get_reward_set_should_succeed() {
         let mock = MockServerClient::new();
@@ -1321,6 +2049,35 @@ mod tests {
         assert_eq!(h.join().unwrap().unwrap(), None);
     }
 
+    #[test]
+    fn get_vote_for_aggregate_public_key_should_fail_on_a_map_entry() {
+        // The vote used to be read as the raw votes map entry
+        let mock = MockServerClient::new();
+        let point = Point::from(Scalar::random(&mut rand::thread_rng()));
+        let stacks_address = mock.client.stacks_address;
+        let map_entry = ClarityValue::some(ClarityValue::Tuple(
+            TupleData::from_data(vec![
+                (
+                    "aggregate-public-key".into(),
+                    ClarityValue::buff_from(point.compress().as_bytes().to_vec()).unwrap(),
+                ),
+                ("signer-weight".into(), ClarityValue::UInt(1)),
+            ])
+            .unwrap(),
+        ))
+        .unwrap();
+        let response = build_read_only_response(&map_entry);
+        let h = spawn(move || {
+            mock.client
+                .get_vote_for_aggregate_public_key(0, 0, stacks_address)
+        });
+        write_response(mock.server, response.as_bytes());
+        assert!(matches!(
+            h.join().unwrap(),
+            Err(ClientError::ClarityError(_))
+        ));
+    }
+
     #[test]
     fn get_round_vote_weight_should_succeed() {
         let mock = MockServerClient::new();
@@ -1338,6 +2095,101 @@ mod tests {
         assert_eq!(h.join().unwrap().unwrap(), None);
     }
 
+    #[test]
+    fn get_aggregate_key_tally_should_succeed() {
+        let mock = MockServerClient::new();
+        let point = Point::from(Scalar::random(&mut rand::thread_rng()));
+        let weight = rand::thread_rng().next_u64() as u128;
+        let h = spawn(move || mock.client.get_aggregate_key_tally(0, 1, &point));
+        let tally_response = build_get_aggregate_key_tally_response(Some(weight));
+        write_response(mock.server, tally_response.as_bytes());
+        assert_eq!(h.join().unwrap().unwrap(), Some(weight));
+
+        let mock = MockServerClient::new();
+        let h = spawn(move || mock.client.get_aggregate_key_tally(0, 1, &point));
+        let tally_response = build_get_aggregate_key_tally_response(None);
+        write_response(mock.server, tally_response.as_bytes());
+        assert_eq!(h.join().unwrap().unwrap(), None);
+    }
+
+    #[test]
+    fn get_candidate_info_should_succeed() {
+        let mock = MockServerClient::new();
+        let point = Point::from(Scalar::random(&mut rand::thread_rng()));
+        let h = spawn(move || mock.client.get_candidate_info(0, 1, &point));
+        let candidate_response = build_get_candidate_info_response(3000, Some(7000));
+        write_response(mock.server, candidate_response.as_bytes());
+        assert_eq!(
+            h.join().unwrap().unwrap(),
+            CandidateInfo {
+                candidate_weight: 3000,
+                total_weight: Some(7000),
+            }
+        );
+
+        let mock = MockServerClient::new();
+        let h = spawn(move || mock.client.get_candidate_info(0, 1, &point));
+        let candidate_response = build_get_candidate_info_response(0, None);
+        write_response(mock.server, candidate_response.as_bytes());
+        assert_eq!(
+            h.join().unwrap().unwrap(),
+            CandidateInfo {
+                candidate_weight: 0,
+                total_weight: None,
+            }
+        );
+    }
+
+    #[test]
+    fn get_round_info_should_report_approved_key() {
+        let mock = MockServerClient::new();
+        let point = Point::from(Scalar::random(&mut rand::thread_rng()));
+        let h = spawn(move || mock.client.get_round_info(0, 0));
+        write_response(
+            mock.server.try_clone().unwrap(),
+            build_get_round_info_response(Some((7, 7000))).as_bytes(),
+        );
+        write_response(
+            mock.server,
+            build_get_approved_aggregate_key_response(Some(point)).as_bytes(),
+        );
+        assert_eq!(
+            h.join().unwrap().unwrap(),
+            Some(RoundInfo {
+                votes_count: 7,
+                votes_weight: 7000,
+                approved_aggregate_key: Some(point),
+            })
+        );
+    }
+
+    #[test]
+    fn get_round_info_should_report_pending_round() {
+        let mock = MockServerClient::new();
+        let h = spawn(move || mock.client.get_round_info(0, 0));
+        write_response(
+            mock.server.try_clone().unwrap(),
+            build_get_round_info_response(Some((2, 1000))).as_bytes(),
+        );
+        write_response(
+            mock.server,
+            build_get_approved_aggregate_key_response(None).as_bytes(),
+        );
+        assert_eq!(
+            h.join().unwrap().unwrap(),
+            Some(RoundInfo {
+                votes_count: 2,
+                votes_weight: 1000,
+                approved_aggregate_key: None,
+            })
+        );
+
+        let mock = MockServerClient::new();
+        let h = spawn(move || mock.client.get_round_info(0, 1));
+        write_response(mock.server, build_get_round_info_response(None).as_bytes());
+        assert_eq!(h.join().unwrap().unwrap(), None);
+    }
+
     #[test]
     fn get_vote_threshold_weight_should_succeed() {
         let mock = MockServerClient::new();
@@ -1370,4 +2222,125 @@ mod tests {
         write_response(mock.server, response.as_bytes());
         assert_eq!(h.join().unwrap().unwrap(), estimate);
     }
+
+    /// Build a stacker-db chunk response holding a miner's nonce request for a block of `reward_cycle`
+    fn build_miner_proposal_response(reward_cycle: u64) -> (Vec<u8>, BlockProposal) {
+        let block_proposal = BlockProposal {
+            block: NakamotoBlock {
+                header: NakamotoBlockHeader::empty(),
+                txs: vec![],
+            },
+            burn_height: 1,
+            reward_cycle,
+        };
+        let nonce_request = NonceRequest {
+            dkg_id: 1,
+            sign_id: 1,
+            sign_iter_id: 1,
+            message: block_proposal.serialize_to_vec(),
+            is_taproot: false,
+            merkle_root: None,
+        };
+        let message = SignerMessage::Packet(Packet {
+            msg: Message::NonceRequest(nonce_request),
+            sig: vec![],
+        });
+        let mut response = b"HTTP/1.1 200 OK\n\n".to_vec();
+        response.extend(message.serialize_to_vec());
+        (response, block_proposal)
+    }
+
+    #[test]
+    fn get_pending_block_proposals_should_succeed() {
+        let mock = MockServerClient::new();
+        let (response, block_proposal) = build_miner_proposal_response(1);
+        let h = spawn(move || mock.client.get_pending_block_proposals(1));
+        let request = write_response(mock.server, &response);
+        let request = String::from_utf8_lossy(&request);
+        assert!(request.starts_with("GET /v2/stackerdb/"));
+        assert_eq!(h.join().unwrap().unwrap(), vec![block_proposal]);
+    }
+
+    #[test]
+    fn get_pending_block_proposals_should_skip_other_reward_cycles() {
+        let mock = MockServerClient::new();
+        let (response, _) = build_miner_proposal_response(2);
+        let h = spawn(move || mock.client.get_pending_block_proposals(1));
+        write_response(mock.server, &response);
+        assert!(h.join().unwrap().unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_pending_block_proposals_should_handle_empty_slot() {
+        let mock = MockServerClient::new();
+        let h = spawn(move || mock.client.get_pending_block_proposals(1));
+        write_response(mock.server, b"HTTP/1.1 404 Not Found\n\n");
+        assert!(h.join().unwrap().unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_block_by_id_should_succeed() {
+        let mock = MockServerClient::new();
+        let block = NakamotoBlock {
+            header: NakamotoBlockHeader::empty(),
+            txs: vec![],
+        };
+        let block_id = block.block_id();
+        let mut response = b"HTTP/1.1 200 OK\n\n".to_vec();
+        response.extend(block.serialize_to_vec());
+        let h = spawn(move || mock.client.get_block_by_id(&block_id));
+        let request = write_response(mock.server, &response);
+        let request = String::from_utf8_lossy(&request);
+        assert!(request.starts_with(&format!("GET /v3/blocks/{block_id}")));
+        assert_eq!(h.join().unwrap().unwrap(), Some(block));
+    }
+
+    #[test]
+    fn get_block_by_id_should_handle_unknown_blocks() {
+        let mock = MockServerClient::new();
+        let block_id = StacksBlockId([1; 32]);
+        let h = spawn(move || mock.client.get_block_by_id(&block_id));
+        write_response(mock.server, b"HTTP/1.1 404 Not Found\n\n");
+        assert_eq!(h.join().unwrap().unwrap(), None);
+    }
+
+    #[test]
+    fn get_block_responses_should_succeed() {
+        let mock = MockServerClient::new();
+        let signer_slot = ClarityValue::Tuple(
+            TupleData::from_data(vec![
+                ("num-slots".into(), ClarityValue::UInt(13)),
+                (
+                    "signer".into(),
+                    ClarityValue::Principal(PrincipalData::Standard(
+                        mock.client.stacks_address.into(),
+                    )),
+                ),
+            ])
+            .unwrap(),
+        );
+        let signer_slots = ClarityValue::okay(
+            ClarityValue::cons_list_unsanitized(vec![signer_slot.clone(), signer_slot]).unwrap(),
+        )
+        .unwrap();
+        let block_response = BlockResponse::Rejected(BlockRejection::new(
+            Sha512Trunc256Sum([1; 32]),
+            RejectCode::ConnectivityIssues,
+        ));
+        let mut chunk_response = b"HTTP/1.1 200 OK\n\n".to_vec();
+        chunk_response
+            .extend(SignerMessage::BlockResponse(block_response.clone()).serialize_to_vec());
+        let h = spawn(move || mock.client.get_block_responses(1));
+        let mock_server = mock.server;
+        write_response(
+            mock_server.try_clone().unwrap(),
+            build_read_only_response(&signer_slots).as_bytes(),
+        );
+        let request = write_response(mock_server.try_clone().unwrap(), &chunk_response);
+        let request = String::from_utf8_lossy(&request);
+        assert!(request.starts_with("GET /v2/stackerdb/"));
+        // The second signer has not responded to a block yet
+        write_response(mock_server, b"HTTP/1.1 404 Not Found\n\n");
+        assert_eq!(h.join().unwrap().unwrap(), vec![block_response]);
+    }
 }
@@ -14,28 +14,87 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::fs;
 use std::net::{SocketAddr, ToSocketAddrs};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 
+use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
 use blockstack_lib::chainstate::stacks::TransactionVersion;
-use libsigner::SignerEntries;
+use libsigner::v1::messages::MessageSlotID;
+use libsigner::{
+    NodeTransport, SignerEntries, SignerSlotOwners, DEFAULT_CHUNK_COMPRESSION_THRESHOLD,
+};
 use serde::Deserialize;
+use slog::{slog_info, slog_warn};
 use stacks_common::address::{
     AddressHashMode, C32_ADDRESS_VERSION_MAINNET_SINGLESIG, C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
 };
 use stacks_common::consts::{CHAIN_ID_MAINNET, CHAIN_ID_TESTNET};
 use stacks_common::types::chainstate::{StacksAddress, StacksPrivateKey, StacksPublicKey};
 use stacks_common::types::PrivateKey;
+use stacks_common::{info, warn};
 use wsts::curve::scalar::Scalar;
+use zeroize::Zeroizing;
 
-use crate::client::SignerSlotID;
+use crate::alerting::AlertNotifier;
+use crate::client::{
+    RateLimitConfig, ResponseLimits, RetryConfig, SignerSlotID, SlotLayout, StacksClient,
+};
+use crate::recording::EventRecorder;
+use crate::v1::signer::Signer;
+use crate::{signer_info, signer_warn};
 
 const EVENT_TIMEOUT_MS: u64 = 5000;
+const HEALTH_CHECK_TIMEOUT_MS: u64 = 60_000;
+const BLOCK_VALIDATION_TIMEOUT_MS: u64 = 120_000;
+const DKG_ROUND_STALL_TIMEOUT_MS: u64 = 600_000;
+// How long signing packets of an iteration we were not asked to sign yet are accepted
+const PACKET_FUTURE_TOLERANCE_MS: u64 = 5_000;
+// How long a shutdown waits for the signers' in-progress operations to complete
+const SHUTDOWN_TIMEOUT_MS: u64 = 30_000;
+// Mocknet blocks are small and its signer sets co-located, so stalls are noticed sooner
+const MOCKNET_BLOCK_VALIDATION_TIMEOUT_MS: u64 = 30_000;
+const MOCKNET_DKG_ROUND_STALL_TIMEOUT_MS: u64 = 60_000;
+// The port the stacks node serves its RPC interface on unless configured otherwise
+const STACKS_NODE_RPC_PORT: u16 = 20443;
+// Default DKG and signing round phase timeouts, sized for mainnet signer sets whose messages
+// take several stacker-db round trips to reach every signer
+const DKG_PUBLIC_TIMEOUT_MS: u64 = 120_000;
+const DKG_PRIVATE_TIMEOUT_MS: u64 = 180_000;
+const DKG_END_TIMEOUT_MS: u64 = 240_000;
+const NONCE_TIMEOUT_MS: u64 = 60_000;
+const SIGN_TIMEOUT_MS: u64 = 120_000;
 // Default transaction fee to use in microstacks (if unspecificed in the config file)
 const TX_FEE_USTX: u64 = 10_000;
+// Default maximum median transaction fee in microstacks of a block we are willing to sign
+const MAX_ACCEPTABLE_MEDIAN_FEE_RATE: u64 = 100_000_000;
+// Default number of times a block proposal is submitted to the stacks node for validation before it is rejected
+const MAX_VALIDATION_ATTEMPTS: u8 = 3;
+// Default number of signature share requests from the same coordinator rejected in a signing round
+// before the round is NACKed to the other signers
+const MAX_REJECTED_REQUESTS_PER_ROUND: u32 = 3;
+// Defaults of the `[stale_node_nack]` section knobs left unset
+const NACK_THRESHOLD_PERCENT: u32 = 70;
+const NACK_BACK_OFF_DURATION_MS: u64 = 30_000;
+const NACK_TTL_MS: u64 = 60_000;
+const NACK_RESEND_COOLDOWN_MS: u64 = 10_000;
+const MAX_NACKS_PER_BLOCK: u32 = 3;
+const NACK_REPORT_INTERVAL_MS: u64 = 30_000;
+/// The longest back-off after stale node NACKs, beyond which a signer would sit out whole tenures
+const MAX_NACK_BACK_OFF_DURATION: Duration = Duration::from_secs(600);
+/// The config fields the signer private key can be loaded from, exactly one of which must be set
+#[cfg(feature = "private_key_cmd")]
+const PRIVATE_KEY_SOURCES: &str =
+    "stacks_private_key, stacks_private_key_path or stacks_private_key_cmd";
+#[cfg(not(feature = "private_key_cmd"))]
+const PRIVATE_KEY_SOURCES: &str = "stacks_private_key or stacks_private_key_path";
+/// The prefix of the environment variables overriding config file fields, e.g.
+/// `STACKS_SIGNER_NODE_HOST` overrides `node_host`
+pub const ENV_VAR_PREFIX: &str = "STACKS_SIGNER_";
 
 #[derive(thiserror::Error, Debug)]
 /// An error occurred parsing the provided configuration
@@ -52,6 +111,21 @@ pub enum ConfigError {
     /// An unsupported address version
     #[error("Failed to convert private key to address: unsupported address version.")]
     UnsupportedAddressVersion,
+    /// A field's value is well-formed, but not one the signer can run with
+    #[error("{0}: {1}")]
+    InvalidField(String, String),
+    /// The configuration has one or more invalid fields
+    #[error("{}", format_violations(.0))]
+    Violations(Vec<ConfigError>),
+}
+
+/// List the given configuration violations, one per line
+fn format_violations(violations: &[ConfigError]) -> String {
+    let mut message = format!("{} invalid configuration field(s):", violations.len());
+    for violation in violations {
+        message.push_str(&format!("\n  - {violation}"));
+    }
+    message
 }
 
 #[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -108,6 +182,147 @@ impl Network {
             Self::Testnet | Self::Mocknet => false,
         }
     }
+
+    /// The settings derived from the network, before any config file override
+    pub const fn profile(&self) -> NetworkProfile {
+        let (block_validation_timeout_ms, dkg_round_stall_timeout_ms) = match self {
+            Self::Mainnet | Self::Testnet => {
+                (BLOCK_VALIDATION_TIMEOUT_MS, DKG_ROUND_STALL_TIMEOUT_MS)
+            }
+            Self::Mocknet => (
+                MOCKNET_BLOCK_VALIDATION_TIMEOUT_MS,
+                MOCKNET_DKG_ROUND_STALL_TIMEOUT_MS,
+            ),
+        };
+        NetworkProfile {
+            mainnet: self.is_mainnet(),
+            chain_id: self.to_chain_id(),
+            transaction_version: self.to_transaction_version(),
+            address_version: self.to_address_version(),
+            default_node_port: STACKS_NODE_RPC_PORT,
+            default_block_validation_timeout: Duration::from_millis(block_validation_timeout_ms),
+            default_dkg_round_stall_timeout: Duration::from_millis(dkg_round_stall_timeout_ms),
+        }
+    }
+}
+
+/// The settings a signer derives from the network it runs on, so that they cannot be
+/// configured inconsistently with each other
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NetworkProfile {
+    /// Whether the network is mainnet
+    pub mainnet: bool,
+    /// The chain id of the network's transactions. May be overridden by the config file.
+    pub chain_id: u32,
+    /// The version of the network's transactions
+    pub transaction_version: TransactionVersion,
+    /// The version of the network's single signature addresses
+    pub address_version: u8,
+    /// The stacks node port used when `node_host` does not specify one
+    pub default_node_port: u16,
+    /// The block validation timeout used when the config file does not specify one
+    pub default_block_validation_timeout: Duration,
+    /// The DKG round stall timeout used when the config file does not specify one
+    pub default_dkg_round_stall_timeout: Duration,
+}
+
+/// How to vote on an otherwise valid block that is missing some of the transactions the signers
+/// expect it to contain, such as their DKG votes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingTransactionPolicy {
+    /// Reject the block
+    #[default]
+    Reject,
+    /// Accept the block, logging the missing transactions
+    AcceptAndWarn,
+    /// Accept the block, unless some of its missing transactions have been missing from this
+    /// many consecutive proposals
+    RejectAfterNBlocks(u32),
+}
+
+impl std::fmt::Display for MissingTransactionPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Reject => write!(f, "reject"),
+            Self::AcceptAndWarn => write!(f, "accept_and_warn"),
+            Self::RejectAfterNBlocks(blocks) => write!(f, "reject_after_n_blocks ({blocks})"),
+        }
+    }
+}
+
+/// A custom block acceptance policy, applied to block proposals before they are submitted to
+/// the stacks node for validation. Declined blocks are rejected without being validated.
+pub trait BlockProposalFilter: Send + Sync {
+    /// Whether the signer may sign the proposed block, provided the stacks node validates it
+    fn should_accept(&self, block: &NakamotoBlock) -> bool;
+}
+
+impl std::fmt::Debug for dyn BlockProposalFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("BlockProposalFilter")
+    }
+}
+
+/// The format of the signers' logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line, with the record's key-values as fields
+    Json,
+}
+
+impl LogFormat {
+    /// Read the log format set by the config file at `path` or by the environment, without
+    /// loading the rest of the configuration, so that the global logger can be set up before
+    /// loading the configuration logs anything. Falls back to text if the format cannot be read,
+    /// leaving it to the configuration loading to report why.
+    pub fn peek_from_file(path: &Path) -> Self {
+        let log_format = std::env::var(format!("{ENV_VAR_PREFIX}LOG_FORMAT"))
+            .ok()
+            .or_else(|| {
+                let fields: toml::value::Table =
+                    toml::from_str(&fs::read_to_string(path).ok()?).ok()?;
+                fields.get("log_format")?.as_str().map(str::to_string)
+            });
+        match log_format.as_deref() {
+            Some("json") => Self::Json,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// How the signer handles NACKs, the rejections signers send when their stacks node is too far
+/// behind to validate a block proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleNodeNackPolicy {
+    /// The percentage of the signer set that must NACK a block before it is treated as proposed
+    /// to stale nodes, between 1 and 100
+    pub nack_threshold_percent: u32,
+    /// How long to back off from a block once its NACKs reach the threshold
+    pub back_off_duration: Duration,
+    /// How long a received NACK counts towards the threshold
+    pub nack_ttl: Duration,
+    /// The minimum time between two of our NACKs of the same block
+    pub resend_cooldown: Duration,
+    /// The maximum number of NACKs we send for a single block
+    pub max_nacks_per_block: u32,
+    /// How often to report the number of signers we received a NACK from to the other signers
+    pub report_interval: Duration,
+}
+
+impl Default for StaleNodeNackPolicy {
+    fn default() -> Self {
+        Self {
+            nack_threshold_percent: NACK_THRESHOLD_PERCENT,
+            back_off_duration: Duration::from_millis(NACK_BACK_OFF_DURATION_MS),
+            nack_ttl: Duration::from_millis(NACK_TTL_MS),
+            resend_cooldown: Duration::from_millis(NACK_RESEND_COOLDOWN_MS),
+            max_nacks_per_block: MAX_NACKS_PER_BLOCK,
+            report_interval: Duration::from_millis(NACK_REPORT_INTERVAL_MS),
+        }
+    }
 }
 
 /// The Configuration info needed for an individual signer per reward cycle
@@ -117,6 +332,8 @@ pub struct SignerConfig {
     pub reward_cycle: u64,
     /// The signer ID assigned to this signer to be used in DKG and Sign rounds
     pub signer_id: u32,
+    /// The human-readable name of this signer, used to identify it in logs and metrics
+    pub signer_name: String,
     /// The signer stackerdb slot id (may be different from signer_id)
     pub signer_slot_id: SignerSlotID,
     /// This signer's key ids
@@ -125,14 +342,18 @@ pub struct SignerConfig {
     pub signer_entries: SignerEntries,
     /// The signer slot ids of all signers registered for this reward cycle
     pub signer_slot_ids: Vec<SignerSlotID>,
+    /// The owners of the signers stacker-db slots, which chunks read from stacker-db must be signed by
+    pub slot_owners: SignerSlotOwners,
     /// The Scalar representation of the private key for signer communication
     pub ecdsa_private_key: Scalar,
     /// The private key for this signer
     pub stacks_private_key: StacksPrivateKey,
     /// The node host for this signer
     pub node_host: String,
-    /// Whether this signer is running on mainnet or not
-    pub mainnet: bool,
+    /// How to connect to the stacks node
+    pub node_transport: NodeTransport,
+    /// The settings derived from the network the signer runs on
+    pub network: NetworkProfile,
     /// timeout to gather DkgPublicShares messages
     pub dkg_public_timeout: Option<Duration>,
     /// timeout to gather DkgPrivateShares messages
@@ -143,19 +364,179 @@ pub struct SignerConfig {
     pub nonce_timeout: Option<Duration>,
     /// timeout to gather signature shares
     pub sign_timeout: Option<Duration>,
+    /// The time to wait for the stacks node to validate a proposed block before rejecting it
+    pub block_validation_timeout: Duration,
+    /// The time after which a DKG round whose vote weight stopped increasing is abandoned
+    pub dkg_round_stall_timeout: Duration,
+    /// How long a signer's packets of a signing round iteration newer than the latest one we
+    /// received a request for are accepted
+    pub packet_future_tolerance: Duration,
     /// the STX tx fee to use in uSTX.
     pub tx_fee_ustx: u64,
     /// If set, will use the estimated fee up to this amount.
     pub max_tx_fee_ustx: Option<u64>,
+    /// The maximum median transaction fee in uSTX of a block we are willing to sign
+    pub max_acceptable_median_fee_rate: u64,
+    /// The number of times a block proposal is submitted to the stacks node for validation
+    /// before it is rejected
+    pub max_validation_attempts: u8,
+    /// The number of signature share requests from the same coordinator rejected in a signing
+    /// round before the round is NACKed to the other signers
+    pub max_rejected_requests_per_round: u32,
     /// The path to the signer's database file
     pub db_path: PathBuf,
+    /// The backoff policy used when retrying requests to the stacks node
+    pub retry_config: RetryConfig,
+    /// The backoff policy used when retrying writes to stacker-db
+    pub stackerdb_write_retry_config: RetryConfig,
+    /// The maximum size of responses read from the stacks node
+    pub response_limits: ResponseLimits,
+    /// The size in bytes above which messages written to stacker-db are compressed.
+    /// If None, messages are always written uncompressed.
+    pub chunk_compression_threshold: Option<usize>,
+    /// The stacker-db slot each type of message is written to and read from
+    pub slot_layout: SlotLayout,
+    /// Whether to clear the signer's stacker-db slots when its tenure ends
+    pub clear_stackerdb_on_tenure_end: bool,
+    /// Whether to submit batches of block proposals for validation concurrently
+    pub parallel_validation: bool,
+    /// How to vote on blocks missing some of the expected transactions
+    pub missing_transaction_policy: MissingTransactionPolicy,
+    /// How to handle stale node NACKs. If None, NACKs are not processed.
+    pub stale_node_nack_policy: Option<StaleNodeNackPolicy>,
+    /// Records the messages the signer sends, if event recording is enabled
+    pub event_recorder: Option<EventRecorder>,
+    /// Alerts operators of critical events, if an alert webhook is configured
+    pub alert_notifier: Option<AlertNotifier>,
+    /// Declines block proposals that do not meet custom criteria before they are validated
+    pub block_proposal_filter: Option<Arc<dyn BlockProposalFilter>>,
+}
+
+/// Where the value of a configuration field came from. Later sources take precedence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// The field was not set, so its default value is used
+    Default,
+    /// The field was set in the config file
+    File,
+    /// The field was set by a `STACKS_SIGNER_*` environment variable
+    Environment,
 }
 
+/// The type an environment variable's value is coerced to, to override a config file field
+#[derive(Clone, Copy, Debug)]
+enum FieldKind {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Table,
+}
+
+impl FieldKind {
+    /// Coerce the environment variable's value to a TOML value of this kind
+    fn coerce(&self, value: &str) -> Option<toml::Value> {
+        match self {
+            Self::String => Some(toml::Value::String(value.to_string())),
+            Self::Integer => value.trim().parse().ok().map(toml::Value::Integer),
+            Self::Float => value.trim().parse().ok().map(toml::Value::Float),
+            Self::Boolean => value.trim().parse().ok().map(toml::Value::Boolean),
+            Self::Table => toml::from_str::<toml::value::Table>(&format!("value = {value}"))
+                .ok()?
+                .remove("value")
+                .filter(toml::Value::is_table),
+        }
+    }
+
+    /// A description of the values accepted for this kind, used in error messages
+    fn expected(&self) -> &'static str {
+        match self {
+            Self::String => "a string",
+            Self::Integer => "an integer",
+            Self::Float => "a number",
+            Self::Boolean => "true or false",
+            Self::Table => "an inline TOML table",
+        }
+    }
+}
+
+/// Every config file field, with the type its environment variable is coerced to
+const CONFIG_FIELDS: &[(&str, FieldKind)] = &[
+    ("node_host", FieldKind::String),
+    ("dangerous_skip_tls_verify", FieldKind::Boolean),
+    ("endpoint", FieldKind::String),
+    ("stacks_private_key", FieldKind::String),
+    ("stacks_private_key_path", FieldKind::String),
+    #[cfg(feature = "private_key_cmd")]
+    ("stacks_private_key_cmd", FieldKind::String),
+    ("network", FieldKind::String),
+    ("chain_id", FieldKind::Integer),
+    ("signer_name", FieldKind::String),
+    ("event_timeout_ms", FieldKind::Integer),
+    ("dkg_public_timeout_ms", FieldKind::Integer),
+    ("dkg_private_timeout_ms", FieldKind::Integer),
+    ("dkg_end_timeout_ms", FieldKind::Integer),
+    ("nonce_timeout_ms", FieldKind::Integer),
+    ("sign_timeout_ms", FieldKind::Integer),
+    ("block_validation_timeout_ms", FieldKind::Integer),
+    ("dkg_round_stall_timeout_ms", FieldKind::Integer),
+    ("packet_future_tolerance_ms", FieldKind::Integer),
+    ("timeouts", FieldKind::Table),
+    ("tx_fee_ustx", FieldKind::Integer),
+    ("max_tx_fee_ustx", FieldKind::Integer),
+    ("max_acceptable_median_fee_rate", FieldKind::Integer),
+    ("max_validation_attempts", FieldKind::Integer),
+    ("max_rejected_requests_per_round", FieldKind::Integer),
+    ("auth_password", FieldKind::String),
+    ("db_path", FieldKind::String),
+    ("metrics_endpoint", FieldKind::String),
+    ("metrics_port", FieldKind::Integer),
+    ("health_check_timeout_ms", FieldKind::Integer),
+    ("shutdown_timeout_ms", FieldKind::Integer),
+    ("retry_initial_interval_ms", FieldKind::Integer),
+    ("retry_multiplier", FieldKind::Float),
+    ("retry_max_interval_ms", FieldKind::Integer),
+    ("retry_max_elapsed_time_ms", FieldKind::Integer),
+    ("retry_jitter", FieldKind::Float),
+    (
+        "stackerdb_write_retry_initial_interval_ms",
+        FieldKind::Integer,
+    ),
+    ("stackerdb_write_retry_multiplier", FieldKind::Float),
+    ("stackerdb_write_retry_max_interval_ms", FieldKind::Integer),
+    (
+        "stackerdb_write_retry_max_elapsed_time_ms",
+        FieldKind::Integer,
+    ),
+    ("stackerdb_write_retry_jitter", FieldKind::Float),
+    ("rate_limit_requests_per_second", FieldKind::Float),
+    ("rate_limit_max_queue_wait_ms", FieldKind::Integer),
+    ("max_response_bytes_account", FieldKind::Integer),
+    ("max_response_bytes_rpc", FieldKind::Integer),
+    ("max_response_bytes_stackerdb", FieldKind::Integer),
+    ("compress_stackerdb_chunks", FieldKind::Boolean),
+    ("chunk_compression_threshold_bytes", FieldKind::Integer),
+    ("stackerdb_slot_layout", FieldKind::Table),
+    ("clear_stackerdb_on_tenure_end", FieldKind::Boolean),
+    ("parallel_validation", FieldKind::Boolean),
+    ("missing_transaction_policy", FieldKind::String),
+    ("log_format", FieldKind::String),
+    (
+        "missing_transaction_reject_after_blocks",
+        FieldKind::Integer,
+    ),
+    ("stale_node_nack", FieldKind::Table),
+    ("record_events_path", FieldKind::String),
+    ("alert_webhook_url", FieldKind::String),
+];
+
 /// The parsed configuration for the signer
 #[derive(Clone, Debug)]
 pub struct GlobalConfig {
     /// endpoint to the stacks node
     pub node_host: String,
+    /// How to connect to the stacks node
+    pub node_transport: NodeTransport,
     /// endpoint to the event receiver
     pub endpoint: SocketAddr,
     /// The Scalar representation of the private key for signer communication
@@ -164,8 +545,12 @@ pub struct GlobalConfig {
     pub stacks_private_key: StacksPrivateKey,
     /// The signer's Stacks address
     pub stacks_address: StacksAddress,
-    /// The network to use. One of "mainnet" or "testnet".
+    /// The network to use. One of "mainnet", "testnet" or "mocknet".
     pub network: Network,
+    /// The settings derived from the network, with any config file override applied
+    pub network_profile: NetworkProfile,
+    /// The human-readable name of the signer. Defaults to `signer-{signer_id}` if unset.
+    pub signer_name: Option<String>,
     /// The time to wait for a response from the stacker-db instance
     pub event_timeout: Duration,
     /// timeout to gather DkgPublicShares messages
@@ -178,53 +563,285 @@ pub struct GlobalConfig {
     pub nonce_timeout: Option<Duration>,
     /// timeout to gather signature shares
     pub sign_timeout: Option<Duration>,
+    /// The time to wait for the stacks node to validate a proposed block before rejecting it
+    pub block_validation_timeout: Duration,
+    /// The time after which a DKG round whose vote weight stopped increasing is abandoned
+    pub dkg_round_stall_timeout: Duration,
+    /// How long a signer's packets of a signing round iteration newer than the latest one we
+    /// received a request for are accepted
+    pub packet_future_tolerance: Duration,
     /// the STX tx fee to use in uSTX.
     pub tx_fee_ustx: u64,
     /// the max STX tx fee to use in uSTX when estimating fees
     pub max_tx_fee_ustx: Option<u64>,
+    /// The maximum median transaction fee in uSTX of a block we are willing to sign
+    pub max_acceptable_median_fee_rate: u64,
+    /// The number of times a block proposal is submitted to the stacks node for validation
+    /// before it is rejected
+    pub max_validation_attempts: u8,
+    /// The number of signature share requests from the same coordinator rejected in a signing
+    /// round before the round is NACKed to the other signers
+    pub max_rejected_requests_per_round: u32,
     /// the authorization password for the block proposal endpoint
     pub auth_password: String,
     /// The path to the signer's database file
     pub db_path: PathBuf,
     /// Metrics endpoint
     pub metrics_endpoint: Option<SocketAddr>,
+    /// The port to serve the per-signer metrics on, in the Prometheus text format
+    pub metrics_port: Option<u16>,
+    /// The time after which the signer is reported unhealthy if the stacks node was not reached
+    /// or the event loop did not run
+    pub health_check_timeout: Duration,
+    /// How long a shutdown waits for the in-progress DKG and signing rounds to complete
+    pub shutdown_timeout: Duration,
+    /// The backoff policy used when retrying requests to the stacks node
+    pub retry_config: RetryConfig,
+    /// The backoff policy used when retrying writes to stacker-db
+    pub stackerdb_write_retry_config: RetryConfig,
+    /// The rate limit applied to requests to the stacks node
+    pub rate_limit_config: RateLimitConfig,
+    /// The maximum size of responses read from the stacks node
+    pub response_limits: ResponseLimits,
+    /// The size in bytes above which messages written to stacker-db are compressed.
+    /// If None, messages are always written uncompressed.
+    pub chunk_compression_threshold: Option<usize>,
+    /// The explicitly configured stacker-db slot layout. If None, the default layout restricted
+    /// to the slots the signers contract allocates is used.
+    pub slot_layout: Option<SlotLayout>,
+    /// Whether to overwrite the signer's stacker-db slots with empty chunks when its tenure
+    /// ends, so that stale messages are not picked up by the next signer set
+    pub clear_stackerdb_on_tenure_end: bool,
+    /// Whether to submit batches of block proposals (e.g. the proposals of a fast tenure
+    /// found while polling the miners' stacker-db) to the stacks node for validation
+    /// concurrently rather than one after the other
+    pub parallel_validation: bool,
+    /// How to vote on blocks missing some of the expected transactions
+    pub missing_transaction_policy: MissingTransactionPolicy,
+    /// How to handle stale node NACKs, from the `[stale_node_nack]` section. If the section is
+    /// absent, NACKs are not processed.
+    pub stale_node_nack_policy: Option<StaleNodeNackPolicy>,
+    /// The format of the signers' logs
+    pub log_format: LogFormat,
+    /// The file the events and stacks node responses of the session are appended to, to inspect
+    /// them when debugging. If None, nothing is recorded.
+    pub record_events_path: Option<PathBuf>,
+    /// The http(s) URL critical signer events are posted to. If None, no alerts are sent.
+    pub alert_webhook_url: Option<String>,
+    /// Declines block proposals that do not meet custom criteria before they are validated.
+    /// It cannot be set from the config file: applications embedding the signer set it before
+    /// starting the runloop.
+    pub block_proposal_filter: Option<Arc<dyn BlockProposalFilter>>,
+    /// The file the configuration was loaded from, if any. It is watched for changes to apply
+    /// while the signer runs.
+    pub config_path: Option<PathBuf>,
+    /// Where the value of each config file field came from
+    pub field_sources: BTreeMap<&'static str, ConfigSource>,
 }
 
 /// Internal struct for loading up the config file
 #[derive(Deserialize, Debug)]
 struct RawConfigFile {
-    /// endpoint to stacks node
+    /// endpoint to stacks node. Prefix with `https://` to connect over TLS.
     pub node_host: String,
+    /// Accept any TLS certificate presented by the stacks node. Only for test setups with self-signed certificates.
+    pub dangerous_skip_tls_verify: Option<bool>,
     /// endpoint to event receiver
     pub endpoint: String,
     /// The hex representation of the signer's Stacks private key used for communicating
     /// with the Stacks Node, including writing to the Stacker DB instance.
-    pub stacks_private_key: String,
-    /// The network to use. One of "mainnet" or "testnet".
+    /// Exactly one of `stacks_private_key`, `stacks_private_key_path` and
+    /// `stacks_private_key_cmd` must be set.
+    pub stacks_private_key: Option<String>,
+    /// Path to a file containing the hex representation of the signer's Stacks private key,
+    /// to keep the key out of the config file. The file must not be accessible by other users,
+    /// i.e. have permissions 0600 or stricter.
+    pub stacks_private_key_path: Option<String>,
+    /// A shell command printing the hex representation of the signer's Stacks private key,
+    /// e.g. to fetch it from the OS keyring
+    #[cfg(feature = "private_key_cmd")]
+    pub stacks_private_key_cmd: Option<String>,
+    /// The network to use. One of "mainnet", "testnet" or "mocknet".
     pub network: Network,
+    /// The chain id of the network's transactions. Defaults to the network's chain id.
+    pub chain_id: Option<u32>,
+    /// The human-readable name of the signer, used to identify it in logs and metrics
+    pub signer_name: Option<String>,
     /// The time to wait (in millisecs) for a response from the stacker-db instance
     pub event_timeout_ms: Option<u64>,
-    /// timeout in (millisecs) to gather DkgPublicShares messages
+    /// The timeouts of the DKG and signing round phases
+    pub timeouts: Option<RawTimeouts>,
+    /// timeout in (millisecs) to gather DkgPublicShares messages.
+    /// Deprecated in favor of `timeouts.dkg_public`.
     pub dkg_public_timeout_ms: Option<u64>,
-    /// timeout in (millisecs) to gather DkgPrivateShares messages
+    /// timeout in (millisecs) to gather DkgPrivateShares messages.
+    /// Deprecated in favor of `timeouts.dkg_private`.
     pub dkg_private_timeout_ms: Option<u64>,
-    /// timeout in (millisecs) to gather DkgEnd messages
+    /// timeout in (millisecs) to gather DkgEnd messages.
+    /// Deprecated in favor of `timeouts.dkg_end`.
     pub dkg_end_timeout_ms: Option<u64>,
-    /// timeout in (millisecs) to gather nonces
+    /// timeout in (millisecs) to gather nonces. Deprecated in favor of `timeouts.nonce`.
     pub nonce_timeout_ms: Option<u64>,
-    /// timeout in (millisecs) to gather signature shares
+    /// timeout in (millisecs) to gather signature shares. Deprecated in favor of `timeouts.sign`.
     pub sign_timeout_ms: Option<u64>,
+    /// timeout in (millisecs) to wait for the stacks node to validate a proposed block
+    pub block_validation_timeout_ms: Option<u64>,
+    /// timeout in (millisecs) after which a DKG round whose vote weight stopped increasing is abandoned
+    pub dkg_round_stall_timeout_ms: Option<u64>,
+    /// time in (millisecs) a signer's packets of a signing round iteration newer than the latest
+    /// one we received a request for are accepted
+    pub packet_future_tolerance_ms: Option<u64>,
     /// the STX tx fee to use in uSTX. If not set, will default to TX_FEE_USTX
     pub tx_fee_ustx: Option<u64>,
     /// the max STX tx fee to use in uSTX when estimating fees.
     /// If not set, will use tx_fee_ustx.
     pub max_tx_fee_ustx: Option<u64>,
+    /// The maximum median transaction fee in uSTX of a block the signer is willing to sign.
+    /// If not set, will default to MAX_ACCEPTABLE_MEDIAN_FEE_RATE
+    pub max_acceptable_median_fee_rate: Option<u64>,
+    /// The number of times a block proposal is submitted to the stacks node for validation
+    /// before it is rejected. If not set, will default to MAX_VALIDATION_ATTEMPTS
+    pub max_validation_attempts: Option<u8>,
+    /// The number of signature share requests from the same coordinator rejected in a signing
+    /// round before the round is NACKed to the other signers.
+    /// If not set, will default to MAX_REJECTED_REQUESTS_PER_ROUND
+    pub max_rejected_requests_per_round: Option<u32>,
     /// The authorization password for the block proposal endpoint
     pub auth_password: String,
     /// The path to the signer's database file or :memory: for an in-memory database
     pub db_path: String,
     /// Metrics endpoint
     pub metrics_endpoint: Option<String>,
+    /// The port to serve the per-signer metrics on, in the Prometheus text format
+    pub metrics_port: Option<u16>,
+    /// The time (in millisecs) after which the signer is reported unhealthy if the stacks node
+    /// was not reached or the event loop did not run
+    pub health_check_timeout_ms: Option<u64>,
+    /// How long (in millisecs) a shutdown waits for the in-progress DKG and signing rounds to
+    /// complete
+    pub shutdown_timeout_ms: Option<u64>,
+    /// The interval (in millisecs) to wait before the first retry of a failed stacks node request
+    pub retry_initial_interval_ms: Option<u64>,
+    /// The factor the retry interval is multiplied by after each failed attempt
+    pub retry_multiplier: Option<f64>,
+    /// The upper bound (in millisecs) on the interval between two retries
+    pub retry_max_interval_ms: Option<u64>,
+    /// The total time (in millisecs) after which a failed request is no longer retried
+    pub retry_max_elapsed_time_ms: Option<u64>,
+    /// The randomization factor in [0, 1] applied to each retry interval
+    pub retry_jitter: Option<f64>,
+    /// The interval (in millisecs) to wait before the first retry of a failed stacker-db write
+    pub stackerdb_write_retry_initial_interval_ms: Option<u64>,
+    /// The factor the stacker-db write retry interval is multiplied by after each failed attempt
+    pub stackerdb_write_retry_multiplier: Option<f64>,
+    /// The upper bound (in millisecs) on the interval between two stacker-db write retries
+    pub stackerdb_write_retry_max_interval_ms: Option<u64>,
+    /// The total time (in millisecs) after which a failed stacker-db write is no longer retried
+    pub stackerdb_write_retry_max_elapsed_time_ms: Option<u64>,
+    /// The randomization factor in [0, 1] applied to each stacker-db write retry interval
+    pub stackerdb_write_retry_jitter: Option<f64>,
+    /// The maximum number of requests per second sent to the stacks node
+    pub rate_limit_requests_per_second: Option<f64>,
+    /// The maximum time (in millisecs) a request may wait for the rate limiter before failing
+    pub rate_limit_max_queue_wait_ms: Option<u64>,
+    /// The maximum size (in bytes) of account, nonce and fee responses from the stacks node
+    pub max_response_bytes_account: Option<u64>,
+    /// The maximum size (in bytes) of other RPC responses from the stacks node
+    pub max_response_bytes_rpc: Option<u64>,
+    /// The maximum size (in bytes) of stacker-db responses from the stacks node
+    pub max_response_bytes_stackerdb: Option<u64>,
+    /// Whether to compress large messages written to stacker-db. Disable while signers
+    /// without compression support are still running. Defaults to true.
+    pub compress_stackerdb_chunks: Option<bool>,
+    /// The size (in bytes) above which messages written to stacker-db are compressed
+    pub chunk_compression_threshold_bytes: Option<usize>,
+    /// The slot offset within the signer's stacker-db slots of each message type, by message slot name.
+    /// Every message type the signer writes must be mapped, or the signer does not register.
+    pub stackerdb_slot_layout: Option<BTreeMap<MessageSlotID, u32>>,
+    /// Whether to clear the signer's stacker-db slots when its tenure ends. Defaults to false,
+    /// keeping the last written chunks around for inspection.
+    pub clear_stackerdb_on_tenure_end: Option<bool>,
+    /// Whether to submit batches of block proposals for validation concurrently.
+    /// Defaults to false.
+    pub parallel_validation: Option<bool>,
+    /// How to vote on blocks missing some of the expected transactions: `reject` (the default),
+    /// `accept_and_warn` or `reject_after_n_blocks`
+    pub missing_transaction_policy: Option<String>,
+    /// The format of the signer process's logs: `text` (the default) or `json`.
+    /// Only read at startup.
+    pub log_format: Option<String>,
+    /// With the `reject_after_n_blocks` policy, the number of consecutive proposals the same
+    /// transactions must be missing from before blocks are rejected
+    pub missing_transaction_reject_after_blocks: Option<u32>,
+    /// How to handle stale node NACKs. NACKs are not processed unless the section is present.
+    pub stale_node_nack: Option<RawStaleNodeNack>,
+    /// The file to append the events and stacks node responses of the session to, to print
+    /// them with the `dump` command
+    pub record_events_path: Option<String>,
+    /// The http(s) URL to post alerts of critical signer events to
+    pub alert_webhook_url: Option<String>,
+}
+
+/// The `[timeouts]` section of the config file. Each timeout is a duration such as "500ms",
+/// "30s", "2m" or "1m30s".
+#[derive(Deserialize, Debug, Default)]
+struct RawTimeouts {
+    /// timeout to gather DkgPublicShares messages
+    pub dkg_public: Option<String>,
+    /// timeout to gather DkgPrivateShares messages. Must be at least `dkg_public`.
+    pub dkg_private: Option<String>,
+    /// timeout to gather DkgEnd messages. Must be at least `dkg_private`.
+    pub dkg_end: Option<String>,
+    /// timeout to gather nonces
+    pub nonce: Option<String>,
+    /// timeout to gather signature shares. Must be at least `nonce`.
+    pub sign: Option<String>,
+}
+
+/// The `[stale_node_nack]` section of the config file. Knobs left unset take their defaults.
+#[derive(Deserialize, Debug, Default)]
+struct RawStaleNodeNack {
+    /// The percentage of the signer set that must NACK a block before it is treated as proposed
+    /// to stale nodes, between 1 and 100
+    pub nack_threshold_percent: Option<u32>,
+    /// The time (in millisecs) to back off from a block once its NACKs reach the threshold.
+    /// At most 10 minutes.
+    pub back_off_duration_ms: Option<u64>,
+    /// The time (in millisecs) a received NACK counts towards the threshold
+    pub nack_ttl_ms: Option<u64>,
+    /// The minimum time (in millisecs) between two of our NACKs of the same block
+    pub resend_cooldown_ms: Option<u64>,
+    /// The maximum number of NACKs we send for a single block
+    pub max_nacks_per_block: Option<u32>,
+    /// The time (in millisecs) between two reports of the number of signers we received a NACK
+    /// from to the other signers
+    pub nack_report_interval_ms: Option<u64>,
+}
+
+impl From<RawStaleNodeNack> for StaleNodeNackPolicy {
+    fn from(raw: RawStaleNodeNack) -> Self {
+        let defaults = Self::default();
+        Self {
+            nack_threshold_percent: raw
+                .nack_threshold_percent
+                .unwrap_or(defaults.nack_threshold_percent),
+            back_off_duration: raw
+                .back_off_duration_ms
+                .map_or(defaults.back_off_duration, Duration::from_millis),
+            nack_ttl: raw
+                .nack_ttl_ms
+                .map_or(defaults.nack_ttl, Duration::from_millis),
+            resend_cooldown: raw
+                .resend_cooldown_ms
+                .map_or(defaults.resend_cooldown, Duration::from_millis),
+            max_nacks_per_block: raw
+                .max_nacks_per_block
+                .unwrap_or(defaults.max_nacks_per_block),
+            report_interval: raw
+                .nack_report_interval_ms
+                .map_or(defaults.report_interval, Duration::from_millis),
+        }
+    }
 }
 
 impl RawConfigFile {
@@ -239,6 +856,62 @@ impl RawConfigFile {
     pub fn load_from_file(path: &str) -> Result<Self, ConfigError> {
         Self::try_from(&PathBuf::from(path))
     }
+
+    /// load the config from a string, with its fields overridden by the given `STACKS_SIGNER_*`
+    /// environment variables. Other variables are ignored.
+    /// Returns the config along with where each of its fields came from.
+    fn load_with_env_overrides(
+        data: &str,
+        env_vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<(Self, BTreeMap<&'static str, ConfigSource>), ConfigError> {
+        let mut fields: toml::value::Table =
+            toml::from_str(data).map_err(|e| ConfigError::ParseError(format!("{e:?}")))?;
+        let mut field_sources: BTreeMap<_, _> = CONFIG_FIELDS
+            .iter()
+            .map(|(field, _)| {
+                let source = if fields.contains_key(*field) {
+                    ConfigSource::File
+                } else {
+                    ConfigSource::Default
+                };
+                (*field, source)
+            })
+            .collect();
+
+        for (name, value) in env_vars {
+            let Some(field_name) = name.strip_prefix(ENV_VAR_PREFIX) else {
+                continue;
+            };
+            let field_name = field_name.to_lowercase();
+            let Some(&(field, kind)) = CONFIG_FIELDS.iter().find(|(field, _)| *field == field_name)
+            else {
+                warn!("Ignoring environment variable {name}: no such config field");
+                continue;
+            };
+            // The value itself is never included in the error, as it may be a secret
+            let value = kind.coerce(&value).ok_or_else(|| {
+                ConfigError::InvalidField(name.clone(), format!("expected {}", kind.expected()))
+            })?;
+            fields.insert(field.to_string(), value);
+            field_sources.insert(field, ConfigSource::Environment);
+        }
+
+        let config = toml::Value::Table(fields)
+            .try_into()
+            .map_err(|e| ConfigError::ParseError(format!("{e:?}")))?;
+        Ok((config, field_sources))
+    }
+}
+
+/// The `STACKS_SIGNER_*` environment variables of this process
+fn signer_env_vars() -> impl Iterator<Item = (String, String)> {
+    std::env::vars_os().filter_map(|(name, value)| {
+        let name = name.into_string().ok()?;
+        if !name.starts_with(ENV_VAR_PREFIX) {
+            return None;
+        }
+        Some((name, value.into_string().ok()?))
+    })
 }
 
 impl TryFrom<&PathBuf> for RawConfigFile {
@@ -256,10 +929,16 @@ impl TryFrom<RawConfigFile> for GlobalConfig {
 
     /// Attempt to decode the raw config file's primitive types into our types.
     /// NOTE: network access is required for this to work
-    fn try_from(raw_data: RawConfigFile) -> Result<Self, Self::Error> {
-        url::Url::parse(&format!("http://{}", raw_data.node_host)).map_err(|_| {
-            ConfigError::BadField("node_host".to_string(), raw_data.node_host.clone())
-        })?;
+    fn try_from(mut raw_data: RawConfigFile) -> Result<Self, Self::Error> {
+        let mut network_profile = raw_data.network.profile();
+        if let Some(chain_id) = raw_data.chain_id {
+            network_profile.chain_id = chain_id;
+        }
+        let (node_host, node_transport) = parse_node_host(
+            &raw_data.node_host,
+            raw_data.dangerous_skip_tls_verify.unwrap_or(false),
+            network_profile.default_node_port,
+        )?;
 
         let endpoint = raw_data
             .endpoint
@@ -270,24 +949,18 @@ impl TryFrom<RawConfigFile> for GlobalConfig {
                 ConfigError::BadField("endpoint".to_string(), raw_data.endpoint.clone())
             })?;
 
-        let stacks_private_key =
-            StacksPrivateKey::from_hex(&raw_data.stacks_private_key).map_err(|_| {
-                ConfigError::BadField(
-                    "stacks_private_key".to_string(),
-                    raw_data.stacks_private_key.clone(),
-                )
-            })?;
+        let private_key_hex = load_private_key_hex(&mut raw_data)?;
+        let stacks_private_key = StacksPrivateKey::from_hex(&private_key_hex).map_err(|_| {
+            ConfigError::BadField("stacks_private_key".to_string(), "<redacted>".to_string())
+        })?;
 
-        let ecdsa_private_key =
-            Scalar::try_from(&stacks_private_key.to_bytes()[..32]).map_err(|_| {
-                ConfigError::BadField(
-                    "stacks_private_key".to_string(),
-                    raw_data.stacks_private_key.clone(),
-                )
-            })?;
+        let private_key_bytes = Zeroizing::new(stacks_private_key.to_bytes());
+        let ecdsa_private_key = Scalar::try_from(&private_key_bytes[..32]).map_err(|_| {
+            ConfigError::BadField("stacks_private_key".to_string(), "<redacted>".to_string())
+        })?;
         let stacks_public_key = StacksPublicKey::from_private(&stacks_private_key);
         let stacks_address = StacksAddress::from_public_keys(
-            raw_data.network.to_address_version(),
+            network_profile.address_version,
             &AddressHashMode::SerializeP2PKH,
             1,
             &vec![stacks_public_key],
@@ -295,11 +968,45 @@ impl TryFrom<RawConfigFile> for GlobalConfig {
         .ok_or(ConfigError::UnsupportedAddressVersion)?;
         let event_timeout =
             Duration::from_millis(raw_data.event_timeout_ms.unwrap_or(EVENT_TIMEOUT_MS));
-        let dkg_end_timeout = raw_data.dkg_end_timeout_ms.map(Duration::from_millis);
-        let dkg_public_timeout = raw_data.dkg_public_timeout_ms.map(Duration::from_millis);
-        let dkg_private_timeout = raw_data.dkg_private_timeout_ms.map(Duration::from_millis);
-        let nonce_timeout = raw_data.nonce_timeout_ms.map(Duration::from_millis);
-        let sign_timeout = raw_data.sign_timeout_ms.map(Duration::from_millis);
+        let timeouts = raw_data.timeouts.unwrap_or_default();
+        let dkg_public_timeout = parse_phase_timeout(
+            "dkg_public",
+            timeouts.dkg_public.as_deref(),
+            raw_data.dkg_public_timeout_ms,
+            DKG_PUBLIC_TIMEOUT_MS,
+        )?;
+        let dkg_private_timeout = parse_phase_timeout(
+            "dkg_private",
+            timeouts.dkg_private.as_deref(),
+            raw_data.dkg_private_timeout_ms,
+            DKG_PRIVATE_TIMEOUT_MS,
+        )?;
+        let dkg_end_timeout = parse_phase_timeout(
+            "dkg_end",
+            timeouts.dkg_end.as_deref(),
+            raw_data.dkg_end_timeout_ms,
+            DKG_END_TIMEOUT_MS,
+        )?;
+        let nonce_timeout = parse_phase_timeout(
+            "nonce",
+            timeouts.nonce.as_deref(),
+            raw_data.nonce_timeout_ms,
+            NONCE_TIMEOUT_MS,
+        )?;
+        let sign_timeout = parse_phase_timeout(
+            "sign",
+            timeouts.sign.as_deref(),
+            raw_data.sign_timeout_ms,
+            SIGN_TIMEOUT_MS,
+        )?;
+        let block_validation_timeout = raw_data.block_validation_timeout_ms.map_or(
+            network_profile.default_block_validation_timeout,
+            Duration::from_millis,
+        );
+        let dkg_round_stall_timeout = raw_data.dkg_round_stall_timeout_ms.map_or(
+            network_profile.default_dkg_round_stall_timeout,
+            Duration::from_millis,
+        );
         let db_path = raw_data.db_path.into();
 
         let metrics_endpoint = match raw_data.metrics_endpoint {
@@ -315,40 +1022,488 @@ impl TryFrom<RawConfigFile> for GlobalConfig {
             None => None,
         };
 
-        Ok(Self {
-            node_host: raw_data.node_host,
+        let retry_config = parse_retry_config(
+            "retry_",
+            RetryConfig::default(),
+            raw_data.retry_initial_interval_ms,
+            raw_data.retry_multiplier,
+            raw_data.retry_max_interval_ms,
+            raw_data.retry_max_elapsed_time_ms,
+            raw_data.retry_jitter,
+        )?;
+        let stackerdb_write_retry_config = parse_retry_config(
+            "stackerdb_write_retry_",
+            RetryConfig::stackerdb_write_default(),
+            raw_data.stackerdb_write_retry_initial_interval_ms,
+            raw_data.stackerdb_write_retry_multiplier,
+            raw_data.stackerdb_write_retry_max_interval_ms,
+            raw_data.stackerdb_write_retry_max_elapsed_time_ms,
+            raw_data.stackerdb_write_retry_jitter,
+        )?;
+
+        let default_rate_limit_config = RateLimitConfig::default();
+        let rate_limit_config = RateLimitConfig {
+            requests_per_second: raw_data
+                .rate_limit_requests_per_second
+                .unwrap_or(default_rate_limit_config.requests_per_second),
+            max_queue_wait: raw_data
+                .rate_limit_max_queue_wait_ms
+                .map(Duration::from_millis)
+                .unwrap_or(default_rate_limit_config.max_queue_wait),
+        };
+        if !rate_limit_config.requests_per_second.is_finite()
+            || rate_limit_config.requests_per_second <= 0.0
+        {
+            return Err(ConfigError::BadField(
+                "rate_limit_requests_per_second".to_string(),
+                rate_limit_config.requests_per_second.to_string(),
+            ));
+        }
+
+        let default_response_limits = ResponseLimits::default();
+        let response_limits = ResponseLimits {
+            account: raw_data
+                .max_response_bytes_account
+                .unwrap_or(default_response_limits.account),
+            rpc: raw_data
+                .max_response_bytes_rpc
+                .unwrap_or(default_response_limits.rpc),
+            stackerdb: raw_data
+                .max_response_bytes_stackerdb
+                .unwrap_or(default_response_limits.stackerdb),
+        };
+        for (field, max_bytes) in [
+            ("max_response_bytes_account", response_limits.account),
+            ("max_response_bytes_rpc", response_limits.rpc),
+            ("max_response_bytes_stackerdb", response_limits.stackerdb),
+        ] {
+            if max_bytes == 0 {
+                return Err(ConfigError::BadField(
+                    field.to_string(),
+                    max_bytes.to_string(),
+                ));
+            }
+        }
+
+        let chunk_compression_threshold = if raw_data.compress_stackerdb_chunks.unwrap_or(true) {
+            Some(
+                raw_data
+                    .chunk_compression_threshold_bytes
+                    .unwrap_or(DEFAULT_CHUNK_COMPRESSION_THRESHOLD),
+            )
+        } else {
+            None
+        };
+
+        let slot_layout = raw_data
+            .stackerdb_slot_layout
+            .map(SlotLayout::new)
+            .transpose()
+            .map_err(|e| {
+                ConfigError::BadField("stackerdb_slot_layout".to_string(), e.to_string())
+            })?;
+
+        let missing_transaction_policy = parse_missing_transaction_policy(
+            raw_data.missing_transaction_policy.as_deref(),
+            raw_data.missing_transaction_reject_after_blocks,
+        )?;
+
+        let log_format = match raw_data.log_format.as_deref() {
+            None | Some("text") => LogFormat::Text,
+            Some("json") => LogFormat::Json,
+            Some(log_format) => {
+                return Err(ConfigError::BadField(
+                    "log_format".to_string(),
+                    log_format.to_string(),
+                ))
+            }
+        };
+
+        if let Some(alert_webhook_url) = &raw_data.alert_webhook_url {
+            let is_http = reqwest::Url::parse(alert_webhook_url)
+                .map_or(false, |url| matches!(url.scheme(), "http" | "https"));
+            if !is_http {
+                return Err(ConfigError::BadField(
+                    "alert_webhook_url".to_string(),
+                    alert_webhook_url.clone(),
+                ));
+            }
+        }
+
+        let signer_name = match raw_data.signer_name {
+            Some(signer_name) if signer_name.trim().is_empty() => {
+                return Err(ConfigError::BadField(
+                    "signer_name".to_string(),
+                    signer_name,
+                ));
+            }
+            signer_name => signer_name,
+        };
+
+        let config = Self {
+            node_host,
+            node_transport,
             endpoint,
             stacks_private_key,
             ecdsa_private_key,
             stacks_address,
             network: raw_data.network,
+            network_profile,
+            signer_name,
             event_timeout,
             dkg_end_timeout,
             dkg_public_timeout,
             dkg_private_timeout,
             nonce_timeout,
             sign_timeout,
+            block_validation_timeout,
+            dkg_round_stall_timeout,
+            packet_future_tolerance: Duration::from_millis(
+                raw_data
+                    .packet_future_tolerance_ms
+                    .unwrap_or(PACKET_FUTURE_TOLERANCE_MS),
+            ),
             tx_fee_ustx: raw_data.tx_fee_ustx.unwrap_or(TX_FEE_USTX),
             max_tx_fee_ustx: raw_data.max_tx_fee_ustx,
+            max_acceptable_median_fee_rate: raw_data
+                .max_acceptable_median_fee_rate
+                .unwrap_or(MAX_ACCEPTABLE_MEDIAN_FEE_RATE),
+            max_validation_attempts: raw_data
+                .max_validation_attempts
+                .unwrap_or(MAX_VALIDATION_ATTEMPTS),
+            max_rejected_requests_per_round: raw_data
+                .max_rejected_requests_per_round
+                .unwrap_or(MAX_REJECTED_REQUESTS_PER_ROUND),
             auth_password: raw_data.auth_password,
             db_path,
             metrics_endpoint,
-        })
+            metrics_port: raw_data.metrics_port,
+            health_check_timeout: Duration::from_millis(
+                raw_data
+                    .health_check_timeout_ms
+                    .unwrap_or(HEALTH_CHECK_TIMEOUT_MS),
+            ),
+            shutdown_timeout: Duration::from_millis(
+                raw_data.shutdown_timeout_ms.unwrap_or(SHUTDOWN_TIMEOUT_MS),
+            ),
+            retry_config,
+            stackerdb_write_retry_config,
+            rate_limit_config,
+            response_limits,
+            chunk_compression_threshold,
+            slot_layout,
+            clear_stackerdb_on_tenure_end: raw_data.clear_stackerdb_on_tenure_end.unwrap_or(false),
+            parallel_validation: raw_data.parallel_validation.unwrap_or(false),
+            missing_transaction_policy,
+            stale_node_nack_policy: raw_data.stale_node_nack.map(StaleNodeNackPolicy::from),
+            log_format,
+            record_events_path: raw_data.record_events_path.map(PathBuf::from),
+            alert_webhook_url: raw_data.alert_webhook_url,
+            block_proposal_filter: None,
+            config_path: None,
+            field_sources: BTreeMap::new(),
+        };
+        let violations = config.validate();
+        if !violations.is_empty() {
+            return Err(ConfigError::Violations(violations));
+        }
+        Ok(config)
     }
 }
 
+/// Resolve the timeout of a DKG or signing round phase from the `[timeouts]` section, falling
+/// back to its deprecated `{name}_timeout_ms` field, then to the given default
+fn parse_phase_timeout(
+    name: &str,
+    section_value: Option<&str>,
+    legacy_ms: Option<u64>,
+    default_ms: u64,
+) -> Result<Option<Duration>, ConfigError> {
+    let field = format!("timeouts.{name}");
+    let timeout = match (section_value, legacy_ms) {
+        (Some(_), Some(_)) => {
+            return Err(ConfigError::InvalidField(
+                field,
+                format!("conflicts with {name}_timeout_ms. Only set one of them."),
+            ))
+        }
+        (Some(value), None) => {
+            parse_duration(value).ok_or_else(|| ConfigError::BadField(field, value.to_string()))?
+        }
+        (None, Some(timeout_ms)) => {
+            warn!("{name}_timeout_ms is deprecated. Set {field} instead.");
+            Duration::from_millis(timeout_ms)
+        }
+        (None, None) => Duration::from_millis(default_ms),
+    };
+    Ok(Some(timeout))
+}
+
+/// Parse a human readable duration made of one or more `<integer><unit>` parts, where the unit
+/// is one of `ms`, `s`, `m` or `h`, e.g. "500ms", "30s" or "1m30s"
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    let mut duration = Duration::ZERO;
+    while !rest.is_empty() {
+        let amount_end = rest.find(|c: char| !c.is_ascii_digit())?;
+        let amount: u32 = rest[..amount_end].parse().ok()?;
+        rest = &rest[amount_end..];
+        let unit_end = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let unit = match &rest[..unit_end] {
+            "ms" => Duration::from_millis(1),
+            "s" => Duration::from_secs(1),
+            "m" => Duration::from_secs(60),
+            "h" => Duration::from_secs(3600),
+            _ => return None,
+        };
+        duration = duration.checked_add(unit.checked_mul(amount)?)?;
+        rest = &rest[unit_end..];
+    }
+    Some(duration)
+}
+
+/// Load the hex representation of the signer's private key from whichever of its sources is
+/// configured. The key is never included in the returned errors.
+fn load_private_key_hex(raw_data: &mut RawConfigFile) -> Result<Zeroizing<String>, ConfigError> {
+    let inline_key = raw_data.stacks_private_key.take().map(Zeroizing::new);
+    let key_path = raw_data.stacks_private_key_path.take();
+    #[cfg(feature = "private_key_cmd")]
+    let key_cmd = raw_data.stacks_private_key_cmd.take();
+    #[cfg(not(feature = "private_key_cmd"))]
+    let key_cmd: Option<String> = None;
+
+    let sources_set = [inline_key.is_some(), key_path.is_some(), key_cmd.is_some()]
+        .into_iter()
+        .filter(|is_set| *is_set)
+        .count();
+    if sources_set != 1 {
+        return Err(ConfigError::InvalidField(
+            "stacks_private_key".to_string(),
+            format!("exactly one of {PRIVATE_KEY_SOURCES} must be set"),
+        ));
+    }
+    if let Some(key) = inline_key {
+        return Ok(key);
+    }
+    if let Some(key_path) = key_path {
+        return read_private_key_file(Path::new(&key_path));
+    }
+    #[cfg(feature = "private_key_cmd")]
+    if let Some(key_cmd) = key_cmd {
+        return run_private_key_cmd(&key_cmd);
+    }
+    unreachable!("Exactly one private key source is set")
+}
+
+/// Read the hex representation of the signer's private key from the given file, refusing to
+/// do so if the file is accessible by other users
+fn read_private_key_file(path: &Path) -> Result<Zeroizing<String>, ConfigError> {
+    let invalid =
+        |reason: String| ConfigError::InvalidField("stacks_private_key_path".to_string(), reason);
+    let metadata = fs::metadata(path)
+        .map_err(|e| invalid(format!("failed to read {}: {e}", path.display())))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = metadata.permissions().mode() & 0o777;
+        if mode & 0o077 != 0 {
+            return Err(invalid(format!(
+                "{} must not be accessible by other users, but has permissions {mode:o}. Run `chmod 600` on it.",
+                path.display()
+            )));
+        }
+    }
+    if !metadata.is_file() {
+        return Err(invalid(format!("{} is not a file", path.display())));
+    }
+    let contents = Zeroizing::new(
+        fs::read_to_string(path)
+            .map_err(|e| invalid(format!("failed to read {}: {e}", path.display())))?,
+    );
+    Ok(Zeroizing::new(contents.trim().to_string()))
+}
+
+/// Run the given shell command and read the hex representation of the signer's private key
+/// from its standard output
+#[cfg(feature = "private_key_cmd")]
+fn run_private_key_cmd(cmd: &str) -> Result<Zeroizing<String>, ConfigError> {
+    let invalid =
+        |reason: String| ConfigError::InvalidField("stacks_private_key_cmd".to_string(), reason);
+    let output = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(cmd)
+        .stderr(std::process::Stdio::inherit())
+        .output()
+        .map_err(|e| invalid(format!("failed to run command: {e}")))?;
+    let stdout = Zeroizing::new(output.stdout);
+    if !output.status.success() {
+        return Err(invalid(format!("command failed: {}", output.status)));
+    }
+    let key = std::str::from_utf8(&stdout)
+        .map_err(|_| invalid("command output is not valid UTF-8".to_string()))?;
+    Ok(Zeroizing::new(key.trim().to_string()))
+}
+
+/// Parse the policy for blocks missing some of the expected transactions
+fn parse_missing_transaction_policy(
+    policy: Option<&str>,
+    reject_after_blocks: Option<u32>,
+) -> Result<MissingTransactionPolicy, ConfigError> {
+    let policy = match policy.unwrap_or("reject") {
+        "reject" => MissingTransactionPolicy::Reject,
+        "accept_and_warn" => MissingTransactionPolicy::AcceptAndWarn,
+        "reject_after_n_blocks" => {
+            let blocks = reject_after_blocks
+                .filter(|blocks| *blocks > 0)
+                .ok_or_else(|| {
+                    ConfigError::InvalidField(
+                        "missing_transaction_reject_after_blocks".to_string(),
+                        "must be a positive number of blocks with the reject_after_n_blocks policy"
+                            .to_string(),
+                    )
+                })?;
+            return Ok(MissingTransactionPolicy::RejectAfterNBlocks(blocks));
+        }
+        policy => {
+            return Err(ConfigError::BadField(
+                "missing_transaction_policy".to_string(),
+                policy.to_string(),
+            ))
+        }
+    };
+    if reject_after_blocks.is_some() {
+        return Err(ConfigError::InvalidField(
+            "missing_transaction_reject_after_blocks".to_string(),
+            format!("only applies to the reject_after_n_blocks policy, not {policy}"),
+        ));
+    }
+    Ok(policy)
+}
+
+/// Build a backoff policy from the configured `{field_prefix}*` options, falling back to
+/// `defaults` for the ones not set
+fn parse_retry_config(
+    field_prefix: &str,
+    defaults: RetryConfig,
+    initial_interval_ms: Option<u64>,
+    multiplier: Option<f64>,
+    max_interval_ms: Option<u64>,
+    max_elapsed_time_ms: Option<u64>,
+    jitter: Option<f64>,
+) -> Result<RetryConfig, ConfigError> {
+    let retry_config = RetryConfig {
+        initial_interval: initial_interval_ms
+            .map(Duration::from_millis)
+            .unwrap_or(defaults.initial_interval),
+        multiplier: multiplier.unwrap_or(defaults.multiplier),
+        max_interval: max_interval_ms
+            .map(Duration::from_millis)
+            .unwrap_or(defaults.max_interval),
+        max_elapsed_time: max_elapsed_time_ms
+            .map(Duration::from_millis)
+            .or(defaults.max_elapsed_time),
+        jitter: jitter.unwrap_or(defaults.jitter),
+    };
+    if retry_config.multiplier.is_nan() || retry_config.multiplier < 1.0 {
+        return Err(ConfigError::BadField(
+            format!("{field_prefix}multiplier"),
+            retry_config.multiplier.to_string(),
+        ));
+    }
+    if !(0.0..=1.0).contains(&retry_config.jitter) {
+        return Err(ConfigError::BadField(
+            format!("{field_prefix}jitter"),
+            retry_config.jitter.to_string(),
+        ));
+    }
+    if retry_config.initial_interval > retry_config.max_interval {
+        return Err(ConfigError::BadField(
+            format!("{field_prefix}initial_interval_ms"),
+            retry_config.initial_interval.as_millis().to_string(),
+        ));
+    }
+    Ok(retry_config)
+}
+
+/// Split the optional `http://` or `https://` scheme off the configured node host.
+/// Returns the `host:port` to connect to, using the default port if none is given, and the
+/// transport to use.
+fn parse_node_host(
+    node_host: &str,
+    skip_tls_verify: bool,
+    default_port: u16,
+) -> Result<(String, NodeTransport), ConfigError> {
+    let bad_field = || ConfigError::BadField("node_host".to_string(), node_host.to_string());
+    let Some((scheme, _)) = node_host.split_once("://") else {
+        url::Url::parse(&format!("http://{node_host}")).map_err(|_| bad_field())?;
+        let has_port = node_host
+            .rsplit_once(':')
+            .is_some_and(|(_, port)| port.parse::<u16>().is_ok());
+        if !has_port {
+            return Ok((format!("{node_host}:{default_port}"), NodeTransport::Plain));
+        }
+        return Ok((node_host.to_string(), NodeTransport::Plain));
+    };
+    let node_transport = match scheme {
+        "http" => NodeTransport::Plain,
+        "https" => NodeTransport::Tls {
+            skip_verify: skip_tls_verify,
+        },
+        _ => return Err(bad_field()),
+    };
+    let url = url::Url::parse(node_host).map_err(|_| bad_field())?;
+    let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+        return Err(bad_field());
+    };
+    Ok((format!("{host}:{port}"), node_transport))
+}
+
 impl TryFrom<&PathBuf> for GlobalConfig {
     type Error = ConfigError;
     fn try_from(path: &PathBuf) -> Result<Self, ConfigError> {
-        let config_file = RawConfigFile::try_from(path)?;
-        Self::try_from(config_file)
+        let data = fs::read_to_string(path).map_err(|e| {
+            ConfigError::InvalidConfig(format!("failed to read config file: {e:?}"))
+        })?;
+        let mut config = Self::load_from_str(&data)?;
+        config.config_path = Some(path.clone());
+        Ok(config)
     }
 }
 
 impl GlobalConfig {
-    /// load the config from a string and parse it
+    /// load the config from a string and parse it, with its fields overridden by this
+    /// process's `STACKS_SIGNER_*` environment variables
     pub fn load_from_str(data: &str) -> Result<Self, ConfigError> {
-        RawConfigFile::load_from_str(data)?.try_into()
+        Self::load_from_str_with_env(data, signer_env_vars())
+    }
+
+    /// load the config from a string and parse it, with its fields overridden by the given
+    /// `STACKS_SIGNER_*` environment variables. The merged config is validated as a whole.
+    pub fn load_from_str_with_env(
+        data: &str,
+        env_vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, ConfigError> {
+        let (config_file, field_sources) = RawConfigFile::load_with_env_overrides(data, env_vars)?;
+        let mut config = Self::try_from(config_file)?;
+        config.field_sources = field_sources;
+        let env_fields: Vec<_> = config.env_overridden_fields().collect();
+        if !env_fields.is_empty() {
+            info!("Config fields overridden by the environment: {env_fields:?}");
+        }
+        Ok(config)
+    }
+
+    /// The config fields whose value was set by a `STACKS_SIGNER_*` environment variable
+    pub fn env_overridden_fields(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.field_sources
+            .iter()
+            .filter(|(_, source)| **source == ConfigSource::Environment)
+            .map(|(field, _)| *field)
     }
 
     /// load the config from a file and parse it
@@ -356,6 +1511,261 @@ impl GlobalConfig {
         Self::try_from(&PathBuf::from(path))
     }
 
+    /// Check the parsed configuration for values the signer cannot run with, so that they are
+    /// reported at startup rather than as confusing runtime errors.
+    /// Returns every violation found, not just the first.
+    pub fn validate(&self) -> Vec<ConfigError> {
+        let mut violations = vec![];
+        let mut violation = |field: &str, reason: String| {
+            violations.push(ConfigError::InvalidField(field.to_string(), reason))
+        };
+
+        if !self.stacks_private_key.compress_public() {
+            violation(
+                "stacks_private_key",
+                "must be a 33 byte compressed key, i.e. 66 hex characters ending in 01".into(),
+            );
+        }
+        if !self
+            .node_host
+            .rsplit_once(':')
+            .is_some_and(|(_, port)| port.parse::<u16>().is_ok())
+        {
+            violation(
+                "node_host",
+                format!("'{}' must include a port", self.node_host),
+            );
+        }
+        // Only mainnet transactions may carry the mainnet chain id
+        if self.network.is_mainnet() != (self.network_profile.chain_id == CHAIN_ID_MAINNET) {
+            violation(
+                "chain_id",
+                format!(
+                    "{:#010x} contradicts network = \"{}\"",
+                    self.network_profile.chain_id, self.network
+                ),
+            );
+        }
+
+        let timeouts = [
+            ("event_timeout_ms", Some(self.event_timeout)),
+            ("timeouts.dkg_public", self.dkg_public_timeout),
+            ("timeouts.dkg_private", self.dkg_private_timeout),
+            ("timeouts.dkg_end", self.dkg_end_timeout),
+            ("timeouts.nonce", self.nonce_timeout),
+            ("timeouts.sign", self.sign_timeout),
+            (
+                "block_validation_timeout_ms",
+                Some(self.block_validation_timeout),
+            ),
+            (
+                "dkg_round_stall_timeout_ms",
+                Some(self.dkg_round_stall_timeout),
+            ),
+        ];
+        for (field, timeout) in timeouts {
+            if timeout == Some(Duration::ZERO) {
+                violation(field, "must be greater than 0".into());
+            }
+        }
+        // An idle event loop only runs once per event timeout
+        if self.health_check_timeout <= self.event_timeout {
+            violation(
+                "health_check_timeout_ms",
+                format!(
+                    "must be greater than event_timeout_ms ({})",
+                    self.event_timeout.as_millis()
+                ),
+            );
+        }
+        // Each round phase waits on the previous one, so its timeout cannot be shorter
+        let dkg_phase_timeouts = [
+            ("timeouts.dkg_public", self.dkg_public_timeout),
+            ("timeouts.dkg_private", self.dkg_private_timeout),
+            ("timeouts.dkg_end", self.dkg_end_timeout),
+        ];
+        let sign_phase_timeouts = [
+            ("timeouts.nonce", self.nonce_timeout),
+            ("timeouts.sign", self.sign_timeout),
+        ];
+        let phase_pairs = dkg_phase_timeouts
+            .windows(2)
+            .chain(sign_phase_timeouts.windows(2));
+        for pair in phase_pairs {
+            let [(previous_field, Some(previous)), (field, Some(timeout))] = pair else {
+                continue;
+            };
+            if timeout < previous {
+                violation(
+                    *field,
+                    format!(
+                        "{}ms must be at least {previous_field} ({}ms)",
+                        timeout.as_millis(),
+                        previous.as_millis()
+                    ),
+                );
+            }
+        }
+
+        if let Some(policy) = &self.stale_node_nack_policy {
+            if !(1..=100).contains(&policy.nack_threshold_percent) {
+                violation(
+                    "stale_node_nack.nack_threshold_percent",
+                    format!(
+                        "{} must be between 1 and 100",
+                        policy.nack_threshold_percent
+                    ),
+                );
+            }
+            if policy.back_off_duration > MAX_NACK_BACK_OFF_DURATION {
+                violation(
+                    "stale_node_nack.back_off_duration_ms",
+                    format!(
+                        "{}ms must be at most {}ms",
+                        policy.back_off_duration.as_millis(),
+                        MAX_NACK_BACK_OFF_DURATION.as_millis()
+                    ),
+                );
+            }
+            if policy.nack_ttl == Duration::ZERO {
+                violation(
+                    "stale_node_nack.nack_ttl_ms",
+                    "must be greater than 0".into(),
+                );
+            }
+            if policy.max_nacks_per_block == 0 {
+                violation(
+                    "stale_node_nack.max_nacks_per_block",
+                    "must be greater than 0".into(),
+                );
+            }
+            if policy.report_interval == Duration::ZERO {
+                violation(
+                    "stale_node_nack.nack_report_interval_ms",
+                    "must be greater than 0".into(),
+                );
+            }
+        }
+
+        if self.tx_fee_ustx == 0 {
+            violation("tx_fee_ustx", "must be greater than 0".into());
+        }
+        if self.max_tx_fee_ustx == Some(0) {
+            violation("max_tx_fee_ustx", "must be greater than 0".into());
+        }
+        if self.max_validation_attempts == 0 {
+            violation("max_validation_attempts", "must be greater than 0".into());
+        }
+        if self.max_rejected_requests_per_round == 0 {
+            violation(
+                "max_rejected_requests_per_round",
+                "must be greater than 0".into(),
+            );
+        }
+
+        if self.db_path != PathBuf::from(":memory:") {
+            if self.db_path.is_dir() {
+                violation(
+                    "db_path",
+                    format!("{} is a directory", self.db_path.display()),
+                );
+            }
+            let parent = match self.db_path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            match fs::metadata(parent) {
+                Ok(metadata) if metadata.is_dir() && !metadata.permissions().readonly() => {}
+                Ok(_) => violation(
+                    "db_path",
+                    format!("{} is not a writable directory", parent.display()),
+                ),
+                Err(e) => violation(
+                    "db_path",
+                    format!("cannot access {}: {e}", parent.display()),
+                ),
+            }
+        }
+        violations
+    }
+
+    /// Apply the settings of this (reloaded) configuration that can change while the signer
+    /// runs to the given signer: timeouts, backoff policies, fee caps and the stacks node
+    /// endpoint of its stacker-db client. The stacks node client is shared by the signers, so
+    /// the caller applies the node settings to it once with `StacksClient::apply_node_config`.
+    /// Settings that require a restart (the private key, network, chain id and database path) are left
+    /// untouched. The names of those that differ from the running signer's are logged and
+    /// returned. Log levels, read from the environment, and the log format also require a
+    /// restart.
+    pub fn apply_runtime_updates(
+        &self,
+        signer: &mut Signer,
+        stacks_client: &StacksClient,
+    ) -> Vec<&'static str> {
+        let mut ignored_fields = vec![];
+        if self.stacks_address != *stacks_client.get_signer_address() {
+            ignored_fields.push("stacks_private_key");
+        }
+        if self.network.is_mainnet() != signer.mainnet {
+            ignored_fields.push("network");
+        }
+        if self.network_profile.chain_id != stacks_client.get_chain_id() {
+            ignored_fields.push("chain_id");
+        }
+        if self.db_path != signer.db_path {
+            ignored_fields.push("db_path");
+        }
+        for field in &ignored_fields {
+            signer_warn!(
+                signer.logger,
+                "Ignoring the updated `{field}`. The signer must be restarted to apply it."
+            );
+        }
+
+        signer.stackerdb.set_node_host(&self.node_host);
+        signer.stackerdb.set_node_transport(self.node_transport);
+        signer
+            .stackerdb
+            .set_max_response_bytes(self.response_limits.stackerdb);
+        signer
+            .stackerdb
+            .set_retry_configs(self.retry_config, self.stackerdb_write_retry_config);
+        signer.update_round_timeouts(self);
+        signer.block_validation_timeout = self.block_validation_timeout;
+        signer.dkg_round_stall_timeout = self.dkg_round_stall_timeout;
+        signer.packet_future_tolerance = self.packet_future_tolerance;
+        signer.tx_fee_ustx = self.tx_fee_ustx;
+        signer.max_tx_fee_ustx = self.max_tx_fee_ustx;
+        signer.max_acceptable_median_fee_rate = self.max_acceptable_median_fee_rate;
+        signer.max_validation_attempts = self.max_validation_attempts;
+        signer.max_rejected_requests_per_round = self.max_rejected_requests_per_round;
+        signer.clear_stackerdb_on_tenure_end = self.clear_stackerdb_on_tenure_end;
+        signer.parallel_validation = self.parallel_validation;
+        signer.missing_transaction_policy = self.missing_transaction_policy;
+        signer.stale_node_nack_policy = self.stale_node_nack_policy;
+        signer_info!(signer.logger, "Applied the updated configuration.");
+        ignored_fields
+    }
+
+    /// Keep the settings of the `running` configuration that require a restart to change, so
+    /// that signers created from this configuration match the running ones
+    pub fn retain_restart_fields(&mut self, running: &GlobalConfig) {
+        self.endpoint = running.endpoint;
+        self.ecdsa_private_key = running.ecdsa_private_key;
+        self.stacks_private_key = running.stacks_private_key;
+        self.stacks_address = running.stacks_address;
+        self.network = running.network.clone();
+        self.network_profile = running.network_profile;
+        self.db_path = running.db_path.clone();
+        self.metrics_endpoint = running.metrics_endpoint;
+        self.metrics_port = running.metrics_port;
+        self.health_check_timeout = running.health_check_timeout;
+        self.log_format = running.log_format;
+        self.record_events_path = running.record_events_path.clone();
+        self.alert_webhook_url = running.alert_webhook_url.clone();
+        self.block_proposal_filter = running.block_proposal_filter.clone();
+    }
+
     /// Return a string with non-sensitive configuration
     /// information for logging purposes
     pub fn config_to_log_string(&self) -> String {
@@ -483,6 +1893,7 @@ metrics_endpoint = "{metrics_endpoint}"
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::tests::generate_signer_config;
 
     #[test]
     fn build_signer_config_tomls_should_produce_deserializable_strings() {
@@ -524,7 +1935,7 @@ mod tests {
         )
         .unwrap();
 
-        let node_host = "localhost";
+        let node_host = "localhost:20443";
         let network = Network::Testnet;
         let password = "melon";
 
@@ -625,6 +2036,184 @@ mod tests {
         assert_eq!(Some(config.tx_fee_ustx), tx_fee_ustx);
     }
 
+    #[test]
+    fn retry_options_should_deserialize_correctly() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        assert_eq!(config.retry_config, RetryConfig::default());
+
+        let config_toml = r#"
+stacks_private_key = "6a1fc1a3183018c6d79a4e11e154d2bdad2d89ac8bc1b0a021de8b4d28774fbb01"
+node_host = "127.0.0.1:20443"
+endpoint = "localhost:30000"
+network = "testnet"
+auth_password = "12345"
+db_path = ":memory:"
+retry_initial_interval_ms = 50
+retry_multiplier = 2.0
+retry_max_interval_ms = 1000
+retry_max_elapsed_time_ms = 5000
+retry_jitter = 0.25
+"#;
+        let config = GlobalConfig::load_from_str(config_toml).unwrap();
+        assert_eq!(
+            config.retry_config,
+            RetryConfig {
+                initial_interval: Duration::from_millis(50),
+                multiplier: 2.0,
+                max_interval: Duration::from_millis(1000),
+                max_elapsed_time: Some(Duration::from_millis(5000)),
+                jitter: 0.25,
+            }
+        );
+
+        // Stacker-db writes keep their own policy
+        assert_eq!(
+            config.stackerdb_write_retry_config,
+            RetryConfig::stackerdb_write_default()
+        );
+
+        let bad_jitter_toml = config_toml.replace("retry_jitter = 0.25", "retry_jitter = 1.5");
+        assert!(matches!(
+            GlobalConfig::load_from_str(&bad_jitter_toml),
+            Err(ConfigError::BadField(field, _)) if field == "retry_jitter"
+        ));
+    }
+
+    #[test]
+    fn stackerdb_write_retry_options_should_deserialize_correctly() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        assert_eq!(
+            config.stackerdb_write_retry_config,
+            RetryConfig::stackerdb_write_default()
+        );
+        let default_write_max_elapsed_time = config.stackerdb_write_retry_config.max_elapsed_time;
+        assert!(default_write_max_elapsed_time < config.retry_config.max_elapsed_time);
+
+        let config_toml = r#"
+stacks_private_key = "6a1fc1a3183018c6d79a4e11e154d2bdad2d89ac8bc1b0a021de8b4d28774fbb01"
+node_host = "127.0.0.1:20443"
+endpoint = "localhost:30000"
+network = "testnet"
+auth_password = "12345"
+db_path = ":memory:"
+stackerdb_write_retry_max_interval_ms = 500
+stackerdb_write_retry_max_elapsed_time_ms = 3000
+"#;
+        let config = GlobalConfig::load_from_str(config_toml).unwrap();
+        assert_eq!(config.retry_config, RetryConfig::default());
+        assert_eq!(
+            config.stackerdb_write_retry_config,
+            RetryConfig {
+                max_interval: Duration::from_millis(500),
+                max_elapsed_time: Some(Duration::from_millis(3000)),
+                ..RetryConfig::stackerdb_write_default()
+            }
+        );
+
+        let bad_multiplier_toml = format!("{config_toml}stackerdb_write_retry_multiplier = 0.5\n");
+        assert!(matches!(
+            GlobalConfig::load_from_str(&bad_multiplier_toml),
+            Err(ConfigError::BadField(field, _)) if field == "stackerdb_write_retry_multiplier"
+        ));
+    }
+
+    #[test]
+    fn node_host_scheme_should_select_transport() {
+        let config_toml = r#"
+stacks_private_key = "6a1fc1a3183018c6d79a4e11e154d2bdad2d89ac8bc1b0a021de8b4d28774fbb01"
+node_host = "127.0.0.1:20443"
+endpoint = "localhost:30000"
+network = "testnet"
+auth_password = "12345"
+db_path = ":memory:"
+"#;
+        let config = GlobalConfig::load_from_str(config_toml).unwrap();
+        assert_eq!(config.node_host, "127.0.0.1:20443");
+        assert_eq!(config.node_transport, NodeTransport::Plain);
+
+        let https_toml = config_toml.replace("127.0.0.1:20443", "https://node.example.com:20443");
+        let config = GlobalConfig::load_from_str(&https_toml).unwrap();
+        assert_eq!(config.node_host, "node.example.com:20443");
+        assert_eq!(
+            config.node_transport,
+            NodeTransport::Tls { skip_verify: false }
+        );
+
+        // The default https port is used if none is given
+        let config = GlobalConfig::load_from_str(&format!(
+            "{}dangerous_skip_tls_verify = true\n",
+            config_toml.replace("127.0.0.1:20443", "https://node.example.com/")
+        ))
+        .unwrap();
+        assert_eq!(config.node_host, "node.example.com:443");
+        assert_eq!(
+            config.node_transport,
+            NodeTransport::Tls { skip_verify: true }
+        );
+
+        let bad_scheme_toml = config_toml.replace("127.0.0.1:20443", "ftp://127.0.0.1:20443");
+        assert!(matches!(
+            GlobalConfig::load_from_str(&bad_scheme_toml),
+            Err(ConfigError::BadField(field, _)) if field == "node_host"
+        ));
+    }
+
+    #[test]
+    fn signer_name_should_deserialize_correctly() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        assert!(config.signer_name.is_none());
+
+        let config_toml = r#"
+stacks_private_key = "6a1fc1a3183018c6d79a4e11e154d2bdad2d89ac8bc1b0a021de8b4d28774fbb01"
+node_host = "127.0.0.1:20443"
+endpoint = "localhost:30000"
+network = "testnet"
+auth_password = "12345"
+db_path = ":memory:"
+signer_name = "alice"
+"#;
+        let config = GlobalConfig::load_from_str(config_toml).unwrap();
+        assert_eq!(config.signer_name, Some("alice".to_string()));
+
+        let empty_name_toml = config_toml.replace("\"alice\"", "\" \"");
+        assert!(matches!(
+            GlobalConfig::load_from_str(&empty_name_toml),
+            Err(ConfigError::BadField(field, _)) if field == "signer_name"
+        ));
+    }
+
+    #[test]
+    fn stackerdb_slot_layout_should_deserialize_correctly() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        assert!(config.slot_layout.is_none());
+
+        let config_toml = r#"
+stacks_private_key = "6a1fc1a3183018c6d79a4e11e154d2bdad2d89ac8bc1b0a021de8b4d28774fbb01"
+node_host = "127.0.0.1:20443"
+endpoint = "localhost:30000"
+network = "testnet"
+auth_password = "12345"
+db_path = ":memory:"
+
+[stackerdb_slot_layout]
+BlockResponse = 0
+Transactions = 1
+"#;
+        let slot_layout = GlobalConfig::load_from_str(config_toml)
+            .unwrap()
+            .slot_layout
+            .expect("Missing slot layout");
+        assert_eq!(slot_layout.offset(&MessageSlotID::BlockResponse), Some(0));
+        assert_eq!(slot_layout.offset(&MessageSlotID::Transactions), Some(1));
+        assert_eq!(slot_layout.offset(&MessageSlotID::DkgBegin), None);
+
+        let shared_offset_toml = config_toml.replace("Transactions = 1", "Transactions = 0");
+        assert!(matches!(
+            GlobalConfig::load_from_str(&shared_offset_toml),
+            Err(ConfigError::BadField(field, _)) if field == "stackerdb_slot_layout"
+        ));
+    }
+
     #[test]
     fn test_config_to_string() {
         let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
@@ -645,4 +2234,784 @@ Metrics endpoint: 0.0.0.0:9090
             )
         );
     }
+
+    /// Build a config TOML from valid defaults, with the given fields overridden or added
+    fn config_toml_with(overrides: &[(&str, &str)]) -> String {
+        let mut fields = BTreeMap::from([
+            (
+                "stacks_private_key",
+                "\"6a1fc1a3183018c6d79a4e11e154d2bdad2d89ac8bc1b0a021de8b4d28774fbb01\"",
+            ),
+            ("node_host", "\"127.0.0.1:20443\""),
+            ("endpoint", "\"localhost:30000\""),
+            ("network", "\"testnet\""),
+            ("auth_password", "\"12345\""),
+            ("db_path", "\":memory:\""),
+        ]);
+        fields.extend(overrides.iter().copied());
+        fields
+            .into_iter()
+            .map(|(field, value)| format!("{field} = {value}\n"))
+            .collect()
+    }
+
+    #[test]
+    fn malformed_configs_should_report_every_violation() {
+        let tmp_dir = std::env::temp_dir();
+        let tmp_dir_path = format!("\"{}\"", tmp_dir.display());
+        let missing_parent_path = format!(
+            "\"{}\"",
+            tmp_dir
+                .join(format!("missing-{}", rand::random::<u64>()))
+                .join("signer.sqlite")
+                .display()
+        );
+        let testnet_chain_id = CHAIN_ID_TESTNET.to_string();
+        let mainnet_chain_id = CHAIN_ID_MAINNET.to_string();
+        let cases: Vec<(&str, Vec<(&str, &str)>, Vec<&str>)> = vec![
+            ("valid", vec![], vec![]),
+            (
+                "zero event timeout",
+                vec![("event_timeout_ms", "0")],
+                vec!["event_timeout_ms"],
+            ),
+            (
+                "health check timeout not above event timeout",
+                vec![
+                    ("event_timeout_ms", "5000"),
+                    ("health_check_timeout_ms", "5000"),
+                ],
+                vec!["health_check_timeout_ms"],
+            ),
+            (
+                "zero phase timeouts",
+                vec![("timeouts.nonce", "\"0s\""), ("timeouts.sign", "\"0ms\"")],
+                vec!["timeouts.nonce", "timeouts.sign"],
+            ),
+            (
+                "dkg phases out of order",
+                vec![
+                    ("timeouts.dkg_public", "\"3s\""),
+                    ("timeouts.dkg_private", "\"2s\""),
+                    ("timeouts.dkg_end", "\"1s\""),
+                ],
+                vec!["timeouts.dkg_private", "timeouts.dkg_end"],
+            ),
+            (
+                "dkg phases in order",
+                vec![
+                    ("timeouts.dkg_public", "\"1s\""),
+                    ("timeouts.dkg_private", "\"1s\""),
+                    ("timeouts.dkg_end", "\"2s\""),
+                ],
+                vec![],
+            ),
+            (
+                "sign shorter than nonce",
+                vec![("timeouts.nonce", "\"2m\""), ("timeouts.sign", "\"90s\"")],
+                vec!["timeouts.sign"],
+            ),
+            (
+                "deprecated dkg phase fields out of order",
+                vec![
+                    ("dkg_public_timeout_ms", "3000"),
+                    ("dkg_private_timeout_ms", "2000"),
+                    ("dkg_end_timeout_ms", "4000"),
+                ],
+                vec!["timeouts.dkg_private"],
+            ),
+            (
+                "node host without port",
+                vec![("node_host", "\"localhost\"")],
+                vec![],
+            ),
+            (
+                "mainnet with a testnet chain id",
+                vec![("network", "\"mainnet\""), ("chain_id", &testnet_chain_id)],
+                vec!["chain_id"],
+            ),
+            (
+                "testnet with the mainnet chain id",
+                vec![("chain_id", &mainnet_chain_id)],
+                vec!["chain_id"],
+            ),
+            (
+                "testnet with a custom chain id",
+                vec![("chain_id", "42")],
+                vec![],
+            ),
+            (
+                "uncompressed private key",
+                vec![(
+                    "stacks_private_key",
+                    "\"6a1fc1a3183018c6d79a4e11e154d2bdad2d89ac8bc1b0a021de8b4d28774fbb\"",
+                )],
+                vec!["stacks_private_key"],
+            ),
+            (
+                "zero tx fee",
+                vec![("tx_fee_ustx", "0")],
+                vec!["tx_fee_ustx"],
+            ),
+            (
+                "db path is a directory",
+                vec![("db_path", &tmp_dir_path)],
+                vec!["db_path"],
+            ),
+            (
+                "db path parent is missing",
+                vec![("db_path", &missing_parent_path)],
+                vec!["db_path"],
+            ),
+            (
+                "several violations",
+                vec![
+                    ("event_timeout_ms", "0"),
+                    ("tx_fee_ustx", "0"),
+                    ("max_tx_fee_ustx", "0"),
+                ],
+                vec!["event_timeout_ms", "tx_fee_ustx", "max_tx_fee_ustx"],
+            ),
+            (
+                "stale node nack knobs out of range",
+                vec![
+                    ("stale_node_nack.nack_threshold_percent", "0"),
+                    ("stale_node_nack.back_off_duration_ms", "600001"),
+                    ("stale_node_nack.nack_ttl_ms", "0"),
+                    ("stale_node_nack.max_nacks_per_block", "0"),
+                    ("stale_node_nack.nack_report_interval_ms", "0"),
+                ],
+                vec![
+                    "stale_node_nack.nack_threshold_percent",
+                    "stale_node_nack.back_off_duration_ms",
+                    "stale_node_nack.nack_ttl_ms",
+                    "stale_node_nack.max_nacks_per_block",
+                    "stale_node_nack.nack_report_interval_ms",
+                ],
+            ),
+            (
+                "no validation attempts",
+                vec![("max_validation_attempts", "0")],
+                vec!["max_validation_attempts"],
+            ),
+            (
+                "no rejected requests per round",
+                vec![("max_rejected_requests_per_round", "0")],
+                vec!["max_rejected_requests_per_round"],
+            ),
+            (
+                "stale node nack threshold above 100",
+                vec![("stale_node_nack.nack_threshold_percent", "101")],
+                vec!["stale_node_nack.nack_threshold_percent"],
+            ),
+            (
+                "stale node nack knobs at their bounds",
+                vec![
+                    ("stale_node_nack.nack_threshold_percent", "100"),
+                    ("stale_node_nack.back_off_duration_ms", "600000"),
+                ],
+                vec![],
+            ),
+        ];
+
+        for (name, overrides, expected_fields) in cases {
+            let toml = config_toml_with(&overrides);
+            let violating_fields: Vec<String> = match GlobalConfig::load_from_str(&toml) {
+                Ok(_) => vec![],
+                Err(ConfigError::Violations(violations)) => violations
+                    .into_iter()
+                    .map(|violation| match violation {
+                        ConfigError::InvalidField(field, _) => field,
+                        e => panic!("{name}: unexpected violation {e:?}"),
+                    })
+                    .collect(),
+                Err(e) => panic!("{name}: unexpected error {e:?}"),
+            };
+            assert_eq!(violating_fields, expected_fields, "{name}");
+        }
+    }
+
+    #[test]
+    fn log_format_should_parse() {
+        let log_format = |overrides: &[(&str, &str)]| {
+            GlobalConfig::load_from_str(&config_toml_with(overrides))
+                .map(|config| config.log_format)
+        };
+        assert_eq!(log_format(&[]).unwrap(), LogFormat::Text);
+        assert_eq!(
+            log_format(&[("log_format", "\"text\"")]).unwrap(),
+            LogFormat::Text
+        );
+        assert_eq!(
+            log_format(&[("log_format", "\"json\"")]).unwrap(),
+            LogFormat::Json
+        );
+        assert!(matches!(
+            log_format(&[("log_format", "\"xml\"")]),
+            Err(ConfigError::BadField(field, value)) if field == "log_format" && value == "xml"
+        ));
+    }
+
+    #[test]
+    fn log_format_should_be_read_before_loading_the_config() {
+        let path = std::env::temp_dir().join(format!("signer-log-{}.toml", rand::random::<u64>()));
+        // The rest of the configuration does not need to be valid
+        fs::write(&path, "log_format = \"json\"\nnode_host = 5").unwrap();
+        assert_eq!(LogFormat::peek_from_file(&path), LogFormat::Json);
+        fs::write(&path, config_toml_with(&[])).unwrap();
+        assert_eq!(LogFormat::peek_from_file(&path), LogFormat::Text);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(LogFormat::peek_from_file(&path), LogFormat::Text);
+    }
+
+    #[test]
+    fn missing_transaction_policy_should_parse() {
+        let policy = |overrides: &[(&str, &str)]| {
+            GlobalConfig::load_from_str(&config_toml_with(overrides))
+                .map(|config| config.missing_transaction_policy)
+        };
+        assert_eq!(policy(&[]).unwrap(), MissingTransactionPolicy::Reject);
+        assert_eq!(
+            policy(&[("missing_transaction_policy", "\"accept_and_warn\"")]).unwrap(),
+            MissingTransactionPolicy::AcceptAndWarn
+        );
+        assert_eq!(
+            policy(&[
+                ("missing_transaction_policy", "\"reject_after_n_blocks\""),
+                ("missing_transaction_reject_after_blocks", "3"),
+            ])
+            .unwrap(),
+            MissingTransactionPolicy::RejectAfterNBlocks(3)
+        );
+
+        assert!(matches!(
+            policy(&[("missing_transaction_policy", "\"sometimes\"")]),
+            Err(ConfigError::BadField(field, _)) if field == "missing_transaction_policy"
+        ));
+        for overrides in [
+            vec![("missing_transaction_policy", "\"reject_after_n_blocks\"")],
+            vec![
+                ("missing_transaction_policy", "\"reject_after_n_blocks\""),
+                ("missing_transaction_reject_after_blocks", "0"),
+            ],
+            vec![("missing_transaction_reject_after_blocks", "3")],
+        ] {
+            assert!(matches!(
+                policy(&overrides),
+                Err(ConfigError::InvalidField(field, _)) if field == "missing_transaction_reject_after_blocks"
+            ));
+        }
+    }
+
+    #[test]
+    fn stale_node_nack_section_should_be_optional() {
+        let config = GlobalConfig::load_from_str(&config_toml_with(&[])).unwrap();
+        assert_eq!(config.stale_node_nack_policy, None);
+
+        let config =
+            GlobalConfig::load_from_str(&config_toml_with(&[("stale_node_nack", "{}")])).unwrap();
+        assert_eq!(
+            config.stale_node_nack_policy,
+            Some(StaleNodeNackPolicy::default())
+        );
+
+        let config = GlobalConfig::load_from_str(&config_toml_with(&[
+            ("stale_node_nack.nack_threshold_percent", "50"),
+            ("stale_node_nack.back_off_duration_ms", "1000"),
+            ("stale_node_nack.nack_ttl_ms", "2000"),
+            ("stale_node_nack.resend_cooldown_ms", "3000"),
+            ("stale_node_nack.max_nacks_per_block", "4"),
+            ("stale_node_nack.nack_report_interval_ms", "5000"),
+        ]))
+        .unwrap();
+        let expected_policy = StaleNodeNackPolicy {
+            nack_threshold_percent: 50,
+            back_off_duration: Duration::from_secs(1),
+            nack_ttl: Duration::from_secs(2),
+            resend_cooldown: Duration::from_secs(3),
+            max_nacks_per_block: 4,
+            report_interval: Duration::from_secs(5),
+        };
+        assert_eq!(config.stale_node_nack_policy, Some(expected_policy));
+
+        let signer = Signer::from(generate_signer_config(&config, 5, 20));
+        assert_eq!(signer.stale_node_nack_policy, Some(expected_policy));
+    }
+
+    #[test]
+    fn network_presets_should_derive_consistent_settings() {
+        for (network, mainnet, chain_id, block_validation_timeout) in [
+            (
+                "mainnet",
+                true,
+                CHAIN_ID_MAINNET,
+                BLOCK_VALIDATION_TIMEOUT_MS,
+            ),
+            (
+                "testnet",
+                false,
+                CHAIN_ID_TESTNET,
+                BLOCK_VALIDATION_TIMEOUT_MS,
+            ),
+            (
+                "mocknet",
+                false,
+                CHAIN_ID_TESTNET,
+                MOCKNET_BLOCK_VALIDATION_TIMEOUT_MS,
+            ),
+        ] {
+            let toml = config_toml_with(&[
+                ("network", &format!("\"{network}\"")),
+                ("node_host", "\"127.0.0.1\""),
+            ]);
+            let config = GlobalConfig::load_from_str(&toml).unwrap();
+            let profile = config.network_profile;
+            assert_eq!(profile, config.network.profile(), "{network}");
+            assert_eq!(profile.mainnet, mainnet, "{network}");
+            assert_eq!(profile.chain_id, chain_id, "{network}");
+            let transaction_version = if mainnet {
+                TransactionVersion::Mainnet
+            } else {
+                TransactionVersion::Testnet
+            };
+            assert_eq!(profile.transaction_version, transaction_version);
+            assert_eq!(config.stacks_address.is_mainnet(), mainnet, "{network}");
+            assert_eq!(config.node_host, "127.0.0.1:20443", "{network}");
+            assert_eq!(
+                config.block_validation_timeout,
+                Duration::from_millis(block_validation_timeout),
+                "{network}"
+            );
+
+            let signer = Signer::from(generate_signer_config(&config, 5, 20));
+            assert_eq!(signer.mainnet, mainnet, "{network}");
+            let stacks_client = StacksClient::from(&config);
+            assert_eq!(stacks_client.get_chain_id(), chain_id, "{network}");
+        }
+
+        // Explicit settings take precedence over the derived defaults
+        let config = GlobalConfig::load_from_str(&config_toml_with(&[
+            ("network", "\"mocknet\""),
+            ("chain_id", "42"),
+            ("block_validation_timeout_ms", "1000"),
+        ]))
+        .unwrap();
+        assert_eq!(config.network_profile.chain_id, 42);
+        assert_eq!(config.block_validation_timeout, Duration::from_secs(1));
+        assert_eq!(StacksClient::from(&config).get_chain_id(), 42);
+    }
+
+    #[test]
+    fn violations_should_not_echo_the_private_key() {
+        let private_key = "6a1fc1a3183018c6d79a4e11e154d2bdad2d89ac8bc1b0a021de8b4d28774fbb";
+        let toml = config_toml_with(&[("stacks_private_key", &format!("\"{private_key}\""))]);
+        let e = GlobalConfig::load_from_str(&toml).unwrap_err();
+        assert!(matches!(e, ConfigError::Violations(_)));
+        assert!(!e.to_string().contains(private_key));
+    }
+
+    #[test]
+    fn runtime_updates_should_apply_to_the_running_signer() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let stacks_client = StacksClient::from(&config);
+
+        let mut updated_config = config.clone();
+        updated_config.node_host = "127.0.0.1:30443".to_string();
+        updated_config.tx_fee_ustx = config.tx_fee_ustx + 1;
+        updated_config.max_tx_fee_ustx = Some(config.tx_fee_ustx * 2);
+        updated_config.max_acceptable_median_fee_rate = 1;
+        updated_config.max_validation_attempts = 5;
+        updated_config.max_rejected_requests_per_round = 7;
+        updated_config.block_validation_timeout = Duration::from_secs(1);
+        updated_config.dkg_round_stall_timeout = Duration::from_secs(2);
+        updated_config.packet_future_tolerance = Duration::from_secs(4);
+        updated_config.nonce_timeout = Some(Duration::from_secs(3));
+        updated_config.clear_stackerdb_on_tenure_end = true;
+        updated_config.parallel_validation = true;
+
+        assert!(updated_config
+            .apply_runtime_updates(&mut signer, &stacks_client)
+            .is_empty());
+        assert_eq!(signer.tx_fee_ustx, updated_config.tx_fee_ustx);
+        assert_eq!(signer.max_tx_fee_ustx, updated_config.max_tx_fee_ustx);
+        assert_eq!(signer.max_acceptable_median_fee_rate, 1);
+        assert_eq!(signer.max_validation_attempts, 5);
+        assert_eq!(signer.max_rejected_requests_per_round, 7);
+        assert_eq!(signer.block_validation_timeout, Duration::from_secs(1));
+        assert_eq!(signer.dkg_round_stall_timeout, Duration::from_secs(2));
+        assert_eq!(signer.packet_future_tolerance, Duration::from_secs(4));
+        assert_eq!(
+            signer.coordinator.config.nonce_timeout,
+            Some(Duration::from_secs(3))
+        );
+        assert!(signer.clear_stackerdb_on_tenure_end);
+        assert!(signer.parallel_validation);
+    }
+
+    #[test]
+    fn runtime_updates_should_ignore_fields_requiring_a_restart() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let stacks_client = StacksClient::from(&config);
+
+        let mut updated_config = GlobalConfig::load_from_file("./src/tests/conf/signer-1.toml")
+            .expect("Failed to load config");
+        updated_config.network = Network::Mainnet;
+        updated_config.db_path = PathBuf::from("/tmp/other-signer.sqlite");
+        updated_config.tx_fee_ustx = config.tx_fee_ustx + 1;
+
+        let ignored_fields = updated_config.apply_runtime_updates(&mut signer, &stacks_client);
+        assert_eq!(
+            ignored_fields,
+            vec!["stacks_private_key", "network", "db_path"]
+        );
+        assert!(!signer.mainnet);
+        assert_eq!(signer.db_path, config.db_path);
+        assert_eq!(stacks_client.get_signer_address(), &config.stacks_address);
+        // The hot-reloadable fields are still applied
+        assert_eq!(signer.tx_fee_ustx, updated_config.tx_fee_ustx);
+
+        let mut reloaded_config = updated_config.clone();
+        reloaded_config.retain_restart_fields(&config);
+        assert_eq!(reloaded_config.stacks_address, config.stacks_address);
+        assert_eq!(reloaded_config.network, config.network);
+        assert_eq!(reloaded_config.db_path, config.db_path);
+        assert_eq!(reloaded_config.tx_fee_ustx, updated_config.tx_fee_ustx);
+    }
+
+    #[test]
+    fn env_vars_should_override_config_file_fields() {
+        let db_path = std::env::temp_dir().join("env-override-signer.sqlite");
+        let config = GlobalConfig::load_from_str_with_env(
+            &config_toml_with(&[("tx_fee_ustx", "1000")]),
+            [
+                ("STACKS_SIGNER_NODE_HOST", "127.0.0.1:30443"),
+                (
+                    "STACKS_SIGNER_STACKS_PRIVATE_KEY",
+                    "eb05c83546fdd2c79f10f5ad5434a90dd28f7e3acb7c092157aa1bc3656b012c01",
+                ),
+                ("STACKS_SIGNER_DB_PATH", db_path.to_str().unwrap()),
+                ("STACKS_SIGNER_TX_FEE_USTX", "2000"),
+                ("STACKS_SIGNER_RETRY_JITTER", "0.5"),
+                ("STACKS_SIGNER_COMPRESS_STACKERDB_CHUNKS", "false"),
+                (
+                    "STACKS_SIGNER_STACKERDB_SLOT_LAYOUT",
+                    "{ BlockResponse = 0, Transactions = 1 }",
+                ),
+                ("STACKS_SIGNER_UNKNOWN_FIELD", "ignored"),
+                ("OTHER_VARIABLE", "ignored"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string())),
+        )
+        .expect("Failed to load config");
+
+        assert_eq!(config.node_host, "127.0.0.1:30443");
+        assert_eq!(
+            config.stacks_private_key.to_hex(),
+            "eb05c83546fdd2c79f10f5ad5434a90dd28f7e3acb7c092157aa1bc3656b012c01"
+        );
+        assert_eq!(config.db_path, db_path);
+        assert_eq!(config.tx_fee_ustx, 2000);
+        assert_eq!(config.retry_config.jitter, 0.5);
+        assert!(config.chunk_compression_threshold.is_none());
+        assert_eq!(
+            config
+                .slot_layout
+                .expect("Missing slot layout")
+                .offset(&MessageSlotID::Transactions),
+            Some(1)
+        );
+
+        assert_eq!(
+            config.field_sources.get("node_host"),
+            Some(&ConfigSource::Environment)
+        );
+        assert_eq!(
+            config.field_sources.get("tx_fee_ustx"),
+            Some(&ConfigSource::Environment)
+        );
+        assert_eq!(
+            config.field_sources.get("endpoint"),
+            Some(&ConfigSource::File)
+        );
+        assert_eq!(
+            config.field_sources.get("event_timeout_ms"),
+            Some(&ConfigSource::Default)
+        );
+        assert_eq!(
+            config.env_overridden_fields().collect::<Vec<_>>(),
+            vec![
+                "compress_stackerdb_chunks",
+                "db_path",
+                "node_host",
+                "retry_jitter",
+                "stackerdb_slot_layout",
+                "stacks_private_key",
+                "tx_fee_ustx",
+            ]
+        );
+    }
+
+    #[test]
+    fn process_env_vars_should_override_config_file_fields() {
+        // Only override a field no other test relies on, as the environment is shared
+        std::env::set_var("STACKS_SIGNER_DKG_ROUND_STALL_TIMEOUT_MS", "1234");
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml");
+        std::env::remove_var("STACKS_SIGNER_DKG_ROUND_STALL_TIMEOUT_MS");
+
+        let config = config.expect("Failed to load config");
+        assert_eq!(config.dkg_round_stall_timeout, Duration::from_millis(1234));
+        assert_eq!(
+            config.field_sources.get("dkg_round_stall_timeout_ms"),
+            Some(&ConfigSource::Environment)
+        );
+    }
+
+    #[test]
+    fn malformed_env_vars_should_not_echo_their_value() {
+        let load = |name: &str, value: &str| {
+            GlobalConfig::load_from_str_with_env(
+                &config_toml_with(&[]),
+                [(name.to_string(), value.to_string())],
+            )
+            .expect_err("Loaded a malformed config")
+            .to_string()
+        };
+
+        let message = load("STACKS_SIGNER_TX_FEE_USTX", "lots-of-ustx");
+        assert!(message.contains("STACKS_SIGNER_TX_FEE_USTX"));
+        assert!(!message.contains("lots-of-ustx"));
+
+        let secret = "deadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef";
+        let message = load("STACKS_SIGNER_STACKS_PRIVATE_KEY", secret);
+        assert!(message.contains("stacks_private_key"));
+        assert!(!message.contains(secret));
+
+        // The merged config goes through the same validation as the config file
+        let message = load("STACKS_SIGNER_NODE_HOST", "127.0.0.1");
+        assert!(message.contains("node_host"));
+    }
+
+    /// Write the given private key to a fresh file with the given permissions
+    fn write_private_key_file(key: &str, mode: u32) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let path = std::env::temp_dir().join(format!("signer-key-{}", rand::random::<u64>()));
+        fs::write(&path, format!("{key}\n")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+        path
+    }
+
+    /// A config TOML from valid defaults, loading the private key from the given file
+    fn config_toml_with_key_path(path: &Path) -> String {
+        config_toml_with(&[])
+            .lines()
+            .filter(|line| !line.starts_with("stacks_private_key"))
+            .chain([format!("stacks_private_key_path = \"{}\"", path.display()).as_str()])
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn private_key_should_load_from_file() {
+        let key = "6a1fc1a3183018c6d79a4e11e154d2bdad2d89ac8bc1b0a021de8b4d28774fbb01";
+        let path = write_private_key_file(key, 0o600);
+        let config = GlobalConfig::load_from_str_with_env(&config_toml_with_key_path(&path), [])
+            .expect("Failed to load config");
+        assert_eq!(config.stacks_private_key.to_hex(), key);
+        assert_eq!(
+            config.field_sources.get("stacks_private_key_path"),
+            Some(&ConfigSource::File)
+        );
+    }
+
+    #[test]
+    fn private_key_file_accessible_by_others_should_be_refused() {
+        let key = "6a1fc1a3183018c6d79a4e11e154d2bdad2d89ac8bc1b0a021de8b4d28774fbb01";
+        let path = write_private_key_file(key, 0o644);
+        let err = GlobalConfig::load_from_str_with_env(&config_toml_with_key_path(&path), [])
+            .expect_err("Loaded a private key readable by other users");
+        assert!(matches!(
+            &err,
+            ConfigError::InvalidField(field, _) if field == "stacks_private_key_path"
+        ));
+        assert!(err.to_string().contains("644"));
+        assert!(!err.to_string().contains(key));
+    }
+
+    #[test]
+    fn exactly_one_private_key_source_should_be_set() {
+        let key = "6a1fc1a3183018c6d79a4e11e154d2bdad2d89ac8bc1b0a021de8b4d28774fbb01";
+        let path = write_private_key_file(key, 0o600);
+        let both_sources = format!(
+            "{}\nstacks_private_key_path = \"{}\"\n",
+            config_toml_with(&[]),
+            path.display()
+        );
+        let no_source = config_toml_with_key_path(&path).replace("stacks_private_key_path", "#");
+        for config_toml in [both_sources, no_source] {
+            let err = GlobalConfig::load_from_str_with_env(&config_toml, [])
+                .expect_err("Loaded a config without exactly one private key source");
+            assert!(matches!(
+                &err,
+                ConfigError::InvalidField(field, _) if field == "stacks_private_key"
+            ));
+            assert!(!err.to_string().contains(key));
+        }
+    }
+
+    #[cfg(feature = "private_key_cmd")]
+    #[test]
+    fn private_key_should_load_from_command_output() {
+        let key = "6a1fc1a3183018c6d79a4e11e154d2bdad2d89ac8bc1b0a021de8b4d28774fbb01";
+        let config_toml = config_toml_with(&[]).replace(
+            "stacks_private_key =",
+            &format!("stacks_private_key_cmd = \"echo {key}\"\n#"),
+        );
+        let config =
+            GlobalConfig::load_from_str_with_env(&config_toml, []).expect("Failed to load config");
+        assert_eq!(config.stacks_private_key.to_hex(), key);
+
+        let failing_toml = config_toml.replace("echo", "false");
+        assert!(matches!(
+            GlobalConfig::load_from_str_with_env(&failing_toml, []),
+            Err(ConfigError::InvalidField(field, _)) if field == "stacks_private_key_cmd"
+        ));
+    }
+
+    #[test]
+    fn durations_should_parse_from_human_readable_strings() {
+        assert_eq!(parse_duration("500ms"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_duration("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration(" 2m "), Some(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1h"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("1m30s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("0s"), Some(Duration::ZERO));
+        for malformed in ["", "30", "s", "1.5s", "-1s", "2 m", "3d", "1m30"] {
+            assert_eq!(parse_duration(malformed), None, "{malformed:?}");
+        }
+    }
+
+    #[test]
+    fn timeouts_section_should_flow_through_to_the_coordinator_config() {
+        let config_toml = format!(
+            r#"{}
+[timeouts]
+dkg_public = "30s"
+dkg_private = "1m"
+dkg_end = "1m30s"
+nonce = "10s"
+sign = "20s"
+"#,
+            config_toml_with(&[])
+        );
+        let config =
+            GlobalConfig::load_from_str_with_env(&config_toml, []).expect("Failed to load config");
+        let signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let coordinator_config = &signer.coordinator.config;
+        assert_eq!(
+            coordinator_config.dkg_public_timeout,
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            coordinator_config.dkg_private_timeout,
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            coordinator_config.dkg_end_timeout,
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            coordinator_config.nonce_timeout,
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            coordinator_config.sign_timeout,
+            Some(Duration::from_secs(20))
+        );
+
+        // Unset timeouts use the defaults
+        let config = GlobalConfig::load_from_str_with_env(&config_toml_with(&[]), []).unwrap();
+        assert_eq!(
+            config.dkg_end_timeout,
+            Some(Duration::from_millis(DKG_END_TIMEOUT_MS))
+        );
+        assert_eq!(
+            config.sign_timeout,
+            Some(Duration::from_millis(SIGN_TIMEOUT_MS))
+        );
+    }
+
+    #[test]
+    fn malformed_timeouts_should_be_rejected() {
+        let load = |overrides: &[(&str, &str)]| {
+            GlobalConfig::load_from_str_with_env(&config_toml_with(overrides), [])
+        };
+        assert!(matches!(
+            load(&[("timeouts.sign", "\"soon\"")]),
+            Err(ConfigError::BadField(field, _)) if field == "timeouts.sign"
+        ));
+        assert!(matches!(
+            load(&[("timeouts.nonce", "\"10s\""), ("nonce_timeout_ms", "10000")]),
+            Err(ConfigError::InvalidField(field, _)) if field == "timeouts.nonce"
+        ));
+        let Err(ConfigError::Violations(violations)) =
+            load(&[("timeouts.nonce", "\"30s\""), ("timeouts.sign", "\"10s\"")])
+        else {
+            panic!("Expected a sign timeout shorter than the nonce timeout to be rejected");
+        };
+        assert!(matches!(
+            violations.as_slice(),
+            [ConfigError::InvalidField(field, _)] if field == "timeouts.sign"
+        ));
+    }
+
+    #[test]
+    fn shutdown_timeout_should_parse() {
+        let load = |fields: &[(&str, &str)]| {
+            GlobalConfig::load_from_str_with_env(&config_toml_with(fields), [])
+                .expect("Failed to load config")
+        };
+        assert_eq!(
+            load(&[]).shutdown_timeout,
+            Duration::from_millis(SHUTDOWN_TIMEOUT_MS)
+        );
+        assert_eq!(
+            load(&[("shutdown_timeout_ms", "1000")]).shutdown_timeout,
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn record_events_path_should_parse() {
+        let load = |fields: &[(&str, &str)]| {
+            GlobalConfig::load_from_str_with_env(&config_toml_with(fields), [])
+                .expect("Failed to load config")
+        };
+        assert_eq!(load(&[]).record_events_path, None);
+        assert_eq!(
+            load(&[("record_events_path", "\"/tmp/signer.events\"")]).record_events_path,
+            Some(PathBuf::from("/tmp/signer.events"))
+        );
+    }
+
+    #[test]
+    fn alert_webhook_url_should_be_http() {
+        let load = |fields: &[(&str, &str)]| {
+            GlobalConfig::load_from_str_with_env(&config_toml_with(fields), [])
+        };
+        assert_eq!(load(&[]).unwrap().alert_webhook_url, None);
+        assert_eq!(
+            load(&[("alert_webhook_url", "\"https://alerts.example.com/hook\"")])
+                .unwrap()
+                .alert_webhook_url,
+            Some("https://alerts.example.com/hook".to_string())
+        );
+        for url in ["\"alerts.example.com\"", "\"ftp://alerts.example.com\""] {
+            assert!(matches!(
+                load(&[("alert_webhook_url", url)]),
+                Err(ConfigError::BadField(field, _)) if field == "alert_webhook_url"
+            ));
+        }
+    }
 }
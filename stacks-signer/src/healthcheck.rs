@@ -0,0 +1,420 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::fmt::Display;
+use std::time::Duration;
+
+use libsigner::SignerSlotOwners;
+use stacks_common::types::StacksEpochId;
+use wsts::curve::point::Point;
+
+use crate::client::{StackerDB, StacksClient};
+use crate::config::GlobalConfig;
+use crate::runloop::{signer_config_for, StacksNodeConfigSource};
+use crate::v1::signerdb::SignerDb;
+
+/// How long each request to the stacks node is retried for before its check fails
+const CHECK_DEADLINE: Duration = Duration::from_secs(10);
+
+/// The outcome of a single check of the signer environment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    /// The name of the check
+    pub name: &'static str,
+    /// Whether the check passed
+    pub passed: bool,
+    /// What was found, or why the check failed
+    pub details: String,
+}
+
+impl CheckResult {
+    /// A passed check
+    pub fn pass(name: &'static str, details: impl Into<String>) -> Self {
+        Self {
+            name,
+            passed: true,
+            details: details.into(),
+        }
+    }
+
+    /// A failed check
+    pub fn fail(name: &'static str, details: impl Into<String>) -> Self {
+        Self {
+            name,
+            passed: false,
+            details: details.into(),
+        }
+    }
+}
+
+impl Display for CheckResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = if self.passed { "PASS" } else { "FAIL" };
+        write!(f, "[{status}] {}: {}", self.name, self.details)
+    }
+}
+
+/// Check that the signer described by the config can run: that its node is reachable and in a
+/// Nakamoto epoch, that it is registered in the upcoming reward set with the keys of the config,
+/// that it owns a stacker-db slot it can read (and, if `write_test` is set, write), and that its
+/// database opens. The node checks stop at the first failure, as each builds on the last.
+///
+/// The write test overwrites the signer's transactions slot with an empty message, so it should
+/// not be run while the signer is running.
+pub fn run_checks(config: &GlobalConfig, write_test: bool) -> Vec<CheckResult> {
+    let mut config = config.clone();
+    config.retry_config = config.retry_config.with_deadline(CHECK_DEADLINE);
+    config.stackerdb_write_retry_config = config
+        .stackerdb_write_retry_config
+        .with_deadline(CHECK_DEADLINE);
+    let mut results = vec![];
+    if let Err(failure) = check_node(&config, write_test, &mut results) {
+        results.push(failure);
+    }
+    results.push(check_signer_db(&config));
+    results
+}
+
+/// Run the checks that need the stacks node, pushing each passed check to `results`
+fn check_node(
+    config: &GlobalConfig,
+    write_test: bool,
+    results: &mut Vec<CheckResult>,
+) -> Result<(), CheckResult> {
+    let stacks_client = StacksClient::from(config);
+    let signer_address = *stacks_client.get_signer_address();
+
+    let peer_info = stacks_client.get_peer_info().map_err(|e| {
+        CheckResult::fail("node", format!("could not reach {}: {e}", config.node_host))
+    })?;
+    results.push(CheckResult::pass(
+        "node",
+        format!(
+            "reached {} running {}",
+            config.node_host, peer_info.server_version
+        ),
+    ));
+
+    let epoch = stacks_client
+        .get_node_epoch()
+        .map_err(|e| CheckResult::fail("epoch", format!("could not get the node epoch: {e}")))?;
+    if epoch < StacksEpochId::Epoch25 {
+        return Err(CheckResult::fail(
+            "epoch",
+            format!(
+                "the node is in epoch {epoch} at burn block height {}, but signers are only active from epoch 2.5",
+                peer_info.burn_block_height
+            ),
+        ));
+    }
+    results.push(CheckResult::pass(
+        "epoch",
+        format!(
+            "the node is in epoch {epoch} at burn block height {}",
+            peer_info.burn_block_height
+        ),
+    ));
+
+    let reward_cycle_info = stacks_client.get_current_reward_cycle_info().map_err(|e| {
+        CheckResult::fail(
+            "reward set",
+            format!("could not get the current reward cycle: {e}"),
+        )
+    })?;
+    // The next reward set is only calculated once its prepare phase has started
+    let reward_cycle = if reward_cycle_info
+        .is_in_next_prepare_phase(reward_cycle_info.last_burnchain_block_height)
+    {
+        reward_cycle_info.reward_cycle.saturating_add(1)
+    } else {
+        reward_cycle_info.reward_cycle
+    };
+    let mut slot_owners = SignerSlotOwners::default();
+    let source = StacksNodeConfigSource {
+        config,
+        stacks_client: &stacks_client,
+        slot_owners: &mut slot_owners,
+        event_recorder: None,
+        alert_notifier: None,
+    };
+    let signer_entries = source
+        .get_parsed_reward_set(reward_cycle)
+        .map_err(|e| {
+            CheckResult::fail(
+                "reward set",
+                format!("could not get the reward set of reward cycle {reward_cycle}: {e}"),
+            )
+        })?
+        .ok_or_else(|| {
+            CheckResult::fail(
+                "reward set",
+                format!("there are no registered signers in reward cycle {reward_cycle}"),
+            )
+        })?;
+    let Some(signer_id) = signer_entries.signer_ids.get(&signer_address).copied() else {
+        return Err(CheckResult::fail(
+            "reward set",
+            format!(
+                "{signer_address} is not one of the {} signers in the reward set of reward cycle {reward_cycle}",
+                signer_entries.signer_ids.len()
+            ),
+        ));
+    };
+    let key_ids = signer_entries
+        .signer_key_ids
+        .get(&signer_id)
+        .cloned()
+        .unwrap_or_default();
+    results.push(CheckResult::pass(
+        "reward set",
+        format!(
+            "{signer_address} is signer #{signer_id} with key ids {key_ids:?} in reward cycle {reward_cycle}"
+        ),
+    ));
+
+    let registered_key = signer_entries.signer_public_keys.get(&signer_id);
+    if registered_key != Some(&Point::from(config.ecdsa_private_key)) {
+        return Err(CheckResult::fail(
+            "keys",
+            format!(
+                "the private key does not correspond to the registered public key {}",
+                registered_key.map_or_else(|| "(none)".to_string(), |key| key.to_string())
+            ),
+        ));
+    }
+    results.push(CheckResult::pass(
+        "keys",
+        "the private key corresponds to the registered public key",
+    ));
+
+    let signer_slot_ids = source.get_parsed_signer_slots(reward_cycle).map_err(|e| {
+        CheckResult::fail(
+            "stacker-db slots",
+            format!("could not get the signer slots of reward cycle {reward_cycle}: {e}"),
+        )
+    })?;
+    let Some((slot_id, num_slots)) = signer_slot_ids.get(&signer_address).copied() else {
+        return Err(CheckResult::fail(
+            "stacker-db slots",
+            format!("{signer_address} does not own a signers stacker-db slot in reward cycle {reward_cycle}"),
+        ));
+    };
+    results.push(CheckResult::pass(
+        "stacker-db slots",
+        format!("owns slot {slot_id} with {num_slots} message slots"),
+    ));
+
+    let signer_config = signer_config_for(
+        config,
+        &signer_address,
+        reward_cycle,
+        signer_entries,
+        signer_slot_ids,
+        &mut slot_owners,
+        None,
+        None,
+    )
+    .ok_or_else(|| {
+        CheckResult::fail(
+            "stacker-db read",
+            "could not build the signer configuration; the slot layout may be invalid",
+        )
+    })?;
+    let mut stackerdb = StackerDB::from(&signer_config);
+    let state = stackerdb.get_encrypted_signer_state(slot_id).map_err(|e| {
+        CheckResult::fail(
+            "stacker-db read",
+            format!("could not read slot {slot_id}: {e}"),
+        )
+    })?;
+    results.push(CheckResult::pass(
+        "stacker-db read",
+        match state {
+            Some(state) => format!(
+                "read {} bytes of signer state from slot {slot_id}",
+                state.len()
+            ),
+            None => format!("read slot {slot_id}, which holds no signer state yet"),
+        },
+    ));
+
+    if write_test {
+        stackerdb.clear_slots(CHECK_DEADLINE).map_err(|e| {
+            CheckResult::fail(
+                "stacker-db write",
+                format!("could not write to slot {slot_id}: {e}"),
+            )
+        })?;
+        results.push(CheckResult::pass(
+            "stacker-db write",
+            format!("overwrote the transactions of slot {slot_id} with an empty message"),
+        ));
+    }
+    Ok(())
+}
+
+/// Check that the signer database opens
+fn check_signer_db(config: &GlobalConfig) -> CheckResult {
+    match SignerDb::new(&config.db_path) {
+        Ok(_) => CheckResult::pass("signer db", format!("opened {}", config.db_path.display())),
+        Err(e) => CheckResult::fail(
+            "signer db",
+            format!("could not open {}: {e}", config.db_path.display()),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread::spawn;
+
+    use blockstack_lib::chainstate::stacks::address::PoxAddress;
+    use blockstack_lib::chainstate::stacks::boot::{
+        NakamotoSignerEntry, PoxStartCycleInfo, RewardSet,
+    };
+    use blockstack_lib::net::api::getstackers::GetStackersResponse;
+    use clarity::vm::types::{PrincipalData, TupleData};
+    use clarity::vm::Value as ClarityValue;
+    use stacks_common::types::chainstate::{StacksPrivateKey, StacksPublicKey};
+
+    use super::*;
+    use crate::client::tests::{
+        build_get_peer_info_response, build_get_pox_data_response, build_read_only_response,
+        write_response, MockServerClient,
+    };
+
+    /// The node responses up to the reward set: the node info, the epoch and the reward
+    /// cycle, which is 1 and outside of its prepare phase
+    fn node_responses() -> Vec<String> {
+        let burn_block_height = 150;
+        let peer_response = build_get_peer_info_response(Some(burn_block_height), None).0;
+        let mut pox_data = build_get_pox_data_response(Some(1), None, Some(100), Some(1000)).1;
+        pox_data.first_burnchain_block_height = 0;
+        pox_data.current_burnchain_block_height = burn_block_height;
+        pox_data.reward_phase_block_length = 90;
+        pox_data.prepare_phase_block_length = 10;
+        let pox_response = format!(
+            "HTTP/1.1 200 OK\n\n{}",
+            serde_json::to_string(&pox_data).unwrap()
+        );
+        vec![
+            peer_response.clone(),
+            pox_response.clone(),
+            peer_response,
+            pox_response,
+        ]
+    }
+
+    /// A reward set response with a single signer using the given private key
+    fn reward_set_response(config: &GlobalConfig, registered: bool) -> String {
+        let private_key = if registered {
+            config.stacks_private_key
+        } else {
+            StacksPrivateKey::new()
+        };
+        let mut signing_key = [0u8; 33];
+        signing_key
+            .copy_from_slice(&StacksPublicKey::from_private(&private_key).to_bytes_compressed());
+        let stackers_response = GetStackersResponse {
+            stacker_set: RewardSet {
+                rewarded_addresses: vec![PoxAddress::standard_burn_address(false)],
+                start_cycle_state: PoxStartCycleInfo {
+                    missed_reward_slots: vec![],
+                },
+                signers: Some(vec![NakamotoSignerEntry {
+                    signing_key,
+                    stacked_amt: 1000,
+                    weight: 4,
+                }]),
+                pox_ustx_threshold: None,
+            },
+        };
+        format!(
+            "HTTP/1.1 200 OK\n\n{}",
+            serde_json::to_string(&stackers_response).unwrap()
+        )
+    }
+
+    #[test]
+    fn run_checks_should_pass() {
+        let mock = MockServerClient::new();
+        let config = mock.config.clone();
+        let signer_slots = ClarityValue::okay(
+            ClarityValue::cons_list_unsanitized(vec![ClarityValue::Tuple(
+                TupleData::from_data(vec![
+                    ("num-slots".into(), ClarityValue::UInt(13)),
+                    (
+                        "signer".into(),
+                        ClarityValue::Principal(PrincipalData::Standard(
+                            (*mock.client.get_signer_address()).into(),
+                        )),
+                    ),
+                ])
+                .unwrap(),
+            )])
+            .unwrap(),
+        )
+        .unwrap();
+        let mut responses = node_responses();
+        responses.push(reward_set_response(&config, true));
+        responses.push(build_read_only_response(&signer_slots));
+        // The signer has not persisted its state yet
+        responses.push("HTTP/1.1 200 OK\n\n".to_string());
+
+        let h = spawn(move || run_checks(&config, false));
+        for response in responses {
+            write_response(mock.server.try_clone().unwrap(), response.as_bytes());
+        }
+        let results = h.join().unwrap();
+        let names: Vec<_> = results.iter().map(|result| result.name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "node",
+                "epoch",
+                "reward set",
+                "keys",
+                "stacker-db slots",
+                "stacker-db read",
+                "signer db"
+            ]
+        );
+        assert!(results.iter().all(|result| result.passed), "{results:?}");
+    }
+
+    #[test]
+    fn run_checks_should_fail_when_missing_from_reward_set() {
+        let mock = MockServerClient::new();
+        let config = mock.config.clone();
+        let mut responses = node_responses();
+        responses.push(reward_set_response(&config, false));
+
+        let h = spawn(move || run_checks(&config, false));
+        for response in responses {
+            write_response(mock.server.try_clone().unwrap(), response.as_bytes());
+        }
+        let results = h.join().unwrap();
+        let failed: Vec<_> = results.iter().filter(|result| !result.passed).collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].name, "reward set");
+        assert!(failed[0]
+            .details
+            .contains(&mock.client.get_signer_address().to_string()));
+        // The database is still checked
+        assert_eq!(results.last().unwrap().name, "signer db");
+        assert!(results.last().unwrap().passed);
+    }
+}
@@ -20,14 +20,22 @@ Usage documentation can be found in the [README](https://github.com/Trust-Machin
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+/// Alerting operators of critical signer events through a webhook
+pub mod alerting;
 /// The cli module for the signer binary
 pub mod cli;
 /// The signer client for communicating with stackerdb/stacks nodes
 pub mod client;
 /// The configuration module for the signer
 pub mod config;
+/// Checks of the signer environment
+pub mod healthcheck;
+/// Structured logging for the signers
+pub mod logging;
 /// The monitoring server for the signer
 pub mod monitoring;
+/// Recording signer sessions to inspect them when debugging
+pub mod recording;
 /// The primary runloop for the signer
 pub mod runloop;
 /// The v0 implementation of the signer. This does not include WSTS support
@@ -36,12 +44,14 @@ pub mod v0;
 pub mod v1;
 use std::fmt::{Debug, Display};
 use std::sync::mpsc::Sender;
+use std::time::Duration;
 
 use libsigner::{SignerEvent, SignerEventTrait};
 use wsts::state_machine::OperationResult;
 
 use crate::client::StacksClient;
-use crate::config::SignerConfig;
+use crate::config::{GlobalConfig, SignerConfig};
+use crate::monitoring::{SignerMetrics, SignerStatus};
 use crate::runloop::RunLoopCommand;
 
 /// A trait which provides a common `Signer` interface for `v1` and `v2`
@@ -52,6 +62,11 @@ pub trait Signer<T: SignerEventTrait>: Debug + Display {
     fn update_next_signer_data(&mut self, next_signer_config: &SignerConfig);
     /// Get the reward cycle of the signer
     fn reward_cycle(&self) -> u64;
+    /// Clean up after the signer's tenure has ended, right before it is dropped
+    fn cleanup(&mut self);
+    /// Apply the settings of a reloaded configuration that can change while the signer runs.
+    /// The stacks node settings are applied to `stacks_client` by the caller beforehand.
+    fn apply_runtime_updates(&mut self, config: &GlobalConfig, stacks_client: &StacksClient);
     /// Process an event
     fn process_event(
         &mut self,
@@ -67,4 +82,12 @@ pub trait Signer<T: SignerEventTrait>: Debug + Display {
         current_reward_cycle: u64,
         command: Option<RunLoopCommand>,
     );
+    /// Take a snapshot of the signer's state to export as metrics
+    fn signer_metrics(&self) -> SignerMetrics;
+    /// Take a snapshot of the signer's state to serve as its status
+    fn signer_status(&self) -> SignerStatus;
+    /// Finish the in-progress operation before the process stops, starting no new one.
+    /// Called after each pass of the event loop once a shutdown is requested, until it
+    /// returns true to signal the signer is ready to stop.
+    fn graceful_shutdown(&mut self, timeout: Duration) -> bool;
 }
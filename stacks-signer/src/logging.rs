@@ -0,0 +1,113 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::ops::Deref;
+use std::sync::Mutex;
+use std::{env, io};
+
+use slog::{o, Drain, Logger};
+use stacks_common::util::log::{get_loglevel, LOGGER};
+
+use crate::config::LogFormat;
+
+/// Set up the global logger, which every record of the signer goes through, to write the given
+/// format. Must be called before anything is logged, as the global logger is built on first use.
+pub fn init_global_logger(format: LogFormat) {
+    if format == LogFormat::Json {
+        env::set_var("STACKS_LOG_JSON", "1");
+    }
+    // Build the global logger before any other thread gets to log
+    let _ = LOGGER.deref();
+}
+
+/// Build a logger that writes each record to `writer` as a line of JSON, with the record's
+/// key-values and those of the logger as fields
+pub fn json_logger<W: io::Write + Send + 'static>(writer: W) -> Logger {
+    let drain = Mutex::new(slog_json::Json::default(writer));
+    let filtered_drain = slog::LevelFilter::new(drain, get_loglevel()).ignore_res();
+    Logger::root(filtered_drain, o!())
+}
+
+/// The logger of a signer. It attaches the signer's ID and reward cycle to each record, so they
+/// appear as fields in structured output instead of being interpolated into the message.
+/// Log through it with the `signer_*!` macros, which honour the stacks log level.
+#[derive(Debug, Clone)]
+pub struct SignerLogger {
+    logger: Logger,
+}
+
+impl SignerLogger {
+    /// Create the logger of the given signer as a child of the global logger, writing in the
+    /// format it was set up with by `init_global_logger`
+    pub fn new(signer_id: u32, reward_cycle: u64) -> Self {
+        Self::with_root(&LOGGER, signer_id, reward_cycle)
+    }
+
+    /// Create the logger of the given signer as a child of `root`
+    pub fn with_root(root: &Logger, signer_id: u32, reward_cycle: u64) -> Self {
+        Self {
+            logger: root.new(o!("signer_id" => signer_id, "reward_cycle" => reward_cycle)),
+        }
+    }
+}
+
+impl Deref for SignerLogger {
+    type Target = Logger;
+
+    fn deref(&self) -> &Logger {
+        &self.logger
+    }
+}
+
+/// Log an info record through a `SignerLogger`
+#[macro_export]
+macro_rules! signer_info {
+    ($logger:expr, $($arg:tt)*) => ({
+        if slog::Level::Info.is_at_least(stacks_common::util::log::get_loglevel()) {
+            slog::slog_info!($logger, $($arg)*)
+        }
+    })
+}
+
+/// Log a debug record through a `SignerLogger`
+#[macro_export]
+macro_rules! signer_debug {
+    ($logger:expr, $($arg:tt)*) => ({
+        if slog::Level::Debug.is_at_least(stacks_common::util::log::get_loglevel()) {
+            slog::slog_debug!($logger, $($arg)*)
+        }
+    })
+}
+
+/// Log a warning record through a `SignerLogger`
+#[macro_export]
+macro_rules! signer_warn {
+    ($logger:expr, $($arg:tt)*) => ({
+        if slog::Level::Warning.is_at_least(stacks_common::util::log::get_loglevel()) {
+            slog::slog_warn!($logger, $($arg)*)
+        }
+    })
+}
+
+/// Log an error record through a `SignerLogger`
+#[macro_export]
+macro_rules! signer_error {
+    ($logger:expr, $($arg:tt)*) => ({
+        if slog::Level::Error.is_at_least(stacks_common::util::log::get_loglevel()) {
+            slog::slog_error!($logger, $($arg)*)
+        }
+    })
+}
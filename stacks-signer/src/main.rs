@@ -27,25 +27,51 @@ extern crate serde_json;
 extern crate toml;
 
 use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use blockstack_lib::util_lib::signed_structured_data::pox4::make_pox_4_signer_key_signature;
 use clap::Parser;
 use clarity::vm::types::QualifiedContractIdentifier;
-use libsigner::{SignerSession, StackerDBSession};
+use libsigner::v1::messages::SignerMessage;
+use libsigner::{SignerEvent, SignerSession, StackerDBSession};
 use libstackerdb::StackerDBChunkData;
 use slog::slog_debug;
 use stacks_common::debug;
+use stacks_common::deps_common::ctrlc::{self as termination, SignalId};
 use stacks_common::util::hash::to_hex;
 use stacks_common::util::secp256k1::{MessageSignature, Secp256k1PublicKey};
 use stacks_signer::cli::{
-    Cli, Command, GenerateStackingSignatureArgs, GetChunkArgs, GetLatestChunkArgs, PutChunkArgs,
-    RunSignerArgs, StackerDBArgs,
+    CheckArgs, Cli, Command, DbArgs, DbCommand, DumpArgs, GenerateStackingSignatureArgs,
+    GetChunkArgs, GetLatestChunkArgs, PutChunkArgs, RunSignerArgs, SimulateArgs, StackerDBArgs,
 };
-use stacks_signer::config::GlobalConfig;
+use stacks_signer::config::{GlobalConfig, LogFormat};
+use stacks_signer::healthcheck::run_checks;
+use stacks_signer::logging;
+use stacks_signer::recording::{
+    read_recording, RecordedConfigSource, RecordedEntry, RecordedMessage,
+};
+use stacks_signer::runloop::SignerConfigSource;
 use stacks_signer::v1;
+use stacks_signer::v1::signer::BlockInfo;
+use stacks_signer::v1::signerdb::SignerDb;
+use stacks_signer::v1::simulate::{simulate, SimulationParams};
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::{fmt, EnvFilter};
 
+/// Load the signer configuration, exiting with every problem found if it is invalid
+fn load_config(path: &PathBuf) -> GlobalConfig {
+    GlobalConfig::try_from(path).unwrap_or_else(|e| {
+        eprintln!(
+            "Failed to load signer configuration from {}: {e}",
+            path.display()
+        );
+        process::exit(1);
+    })
+}
+
 /// Create a new stacker db session
 fn stackerdb_session(host: &str, contract: QualifiedContractIdentifier) -> StackerDBSession {
     let mut session = StackerDBSession::new(host, contract.clone());
@@ -100,20 +126,45 @@ fn handle_put_chunk(args: PutChunkArgs) {
     println!("{}", serde_json::to_string(&chunk_ack).unwrap());
 }
 
+/// Stop the signer once its in-progress operations complete when the process is asked to
+/// terminate. A second request stops it immediately.
+fn set_shutdown_signal_handler(shutdown_requested: Arc<AtomicBool>) {
+    termination::set_handler(move |sig_id| {
+        if sig_id == SignalId::Bus {
+            eprintln!("Caught SIGBUS; crashing immediately and dumping core");
+            process::abort();
+        }
+        if shutdown_requested.swap(true, Ordering::SeqCst) {
+            eprintln!("Termination request received again (signal `{sig_id}`), stopping now");
+            process::exit(1);
+        }
+        eprintln!("Termination request received (signal `{sig_id}`), will complete the in-progress signer operations and stop");
+    })
+    .expect("FATAL: failed to set signal handler");
+}
+
 fn handle_run(args: RunSignerArgs) {
+    logging::init_global_logger(LogFormat::peek_from_file(&args.config));
     debug!("Running signer...");
-    let config = GlobalConfig::try_from(&args.config).unwrap();
+    let config = load_config(&args.config);
     let spawned_signer = v1::SpawnedSigner::from(config);
+    let shutdown_requested = spawned_signer.shutdown_requested.clone();
+    set_shutdown_signal_handler(shutdown_requested.clone());
     println!("Signer spawned successfully. Waiting for messages to process...");
-    // Wait for the spawned signer to stop (will only occur if an error occurs)
+    // Wait for the spawned signer to stop, on error or termination request
     let _ = spawned_signer.join();
+    if !shutdown_requested.load(Ordering::SeqCst) {
+        eprintln!("Signer stopped unexpectedly");
+        process::exit(1);
+    }
+    println!("Signer stopped");
 }
 
 fn handle_generate_stacking_signature(
     args: GenerateStackingSignatureArgs,
     do_print: bool,
 ) -> MessageSignature {
-    let config = GlobalConfig::try_from(&args.config).unwrap();
+    let config = load_config(&args.config);
 
     let private_key = config.stacks_private_key;
     let public_key = Secp256k1PublicKey::from_private(&private_key);
@@ -123,7 +174,7 @@ fn handle_generate_stacking_signature(
         &private_key, //
         args.reward_cycle.into(),
         args.method.topic(),
-        config.network.to_chain_id(),
+        config.network_profile.chain_id,
         args.period.into(),
         args.max_amount,
         args.auth_id,
@@ -158,10 +209,308 @@ fn handle_generate_stacking_signature(
 }
 
 fn handle_check_config(args: RunSignerArgs) {
-    let config = GlobalConfig::try_from(&args.config).unwrap();
+    let config = load_config(&args.config);
     println!("Config: {}", config);
 }
 
+/// The name of our vote on the block, if we voted on it
+fn block_vote(block_info: &BlockInfo) -> Option<&'static str> {
+    block_info
+        .vote
+        .as_ref()
+        .map(|vote| if vote.rejected { "reject" } else { "accept" })
+}
+
+/// Run the db subcommand, returning its output
+fn db_command_output(args: &DbArgs) -> Result<String, String> {
+    let open_readonly = || {
+        SignerDb::readonly(&args.db_path).map_err(|e| {
+            format!(
+                "Failed to open signer database {}: {e}",
+                args.db_path.display()
+            )
+        })
+    };
+    let output = match &args.command {
+        DbCommand::ListBlocks(cycle_args) => {
+            let blocks = open_readonly()?
+                .get_blocks(cycle_args.reward_cycle)
+                .map_err(|e| format!("Failed to list blocks: {e}"))?;
+            if args.json {
+                let blocks: Vec<_> = blocks
+                    .iter()
+                    .map(|record| {
+                        let block_info = &record.block_info;
+                        serde_json::json!({
+                            "signer_signature_hash": block_info.signer_signature_hash().to_string(),
+                            "block_id": block_info.block.block_id().to_string(),
+                            "valid": block_info.valid(),
+                            "vote": block_vote(block_info),
+                            "signed_over": block_info.signed_over,
+                        })
+                    })
+                    .collect();
+                serde_json::to_string(&blocks).expect("Failed to serialize JSON")
+            } else {
+                blocks
+                    .iter()
+                    .map(|record| {
+                        let block_info = &record.block_info;
+                        format!(
+                            "{} block_id={} valid={} vote={} signed_over={}",
+                            block_info.signer_signature_hash(),
+                            block_info.block.block_id(),
+                            block_info
+                                .valid()
+                                .map_or("unknown".to_string(), |valid| valid.to_string()),
+                            block_vote(block_info).unwrap_or("none"),
+                            block_info.signed_over
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
+        DbCommand::ShowBlock(show_args) => {
+            let blocks = open_readonly()?
+                .get_blocks_by_hash(&show_args.sighash)
+                .map_err(|e| format!("Failed to look up block: {e}"))?;
+            if blocks.is_empty() {
+                return Err(format!(
+                    "No block with signer signature hash {}",
+                    show_args.sighash
+                ));
+            }
+            if args.json {
+                serde_json::to_string(&blocks).expect("Failed to serialize JSON")
+            } else {
+                blocks
+                    .iter()
+                    .map(|block_info| format!("{block_info:#?}"))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
+        DbCommand::Votes(cycle_args) => {
+            let blocks = open_readonly()?
+                .get_blocks(cycle_args.reward_cycle)
+                .map_err(|e| format!("Failed to list votes: {e}"))?;
+            if args.json {
+                let votes: Vec<_> = blocks
+                    .iter()
+                    .map(|record| {
+                        let block_info = &record.block_info;
+                        serde_json::json!({
+                            "signer_signature_hash": block_info.signer_signature_hash().to_string(),
+                            "vote": block_vote(block_info),
+                            "rejection_reason": record.rejection_reason,
+                        })
+                    })
+                    .collect();
+                serde_json::to_string(&votes).expect("Failed to serialize JSON")
+            } else {
+                blocks
+                    .iter()
+                    .map(|record| {
+                        format!(
+                            "{} vote={} rejection_reason={}",
+                            record.block_info.signer_signature_hash(),
+                            block_vote(&record.block_info).unwrap_or("none"),
+                            record.rejection_reason.as_deref().unwrap_or("none")
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
+        DbCommand::Stats(cycle_args) => {
+            let stats = open_readonly()?
+                .get_reward_cycle_stats(cycle_args.reward_cycle)
+                .map_err(|e| format!("Failed to summarize reward cycle: {e}"))?;
+            if args.json {
+                serde_json::to_string(&stats).expect("Failed to serialize JSON")
+            } else {
+                let height =
+                    |height: Option<u64>| height.map_or("none".to_string(), |h| h.to_string());
+                format!(
+                    "total_blocks_seen={} accepted={} rejected={} pending={} dkg_rounds={} first_block_at={} last_block_at={}",
+                    stats.total_blocks_seen,
+                    stats.blocks_accepted,
+                    stats.blocks_rejected,
+                    stats.blocks_pending,
+                    stats.dkg_rounds,
+                    height(stats.first_block_at),
+                    height(stats.last_block_at)
+                )
+            }
+        }
+        DbCommand::Prune(prune_args) => {
+            // Do not create a database where there was none
+            if !args.db_path.exists() {
+                return Err(format!("No signer database at {}", args.db_path.display()));
+            }
+            let pruned_blocks = SignerDb::new(&args.db_path)
+                .and_then(|mut db| db.prune_reward_cycles(prune_args.keep))
+                .map_err(|e| format!("Failed to prune signer database: {e}"))?;
+            if args.json {
+                serde_json::json!({ "pruned_blocks": pruned_blocks }).to_string()
+            } else {
+                format!("Pruned {pruned_blocks} blocks")
+            }
+        }
+        DbCommand::MigrateJsonState(migrate_args) => {
+            let imported_blocks = SignerDb::new(&args.db_path)
+                .and_then(|mut db| db.migrate_from_json_state(&migrate_args.path))
+                .map_err(|e| format!("Failed to migrate the JSON signer state: {e}"))?;
+            if args.json {
+                serde_json::json!({ "imported_blocks": imported_blocks }).to_string()
+            } else {
+                format!("Imported {imported_blocks} blocks")
+            }
+        }
+    };
+    Ok(output)
+}
+
+fn handle_db(args: DbArgs) {
+    match db_command_output(&args) {
+        Ok(output) => println!("{output}"),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn handle_simulate(args: SimulateArgs) {
+    let params = SimulationParams {
+        num_signers: args.signers,
+        num_keys: args.keys,
+        threshold_percent: args.threshold_percent,
+    };
+    let report = simulate(&params).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+    println!(
+        "Signers: {}, keys: {}, signing threshold: {} keys, DKG threshold: {} keys",
+        params.num_signers, params.num_keys, report.threshold, report.dkg_threshold
+    );
+    println!("DKG round: {:?}", report.dkg_duration);
+    println!("Signing round: {:?}", report.sign_duration);
+    println!("Signing round breakdown: {}", report.sign_breakdown);
+    println!("Aggregate key: {}", report.aggregate_key);
+    println!("Signed message: {}", to_hex(&report.message));
+    if report.signature_verified {
+        println!("Signature verified against the aggregate key");
+    } else {
+        eprintln!("Signature did NOT verify against the aggregate key");
+        process::exit(1);
+    }
+}
+
+/// Describe the recorded signer messages, decoding them
+fn describe_messages(messages: &[RecordedMessage]) -> String {
+    let messages: Vec<_> = messages
+        .iter()
+        .map(|message| match message.decode::<SignerMessage>() {
+            Ok(message) => format!("{message:?}"),
+            Err(e) => format!("<undecodable message: {e}>"),
+        })
+        .collect();
+    format!("[{}]", messages.join(", "))
+}
+
+/// Describe an entry of a recorded session
+fn describe_entry(entry: &RecordedEntry) -> String {
+    match entry {
+        RecordedEntry::Event(SignerEvent::MinerMessages(messages, miner_key)) => format!(
+            "miner messages from {}: {}",
+            miner_key.to_hex(),
+            describe_messages(messages)
+        ),
+        RecordedEntry::Event(SignerEvent::SignerMessages(signer_set, messages)) => format!(
+            "signer messages for signer set {signer_set}: {}",
+            describe_messages(messages)
+        ),
+        RecordedEntry::Event(event) => format!("event: {event:?}"),
+        RecordedEntry::RewardSet {
+            reward_cycle,
+            signers,
+        } => match signers {
+            Some(signers) => format!(
+                "reward set of reward cycle {reward_cycle}: {} signers",
+                signers.len()
+            ),
+            None => format!("reward set of reward cycle {reward_cycle}: not calculated"),
+        },
+        RecordedEntry::SignerSlots {
+            reward_cycle,
+            slots,
+        } => format!(
+            "signer slots of reward cycle {reward_cycle}: {} signers",
+            slots.len()
+        ),
+        RecordedEntry::Outbound {
+            reward_cycle,
+            message,
+        } => format!(
+            "sent by the signer of reward cycle {reward_cycle}: {}",
+            describe_messages(std::slice::from_ref(message))
+        ),
+    }
+}
+
+/// Print a recorded session, with the signers the recorded stacks node responses register the
+/// configured signer as. Only the recording is read: nothing is sent to the stacks node.
+fn handle_dump(args: DumpArgs) {
+    let config = load_config(&args.config);
+    let entries = read_recording(&args.events).unwrap_or_else(|e| {
+        eprintln!(
+            "Failed to read the recorded session from {}: {e}",
+            args.events.display()
+        );
+        process::exit(1);
+    });
+    println!(
+        "Dumping {} entries recorded in {} as signer {}",
+        entries.len(),
+        args.events.display(),
+        config.stacks_address
+    );
+    let mut source = RecordedConfigSource::new(&config, &entries);
+    for reward_cycle in source.reward_cycles() {
+        match source.signer_config(reward_cycle) {
+            Some(signer_config) => println!(
+                "Reward cycle {reward_cycle}: registered as signer #{} in stacker-db slot {} with key ids {:?}",
+                signer_config.signer_id, signer_config.signer_slot_id, signer_config.key_ids
+            ),
+            None => println!("Reward cycle {reward_cycle}: not registered"),
+        }
+    }
+    for (index, entry) in entries.iter().enumerate() {
+        println!("#{index} {}", describe_entry(entry));
+    }
+}
+
+fn handle_check(args: CheckArgs) {
+    let config = GlobalConfig::try_from(&args.config).unwrap_or_else(|e| {
+        println!(
+            "[FAIL] config: could not load {}: {e}",
+            args.config.display()
+        );
+        process::exit(1);
+    });
+    println!("[PASS] config: loaded {}", args.config.display());
+    let results = run_checks(&config, args.write_test);
+    for result in &results {
+        println!("{result}");
+    }
+    if results.iter().any(|result| !result.passed) {
+        process::exit(1);
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
 
@@ -192,21 +541,39 @@ fn main() {
         Command::CheckConfig(args) => {
             handle_check_config(args);
         }
+        Command::Db(args) => {
+            handle_db(args);
+        }
+        Command::Simulate(args) => {
+            handle_simulate(args);
+        }
+        Command::Dump(args) => {
+            handle_dump(args);
+        }
+        Command::Check(args) => {
+            handle_check(args);
+        }
     }
 }
 
 #[cfg(test)]
 pub mod tests {
+    use blockstack_lib::chainstate::nakamoto::{
+        NakamotoBlock, NakamotoBlockHeader, NakamotoBlockVote,
+    };
     use blockstack_lib::chainstate::stacks::address::PoxAddress;
     use blockstack_lib::chainstate::stacks::boot::POX_4_CODE;
     use blockstack_lib::util_lib::signed_structured_data::pox4::{
         make_pox_4_signer_key_message_hash, Pox4SignatureTopic,
     };
     use clarity::vm::{execute_v2, Value};
+    use libsigner::v1::messages::RejectCode;
+    use libsigner::BlockProposal;
     use stacks_common::consts::CHAIN_ID_TESTNET;
     use stacks_common::types::PublicKey;
+    use stacks_common::util::hash::Sha512Trunc256Sum;
     use stacks_common::util::secp256k1::Secp256k1PublicKey;
-    use stacks_signer::cli::parse_pox_addr;
+    use stacks_signer::cli::{parse_pox_addr, PruneArgs, RewardCycleArgs, ShowBlockArgs};
 
     use super::{handle_generate_stacking_signature, *};
     use crate::{GenerateStackingSignatureArgs, GlobalConfig};
@@ -336,4 +703,134 @@ pub mod tests {
         assert!(verify_result.is_ok());
         assert!(verify_result.unwrap());
     }
+
+    fn block_info(chain_length: u64, reward_cycle: u64, burn_height: u64) -> BlockInfo {
+        let mut header = NakamotoBlockHeader::empty();
+        header.chain_length = chain_length;
+        BlockInfo::from(BlockProposal {
+            block: NakamotoBlock {
+                header,
+                txs: vec![],
+            },
+            burn_height,
+            reward_cycle,
+        })
+    }
+
+    #[test]
+    fn db_commands_should_query_and_prune_the_signer_db() {
+        let db_path = std::env::temp_dir().join(format!(
+            "stacks-signer-cli-test-{}.sqlite",
+            rand::random::<u64>()
+        ));
+        let mut db = SignerDb::new(&db_path).unwrap();
+        let accepted = block_info(1, 5, 10);
+        let mut rejected = block_info(2, 5, 11);
+        rejected.vote = Some(NakamotoBlockVote {
+            signer_signature_hash: rejected.signer_signature_hash(),
+            rejected: true,
+        });
+        rejected.signed_over = true;
+        let next_cycle = block_info(3, 6, 20);
+        for block_info in [&accepted, &rejected, &next_cycle] {
+            db.insert_block(block_info).unwrap();
+        }
+        db.record_block_rejection(
+            5,
+            &rejected.signer_signature_hash(),
+            &RejectCode::ConnectivityIssues,
+        )
+        .unwrap();
+        drop(db);
+
+        let run = |json: bool, command: DbCommand| {
+            db_command_output(&DbArgs {
+                db_path: db_path.clone(),
+                json,
+                command,
+            })
+        };
+        let cycle_5 = || RewardCycleArgs { reward_cycle: 5 };
+
+        assert_eq!(
+            run(false, DbCommand::ListBlocks(cycle_5())).unwrap(),
+            format!(
+                "{} block_id={} valid=unknown vote=none signed_over=false\n\
+                 {} block_id={} valid=unknown vote=reject signed_over=true",
+                accepted.signer_signature_hash(),
+                accepted.block.block_id(),
+                rejected.signer_signature_hash(),
+                rejected.block.block_id(),
+            )
+        );
+        let blocks: serde_json::Value =
+            serde_json::from_str(&run(true, DbCommand::ListBlocks(cycle_5())).unwrap()).unwrap();
+        assert_eq!(blocks.as_array().unwrap().len(), 2);
+        assert_eq!(
+            blocks[1]["signer_signature_hash"],
+            rejected.signer_signature_hash().to_string()
+        );
+        assert_eq!(blocks[1]["block_id"], rejected.block.block_id().to_string());
+        assert!(blocks[1]["valid"].is_null());
+        assert_eq!(blocks[1]["vote"], "reject");
+        assert_eq!(blocks[1]["signed_over"], true);
+
+        let show_block = || {
+            DbCommand::ShowBlock(ShowBlockArgs {
+                sighash: next_cycle.signer_signature_hash(),
+            })
+        };
+        assert!(run(false, show_block())
+            .unwrap()
+            .contains("header: NakamotoBlockHeader {"));
+        let shown: serde_json::Value =
+            serde_json::from_str(&run(true, show_block()).unwrap()).unwrap();
+        assert_eq!(shown[0]["reward_cycle"], 6);
+        assert_eq!(shown[0]["block"]["header"]["chain_length"], 3);
+        assert!(run(
+            false,
+            DbCommand::ShowBlock(ShowBlockArgs {
+                sighash: Sha512Trunc256Sum([0; 32]),
+            })
+        )
+        .is_err());
+
+        assert_eq!(
+            run(false, DbCommand::Votes(cycle_5())).unwrap(),
+            format!(
+                "{} vote=none rejection_reason=none\n\
+                 {} vote=reject rejection_reason=ConnectivityIssues",
+                accepted.signer_signature_hash(),
+                rejected.signer_signature_hash(),
+            )
+        );
+        let votes: serde_json::Value =
+            serde_json::from_str(&run(true, DbCommand::Votes(cycle_5())).unwrap()).unwrap();
+        assert!(votes[0]["vote"].is_null());
+        assert_eq!(votes[1]["rejection_reason"], "ConnectivityIssues");
+
+        assert_eq!(
+            run(false, DbCommand::Stats(cycle_5())).unwrap(),
+            "total_blocks_seen=2 accepted=0 rejected=1 pending=1 dkg_rounds=0 first_block_at=10 last_block_at=11"
+        );
+        let stats: serde_json::Value =
+            serde_json::from_str(&run(true, DbCommand::Stats(cycle_5())).unwrap()).unwrap();
+        assert_eq!(stats["blocks_rejected"], 1);
+        assert_eq!(stats["last_block_at"], 11);
+
+        let prune = || DbCommand::Prune(PruneArgs { keep: 1 });
+        assert_eq!(run(false, prune()).unwrap(), "Pruned 2 blocks");
+        assert_eq!(run(true, prune()).unwrap(), r#"{"pruned_blocks":0}"#);
+        assert_eq!(run(false, DbCommand::ListBlocks(cycle_5())).unwrap(), "");
+        assert_eq!(
+            run(
+                true,
+                DbCommand::ListBlocks(RewardCycleArgs { reward_cycle: 6 })
+            )
+            .unwrap()
+            .matches("signer_signature_hash")
+            .count(),
+            1
+        );
+    }
 }
@@ -14,6 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::time::Duration;
+
 #[cfg(feature = "monitoring_prom")]
 use ::prometheus::HistogramTimer;
 #[cfg(feature = "monitoring_prom")]
@@ -25,14 +27,22 @@ use stacks_common::error;
 #[cfg(not(feature = "monitoring_prom"))]
 use stacks_common::warn;
 
+use libsigner::v1::messages::MessageSlotID;
+
 use crate::config::GlobalConfig;
 
 #[cfg(feature = "monitoring_prom")]
-mod prometheus;
+pub(crate) mod prometheus;
 
 #[cfg(feature = "monitoring_prom")]
 mod server;
 
+mod signer_metrics;
+mod signer_status;
+
+pub use signer_metrics::{format_prometheus, SignerCounters, SignerMetrics, SignerMetricsServer};
+pub use signer_status::{ExportedStatus, ProcessStatus, SignerStatus};
+
 /// Update stacks tip height gauge
 #[allow(unused_variables)]
 pub fn update_stacks_tip_height(height: i64) {
@@ -47,6 +57,19 @@ pub fn update_reward_cycle(reward_cycle: i64) {
     prometheus::CURRENT_REWARD_CYCLE.set(reward_cycle);
 }
 
+/// Record the name and ID of the signer registered for a reward cycle
+#[allow(unused_variables)]
+pub fn update_signer_info(signer_name: &str, signer_id: u32, reward_cycle: u64) {
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::SIGNER_INFO
+        .with_label_values(&[
+            signer_name,
+            &signer_id.to_string(),
+            &reward_cycle.to_string(),
+        ])
+        .set(1);
+}
+
 /// Increment the block validation responses counter
 #[allow(unused_variables)]
 pub fn increment_block_validation_responses(accepted: bool) {
@@ -101,6 +124,75 @@ pub fn increment_commands_processed(command_type: &str) {
         .inc();
 }
 
+/// Increment the number of stackerdb chunks dropped for not being signed by their slot's owner
+pub fn increment_rejected_stackerdb_chunks() {
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::REJECTED_STACKERDB_CHUNKS.inc();
+}
+
+/// Increment the number of stackerdb chunks not delivered to the signer because they were already delivered
+pub fn increment_suppressed_duplicate_chunks() {
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::SUPPRESSED_DUPLICATE_CHUNKS.inc();
+}
+
+/// The outcome of a stackerdb chunk write
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StackerDBAckOutcome {
+    /// The chunk was stored
+    Success,
+    /// The chunk was rejected, or the write failed
+    Failure,
+    /// The chunk was rejected for a stale slot version
+    VersionConflict,
+}
+
+impl StackerDBAckOutcome {
+    /// The label value of the outcome
+    #[cfg(feature = "monitoring_prom")]
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+            Self::VersionConflict => "version_conflict",
+        }
+    }
+}
+
+/// Increment the number of stackerdb chunk writes with the given outcome
+#[allow(unused_variables)]
+pub fn increment_stackerdb_acks(msg_id: &MessageSlotID, outcome: StackerDBAckOutcome) {
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::STACKERDB_ACKS
+        .with_label_values(&[&format!("{msg_id:?}"), outcome.as_str()])
+        .inc();
+}
+
+/// Update the size of the last chunk accepted into the signer's slot for the given message ID
+#[allow(unused_variables)]
+pub fn update_stackerdb_last_chunk_size(msg_id: &MessageSlotID, size: usize) {
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::STACKERDB_LAST_CHUNK_SIZE
+        .with_label_values(&[&format!("{msg_id:?}")])
+        .set(i64::try_from(size).unwrap_or(i64::MAX));
+}
+
+/// Increment the number of stackerdb chunks read for the given message ID
+#[allow(unused_variables)]
+pub fn increment_stackerdb_chunks_received(msg_id: &MessageSlotID, amount: usize) {
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::STACKERDB_CHUNKS_RECEIVED
+        .with_label_values(&[&format!("{msg_id:?}")])
+        .inc_by(i64::try_from(amount).unwrap_or(i64::MAX));
+}
+
+/// Increment the number of events received for a signer set no signer is running for
+#[allow(unused_variables)]
+pub fn increment_unrouted_signer_events() {
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::UNROUTED_SIGNER_EVENTS.inc();
+}
+
 /// Increment the number of DKG votes submitted
 #[allow(unused_variables)]
 pub fn increment_dkg_votes_submitted() {
@@ -138,6 +230,15 @@ pub fn update_signer_nonce(nonce: u64) {
     prometheus::SIGNER_NONCE.set(nonce as i64);
 }
 
+/// Record how long a phase of a completed signing round took
+#[allow(unused_variables)]
+pub fn record_sign_round_phase_latency(phase: &str, latency: Duration) {
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::SIGN_ROUND_PHASE_LATENCIES_HISTOGRAM
+        .with_label_values(&[phase])
+        .observe(latency.as_secs_f64());
+}
+
 /// Start a new RPC call timer.
 /// The `origin` parameter is the base path of the RPC call, e.g. `http://node.com`.
 /// The `origin` parameter is removed from `full_path` when storing in prometheus.
@@ -161,6 +262,20 @@ pub fn new_rpc_call_timer(_full_path: &str, _origin: &str) -> NoOpTimer {
     NoOpTimer
 }
 
+/// Start a new stackerdb chunk write timer for the given message ID.
+#[cfg(feature = "monitoring_prom")]
+pub fn new_stackerdb_send_timer(msg_id: &MessageSlotID) -> HistogramTimer {
+    let histogram =
+        prometheus::STACKERDB_SEND_LATENCIES_HISTOGRAM.with_label_values(&[&format!("{msg_id:?}")]);
+    histogram.start_timer()
+}
+
+/// Start a no-op stackerdb chunk write timer.
+#[cfg(not(feature = "monitoring_prom"))]
+pub fn new_stackerdb_send_timer(_msg_id: &MessageSlotID) -> NoOpTimer {
+    NoOpTimer
+}
+
 /// Start serving monitoring metrics.
 /// This will only serve the metrics if the `monitoring_prom` feature is enabled.
 #[allow(unused_variables)]
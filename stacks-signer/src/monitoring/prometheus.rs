@@ -17,8 +17,8 @@
 use lazy_static::lazy_static;
 use prometheus::{
     gather, histogram_opts, opts, register_histogram_vec, register_int_counter,
-    register_int_counter_vec, register_int_gauge, Encoder, HistogramVec, IntCounter, IntCounterVec,
-    IntGauge, TextEncoder,
+    register_int_counter_vec, register_int_gauge, register_int_gauge_vec, Encoder, HistogramVec,
+    IntCounter, IntCounterVec, IntGauge, IntGaugeVec, TextEncoder,
 };
 
 lazy_static! {
@@ -54,6 +54,21 @@ lazy_static! {
         "The number of inbound packets received by the signer"
     ))
     .unwrap();
+    pub static ref REJECTED_STACKERDB_CHUNKS: IntCounter = register_int_counter!(opts!(
+        "stacks_signer_rejected_stackerdb_chunks",
+        "The number of stackerdb chunks dropped for not being signed by the owner of their slot"
+    ))
+    .unwrap();
+    pub static ref SUPPRESSED_DUPLICATE_CHUNKS: IntCounter = register_int_counter!(opts!(
+        "stacks_signer_suppressed_duplicate_chunks",
+        "The number of stackerdb chunks not delivered to the signer because they were already delivered"
+    ))
+    .unwrap();
+    pub static ref UNROUTED_SIGNER_EVENTS: IntCounter = register_int_counter!(opts!(
+        "stacks_signer_unrouted_events",
+        "The number of events received for a signer set no signer is running for"
+    ))
+    .unwrap();
     pub static ref COMMANDS_PROCESSED: IntCounterVec = register_int_counter_vec!(
         "stacks_signer_commands_processed",
         "The number of commands processed by the signer",
@@ -88,12 +103,48 @@ lazy_static! {
         "stacks_signer_nonce",
         "The current nonce of the signer"
     )).unwrap();
+    pub static ref SIGNER_INFO: IntGaugeVec = register_int_gauge_vec!(
+        "stacks_signer_info",
+        "Always 1. Identifies the signer registered for each reward cycle by its `signer_name` and `signer_id`",
+        &["signer_name", "signer_id", "reward_cycle"]
+    )
+    .unwrap();
+
+    pub static ref STACKERDB_ACKS: IntCounterVec = register_int_counter_vec!(
+        "stacks_signer_stackerdb_acks",
+        "The number of stackerdb chunk writes by outcome. `outcome` is either 'success', 'failure' or 'version_conflict'",
+        &["message_slot", "outcome"]
+    )
+    .unwrap();
+    pub static ref STACKERDB_LAST_CHUNK_SIZE: IntGaugeVec = register_int_gauge_vec!(
+        "stacks_signer_stackerdb_last_chunk_size",
+        "The size (bytes) of the last chunk accepted into each of the signer's stackerdb slots",
+        &["message_slot"]
+    )
+    .unwrap();
+    pub static ref STACKERDB_CHUNKS_RECEIVED: IntCounterVec = register_int_counter_vec!(
+        "stacks_signer_stackerdb_chunks_received",
+        "The number of stackerdb chunks read by the signer",
+        &["message_slot"]
+    )
+    .unwrap();
+
+    pub static ref STACKERDB_SEND_LATENCIES_HISTOGRAM: HistogramVec = register_histogram_vec!(histogram_opts!(
+        "stacks_signer_stackerdb_send_latencies_histogram",
+        "Time (seconds) measuring the round trip of a stackerdb chunk write, retries included"
+    ), &["message_slot"]).unwrap();
 
     pub static ref SIGNER_RPC_CALL_LATENCIES_HISTOGRAM: HistogramVec = register_histogram_vec!(histogram_opts!(
         "stacks_signer_node_rpc_call_latencies_histogram",
         "Time (seconds) measuring round-trip RPC call latency to the Stacks node"
         // Will use DEFAULT_BUCKETS = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0] by default
     ), &["path"]).unwrap();
+
+    pub static ref SIGN_ROUND_PHASE_LATENCIES_HISTOGRAM: HistogramVec = register_histogram_vec!(histogram_opts!(
+        "stacks_signer_sign_round_phase_latencies_histogram",
+        "Time (seconds) spent in each phase of a completed signing round. `phase` is one of 'validation', 'awaiting_nonce_round', 'nonce_round', 'share_round', 'total', 'slowest_nonce' or 'slowest_share'",
+        vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0]
+    ), &["phase"]).unwrap();
 }
 
 pub fn gather_metrics_string() -> String {
@@ -24,7 +24,7 @@ use stacks_common::{debug, error, info, warn};
 use tiny_http::{Response as HttpResponse, Server as HttpServer};
 
 use super::{update_reward_cycle, update_signer_stx_balance};
-use crate::client::{ClientError, StacksClient};
+use crate::client::{build_http_client, ClientError, StacksClient};
 use crate::config::{GlobalConfig, Network};
 use crate::monitoring::prometheus::gather_metrics_string;
 use crate::monitoring::{update_signer_nonce, update_stacks_tip_height};
@@ -66,6 +66,7 @@ impl MonitoringServer {
         network: Network,
         public_key: Secp256k1PublicKey,
         stacks_node_origin: String,
+        stacks_node_client: reqwest::blocking::Client,
     ) -> Self {
         Self {
             http_server,
@@ -74,7 +75,7 @@ impl MonitoringServer {
             last_metrics_poll: Instant::now(),
             network,
             public_key,
-            stacks_node_client: reqwest::blocking::Client::new(),
+            stacks_node_client,
             stacks_node_origin,
         }
     }
@@ -93,7 +94,8 @@ impl MonitoringServer {
             stacks_client,
             config.network.clone(),
             public_key,
-            format!("http://{}", config.node_host),
+            format!("{}://{}", config.node_transport.scheme(), config.node_host),
+            build_http_client(config.node_transport),
         );
         server.update_metrics()?;
         server.main_loop()
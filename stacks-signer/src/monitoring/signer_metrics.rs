@@ -0,0 +1,497 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use slog::{slog_info, slog_warn};
+use stacks_common::{info, warn};
+
+use super::signer_status::ExportedStatus;
+
+/// A snapshot of the state of a signer, exported in the Prometheus text format
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct SignerMetrics {
+    /// The ID of the signer in its reward cycle
+    pub signer_id: u32,
+    /// The reward cycle the signer is registered for
+    pub reward_cycle: u64,
+    /// The number of proposed blocks awaiting validation by the stacks node
+    pub pending_blocks: u64,
+    /// The number of validated blocks no signing round was started for yet
+    pub blocks_pending_signature: u64,
+    /// The number of blocks whose signing round is in progress
+    pub blocks_being_signed: u64,
+    /// The number of nonce requests cached until their block is validated
+    pub pending_nonce_requests: u64,
+    /// The number of messages waiting to be written to stacker-db again
+    pub queued_messages: u64,
+    /// The number of other signers' vote transactions known to the signer
+    pub known_vote_transactions: u64,
+    /// The fraction of the signers that took part in the latest DKG round
+    pub dkg_participation_rate: f64,
+    /// The number of signing rounds that timed out gathering nonces
+    pub sign_nonce_timeouts: u64,
+    /// The number of signing rounds that did not gather enough signers
+    pub sign_insufficient_signers: u64,
+    /// The number of signing rounds that failed to aggregate the signature
+    pub sign_aggregator_errors: u64,
+    /// The activity of the signer since it started
+    pub counters: SignerCounters,
+}
+
+/// Counts of the events, rounds and messages processed by a signer since it started
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub struct SignerCounters {
+    /// The number of block validation responses received from the stacks node
+    pub block_validation_events: u64,
+    /// The number of signer message events received
+    pub signer_message_events: u64,
+    /// The number of miner message events received
+    pub miner_message_events: u64,
+    /// The number of status check events received
+    pub status_check_events: u64,
+    /// The number of new burn block events received
+    pub new_burn_block_events: u64,
+    /// The number of packets handed to the signer and coordinator state machines
+    pub packets_processed: u64,
+    /// The number of signing rounds started as the coordinator
+    pub sign_rounds_started: u64,
+    /// The number of signing rounds completed with a signature
+    pub sign_rounds_completed: u64,
+    /// The number of signing rounds that ended in an error
+    pub sign_rounds_failed: u64,
+    /// The number of DKG rounds started as the coordinator
+    pub dkg_rounds_started: u64,
+    /// The number of DKG rounds completed with an aggregate key
+    pub dkg_rounds_completed: u64,
+    /// The number of DKG rounds that ended in an error
+    pub dkg_rounds_failed: u64,
+    /// The number of proposed blocks the stacks node validated
+    pub block_validations_accepted: u64,
+    /// The number of proposed blocks the stacks node rejected
+    pub block_validations_rejected: u64,
+    /// The total time, in seconds, the stacks node took to answer block validations
+    pub block_validation_seconds: u64,
+    /// The number of messages the stacks node acked when written to stacker-db
+    pub stackerdb_sends_acked: u64,
+    /// The number of messages the stacks node never acknowledged
+    pub stackerdb_sends_unacknowledged: u64,
+    /// The number of messages stacker-db rejected
+    pub stackerdb_sends_rejected: u64,
+    /// The number of messages that could not be written to stacker-db at all
+    pub stackerdb_sends_failed: u64,
+    /// The number of NACKs sent for signing rounds
+    pub nacks_sent: u64,
+    /// The number of NACKs received from other signers
+    pub nacks_received: u64,
+}
+
+impl SignerCounters {
+    /// Increment the given counter
+    pub fn increment(counter: &mut u64) {
+        *counter = counter.saturating_add(1);
+    }
+}
+
+/// The type of a metric in the Prometheus text format
+#[derive(Clone, Copy)]
+enum MetricType {
+    Gauge,
+    Counter,
+}
+
+impl MetricType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gauge => "gauge",
+            Self::Counter => "counter",
+        }
+    }
+}
+
+/// A metric exported for each signer
+struct MetricDescription {
+    name: &'static str,
+    help: &'static str,
+    metric_type: MetricType,
+    value: fn(&SignerMetrics) -> f64,
+}
+
+/// Every exported signer metric
+const METRICS: &[MetricDescription] = &[
+    MetricDescription {
+        name: "stacks_signer_pending_blocks",
+        help: "The number of proposed blocks awaiting validation by the stacks node",
+        metric_type: MetricType::Gauge,
+        value: |metrics| metrics.pending_blocks as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_blocks_pending_signature",
+        help: "The number of validated blocks no signing round was started for yet",
+        metric_type: MetricType::Gauge,
+        value: |metrics| metrics.blocks_pending_signature as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_blocks_being_signed",
+        help: "The number of blocks whose signing round is in progress",
+        metric_type: MetricType::Gauge,
+        value: |metrics| metrics.blocks_being_signed as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_pending_nonce_requests",
+        help: "The number of nonce requests cached until their block is validated",
+        metric_type: MetricType::Gauge,
+        value: |metrics| metrics.pending_nonce_requests as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_queued_messages",
+        help: "The number of messages waiting to be written to stacker-db again",
+        metric_type: MetricType::Gauge,
+        value: |metrics| metrics.queued_messages as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_known_vote_transactions",
+        help: "The number of other signers' vote transactions known to the signer",
+        metric_type: MetricType::Gauge,
+        value: |metrics| metrics.known_vote_transactions as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_dkg_participation_rate",
+        help: "The fraction of the signers that took part in the latest DKG round",
+        metric_type: MetricType::Gauge,
+        value: |metrics| metrics.dkg_participation_rate,
+    },
+    MetricDescription {
+        name: "stacks_signer_sign_nonce_timeouts_total",
+        help: "The number of signing rounds that timed out gathering nonces",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.sign_nonce_timeouts as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_sign_insufficient_signers_total",
+        help: "The number of signing rounds that did not gather enough signers",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.sign_insufficient_signers as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_sign_aggregator_errors_total",
+        help: "The number of signing rounds that failed to aggregate the signature",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.sign_aggregator_errors as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_block_validation_events_total",
+        help: "The number of block validation responses received from the stacks node",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.block_validation_events as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_signer_message_events_total",
+        help: "The number of signer message events received",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.signer_message_events as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_miner_message_events_total",
+        help: "The number of miner message events received",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.miner_message_events as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_status_check_events_total",
+        help: "The number of status check events received",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.status_check_events as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_new_burn_block_events_total",
+        help: "The number of new burn block events received",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.new_burn_block_events as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_packets_processed_total",
+        help: "The number of packets handed to the signer and coordinator state machines",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.packets_processed as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_sign_rounds_started_total",
+        help: "The number of signing rounds started as the coordinator",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.sign_rounds_started as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_sign_rounds_completed_total",
+        help: "The number of signing rounds completed with a signature",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.sign_rounds_completed as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_sign_rounds_failed_total",
+        help: "The number of signing rounds that ended in an error",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.sign_rounds_failed as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_dkg_rounds_started_total",
+        help: "The number of DKG rounds started as the coordinator",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.dkg_rounds_started as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_dkg_rounds_completed_total",
+        help: "The number of DKG rounds completed with an aggregate key",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.dkg_rounds_completed as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_dkg_rounds_failed_total",
+        help: "The number of DKG rounds that ended in an error",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.dkg_rounds_failed as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_block_validations_accepted_total",
+        help: "The number of proposed blocks the stacks node validated",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.block_validations_accepted as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_block_validations_rejected_total",
+        help: "The number of proposed blocks the stacks node rejected",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.block_validations_rejected as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_block_validation_seconds_sum",
+        help: "The total time the stacks node took to answer block validations",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.block_validation_seconds as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_block_validation_seconds_count",
+        help: "The number of block validations answered by the stacks node",
+        metric_type: MetricType::Counter,
+        value: |metrics| {
+            metrics
+                .counters
+                .block_validations_accepted
+                .saturating_add(metrics.counters.block_validations_rejected) as f64
+        },
+    },
+    MetricDescription {
+        name: "stacks_signer_stackerdb_sends_acked_total",
+        help: "The number of messages the stacks node acked when written to stacker-db",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.stackerdb_sends_acked as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_stackerdb_sends_unacknowledged_total",
+        help: "The number of messages the stacks node never acknowledged",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.stackerdb_sends_unacknowledged as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_stackerdb_sends_rejected_total",
+        help: "The number of messages stacker-db rejected",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.stackerdb_sends_rejected as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_stackerdb_sends_failed_total",
+        help: "The number of messages that could not be written to stacker-db at all",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.stackerdb_sends_failed as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_nacks_sent_total",
+        help: "The number of NACKs sent for signing rounds",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.nacks_sent as f64,
+    },
+    MetricDescription {
+        name: "stacks_signer_nacks_received_total",
+        help: "The number of NACKs received from other signers",
+        metric_type: MetricType::Counter,
+        value: |metrics| metrics.counters.nacks_received as f64,
+    },
+];
+
+/// Format the metrics of the given signers in the Prometheus text format, with one sample per
+/// signer of each metric, labelled by the signer's ID and reward cycle
+pub fn format_prometheus(signers_metrics: &[SignerMetrics]) -> String {
+    let mut output = String::new();
+    for metric in METRICS {
+        let _ = writeln!(output, "# HELP {} {}", metric.name, metric.help);
+        let _ = writeln!(
+            output,
+            "# TYPE {} {}",
+            metric.name,
+            metric.metric_type.as_str()
+        );
+        for signer_metrics in signers_metrics {
+            let _ = writeln!(
+                output,
+                "{}{{signer_id=\"{}\",reward_cycle=\"{}\"}} {}",
+                metric.name,
+                signer_metrics.signer_id,
+                signer_metrics.reward_cycle,
+                (metric.value)(signer_metrics)
+            );
+        }
+    }
+    output
+}
+
+/// Serves the latest exported metrics of the running signers over HTTP at `/metrics`, on a
+/// dedicated port, along with the process status at `/status` and its health at `/health`
+pub struct SignerMetricsServer {
+    listener: TcpListener,
+    exported_metrics: Arc<Mutex<String>>,
+    exported_status: Arc<Mutex<ExportedStatus>>,
+    health_check_timeout: Duration,
+}
+
+impl SignerMetricsServer {
+    /// Listen on the given port of every interface. Port 0 picks any available port.
+    /// The process is reported unhealthy once the stacks node was not reached or the event
+    /// loop did not run for `health_check_timeout`.
+    pub fn bind(port: u16, health_check_timeout: Duration) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(("0.0.0.0", port))?,
+            exported_metrics: Arc::new(Mutex::new(String::new())),
+            exported_status: Arc::new(Mutex::new(ExportedStatus::default())),
+            health_check_timeout,
+        })
+    }
+
+    /// The address the server listens on
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// The metrics served, to be replaced with the latest export of the running signers
+    pub fn exported_metrics(&self) -> Arc<Mutex<String>> {
+        self.exported_metrics.clone()
+    }
+
+    /// The status served, to be replaced with the latest status of the signer process
+    pub fn exported_status(&self) -> Arc<Mutex<ExportedStatus>> {
+        self.exported_status.clone()
+    }
+
+    /// Serve the metrics in a background thread
+    pub fn spawn(self) -> JoinHandle<()> {
+        thread::spawn(move || {
+            if let Ok(local_addr) = self.listener.local_addr() {
+                info!("Serving signer metrics at http://{local_addr}/metrics");
+            }
+            for stream in self.listener.incoming() {
+                let result = stream.and_then(|stream| self.respond(stream));
+                if let Err(e) = result {
+                    warn!("Failed to serve signer metrics: {e:?}");
+                }
+            }
+        })
+    }
+
+    /// Respond to a single HTTP request
+    fn respond(&self, mut stream: TcpStream) -> io::Result<()> {
+        let mut request_line = String::new();
+        BufReader::new(&stream).read_line(&mut request_line)?;
+        let (status, content_type, body) = if request_line.starts_with("GET /metrics ") {
+            let body = self
+                .exported_metrics
+                .lock()
+                .map(|metrics| metrics.clone())
+                .unwrap_or_default();
+            ("200 OK", "text/plain; version=0.0.4", body)
+        } else if request_line.starts_with("GET /status ") {
+            let status = self.latest_status().status;
+            let body = serde_json::to_string(status.as_ref()).unwrap_or_default();
+            ("200 OK", "application/json", body)
+        } else if request_line.starts_with("GET /health ") {
+            if self
+                .latest_status()
+                .is_healthy(self.health_check_timeout, Instant::now())
+            {
+                ("200 OK", "text/plain", "OK".to_string())
+            } else {
+                (
+                    "503 Service Unavailable",
+                    "text/plain",
+                    "Unhealthy".to_string(),
+                )
+            }
+        } else {
+            ("404 Not Found", "text/plain", "Not Found".to_string())
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )?;
+        stream.flush()
+    }
+
+    /// A copy of the latest exported status, only holding the lock to clone its snapshot
+    fn latest_status(&self) -> ExportedStatus {
+        self.exported_status
+            .lock()
+            .map(|status| status.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    #[test]
+    fn metrics_server_should_serve_the_exported_metrics() {
+        let server = SignerMetricsServer::bind(0, Duration::from_secs(60)).unwrap();
+        let port = server.local_addr().unwrap().port();
+        let exported_metrics = server.exported_metrics();
+        *exported_metrics.lock().unwrap() = format_prometheus(&[SignerMetrics {
+            signer_id: 3,
+            reward_cycle: 7,
+            pending_blocks: 2,
+            ..Default::default()
+        }]);
+        server.spawn();
+
+        let get = |path: &str| {
+            let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+            write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        let response = get("/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response
+            .contains("stacks_signer_pending_blocks{signer_id=\"3\",reward_cycle=\"7\"} 2\n"));
+        assert!(get("/other").starts_with("HTTP/1.1 404 Not Found"));
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde_derive::Serialize;
+
+/// A snapshot of the state of a signer, served as JSON at `/status`
+#[derive(Serialize, PartialEq, Debug, Clone, Default)]
+pub struct SignerStatus {
+    /// The ID of the signer in its reward cycle
+    pub signer_id: u32,
+    /// The reward cycle the signer is registered for
+    pub reward_cycle: u64,
+    /// The operation the signer is busy with, if any
+    pub state: String,
+    /// The ID of the signer currently selected as coordinator
+    pub coordinator_id: u32,
+    /// Whether this signer is the coordinator
+    pub is_coordinator: bool,
+    /// The number of proposed blocks awaiting validation or a signing round
+    pub cached_blocks: u64,
+    /// The number of commands waiting to be processed
+    pub queued_commands: u64,
+    /// The ID of the latest DKG round
+    pub last_dkg_round: u64,
+    /// Whether the signer knows the approved aggregate public key
+    pub has_aggregate_key: bool,
+}
+
+/// A snapshot of the state of the signer process, served as JSON at `/status`
+#[derive(Serialize, PartialEq, Debug, Clone, Default)]
+pub struct ProcessStatus {
+    /// The version of the signer binary
+    pub version: String,
+    /// The state of the event loop
+    pub runloop_state: String,
+    /// The height of the last burn block the event loop saw
+    pub last_burn_block_height: Option<u64>,
+    /// How long requests to the stacks node are still held back at its request, in millisecs
+    pub node_backoff_ms: u64,
+    /// The state of each running signer
+    pub signers: Vec<SignerStatus>,
+}
+
+/// The status shared between the event loop and the metrics server. The event loop replaces
+/// it after each pass, so requests never lock the live signer state.
+#[derive(Debug, Clone, Default)]
+pub struct ExportedStatus {
+    /// The latest snapshot of the signer process, cheap to clone out of the shared lock
+    pub status: Arc<ProcessStatus>,
+    /// When the event loop last completed a pass
+    pub last_heartbeat: Option<Instant>,
+    /// When the stacks node last answered a request
+    pub last_node_contact: Option<Instant>,
+}
+
+impl ExportedStatus {
+    /// Whether the stacks node answered and the event loop ran within the last `timeout`
+    pub fn is_healthy(&self, timeout: Duration, now: Instant) -> bool {
+        let is_recent = |instant: Option<Instant>| {
+            instant.map_or(false, |instant| {
+                now.saturating_duration_since(instant) <= timeout
+            })
+        };
+        is_recent(self.last_heartbeat) && is_recent(self.last_node_contact)
+    }
+}
@@ -0,0 +1,390 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use blockstack_lib::chainstate::stacks::boot::NakamotoSignerEntry;
+use clarity::codec::{read_next, write_next, Error as CodecError, StacksMessageCodec};
+use hashbrown::HashMap;
+use libsigner::{SignerEntries, SignerEvent, SignerEventTrait, SignerSlotOwners};
+use serde::{Deserialize, Serialize};
+use slog::slog_warn;
+use stacks_common::types::chainstate::StacksAddress;
+use stacks_common::warn;
+
+use crate::config::{GlobalConfig, SignerConfig};
+use crate::runloop::{parse_signer_slots, signer_config_for, SignerConfigSource};
+
+/// The largest entry read back from a recording. Guards against allocating for a corrupt
+/// length prefix.
+const MAX_ENTRY_LEN: u32 = 64 * 1024 * 1024;
+
+/// A signer message in its consensus encoding, so that the events of any message type can be
+/// recorded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedMessage(pub Vec<u8>);
+
+impl RecordedMessage {
+    /// Record the message
+    pub fn new<T: StacksMessageCodec>(message: &T) -> Self {
+        Self(message.serialize_to_vec())
+    }
+
+    /// Decode the recorded message
+    pub fn decode<T: StacksMessageCodec>(&self) -> Result<T, CodecError> {
+        read_next(&mut self.0.as_slice())
+    }
+}
+
+impl StacksMessageCodec for RecordedMessage {
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), CodecError> {
+        write_next(fd, &self.0)
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<Self, CodecError> {
+        Ok(Self(read_next(fd)?))
+    }
+}
+
+/// An entry of a recorded signer session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RecordedEntry {
+    /// An event received by the event loop
+    Event(SignerEvent<RecordedMessage>),
+    /// The reward set signers returned by the stacks node for a reward cycle
+    RewardSet {
+        /// The reward cycle
+        reward_cycle: u64,
+        /// The signers, or None if the reward set is not calculated yet
+        signers: Option<Vec<NakamotoSignerEntry>>,
+    },
+    /// The signers stacker-db slots returned by the stacks node for a reward cycle
+    SignerSlots {
+        /// The reward cycle
+        reward_cycle: u64,
+        /// The owner and number of message slots of each signer slot, in slot order
+        slots: Vec<(StacksAddress, u128)>,
+    },
+    /// A message a signer sent to its stacker-db slots
+    Outbound {
+        /// The reward cycle of the sending signer
+        reward_cycle: u64,
+        /// The message
+        message: RecordedMessage,
+    },
+}
+
+impl RecordedEntry {
+    /// Record an event, encoding its messages
+    pub fn event<T: SignerEventTrait>(event: &SignerEvent<T>) -> Self {
+        let record = |messages: &[T]| messages.iter().map(RecordedMessage::new).collect();
+        Self::Event(match event {
+            SignerEvent::MinerMessages(messages, miner_key) => {
+                SignerEvent::MinerMessages(record(messages), *miner_key)
+            }
+            SignerEvent::SignerMessages(signer_set, messages) => {
+                SignerEvent::SignerMessages(*signer_set, record(messages))
+            }
+            SignerEvent::BlockValidationResponse(response) => {
+                SignerEvent::BlockValidationResponse(response.clone())
+            }
+            SignerEvent::StatusCheck => SignerEvent::StatusCheck,
+            SignerEvent::NewBurnBlock(height) => SignerEvent::NewBurnBlock(*height),
+        })
+    }
+
+    /// Write the entry, prefixed with its length as a big-endian u32
+    pub fn write_to<W: Write>(&self, fd: &mut W) -> Result<(), RecordingError> {
+        let bytes =
+            serde_json::to_vec(self).map_err(|e| RecordingError::Malformed(e.to_string()))?;
+        let len = u32::try_from(bytes.len())
+            .ok()
+            .filter(|len| *len <= MAX_ENTRY_LEN)
+            .ok_or(RecordingError::TooLarge(bytes.len()))?;
+        fd.write_all(&len.to_be_bytes())?;
+        fd.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// Read the next entry. Returns None at the end of the recording.
+    pub fn read_from<R: Read>(fd: &mut R) -> Result<Option<Self>, RecordingError> {
+        let mut len_bytes = [0u8; 4];
+        let mut read = 0;
+        while read < len_bytes.len() {
+            match fd.read(&mut len_bytes[read..])? {
+                0 if read == 0 => return Ok(None),
+                0 => return Err(RecordingError::Truncated),
+                n => read += n,
+            }
+        }
+        let len = u32::from_be_bytes(len_bytes);
+        if len > MAX_ENTRY_LEN {
+            return Err(RecordingError::TooLarge(len as usize));
+        }
+        let mut bytes = vec![0u8; len as usize];
+        fd.read_exact(&mut bytes).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => RecordingError::Truncated,
+            _ => RecordingError::Io(e),
+        })?;
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| RecordingError::Malformed(e.to_string()))
+    }
+}
+
+/// Errors reading or writing a recorded session
+#[derive(thiserror::Error, Debug)]
+pub enum RecordingError {
+    /// The recording could not be read or written
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    /// The recording ends in the middle of an entry
+    #[error("The recording ends in the middle of an entry")]
+    Truncated,
+    /// An entry exceeds the maximum entry size
+    #[error("Entry of {0} bytes exceeds the maximum of {MAX_ENTRY_LEN} bytes")]
+    TooLarge(usize),
+    /// An entry could not be encoded or decoded
+    #[error("Malformed entry: {0}")]
+    Malformed(String),
+}
+
+/// Appends the events and stacks node responses of a signer session to a file, to inspect them
+/// when debugging. Clones write to the same file.
+#[derive(Debug, Clone)]
+pub struct EventRecorder {
+    /// The file recorded to
+    path: PathBuf,
+    /// The open file, shared with the signers
+    file: Arc<Mutex<File>>,
+}
+
+impl EventRecorder {
+    /// Open the file to append entries to, creating it if it does not exist
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+
+    /// Append the entry. Failures are logged: recording must never stop the signer.
+    pub fn record(&self, entry: &RecordedEntry) {
+        let Ok(mut file) = self.file.lock() else {
+            warn!(
+                "Failed to record to {}: the file lock is poisoned",
+                self.path.display()
+            );
+            return;
+        };
+        if let Err(e) = entry.write_to(&mut *file) {
+            warn!("Failed to record to {}: {e}", self.path.display());
+        }
+    }
+
+    /// Append an event received by the event loop
+    pub fn record_event<T: SignerEventTrait>(&self, event: &SignerEvent<T>) {
+        self.record(&RecordedEntry::event(event));
+    }
+}
+
+/// Read every entry of a recorded session
+pub fn read_recording(path: &Path) -> Result<Vec<RecordedEntry>, RecordingError> {
+    let mut file = io::BufReader::new(File::open(path)?);
+    let mut entries = vec![];
+    while let Some(entry) = RecordedEntry::read_from(&mut file)? {
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Produces the signer configurations from the stacks node responses of a recorded session,
+/// the same way the event loop does from the live ones
+pub struct RecordedConfigSource<'a> {
+    /// The configuration of the signer the session was recorded by
+    pub config: &'a GlobalConfig,
+    /// The recorded reward set signers of each reward cycle
+    pub reward_sets: HashMap<u64, Option<Vec<NakamotoSignerEntry>>>,
+    /// The recorded signers stacker-db slots of each reward cycle
+    pub signer_slots: HashMap<u64, Vec<(StacksAddress, u128)>>,
+    /// The owners of the signers stacker-db slots, updated with each configured signer set
+    pub slot_owners: SignerSlotOwners,
+}
+
+impl<'a> RecordedConfigSource<'a> {
+    /// Collect the stacks node responses of the recording. Later responses for a reward cycle
+    /// replace earlier ones.
+    pub fn new(config: &'a GlobalConfig, entries: &[RecordedEntry]) -> Self {
+        let mut reward_sets = HashMap::new();
+        let mut signer_slots = HashMap::new();
+        for entry in entries {
+            match entry {
+                RecordedEntry::RewardSet {
+                    reward_cycle,
+                    signers,
+                } => {
+                    reward_sets.insert(*reward_cycle, signers.clone());
+                }
+                RecordedEntry::SignerSlots {
+                    reward_cycle,
+                    slots,
+                } => {
+                    signer_slots.insert(*reward_cycle, slots.clone());
+                }
+                RecordedEntry::Event(_) | RecordedEntry::Outbound { .. } => {}
+            }
+        }
+        Self {
+            config,
+            reward_sets,
+            signer_slots,
+            slot_owners: SignerSlotOwners::default(),
+        }
+    }
+
+    /// The reward cycles with a recorded reward set, in order
+    pub fn reward_cycles(&self) -> Vec<u64> {
+        let mut reward_cycles: Vec<_> = self.reward_sets.keys().copied().collect();
+        reward_cycles.sort_unstable();
+        reward_cycles
+    }
+}
+
+impl SignerConfigSource for RecordedConfigSource<'_> {
+    fn signer_config(&mut self, reward_cycle: u64) -> Option<SignerConfig> {
+        let signers = self.reward_sets.get(&reward_cycle)?.as_ref()?;
+        if signers.is_empty() {
+            return None;
+        }
+        let signer_entries =
+            SignerEntries::parse(self.config.network_profile.mainnet, signers).ok()?;
+        let signer_slot_ids = parse_signer_slots(self.signer_slots.get(&reward_cycle)?.clone());
+        signer_config_for(
+            self.config,
+            &self.config.stacks_address,
+            reward_cycle,
+            signer_entries,
+            signer_slot_ids,
+            &mut self.slot_owners,
+            None,
+            None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use blockstack_lib::chainstate::nakamoto::NakamotoBlockHeader;
+    use libsigner::v1::messages::{BlockRejection, BlockResponse, RejectCode, SignerMessage};
+    use rand::{thread_rng, RngCore};
+    use stacks_common::types::chainstate::StacksPublicKey;
+
+    use super::*;
+
+    fn tmp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("{name}-{}.events", thread_rng().next_u64()))
+    }
+
+    /// A short session: the node responses registering the configured signer for reward cycle
+    /// 5, a block rejection from another signer, and the signer's own rejection of the block
+    fn synthetic_session(config: &GlobalConfig) -> Vec<RecordedEntry> {
+        let public_key = StacksPublicKey::from_private(&config.stacks_private_key);
+        let mut signing_key = [0u8; 33];
+        signing_key.copy_from_slice(&public_key.to_bytes_compressed());
+        let rejection = SignerMessage::BlockResponse(BlockResponse::Rejected(BlockRejection::new(
+            NakamotoBlockHeader::empty().signer_signature_hash(),
+            RejectCode::ConnectivityIssues,
+        )));
+        vec![
+            RecordedEntry::RewardSet {
+                reward_cycle: 5,
+                signers: Some(vec![NakamotoSignerEntry {
+                    signing_key,
+                    stacked_amt: 1,
+                    weight: 1,
+                }]),
+            },
+            RecordedEntry::SignerSlots {
+                reward_cycle: 5,
+                slots: vec![(config.stacks_address, 14)],
+            },
+            RecordedEntry::event(&SignerEvent::NewBurnBlock(7)),
+            RecordedEntry::event(&SignerEvent::SignerMessages(1, vec![rejection.clone()])),
+            RecordedEntry::Outbound {
+                reward_cycle: 5,
+                message: RecordedMessage::new(&rejection),
+            },
+        ]
+    }
+
+    #[test]
+    fn recorded_session_should_read_back_byte_identically() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let session = synthetic_session(&config);
+        let path = tmp_path("recorded-session");
+        let recorder = EventRecorder::open(&path).unwrap();
+        for entry in &session {
+            recorder.record(entry);
+        }
+        let recorded = std::fs::read(&path).unwrap();
+
+        let read_back = read_recording(&path).unwrap();
+        assert_eq!(read_back, session);
+        let mut rerecorded = vec![];
+        for entry in &read_back {
+            entry.write_to(&mut rerecorded).unwrap();
+        }
+        assert_eq!(rerecorded, recorded);
+
+        let RecordedEntry::Event(SignerEvent::SignerMessages(1, messages)) = &read_back[3] else {
+            panic!("Expected the recorded signer messages");
+        };
+        assert_eq!(messages[0].decode::<SignerMessage>().unwrap(), rejection);
+
+        let mut source = RecordedConfigSource::new(&config, &read_back);
+        assert_eq!(source.reward_cycles(), vec![5]);
+        let signer_config = source
+            .signer_config(5)
+            .expect("Signer should be registered");
+        assert_eq!(signer_config.signer_id, 0);
+        assert_eq!(signer_config.key_ids, vec![1]);
+        assert!(source.signer_config(6).is_none());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn truncated_recording_should_be_reported() {
+        let mut bytes = vec![];
+        RecordedEntry::Outbound {
+            reward_cycle: 5,
+            message: RecordedMessage(vec![1, 2, 3]),
+        }
+        .write_to(&mut bytes)
+        .unwrap();
+        bytes.pop();
+        assert!(matches!(
+            RecordedEntry::read_from(&mut bytes.as_slice()),
+            Err(RecordingError::Truncated)
+        ));
+        assert!(RecordedEntry::read_from(&mut [].as_slice())
+            .unwrap()
+            .is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::fmt::Debug;
 // Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
 // Copyright (C) 2020-2024 Stacks Open Internet Foundation
@@ -15,23 +15,31 @@ use std::fmt::Debug;
 //
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 
 use blockstack_lib::burnchains::PoxConstants;
 use blockstack_lib::chainstate::stacks::boot::SIGNERS_NAME;
 use blockstack_lib::util_lib::boot::boot_code_id;
 use clarity::codec::StacksMessageCodec;
 use hashbrown::HashMap;
-use libsigner::{BlockProposal, SignerEntries, SignerEvent, SignerRunLoop};
+use libsigner::{BlockProposal, SignerEntries, SignerEvent, SignerRunLoop, SignerSlotOwners};
 use slog::{slog_debug, slog_error, slog_info, slog_warn};
 use stacks_common::types::chainstate::StacksAddress;
 use stacks_common::{debug, error, info, warn};
 use wsts::common::MerkleRoot;
 use wsts::state_machine::OperationResult;
 
-use crate::client::{retry_with_exponential_backoff, ClientError, SignerSlotID, StacksClient};
+use crate::alerting::{AlertEvent, AlertNotifier};
+use crate::client::{
+    retry_with_exponential_backoff, ClientError, SignerSlotID, SlotLayout, StacksClient,
+};
 use crate::config::{GlobalConfig, SignerConfig};
+use crate::monitoring::{format_prometheus, ExportedStatus, ProcessStatus, SignerMetricsServer};
+use crate::recording::{EventRecorder, RecordedEntry};
 use crate::Signer as SignerTrait;
 
 /// Which signer operation to perform
@@ -119,6 +127,385 @@ impl RewardCycleInfo {
     }
 }
 
+/// Produces the configuration of the signer registered for a reward cycle
+pub trait SignerConfigSource {
+    /// Get the configuration of the signer for the given reward cycle, including its reward set,
+    /// stacker-db slot ids and coordinator ids. Returns None if it is not registered for the cycle.
+    fn signer_config(&mut self, reward_cycle: u64) -> Option<SignerConfig>;
+}
+
+/// Reads the signer configurations of reward cycles from the stacks node
+pub struct StacksNodeConfigSource<'a> {
+    /// The signer configuration
+    pub config: &'a GlobalConfig,
+    /// The stacks node client
+    pub stacks_client: &'a StacksClient,
+    /// The owners of the signers stacker-db slots, updated with the signer set of each
+    /// configured reward cycle
+    pub slot_owners: &'a mut SignerSlotOwners,
+    /// Records the stacks node responses, if event recording is enabled
+    pub event_recorder: Option<&'a EventRecorder>,
+    /// Alerts operators of critical events, if an alert webhook is configured
+    pub alert_notifier: Option<&'a AlertNotifier>,
+}
+
+impl StacksNodeConfigSource<'_> {
+    /// Get the registered signers for a specific reward cycle
+    /// Returns None if no signers are registered or its not Nakamoto cycle
+    pub fn get_parsed_reward_set(
+        &self,
+        reward_cycle: u64,
+    ) -> Result<Option<SignerEntries>, ClientError> {
+        debug!("Getting registered signers for reward cycle {reward_cycle}...");
+        let signers = self.stacks_client.get_reward_set_signers(reward_cycle)?;
+        if let Some(recorder) = self.event_recorder {
+            recorder.record(&RecordedEntry::RewardSet {
+                reward_cycle,
+                signers: signers.clone(),
+            });
+        }
+        let Some(signers) = signers else {
+            warn!("No reward set signers found for reward cycle {reward_cycle}.");
+            return Ok(None);
+        };
+        if signers.is_empty() {
+            warn!("No registered signers found for reward cycle {reward_cycle}.");
+            return Ok(None);
+        }
+        let entries = SignerEntries::parse(self.config.network_profile.mainnet, &signers).unwrap();
+        Ok(Some(entries))
+    }
+
+    /// Get the stackerdb signer slots for a specific reward cycle, along with the number of
+    /// message slots the contract allocates to each signer
+    pub fn get_parsed_signer_slots(
+        &self,
+        reward_cycle: u64,
+    ) -> Result<HashMap<StacksAddress, (SignerSlotID, u32)>, ClientError> {
+        let signer_set =
+            u32::try_from(reward_cycle % 2).expect("FATAL: reward_cycle % 2 exceeds u32::MAX");
+        let signer_stackerdb_contract_id =
+            boot_code_id(SIGNERS_NAME, self.config.network_profile.mainnet);
+        // Get the signer writers from the stacker-db to find the signer slot id
+        let stackerdb_signer_slots = self
+            .stacks_client
+            .get_stackerdb_signer_slots(&signer_stackerdb_contract_id, signer_set)?;
+        if let Some(recorder) = self.event_recorder {
+            recorder.record(&RecordedEntry::SignerSlots {
+                reward_cycle,
+                slots: stackerdb_signer_slots.clone(),
+            });
+        }
+        Ok(parse_signer_slots(stackerdb_signer_slots))
+    }
+}
+
+impl SignerConfigSource for StacksNodeConfigSource<'_> {
+    fn signer_config(&mut self, reward_cycle: u64) -> Option<SignerConfig> {
+        // We can only register for a reward cycle if a reward set exists.
+        let signer_entries = self.get_parsed_reward_set(reward_cycle).ok()??;
+        let signer_slot_ids = self.get_parsed_signer_slots(reward_cycle).ok()?;
+        signer_config_for(
+            self.config,
+            self.stacks_client.get_signer_address(),
+            reward_cycle,
+            signer_entries,
+            signer_slot_ids,
+            self.slot_owners,
+            self.event_recorder.cloned(),
+            self.alert_notifier.cloned(),
+        )
+    }
+}
+
+/// Map the owner of each signers stacker-db slot, in slot order, to its slot id and the number
+/// of message slots the contract allocates to it
+pub fn parse_signer_slots(
+    stackerdb_signer_slots: Vec<(StacksAddress, u128)>,
+) -> HashMap<StacksAddress, (SignerSlotID, u32)> {
+    let mut signer_slot_ids = HashMap::with_capacity(stackerdb_signer_slots.len());
+    for (index, (address, num_slots)) in stackerdb_signer_slots.into_iter().enumerate() {
+        let signer_slot_id =
+            SignerSlotID(u32::try_from(index).expect("FATAL: number of signers exceeds u32::MAX"));
+        let num_slots = u32::try_from(num_slots).unwrap_or(u32::MAX);
+        signer_slot_ids.insert(address, (signer_slot_id, num_slots));
+    }
+    signer_slot_ids
+}
+
+/// Build the configuration of the signer with the given address from the reward set and
+/// stacker-db slots of a reward cycle, updating the slot owners with its signer set.
+/// Returns None if the signer is not registered for the reward cycle.
+pub fn signer_config_for(
+    config: &GlobalConfig,
+    signer_address: &StacksAddress,
+    reward_cycle: u64,
+    signer_entries: SignerEntries,
+    signer_slot_ids: HashMap<StacksAddress, (SignerSlotID, u32)>,
+    slot_owners: &mut SignerSlotOwners,
+    event_recorder: Option<EventRecorder>,
+    alert_notifier: Option<AlertNotifier>,
+) -> Option<SignerConfig> {
+    let signer_set =
+        u32::try_from(reward_cycle % 2).expect("FATAL: reward_cycle % 2 exceeds u32::MAX");
+    slot_owners.set_signer_set(
+        signer_set,
+        signer_slot_ids
+            .iter()
+            .map(|(address, (slot_id, _))| (slot_id.0, *address))
+            .collect(),
+    );
+    let Some((signer_slot_id, num_slots)) = signer_slot_ids.get(signer_address) else {
+        warn!(
+            "Signer {signer_address} was not found in stacker db. Must not be registered for this reward cycle {reward_cycle}."
+        );
+        return None;
+    };
+    let Some(signer_id) = signer_entries.signer_ids.get(signer_address) else {
+        warn!(
+            "Signer {signer_address} was found in stacker db but not the reward set for reward cycle {reward_cycle}."
+        );
+        return None;
+    };
+    let signer_name = config
+        .signer_name
+        .clone()
+        .unwrap_or_else(|| format!("signer-{signer_id}"));
+    info!(
+        "Signer #{signer_id} [{signer_name}] ({signer_address}) is registered for reward cycle {reward_cycle}."
+    );
+    let slot_layout = config
+        .slot_layout
+        .clone()
+        .unwrap_or_else(|| SlotLayout::default_for_slots(*num_slots));
+    if let Err(e) = slot_layout.validate(*num_slots) {
+        error!("Signer #{signer_id} [{signer_name}]: invalid stacker-db slot layout for reward cycle {reward_cycle}: {e}");
+        return None;
+    }
+    let key_ids = signer_entries
+        .signer_key_ids
+        .get(signer_id)
+        .cloned()
+        .unwrap_or_default();
+    Some(SignerConfig {
+        reward_cycle,
+        signer_id: *signer_id,
+        signer_name,
+        signer_slot_id: *signer_slot_id,
+        key_ids,
+        signer_entries,
+        signer_slot_ids: signer_slot_ids
+            .into_values()
+            .map(|(slot_id, _)| slot_id)
+            .collect(),
+        slot_owners: slot_owners.clone(),
+        ecdsa_private_key: config.ecdsa_private_key,
+        stacks_private_key: config.stacks_private_key,
+        node_host: config.node_host.to_string(),
+        node_transport: config.node_transport,
+        network: config.network_profile,
+        dkg_end_timeout: config.dkg_end_timeout,
+        dkg_private_timeout: config.dkg_private_timeout,
+        dkg_public_timeout: config.dkg_public_timeout,
+        nonce_timeout: config.nonce_timeout,
+        sign_timeout: config.sign_timeout,
+        block_validation_timeout: config.block_validation_timeout,
+        dkg_round_stall_timeout: config.dkg_round_stall_timeout,
+        packet_future_tolerance: config.packet_future_tolerance,
+        tx_fee_ustx: config.tx_fee_ustx,
+        max_tx_fee_ustx: config.max_tx_fee_ustx,
+        max_acceptable_median_fee_rate: config.max_acceptable_median_fee_rate,
+        max_validation_attempts: config.max_validation_attempts,
+        max_rejected_requests_per_round: config.max_rejected_requests_per_round,
+        db_path: config.db_path.clone(),
+        retry_config: config.retry_config,
+        stackerdb_write_retry_config: config.stackerdb_write_retry_config,
+        response_limits: config.response_limits,
+        chunk_compression_threshold: config.chunk_compression_threshold,
+        slot_layout,
+        clear_stackerdb_on_tenure_end: config.clear_stackerdb_on_tenure_end,
+        parallel_validation: config.parallel_validation,
+        missing_transaction_policy: config.missing_transaction_policy,
+        stale_node_nack_policy: config.stale_node_nack_policy,
+        event_recorder,
+        alert_notifier,
+        block_proposal_filter: config.block_proposal_filter.clone(),
+    })
+}
+
+/// The signers of the current reward cycle and, once its signer set is calculated in the prepare
+/// phase, of the next reward cycle, keyed by reward cycle
+pub struct SignerManager<Signer, T>
+where
+    Signer: SignerTrait<T>,
+    T: StacksMessageCodec + Clone + Send + Debug,
+{
+    /// The running signers, at most one per signer set
+    signers: BTreeMap<u64, Signer>,
+    /// The number of events received for a signer set no signer is running for
+    unrouted_events: u64,
+    /// Phantom data for the message codec
+    _phantom_data: std::marker::PhantomData<T>,
+}
+
+impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> Default
+    for SignerManager<Signer, T>
+{
+    fn default() -> Self {
+        Self {
+            signers: BTreeMap::new(),
+            unrouted_events: 0,
+            _phantom_data: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug>
+    SignerManager<Signer, T>
+{
+    /// Whether no signer is running
+    pub fn is_empty(&self) -> bool {
+        self.signers.is_empty()
+    }
+
+    /// Get the signer running for the given reward cycle
+    pub fn get(&self, reward_cycle: u64) -> Option<&Signer> {
+        self.signers.get(&reward_cycle)
+    }
+
+    /// The running signers, ordered by reward cycle
+    pub fn signers(&self) -> impl Iterator<Item = &Signer> {
+        self.signers.values()
+    }
+
+    /// The running signers, ordered by reward cycle
+    pub fn signers_mut(&mut self) -> impl Iterator<Item = &mut Signer> {
+        self.signers.values_mut()
+    }
+
+    /// The number of events received for a signer set no signer is running for
+    pub fn unrouted_events(&self) -> u64 {
+        self.unrouted_events
+    }
+
+    /// Start the signer of the given reward cycle if `source` has a configuration for it.
+    /// The signer of the prior reward cycle learns about its successors, and the signer it
+    /// shares its signer set with, if any, is replaced. Returns whether a signer was started.
+    pub fn register_signer(
+        &mut self,
+        source: &mut impl SignerConfigSource,
+        reward_cycle: u64,
+    ) -> bool {
+        let Some(new_signer_config) = source.signer_config(reward_cycle) else {
+            warn!("Signer is not registered for reward cycle {reward_cycle}. Waiting for confirmed registration...");
+            return false;
+        };
+        let signer_id = new_signer_config.signer_id;
+        let signer_name = &new_signer_config.signer_name;
+        debug!("Signer is registered for reward cycle {reward_cycle} as signer #{signer_id} [{signer_name}]. Initializing signer state.");
+        if let Some(signer) = reward_cycle
+            .checked_sub(1)
+            .and_then(|prior_reward_cycle| self.signers.get_mut(&prior_reward_cycle))
+        {
+            // The signers have been calculated for the next reward cycle. Update the current one
+            debug!("Next reward cycle signer set calculated. Reconfiguring current reward cycle signer.";
+                "signer" => %signer,
+                "next_reward_cycle" => reward_cycle,
+            );
+            signer.update_next_signer_data(&new_signer_config);
+        }
+        let same_signer_set = self
+            .signers
+            .keys()
+            .copied()
+            .filter(|cycle| *cycle != reward_cycle && cycle % 2 == reward_cycle % 2)
+            .collect::<Vec<_>>();
+        for cycle in same_signer_set {
+            self.retire_signer(cycle);
+        }
+        let new_signer = Signer::new(new_signer_config);
+        info!("{new_signer} initialized.");
+        self.signers.insert(reward_cycle, new_signer);
+        true
+    }
+
+    /// Retire the signers whose tenure ended before the given reward cycle
+    pub fn retire_stale_signers(&mut self, current_reward_cycle: u64) {
+        let stale = self
+            .signers
+            .range(..current_reward_cycle)
+            .map(|(cycle, _)| *cycle)
+            .collect::<Vec<_>>();
+        for cycle in stale {
+            self.retire_signer(cycle);
+        }
+    }
+
+    /// Ask every signer to finish its in-progress operation before the process stops.
+    /// Once all of them are ready to stop, they are dropped, closing their databases, and true
+    /// is returned.
+    pub fn graceful_shutdown(&mut self, timeout: Duration) -> bool {
+        let ready = self.signers.values_mut().fold(true, |ready, signer| {
+            signer.graceful_shutdown(timeout) && ready
+        });
+        if ready {
+            self.signers.clear();
+        }
+        ready
+    }
+
+    /// Clean up after the signer of the given reward cycle and drop it
+    fn retire_signer(&mut self, reward_cycle: u64) {
+        if let Some(mut signer) = self.signers.remove(&reward_cycle) {
+            debug!("Signer's tenure has completed."; "signer" => %signer);
+            signer.cleanup();
+        }
+    }
+
+    /// The signer set an event is addressed to, if it is not for every signer
+    fn event_signer_set(event: Option<&SignerEvent<T>>, current_reward_cycle: u64) -> Option<u64> {
+        match event {
+            Some(SignerEvent::SignerMessages(signer_set, _)) => Some(u64::from(*signer_set) % 2),
+            // Block proposals are only validated for the current reward cycle
+            Some(SignerEvent::BlockValidationResponse(_)) => Some(current_reward_cycle % 2),
+            // Block proposal events do have reward cycles, but each proposal has its own cycle,
+            //  and the vec could be heterogenous, so, don't differentiate.
+            Some(SignerEvent::MinerMessages(..))
+            | Some(SignerEvent::NewBurnBlock(_))
+            | Some(SignerEvent::StatusCheck)
+            | None => None,
+        }
+    }
+
+    /// Route the event to the signers of the signer set it is addressed to, then run the next
+    /// command of each signer. Events for a signer set no signer is running for are counted.
+    pub fn process_event(
+        &mut self,
+        stacks_client: &StacksClient,
+        event: Option<&SignerEvent<T>>,
+        res: Sender<Vec<OperationResult>>,
+        current_reward_cycle: u64,
+        commands: &mut VecDeque<RunLoopCommand>,
+    ) {
+        let signer_set = Self::event_signer_set(event, current_reward_cycle);
+        let routed = signer_set.map_or(true, |signer_set| {
+            self.signers.keys().any(|cycle| cycle % 2 == signer_set)
+        });
+        if !routed {
+            self.unrouted_events = self.unrouted_events.saturating_add(1);
+            crate::monitoring::increment_unrouted_signer_events();
+            debug!("No signer is running for signer set {signer_set:?}. Ignoring event: {event:?}");
+        }
+        for signer in self.signers.values_mut() {
+            if signer_set.map_or(true, |signer_set| signer.reward_cycle() % 2 == signer_set) {
+                signer.process_event(stacks_client, event, res.clone(), current_reward_cycle);
+            }
+            // After processing event, run the next command for each signer
+            signer.process_command(stacks_client, current_reward_cycle, commands.pop_front());
+        }
+    }
+}
+
 /// The runloop for the stacks signer
 pub struct RunLoop<Signer, T>
 where
@@ -129,155 +516,208 @@ where
     pub config: GlobalConfig,
     /// The stacks node client
     pub stacks_client: StacksClient,
-    /// The internal signer for an odd or even reward cycle
-    /// Keyed by reward cycle % 2
-    pub stacks_signers: HashMap<u64, Signer>,
+    /// The signers of the current and next reward cycles
+    pub signer_manager: SignerManager<Signer, T>,
     /// The state of the runloop
     pub state: State,
     /// The commands received thus far
     pub commands: VecDeque<RunLoopCommand>,
     /// The current reward cycle info. Only None if the runloop is uninitialized
     pub current_reward_cycle_info: Option<RewardCycleInfo>,
+    /// The owners of the signers stacker-db slots, shared with the event receiver
+    pub slot_owners: SignerSlotOwners,
+    /// Set by the signal handler to stop the event loop once the signers' in-progress
+    /// operations complete
+    pub shutdown_requested: Arc<AtomicBool>,
+    /// Records the events and stacks node responses of the session, if enabled
+    pub event_recorder: Option<EventRecorder>,
+    /// Alerts operators of critical events, if an alert webhook is configured
+    pub alert_notifier: Option<AlertNotifier>,
+    /// When the runloop was created, standing in for the last stacks node contact until the
+    /// node is first reached
+    started_at: Instant,
+    /// Whether operators were alerted that the stacks node is unreachable since it was last reached
+    node_unreachable_alerted: bool,
+    /// The modification time of the configuration file when it was last loaded
+    config_modified: Option<SystemTime>,
+    /// The metrics served on the metrics port, if one is configured
+    exported_metrics: Option<Arc<Mutex<String>>>,
+    /// The status served on the metrics port, if one is configured
+    exported_status: Option<Arc<Mutex<ExportedStatus>>>,
     /// Phantom data for the message codec
     _phantom_data: std::marker::PhantomData<T>,
 }
 
+/// The version of the signer binary, with the git branch and commit it was built from
+fn signer_version() -> String {
+    blockstack_lib::version_string(
+        "stacks-signer",
+        option_env!("STACKS_SIGNER_VERSION")
+            .or(option_env!("CARGO_PKG_VERSION"))
+            .unwrap_or("0.0.0.0"),
+    )
+}
+
 impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLoop<Signer, T> {
     /// Create a new signer runloop from the provided configuration
     pub fn new(config: GlobalConfig) -> Self {
         let stacks_client = StacksClient::from(&config);
+        let config_modified = config
+            .config_path
+            .as_ref()
+            .and_then(|path| fs::metadata(path).and_then(|m| m.modified()).ok());
+        let (exported_metrics, exported_status) = config
+            .metrics_port
+            .and_then(|port| {
+                SignerMetricsServer::bind(port, config.health_check_timeout)
+                    .map(|server| {
+                        let exported = (server.exported_metrics(), server.exported_status());
+                        server.spawn();
+                        exported
+                    })
+                    .map_err(|e| error!("Failed to serve signer metrics on port {port}: {e:?}"))
+                    .ok()
+            })
+            .unzip();
+        let event_recorder = config.record_events_path.as_ref().and_then(|path| {
+            EventRecorder::open(path)
+                .map_err(|e| error!("Failed to open {} to record events: {e:?}", path.display()))
+                .ok()
+        });
+        let alert_notifier = config
+            .alert_webhook_url
+            .as_deref()
+            .map(AlertNotifier::spawn);
         Self {
             config,
             stacks_client,
-            stacks_signers: HashMap::with_capacity(2),
+            signer_manager: SignerManager::default(),
             state: State::Uninitialized,
             commands: VecDeque::new(),
             current_reward_cycle_info: None,
+            slot_owners: SignerSlotOwners::default(),
+            shutdown_requested: Arc::new(AtomicBool::new(false)),
+            event_recorder,
+            alert_notifier,
+            started_at: Instant::now(),
+            node_unreachable_alerted: false,
+            config_modified,
+            exported_metrics,
+            exported_status,
             _phantom_data: std::marker::PhantomData,
         }
     }
-    /// Get the registered signers for a specific reward cycle
-    /// Returns None if no signers are registered or its not Nakamoto cycle
-    pub fn get_parsed_reward_set(
-        &self,
-        reward_cycle: u64,
-    ) -> Result<Option<SignerEntries>, ClientError> {
-        debug!("Getting registered signers for reward cycle {reward_cycle}...");
-        let Some(signers) = self.stacks_client.get_reward_set_signers(reward_cycle)? else {
-            warn!("No reward set signers found for reward cycle {reward_cycle}.");
-            return Ok(None);
+    /// Whether the event can help the signers complete their in-progress operations. Only those
+    /// events are processed once a shutdown is requested: new block proposals and burn blocks
+    /// are not accepted.
+    fn drives_operations(event: &SignerEvent<T>) -> bool {
+        matches!(
+            event,
+            SignerEvent::SignerMessages(..)
+                | SignerEvent::BlockValidationResponse(_)
+                | SignerEvent::StatusCheck
+        )
+    }
+
+    /// Replace the metrics served on the metrics port with the current state of the signers
+    fn export_metrics(&self) {
+        let Some(exported_metrics) = &self.exported_metrics else {
+            return;
         };
-        if signers.is_empty() {
-            warn!("No registered signers found for reward cycle {reward_cycle}.");
-            return Ok(None);
+        let signers_metrics: Vec<_> = self
+            .signer_manager
+            .signers()
+            .map(|signer| signer.signer_metrics())
+            .collect();
+        let metrics = format_prometheus(&signers_metrics);
+        match exported_metrics.lock() {
+            Ok(mut exported_metrics) => *exported_metrics = metrics,
+            Err(e) => warn!("Failed to export the signer metrics: {e:?}"),
         }
-        let entries = SignerEntries::parse(self.config.network.is_mainnet(), &signers).unwrap();
-        Ok(Some(entries))
     }
 
-    /// Get the stackerdb signer slots for a specific reward cycle
-    pub fn get_parsed_signer_slots(
-        &self,
-        stacks_client: &StacksClient,
-        reward_cycle: u64,
-    ) -> Result<HashMap<StacksAddress, SignerSlotID>, ClientError> {
-        let signer_set =
-            u32::try_from(reward_cycle % 2).expect("FATAL: reward_cycle % 2 exceeds u32::MAX");
-        let signer_stackerdb_contract_id =
-            boot_code_id(SIGNERS_NAME, self.config.network.is_mainnet());
-        // Get the signer writers from the stacker-db to find the signer slot id
-        let stackerdb_signer_slots =
-            stacks_client.get_stackerdb_signer_slots(&signer_stackerdb_contract_id, signer_set)?;
-        let mut signer_slot_ids = HashMap::with_capacity(stackerdb_signer_slots.len());
-        for (index, (address, _)) in stackerdb_signer_slots.into_iter().enumerate() {
-            signer_slot_ids.insert(
-                address,
-                SignerSlotID(
-                    u32::try_from(index).expect("FATAL: number of signers exceeds u32::MAX"),
-                ),
-            );
+    /// Replace the status served on the metrics port with the current state of the signer
+    /// process, marking the event loop as alive
+    fn export_status(&self) {
+        let Some(exported_status) = &self.exported_status else {
+            return;
+        };
+        let status = ProcessStatus {
+            version: signer_version(),
+            runloop_state: format!("{:?}", self.state),
+            last_burn_block_height: self
+                .current_reward_cycle_info
+                .map(|info| info.last_burnchain_block_height),
+            node_backoff_ms: self
+                .stacks_client
+                .node_backoff_remaining()
+                .map_or(0, |remaining| {
+                    u64::try_from(remaining.as_millis()).unwrap_or(u64::MAX)
+                }),
+            signers: self
+                .signer_manager
+                .signers()
+                .map(|signer| signer.signer_status())
+                .collect(),
+        };
+        let exported = ExportedStatus {
+            status: Arc::new(status),
+            last_heartbeat: Some(Instant::now()),
+            last_node_contact: self.stacks_client.last_node_contact(),
+        };
+        match exported_status.lock() {
+            Ok(mut exported_status) => *exported_status = exported,
+            Err(e) => warn!("Failed to export the signer status: {e:?}"),
         }
-        Ok(signer_slot_ids)
     }
-    /// Get a signer configuration for a specific reward cycle from the stacks node
-    fn get_signer_config(&mut self, reward_cycle: u64) -> Option<SignerConfig> {
-        // We can only register for a reward cycle if a reward set exists.
-        let signer_entries = self.get_parsed_reward_set(reward_cycle).ok()??;
-        let signer_slot_ids = self
-            .get_parsed_signer_slots(&self.stacks_client, reward_cycle)
-            .ok()?;
-        let current_addr = self.stacks_client.get_signer_address();
-
-        let Some(signer_slot_id) = signer_slot_ids.get(current_addr) else {
-            warn!(
-                    "Signer {current_addr} was not found in stacker db. Must not be registered for this reward cycle {reward_cycle}."
-                );
-            return None;
-        };
-        let Some(signer_id) = signer_entries.signer_ids.get(current_addr) else {
-            warn!(
-                "Signer {current_addr} was found in stacker db but not the reward set for reward cycle {reward_cycle}."
-            );
-            return None;
+
+    /// Alert operators once the stacks node has not been reached for longer than the health
+    /// check timeout, and again after each outage once the node was reached in between
+    fn alert_if_node_unreachable(&mut self) {
+        let Some(alert_notifier) = &self.alert_notifier else {
+            return;
         };
-        info!(
-            "Signer #{signer_id} ({current_addr}) is registered for reward cycle {reward_cycle}."
+        let unreachable_for = self
+            .stacks_client
+            .last_node_contact()
+            .unwrap_or(self.started_at)
+            .elapsed();
+        if unreachable_for <= self.config.health_check_timeout {
+            self.node_unreachable_alerted = false;
+            return;
+        }
+        if self.node_unreachable_alerted {
+            return;
+        }
+        alert_notifier.notify(
+            AlertEvent::NodeUnreachable,
+            None,
+            self.current_reward_cycle_info.map(|info| info.reward_cycle),
+            format!(
+                "The stacks node at {} was not reached for {}s",
+                self.config.node_host,
+                unreachable_for.as_secs()
+            ),
         );
-        let key_ids = signer_entries
-            .signer_key_ids
-            .get(signer_id)
-            .cloned()
-            .unwrap_or_default();
-        Some(SignerConfig {
-            reward_cycle,
-            signer_id: *signer_id,
-            signer_slot_id: *signer_slot_id,
-            key_ids,
-            signer_entries,
-            signer_slot_ids: signer_slot_ids.into_values().collect(),
-            ecdsa_private_key: self.config.ecdsa_private_key,
-            stacks_private_key: self.config.stacks_private_key,
-            node_host: self.config.node_host.to_string(),
-            mainnet: self.config.network.is_mainnet(),
-            dkg_end_timeout: self.config.dkg_end_timeout,
-            dkg_private_timeout: self.config.dkg_private_timeout,
-            dkg_public_timeout: self.config.dkg_public_timeout,
-            nonce_timeout: self.config.nonce_timeout,
-            sign_timeout: self.config.sign_timeout,
-            tx_fee_ustx: self.config.tx_fee_ustx,
-            max_tx_fee_ustx: self.config.max_tx_fee_ustx,
-            db_path: self.config.db_path.clone(),
-        })
+        self.node_unreachable_alerted = true;
     }
 
     /// Refresh signer configuration for a specific reward cycle
     fn refresh_signer_config(&mut self, reward_cycle: u64) {
-        let reward_index = reward_cycle % 2;
-        if let Some(new_signer_config) = self.get_signer_config(reward_cycle) {
-            let signer_id = new_signer_config.signer_id;
-            debug!("Signer is registered for reward cycle {reward_cycle} as signer #{signer_id}. Initializing signer state.");
-            if reward_cycle != 0 {
-                let prior_reward_cycle = reward_cycle.saturating_sub(1);
-                let prior_reward_set = prior_reward_cycle % 2;
-                if let Some(signer) = self.stacks_signers.get_mut(&prior_reward_set) {
-                    if signer.reward_cycle() == prior_reward_cycle {
-                        // The signers have been calculated for the next reward cycle. Update the current one
-                        debug!("{signer}: Next reward cycle ({reward_cycle}) signer set calculated. Reconfiguring current reward cycle signer.");
-                        signer.update_next_signer_data(&new_signer_config);
-                    }
-                }
-            }
-            let new_signer = Signer::new(new_signer_config);
-            info!("{new_signer} initialized.");
-            self.stacks_signers.insert(reward_index, new_signer);
-        } else {
-            warn!("Signer is not registered for reward cycle {reward_cycle}. Waiting for confirmed registration...");
-        }
+        let mut source = StacksNodeConfigSource {
+            config: &self.config,
+            stacks_client: &self.stacks_client,
+            slot_owners: &mut self.slot_owners,
+            event_recorder: self.event_recorder.as_ref(),
+            alert_notifier: self.alert_notifier.as_ref(),
+        };
+        self.signer_manager
+            .register_signer(&mut source, reward_cycle);
     }
 
     fn initialize_runloop(&mut self) -> Result<(), ClientError> {
         debug!("Initializing signer runloop...");
-        let reward_cycle_info = retry_with_exponential_backoff(|| {
+        let reward_cycle_info = retry_with_exponential_backoff(&self.config.retry_config, || {
             self.stacks_client
                 .get_current_reward_cycle_info()
                 .map_err(backoff::Error::transient)
@@ -290,7 +730,7 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLo
             self.refresh_signer_config(current_reward_cycle.saturating_add(1));
         }
         self.current_reward_cycle_info = Some(reward_cycle_info);
-        if self.stacks_signers.is_empty() {
+        if self.signer_manager.is_empty() {
             self.state = State::NoRegisteredSigners;
         } else {
             self.state = State::RegisteredSigners;
@@ -308,7 +748,8 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLo
 
         // First ensure we refresh our view of the current reward cycle information
         if block_reward_cycle != current_reward_cycle {
-            let new_reward_cycle_info = retry_with_exponential_backoff(|| {
+            let retry_config = &self.config.retry_config;
+            let new_reward_cycle_info = retry_with_exponential_backoff(retry_config, || {
                 let info = self
                     .stacks_client
                     .get_current_reward_cycle_info()
@@ -332,18 +773,14 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLo
         // We should only attempt to refresh the signer if we are not configured for the next reward cycle yet and we received a new burn block for its prepare phase
         if reward_cycle_info.is_in_next_prepare_phase(current_burn_block_height) {
             let next_reward_cycle = current_reward_cycle.saturating_add(1);
-            if self
-                .stacks_signers
-                .get(&(next_reward_cycle % 2))
-                .map(|signer| signer.reward_cycle() != next_reward_cycle)
-                .unwrap_or(true)
-            {
+            if self.signer_manager.get(next_reward_cycle).is_none() {
                 info!("Received a new burnchain block height ({current_burn_block_height}) in the prepare phase of the next reward cycle ({next_reward_cycle}). Checking for signer registration...");
                 self.refresh_signer_config(next_reward_cycle);
             }
         }
-        self.cleanup_stale_signers(current_reward_cycle);
-        if self.stacks_signers.is_empty() {
+        self.signer_manager
+            .retire_stale_signers(current_reward_cycle);
+        if self.signer_manager.is_empty() {
             self.state = State::NoRegisteredSigners;
         } else {
             self.state = State::RegisteredSigners;
@@ -351,18 +788,31 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLo
         Ok(())
     }
 
-    fn cleanup_stale_signers(&mut self, current_reward_cycle: u64) {
-        let mut to_delete = Vec::new();
-        for (idx, signer) in &mut self.stacks_signers {
-            if signer.reward_cycle() < current_reward_cycle {
-                debug!("{signer}: Signer's tenure has completed.");
-                to_delete.push(*idx);
-                continue;
-            }
+    /// Reload the configuration file if it changed since it was last loaded, and apply the
+    /// settings that can change while the signer runs, without aborting in-progress rounds
+    fn reload_config_if_modified(&mut self) {
+        let Some(config_path) = self.config.config_path.clone() else {
+            return;
+        };
+        let modified = fs::metadata(&config_path).and_then(|m| m.modified()).ok();
+        if modified.is_none() || modified == self.config_modified {
+            return;
         }
-        for idx in to_delete {
-            self.stacks_signers.remove(&idx);
+        self.config_modified = modified;
+        let mut config = match GlobalConfig::try_from(&config_path) {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("Failed to reload the signer configuration from {config_path:?}. Keeping the running configuration: {e}");
+                return;
+            }
+        };
+        info!("Signer configuration file {config_path:?} changed. Applying the updated configuration. Log levels and format are only read at startup.");
+        self.stacks_client.apply_node_config(&config);
+        for signer in self.signer_manager.signers_mut() {
+            signer.apply_runtime_updates(&config, &self.stacks_client);
         }
+        config.retain_restart_fields(&self.config);
+        self.config = config;
     }
 }
 
@@ -387,10 +837,31 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug>
             "Running one pass for the signer. state={:?}, cmd={cmd:?}, event={event:?}",
             self.state
         );
+        if let (Some(recorder), Some(event)) = (&self.event_recorder, &event) {
+            recorder.record_event(event);
+        }
+        let shutdown_requested = self.shutdown_requested.load(Ordering::SeqCst);
+        let event = match event {
+            Some(event) if shutdown_requested && !Self::drives_operations(&event) => {
+                debug!("Signer runloop is shutting down. Ignoring event: {event:?}");
+                None
+            }
+            event => event,
+        };
         if let Some(cmd) = cmd {
-            self.commands.push_back(cmd);
+            if shutdown_requested {
+                warn!("Signer runloop is shutting down. Ignoring command: {cmd:?}");
+            } else {
+                self.commands.push_back(cmd);
+            }
         }
+        self.reload_config_if_modified();
+        self.alert_if_node_unreachable();
         if self.state == State::Uninitialized {
+            if shutdown_requested {
+                info!("Signer runloop is shutting down before it was initialized.");
+                return Some(vec![]);
+            }
             if let Err(e) = self.initialize_runloop() {
                 error!("Failed to initialize signer runloop: {e}.");
                 if let Some(event) = event {
@@ -399,6 +870,7 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug>
                 return None;
             }
         } else if let Some(SignerEvent::NewBurnBlock(current_burn_block_height)) = event {
+            self.stacks_client.clear_sortition_info_cache();
             if let Err(e) = self.refresh_runloop(current_burn_block_height) {
                 error!("Failed to refresh signer runloop: {e}.");
                 warn!("Signer may have an outdated view of the network.");
@@ -415,21 +887,26 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug>
                 info!("Signer is not registered for the current reward cycle ({current_reward_cycle}). Reward set is not yet determined or signer is not registered for the upcoming reward cycle ({next_reward_cycle}).");
                 warn!("Ignoring event: {event:?}");
             }
-            return None;
+            self.export_metrics();
+            self.export_status();
+            return shutdown_requested.then(Vec::new);
         }
-        for signer in self.stacks_signers.values_mut() {
-            signer.process_event(
-                &self.stacks_client,
-                event.as_ref(),
-                res.clone(),
-                current_reward_cycle,
-            );
-            // After processing event, run the next command for each signer
-            signer.process_command(
-                &self.stacks_client,
-                current_reward_cycle,
-                self.commands.pop_front(),
-            );
+        self.signer_manager.process_event(
+            &self.stacks_client,
+            event.as_ref(),
+            res,
+            current_reward_cycle,
+            &mut self.commands,
+        );
+        self.export_metrics();
+        self.export_status();
+        if shutdown_requested
+            && self
+                .signer_manager
+                .graceful_shutdown(self.config.shutdown_timeout)
+        {
+            info!("Signer runloop is shutting down.");
+            return Some(vec![]);
         }
         None
     }
@@ -437,12 +914,241 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug>
 
 #[cfg(test)]
 mod tests {
+    use std::cell::RefCell;
+    use std::fmt::Display;
+    use std::io::{Read, Write};
+    use std::sync::mpsc::channel;
+
     use blockstack_lib::chainstate::stacks::boot::NakamotoSignerEntry;
+    use libsigner::v1::messages::SignerMessage;
     use libsigner::SignerEntries;
     use rand::{thread_rng, Rng, RngCore};
     use stacks_common::types::chainstate::{StacksPrivateKey, StacksPublicKey};
 
-    use super::RewardCycleInfo;
+    use wsts::curve::point::Point;
+
+    use super::*;
+    use crate::client::tests::{generate_signer_config, mock_server_random, write_response};
+    use crate::monitoring::{SignerMetrics, SignerStatus};
+    use crate::v1;
+    use crate::v1::signerdb::SignerDb;
+
+    thread_local! {
+        /// The reward cycles of the signers retired on this thread, in order
+        static RETIRED_SIGNERS: RefCell<Vec<u64>> = RefCell::new(vec![]);
+    }
+
+    /// A signer recording the events and signer set updates it receives
+    #[derive(Debug)]
+    struct RecordingSigner {
+        reward_cycle: u64,
+        events: Vec<&'static str>,
+        next_reward_cycles: Vec<u64>,
+    }
+
+    impl Display for RecordingSigner {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Cycle #{} Recording Signer", self.reward_cycle)
+        }
+    }
+
+    impl SignerTrait<SignerMessage> for RecordingSigner {
+        fn new(config: SignerConfig) -> Self {
+            Self {
+                reward_cycle: config.reward_cycle,
+                events: vec![],
+                next_reward_cycles: vec![],
+            }
+        }
+
+        fn update_next_signer_data(&mut self, next_signer_config: &SignerConfig) {
+            self.next_reward_cycles
+                .push(next_signer_config.reward_cycle);
+        }
+
+        fn reward_cycle(&self) -> u64 {
+            self.reward_cycle
+        }
+
+        fn cleanup(&mut self) {
+            RETIRED_SIGNERS.with(|retired| retired.borrow_mut().push(self.reward_cycle));
+        }
+
+        fn apply_runtime_updates(&mut self, _config: &GlobalConfig, _client: &StacksClient) {}
+
+        fn graceful_shutdown(&mut self, _timeout: Duration) -> bool {
+            true
+        }
+
+        fn process_event(
+            &mut self,
+            _stacks_client: &StacksClient,
+            event: Option<&SignerEvent<SignerMessage>>,
+            _res: Sender<Vec<OperationResult>>,
+            _current_reward_cycle: u64,
+        ) {
+            self.events.push(match event {
+                Some(SignerEvent::SignerMessages(0, _)) => "signer set 0",
+                Some(SignerEvent::SignerMessages(..)) => "signer set 1",
+                Some(SignerEvent::StatusCheck) => "status check",
+                _ => "other",
+            });
+        }
+
+        fn process_command(
+            &mut self,
+            _stacks_client: &StacksClient,
+            _current_reward_cycle: u64,
+            _command: Option<RunLoopCommand>,
+        ) {
+        }
+
+        fn signer_metrics(&self) -> SignerMetrics {
+            SignerMetrics {
+                reward_cycle: self.reward_cycle,
+                ..Default::default()
+            }
+        }
+
+        fn signer_status(&self) -> SignerStatus {
+            SignerStatus {
+                reward_cycle: self.reward_cycle,
+                ..Default::default()
+            }
+        }
+    }
+
+    /// Serves the signer configurations of a fixed set of reward cycles
+    struct FixedConfigSource(HashMap<u64, SignerConfig>);
+
+    impl SignerConfigSource for FixedConfigSource {
+        fn signer_config(&mut self, reward_cycle: u64) -> Option<SignerConfig> {
+            self.0.get(&reward_cycle).cloned()
+        }
+    }
+
+    #[test]
+    fn signer_manager_should_route_events_across_a_reward_cycle_rollover() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let stacks_client = StacksClient::from(&config);
+        let mut source = FixedConfigSource(
+            (4..=7)
+                .map(|reward_cycle| {
+                    let mut signer_config = generate_signer_config(&config, 5, 20);
+                    signer_config.reward_cycle = reward_cycle;
+                    (reward_cycle, signer_config)
+                })
+                .collect(),
+        );
+        let mut manager = SignerManager::<RecordingSigner, SignerMessage>::default();
+        let (res, _) = channel();
+        let mut process = |manager: &mut SignerManager<_, _>, event, current_reward_cycle| {
+            manager.process_event(
+                &stacks_client,
+                Some(&event),
+                res.clone(),
+                current_reward_cycle,
+                &mut VecDeque::new(),
+            )
+        };
+
+        // The next cycle's signer starts in the prepare phase, while the current one still runs
+        assert!(manager.register_signer(&mut source, 4));
+        assert!(manager.register_signer(&mut source, 5));
+        assert_eq!(manager.get(4).unwrap().next_reward_cycles, vec![5]);
+        process(&mut manager, SignerEvent::SignerMessages(0, vec![]), 4);
+        process(&mut manager, SignerEvent::SignerMessages(1, vec![]), 4);
+        process(&mut manager, SignerEvent::StatusCheck, 4);
+        assert_eq!(
+            manager.get(4).unwrap().events,
+            vec!["signer set 0", "status check"]
+        );
+        assert_eq!(
+            manager.get(5).unwrap().events,
+            vec!["signer set 1", "status check"]
+        );
+        assert_eq!(manager.unrouted_events(), 0);
+
+        // Once the next cycle starts, the signer of the prior one retires and its signer set's
+        // messages have nowhere to go
+        manager.retire_stale_signers(5);
+        assert!(manager.get(4).is_none());
+        process(&mut manager, SignerEvent::SignerMessages(0, vec![]), 5);
+        assert_eq!(
+            manager.get(5).unwrap().events,
+            vec!["signer set 1", "status check"]
+        );
+        assert_eq!(manager.unrouted_events(), 1);
+
+        // A signer replaces the one it shares its signer set with
+        assert!(manager.register_signer(&mut source, 6));
+        assert!(manager.register_signer(&mut source, 7));
+        assert_eq!(
+            manager
+                .signers()
+                .map(|signer| signer.reward_cycle)
+                .collect::<Vec<_>>(),
+            vec![6, 7]
+        );
+        assert!(!manager.register_signer(&mut source, 8));
+        RETIRED_SIGNERS.with(|retired| assert_eq!(*retired.borrow(), vec![4, 5]));
+    }
+
+    #[test]
+    fn status_endpoints_should_serve_the_state_exported_by_the_event_loop() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let (node_server, node_addr) = mock_server_random();
+        config.node_host = node_addr.to_string();
+        let (metrics_listener, metrics_addr) = mock_server_random();
+        drop(metrics_listener);
+        config.metrics_port = Some(metrics_addr.port());
+        let mut runloop = RunLoop::<RecordingSigner, SignerMessage>::new(config.clone());
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.reward_cycle = 4;
+        let mut source = FixedConfigSource([(4, signer_config)].into_iter().collect());
+        assert!(runloop.signer_manager.register_signer(&mut source, 4));
+        runloop.state = State::RegisteredSigners;
+        runloop.current_reward_cycle_info = Some(RewardCycleInfo {
+            reward_cycle: 4,
+            reward_cycle_length: 20,
+            prepare_phase_block_length: 5,
+            first_burnchain_block_height: 0,
+            last_burnchain_block_height: 85,
+        });
+        let get = |path: &str| {
+            let mut stream =
+                std::net::TcpStream::connect(("127.0.0.1", metrics_addr.port())).unwrap();
+            write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let (res, _) = channel();
+        runloop.run_one_pass(Some(SignerEvent::StatusCheck), None, res.clone());
+        let response = get("/status");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        let body = response.split_once("\r\n\r\n").unwrap().1;
+        let status: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(status["runloop_state"], "RegisteredSigners");
+        assert_eq!(status["last_burn_block_height"], 85);
+        assert_eq!(status["node_backoff_ms"], 0);
+        assert_eq!(status["signers"][0]["reward_cycle"], 4);
+        assert!(status["version"]
+            .as_str()
+            .unwrap()
+            .contains("stacks-signer"));
+        // The event loop runs, but the stacks node was never reached
+        assert!(get("/health").starts_with("HTTP/1.1 503 Service Unavailable"));
+
+        // Any answer of the stacks node shows it is reachable
+        let h = std::thread::spawn(move || write_response(node_server, b"HTTP/1.1 200 OK\n\n{}"));
+        assert!(runloop.stacks_client.get_peer_info().is_err());
+        h.join().unwrap();
+        runloop.run_one_pass(Some(SignerEvent::StatusCheck), None, res);
+        assert!(get("/health").starts_with("HTTP/1.1 200 OK"));
+        assert!(get("/metrics").starts_with("HTTP/1.1 200 OK"));
+    }
 
     #[test]
     fn parse_nakamoto_signer_entries_test() {
@@ -607,4 +1313,67 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn shutdown_should_complete_the_in_progress_operation_within_the_grace_period() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let db_path = std::env::temp_dir().join(format!(
+            "stacks-signer-shutdown-test-{}.sqlite",
+            rand::random::<u64>()
+        ));
+        config.db_path = db_path.clone();
+        config.shutdown_timeout = Duration::from_millis(200);
+        let mut runloop = RunLoop::<v1::signer::Signer, SignerMessage>::new(config.clone());
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.reward_cycle = 4;
+        let mut source = FixedConfigSource([(4, signer_config)].into_iter().collect());
+        assert!(runloop.signer_manager.register_signer(&mut source, 4));
+        runloop.state = State::RegisteredSigners;
+        runloop.current_reward_cycle_info = Some(RewardCycleInfo {
+            reward_cycle: 4,
+            reward_cycle_length: 20,
+            prepare_phase_block_length: 5,
+            first_burnchain_block_height: 0,
+            last_burnchain_block_height: 85,
+        });
+        for signer in runloop.signer_manager.signers_mut() {
+            // Keep the signer from asking the stacks node for the DKG state
+            signer.approved_aggregate_public_key = Some(Point::new());
+            signer.state = v1::signer::State::OperationInProgress(v1::signer::Operation::Sign);
+        }
+
+        let (res, res_recv) = channel();
+        runloop.shutdown_requested.store(true, Ordering::SeqCst);
+        let shutdown_start = Instant::now();
+        let command = RunLoopCommand {
+            reward_cycle: 4,
+            command: SignerCommand::Dkg,
+        };
+        assert!(runloop
+            .run_one_pass(None, Some(command), res.clone())
+            .is_none());
+        // No new command is accepted once shutting down
+        assert!(runloop.commands.is_empty());
+        let signer = runloop.signer_manager.get(4).unwrap();
+        assert!(signer.commands.is_empty());
+        assert!(signer.shutdown_started.is_some());
+
+        let final_results = loop {
+            if let Some(results) = runloop.run_one_pass(None, None, res.clone()) {
+                break results;
+            }
+            assert!(shutdown_start.elapsed() < Duration::from_secs(5));
+            std::thread::sleep(Duration::from_millis(20));
+        };
+        assert!(final_results.is_empty());
+        assert!(shutdown_start.elapsed() >= config.shutdown_timeout);
+        // The signers were dropped, closing their databases, after saving their state
+        assert!(runloop.signer_manager.is_empty());
+        let signer_db = SignerDb::readonly(&db_path).unwrap();
+        assert!(signer_db.get_encrypted_signer_state(4).unwrap().is_some());
+        assert!(signer_db.get_blocks(4).unwrap().is_empty());
+        drop(res);
+        assert!(res_recv.try_recv().is_err());
+        std::fs::remove_file(db_path).unwrap();
+    }
 }
@@ -17,6 +17,7 @@
 use std::time::Instant;
 
 use blockstack_lib::chainstate::burn::ConsensusHashExtensions;
+pub use libsigner::v1::messages::CoordinatorMetadata;
 use slog::slog_debug;
 use stacks_common::debug;
 use stacks_common::types::chainstate::ConsensusHash;
@@ -24,12 +25,25 @@ use stacks_common::util::hash::Sha256Sum;
 use wsts::curve::ecdsa;
 use wsts::state_machine::PublicKeys;
 
+use crate::client::SortitionInfo;
+
 /// TODO: test this value and adjust as necessary. Maybe make configurable?
 pub const COORDINATOR_OPERATION_TIMEOUT_SECS: u64 = 300;
 
 /// TODO: test this value and adjust as necessary. Maybe make configurable?
 pub const COORDINATOR_TENURE_TIMEOUT_SECS: u64 = 600;
 
+impl From<SortitionInfo> for CoordinatorMetadata {
+    fn from(sortition_info: SortitionInfo) -> Self {
+        Self {
+            burn_block_height: sortition_info.burn_block_height,
+            // The stacks node does not report the burn header hash of its canonical tip
+            burn_header_hash: None,
+            pox_consensus_hash: sortition_info.pox_consensus_hash,
+        }
+    }
+}
+
 /// The coordinator selector
 #[derive(Clone, Debug)]
 pub struct CoordinatorSelector {
@@ -124,6 +138,51 @@ impl CoordinatorSelector {
         self.coordinator_id
     }
 
+    /// Switch to the given coordinator and start its tenure, e.g. because the current
+    /// coordinator failed mid-round. Returns false if the id is not a known coordinator.
+    pub fn set_coordinator(&mut self, coordinator_id: u32) -> bool {
+        let Some(index) = self
+            .coordinator_ids
+            .iter()
+            .position(|id| *id == coordinator_id)
+        else {
+            return false;
+        };
+        self.coordinator_index = index;
+        self.coordinator_id = coordinator_id;
+        self.tenure_start = Instant::now();
+        self.last_message_time = None;
+        true
+    }
+
+    /// Switch to the coordinator following the current one in the ordered list of coordinators,
+    /// wrapping around, and start its tenure, e.g. because the current coordinator is
+    /// unresponsive. Returns the new coordinator ID, unchanged if there is a single coordinator.
+    pub fn advance_coordinator(&mut self) -> u32 {
+        self.coordinator_index =
+            self.coordinator_index.saturating_add(1) % self.coordinator_ids.len();
+        self.coordinator_id = *self
+            .coordinator_ids
+            .get(self.coordinator_index)
+            .expect("FATAL: Invalid number of registered signers");
+        self.tenure_start = Instant::now();
+        self.last_message_time = None;
+        self.coordinator_id
+    }
+
+    /// Pretend the given time elapsed since the coordinator's tenure started and it last sent
+    /// a message, so that tests can expire its timeouts without waiting for them
+    #[cfg(test)]
+    pub fn pass_time(&mut self, elapsed: std::time::Duration) {
+        self.tenure_start = self
+            .tenure_start
+            .checked_sub(elapsed)
+            .unwrap_or(self.tenure_start);
+        self.last_message_time = self
+            .last_message_time
+            .map(|time| time.checked_sub(elapsed).unwrap_or(time));
+    }
+
     /// Get the current coordinator id and public key
     pub fn get_coordinator(&self) -> (u32, ecdsa::PublicKey) {
         (
@@ -213,6 +272,45 @@ mod tests {
         results
     }
 
+    #[test]
+    fn set_coordinator_should_only_accept_known_coordinators() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let public_keys = generate_signer_config(&config, 10, 4000)
+            .signer_entries
+            .public_keys;
+        let mut selector = CoordinatorSelector::from(public_keys);
+        selector.last_message_time = Some(Instant::now());
+        let new_coordinator_id = *selector.coordinator_ids.last().unwrap();
+
+        assert!(selector.set_coordinator(new_coordinator_id));
+        assert_eq!(selector.get_coordinator().0, new_coordinator_id);
+        assert_eq!(
+            selector.coordinator_index,
+            selector.coordinator_ids.len() - 1
+        );
+        assert!(selector.last_message_time.is_none());
+
+        assert!(!selector.set_coordinator(u32::MAX));
+        assert_eq!(selector.get_coordinator().0, new_coordinator_id);
+    }
+
+    #[test]
+    fn advance_coordinator_should_cycle_through_the_coordinators() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let public_keys = generate_signer_config(&config, 3, 30)
+            .signer_entries
+            .public_keys;
+        let mut selector = CoordinatorSelector::from(public_keys);
+        let coordinator_ids = selector.coordinator_ids.clone();
+        selector.last_message_time = Some(Instant::now());
+
+        assert_eq!(selector.advance_coordinator(), coordinator_ids[1]);
+        assert!(selector.last_message_time.is_none());
+        assert_eq!(selector.advance_coordinator(), coordinator_ids[2]);
+        assert_eq!(selector.advance_coordinator(), coordinator_ids[0]);
+        assert_eq!(selector.get_coordinator().0, coordinator_ids[0]);
+    }
+
     #[test]
     fn calculate_coordinator_results_should_vary_or_match_based_on_hash() {
         let results_with_random_hash = generate_calculate_coordinator_test_results(true, 5);
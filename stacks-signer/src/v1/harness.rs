@@ -0,0 +1,722 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use blockstack_lib::chainstate::nakamoto::{NakamotoBlock, NakamotoBlockHeader, NakamotoBlockVote};
+use blockstack_lib::chainstate::stacks::boot::NakamotoSignerEntry;
+use blockstack_lib::chainstate::stacks::ThresholdSignature;
+use blockstack_lib::net::api::postblock_proposal::{
+    BlockValidateOk, BlockValidateResponse, NakamotoBlockProposal,
+};
+use blockstack_lib::net::api::poststackerdbchunk::StackerDBErrorCodes;
+use clarity::vm::costs::ExecutionCost;
+use clarity::vm::Value as ClarityValue;
+use libsigner::v1::messages::{BlockResponse, SignerMessage};
+use libsigner::{decode_chunk, BlockProposal, FragmentBuffer, SignerEntries, SignerEvent};
+use libstackerdb::{SlotMetadata, StackerDBChunkAckData, StackerDBChunkData};
+use stacks_common::codec::StacksMessageCodec;
+use stacks_common::types::chainstate::{StacksBlockId, StacksPrivateKey};
+use stacks_common::util::hash::Sha512Trunc256Sum;
+use wsts::curve::ecdsa;
+use wsts::curve::point::Point;
+use wsts::curve::scalar::Scalar;
+use wsts::net::Packet;
+use wsts::state_machine::coordinator::Coordinator;
+use wsts::state_machine::OperationResult;
+
+use crate::client::tests::generate_signer_config;
+use crate::client::{SignerSlotID, StacksClient};
+use crate::config::GlobalConfig;
+use crate::runloop::{RunLoopCommand, SignerCommand};
+use crate::v1::signer::{BlockInfo, Signer};
+use crate::Signer as _;
+
+/// The number of signers in the simulated signer set
+pub const NUM_SIGNERS: u32 = 5;
+/// The number of keys each simulated signer holds
+pub const KEYS_PER_SIGNER: u32 = 4;
+/// The reward cycle the simulated signer set is registered for
+pub const REWARD_CYCLE: u64 = 11;
+/// The reward cycle the simulated chain is in. It differs from the signers' reward cycle so
+/// that the signers, rather than the miner, coordinate signing rounds, but it has the same
+/// parity so that the signers process the block validations of the stacks node.
+pub const CURRENT_REWARD_CYCLE: u64 = REWARD_CYCLE - 2;
+/// The time the simulated coordinators wait for nonces
+pub const NONCE_TIMEOUT: Duration = Duration::from_secs(5);
+/// The seed of the key of the miner proposing the simulated blocks
+const MINER_KEY_SEED: &[u8] = b"harness-miner";
+/// The most message exchanges of the in-memory DKG round
+const MAX_DKG_EXCHANGES: usize = 16;
+/// The most rounds of message delivery and block validation before the signers must settle
+const MAX_SETTLE_ROUNDS: usize = 200;
+
+/// The state of the simulated stacks node, shared with the thread serving its RPC endpoints
+#[derive(Default)]
+struct MockStacksNodeState {
+    /// The latest chunk of each stacker-db slot, by stacker-db and slot ID
+    chunks: HashMap<(String, u32), StackerDBChunkData>,
+    /// The chunks stacker-db accepted that are yet to be delivered, in the order they were written
+    written_chunks: VecDeque<StackerDBChunkData>,
+    /// The blocks submitted for validation that are yet to be validated
+    block_proposals: VecDeque<NakamotoBlock>,
+    /// The blocks the node knows of, by block ID
+    blocks: HashMap<StacksBlockId, NakamotoBlock>,
+    /// The aggregate key approved by the signers voting contract, if any
+    approved_aggregate_key: Option<Point>,
+}
+
+impl MockStacksNodeState {
+    /// Answer the request with the given method, path and body with a status line and a body
+    fn handle(&mut self, method: &str, path: &str, body: &[u8]) -> (&'static str, Vec<u8>) {
+        let path = path.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+        match (method, segments.as_slice()) {
+            ("POST", ["v2", "stackerdb", address, name, "chunks"]) => {
+                self.put_chunk(format!("{address}/{name}"), body)
+            }
+            ("GET", ["v2", "stackerdb", address, name]) => {
+                self.list_chunks(&format!("{address}/{name}"))
+            }
+            ("GET", ["v2", "stackerdb", address, name, slot_id]) => {
+                self.get_chunk(&format!("{address}/{name}"), slot_id, None)
+            }
+            ("GET", ["v2", "stackerdb", address, name, slot_id, slot_version]) => {
+                self.get_chunk(&format!("{address}/{name}"), slot_id, Some(*slot_version))
+            }
+            ("POST", ["v2", "block_proposal"]) => {
+                let Ok(proposal) = serde_json::from_slice::<NakamotoBlockProposal>(body) else {
+                    return (BAD_REQUEST, vec![]);
+                };
+                self.block_proposals.push_back(proposal.block);
+                (ACCEPTED, vec![])
+            }
+            ("GET", ["v3", "blocks", block_id]) => StacksBlockId::from_hex(block_id)
+                .ok()
+                .and_then(|block_id| self.blocks.get(&block_id))
+                .map_or((NOT_FOUND, vec![]), |block| (OK, block.serialize_to_vec())),
+            ("POST", ["v2", "contracts", "call-read-only", _, _, function_name]) => {
+                let value = match (*function_name, self.approved_aggregate_key) {
+                    ("get-approved-aggregate-key", Some(aggregate_key)) => ClarityValue::some(
+                        ClarityValue::buff_from(aggregate_key.compress().as_bytes().to_vec())
+                            .expect("BUG: Failed to create clarity value from point"),
+                    )
+                    .expect("BUG: Failed to create clarity value from point"),
+                    _ => ClarityValue::none(),
+                };
+                let hex = value
+                    .serialize_to_hex()
+                    .expect("Failed to serialize hex value");
+                (
+                    OK,
+                    format!("{{\"okay\":true,\"result\":\"{hex}\"}}").into_bytes(),
+                )
+            }
+            _ => (NOT_FOUND, vec![]),
+        }
+    }
+
+    /// Store the chunk unless its slot already holds a chunk of the same or a later version
+    fn put_chunk(&mut self, stackerdb: String, body: &[u8]) -> (&'static str, Vec<u8>) {
+        let Ok(chunk) = serde_json::from_slice::<StackerDBChunkData>(body) else {
+            return (BAD_REQUEST, vec![]);
+        };
+        let key = (stackerdb, chunk.slot_id);
+        let ack = match self.chunks.get(&key) {
+            Some(stored) if stored.slot_version >= chunk.slot_version => StackerDBChunkAckData {
+                accepted: false,
+                reason: Some(StackerDBErrorCodes::DataAlreadyExists.reason().into()),
+                metadata: Some(stored.get_slot_metadata()),
+                code: Some(StackerDBErrorCodes::DataAlreadyExists.code()),
+            },
+            _ => {
+                let metadata = chunk.get_slot_metadata();
+                self.written_chunks.push_back(chunk.clone());
+                self.chunks.insert(key, chunk);
+                StackerDBChunkAckData {
+                    accepted: true,
+                    reason: None,
+                    metadata: Some(metadata),
+                    code: None,
+                }
+            }
+        };
+        (
+            OK,
+            serde_json::to_vec(&ack).expect("Failed to serialize chunk ack"),
+        )
+    }
+
+    /// List the metadata of the stacker-db's slots that hold a chunk
+    fn list_chunks(&self, stackerdb: &str) -> (&'static str, Vec<u8>) {
+        let mut metadata: Vec<SlotMetadata> = self
+            .chunks
+            .iter()
+            .filter(|((chunk_stackerdb, _), _)| chunk_stackerdb == stackerdb)
+            .map(|(_, chunk)| chunk.get_slot_metadata())
+            .collect();
+        metadata.sort_by_key(|slot_metadata| slot_metadata.slot_id);
+        (
+            OK,
+            serde_json::to_vec(&metadata).expect("Failed to serialize slot metadata"),
+        )
+    }
+
+    /// Get the data of the latest chunk of the slot, if it has the requested version
+    fn get_chunk(
+        &self,
+        stackerdb: &str,
+        slot_id: &str,
+        slot_version: Option<&str>,
+    ) -> (&'static str, Vec<u8>) {
+        let Ok(slot_id) = slot_id.parse::<u32>() else {
+            return (BAD_REQUEST, vec![]);
+        };
+        let Some(chunk) = self.chunks.get(&(stackerdb.to_string(), slot_id)) else {
+            return (NOT_FOUND, vec![]);
+        };
+        if slot_version.is_some_and(|slot_version| slot_version != chunk.slot_version.to_string()) {
+            return (NOT_FOUND, vec![]);
+        }
+        (OK, chunk.data.clone())
+    }
+}
+
+const OK: &str = "200 OK";
+const ACCEPTED: &str = "202 Accepted";
+const BAD_REQUEST: &str = "400 Bad Request";
+const NOT_FOUND: &str = "404 Not Found";
+
+/// A stacks node serving the RPC endpoints the signers use, including an in-memory stacker-db,
+/// from a background thread. Chunks written to stacker-db are kept for the harness to deliver.
+pub struct MockStacksNode {
+    /// The address the node listens on
+    pub addr: SocketAddr,
+    /// The state of the node, shared with the thread serving its endpoints
+    state: Arc<Mutex<MockStacksNodeState>>,
+}
+
+impl MockStacksNode {
+    /// Start serving the node's endpoints on a random local port
+    pub fn spawn() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind mock stacks node");
+        let addr = listener
+            .local_addr()
+            .expect("Failed to get mock stacks node address");
+        let state = Arc::new(Mutex::new(MockStacksNodeState::default()));
+        let served_state = state.clone();
+        thread::Builder::new()
+            .name("mock-stacks-node".into())
+            .spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    serve_request(stream, &served_state);
+                }
+            })
+            .expect("Failed to spawn the mock stacks node thread");
+        Self { addr, state }
+    }
+
+    fn state(&self) -> MutexGuard<MockStacksNodeState> {
+        self.state
+            .lock()
+            .expect("Mock stacks node state lock poisoned")
+    }
+
+    /// Make the block known to the node, e.g. as the parent of proposed blocks
+    pub fn add_block(&self, block: NakamotoBlock) {
+        self.state().blocks.insert(block.block_id(), block);
+    }
+
+    /// Approve the aggregate key in the signers voting contract
+    pub fn approve_aggregate_key(&self, aggregate_key: Point) {
+        self.state().approved_aggregate_key = Some(aggregate_key);
+    }
+
+    /// Take the chunks written to stacker-db since the last call, in the order they were written
+    fn take_written_chunks(&self) -> Vec<StackerDBChunkData> {
+        self.state().written_chunks.drain(..).collect()
+    }
+
+    /// Take the blocks submitted for validation since the last call
+    fn take_block_proposals(&self) -> Vec<NakamotoBlock> {
+        self.state().block_proposals.drain(..).collect()
+    }
+}
+
+/// Read a request from the stream, answer it and close the connection
+fn serve_request(mut stream: TcpStream, state: &Mutex<MockStacksNodeState>) {
+    let Some((method, path, body)) = read_request(&stream) else {
+        return;
+    };
+    let (status, response_body) = state
+        .lock()
+        .expect("Mock stacks node state lock poisoned")
+        .handle(&method, &path, &body);
+    let header = format!(
+        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response_body.len()
+    );
+    let _ = stream
+        .write_all(header.as_bytes())
+        .and_then(|_| stream.write_all(&response_body));
+}
+
+/// Read the method, path and body of an HTTP request
+fn read_request(stream: &TcpStream) -> Option<(String, String, Vec<u8>)> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    let mut request_line = request_line.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().ok()?;
+            }
+        }
+    }
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body).ok()?;
+    Some((method, path, body))
+}
+
+/// A signer set of in-process signers sharing a simulated stacks node. The harness drives the
+/// signers by hand: it delivers the messages they write to stacker-db, answers the block
+/// validations they request and sends them status checks, in a controlled order. Time only
+/// passes for the signers' timeouts when the harness advances their clock.
+pub struct SignerHarness {
+    /// The stacks node the signers talk to
+    pub node: MockStacksNode,
+    /// The signers, indexed by signer ID
+    pub signers: Vec<Signer>,
+    /// The aggregate key the signers agreed on
+    pub aggregate_key: Point,
+    /// The messages delivered to the signers so far, along with the slot they were written to
+    pub delivered: Vec<(u32, SignerMessage)>,
+    /// The client the signers reach the stacks node with
+    stacks_client: StacksClient,
+    /// The signers that neither receive events nor run commands
+    offline: HashSet<u32>,
+    /// Reassembles the messages written to stacker-db in several fragments
+    fragments: FragmentBuffer,
+    /// Where the signers send their operation results
+    res_send: Sender<Vec<OperationResult>>,
+    /// The operation results of the signers
+    res_recv: Receiver<Vec<OperationResult>>,
+}
+
+impl SignerHarness {
+    /// Build the signer set over a new stacks node and agree on an aggregate key in memory, so
+    /// that the signers start out ready to sign
+    pub fn spawn() -> Self {
+        let node = MockStacksNode::spawn();
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        config.node_host = node.addr.to_string();
+        let stacks_client = StacksClient::from(&config);
+
+        let stacks_private_keys: Vec<_> =
+            (0..NUM_SIGNERS).map(|_| StacksPrivateKey::new()).collect();
+        let ecdsa_private_keys: Vec<_> = stacks_private_keys
+            .iter()
+            .map(|private_key| Scalar::try_from(&private_key.to_bytes()[..32]).unwrap())
+            .collect();
+        let reward_set: Vec<_> = ecdsa_private_keys
+            .iter()
+            .map(|private_key| {
+                let mut signing_key = [0u8; 33];
+                signing_key.copy_from_slice(
+                    &ecdsa::PublicKey::new(private_key)
+                        .expect("Failed to create ecdsa public key")
+                        .to_bytes(),
+                );
+                NakamotoSignerEntry {
+                    signing_key,
+                    stacked_amt: u128::from(KEYS_PER_SIGNER),
+                    weight: KEYS_PER_SIGNER,
+                }
+            })
+            .collect();
+        let signer_entries = SignerEntries::parse(false, &reward_set).unwrap();
+
+        let mut signers: Vec<_> = (0..NUM_SIGNERS)
+            .zip(stacks_private_keys.into_iter().zip(ecdsa_private_keys))
+            .map(|(signer_id, (stacks_private_key, ecdsa_private_key))| {
+                let mut signer_config =
+                    generate_signer_config(&config, NUM_SIGNERS, NUM_SIGNERS * KEYS_PER_SIGNER);
+                signer_config.reward_cycle = REWARD_CYCLE;
+                signer_config.signer_id = signer_id;
+                signer_config.signer_name = format!("signer-{signer_id}");
+                signer_config.signer_slot_id = SignerSlotID(signer_id);
+                signer_config.key_ids = signer_entries
+                    .signer_key_ids
+                    .get(&signer_id)
+                    .cloned()
+                    .unwrap_or_default();
+                signer_config.signer_entries = signer_entries.clone();
+                signer_config.signer_slot_ids = (0..NUM_SIGNERS).map(SignerSlotID).collect();
+                signer_config.ecdsa_private_key = ecdsa_private_key;
+                signer_config.stacks_private_key = stacks_private_key;
+                signer_config.node_host = node.addr.to_string();
+                signer_config.nonce_timeout = Some(NONCE_TIMEOUT);
+                let mut signer = Signer::from(signer_config);
+                // The signers share a stacks client, so only the first could pass its self test
+                signer.self_test_passed = true;
+                signer
+            })
+            .collect();
+
+        let aggregate_key = run_dkg(&mut signers);
+        node.approve_aggregate_key(aggregate_key);
+        for signer in signers.iter_mut() {
+            signer
+                .update_approved_aggregate_key(&stacks_client)
+                .expect("Failed to load the approved aggregate key");
+        }
+        let (res_send, res_recv) = channel();
+        Self {
+            node,
+            signers,
+            aggregate_key,
+            delivered: vec![],
+            stacks_client,
+            offline: HashSet::new(),
+            fragments: FragmentBuffer::default(),
+            res_send,
+            res_recv,
+        }
+    }
+
+    /// A proposal of a block building on a block the stacks node knows of, signed by the
+    /// simulated miner
+    pub fn propose_block(&self) -> BlockProposal {
+        let parent = NakamotoBlock {
+            header: NakamotoBlockHeader::empty(),
+            txs: vec![],
+        };
+        let mut header = NakamotoBlockHeader::empty();
+        header.chain_length = 1;
+        header.parent_block_id = parent.block_id();
+        header
+            .sign_miner(&StacksPrivateKey::from_seed(MINER_KEY_SEED))
+            .expect("Failed to sign the block as the miner");
+        self.node.add_block(parent);
+        BlockProposal {
+            block: NakamotoBlock {
+                header,
+                txs: vec![],
+            },
+            burn_height: 1,
+            reward_cycle: REWARD_CYCLE,
+        }
+    }
+
+    /// The coordinator selected by the first online signer
+    pub fn coordinator_id(&self) -> u32 {
+        self.online_signers()
+            .next()
+            .expect("No signer is online")
+            .coordinator_selector
+            .get_coordinator()
+            .0
+    }
+
+    /// Stop delivering events to the signer and running its commands
+    pub fn disconnect(&mut self, signer_id: u32) {
+        self.offline.insert(signer_id);
+    }
+
+    /// Pretend the given time elapsed for every signer's timeouts
+    pub fn advance_clock(&mut self, elapsed: Duration) {
+        for signer in self.signers.iter_mut() {
+            signer.pass_time(elapsed);
+        }
+    }
+
+    /// Ask the online signers to sign the block, as the run loop does for the whole signer set
+    pub fn sign(&mut self, block_proposal: BlockProposal) {
+        let command = RunLoopCommand {
+            command: SignerCommand::Sign {
+                block_proposal,
+                is_taproot: false,
+                merkle_root: None,
+            },
+            reward_cycle: REWARD_CYCLE,
+        };
+        for signer in self
+            .signers
+            .iter_mut()
+            .filter(|signer| !self.offline.contains(&signer.signer_id))
+        {
+            signer.process_command(
+                &self.stacks_client,
+                CURRENT_REWARD_CYCLE,
+                Some(command.clone()),
+            );
+        }
+    }
+
+    /// Record the block in the db of every online signer, as a signer does on learning of a
+    /// block before any coordinator requests nonces for it. The signers submit it for
+    /// validation on their next status check.
+    pub fn receive_block(&mut self, block_proposal: &BlockProposal) {
+        for signer in self
+            .signers
+            .iter_mut()
+            .filter(|signer| !self.offline.contains(&signer.signer_id))
+        {
+            signer
+                .signer_db
+                .insert_block(&BlockInfo::from(block_proposal.clone()))
+                .expect("Failed to insert block in signer db");
+        }
+    }
+
+    /// Send a status check to the online signers
+    pub fn status_check(&mut self) {
+        self.deliver(SignerEvent::StatusCheck);
+    }
+
+    /// Deliver the messages written to stacker-db and validate the submitted blocks until the
+    /// signers stop writing messages and submitting blocks
+    pub fn settle(&mut self) {
+        for _ in 0..MAX_SETTLE_ROUNDS {
+            let delivered = self.deliver_written_messages();
+            let validated = self.validate_blocks();
+            if delivered == 0 && validated == 0 {
+                return;
+            }
+        }
+        panic!("The signers did not settle within {MAX_SETTLE_ROUNDS} rounds");
+    }
+
+    /// Deliver the messages written to stacker-db since the last delivery, one at a time and in
+    /// the order they were written. Returns the number of messages delivered.
+    pub fn deliver_written_messages(&mut self) -> usize {
+        let chunks = self
+            .fragments
+            .reassemble_chunks(self.node.take_written_chunks(), Instant::now());
+        let signer_set = u32::try_from(REWARD_CYCLE % 2).expect("infallible");
+        let mut delivered = 0;
+        for chunk in chunks {
+            let Ok(message) = decode_chunk::<SignerMessage>(&chunk.data) else {
+                // e.g. a slot cleared with an empty chunk
+                continue;
+            };
+            self.deliver(SignerEvent::SignerMessages(
+                signer_set,
+                vec![message.clone()],
+            ));
+            self.delivered.push((chunk.slot_id, message));
+            delivered += 1;
+        }
+        delivered
+    }
+
+    /// Have the stacks node find every block submitted for validation since the last call
+    /// valid. Returns the number of blocks validated.
+    pub fn validate_blocks(&mut self) -> usize {
+        let mut validated = HashSet::new();
+        for block in self.node.take_block_proposals() {
+            let signer_signature_hash = block.header.signer_signature_hash();
+            if !validated.insert(signer_signature_hash) {
+                continue;
+            }
+            self.deliver(SignerEvent::BlockValidationResponse(
+                BlockValidateResponse::Ok(BlockValidateOk {
+                    signer_signature_hash,
+                    cost: ExecutionCost::zero(),
+                    size: 0,
+                }),
+            ));
+        }
+        validated.len()
+    }
+
+    /// The operation results the signers returned since the last call
+    pub fn operation_results(&self) -> Vec<OperationResult> {
+        self.res_recv.try_iter().flatten().collect()
+    }
+
+    /// The slot of the first signer that broadcast the acceptance of the block, and the
+    /// signature it broadcast
+    pub fn accepted_block_response(
+        &self,
+        signer_signature_hash: &Sha512Trunc256Sum,
+    ) -> Option<(u32, ThresholdSignature)> {
+        self.delivered
+            .iter()
+            .find_map(|(slot_id, message)| match message {
+                SignerMessage::BlockResponse(BlockResponse::Accepted((hash, signature)))
+                    if hash == signer_signature_hash =>
+                {
+                    Some((*slot_id, signature.clone()))
+                }
+                _ => None,
+            })
+    }
+
+    /// Whether the signature is the signer set's signature accepting the block
+    pub fn verify_acceptance(
+        &self,
+        signer_signature_hash: &Sha512Trunc256Sum,
+        signature: &ThresholdSignature,
+    ) -> bool {
+        let block_vote = NakamotoBlockVote {
+            signer_signature_hash: *signer_signature_hash,
+            rejected: false,
+        };
+        signature
+            .0
+            .verify(&self.aggregate_key, &block_vote.serialize_to_vec())
+    }
+
+    /// Hand the event to every online signer, then let it run its next command
+    fn deliver(&mut self, event: SignerEvent<SignerMessage>) {
+        for signer in self
+            .signers
+            .iter_mut()
+            .filter(|signer| !self.offline.contains(&signer.signer_id))
+        {
+            signer.process_event(
+                &self.stacks_client,
+                Some(&event),
+                self.res_send.clone(),
+                CURRENT_REWARD_CYCLE,
+            );
+            signer.process_command(&self.stacks_client, CURRENT_REWARD_CYCLE, None);
+        }
+    }
+
+    fn online_signers(&self) -> impl Iterator<Item = &Signer> {
+        self.signers
+            .iter()
+            .filter(|signer| !self.offline.contains(&signer.signer_id))
+    }
+}
+
+/// Run a DKG round in memory with the first signer's coordinator and every signer's state
+/// machine, then share its outcome with the other coordinators. Returns the aggregate key.
+fn run_dkg(signers: &mut [Signer]) -> Point {
+    let (first_signer, other_signers) = signers.split_first_mut().expect("No signers");
+    let mut in_flight: Vec<Packet> = vec![first_signer
+        .coordinator
+        .start_dkg_round()
+        .expect("Failed to start DKG")];
+    for _ in 0..MAX_DKG_EXCHANGES {
+        let mut signer_packets = first_signer
+            .state_machine
+            .process_inbound_messages(&in_flight)
+            .expect("Failed to process DKG packets");
+        for signer in other_signers.iter_mut() {
+            signer_packets.extend(
+                signer
+                    .state_machine
+                    .process_inbound_messages(&in_flight)
+                    .expect("Failed to process DKG packets"),
+            );
+        }
+        let (coordinator_packets, results) = first_signer
+            .coordinator
+            .process_inbound_messages(&signer_packets)
+            .expect("Failed to coordinate DKG");
+        if let Some(OperationResult::Dkg(aggregate_key)) = results.first() {
+            for signer in other_signers.iter_mut() {
+                signer.coordinator.current_dkg_id = first_signer.coordinator.current_dkg_id;
+                signer.coordinator.party_polynomials =
+                    first_signer.coordinator.party_polynomials.clone();
+            }
+            return *aggregate_key;
+        }
+        in_flight = signer_packets;
+        in_flight.extend(coordinator_packets);
+    }
+    panic!("DKG did not complete within {MAX_DKG_EXCHANGES} message exchanges");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proposed_block_should_be_signed_and_accepted() {
+        let mut harness = SignerHarness::spawn();
+        let block_proposal = harness.propose_block();
+        let signer_signature_hash = block_proposal.block.header.signer_signature_hash();
+
+        // The coordinator requests nonces right away, and the signers respond once the stacks
+        // node validated the block
+        harness.sign(block_proposal);
+        harness.settle();
+
+        let (_, signature) = harness
+            .accepted_block_response(&signer_signature_hash)
+            .expect("No signer broadcast the acceptance of the block");
+        assert!(harness.verify_acceptance(&signer_signature_hash, &signature));
+        assert!(harness
+            .operation_results()
+            .iter()
+            .any(|result| matches!(result, OperationResult::Sign(_))));
+    }
+
+    #[test]
+    fn unresponsive_coordinator_should_be_failed_over() {
+        let mut harness = SignerHarness::spawn();
+        let coordinator_id = harness.coordinator_id();
+        harness.disconnect(coordinator_id);
+        let block_proposal = harness.propose_block();
+        let signer_signature_hash = block_proposal.block.header.signer_signature_hash();
+
+        // The signers validate the block, but its coordinator never requests nonces
+        harness.receive_block(&block_proposal);
+        harness.sign(block_proposal);
+        harness.status_check();
+        harness.settle();
+        harness.status_check();
+        harness.settle();
+        assert!(harness
+            .accepted_block_response(&signer_signature_hash)
+            .is_none());
+
+        // Once the signers waited twice the nonce timeout, the next coordinator takes over
+        harness.advance_clock(NONCE_TIMEOUT * 2 + Duration::from_secs(1));
+        harness.status_check();
+        let new_coordinator_id = harness.coordinator_id();
+        assert_ne!(new_coordinator_id, coordinator_id);
+        assert!(harness.online_signers().all(|signer| {
+            signer.coordinator_selector.get_coordinator().0 == new_coordinator_id
+        }));
+        harness.settle();
+
+        let (_, signature) = harness
+            .accepted_block_response(&signer_signature_hash)
+            .expect("No signer broadcast the acceptance of the block");
+        assert!(harness.verify_acceptance(&signer_signature_hash, &signature));
+    }
+}
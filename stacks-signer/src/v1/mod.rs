@@ -16,15 +16,25 @@
 
 /// The coordinator selector for the signer
 pub mod coordinator;
+/// An in-process signer set over a simulated stacks node, for integration tests
+#[cfg(test)]
+pub mod harness;
 /// The signer module for processing events
 pub mod signer;
 /// The state module for the signer
 pub mod signerdb;
+/// The in-memory simulation of a signer set
+pub mod simulate;
+/// The latency breakdown of signing rounds
+pub mod timings;
+pub mod vote_failure;
 
+use std::sync::atomic::AtomicBool;
 use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
 
 use libsigner::v1::messages::SignerMessage;
-use libsigner::SignerEventReceiver;
+use libsigner::{SignerEventReceiver, SignerSlotOwners};
 use slog::slog_info;
 use stacks_common::info;
 use wsts::state_machine::OperationResult;
@@ -48,6 +58,8 @@ pub struct SpawnedSigner {
     pub cmd_send: Sender<RunLoopCommand>,
     /// The result receiver for interacting with the running signer
     pub res_recv: Receiver<Vec<OperationResult>>,
+    /// Set to stop the running signer once its in-progress operations complete
+    pub shutdown_requested: Arc<AtomicBool>,
 }
 
 impl From<GlobalConfig> for SpawnedSigner {
@@ -56,12 +68,17 @@ impl From<GlobalConfig> for SpawnedSigner {
         info!("Starting signer with config: {}", config);
         let (cmd_send, cmd_recv) = channel();
         let (res_send, res_recv) = channel();
-        let ev = SignerEventReceiver::new(config.network.is_mainnet());
+        let slot_owners = SignerSlotOwners::default()
+            .with_rejected_chunk_callback(crate::monitoring::increment_rejected_stackerdb_chunks);
+        let mut ev = SignerEventReceiver::new(config.network_profile.mainnet);
+        ev.set_slot_owners(slot_owners.clone());
         #[cfg(feature = "monitoring_prom")]
         {
             crate::monitoring::start_serving_monitoring_metrics(config.clone()).ok();
         }
-        let runloop = RunLoop::new(config);
+        let mut runloop = RunLoop::new(config);
+        runloop.slot_owners = slot_owners;
+        let shutdown_requested = runloop.shutdown_requested.clone();
         let mut signer: libsigner::Signer<
             RunLoopCommand,
             Vec<OperationResult>,
@@ -74,6 +91,7 @@ impl From<GlobalConfig> for SpawnedSigner {
             running_signer,
             cmd_send,
             res_recv,
+            shutdown_requested,
         }
     }
 }
@@ -13,37 +13,45 @@
 //
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::fmt::Debug;
 use std::path::PathBuf;
 use std::sync::mpsc::Sender;
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use blockstack_lib::burnchains::Txid;
 use blockstack_lib::chainstate::burn::ConsensusHashExtensions;
 use blockstack_lib::chainstate::nakamoto::signer_set::NakamotoSigners;
 use blockstack_lib::chainstate::nakamoto::{NakamotoBlock, NakamotoBlockVote};
-use blockstack_lib::chainstate::stacks::boot::SIGNERS_VOTING_FUNCTION_NAME;
-use blockstack_lib::chainstate::stacks::StacksTransaction;
+use blockstack_lib::chainstate::stacks::boot::{
+    SIGNERS_VOTING_FUNCTION_NAME, SIGNERS_VOTING_MAX_BATCH_VOTES,
+};
+use blockstack_lib::chainstate::stacks::{StacksTransaction, ThresholdSignature};
 use blockstack_lib::net::api::postblock_proposal::BlockValidateResponse;
 use blockstack_lib::util_lib::db::Error as DBError;
-use hashbrown::HashSet;
+use hashbrown::{HashMap, HashSet};
 use libsigner::v1::messages::{
-    BlockRejection, BlockResponse, MessageSlotID, RejectCode, SignerMessage,
+    BlockRejection, BlockResponse, MessageSlotID, MessageTypePrefix, NackMessage, NackReason,
+    RejectCode, SignerMessage,
 };
-use libsigner::{BlockProposal, SignerEvent};
+use libsigner::{BlockProposal, SignerEntries, SignerEvent};
 use rand_core::OsRng;
+use rayon::prelude::*;
 use serde_derive::{Deserialize, Serialize};
-use slog::{slog_debug, slog_error, slog_info, slog_warn};
+use slog::{slog_debug, slog_warn};
 use stacks_common::codec::{read_next, StacksMessageCodec};
 use stacks_common::types::chainstate::{ConsensusHash, StacksAddress};
 use stacks_common::types::StacksEpochId;
+use stacks_common::util::get_epoch_time_secs;
 use stacks_common::util::hash::Sha512Trunc256Sum;
-use stacks_common::{debug, error, info, warn};
+use stacks_common::{debug, warn};
 use wsts::common::Signature;
+use wsts::curve::ecdsa;
 use wsts::curve::keys::PublicKey;
 use wsts::curve::point::Point;
 use wsts::curve::scalar::Scalar;
-use wsts::net::{Message, NonceRequest, Packet, SignatureShareRequest};
+use wsts::net::{DkgPublicShares, Message, NonceRequest, Packet, SignatureShareRequest};
 use wsts::state_machine::coordinator::fire::Coordinator as FireCoordinator;
 use wsts::state_machine::coordinator::{
     Config as CoordinatorConfig, Coordinator, State as CoordinatorState,
@@ -53,12 +61,55 @@ use wsts::state_machine::{OperationResult, SignError};
 use wsts::traits::Signer as _;
 use wsts::v2;
 
-use crate::client::{ClientError, SignerSlotID, StackerDB, StacksClient};
-use crate::config::SignerConfig;
+use crate::alerting::{AlertEvent, AlertNotifier};
+use crate::client::{
+    CandidateInfo, ClientError, QueuedMessage, RoundInfo, SendOutcome, SignerSlotID, StackerDB,
+    StacksClient,
+};
+use crate::config::{
+    BlockProposalFilter, GlobalConfig, MissingTransactionPolicy, SignerConfig, StaleNodeNackPolicy,
+};
+use crate::logging::SignerLogger;
+use crate::monitoring::{format_prometheus, SignerCounters, SignerMetrics, SignerStatus};
+use crate::recording::{EventRecorder, RecordedEntry, RecordedMessage};
 use crate::runloop::{RunLoopCommand, SignerCommand};
-use crate::v1::coordinator::CoordinatorSelector;
-use crate::v1::signerdb::SignerDb;
-use crate::Signer as SignerTrait;
+use crate::v1::coordinator::{CoordinatorMetadata, CoordinatorSelector};
+use crate::v1::signerdb::{RejectedRequest, SignerDb};
+use crate::v1::timings::{now_ms, SignRoundStep, SignRoundTimings, SignRoundWaits};
+use crate::{signer_debug, signer_error, signer_info, signer_warn, Signer as SignerTrait};
+
+/// The message signed with the signer's key by its self test
+const SELF_TEST_MESSAGE: &[u8] = b"stacks-signer self test";
+/// The maximum time to spend retrying a block validation submission while handling a nonce request
+const NONCE_REQUEST_RETRY_DEADLINE: Duration = Duration::from_secs(5);
+/// How long an undelivered round packet is kept if the round has no configured timeout
+const OUTBOUND_PACKET_DEFAULT_TTL: Duration = Duration::from_secs(60);
+/// How long an undelivered block response is kept. Miners may still act on a late response,
+/// so these outlive the signing round that produced them.
+const OUTBOUND_BLOCK_RESPONSE_TTL: Duration = Duration::from_secs(600);
+/// The maximum number of nonce requests cached for a block awaiting validation
+const MAX_PENDING_NONCE_REQUESTS_PER_BLOCK: usize = 16;
+/// The maximum time to spend retrying each write clearing our stacker-db slots at tenure end
+const TENURE_END_CLEAR_SLOTS_DEADLINE: Duration = Duration::from_secs(5);
+/// The DKG participation rate below which we warn that too few signers are live
+const DKG_PARTICIPATION_WARN_THRESHOLD: f64 = 0.7;
+/// The maximum number of packets from the round after ours held until we catch up with it
+const MAX_NEXT_ROUND_PACKETS: usize = 64;
+/// How long a packet from the round after ours is held before it is dropped
+const NEXT_ROUND_PACKET_TTL: Duration = Duration::from_secs(10);
+/// The maximum number of coordinator changes kept in the coordinator selection log
+const MAX_COORDINATOR_SELECTION_LOG_LEN: usize = 100;
+/// How long after its proposal an accepted block must still be unknown to the stacks node
+/// before its block response is broadcast again
+const BLOCK_REBROADCAST_MIN_AGE: Duration = Duration::from_secs(60);
+/// How often to look for accepted blocks whose block response should be broadcast again
+const BLOCK_REBROADCAST_INTERVAL: Duration = Duration::from_secs(30);
+/// The fewest block proposals submitted at once for which validation requests are sent
+/// concurrently, if parallel validation is enabled
+const PARALLEL_VALIDATION_MIN_BLOCKS: usize = 5;
+/// How many burn blocks the miner of a tenure is remembered for after the last block of the
+/// tenure we saw
+const TENURE_MINER_RETENTION_BURN_BLOCKS: u64 = 6;
 
 /// Additional Info about a proposed block
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -77,6 +128,20 @@ pub struct BlockInfo {
     nonce_request: Option<NonceRequest>,
     /// Whether this block is already being signed over
     pub signed_over: bool,
+    /// The time (in seconds since the epoch) at which the block was submitted to the stacks node for validation
+    proposed_at: Option<u64>,
+    /// The number of times we asked the stacks node to validate the block
+    #[serde(default)]
+    pub validation_attempts: u8,
+    /// When the block reached each step of its signing round
+    #[serde(default)]
+    pub timings: SignRoundTimings,
+    /// The aggregate signature over the block, if we coordinated the round that accepted it
+    #[serde(default)]
+    pub signature: Option<ThresholdSignature>,
+    /// Whether the stacks node is known to have the block since we accepted it
+    #[serde(default)]
+    pub confirmed: bool,
 }
 
 impl From<BlockProposal> for BlockInfo {
@@ -89,6 +154,11 @@ impl From<BlockProposal> for BlockInfo {
             valid: None,
             nonce_request: None,
             signed_over: false,
+            proposed_at: None,
+            validation_attempts: 0,
+            timings: SignRoundTimings::default(),
+            signature: None,
+            confirmed: false,
         }
     }
 }
@@ -101,10 +171,57 @@ impl BlockInfo {
         block_info
     }
 
+    /// Create a new BlockInfo for a block the signer set already voted on. It is marked as
+    /// signed over, so no signing round is started for it.
+    pub fn new_with_vote(block_proposal: BlockProposal, rejected: bool) -> Self {
+        let mut block_info = BlockInfo::from(block_proposal);
+        block_info.vote = Some(NakamotoBlockVote {
+            signer_signature_hash: block_info.signer_signature_hash(),
+            rejected,
+        });
+        block_info.signed_over = true;
+        block_info
+    }
+
     /// Return the block's signer signature hash
     pub fn signer_signature_hash(&self) -> Sha512Trunc256Sum {
         self.block.header.signer_signature_hash()
     }
+
+    /// Whether the block contents are valid, or None if they have not been validated yet
+    pub fn valid(&self) -> Option<bool> {
+        self.valid
+    }
+
+    /// Whether we asked the stacks node to validate the block and are still awaiting its response,
+    /// including when the request failed and is yet to be retried
+    pub fn is_awaiting_validation(&self) -> bool {
+        self.valid.is_none() && (self.proposed_at.is_some() || self.validation_attempts > 0)
+    }
+
+    /// Whether the block was validated and no signing round has been started for it yet
+    pub fn is_pending_signature(&self) -> bool {
+        self.valid == Some(true) && !self.signed_over
+    }
+
+    /// Whether we signed over and accepted the block, it was proposed more than `min_age` ago,
+    /// and the stacks node is yet to confirm it
+    pub fn needs_rebroadcast(&self, min_age: Duration) -> bool {
+        self.signed_over
+            && !self.confirmed
+            && self.vote.as_ref().map_or(false, |vote| !vote.rejected)
+            && self.proposed_at.map_or(false, |proposed_at| {
+                proposed_at < get_epoch_time_secs().saturating_sub(min_age.as_secs())
+            })
+    }
+
+    /// Whether the stacks node has failed to validate the block within the given timeout
+    pub fn validation_timed_out(&self, timeout: Duration) -> bool {
+        self.valid.is_none()
+            && self.proposed_at.map_or(false, |proposed_at| {
+                get_epoch_time_secs().saturating_sub(proposed_at) >= timeout.as_secs()
+            })
+    }
 }
 
 /// The specific operations that a signer can perform
@@ -116,6 +233,259 @@ pub enum Operation {
     Sign,
 }
 
+/// Counts of the signing round failures observed by a signer, by failure mode
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub struct SignErrorStats {
+    /// The number of signing rounds that timed out gathering nonces
+    pub nonce_timeouts: u64,
+    /// The number of signing rounds that did not gather enough signers
+    pub insufficient_signers: u64,
+    /// The number of signing rounds that failed to aggregate the signature
+    pub aggregator_errors: u64,
+}
+
+impl SignErrorStats {
+    /// Record the given sign error
+    pub fn record(&mut self, e: &SignError) {
+        let counter = match e {
+            SignError::NonceTimeout(..) => &mut self.nonce_timeouts,
+            SignError::InsufficientSigners(_) => &mut self.insufficient_signers,
+            SignError::Aggregator(_) => &mut self.aggregator_errors,
+        };
+        *counter = counter.saturating_add(1);
+    }
+}
+
+/// The signers observed taking part in the latest DKG round
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct DkgHistory {
+    /// The ID of the DKG round
+    pub dkg_id: u64,
+    /// The IDs of the signers that sent their `DkgPublicShares` in the round, sorted
+    pub participants: Vec<u32>,
+}
+
+impl DkgHistory {
+    /// Record the sender of the given public shares as a participant of its round.
+    /// Shares of a newer round start a new history; shares of an older round are ignored.
+    pub fn record_public_shares(&mut self, shares: &DkgPublicShares) {
+        if shares.dkg_id < self.dkg_id {
+            return;
+        }
+        if shares.dkg_id > self.dkg_id {
+            self.dkg_id = shares.dkg_id;
+            self.participants.clear();
+        }
+        if let Err(idx) = self.participants.binary_search(&shares.signer_id) {
+            self.participants.insert(idx, shares.signer_id);
+        }
+    }
+}
+
+/// How the round of an inbound packet relates to the signer's current round
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum PacketRound {
+    /// The packet belongs to a completed round
+    Stale,
+    /// The packet belongs to the current round, or starts a new one
+    Current,
+    /// The packet belongs to the round after ours, which we may be slightly behind on
+    Next,
+    /// The packet belongs to a round further ahead than the next one
+    Future,
+}
+
+/// Checks the rounds of inbound packets against the signer's DKG and signing rounds, so that
+/// packets of completed rounds (e.g. replayed from stacker-db after a restart) never reach the
+/// signer state machine. Packets of the round after ours are held until we catch up with it.
+#[derive(Debug, Default)]
+pub struct PacketRoundGuard {
+    /// The latest DKG round started by a `DkgBegin` packet
+    pub dkg_id: u64,
+    /// The signing round started by the latest `NonceRequest` packet, if any
+    pub sign_id: Option<u64>,
+    /// The packets of the round after ours, with the time they were received
+    next_round_packets: VecDeque<(Instant, Packet)>,
+}
+
+impl PacketRoundGuard {
+    /// Return the packets that belong to the current rounds, in order, followed by any held
+    /// packet whose round they started. Packets of the next round are held, and the others
+    /// dropped. Rounds are compared against the later of our latest DKG round and the given one.
+    pub fn filter_packets(
+        &mut self,
+        packets: &[Packet],
+        current_dkg_id: u64,
+        now: Instant,
+    ) -> Vec<Packet> {
+        self.dkg_id = self.dkg_id.max(current_dkg_id);
+        self.next_round_packets.retain(|(received_at, _)| {
+            now.saturating_duration_since(*received_at) < NEXT_ROUND_PACKET_TTL
+        });
+        let mut accepted = self.take_current_round_packets();
+        for packet in packets {
+            match self.packet_round(&packet.msg) {
+                PacketRound::Current => {
+                    let started_round = self.start_round(&packet.msg);
+                    accepted.push(packet.clone());
+                    if started_round {
+                        accepted.extend(self.take_current_round_packets());
+                    }
+                }
+                PacketRound::Next => {
+                    if self.next_round_packets.len() >= MAX_NEXT_ROUND_PACKETS {
+                        warn!("Too many packets held for the next round. Dropping the oldest.");
+                        self.next_round_packets.pop_front();
+                    }
+                    self.next_round_packets.push_back((now, packet.clone()));
+                }
+                round @ (PacketRound::Stale | PacketRound::Future) => {
+                    debug!("Dropping packet outside of the current rounds";
+                        "round" => ?round,
+                        "dkg_id" => self.dkg_id,
+                        "sign_id" => ?self.sign_id,
+                        "packet" => ?packet.msg,
+                    );
+                }
+            }
+        }
+        accepted
+    }
+
+    /// The number of packets held for the round after ours
+    pub fn num_next_round_packets(&self) -> usize {
+        self.next_round_packets.len()
+    }
+
+    /// How the round of the given message relates to ours. `DkgBegin` and `NonceRequest`
+    /// messages start rounds. Signing rounds are only compared by their `sign_id`, which miners
+    /// restart every reward cycle, so a `NonceRequest` always starts a new signing round.
+    pub fn packet_round(&self, msg: &Message) -> PacketRound {
+        let compare = |id: u64, current: u64| {
+            if id < current {
+                PacketRound::Stale
+            } else if id == current {
+                PacketRound::Current
+            } else if id == current.wrapping_add(1) {
+                PacketRound::Next
+            } else {
+                PacketRound::Future
+            }
+        };
+        let compare_sign_id = |sign_id: u64| match self.sign_id {
+            Some(current) if sign_id == current => PacketRound::Current,
+            Some(current) if sign_id == current.wrapping_add(1) => PacketRound::Next,
+            // Without the NonceRequest that started its round, the packet is of no use
+            Some(_) => PacketRound::Stale,
+            // We have yet to see the NonceRequest of any round
+            None => PacketRound::Next,
+        };
+        match msg {
+            Message::DkgBegin(msg) if msg.dkg_id < self.dkg_id => PacketRound::Stale,
+            Message::DkgBegin(_) | Message::NonceRequest(_) => PacketRound::Current,
+            Message::DkgPrivateBegin(msg) => compare(msg.dkg_id, self.dkg_id),
+            Message::DkgEndBegin(msg) => compare(msg.dkg_id, self.dkg_id),
+            Message::DkgEnd(msg) => compare(msg.dkg_id, self.dkg_id),
+            Message::DkgPublicShares(msg) => compare(msg.dkg_id, self.dkg_id),
+            Message::DkgPrivateShares(msg) => compare(msg.dkg_id, self.dkg_id),
+            Message::NonceResponse(msg) => compare_sign_id(msg.sign_id),
+            Message::SignatureShareRequest(msg) => compare_sign_id(msg.sign_id),
+            Message::SignatureShareResponse(msg) => compare_sign_id(msg.sign_id),
+        }
+    }
+
+    /// Advance our rounds if the message starts one. Returns whether it did.
+    fn start_round(&mut self, msg: &Message) -> bool {
+        match msg {
+            Message::DkgBegin(begin) if begin.dkg_id > self.dkg_id => {
+                self.dkg_id = begin.dkg_id;
+                true
+            }
+            Message::NonceRequest(request) if self.sign_id != Some(request.sign_id) => {
+                self.sign_id = Some(request.sign_id);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove the held packets that belong to the current rounds, dropping those that became
+    /// stale, and return the former in the order they were received
+    fn take_current_round_packets(&mut self) -> Vec<Packet> {
+        let mut current_round_packets = vec![];
+        let mut next_round_packets = VecDeque::with_capacity(self.next_round_packets.len());
+        for (received_at, packet) in self.next_round_packets.drain(..) {
+            match self.packet_round(&packet.msg) {
+                PacketRound::Current => current_round_packets.push(packet),
+                PacketRound::Next => next_round_packets.push_back((received_at, packet)),
+                PacketRound::Stale | PacketRound::Future => {}
+            }
+        }
+        self.next_round_packets = next_round_packets;
+        current_round_packets
+    }
+}
+
+/// The nonces of the signing round we received requests for. WSTS packets carry no timestamp,
+/// so the `sign_iter_id` of signing packets, which the coordinator increases with each iteration
+/// of a round, serves as their nonce.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct SignRoundNonces {
+    /// The ID of the signing round
+    pub sign_id: u64,
+    /// The nonce of the request that started the round
+    pub first_nonce: u64,
+    /// The nonce of the latest request of the round
+    pub latest_nonce: u64,
+}
+
+/// What to do about DKG given the votes cast in a round we already voted in
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+enum DkgRoundDecision {
+    /// A key reached the weight threshold and was approved. Do not trigger DKG.
+    Approved,
+    /// The round is still gathering votes. Wait for more votes to arrive.
+    Wait,
+    /// No key can reach the weight threshold in the round anymore. Trigger a new round.
+    NewRound,
+}
+
+impl DkgRoundDecision {
+    /// Decide what to do given the round's tallies, the tally of its best candidate key, the
+    /// weight threshold, and whether the round's vote weight has stopped increasing for longer
+    /// than the stall timeout
+    fn from_round_info(
+        round_info: &RoundInfo,
+        best_candidate: &CandidateInfo,
+        threshold_weight: u128,
+        stalled: bool,
+    ) -> Self {
+        if round_info.approved_aggregate_key.is_some() {
+            return Self::Approved;
+        }
+        if stalled {
+            // The remaining signers are not voting
+            return Self::NewRound;
+        }
+        // Until the contract records the total weight, any number of signers may still vote
+        let unvoted_weight = best_candidate
+            .total_weight
+            .map_or(u128::MAX, |total_weight| {
+                total_weight.saturating_sub(round_info.votes_weight)
+            });
+        if best_candidate
+            .candidate_weight
+            .saturating_add(unvoted_weight)
+            < threshold_weight
+        {
+            // The votes are split across keys such that none can be approved
+            Self::NewRound
+        } else {
+            Self::Wait
+        }
+    }
+}
+
 /// The Signer state
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub enum State {
@@ -144,10 +514,16 @@ pub struct Signer {
     pub mainnet: bool,
     /// The signer id
     pub signer_id: u32,
+    /// The human-readable name of the signer
+    pub signer_name: String,
     /// The signer slot ids for the signers in the reward cycle
     pub signer_slot_ids: Vec<SignerSlotID>,
     /// The addresses of other signers
     pub signer_addresses: Vec<StacksAddress>,
+    /// The signer ids of the signers in the reward cycle, keyed by their address
+    pub signer_address_ids: HashMap<StacksAddress, u32>,
+    /// The reward cycle of the reward set the signer address ids were last refreshed from
+    pub signer_address_ids_reward_cycle: Option<u64>,
     /// The signer slot ids for the signers in the NEXT reward cycle
     pub next_signer_slot_ids: Vec<SignerSlotID>,
     /// The addresses of the signers for the NEXT reward cycle
@@ -159,25 +535,120 @@ pub struct Signer {
     /// If estimating the tx fee, the max tx fee in uSTX to use when the epoch is pre Nakamoto (Epoch 3.0)
     /// If None, will not cap the fee.
     pub max_tx_fee_ustx: Option<u64>,
+    /// The maximum median transaction fee in uSTX of a block we are willing to sign
+    pub max_acceptable_median_fee_rate: u64,
+    /// The number of times a block proposal is submitted to the stacks node for validation
+    /// before it is rejected
+    pub max_validation_attempts: u8,
+    /// The number of signature share requests from the same coordinator rejected in a signing
+    /// round before the round is NACKed to the other signers
+    pub max_rejected_requests_per_round: u32,
     /// The coordinator info for the signer
     pub coordinator_selector: CoordinatorSelector,
     /// The approved key registered to the contract
     pub approved_aggregate_public_key: Option<Point>,
+    /// Whether the signer passed its self test. Until it does, it stays uninitialized and takes
+    /// no part in signing rounds
+    pub self_test_passed: bool,
     /// The current active miner's key (if we know it!)
     pub miner_key: Option<PublicKey>,
+    /// The miner of each tenure we saw blocks of and the burn height of the last of its blocks we
+    /// saw, by the tenure's consensus hash
+    pub tenure_miners: HashMap<ConsensusHash, (StacksAddress, u64)>,
     /// Signer DB path
     pub db_path: PathBuf,
     /// SignerDB for state management
     pub signer_db: SignerDb,
+    /// The time to wait for the stacks node to validate a proposed block before rejecting it
+    pub block_validation_timeout: Duration,
+    /// The signing round failures observed by this signer
+    pub sign_error_stats: SignErrorStats,
+    /// The events, rounds and messages processed by this signer, exported as metrics
+    pub counters: SignerCounters,
+    /// The logger attaching the signer ID and reward cycle to the records of this signer
+    pub logger: SignerLogger,
+    /// Records the messages this signer sends, if event recording is enabled
+    pub event_recorder: Option<EventRecorder>,
+    /// Alerts operators of critical events, if an alert webhook is configured
+    pub alert_notifier: Option<AlertNotifier>,
+    /// Declines block proposals that do not meet custom criteria before they are validated
+    pub block_proposal_filter: Option<Arc<dyn BlockProposalFilter>>,
+    /// The time after which a DKG round whose vote weight stopped increasing is considered stalled
+    pub dkg_round_stall_timeout: Duration,
+    /// The last observed (round, vote weight) of the DKG round we voted in, and when it was first observed
+    dkg_round_progress: Option<(u64, u128, Instant)>,
+    /// The time after which an undelivered DKG packet is no longer relevant
+    dkg_round_timeout: Duration,
+    /// The time after which an undelivered signing round packet is no longer relevant
+    sign_round_timeout: Duration,
+    /// The nonce requests received for blocks still awaiting validation, in the order they
+    /// were received, keyed by the blocks' signer signature hash
+    pub pending_nonce_requests: HashMap<Sha512Trunc256Sum, Vec<NonceRequest>>,
+    /// Whether to clear our stacker-db slots when our tenure ends
+    pub clear_stackerdb_on_tenure_end: bool,
+    /// Whether to submit batches of block proposals for validation concurrently
+    pub parallel_validation: bool,
+    /// How to vote on block proposals missing expected transactions
+    pub missing_transaction_policy: MissingTransactionPolicy,
+    /// How to handle stale node NACKs. If None, NACKs are not processed.
+    pub stale_node_nack_policy: Option<StaleNodeNackPolicy>,
+    /// The signers that took part in the latest DKG round
+    pub dkg_history: DkgHistory,
+    /// The voting round and aggregate key of the latest DKG round this signer saw complete
+    pub last_dkg_result: Option<(u64, Point)>,
+    /// The coordinators selected over time, with the time each was first seen selected.
+    /// Frequent changes point to network instability or misconfiguration.
+    pub coordinator_selection_log: VecDeque<(u32, Instant)>,
+    /// Keeps packets of other rounds than ours away from the signer state machine
+    pub packet_round_guard: PacketRoundGuard,
+    /// The nonces of the current signing round, if we received a request for one
+    pub sign_round_nonces: Option<SignRoundNonces>,
+    /// The latest nonce seen from each signer in the current signing round, keyed by signer ID,
+    /// with the time it was first seen
+    pub packet_window: HashMap<u32, (u64, Instant)>,
+    /// How long a signer's packets of a newer iteration than the latest one we received a
+    /// request for are accepted, as the request may still be on its way to us
+    pub packet_future_tolerance: Duration,
+    /// When the signer was asked to shut down, if it was. It starts no new operation from then on.
+    pub shutdown_started: Option<Instant>,
+    /// Whether the signer finished shutting down and saved its state
+    pub shutdown_complete: bool,
+    /// The time by which the signing round of each block we started signing must have
+    /// accumulated enough signatures, keyed by the blocks' signer signature hash
+    pub block_sign_deadline: HashMap<Sha512Trunc256Sum, Instant>,
+    /// The signer signature hashes of the blocks we already broadcast a block response for
+    pub broadcast_responses: HashSet<Sha512Trunc256Sum>,
+    /// The time we started waiting on another coordinator to request nonces for the valid
+    /// blocks awaiting a signature, if we are waiting
+    pub coordinator_wait_start: Option<Instant>,
+    /// The vote transactions the signers of the reward cycle wrote to stacker-db, keyed by their
+    /// origin address. Forwarded to signers that appear to have missed them.
+    pub known_vote_transactions: HashMap<StacksAddress, StacksTransaction>,
+    /// The transactions we last wrote to our stacker-db slot when forwarding known vote transactions
+    forwarded_txids: HashSet<Txid>,
+    /// The signers we received a NACK from, with the time we received the latest one
+    received_nacks: HashMap<u32, Instant>,
+    /// When we last considered reporting the NACKs we received to the other signers
+    last_nack_report: Instant,
+    /// The burn chain view sent along with our NACK reports, refreshed on each new burn block
+    pub coordinator_metadata: Option<CoordinatorMetadata>,
+    /// The time until which to back off, set once the NACK reports of the other signers show
+    /// that enough of the signer set is NACKing
+    pub back_off_until: Option<Instant>,
+    /// When we last looked for accepted blocks the stacks node is yet to confirm
+    last_block_rebroadcast: Instant,
+    /// How long the signing round we coordinate waited for its slowest nonce and share so far
+    sign_round_waits: SignRoundWaits,
 }
 
 impl std::fmt::Display for Signer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Cycle #{} Signer #{}(C:{})",
+            "Cycle #{} Signer #{} [{}](C:{})",
             self.reward_cycle,
             self.signer_id,
+            self.signer_name,
             self.coordinator_selector.get_coordinator().0,
         )
     }
@@ -203,6 +674,26 @@ impl SignerTrait<SignerMessage> for Signer {
         self.reward_cycle
     }
 
+    /// Clear our stacker-db slots if configured to, so that the next signer set does not
+    /// consume our stale transactions and packets
+    fn cleanup(&mut self) {
+        if !self.clear_stackerdb_on_tenure_end {
+            return;
+        }
+        signer_debug!(self.logger, "Clearing stacker-db slots at tenure end.");
+        if let Err(e) = self.stackerdb.clear_slots(TENURE_END_CLEAR_SLOTS_DEADLINE) {
+            signer_warn!(
+                self.logger,
+                "Failed to clear stacker-db slots at tenure end: {e:?}"
+            );
+        }
+    }
+
+    /// Apply the reloaded timeouts, fee caps and stacks node settings
+    fn apply_runtime_updates(&mut self, config: &GlobalConfig, stacks_client: &StacksClient) {
+        config.apply_runtime_updates(self, stacks_client);
+    }
+
     /// Process the event
     fn process_event(
         &mut self,
@@ -225,22 +716,45 @@ impl SignerTrait<SignerMessage> for Signer {
         if event_parity == Some(other_signer_parity) {
             return;
         }
+        if let Some(event) = event {
+            SignerCounters::increment(match event {
+                SignerEvent::BlockValidationResponse(_) => {
+                    &mut self.counters.block_validation_events
+                }
+                SignerEvent::SignerMessages(..) => &mut self.counters.signer_message_events,
+                SignerEvent::MinerMessages(..) => &mut self.counters.miner_message_events,
+                SignerEvent::StatusCheck => &mut self.counters.status_check_events,
+                SignerEvent::NewBurnBlock(_) => &mut self.counters.new_burn_block_events,
+            });
+        }
+        if !self.self_test_passed {
+            // Retried on every event until the signer is found able to sign
+            if let Err(e) = self.self_test(stacks_client) {
+                signer_error!(self.logger, "{e}. Not taking part in signing rounds.");
+                return;
+            }
+            self.self_test_passed = true;
+        }
+        self.flush_outbound_queue();
         if self.approved_aggregate_public_key.is_none() {
             if let Err(e) = self.refresh_dkg(stacks_client, res.clone(), current_reward_cycle) {
-                error!("{self}: failed to refresh DKG: {e}");
+                signer_error!(self.logger, "failed to refresh DKG: {e}");
             }
         }
         self.refresh_coordinator();
         if self.approved_aggregate_public_key.is_none() {
             if let Err(e) = self.refresh_dkg(stacks_client, res.clone(), current_reward_cycle) {
-                error!("{self}: failed to refresh DKG: {e}");
+                signer_error!(self.logger, "failed to refresh DKG: {e}");
             }
         }
         self.refresh_coordinator();
-        debug!("{self}: Processing event: {event:?}");
+        signer_debug!(self.logger, "Processing event: {event:?}");
         match event {
             Some(SignerEvent::BlockValidationResponse(block_validate_response)) => {
-                debug!("{self}: Received a block proposal result from the stacks node...");
+                signer_debug!(
+                    self.logger,
+                    "Received a block proposal result from the stacks node..."
+                );
                 self.handle_block_validate_response(
                     stacks_client,
                     block_validate_response,
@@ -250,11 +764,12 @@ impl SignerTrait<SignerMessage> for Signer {
             }
             Some(SignerEvent::SignerMessages(signer_set, messages)) => {
                 if *signer_set != self.stackerdb.get_signer_set() {
-                    debug!("{self}: Received a signer message for a reward cycle that does not belong to this signer. Ignoring...");
+                    signer_debug!(self.logger, "Received a signer message for a reward cycle that does not belong to this signer. Ignoring...");
                     return;
                 }
-                debug!(
-                    "{self}: Received {} messages from the other signers...",
+                signer_debug!(
+                    self.logger,
+                    "Received {} messages from the other signers...",
                     messages.len()
                 );
                 self.handle_signer_messages(stacks_client, res, messages, current_reward_cycle);
@@ -265,25 +780,37 @@ impl SignerTrait<SignerMessage> for Signer {
                 self.miner_key = Some(miner_key);
                 if current_reward_cycle != self.reward_cycle {
                     // There is not point in processing blocks if we are not the current reward cycle (we can never actually contribute to signing these blocks)
-                    debug!("{self}: Received a proposed block, but this signer's reward cycle is not the current one ({current_reward_cycle}). Ignoring...");
+                    signer_debug!(self.logger, "Received a proposed block, but this signer's reward cycle is not the current one ({current_reward_cycle}). Ignoring...");
                     return;
                 }
-                debug!(
-                    "{self}: Received {} messages from the miner",
+                signer_debug!(
+                    self.logger,
+                    "Received {} messages from the miner",
                     messages.len();
                     "miner_key" => ?miner_key,
                 );
                 self.handle_signer_messages(stacks_client, res, messages, current_reward_cycle);
             }
             Some(SignerEvent::StatusCheck) => {
-                debug!("{self}: Received a status check event.")
+                signer_debug!(self.logger, "Received a status check event.");
+                self.handle_status_check(stacks_client, current_reward_cycle);
             }
             Some(SignerEvent::NewBurnBlock(height)) => {
-                debug!("{self}: Receved a new burn block event for block height {height}")
+                signer_debug!(
+                    self.logger,
+                    "Receved a new burn block event for block height {height}"
+                );
+                if let Err(e) = self.update_signer_address_ids(stacks_client, self.reward_cycle) {
+                    signer_warn!(self.logger, "Failed to update signer address ids: {e:?}");
+                }
+                if self.stale_node_nack_policy.is_some() {
+                    self.refresh_coordinator_metadata(stacks_client);
+                }
+                self.forget_stale_tenure_miners(*height);
             }
             None => {
                 // No event. Do nothing.
-                debug!("{self}: No event received")
+                signer_debug!(self.logger, "No event received")
             }
         }
     }
@@ -297,12 +824,14 @@ impl SignerTrait<SignerMessage> for Signer {
         if let Some(command) = command {
             let reward_cycle = command.reward_cycle;
             if self.reward_cycle != reward_cycle {
-                warn!(
-                    "{self}: not registered for reward cycle {reward_cycle}. Ignoring command: {command:?}"
+                signer_warn!(
+                    self.logger,
+                    "not registered for reward cycle {reward_cycle}. Ignoring command: {command:?}"
                 );
             } else {
-                info!(
-                    "{self}: Queuing an external runloop command ({:?}): {command:?}",
+                signer_info!(
+                    self.logger,
+                    "Queuing an external runloop command ({:?}): {command:?}",
                     self.state_machine.public_keys.signers.get(&self.signer_id)
                 );
                 self.commands.push_back(command.command);
@@ -310,19 +839,126 @@ impl SignerTrait<SignerMessage> for Signer {
         }
         self.process_next_command(stacks_client, current_reward_cycle);
     }
+
+    fn signer_metrics(&self) -> SignerMetrics {
+        let count_blocks = |blocks: Result<Vec<_>, DBError>| {
+            blocks
+                .map(|blocks| blocks.len() as u64)
+                .unwrap_or_else(|e| {
+                    signer_warn!(self.logger, "Failed to count blocks for the metrics: {e:?}");
+                    0
+                })
+        };
+        SignerMetrics {
+            signer_id: self.signer_id,
+            reward_cycle: self.reward_cycle,
+            pending_blocks: count_blocks(
+                self.signer_db
+                    .get_blocks_awaiting_validation(self.reward_cycle),
+            ),
+            blocks_pending_signature: count_blocks(
+                self.signer_db
+                    .get_blocks_pending_signature(self.reward_cycle),
+            ),
+            blocks_being_signed: self.block_sign_deadline.len() as u64,
+            pending_nonce_requests: self
+                .pending_nonce_requests
+                .values()
+                .map(|requests| requests.len() as u64)
+                .sum(),
+            queued_messages: self.stackerdb.num_queued_messages() as u64,
+            known_vote_transactions: self.known_vote_transactions.len() as u64,
+            dkg_participation_rate: self.compute_dkg_participation_rate(),
+            sign_nonce_timeouts: self.sign_error_stats.nonce_timeouts,
+            sign_insufficient_signers: self.sign_error_stats.insufficient_signers,
+            sign_aggregator_errors: self.sign_error_stats.aggregator_errors,
+            counters: self.counters,
+        }
+    }
+
+    fn signer_status(&self) -> SignerStatus {
+        let metrics = self.signer_metrics();
+        let coordinator_id = self.coordinator_selector.get_coordinator().0;
+        let state = match &self.state {
+            State::Uninitialized => "uninitialized".to_string(),
+            State::Idle => "idle".to_string(),
+            State::OperationInProgress(Operation::Dkg) => "dkg".to_string(),
+            State::OperationInProgress(Operation::Sign) => "sign".to_string(),
+        };
+        SignerStatus {
+            signer_id: self.signer_id,
+            reward_cycle: self.reward_cycle,
+            state,
+            coordinator_id,
+            is_coordinator: coordinator_id == self.signer_id,
+            cached_blocks: metrics
+                .pending_blocks
+                .saturating_add(metrics.blocks_pending_signature),
+            queued_commands: self.commands.len() as u64,
+            last_dkg_round: self.coordinator.current_dkg_id,
+            has_aggregate_key: self.approved_aggregate_public_key.is_some(),
+        }
+    }
+
+    /// Wait for the in-progress DKG or signing round to complete, as the events processed in the
+    /// meantime drive it, then save the signer state. Gives up waiting once `timeout` has elapsed
+    /// since the first call.
+    fn graceful_shutdown(&mut self, timeout: Duration) -> bool {
+        if self.shutdown_complete {
+            return true;
+        }
+        let shutdown_started = *self.shutdown_started.get_or_insert_with(Instant::now);
+        if let State::OperationInProgress(operation) = &self.state {
+            if shutdown_started.elapsed() < timeout {
+                signer_debug!(
+                    self.logger,
+                    "Waiting for the in-progress operation to complete before shutting down";
+                    "operation" => ?operation
+                );
+                return false;
+            }
+            signer_warn!(
+                self.logger,
+                "Timed out waiting for the in-progress operation to complete. Shutting down anyway.";
+                "operation" => ?operation,
+                "timeout" => ?timeout
+            );
+        }
+        self.flush_outbound_queue();
+        if let Err(e) = self.save_signer_state() {
+            signer_error!(
+                self.logger,
+                "Failed to save the signer state before shutting down";
+                "error" => %e
+            );
+        }
+        signer_info!(self.logger, "Ready to shut down");
+        self.shutdown_complete = true;
+        true
+    }
 }
 
 impl Signer {
     /// Attempt to process the next command in the queue, and update state accordingly
     fn process_next_command(&mut self, stacks_client: &StacksClient, current_reward_cycle: u64) {
+        if self.shutdown_started.is_some() {
+            signer_debug!(
+                self.logger,
+                "Shutting down. Will not start a new operation."
+            );
+            return;
+        }
         match &self.state {
             State::Uninitialized => {
                 // We cannot process any commands until we have restored our state
-                warn!("{self}: Cannot process commands until state is restored. Waiting...");
+                signer_warn!(
+                    self.logger,
+                    "Cannot process commands until state is restored. Waiting..."
+                );
             }
             State::Idle => {
                 let Some(command) = self.commands.front() else {
-                    debug!("{self}: Nothing to process. Waiting for command...");
+                    signer_debug!(self.logger, "Nothing to process. Waiting for command...");
                     return;
                 };
                 let coordinator_id = if matches!(command, SignerCommand::Dkg) {
@@ -332,9 +968,11 @@ impl Signer {
                     self.get_coordinator_sign(current_reward_cycle).0
                 };
                 if coordinator_id != Some(self.signer_id) {
-                    debug!(
-                                "{self}: Coordinator is {coordinator_id:?}. Will not process any commands...",
-                            );
+                    signer_debug!(
+                        self.logger,
+                        "Not the coordinator. Will not process any commands...";
+                        "coordinator_id" => ?coordinator_id
+                    );
                     return;
                 }
                 let command = self
@@ -345,9 +983,11 @@ impl Signer {
             }
             State::OperationInProgress(op) => {
                 // We cannot execute the next command until the current one is finished...
-                debug!(
-                    "{self}: Waiting for {op:?} operation to finish. Coordinator state = {:?}",
-                    self.coordinator.state
+                signer_debug!(
+                    self.logger,
+                    "Waiting for the operation to finish...";
+                    "operation" => ?op,
+                    "coordinator_state" => ?self.coordinator.state
                 );
             }
         }
@@ -359,9 +999,9 @@ impl Signer {
     fn get_coordinator_sign(&self, current_reward_cycle: u64) -> (Option<u32>, PublicKey) {
         if self.reward_cycle == current_reward_cycle {
             let Some(ref cur_miner) = self.miner_key else {
-                error!(
-                    "Signer #{}: Could not lookup current miner while in active reward cycle",
-                    self.signer_id
+                signer_error!(
+                    self.logger,
+                    "Could not lookup current miner while in active reward cycle"
                 );
                 let selected = self.coordinator_selector.get_coordinator();
                 return (Some(selected.0), selected.1);
@@ -399,8 +1039,9 @@ impl Signer {
                 let coordinator_pubkey = if Self::is_dkg_message(&packet.msg) {
                     self.get_coordinator_dkg().1
                 } else {
-                    debug!(
-                        "{self}: Received a non-DKG message in the DKG message queue. Ignoring it."
+                    signer_debug!(
+                        self.logger,
+                        "Received a non-DKG message in the DKG message queue. Ignoring it."
                     );
                     return None;
                 };
@@ -409,8 +1050,9 @@ impl Signer {
             .collect::<Vec<_>>();
         // We successfully read stackerdb so we are no longer uninitialized
         self.state = State::Idle;
-        debug!(
-            "{self}: Processing {} DKG messages from stackerdb: {ordered_packets:?}",
+        signer_debug!(
+            self.logger,
+            "Processing {} DKG messages from stackerdb: {ordered_packets:?}",
             ordered_packets.len()
         );
         self.handle_packets(stacks_client, res, &ordered_packets, current_reward_cycle);
@@ -421,6 +1063,7 @@ impl Signer {
 impl From<SignerConfig> for Signer {
     fn from(signer_config: SignerConfig) -> Self {
         let mut stackerdb = StackerDB::from(&signer_config);
+        let logger = SignerLogger::new(signer_config.signer_id, signer_config.reward_cycle);
 
         let num_signers = signer_config
             .signer_entries
@@ -439,87 +1082,408 @@ impl From<SignerConfig> for Signer {
             .get_dkg_threshold()
             .expect("FATAL: Too many key ids to fit in a u32");
 
+        let dkg_round_timeout = round_timeout(&[
+            signer_config.dkg_public_timeout,
+            signer_config.dkg_private_timeout,
+            signer_config.dkg_end_timeout,
+        ]);
+        let sign_round_timeout =
+            round_timeout(&[signer_config.nonce_timeout, signer_config.sign_timeout]);
+
         let coordinator_config = CoordinatorConfig {
-            threshold,
-            dkg_threshold,
-            num_signers,
-            num_keys,
-            message_private_key: signer_config.ecdsa_private_key,
             dkg_public_timeout: signer_config.dkg_public_timeout,
             dkg_private_timeout: signer_config.dkg_private_timeout,
             dkg_end_timeout: signer_config.dkg_end_timeout,
             nonce_timeout: signer_config.nonce_timeout,
             sign_timeout: signer_config.sign_timeout,
-            signer_key_ids: signer_config.signer_entries.coordinator_key_ids,
-            signer_public_keys: signer_config.signer_entries.signer_public_keys,
+            ..new_coordinator_config(
+                &signer_config.signer_entries,
+                threshold,
+                dkg_threshold,
+                signer_config.ecdsa_private_key,
+            )
         };
 
+        let signer_db =
+            SignerDb::new(&signer_config.db_path).expect("Failed to connect to signer Db");
+        // A saved DKG state computed with different signing parameters is unusable
+        let config_drift = signer_db
+            .record_coordinator_config(signer_config.reward_cycle, &coordinator_config)
+            .unwrap_or_else(|e| {
+                signer_warn!(
+                    logger,
+                    "Failed to record the coordinator configuration in signer db";
+                    "error" => ?e,
+                );
+                None
+            });
+        if let Some(previous) = &config_drift {
+            signer_warn!(
+                logger,
+                "Coordinator configuration changed since the last run. Discarding the saved DKG state and triggering a fresh DKG.";
+                "previous_threshold" => previous.threshold,
+                "previous_num_signers" => previous.num_signers,
+                "previous_num_keys" => previous.num_keys,
+                "threshold" => threshold,
+                "num_signers" => num_signers,
+                "num_keys" => num_keys,
+            );
+        }
+
         let coordinator = FireCoordinator::new(coordinator_config);
         let coordinator_selector =
             CoordinatorSelector::from(signer_config.signer_entries.public_keys.clone());
+        let initial_coordinator_id = coordinator_selector.get_coordinator().0;
 
-        debug!(
-            "Reward cycle #{} Signer #{}: initial coordinator is signer {}",
-            signer_config.reward_cycle,
-            signer_config.signer_id,
-            coordinator_selector.get_coordinator().0
+        signer_debug!(
+            logger,
+            "Initial coordinator is signer {initial_coordinator_id}"
         );
-        let signer_db =
-            SignerDb::new(&signer_config.db_path).expect("Failed to connect to signer Db");
+        // Pick up the messages we failed to deliver before a restart
+        match signer_db.get_broadcasts(signer_config.reward_cycle) {
+            Ok(broadcasts) => {
+                for queued in broadcasts {
+                    stackerdb.queue_message(queued);
+                }
+            }
+            Err(e) => signer_warn!(
+                logger,
+                "Failed to load queued messages from signer db";
+                "error" => ?e,
+            ),
+        }
 
-        let mut state_machine = SignerStateMachine::new(
+        let mut state_machine = new_state_machine(
+            &signer_config.signer_entries,
             threshold,
-            num_signers,
-            num_keys,
             signer_config.signer_id,
             signer_config.key_ids,
             signer_config.ecdsa_private_key,
-            signer_config.signer_entries.public_keys,
         );
 
-        if let Some(state) = load_encrypted_signer_state(
-            &mut stackerdb,
-            signer_config.signer_slot_id,
-            &state_machine.network_private_key,
-        ).or_else(|err| {
-                warn!("Failed to load encrypted signer state from StackerDB, falling back to SignerDB: {err}");
-                load_encrypted_signer_state(
-                    &signer_db,
-                    signer_config.reward_cycle,
-                    &state_machine.network_private_key)
-            }).expect("Failed to load encrypted signer state from both StackerDB and SignerDB") {
-            state_machine.signer = state;
-        };
+        // Start over with a fresh state machine if the signing parameters drifted
+        if config_drift.is_none() {
+            if let Some(state) = load_encrypted_signer_state(
+                &mut stackerdb,
+                signer_config.signer_slot_id,
+                &state_machine.network_private_key,
+            ).or_else(|err| {
+                    signer_warn!(
+                        logger,
+                        "Failed to load encrypted signer state from StackerDB, falling back to SignerDB";
+                        "error" => %err,
+                    );
+                    load_encrypted_signer_state(
+                        &signer_db,
+                        signer_config.reward_cycle,
+                        &state_machine.network_private_key)
+                }).expect("Failed to load encrypted signer state from both StackerDB and SignerDB") {
+                state_machine.signer = state;
+            };
+        }
+
+        let mut commands = VecDeque::new();
+        if config_drift.is_some()
+            && coordinator_selector.get_coordinator().0 == signer_config.signer_id
+        {
+            commands.push_back(SignerCommand::Dkg);
+        }
+
+        crate::monitoring::update_signer_info(
+            &signer_config.signer_name,
+            signer_config.signer_id,
+            signer_config.reward_cycle,
+        );
 
         Self {
             coordinator,
             state_machine,
             state: State::Uninitialized,
-            commands: VecDeque::new(),
+            commands,
             stackerdb,
-            mainnet: signer_config.mainnet,
+            mainnet: signer_config.network.mainnet,
             signer_id: signer_config.signer_id,
+            signer_name: signer_config.signer_name,
             signer_addresses: signer_config
                 .signer_entries
                 .signer_ids
-                .into_keys()
+                .keys()
+                .copied()
                 .collect(),
+            signer_address_ids: signer_config.signer_entries.signer_ids,
+            signer_address_ids_reward_cycle: None,
             signer_slot_ids: signer_config.signer_slot_ids.clone(),
             next_signer_slot_ids: vec![],
             next_signer_addresses: vec![],
             reward_cycle: signer_config.reward_cycle,
             tx_fee_ustx: signer_config.tx_fee_ustx,
             max_tx_fee_ustx: signer_config.max_tx_fee_ustx,
+            max_acceptable_median_fee_rate: signer_config.max_acceptable_median_fee_rate,
+            max_validation_attempts: signer_config.max_validation_attempts,
+            max_rejected_requests_per_round: signer_config.max_rejected_requests_per_round,
             coordinator_selector,
             approved_aggregate_public_key: None,
+            self_test_passed: false,
             miner_key: None,
+            tenure_miners: HashMap::new(),
             db_path: signer_config.db_path,
             signer_db,
+            block_validation_timeout: signer_config.block_validation_timeout,
+            sign_error_stats: SignErrorStats::default(),
+            counters: SignerCounters::default(),
+            logger,
+            event_recorder: signer_config.event_recorder,
+            alert_notifier: signer_config.alert_notifier,
+            block_proposal_filter: signer_config.block_proposal_filter,
+            dkg_round_stall_timeout: signer_config.dkg_round_stall_timeout,
+            dkg_round_progress: None,
+            dkg_round_timeout,
+            sign_round_timeout,
+            pending_nonce_requests: HashMap::new(),
+            clear_stackerdb_on_tenure_end: signer_config.clear_stackerdb_on_tenure_end,
+            parallel_validation: signer_config.parallel_validation,
+            missing_transaction_policy: signer_config.missing_transaction_policy,
+            stale_node_nack_policy: signer_config.stale_node_nack_policy,
+            dkg_history: DkgHistory::default(),
+            last_dkg_result: None,
+            coordinator_selection_log: VecDeque::from([(initial_coordinator_id, Instant::now())]),
+            packet_round_guard: PacketRoundGuard::default(),
+            sign_round_nonces: None,
+            packet_window: HashMap::new(),
+            packet_future_tolerance: signer_config.packet_future_tolerance,
+            shutdown_started: None,
+            shutdown_complete: false,
+            block_sign_deadline: HashMap::new(),
+            broadcast_responses: HashSet::new(),
+            coordinator_wait_start: None,
+            known_vote_transactions: HashMap::new(),
+            forwarded_txids: HashSet::new(),
+            received_nacks: HashMap::new(),
+            last_nack_report: Instant::now(),
+            coordinator_metadata: None,
+            back_off_until: None,
+            last_block_rebroadcast: Instant::now(),
+            sign_round_waits: SignRoundWaits::default(),
         }
     }
 }
 
+/// The configuration of the WSTS coordinator of a signer of the given signer set, without round
+/// timeouts. `message_private_key` is the signer's key to sign its packets with.
+pub fn new_coordinator_config(
+    signer_entries: &SignerEntries,
+    threshold: u32,
+    dkg_threshold: u32,
+    message_private_key: Scalar,
+) -> CoordinatorConfig {
+    CoordinatorConfig {
+        threshold,
+        dkg_threshold,
+        num_signers: signer_entries
+            .count_signers()
+            .expect("FATAL: Too many registered signers to fit in a u32"),
+        num_keys: signer_entries
+            .count_keys()
+            .expect("FATAL: Too many key ids to fit in a u32"),
+        message_private_key,
+        dkg_public_timeout: None,
+        dkg_private_timeout: None,
+        dkg_end_timeout: None,
+        nonce_timeout: None,
+        sign_timeout: None,
+        signer_key_ids: signer_entries.coordinator_key_ids.clone(),
+        signer_public_keys: signer_entries.signer_public_keys.clone(),
+    }
+}
+
+/// The WSTS state machine of the given signer of the given signer set, owning the given key IDs
+pub fn new_state_machine(
+    signer_entries: &SignerEntries,
+    threshold: u32,
+    signer_id: u32,
+    key_ids: Vec<u32>,
+    private_key: Scalar,
+) -> SignerStateMachine<v2::Signer> {
+    SignerStateMachine::new(
+        threshold,
+        signer_entries
+            .count_signers()
+            .expect("FATAL: Too many registered signers to fit in a u32"),
+        signer_entries
+            .count_keys()
+            .expect("FATAL: Too many key ids to fit in a u32"),
+        signer_id,
+        key_ids,
+        private_key,
+        signer_entries.public_keys.clone(),
+    )
+}
+
+/// Whether two nonce requests belong to the same signing round
+fn same_signing_round(a: &NonceRequest, b: &NonceRequest) -> bool {
+    (a.dkg_id, a.sign_id, a.sign_iter_id) == (b.dkg_id, b.sign_id, b.sign_iter_id)
+}
+
+/// Add a nonce request to the requests cached for a block, replacing the cached request of
+/// the same signing round if any. If too many requests are cached, the oldest is dropped and returned.
+fn push_pending_nonce_request(
+    nonce_requests: &mut Vec<NonceRequest>,
+    nonce_request: &NonceRequest,
+) -> Option<NonceRequest> {
+    if let Some(cached) = nonce_requests
+        .iter_mut()
+        .find(|request| same_signing_round(request, nonce_request))
+    {
+        *cached = nonce_request.clone();
+        return None;
+    }
+    let dropped = if nonce_requests.len() >= MAX_PENDING_NONCE_REQUESTS_PER_BLOCK {
+        Some(nonce_requests.remove(0))
+    } else {
+        None
+    };
+    nonce_requests.push(nonce_request.clone());
+    dropped
+}
+
+/// The key identifying an outbound packet: its message type, the signer it originates from
+/// (None for coordinator messages), and its (DKG, sign, sign iteration) round ids
+fn outbound_message_key(msg: &Message) -> (MessageTypePrefix, Option<u32>, (u64, u64, u64)) {
+    let (signer_id, round) = match msg {
+        Message::DkgBegin(msg) => (None, (msg.dkg_id, 0, 0)),
+        Message::DkgPrivateBegin(msg) => (None, (msg.dkg_id, 0, 0)),
+        Message::DkgEndBegin(msg) => (None, (msg.dkg_id, 0, 0)),
+        Message::DkgEnd(msg) => (Some(msg.signer_id), (msg.dkg_id, 0, 0)),
+        Message::DkgPublicShares(msg) => (Some(msg.signer_id), (msg.dkg_id, 0, 0)),
+        Message::DkgPrivateShares(msg) => (Some(msg.signer_id), (msg.dkg_id, 0, 0)),
+        Message::NonceRequest(msg) => (None, (msg.dkg_id, msg.sign_id, msg.sign_iter_id)),
+        Message::NonceResponse(msg) => (
+            Some(msg.signer_id),
+            (msg.dkg_id, msg.sign_id, msg.sign_iter_id),
+        ),
+        Message::SignatureShareRequest(msg) => (None, (msg.dkg_id, msg.sign_id, msg.sign_iter_id)),
+        Message::SignatureShareResponse(msg) => (
+            Some(msg.signer_id),
+            (msg.dkg_id, msg.sign_id, msg.sign_iter_id),
+        ),
+    };
+    (MessageTypePrefix::from(msg), signer_id, round)
+}
+
+/// The total of the given round phase timeouts, or a default if none is configured
+fn round_timeout(phase_timeouts: &[Option<Duration>]) -> Duration {
+    let configured: Vec<_> = phase_timeouts.iter().flatten().collect();
+    if configured.is_empty() {
+        OUTBOUND_PACKET_DEFAULT_TTL
+    } else {
+        configured.into_iter().sum()
+    }
+}
+
 impl Signer {
+    /// Use the DKG and signing round phase timeouts of the given configuration for the rounds
+    /// started from now on
+    pub fn update_round_timeouts(&mut self, config: &GlobalConfig) {
+        let coordinator_config = &mut self.coordinator.config;
+        coordinator_config.dkg_public_timeout = config.dkg_public_timeout;
+        coordinator_config.dkg_private_timeout = config.dkg_private_timeout;
+        coordinator_config.dkg_end_timeout = config.dkg_end_timeout;
+        coordinator_config.nonce_timeout = config.nonce_timeout;
+        coordinator_config.sign_timeout = config.sign_timeout;
+        self.dkg_round_timeout = round_timeout(&[
+            config.dkg_public_timeout,
+            config.dkg_private_timeout,
+            config.dkg_end_timeout,
+        ]);
+        self.sign_round_timeout = round_timeout(&[config.nonce_timeout, config.sign_timeout]);
+    }
+
+    /// Check the signer can take part in signing rounds before it joins one: its key must sign
+    /// messages the signer set can verify, its signing round must be the one registered for its
+    /// address and stacker-db slot, and the stacks node must be reachable.
+    pub fn self_test(&self, stacks_client: &StacksClient) -> Result<(), SignerError> {
+        let signer_id = self.state_machine.signer_id;
+        let private_key = &self.state_machine.network_private_key;
+        let message_hash = Sha512Trunc256Sum::from_data(SELF_TEST_MESSAGE);
+        let signature = ecdsa::Signature::new(&message_hash.0, private_key).map_err(|e| {
+            SignerError::SelfTestFailed(format!("Failed to sign a test message: {e:?}"))
+        })?;
+        let Some(public_key) = self.state_machine.public_keys.signers.get(&signer_id) else {
+            return Err(SignerError::SelfTestFailed(format!(
+                "No public key is registered for signer #{signer_id}"
+            )));
+        };
+        if !signature.verify(&message_hash.0, public_key) {
+            return Err(SignerError::SelfTestFailed(format!(
+                "The private key does not match the public key registered for signer #{signer_id}"
+            )));
+        }
+        if signer_id != self.signer_id {
+            return Err(SignerError::SelfTestFailed(format!(
+                "The signing round is for signer #{signer_id}, but the signer is #{}",
+                self.signer_id
+            )));
+        }
+        let signer_address = stacks_client.get_signer_address();
+        if self.signer_address_ids.get(signer_address) != Some(&signer_id) {
+            return Err(SignerError::SelfTestFailed(format!(
+                "Signer #{signer_id} is not registered for address {signer_address}"
+            )));
+        }
+        let signer_slot_id = self.stackerdb.get_signer_slot_id();
+        if !self.signer_slot_ids.contains(&signer_slot_id) {
+            return Err(SignerError::SelfTestFailed(format!(
+                "Stacker-db slot {} is not a slot of the signer set",
+                signer_slot_id.0
+            )));
+        }
+        stacks_client.get_peer_info().map_err(|e| {
+            SignerError::SelfTestFailed(format!("The stacks node is unreachable: {e}"))
+        })?;
+        Ok(())
+    }
+
+    /// Return the signing round failure counts observed by this signer
+    pub fn metrics(&self) -> SignErrorStats {
+        self.sign_error_stats
+    }
+
+    /// Export a snapshot of this signer's state in the Prometheus text format
+    pub fn metrics_export_prometheus(&self) -> String {
+        format_prometheus(&[self.signer_metrics()])
+    }
+
+    /// Re-fetch the reward set for the given reward cycle and update the signer address to id mapping.
+    /// The reward set of a reward cycle does not change once known, so it is only fetched once per cycle.
+    pub fn update_signer_address_ids(
+        &mut self,
+        stacks_client: &StacksClient,
+        reward_cycle: u64,
+    ) -> Result<(), ClientError> {
+        if self.signer_address_ids_reward_cycle == Some(reward_cycle) {
+            return Ok(());
+        }
+        let Some(signers) = stacks_client.get_reward_set_signers(reward_cycle)? else {
+            signer_debug!(self.logger, "No reward set signers found for reward cycle {reward_cycle}. Keeping existing signer address ids.");
+            return Ok(());
+        };
+        let signer_entries = SignerEntries::parse(self.mainnet, &signers).map_err(|e| {
+            ClientError::MalformedContractData(format!("Failed to parse reward set: {e:?}"))
+        })?;
+        if signer_entries.signer_ids != self.signer_address_ids {
+            signer_info!(
+                self.logger,
+                "Signer address ids changed for reward cycle {reward_cycle}. Updating.";
+                "old_num_signers" => self.signer_address_ids.len(),
+                "new_num_signers" => signer_entries.signer_ids.len(),
+            );
+            self.signer_addresses = signer_entries.signer_ids.keys().copied().collect();
+            self.signer_address_ids = signer_entries.signer_ids;
+        }
+        self.signer_address_ids_reward_cycle = Some(reward_cycle);
+        Ok(())
+    }
+
     /// Refresh the coordinator selector
     pub fn refresh_coordinator(&mut self) {
         // TODO: do not use an empty consensus hash
@@ -529,8 +1493,9 @@ impl Signer {
             .coordinator_selector
             .refresh_coordinator(&pox_consensus_hash);
         if old_coordinator_id != updated_coordinator_id {
-            debug!(
-                "{self}: Coordinator updated. Resetting state to Idle.";
+            signer_debug!(
+                self.logger,
+                "Coordinator updated. Resetting state to Idle.";
                 "old_coordinator_id" => {old_coordinator_id},
                 "updated_coordinator_id" => {updated_coordinator_id},
                 "pox_consensus_hash" => %pox_consensus_hash
@@ -538,64 +1503,320 @@ impl Signer {
             self.coordinator.state = CoordinatorState::Idle;
             self.state = State::Idle;
         }
+        self.track_coordinator_selection_history();
     }
 
-    /// Finish an operation and update the coordinator selector accordingly
-    fn finish_operation(&mut self) {
-        self.state = State::Idle;
-        self.coordinator_selector.last_message_time = None;
+    /// Record the currently selected coordinator in the coordinator selection log if it differs
+    /// from the last one recorded
+    pub fn track_coordinator_selection_history(&mut self) {
+        self.record_coordinator_selection(Instant::now());
     }
 
-    /// Update operation
-    fn update_operation(&mut self, operation: Operation) {
-        self.state = State::OperationInProgress(operation);
-        self.coordinator_selector.last_message_time = Some(Instant::now());
+    fn record_coordinator_selection(&mut self, now: Instant) {
+        let coordinator_id = self.coordinator_selector.get_coordinator().0;
+        if self
+            .coordinator_selection_log
+            .back()
+            .is_some_and(|(last_coordinator_id, _)| *last_coordinator_id == coordinator_id)
+        {
+            return;
+        }
+        if self.coordinator_selection_log.len() >= MAX_COORDINATOR_SELECTION_LOG_LEN {
+            self.coordinator_selection_log.pop_front();
+        }
+        self.coordinator_selection_log
+            .push_back((coordinator_id, now));
     }
 
-    /// Execute the given command and update state accordingly
-    fn execute_command(&mut self, stacks_client: &StacksClient, command: &SignerCommand) {
-        match command {
-            SignerCommand::Dkg => {
-                crate::monitoring::increment_commands_processed("dkg");
-                if self.approved_aggregate_public_key.is_some() {
-                    debug!("Reward cycle #{} Signer #{}: Already have an aggregate key. Ignoring DKG command.", self.reward_cycle, self.signer_id);
-                    return;
-                }
-                let vote_round = match stacks_client.get_last_round(self.reward_cycle) {
-                    Ok(last_round) => last_round,
-                    Err(e) => {
-                        error!("{self}: Unable to perform DKG. Failed to get last round from stacks node: {e:?}");
-                        return;
-                    }
-                };
-                // The dkg id will increment internally following "start_dkg_round" so do not increment it here
-                self.coordinator.current_dkg_id = vote_round.unwrap_or(0);
-                info!(
-                    "{self}: Starting DKG vote";
-                    "round" => self.coordinator.current_dkg_id.wrapping_add(1),
-                    "cycle" => self.reward_cycle,
-                );
-                match self.coordinator.start_dkg_round() {
-                    Ok(msg) => {
-                        let ack = self.stackerdb.send_message_with_retry(msg.into());
-                        debug!("{self}: ACK: {ack:?}",);
-                        self.update_operation(Operation::Dkg);
-                    }
-                    Err(e) => {
-                        error!("{self}: Failed to start DKG: {e:?}",);
-                        return;
-                    }
-                }
-                self.update_operation(Operation::Dkg);
-            }
-            SignerCommand::Sign {
-                block_proposal,
-                is_taproot,
-                merkle_root,
-            } => {
-                crate::monitoring::increment_commands_processed("sign");
-                if self.approved_aggregate_public_key.is_none() {
-                    debug!("{self}: Cannot sign a block without an approved aggregate public key. Ignore it.");
+    /// The rate at which the selected coordinator changed over the coordinator selection log,
+    /// in changes per hour. A stable coordinator scores 0.
+    pub fn coordinator_stability_score(&self) -> f64 {
+        self.coordinator_changes_per_hour(Instant::now())
+    }
+
+    fn coordinator_changes_per_hour(&self, now: Instant) -> f64 {
+        let Some((_, first_selected_at)) = self.coordinator_selection_log.front() else {
+            return 0.0;
+        };
+        let changes = self.coordinator_selection_log.len().saturating_sub(1);
+        let hours = now
+            .saturating_duration_since(*first_selected_at)
+            .as_secs_f64()
+            / 3600.0;
+        if changes == 0 || hours == 0.0 {
+            return 0.0;
+        }
+        changes as f64 / hours
+    }
+
+    /// Switch to a coordinator selected while a round may be in progress, e.g. because the
+    /// current coordinator stopped responding. The new coordinator restarts signing from scratch:
+    /// the in-progress round is aborted, queued sign commands are dropped and, if this signer is
+    /// the new coordinator, sign commands are queued again for the valid blocks awaiting a signature.
+    pub fn process_coordinator_rotation(&mut self, new_coordinator_id: u32) {
+        let old_coordinator_id = self.coordinator_selector.get_coordinator().0;
+        if new_coordinator_id == old_coordinator_id {
+            signer_debug!(
+                self.logger,
+                "Coordinator {new_coordinator_id} is already selected. Nothing to rotate."
+            );
+            return;
+        }
+        if !self
+            .coordinator_selector
+            .set_coordinator(new_coordinator_id)
+        {
+            signer_warn!(
+                self.logger,
+                "Cannot rotate to unknown coordinator {new_coordinator_id}. Ignoring."
+            );
+            return;
+        }
+        self.restart_rounds_after_rotation(old_coordinator_id, new_coordinator_id);
+    }
+
+    /// Rotate to the next coordinator if the current one, another signer, has not requested
+    /// nonces for the valid blocks awaiting a signature within twice the nonce timeout
+    pub fn escalate_coordinator(&mut self, stacks_client: &StacksClient) {
+        let coordinator_id = self.coordinator_selector.get_coordinator().0;
+        let awaiting_signature = match self
+            .signer_db
+            .get_blocks_pending_signature(self.reward_cycle)
+        {
+            Ok(blocks) => !blocks.is_empty(),
+            Err(e) => {
+                signer_error!(
+                    self.logger,
+                    "Failed to load blocks awaiting a signature: {e:?}"
+                );
+                return;
+            }
+        };
+        let Some(nonce_timeout) = self.coordinator.config.nonce_timeout else {
+            self.coordinator_wait_start = None;
+            return;
+        };
+        if coordinator_id == self.signer_id || !awaiting_signature {
+            self.coordinator_wait_start = None;
+            return;
+        }
+        let wait_start = *self.coordinator_wait_start.get_or_insert_with(Instant::now);
+        if wait_start.elapsed() < nonce_timeout.saturating_mul(2) {
+            return;
+        }
+        if self.miner_key.is_some() {
+            // The miner coordinates signing in the active reward cycle, so rotating the
+            // signers' coordinator would not get the blocks signed any sooner
+            match stacks_client.get_current_reward_cycle_info() {
+                Ok(info) if info.reward_cycle == self.reward_cycle => {
+                    signer_debug!(
+                        self.logger,
+                        "The miner coordinates signing. Not escalating the coordinator."
+                    );
+                    self.coordinator_wait_start = None;
+                    return;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    signer_warn!(self.logger, "Failed to get the current reward cycle: {e:?}");
+                    return;
+                }
+            }
+        }
+        let new_coordinator_id = self.coordinator_selector.advance_coordinator();
+        if new_coordinator_id == coordinator_id {
+            signer_debug!(self.logger, "No other coordinator to escalate to.");
+            self.coordinator_wait_start = None;
+            return;
+        }
+        signer_warn!(
+            self.logger,
+            "Coordinator did not request nonces in time. Escalating to the next coordinator.";
+            "old_coordinator_id" => coordinator_id,
+            "new_coordinator_id" => new_coordinator_id,
+            "waited_ms" => wait_start.elapsed().as_millis(),
+        );
+        self.restart_rounds_after_rotation(coordinator_id, new_coordinator_id);
+    }
+
+    /// Restart signing from scratch under the newly selected coordinator: abort any in-progress
+    /// round, drop queued sign commands and, if this signer is the new coordinator, queue sign
+    /// commands again for the valid blocks awaiting a signature
+    fn restart_rounds_after_rotation(&mut self, old_coordinator_id: u32, new_coordinator_id: u32) {
+        signer_info!(
+            self.logger,
+            "Coordinator rotated. Restarting any in-progress round.";
+            "old_coordinator_id" => old_coordinator_id,
+            "new_coordinator_id" => new_coordinator_id,
+        );
+        self.track_coordinator_selection_history();
+        self.coordinator_wait_start = None;
+        if matches!(self.state, State::OperationInProgress(Operation::Sign)) {
+            self.release_aborted_sign_block();
+        }
+        if matches!(self.state, State::OperationInProgress(_)) {
+            self.coordinator.state = CoordinatorState::Idle;
+            self.finish_operation();
+        }
+        self.commands
+            .retain(|command| !matches!(command, SignerCommand::Sign { .. }));
+        if new_coordinator_id != self.signer_id {
+            return;
+        }
+        let blocks = match self
+            .signer_db
+            .get_blocks_pending_signature(self.reward_cycle)
+        {
+            Ok(blocks) => blocks,
+            Err(e) => {
+                signer_error!(
+                    self.logger,
+                    "Failed to load blocks awaiting a signature: {e:?}"
+                );
+                return;
+            }
+        };
+        for block_info in blocks {
+            signer_debug!(
+                self.logger,
+                "Queuing sign command after coordinator rotation";
+                "signer_sighash" => %block_info.signer_signature_hash(),
+            );
+            self.commands.push_back(SignerCommand::Sign {
+                block_proposal: BlockProposal {
+                    block: block_info.block,
+                    burn_height: block_info.burn_block_height,
+                    reward_cycle: block_info.reward_cycle,
+                },
+                is_taproot: false,
+                merkle_root: None,
+            });
+        }
+    }
+
+    /// Mark the block of an aborted signing round as no longer signed over,
+    /// so that it can be signed again once a new round starts
+    fn release_aborted_sign_block(&mut self) {
+        let message = self.coordinator.get_message();
+        let Ok(block) = read_next::<NakamotoBlock, _>(&mut &message[..]) else {
+            signer_debug!(
+                self.logger,
+                "Aborted signing round was not over a block. Nothing to release."
+            );
+            return;
+        };
+        let signer_signature_hash = block.header.signer_signature_hash();
+        match self
+            .signer_db
+            .block_lookup(self.reward_cycle, &signer_signature_hash)
+        {
+            Ok(Some(mut block_info)) => {
+                block_info.signed_over = false;
+                if let Err(e) = self.signer_db.insert_block(&block_info) {
+                    signer_error!(self.logger, "Failed to update block in DB: {e:?}");
+                }
+            }
+            Ok(None) => {}
+            Err(e) => signer_error!(self.logger, "Failed to look up block in DB: {e:?}"),
+        }
+    }
+
+    /// Finish an operation and update the coordinator selector accordingly
+    fn finish_operation(&mut self) {
+        self.state = State::Idle;
+        self.coordinator_selector.last_message_time = None;
+    }
+
+    /// Update operation
+    fn update_operation(&mut self, operation: Operation) {
+        self.state = State::OperationInProgress(operation);
+        self.coordinator_selector.last_message_time = Some(Instant::now());
+    }
+
+    /// Pretend the given time elapsed, by moving every instant the signer measures its timeouts
+    /// from back by as much, so that tests can expire timeouts without waiting for them
+    #[cfg(test)]
+    pub fn pass_time(&mut self, elapsed: Duration) {
+        let rewind = |instant: &mut Instant| {
+            *instant = instant.checked_sub(elapsed).unwrap_or(*instant);
+        };
+        self.coordinator_selector.pass_time(elapsed);
+        self.coordinator_wait_start.iter_mut().for_each(rewind);
+        self.block_sign_deadline.values_mut().for_each(rewind);
+        self.back_off_until.iter_mut().for_each(rewind);
+        self.shutdown_started.iter_mut().for_each(rewind);
+        self.received_nacks.values_mut().for_each(rewind);
+        self.coordinator_selection_log
+            .iter_mut()
+            .for_each(|(_, selected_at)| rewind(selected_at));
+        self.packet_window
+            .values_mut()
+            .for_each(|(_, first_seen)| rewind(first_seen));
+        if let Some((_, _, since)) = self.dkg_round_progress.as_mut() {
+            rewind(since);
+        }
+        rewind(&mut self.last_nack_report);
+        rewind(&mut self.last_block_rebroadcast);
+    }
+
+    /// Execute the given command and update state accordingly
+    fn execute_command(&mut self, stacks_client: &StacksClient, command: &SignerCommand) {
+        match command {
+            SignerCommand::Dkg => {
+                crate::monitoring::increment_commands_processed("dkg");
+                if self.approved_aggregate_public_key.is_some() {
+                    signer_debug!(
+                        self.logger,
+                        "Already have an aggregate key. Ignoring DKG command."
+                    );
+                    return;
+                }
+                let vote_round = match stacks_client.get_last_round(self.reward_cycle) {
+                    Ok(last_round) => last_round,
+                    Err(e) => {
+                        signer_error!(
+                            self.logger,
+                            "Unable to perform DKG. Failed to get last round from stacks node";
+                            "error" => ?e
+                        );
+                        return;
+                    }
+                };
+                // The dkg id will increment internally following "start_dkg_round" so do not increment it here
+                let new_round = vote_round.unwrap_or(0).wrapping_add(1);
+                if !self.validate_dkg_round_monotonicity(new_round) {
+                    return;
+                }
+                self.coordinator.current_dkg_id = vote_round.unwrap_or(0);
+                signer_info!(
+                    self.logger,
+                    "Starting DKG vote";
+                    "round" => self.coordinator.current_dkg_id.wrapping_add(1),
+                );
+                match self.coordinator.start_dkg_round() {
+                    Ok(msg) => {
+                        SignerCounters::increment(&mut self.counters.dkg_rounds_started);
+                        self.send_or_queue_message(msg.into());
+                        self.update_operation(Operation::Dkg);
+                    }
+                    Err(e) => {
+                        signer_error!(self.logger, "Failed to start DKG"; "error" => ?e);
+                        return;
+                    }
+                }
+                self.update_operation(Operation::Dkg);
+            }
+            SignerCommand::Sign {
+                block_proposal,
+                is_taproot,
+                merkle_root,
+            } => {
+                crate::monitoring::increment_commands_processed("sign");
+                if self.approved_aggregate_public_key.is_none() {
+                    signer_debug!(
+                        self.logger,
+                        "Cannot sign a block without an approved aggregate public key. Ignore it."
+                    );
                     return;
                 }
                 let signer_signature_hash = block_proposal.block.header.signer_signature_hash();
@@ -605,13 +1826,20 @@ impl Signer {
                     .unwrap_or_else(|_| Some(BlockInfo::from(block_proposal.clone())))
                     .unwrap_or_else(|| BlockInfo::from(block_proposal.clone()));
                 if block_info.signed_over {
-                    debug!("{self}: Received a sign command for a block we are already signing over. Ignore it.");
+                    signer_debug!(
+                        self.logger,
+                        "Received a sign command for a block we are already signing over. Ignore it.";
+                        "signer_sighash" => %signer_signature_hash
+                    );
                     return;
                 }
-                info!("{self}: Signing block";
-                         "block_consensus_hash" => %block_proposal.block.header.consensus_hash,
-                         "block_height" => block_proposal.block.header.chain_length,
-                         "pre_sign_block_id" => %block_proposal.block.block_id(),
+                signer_info!(
+                    self.logger,
+                    "Signing block";
+                    "signer_sighash" => %signer_signature_hash,
+                    "block_consensus_hash" => %block_proposal.block.header.consensus_hash,
+                    "block_height" => block_proposal.block.header.chain_length,
+                    "pre_sign_block_id" => %block_proposal.block.block_id(),
                 );
                 match self.coordinator.start_signing_round(
                     &block_proposal.serialize_to_vec(),
@@ -619,18 +1847,41 @@ impl Signer {
                     *merkle_root,
                 ) {
                     Ok(msg) => {
-                        let ack = self.stackerdb.send_message_with_retry(msg.into());
-                        debug!("{self}: ACK: {ack:?}",);
+                        SignerCounters::increment(&mut self.counters.sign_rounds_started);
+                        self.sign_round_waits
+                            .record_outbound(&msg.msg, Instant::now());
+                        let started_at_ms = now_ms();
+                        block_info
+                            .timings
+                            .record(SignRoundStep::Received, started_at_ms);
+                        block_info
+                            .timings
+                            .record(SignRoundStep::NonceRound, started_at_ms);
+                        self.send_or_queue_message(msg.into());
+                        self.block_sign_deadline.insert(
+                            signer_signature_hash,
+                            Instant::now() + self.sign_round_timeout,
+                        );
                         block_info.signed_over = true;
                         self.signer_db
                             .insert_block(&block_info)
                             .unwrap_or_else(|e| {
-                                error!("{self}: Failed to insert block in DB: {e:?}");
+                                signer_error!(
+                                    self.logger,
+                                    "Failed to insert block in DB";
+                                    "signer_sighash" => %signer_signature_hash,
+                                    "error" => ?e
+                                );
                             });
                         self.update_operation(Operation::Sign);
                     }
                     Err(e) => {
-                        error!("{self}: Failed to start signing block: {e:?}",);
+                        signer_error!(
+                            self.logger,
+                            "Failed to start signing block";
+                            "signer_sighash" => %signer_signature_hash,
+                            "error" => ?e
+                        );
                         return;
                     }
                 }
@@ -639,6 +1890,27 @@ impl Signer {
         }
     }
 
+    /// Validate that the DKG round we are about to start is strictly greater than the last round
+    /// we completed DKG for. Our vote for that round may not be confirmed in the contract yet, in
+    /// which case the contract still reports the round before it as its last round. Starting DKG
+    /// again for a completed round would only produce a competing key for it.
+    fn validate_dkg_round_monotonicity(&self, new_round: u64) -> bool {
+        let Some((completed_round, _)) = self.last_dkg_result else {
+            // No DKG round has completed yet. Any round is valid.
+            return true;
+        };
+        if new_round <= completed_round {
+            signer_error!(
+                self.logger,
+                "Refusing to start DKG for a round that is not newer than the last completed round";
+                "new_round" => new_round,
+                "completed_round" => completed_round,
+            );
+            return false;
+        }
+        true
+    }
+
     /// Handle the block validate response returned from our prior calls to submit a block for validation
     fn handle_block_validate_response(
         &mut self,
@@ -659,23 +1931,38 @@ impl Signer {
                     Ok(Some(block_info)) => block_info,
                     Ok(None) => {
                         // We have not seen this block before. Why are we getting a response for it?
-                        debug!("{self}: Received a block validate response for a block we have not seen before. Ignoring...");
+                        signer_debug!(
+                            self.logger,
+                            "Received a block validate response for a block we have not seen before. Ignoring...";
+                            "signer_sighash" => %signer_signature_hash
+                        );
                         return;
                     }
                     Err(e) => {
-                        error!("{self}: Failed to lookup block in signer db: {e:?}",);
+                        signer_error!(
+                            self.logger,
+                            "Failed to lookup block in signer db";
+                            "signer_sighash" => %signer_signature_hash,
+                            "error" => ?e
+                        );
                         return;
                     }
                 };
-                let is_valid = self.verify_block_transactions(stacks_client, &block_info.block);
+                let is_valid = self.verify_block_contents(
+                    stacks_client,
+                    &block_info.block,
+                    block_info.burn_block_height,
+                );
                 block_info.valid = Some(is_valid);
                 self.signer_db
                     .insert_block(&block_info)
                     .unwrap_or_else(|_| panic!("{self}: Failed to insert block in DB"));
-                info!(
-                    "{self}: Treating block validation for block {} as valid: {:?}",
-                    &block_info.block.block_id(),
-                    block_info.valid
+                signer_info!(
+                    self.logger,
+                    "Treating block validation as valid";
+                    "signer_sighash" => %signer_signature_hash,
+                    "block_id" => %block_info.block.block_id(),
+                    "valid" => ?block_info.valid
                 );
                 block_info
             }
@@ -689,40 +1976,45 @@ impl Signer {
                     Ok(Some(block_info)) => block_info,
                     Ok(None) => {
                         // We have not seen this block before. Why are we getting a response for it?
-                        debug!("{self}: Received a block validate response for a block we have not seen before. Ignoring...");
+                        signer_debug!(
+                            self.logger,
+                            "Received a block validate response for a block we have not seen before. Ignoring...";
+                            "signer_sighash" => %signer_signature_hash
+                        );
                         return;
                     }
                     Err(e) => {
-                        error!("{self}: Failed to lookup block in signer db: {e:?}");
+                        signer_error!(
+                            self.logger,
+                            "Failed to lookup block in signer db";
+                            "signer_sighash" => %signer_signature_hash,
+                            "error" => ?e
+                        );
                         return;
                     }
                 };
                 block_info.valid = Some(false);
                 // Submit a rejection response to the .signers contract for miners
                 // to observe so they know to send another block and to prove signers are doing work);
-                warn!("{self}: Broadcasting a block rejection due to stacks node validation failure...");
-                if let Err(e) = self
-                    .stackerdb
-                    .send_message_with_retry(block_validate_reject.clone().into())
-                {
-                    warn!("{self}: Failed to send block rejection to stacker-db: {e:?}",);
-                }
+                signer_warn!(
+                    self.logger,
+                    "Broadcasting a block rejection due to stacks node validation failure...";
+                    "signer_sighash" => %signer_signature_hash,
+                    "reason_code" => ?block_validate_reject.reason_code
+                );
+                self.send_or_queue_message(block_validate_reject.clone().into());
                 block_info
             }
         };
-        if let Some(mut nonce_request) = block_info.nonce_request.take() {
-            debug!("{self}: Received a block validate response from the stacks node for a block we already received a nonce request for. Responding to the nonce request...");
-            // We have received validation from the stacks node. Determine our vote and update the request message
-            self.determine_vote(&mut block_info, &mut nonce_request);
-            // Send the nonce request through with our vote
-            let packet = Packet {
-                msg: Message::NonceRequest(nonce_request),
-                sig: vec![],
-            };
-            self.handle_packets(stacks_client, res, &[packet], current_reward_cycle);
-        }
-        debug!(
-            "{self}: Received a block validate response";
+        block_info
+            .timings
+            .record(SignRoundStep::Validated, now_ms());
+        self.record_block_validation(&block_info, block_validate_response);
+        self.retry_stale_nonce_requests(stacks_client, res, &mut block_info, current_reward_cycle);
+        signer_debug!(
+            self.logger,
+            "Received a block validate response";
+            "signer_sighash" => %block_info.signer_signature_hash(),
             "block_hash" => block_info.block.header.block_hash(),
             "valid" => block_info.valid,
             "signed_over" => block_info.signed_over,
@@ -732,132 +2024,701 @@ impl Signer {
             .unwrap_or_else(|_| panic!("{self}: Failed to insert block in DB"));
     }
 
-    /// Handle signer messages submitted to signers stackerdb
-    fn handle_signer_messages(
+    /// Count the stacks node's answer to the validation of the given block and the time it took
+    fn record_block_validation(
+        &mut self,
+        block_info: &BlockInfo,
+        block_validate_response: &BlockValidateResponse,
+    ) {
+        SignerCounters::increment(match block_validate_response {
+            BlockValidateResponse::Ok(_) => &mut self.counters.block_validations_accepted,
+            BlockValidateResponse::Reject(_) => &mut self.counters.block_validations_rejected,
+        });
+        if let Some(proposed_at) = block_info.proposed_at {
+            let latency = get_epoch_time_secs().saturating_sub(proposed_at);
+            self.counters.block_validation_seconds = self
+                .counters
+                .block_validation_seconds
+                .saturating_add(latency);
+        }
+    }
+
+    /// Respond to every nonce request cached while the given block was awaiting validation,
+    /// now that the stacks node validated it
+    fn retry_stale_nonce_requests(
         &mut self,
         stacks_client: &StacksClient,
         res: Sender<Vec<OperationResult>>,
-        messages: &[SignerMessage],
+        block_info: &mut BlockInfo,
         current_reward_cycle: u64,
     ) {
-        let packets: Vec<Packet> = messages
-            .iter()
-            .filter_map(|msg| match msg {
-                SignerMessage::DkgResults { .. }
-                | SignerMessage::BlockResponse(_)
-                | SignerMessage::EncryptedSignerState(_)
-                | SignerMessage::Transactions(_) => None,
-                // TODO: if a signer tries to trigger DKG and we already have one set in the contract, ignore the request.
-                SignerMessage::Packet(packet) => {
-                    let coordinator_pubkey = if Self::is_dkg_message(&packet.msg) {
-                        self.get_coordinator_dkg().1
-                    } else {
-                        self.get_coordinator_sign(current_reward_cycle).1
-                    };
-                    self.verify_packet(stacks_client, packet.clone(), &coordinator_pubkey)
+        let signer_signature_hash = block_info.signer_signature_hash();
+        let mut nonce_requests = self
+            .pending_nonce_requests
+            .remove(&signer_signature_hash)
+            .unwrap_or_default();
+        // Requests cached before a restart are only known to the signer db
+        if let Some(nonce_request) = block_info.nonce_request.take() {
+            if !nonce_requests
+                .iter()
+                .any(|request| same_signing_round(request, &nonce_request))
+            {
+                nonce_requests.insert(0, nonce_request);
+            }
+        }
+        if nonce_requests.is_empty() {
+            return;
+        }
+        signer_debug!(
+            self.logger,
+            "Received a block validate response from the stacks node for a block we already received nonce requests for. Responding to the nonce requests...";
+            "signer_sighash" => %signer_signature_hash,
+            "nonce_requests" => nonce_requests.len(),
+        );
+        let packets: Vec<_> = nonce_requests
+            .into_iter()
+            .map(|mut nonce_request| {
+                // We have received validation from the stacks node. Determine our vote and update the request message
+                self.determine_vote(block_info, &mut nonce_request);
+                // Send the nonce request through with our vote
+                Packet {
+                    msg: Message::NonceRequest(nonce_request),
+                    sig: vec![],
                 }
             })
             .collect();
         self.handle_packets(stacks_client, res, &packets, current_reward_cycle);
     }
 
-    /// Helper function for determining if the provided message is a DKG specific message
-    fn is_dkg_message(msg: &Message) -> bool {
-        matches!(
-            msg,
-            Message::DkgBegin(_)
-                | Message::DkgEnd(_)
-                | Message::DkgEndBegin(_)
-                | Message::DkgPrivateBegin(_)
-                | Message::DkgPrivateShares(_)
-                | Message::DkgPublicShares(_)
-        )
-    }
-
-    /// Process inbound packets as both a signer and a coordinator
-    /// Will send outbound packets and operation results as appropriate
-    fn handle_packets(
+    /// Cache a nonce request for a block awaiting validation.
+    /// A request for the same signing round as a cached one replaces it.
+    fn cache_nonce_request(
         &mut self,
-        stacks_client: &StacksClient,
-        res: Sender<Vec<OperationResult>>,
-        packets: &[Packet],
-        current_reward_cycle: u64,
+        signer_signature_hash: Sha512Trunc256Sum,
+        nonce_request: &NonceRequest,
     ) {
-        if let Ok(packets_len) = packets.len().try_into() {
-            crate::monitoring::increment_inbound_packets(packets_len);
+        let nonce_requests = self
+            .pending_nonce_requests
+            .entry(signer_signature_hash)
+            .or_default();
+        if push_pending_nonce_request(nonce_requests, nonce_request).is_some() {
+            signer_warn!(self.logger, "Too many nonce requests cached for a block awaiting validation. Dropped the oldest.";
+                "signer_sighash" => %signer_signature_hash,
+            );
         }
-        let signer_outbound_messages = self
-            .state_machine
-            .process_inbound_messages(packets)
-            .unwrap_or_else(|e| {
-                error!("{self}: Failed to process inbound messages as a signer: {e:?}",);
-                vec![]
-            });
+    }
 
-        // Next process the message as the coordinator
-        let (coordinator_outbound_messages, operation_results) = if self.reward_cycle
-            != current_reward_cycle
+    /// Submit any block proposal we missed for validation, resubmit any block the stacks node
+    /// could not be reached for, reject any block the stacks node has failed to validate within
+    /// the block validation timeout, and escalate to the next coordinator if the current one stalls
+    fn handle_status_check(&mut self, stacks_client: &StacksClient, current_reward_cycle: u64) {
+        if current_reward_cycle == self.reward_cycle {
+            self.poll_pending_block_proposals(stacks_client);
+        }
+        self.process_block_timeout();
+        self.escalate_coordinator(stacks_client);
+        if self.stale_node_nack_policy.is_some() && self.coordinator_metadata.is_none() {
+            self.refresh_coordinator_metadata(stacks_client);
+        }
+        self.aggregate_nack_report();
+        self.rebroadcast_unconfirmed_blocks(stacks_client);
+        let pending_blocks = match self
+            .signer_db
+            .get_blocks_awaiting_validation(self.reward_cycle)
         {
-            self.coordinator
-                .process_inbound_messages(packets)
-                .unwrap_or_else(|e| {
-                    error!("{self}: Failed to process inbound messages as a coordinator: {e:?}");
-                    (vec![], vec![])
-                })
-        } else {
-            (vec![], vec![])
+            Ok(blocks) => blocks,
+            Err(e) => {
+                signer_error!(
+                    self.logger,
+                    "Failed to lookup blocks awaiting validation in signer db: {e:?}"
+                );
+                return;
+            }
         };
-
-        if !operation_results.is_empty() {
-            // We have finished a signing or DKG round, either successfully or due to error.
-            // Regardless of the why, update our state to Idle as we should not expect the operation to continue.
-            self.process_operation_results(stacks_client, &operation_results);
-            self.send_operation_results(res, operation_results);
-            self.finish_operation();
-        } else if !packets.is_empty() {
-            // We have received a message. Update our state accordingly
-            // Let us be extra explicit in case a new state type gets added to wsts' state machine
-            match &self.coordinator.state {
-                CoordinatorState::Idle => {}
-                CoordinatorState::DkgPublicDistribute
-                | CoordinatorState::DkgPublicGather
-                | CoordinatorState::DkgPrivateDistribute
-                | CoordinatorState::DkgPrivateGather
-                | CoordinatorState::DkgEndDistribute
-                | CoordinatorState::DkgEndGather => {
-                    self.update_operation(Operation::Dkg);
-                }
-                CoordinatorState::NonceRequest(_, _)
-                | CoordinatorState::NonceGather(_, _)
-                | CoordinatorState::SigShareRequest(_, _)
-                | CoordinatorState::SigShareGather(_, _) => {
-                    self.update_operation(Operation::Sign);
+        for mut block_info in pending_blocks {
+            if block_info.proposed_at.is_none() {
+                // The stacks node was not reached when the block was submitted. Try again.
+                if self.submit_block_for_validation(stacks_client, &mut block_info) {
+                    if let Err(e) = self.signer_db.insert_block(&block_info) {
+                        signer_error!(self.logger, "Failed to update block in signer db: {e:?}");
+                    }
                 }
+                continue;
             }
-        }
+            if !block_info.validation_timed_out(self.block_validation_timeout) {
+                continue;
+            }
+            let signer_signature_hash = block_info.signer_signature_hash();
+            signer_warn!(
+                self.logger,
+                "Timed out waiting for the stacks node to validate block. Rejecting it.";
+                "signer_sighash" => %signer_signature_hash,
+                "block_id" => %block_info.block.block_id(),
+                "timeout_ms" => self.block_validation_timeout.as_millis(),
+            );
+            if let Err(e) = self
+                .signer_db
+                .remove_block(self.reward_cycle, &signer_signature_hash)
+            {
+                signer_error!(
+                    self.logger,
+                    "Failed to remove timed out block from signer db: {e:?}"
+                );
+            }
+            self.pending_nonce_requests.remove(&signer_signature_hash);
+            let block_rejection =
+                BlockRejection::new(signer_signature_hash, RejectCode::ValidationTimeout);
+            // Submit the rejection to miners to observe
+            self.send_or_queue_message(block_rejection.into());
+        }
+    }
+
+    /// Broadcast the block response of every block we accepted a while ago that the stacks node
+    /// still does not have, in case the original response was lost. Only the coordinator of the
+    /// round holds the aggregate signature, so other signers merely mark blocks as confirmed.
+    fn rebroadcast_unconfirmed_blocks(&mut self, stacks_client: &StacksClient) {
+        if self.last_block_rebroadcast.elapsed() < BLOCK_REBROADCAST_INTERVAL {
+            return;
+        }
+        self.last_block_rebroadcast = Instant::now();
+        let blocks = match self
+            .signer_db
+            .get_blocks_needing_rebroadcast(self.reward_cycle, BLOCK_REBROADCAST_MIN_AGE)
+        {
+            Ok(blocks) => blocks,
+            Err(e) => {
+                signer_error!(
+                    self.logger,
+                    "Failed to lookup blocks needing rebroadcast in signer db: {e:?}"
+                );
+                return;
+            }
+        };
+        for mut block_info in blocks {
+            let signer_signature_hash = block_info.signer_signature_hash();
+            let Some(signature) = block_info.signature.clone() else {
+                continue;
+            };
+            // The stacks node stores the block with the aggregate signature applied, which is
+            // part of the block id
+            let mut signed_header = block_info.block.header.clone();
+            signed_header.signer_signature = signature.clone();
+            let block_id = signed_header.block_id();
+            match stacks_client.get_block_by_id(&block_id) {
+                Ok(Some(_)) => {
+                    block_info.confirmed = true;
+                    if let Err(e) = self.signer_db.insert_block(&block_info) {
+                        signer_error!(self.logger, "Failed to update block in signer db: {e:?}");
+                    }
+                    continue;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    signer_warn!(
+                        self.logger,
+                        "Failed to check whether the stacks node has accepted blocks: {e:?}"
+                    );
+                    return;
+                }
+            }
+            signer_info!(
+                self.logger,
+                "The stacks node does not have a block we accepted. Broadcast its block response again.";
+                "signer_sighash" => %signer_signature_hash,
+                "block_id" => %block_id,
+            );
+            self.send_or_queue_message(
+                BlockResponse::Accepted((signer_signature_hash, signature)).into(),
+            );
+        }
+    }
+
+    /// Reject any block whose signing round has not accumulated enough signatures by its
+    /// deadline, so that miners do not wait on it indefinitely
+    pub fn process_block_timeout(&mut self) {
+        let now = Instant::now();
+        let expired: Vec<_> = self
+            .block_sign_deadline
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(signer_signature_hash, _)| *signer_signature_hash)
+            .collect();
+        for signer_signature_hash in expired {
+            signer_warn!(
+                self.logger,
+                "Timed out waiting for the signing round of block to complete. Rejecting it.";
+                "signer_sighash" => %signer_signature_hash,
+                "timeout_ms" => self.sign_round_timeout.as_millis(),
+            );
+            self.alert(
+                AlertEvent::SignRoundTimeout,
+                format!("The signing round of block {signer_signature_hash} timed out"),
+            );
+            let block_rejection =
+                BlockRejection::new(signer_signature_hash, RejectCode::SigningTimeout);
+            // Submit the rejection to miners to observe
+            self.send_or_queue_message(block_rejection.into());
+            if let Err(e) = self
+                .signer_db
+                .remove_block(self.reward_cycle, &signer_signature_hash)
+            {
+                signer_error!(
+                    self.logger,
+                    "Failed to remove timed out block from signer db: {e:?}"
+                );
+            }
+            self.block_sign_deadline.remove(&signer_signature_hash);
+        }
+    }
+
+    /// Ask the stacks node to validate the block, unless the block proposal filter declines it or
+    /// it already used up its validation attempts, in which case it is rejected and forgotten.
+    /// If the request fails, the block is submitted again on the next status check.
+    /// Returns whether the block was submitted.
+    fn submit_block_for_validation(
+        &mut self,
+        stacks_client: &StacksClient,
+        block_info: &mut BlockInfo,
+    ) -> bool {
+        if !self.prepare_block_for_validation(block_info) {
+            return false;
+        }
+        self.send_validation_requests(stacks_client, std::slice::from_mut(block_info));
+        true
+    }
+
+    /// Ask the stacks node to validate the blocks, recording when each request succeeded.
+    /// If parallel validation is enabled and there are enough blocks, the requests are sent
+    /// concurrently. The validation results still arrive as block validation response events.
+    fn send_validation_requests(
+        &self,
+        stacks_client: &StacksClient,
+        block_infos: &mut [BlockInfo],
+    ) {
+        let stacks_client = stacks_client.with_retry_deadline(NONCE_REQUEST_RETRY_DEADLINE);
+        let submit = |block_info: &BlockInfo| {
+            stacks_client.submit_block_for_validation(block_info.block.clone())
+        };
+        let results: Vec<_> =
+            if self.parallel_validation && block_infos.len() >= PARALLEL_VALIDATION_MIN_BLOCKS {
+                signer_debug!(
+                    self.logger,
+                    "Submitting {} blocks for validation concurrently",
+                    block_infos.len()
+                );
+                block_infos.par_iter().map(submit).collect()
+            } else {
+                block_infos.iter().map(submit).collect()
+            };
+        for (block_info, result) in block_infos.iter_mut().zip(results) {
+            self.record_validation_submission(block_info, result);
+        }
+    }
+
+    /// Check that the block may be submitted for validation, rejecting and forgetting it if the
+    /// block proposal filter declines it or it already used up its validation attempts, and
+    /// count the attempt. Returns whether the block may be submitted.
+    fn prepare_block_for_validation(&mut self, block_info: &mut BlockInfo) -> bool {
+        let signer_signature_hash = block_info.signer_signature_hash();
+        if self
+            .block_proposal_filter
+            .as_ref()
+            .is_some_and(|filter| !filter.should_accept(&block_info.block))
+        {
+            signer_warn!(
+                self.logger,
+                "Block proposal declined by the block proposal filter. Rejecting it.";
+                "signer_sighash" => %signer_signature_hash,
+                "block_id" => %block_info.block.block_id(),
+            );
+            self.reject_unvalidated_block(signer_signature_hash, RejectCode::FilteredProposal);
+            return false;
+        }
+        if block_info.validation_attempts >= self.max_validation_attempts {
+            signer_warn!(
+                self.logger,
+                "Failed to submit block for validation too many times. Rejecting it.";
+                "signer_sighash" => %signer_signature_hash,
+                "validation_attempts" => block_info.validation_attempts,
+            );
+            self.reject_unvalidated_block(
+                signer_signature_hash,
+                RejectCode::ValidationAttemptsExceeded,
+            );
+            return false;
+        }
+        block_info.validation_attempts = block_info.validation_attempts.saturating_add(1);
+        block_info.timings.record(SignRoundStep::Received, now_ms());
+        true
+    }
+
+    /// Record the outcome of the request submitting the block for validation
+    fn record_validation_submission(
+        &self,
+        block_info: &mut BlockInfo,
+        result: Result<(), ClientError>,
+    ) {
+        match result {
+            Ok(_) => block_info.proposed_at = Some(get_epoch_time_secs()),
+            Err(e) => signer_warn!(
+                self.logger,
+                "Failed to submit block for validation: {e:?}";
+                "signer_sighash" => %block_info.signer_signature_hash(),
+                "validation_attempts" => block_info.validation_attempts,
+            ),
+        }
+    }
+
+    /// Forget a block that will not be validated, broadcasting its rejection
+    fn reject_unvalidated_block(
+        &mut self,
+        signer_signature_hash: Sha512Trunc256Sum,
+        reject_code: RejectCode,
+    ) {
+        if let Err(e) = self
+            .signer_db
+            .remove_block(self.reward_cycle, &signer_signature_hash)
+        {
+            signer_error!(
+                self.logger,
+                "Failed to remove rejected block from signer db: {e:?}"
+            );
+        }
+        self.pending_nonce_requests.remove(&signer_signature_hash);
+        let block_rejection = BlockRejection::new(signer_signature_hash, reject_code);
+        self.send_or_queue_message(block_rejection.into());
+    }
+
+    /// Poll the miners' stacker-db for block proposals whose events we may have missed and submit
+    /// the ones we have not seen yet for validation
+    fn poll_pending_block_proposals(&mut self, stacks_client: &StacksClient) {
+        let block_proposals = match stacks_client
+            .with_retry_deadline(NONCE_REQUEST_RETRY_DEADLINE)
+            .get_pending_block_proposals(self.reward_cycle)
+        {
+            Ok(block_proposals) => block_proposals,
+            Err(e) => {
+                signer_warn!(
+                    self.logger,
+                    "Failed to poll for pending block proposals: {e:?}"
+                );
+                return;
+            }
+        };
+        let mut block_infos = vec![];
+        for block_proposal in block_proposals {
+            let signer_signature_hash = block_proposal.block.header.signer_signature_hash();
+            match self
+                .signer_db
+                .block_lookup(self.reward_cycle, &signer_signature_hash)
+            {
+                Ok(None) => {}
+                Ok(Some(_)) => continue,
+                Err(e) => {
+                    signer_error!(self.logger, "Failed to lookup block in signer db: {e:?}");
+                    continue;
+                }
+            }
+            if self.detect_duplicate_block_proposals(&block_proposal.block) {
+                let block_rejection =
+                    BlockRejection::new(signer_signature_hash, RejectCode::ConflictingProposal);
+                self.send_or_queue_message(block_rejection.into());
+                continue;
+            }
+            signer_info!(
+                self.logger,
+                "Found a block proposal we missed. Submit block for validation.";
+                "signer_sighash" => %signer_signature_hash,
+                "block_id" => %block_proposal.block.block_id(),
+            );
+            let mut block_info = BlockInfo::from(block_proposal);
+            if !self.prepare_block_for_validation(&mut block_info) {
+                continue;
+            }
+            // Store the block right away, so that conflicting proposals found later in this
+            // poll are detected
+            if let Err(e) = self.signer_db.insert_block(&block_info) {
+                signer_error!(self.logger, "Failed to insert block in signer db: {e:?}");
+            }
+            block_infos.push(block_info);
+        }
+        if block_infos.is_empty() {
+            return;
+        }
+        self.send_validation_requests(stacks_client, &mut block_infos);
+        if let Err(e) = self
+            .signer_db
+            .batch_insert_blocks(self.reward_cycle, &block_infos)
+        {
+            signer_error!(self.logger, "Failed to insert blocks in signer db: {e:?}");
+        }
+    }
+
+    /// Handle signer messages submitted to signers stackerdb
+    fn handle_signer_messages(
+        &mut self,
+        stacks_client: &StacksClient,
+        res: Sender<Vec<OperationResult>>,
+        messages: &[SignerMessage],
+        current_reward_cycle: u64,
+    ) {
+        for message in messages {
+            match message {
+                SignerMessage::Transactions(transactions) => {
+                    self.cache_vote_transactions(transactions)
+                }
+                SignerMessage::Nack(nack) => {
+                    SignerCounters::increment(&mut self.counters.nacks_received);
+                    if self.stale_node_nack_policy.is_some() {
+                        self.received_nacks.insert(nack.signer_id, Instant::now());
+                    }
+                }
+                SignerMessage::NackReport {
+                    sender_id,
+                    nacked_signer_count,
+                    ..
+                } => self.handle_nack_report(*sender_id, *nacked_signer_count, Instant::now()),
+                SignerMessage::DkgConflict {
+                    sender_id,
+                    reward_cycle,
+                    previous_round,
+                    previous_key,
+                    conflicting_round,
+                    conflicting_key,
+                } => self.handle_dkg_conflict(
+                    stacks_client,
+                    *sender_id,
+                    *reward_cycle,
+                    (*previous_round, *previous_key),
+                    (*conflicting_round, *conflicting_key),
+                ),
+                _ => {}
+            }
+        }
+        let packets: Vec<Packet> = messages
+            .iter()
+            .filter_map(|msg| match msg {
+                SignerMessage::DkgResults { .. }
+                | SignerMessage::BlockResponse(_)
+                | SignerMessage::EncryptedSignerState(_)
+                | SignerMessage::Transactions(_) => None,
+                SignerMessage::StackerDbSlotUpdate {
+                    signer_id,
+                    slot_version,
+                } => {
+                    signer_debug!(
+                        self.logger,
+                        "A signer wrote a new version of its slot";
+                        "sender_id" => signer_id,
+                        "slot_version" => slot_version,
+                    );
+                    None
+                }
+                SignerMessage::Nack(nack) => {
+                    signer_info!(
+                        self.logger,
+                        "A signer NACKed a signing round";
+                        "sender_id" => nack.signer_id,
+                        "sign_id" => nack.sign_id,
+                        "sign_iter_id" => nack.sign_iter_id,
+                        "coordinator_id" => nack.coordinator_id,
+                        "reason" => ?nack.reason,
+                    );
+                    None
+                }
+                SignerMessage::NackReport {
+                    sender_id,
+                    coordinator_metadata,
+                    nacked_signer_count,
+                } => {
+                    signer_debug!(
+                        self.logger,
+                        "A signer reported the NACKs it received";
+                        "sender_id" => sender_id,
+                        "nacked_signer_count" => nacked_signer_count,
+                        "burn_block_height" => coordinator_metadata.burn_block_height,
+                        "pox_consensus_hash" => %coordinator_metadata.pox_consensus_hash,
+                    );
+                    None
+                }
+                SignerMessage::DkgConflict { .. } => None,
+                // TODO: if a signer tries to trigger DKG and we already have one set in the contract, ignore the request.
+                SignerMessage::Packet(packet) => {
+                    let coordinator_pubkey = if Self::is_dkg_message(&packet.msg) {
+                        self.get_coordinator_dkg().1
+                    } else {
+                        self.get_coordinator_sign(current_reward_cycle).1
+                    };
+                    self.verify_packet(stacks_client, packet.clone(), &coordinator_pubkey)
+                }
+            })
+            .collect();
+        self.handle_packets(stacks_client, res, &packets, current_reward_cycle);
+    }
+
+    /// Helper function for determining if the provided message is a DKG specific message
+    fn is_dkg_message(msg: &Message) -> bool {
+        matches!(
+            msg,
+            Message::DkgBegin(_)
+                | Message::DkgEnd(_)
+                | Message::DkgEndBegin(_)
+                | Message::DkgPrivateBegin(_)
+                | Message::DkgPrivateShares(_)
+                | Message::DkgPublicShares(_)
+        )
+    }
+
+    /// Process inbound packets as both a signer and a coordinator
+    /// Will send outbound packets and operation results as appropriate
+    fn handle_packets(
+        &mut self,
+        stacks_client: &StacksClient,
+        res: Sender<Vec<OperationResult>>,
+        packets: &[Packet],
+        current_reward_cycle: u64,
+    ) {
+        if let Ok(packets_len) = packets.len().try_into() {
+            crate::monitoring::increment_inbound_packets(packets_len);
+        }
+        let now = Instant::now();
+        let packets =
+            &self
+                .packet_round_guard
+                .filter_packets(packets, self.coordinator.current_dkg_id, now);
+        self.counters.packets_processed = self
+            .counters
+            .packets_processed
+            .saturating_add(packets.len() as u64);
+        if !packets.is_empty() {
+            signer_debug!(
+                self.logger,
+                "Processing inbound packets";
+                "packets" => packets.len(),
+                "dkg_id" => self.coordinator.current_dkg_id,
+            );
+        }
+        for packet in packets {
+            self.sign_round_waits.record_inbound(&packet.msg, now);
+            match &packet.msg {
+                Message::DkgPublicShares(shares) => self.dkg_history.record_public_shares(shares),
+                // The coordinator is making progress on signing
+                Message::NonceRequest(_) => self.coordinator_wait_start = None,
+                _ => {}
+            }
+        }
+        let signer_outbound_messages = self
+            .state_machine
+            .process_inbound_messages(packets)
+            .unwrap_or_else(|e| {
+                signer_error!(
+                    self.logger,
+                    "Failed to process inbound messages as a signer";
+                    "error" => ?e
+                );
+                vec![]
+            });
+
+        // Next process the message as the coordinator
+        let (coordinator_outbound_messages, operation_results) =
+            if self.reward_cycle != current_reward_cycle {
+                self.coordinator
+                    .process_inbound_messages(packets)
+                    .unwrap_or_else(|e| {
+                        signer_error!(
+                            self.logger,
+                            "Failed to process inbound messages as a coordinator";
+                            "error" => ?e
+                        );
+                        (vec![], vec![])
+                    })
+            } else {
+                (vec![], vec![])
+            };
+        for packet in &coordinator_outbound_messages {
+            self.sign_round_waits
+                .record_outbound(&packet.msg, Instant::now());
+            if let Message::SignatureShareRequest(request) = &packet.msg {
+                if let Ok(block_vote) = read_next::<NakamotoBlockVote, _>(&mut &request.message[..])
+                {
+                    self.update_block_info(&block_vote.signer_signature_hash, |block_info| {
+                        block_info
+                            .timings
+                            .record(SignRoundStep::ShareRound, now_ms())
+                    });
+                }
+            }
+        }
+
+        if !operation_results.is_empty() {
+            // We have finished a signing or DKG round, either successfully or due to error.
+            // Regardless of the why, update our state to Idle as we should not expect the operation to continue.
+            self.process_operation_results(stacks_client, &operation_results);
+            self.send_operation_results(res, operation_results);
+            self.finish_operation();
+        } else if !packets.is_empty() {
+            // We have received a message. Update our state accordingly
+            // Let us be extra explicit in case a new state type gets added to wsts' state machine
+            match &self.coordinator.state {
+                CoordinatorState::Idle => {}
+                CoordinatorState::DkgPublicDistribute
+                | CoordinatorState::DkgPublicGather
+                | CoordinatorState::DkgPrivateDistribute
+                | CoordinatorState::DkgPrivateGather
+                | CoordinatorState::DkgEndDistribute
+                | CoordinatorState::DkgEndGather => {
+                    self.update_operation(Operation::Dkg);
+                }
+                CoordinatorState::NonceRequest(_, _)
+                | CoordinatorState::NonceGather(_, _)
+                | CoordinatorState::SigShareRequest(_, _)
+                | CoordinatorState::SigShareGather(_, _) => {
+                    self.update_operation(Operation::Sign);
+                }
+            }
+        }
 
         if packets
             .iter()
             .any(|packet| matches!(packet.msg, Message::DkgEnd(_)))
         {
-            debug!("{self}: Saving signer state");
+            signer_debug!(self.logger, "Saving signer state");
             self.save_signer_state()
                 .unwrap_or_else(|_| panic!("{self}: Failed to save signer state"));
         }
-        self.send_outbound_messages(signer_outbound_messages);
-        self.send_outbound_messages(coordinator_outbound_messages);
+        let outbound_messages = Self::deduplicate_outbound_messages(
+            signer_outbound_messages
+                .into_iter()
+                .chain(coordinator_outbound_messages)
+                .collect(),
+        );
+        self.send_outbound_messages(outbound_messages);
+    }
+
+    /// Drop repeated packets from the outbound messages, keeping the first occurrence of each.
+    /// Acting as both signer and coordinator can produce the same packet twice in a single pass.
+    /// Packets are identified by their message type, source signer and round.
+    pub fn deduplicate_outbound_messages(messages: Vec<Packet>) -> Vec<Packet> {
+        let mut seen = HashSet::new();
+        messages
+            .into_iter()
+            .filter(|packet| seen.insert(outbound_message_key(&packet.msg)))
+            .collect()
     }
 
     /// Validate a signature share request, updating its message where appropriate.
     /// If the request is for a block it has already agreed to sign, it will overwrite the message with the agreed upon value
     /// Returns whether the request is valid or not.
-    fn validate_signature_share_request(&self, request: &mut SignatureShareRequest) -> bool {
+    fn validate_signature_share_request(&mut self, request: &mut SignatureShareRequest) -> bool {
         let Some(block_vote): Option<NakamotoBlockVote> = read_next(&mut &request.message[..]).ok()
         else {
             // We currently reject anything that is not a block vote
-            debug!(
-                "{self}: Received a signature share request for an unknown message stream. Reject it.",
+            signer_debug!(
+                self.logger,
+                "Received a signature share request for an unknown message stream. Reject it.",
             );
+            let message_hash = Sha512Trunc256Sum::from_data(&request.message);
+            self.record_rejected_request(request, message_hash, NackReason::MalformedPacket);
             return false;
         };
 
@@ -869,60 +2730,231 @@ impl Signer {
         {
             Some(Some(vote)) => {
                 // Overwrite with our agreed upon value in case another message won majority or the coordinator is trying to cheat...
-                debug!(
-                    "{self}: Set vote (rejected = {}) to {vote:?}", block_vote.rejected;
+                signer_debug!(
+                    self.logger,
+                    "Set vote (rejected = {}) to {vote:?}", block_vote.rejected;
                     "requested_sighash" => %block_vote.signer_signature_hash,
                 );
                 request.message = vote.serialize_to_vec();
+                self.update_block_info(&block_vote.signer_signature_hash, |block_info| {
+                    block_info
+                        .timings
+                        .record(SignRoundStep::ShareRound, now_ms())
+                });
                 true
             }
             Some(None) => {
-                // We never agreed to sign this block. Reject it.
+                // We never voted on this block, neither yes nor no. Explicitly reject it.
                 // This can happen if the coordinator received enough votes to sign yes
                 // or no on a block before we received validation from the stacks node.
-                debug!(
-                    "{self}: Received a signature share request for a block we never agreed to sign. Ignore it.";
+                signer_debug!(
+                    self.logger,
+                    "Received a signature share request for a block we never voted on. Reject it.";
                     "requested_sighash" => %block_vote.signer_signature_hash,
                 );
+                let block_rejection =
+                    BlockRejection::new(block_vote.signer_signature_hash, RejectCode::NoVote);
+                self.send_or_queue_message(block_rejection.into());
                 false
             }
             None => {
                 // We will only sign across block hashes or block hashes + b'n' byte for
                 // blocks we have seen a Nonce Request for (and subsequent validation)
                 // We are missing the context here necessary to make a decision. Reject the block
-                debug!(
-                    "{self}: Received a signature share request from an unknown block. Reject it.";
+                signer_debug!(
+                    self.logger,
+                    "Received a signature share request from an unknown block. Reject it.";
                     "requested_sighash" => %block_vote.signer_signature_hash,
                 );
+                self.record_rejected_request(
+                    request,
+                    block_vote.signer_signature_hash,
+                    NackReason::UnexpectedCoordinator,
+                );
                 false
             }
         }
     }
 
-    /// Validate a nonce request, updating its message appropriately.
-    /// If the request is for a block, we will update the request message
-    /// as either a hash indicating a vote no or the signature hash indicating a vote yes
-    /// Returns whether the request is valid or not
-    fn validate_nonce_request(
+    /// Keep an audit record of a rejected signature share request. Once enough requests from
+    /// the coordinator are rejected in the signing round, NACK the round so that the other
+    /// signers learn it is broken rather than each waiting for it to time out.
+    fn record_rejected_request(
         &mut self,
-        stacks_client: &StacksClient,
-        nonce_request: &mut NonceRequest,
-    ) -> Option<BlockInfo> {
-        let Some(block_proposal) =
-            BlockProposal::consensus_deserialize(&mut nonce_request.message.as_slice()).ok()
-        else {
-            // We currently reject anything that is not a valid block proposal
-            warn!("{self}: Received a nonce request for an unknown message stream. Reject it.",);
-            return None;
+        request: &SignatureShareRequest,
+        message_hash: Sha512Trunc256Sum,
+        reason: NackReason,
+    ) {
+        let coordinator_id = self.coordinator_selector.get_coordinator().0;
+        let rejected_request = RejectedRequest {
+            reward_cycle: self.reward_cycle,
+            sign_id: request.sign_id,
+            coordinator_id,
+            message_hash,
+            reason,
+            rejected_at: get_epoch_time_secs(),
+        };
+        let rejections = match self.signer_db.record_rejected_request(&rejected_request) {
+            Ok(rejections) => rejections,
+            Err(e) => {
+                signer_warn!(
+                    self.logger,
+                    "Failed to record rejected signature share request";
+                    "sign_id" => request.sign_id,
+                    "signer_sighash" => %message_hash,
+                    "error" => ?e
+                );
+                return;
+            }
+        };
+        // Only NACK the round once, when the threshold is first reached
+        if rejections != self.max_rejected_requests_per_round {
+            return;
+        }
+        signer_warn!(
+            self.logger,
+            "Broadcasting a NACK of the signing round";
+            "sign_id" => request.sign_id,
+            "signer_sighash" => %message_hash,
+            "coordinator_id" => coordinator_id,
+            "rejected_requests" => rejections,
+            "reason" => ?reason,
+        );
+        let nack = NackMessage {
+            signer_id: self.signer_id,
+            sign_id: request.sign_id,
+            sign_iter_id: request.sign_iter_id,
+            coordinator_id,
+            reason,
+        };
+        SignerCounters::increment(&mut self.counters.nacks_sent);
+        self.send_or_queue_message(SignerMessage::Nack(nack));
+    }
+
+    /// Refresh the burn chain view sent along with our NACK reports
+    fn refresh_coordinator_metadata(&mut self, stacks_client: &StacksClient) {
+        match stacks_client.get_current_coordinator_metadata() {
+            Ok(coordinator_metadata) => self.coordinator_metadata = Some(coordinator_metadata),
+            Err(e) => signer_warn!(
+                self.logger,
+                "Failed to get the current coordinator metadata: {e:?}"
+            ),
+        }
+    }
+
+    /// Broadcast how many signers NACKed recently, once every NACK report interval, so that
+    /// the other signers get an aggregate view of the chain view disagreements without
+    /// collecting every NACK themselves. Nothing is sent if no signer NACKed recently.
+    pub fn aggregate_nack_report(&mut self) {
+        self.aggregate_nack_report_at(Instant::now());
+    }
+
+    fn aggregate_nack_report_at(&mut self, now: Instant) {
+        let Some(policy) = self.stale_node_nack_policy else {
+            return;
+        };
+        if now.saturating_duration_since(self.last_nack_report) < policy.report_interval {
+            return;
+        }
+        self.last_nack_report = now;
+        self.received_nacks
+            .retain(|_, received_at| now.saturating_duration_since(*received_at) < policy.nack_ttl);
+        let nacked_signer_count = self.received_nacks.len();
+        if nacked_signer_count == 0 {
+            return;
+        }
+        let Some(coordinator_metadata) = self.coordinator_metadata.clone() else {
+            signer_debug!(
+                self.logger,
+                "Not reporting the NACKs received, as the burn chain view is not known yet";
+                "nacked_signer_count" => nacked_signer_count,
+            );
+            return;
+        };
+        signer_info!(
+            self.logger,
+            "Broadcasting a report of the NACKs received";
+            "nacked_signer_count" => nacked_signer_count,
+            "burn_block_height" => coordinator_metadata.burn_block_height,
+        );
+        self.send_or_queue_message(SignerMessage::NackReport {
+            sender_id: self.signer_id,
+            coordinator_metadata,
+            nacked_signer_count,
+        });
+    }
+
+    /// Back off once another signer reports NACKs from at least the threshold percentage of
+    /// the signer set
+    fn handle_nack_report(&mut self, sender_id: u32, nacked_signer_count: usize, now: Instant) {
+        let Some(policy) = self.stale_node_nack_policy else {
+            return;
+        };
+        if sender_id == self.signer_id {
+            return;
+        }
+        let num_signers = self.signer_address_ids.len();
+        let threshold_percent = usize::try_from(policy.nack_threshold_percent)
+            .expect("FATAL: nack threshold percent exceeds usize::MAX");
+        if nacked_signer_count.saturating_mul(100) < num_signers.saturating_mul(threshold_percent) {
+            return;
+        }
+        let back_off_until = now + policy.back_off_duration;
+        if self
+            .back_off_until
+            .is_some_and(|current| current >= back_off_until)
+        {
+            return;
+        }
+        signer_warn!(
+            self.logger,
+            "Backing off, as a signer reported NACKs from enough of the signer set";
+            "sender_id" => sender_id,
+            "nacked_signer_count" => nacked_signer_count,
+            "num_signers" => num_signers,
+            "back_off_ms" => policy.back_off_duration.as_millis(),
+        );
+        if self.back_off_until.map_or(true, |current| current <= now) {
+            self.alert(
+                AlertEvent::NackBackOff,
+                format!(
+                    "Signer {sender_id} reported NACKs from {nacked_signer_count} of {num_signers} signers. Backing off for {}ms.",
+                    policy.back_off_duration.as_millis()
+                ),
+            );
+        }
+        self.back_off_until = Some(back_off_until);
+    }
+
+    /// Validate a nonce request, updating its message appropriately.
+    /// If the request is for a block, we will update the request message
+    /// as either a hash indicating a vote no or the signature hash indicating a vote yes
+    /// Returns whether the request is valid or not
+    fn validate_nonce_request(
+        &mut self,
+        stacks_client: &StacksClient,
+        nonce_request: &mut NonceRequest,
+    ) -> Option<BlockInfo> {
+        let Some(block_proposal) =
+            BlockProposal::consensus_deserialize(&mut nonce_request.message.as_slice()).ok()
+        else {
+            // We currently reject anything that is not a valid block proposal
+            signer_warn!(
+                self.logger,
+                "Received a nonce request for an unknown message stream. Reject it.",
+            );
+            return None;
         };
         if block_proposal.reward_cycle != self.reward_cycle {
             // We are not signing for this reward cycle. Reject the block
-            warn!(
-                "{self}: Received a nonce request for a different reward cycle. Reject it.";
+            signer_warn!(
+                self.logger,
+                "Received a nonce request for a different reward cycle. Reject it.";
                 "requested_reward_cycle" => block_proposal.reward_cycle,
             );
             return None;
         }
+        self.forward_transaction_to_peers(stacks_client, &block_proposal.block);
         // TODO: could add a check to ignore an old burn block height if we know its oudated. Would require us to store the burn block height we last saw on the side.
         let signer_signature_hash = block_proposal.block.header.signer_signature_hash();
         let Some(mut block_info) = self
@@ -930,23 +2962,30 @@ impl Signer {
             .block_lookup(self.reward_cycle, &signer_signature_hash)
             .expect("Failed to connect to signer DB")
         else {
-            debug!(
-                "{self}: received a nonce request for a new block. Submit block for validation. ";
+            if self.detect_duplicate_block_proposals(&block_proposal.block) {
+                let block_rejection =
+                    BlockRejection::new(signer_signature_hash, RejectCode::ConflictingProposal);
+                self.send_or_queue_message(block_rejection.into());
+                return None;
+            }
+            signer_debug!(
+                self.logger,
+                "received a nonce request for a new block. Submit block for validation. ";
                 "signer_sighash" => %signer_signature_hash,
             );
-            let block_info = BlockInfo::new_with_request(block_proposal, nonce_request.clone());
-            stacks_client
-                .submit_block_for_validation(block_info.block.clone())
-                .unwrap_or_else(|e| {
-                    warn!("{self}: Failed to submit block for validation: {e:?}",);
-                });
+            let mut block_info = BlockInfo::new_with_request(block_proposal, nonce_request.clone());
+            if !self.submit_block_for_validation(stacks_client, &mut block_info) {
+                return None;
+            }
+            self.cache_nonce_request(signer_signature_hash, nonce_request);
             return Some(block_info);
         };
 
         if block_info.valid.is_none() {
             // We have not yet received validation from the stacks node. Cache the request and wait for validation
-            debug!("{self}: We have yet to receive validation from the stacks node for a nonce request. Cache the nonce request and wait for block validation...");
+            signer_debug!(self.logger, "We have yet to receive validation from the stacks node for a nonce request. Cache the nonce request and wait for block validation...");
             block_info.nonce_request = Some(nonce_request.clone());
+            self.cache_nonce_request(signer_signature_hash, nonce_request);
             return Some(block_info);
         }
 
@@ -954,6 +2993,331 @@ impl Signer {
         Some(block_info)
     }
 
+    /// Cache the vote transactions of the reward cycle's signers found in a transactions message,
+    /// keeping the one with the lowest nonce per signer. A signer may change its vote within a
+    /// round until a key is approved, so a later vote for another key in one of the cached
+    /// transaction's rounds supersedes the cached one rather than being dropped as a duplicate.
+    fn cache_vote_transactions(&mut self, transactions: &[StacksTransaction]) {
+        for transaction in transactions {
+            let origin_address = transaction.origin_address();
+            if transaction.is_mainnet() != self.mainnet
+                || !self.signer_address_ids.contains_key(&origin_address)
+            {
+                continue;
+            }
+            let Some(votes) = NakamotoSigners::parse_votes_for_aggregate_public_keys(transaction)
+            else {
+                continue;
+            };
+            let replaces_known =
+                self.known_vote_transactions
+                    .get(&origin_address)
+                    .map_or(true, |known| {
+                        let supersedes_known =
+                            NakamotoSigners::parse_votes_for_aggregate_public_keys(known)
+                                .is_some_and(|known_votes| {
+                                    transaction.get_origin_nonce() > known.get_origin_nonce()
+                                        && votes.iter().any(|vote| {
+                                            known_votes.iter().any(|known_vote| {
+                                                known_vote.reward_cycle == vote.reward_cycle
+                                                    && known_vote.voting_round == vote.voting_round
+                                                    && known_vote.aggregate_key
+                                                        != vote.aggregate_key
+                                            })
+                                        })
+                                });
+                        supersedes_known
+                            || (transaction.get_origin_nonce(), transaction.txid())
+                                < (known.get_origin_nonce(), known.txid())
+                    });
+            if replaces_known {
+                self.known_vote_transactions
+                    .insert(origin_address, transaction.clone());
+            }
+        }
+    }
+
+    /// Forward the vote transactions we know of to stacker-db if the block of a nonce request
+    /// misses some of them. The requesting signer's view of the other signers' transactions is
+    /// then likely outdated, and it can catch up by reading them from our slot.
+    /// Returns whether any transaction was forwarded.
+    pub fn forward_transaction_to_peers(
+        &mut self,
+        stacks_client: &StacksClient,
+        block: &NakamotoBlock,
+    ) -> bool {
+        let block_txids: HashSet<_> = block.txs.iter().map(|tx| tx.txid()).collect();
+        if self
+            .known_vote_transactions
+            .values()
+            .all(|tx| block_txids.contains(&tx.txid()))
+        {
+            return false;
+        }
+        // Drop the transactions made obsolete since we cached them
+        let origin_addresses: Vec<_> = self.known_vote_transactions.keys().copied().collect();
+        let account_nonces = self.get_account_nonces(stacks_client, &origin_addresses);
+        self.known_vote_transactions.retain(|address, tx| {
+            account_nonces
+                .get(address)
+                .map_or(true, |nonce| tx.get_origin_nonce() >= *nonce)
+        });
+        let missing_transactions: Vec<_> = self
+            .known_vote_transactions
+            .values()
+            .filter(|tx| !block_txids.contains(&tx.txid()))
+            .cloned()
+            .collect();
+        if missing_transactions
+            .iter()
+            .all(|tx| self.forwarded_txids.contains(&tx.txid()))
+        {
+            // Either nothing is missing anymore, or we already forwarded what is
+            return false;
+        }
+        // Keep our own transactions in our slot alongside the forwarded ones
+        let mut transactions = match self.stackerdb.get_current_transactions() {
+            Ok(transactions) => transactions,
+            Err(e) => {
+                signer_warn!(self.logger, "Failed to read our transactions from stacker-db. Not forwarding known vote transactions: {e:?}");
+                return false;
+            }
+        };
+        let own_txids: HashSet<_> = transactions.iter().map(|tx| tx.txid()).collect();
+        transactions.extend(
+            missing_transactions
+                .into_iter()
+                .filter(|tx| !own_txids.contains(&tx.txid())),
+        );
+        let txids: HashSet<_> = transactions.iter().map(|tx| tx.txid()).collect();
+        signer_info!(
+            self.logger,
+            "Block proposal misses known vote transactions. Forwarding them to stacker-db.";
+            "signer_sighash" => %block.header.signer_signature_hash(),
+            "num_transactions" => transactions.len(),
+        );
+        let result = self
+            .stackerdb
+            .send_message_with_retry(SignerMessage::Transactions(transactions))
+            .and_then(|outcome| outcome.into_ack());
+        if let Err(e) = result {
+            signer_warn!(
+                self.logger,
+                "Failed to forward known vote transactions to stacker-db: {e:?}"
+            );
+            return false;
+        }
+        self.forwarded_txids = txids;
+        true
+    }
+
+    /// Check whether the miner already proposed a different block at the same height of the
+    /// same tenure. A miner doing so may be trying to get conflicting blocks signed.
+    pub fn detect_duplicate_block_proposals(&self, block: &NakamotoBlock) -> bool {
+        let signer_signature_hash = block.header.signer_signature_hash();
+        let blocks_in_tenure = match self
+            .signer_db
+            .get_blocks_in_tenure(&block.header.consensus_hash)
+        {
+            Ok(blocks_in_tenure) => blocks_in_tenure,
+            Err(e) => {
+                signer_warn!(
+                    self.logger,
+                    "Failed to look up the blocks of the proposed block's tenure: {e:?}"
+                );
+                return false;
+            }
+        };
+        let Some(conflicting_block) = blocks_in_tenure.iter().find(|block_info| {
+            block_info.block.header.chain_length == block.header.chain_length
+                && block_info.signer_signature_hash() != signer_signature_hash
+        }) else {
+            return false;
+        };
+        signer_warn!(
+            self.logger,
+            "Miner proposed a different block at the same height of its tenure. It may be malicious.";
+            "signer_sighash" => %signer_signature_hash,
+            "conflicting_signer_sighash" => %conflicting_block.signer_signature_hash(),
+            "consensus_hash" => %block.header.consensus_hash,
+            "chain_length" => block.header.chain_length,
+        );
+        self.alert(
+            AlertEvent::ConflictingProposal,
+            format!(
+                "The miner of tenure {} proposed blocks {signer_signature_hash} and {} at height {}",
+                block.header.consensus_hash,
+                conflicting_block.signer_signature_hash(),
+                block.header.chain_length
+            ),
+        );
+        true
+    }
+
+    /// Verify the median transaction fee of a block does not exceed the maximum we accept.
+    /// Protects the signer's funds against blocks stuffed with very high fee transactions.
+    pub fn verify_block_fee_rate(&self, block: &NakamotoBlock) -> bool {
+        median_tx_fee(block).map_or(true, |median_fee| {
+            median_fee <= self.max_acceptable_median_fee_rate
+        })
+    }
+
+    /// Validate the coinbase of a block that starts a tenure. The tenure change and coinbase must
+    /// be well-formed, the coinbase must carry a VRF proof, and the block must be signed by the
+    /// current miner, if we know its key yet. Blocks that do not start a tenure have no coinbase.
+    /// The VRF proof itself is verified by the stacks node, as it needs the miner's VRF key.
+    pub fn validate_block_coinbase_tx(&self, block: &NakamotoBlock) -> bool {
+        match block.is_wellformed_tenure_start_block() {
+            Ok(true) => {}
+            Ok(false) => return true,
+            Err(()) => {
+                signer_debug!(
+                    self.logger,
+                    "Block has a malformed tenure change or coinbase transaction"
+                );
+                return false;
+            }
+        }
+        let Some(miner_key) = self.miner_key.as_ref() else {
+            signer_debug!(
+                self.logger,
+                "Do not know the current miner yet. Cannot check who mined the block."
+            );
+            return true;
+        };
+        // The coinbase is signed with the miner's burnchain key, while its blocks and messages
+        // are signed with its mining key.
+        let Some(block_miner_key) = block.header.recover_miner_pk() else {
+            signer_debug!(self.logger, "Failed to recover the miner key of the block");
+            return false;
+        };
+        if block_miner_key.to_bytes_compressed() != miner_key.to_bytes() {
+            signer_debug!(self.logger, "Block was not mined by the current miner";
+                "block_miner_key" => %block_miner_key.to_hex(),
+                "miner_key" => ?miner_key,
+            );
+            return false;
+        }
+        true
+    }
+
+    /// Check whether a block, proposed at the given burn height, must be rejected as it was not
+    /// mined by the miner of its tenure, i.e. of its consensus hash. The tenure's miner is the
+    /// miner of the block starting it, or of the first block of the tenure we see if we missed its
+    /// start, as inferred from the block's miner signature.
+    pub fn reject_blocks_from_unknown_miner(
+        &mut self,
+        block: &NakamotoBlock,
+        burn_height: u64,
+    ) -> bool {
+        let Some(block_miner_key) = block.header.recover_miner_pk() else {
+            signer_debug!(self.logger, "Failed to recover the miner key of the block");
+            return true;
+        };
+        let block_miner = StacksAddress::p2pkh(self.mainnet, &block_miner_key);
+        let tenure = &block.header.consensus_hash;
+        // Only the block starting a tenure may set the miner of its own tenure
+        let is_tenure_start = matches!(block.is_wellformed_tenure_start_block(), Ok(true));
+        if let Some((tenure_miner, _)) = self.tenure_miners.get(tenure) {
+            if *tenure_miner != block_miner && !is_tenure_start {
+                signer_debug!(self.logger, "Block was not mined by the miner of its tenure";
+                    "block_miner" => %block_miner,
+                    "tenure_miner" => %tenure_miner,
+                    "consensus_hash" => %tenure,
+                );
+                return true;
+            }
+        }
+        self.tenure_miners
+            .insert(tenure.clone(), (block_miner, burn_height));
+        false
+    }
+
+    /// Forget the miners of the tenures we have not seen blocks of for
+    /// `TENURE_MINER_RETENTION_BURN_BLOCKS` burn blocks as of the given burn height
+    fn forget_stale_tenure_miners(&mut self, burn_height: u64) {
+        self.tenure_miners.retain(|_, (_, last_seen)| {
+            last_seen.saturating_add(TENURE_MINER_RETENTION_BURN_BLOCKS) > burn_height
+        });
+    }
+
+    /// Verify the contents of a block the stacks node validated: its coinbase, its miner, its fee
+    /// rate, its parent and its transactions. Broadcasts a rejection of the block if any check fails.
+    fn verify_block_contents(
+        &mut self,
+        stacks_client: &StacksClient,
+        block: &NakamotoBlock,
+        burn_height: u64,
+    ) -> bool {
+        let signer_signature_hash = block.header.signer_signature_hash();
+        if !self.validate_block_coinbase_tx(block) {
+            signer_warn!(self.logger, "Broadcasting a block rejection due to an invalid coinbase...";
+                "signer_sighash" => %signer_signature_hash,
+            );
+            let block_rejection =
+                BlockRejection::new(signer_signature_hash, RejectCode::InvalidCoinbase);
+            self.send_or_queue_message(block_rejection.into());
+            return false;
+        }
+        if self.reject_blocks_from_unknown_miner(block, burn_height) {
+            signer_warn!(self.logger, "Broadcasting a block rejection due to an unknown miner...";
+                "signer_sighash" => %signer_signature_hash,
+            );
+            let block_rejection =
+                BlockRejection::new(signer_signature_hash, RejectCode::UnknownMiner);
+            self.send_or_queue_message(block_rejection.into());
+            return false;
+        }
+        if !self.verify_block_fee_rate(block) {
+            let median_fee = median_tx_fee(block).unwrap_or_default();
+            signer_warn!(self.logger, "Broadcasting a block rejection due to an excessive median transaction fee...";
+                "signer_sighash" => %signer_signature_hash,
+                "median_fee_ustx" => median_fee,
+                "max_acceptable_median_fee_rate" => self.max_acceptable_median_fee_rate,
+            );
+            let block_rejection = BlockRejection::new(
+                signer_signature_hash,
+                RejectCode::ExcessiveFeeRate(median_fee),
+            );
+            self.send_or_queue_message(block_rejection.into());
+            return false;
+        }
+        let reject_code = match self.verify_block_ancestor(stacks_client, block) {
+            Ok(true) => None,
+            Ok(false) => {
+                signer_warn!(self.logger, "Broadcasting a block rejection due to an unknown parent block...";
+                    "signer_sighash" => %signer_signature_hash,
+                    "parent_block_id" => %block.header.parent_block_id,
+                );
+                Some(RejectCode::UnknownParent)
+            }
+            Err(e) => {
+                // Cannot verify the block's parent. Reject it.
+                signer_warn!(
+                    self.logger,
+                    "Broadcasting a block rejection due to signer connectivity issues: {e:?}"
+                );
+                Some(RejectCode::ConnectivityIssues)
+            }
+        };
+        if let Some(reject_code) = reject_code {
+            let block_rejection = BlockRejection::new(signer_signature_hash, reject_code);
+            self.send_or_queue_message(block_rejection.into());
+            return false;
+        }
+        self.verify_block_transactions(stacks_client, block)
+    }
+
+    /// Verify the parent of a block is a block known to the stacks node
+    pub fn verify_block_ancestor(
+        &self,
+        stacks_client: &StacksClient,
+        block: &NakamotoBlock,
+    ) -> Result<bool, ClientError> {
+        let parent_block = stacks_client.get_block_by_id(&block.header.parent_block_id)?;
+        Ok(parent_block.is_some())
+    }
+
     /// Verify the transactions in a block are as expected
     fn verify_block_transactions(
         &mut self,
@@ -967,7 +3331,7 @@ impl Signer {
         if approved_aggregate_public_key.is_some() {
             // We do not enforce a block contain any transactions except the aggregate votes when it is NOT already set for the upcoming signers' reward cycle
             // Otherwise it is a waste of block space and time to enforce as the desired outcome has been reached.
-            debug!("{self}: Already have an aggregate key for the next signer set's reward cycle ({}). Skipping transaction verification...", next_reward_cycle);
+            signer_debug!(self.logger, "Already have an aggregate key for the next signer set's reward cycle ({}). Skipping transaction verification...", next_reward_cycle);
             return true;
         }
         if let Ok(expected_transactions) = self.get_expected_transactions(stacks_client) {
@@ -978,48 +3342,83 @@ impl Signer {
                 .into_iter()
                 .filter_map(|tx| {
                     if !block_tx_hashset.contains(&tx.txid()) {
-                        debug!("{self}: expected txid {} is in the block", &tx.txid());
+                        signer_debug!(self.logger, "missing expected txid {}", &tx.txid());
                         Some(tx)
                     } else {
-                        debug!("{self}: missing expected txid {}", &tx.txid());
+                        signer_debug!(self.logger, "expected txid {} is in the block", &tx.txid());
                         None
                     }
                 })
                 .collect::<Vec<_>>();
-            let is_valid = missing_transactions.is_empty();
+            let is_valid = !self.should_reject_missing_transactions(&missing_transactions);
             if !is_valid {
-                debug!("{self}: Broadcasting a block rejection due to missing expected transactions...");
+                signer_debug!(
+                    self.logger,
+                    "Broadcasting a block rejection due to missing expected transactions..."
+                );
                 let block_rejection = BlockRejection::new(
                     block.header.signer_signature_hash(),
                     RejectCode::MissingTransactions(missing_transactions),
                 );
                 // Submit signature result to miners to observe
-                if let Err(e) = self
-                    .stackerdb
-                    .send_message_with_retry(block_rejection.into())
-                {
-                    warn!("{self}: Failed to send block rejection to stacker-db: {e:?}",);
-                }
+                self.send_or_queue_message(block_rejection.into());
             }
             is_valid
         } else {
             // Failed to connect to the stacks node to get transactions. Cannot validate the block. Reject it.
-            debug!("{self}: Broadcasting a block rejection due to signer connectivity issues...",);
+            signer_debug!(
+                self.logger,
+                "Broadcasting a block rejection due to signer connectivity issues...",
+            );
             let block_rejection = BlockRejection::new(
                 block.header.signer_signature_hash(),
                 RejectCode::ConnectivityIssues,
             );
             // Submit signature result to miners to observe
-            if let Err(e) = self
-                .stackerdb
-                .send_message_with_retry(block_rejection.into())
-            {
-                warn!("{self}: Failed to send block submission to stacker-db: {e:?}",);
-            }
+            self.send_or_queue_message(block_rejection.into());
             false
         }
     }
 
+    /// Whether to reject a block proposal missing the given expected transactions, according
+    /// to the missing transaction policy
+    fn should_reject_missing_transactions(
+        &mut self,
+        missing_transactions: &[StacksTransaction],
+    ) -> bool {
+        let txids: Vec<_> = missing_transactions.iter().map(|tx| tx.txid()).collect();
+        let consecutive_proposals = self
+            .signer_db
+            .record_missing_transactions(self.reward_cycle, &txids)
+            .unwrap_or_else(|e| {
+                signer_warn!(
+                    self.logger,
+                    "Failed to record the missing expected transactions: {e:?}"
+                );
+                0
+            });
+        if txids.is_empty() {
+            return false;
+        }
+        let reject = match self.missing_transaction_policy {
+            MissingTransactionPolicy::Reject => true,
+            MissingTransactionPolicy::AcceptAndWarn => false,
+            MissingTransactionPolicy::RejectAfterNBlocks(max_proposals) => {
+                consecutive_proposals >= max_proposals
+            }
+        };
+        if !reject {
+            signer_warn!(
+                self.logger,
+                "Accepting a block proposal missing expected transactions";
+                "missing_txids" => ?txids,
+                "consecutive_proposals" => consecutive_proposals,
+                "policy" => %self.missing_transaction_policy,
+            );
+        }
+        reject
+    }
+
     /// Get transactions from stackerdb for the given addresses and account nonces, filtering out any malformed transactions
     fn get_signer_transactions(
         &mut self,
@@ -1045,7 +3444,10 @@ impl Signer {
         stacks_client: &StacksClient,
     ) -> Result<Vec<StacksTransaction>, ClientError> {
         if self.next_signer_slot_ids.is_empty() {
-            debug!("{self}: No next signers. Skipping transaction retrieval.",);
+            signer_debug!(
+                self.logger,
+                "No next signers. Skipping transaction retrieval.",
+            );
             return Ok(vec![]);
         }
         // Get all the account nonces for the next signers
@@ -1068,9 +3470,17 @@ impl Signer {
     fn determine_vote(&self, block_info: &mut BlockInfo, nonce_request: &mut NonceRequest) {
         let rejected = !block_info.valid.unwrap_or(false);
         if rejected {
-            debug!("{self}: Rejecting block {}", block_info.block.block_id());
+            signer_debug!(
+                self.logger,
+                "Rejecting block {}",
+                block_info.block.block_id()
+            );
         } else {
-            debug!("{self}: Accepting block {}", block_info.block.block_id());
+            signer_debug!(
+                self.logger,
+                "Accepting block {}",
+                block_info.block.block_id()
+            );
         }
         let block_vote = NakamotoBlockVote {
             signer_signature_hash: block_info.block.header.signer_signature_hash(),
@@ -1095,6 +3505,9 @@ impl Signer {
     ) -> Option<Packet> {
         // We only care about verified wsts packets. Ignore anything else.
         if packet.verify(&self.state_machine.public_keys, coordinator_public_key) {
+            if !self.verify_packet_timestamp(&packet.msg, Instant::now()) {
+                return None;
+            }
             match &mut packet.msg {
                 Message::SignatureShareRequest(request) => {
                     if !self.validate_signature_share_request(request) {
@@ -1102,18 +3515,21 @@ impl Signer {
                     }
                 }
                 Message::NonceRequest(request) => {
-                    let Some(updated_block_info) =
+                    let Some(mut updated_block_info) =
                         self.validate_nonce_request(stacks_client, request)
                     else {
-                        warn!("Failed to validate and parse nonce request");
+                        signer_warn!(self.logger, "Failed to validate and parse nonce request");
                         return None;
                     };
+                    updated_block_info
+                        .timings
+                        .record(SignRoundStep::NonceRound, now_ms());
                     self.signer_db
                         .insert_block(&updated_block_info)
                         .unwrap_or_else(|_| panic!("{self}: Failed to insert block in DB"));
                     let process_request = updated_block_info.vote.is_some();
                     if !process_request {
-                        debug!("Failed to validate nonce request");
+                        signer_debug!(self.logger, "Failed to validate nonce request");
                         return None;
                     }
                 }
@@ -1123,14 +3539,117 @@ impl Signer {
             }
             Some(packet)
         } else {
-            debug!(
-                "{self}: Failed to verify wsts packet with {}: {packet:?}",
+            signer_debug!(
+                self.logger,
+                "Failed to verify wsts packet with {}: {packet:?}",
                 coordinator_public_key
             );
             None
         }
     }
 
+    /// Check the nonce of a verified signing packet against the current signing round, to reject
+    /// packets replayed from earlier iterations and packets of iterations we were not asked to
+    /// sign for longer than the packet future tolerance. Requests of the coordinator advance the
+    /// round. Packets of other rounds are left to the packet round guard. Returns whether the
+    /// packet should be processed.
+    fn verify_packet_timestamp(&mut self, msg: &Message, now: Instant) -> bool {
+        let (signer_id, sign_id, nonce) = match msg {
+            Message::NonceRequest(request) => {
+                return self.record_sign_request(request.sign_id, request.sign_iter_id);
+            }
+            Message::SignatureShareRequest(request) => {
+                let is_replayed = self.sign_round_nonces.map_or(false, |round| {
+                    round.sign_id == request.sign_id && request.sign_iter_id < round.first_nonce
+                });
+                if is_replayed {
+                    signer_warn!(self.logger, "Dropping a replayed signature share request";
+                        "sign_id" => request.sign_id,
+                        "sign_iter_id" => request.sign_iter_id,
+                    );
+                }
+                return !is_replayed;
+            }
+            Message::NonceResponse(response) => {
+                (response.signer_id, response.sign_id, response.sign_iter_id)
+            }
+            Message::SignatureShareResponse(response) => {
+                (response.signer_id, response.sign_id, response.sign_iter_id)
+            }
+            // DKG packets carry no signing nonce
+            _ => return true,
+        };
+        let Some(round) = self
+            .sign_round_nonces
+            .filter(|round| round.sign_id == sign_id)
+        else {
+            return true;
+        };
+        let (latest_nonce, first_seen) = self
+            .packet_window
+            .get(&signer_id)
+            .copied()
+            .unwrap_or((round.first_nonce, now));
+        if nonce < round.first_nonce || nonce < latest_nonce {
+            signer_warn!(self.logger, "Dropping a replayed signing packet";
+                "sender_id" => signer_id,
+                "sign_id" => sign_id,
+                "nonce" => nonce,
+                "first_nonce" => round.first_nonce,
+                "latest_sender_nonce" => latest_nonce,
+            );
+            return false;
+        }
+        let first_seen = if nonce > latest_nonce {
+            now
+        } else {
+            first_seen
+        };
+        self.packet_window.insert(signer_id, (nonce, first_seen));
+        if nonce > round.latest_nonce
+            && now.saturating_duration_since(first_seen) > self.packet_future_tolerance
+        {
+            signer_warn!(self.logger, "Dropping a future-dated signing packet";
+                "sender_id" => signer_id,
+                "sign_id" => sign_id,
+                "nonce" => nonce,
+                "latest_nonce" => round.latest_nonce,
+            );
+            return false;
+        }
+        true
+    }
+
+    /// Advance the current signing round to the given request. A request of another round
+    /// starts a new one. Returns false if the request was replayed from an earlier iteration.
+    fn record_sign_request(&mut self, sign_id: u64, nonce: u64) -> bool {
+        match self.sign_round_nonces {
+            Some(round) if round.sign_id == sign_id => {
+                if nonce < round.first_nonce {
+                    signer_warn!(self.logger, "Dropping a replayed nonce request";
+                        "sign_id" => sign_id,
+                        "nonce" => nonce,
+                        "first_nonce" => round.first_nonce,
+                    );
+                    return false;
+                }
+                self.sign_round_nonces = Some(SignRoundNonces {
+                    latest_nonce: round.latest_nonce.max(nonce),
+                    ..round
+                });
+            }
+            _ => {
+                self.sign_round_nonces = Some(SignRoundNonces {
+                    sign_id,
+                    first_nonce: nonce,
+                    latest_nonce: nonce,
+                });
+                self.packet_window.clear();
+            }
+        }
+        true
+    }
+
     /// Processes the operation results, broadcasting block acceptance or rejection messages
     /// and DKG vote results accordingly
     fn process_operation_results(
@@ -1143,36 +3662,103 @@ impl Signer {
             match operation_result {
                 OperationResult::Sign(signature) => {
                     crate::monitoring::increment_operation_results("sign");
-                    debug!("{self}: Received signature result");
+                    SignerCounters::increment(&mut self.counters.sign_rounds_completed);
+                    signer_debug!(self.logger, "Received signature result");
                     self.process_signature(signature);
                 }
                 OperationResult::SignTaproot(_) => {
                     crate::monitoring::increment_operation_results("sign_taproot");
-                    debug!("{self}: Received a signature result for a taproot signature. Nothing to broadcast as we currently sign blocks with a FROST signature.");
+                    signer_debug!(self.logger, "Received a signature result for a taproot signature. Nothing to broadcast as we currently sign blocks with a FROST signature.");
                 }
                 OperationResult::Dkg(aggregate_key) => {
                     crate::monitoring::increment_operation_results("dkg");
+                    SignerCounters::increment(&mut self.counters.dkg_rounds_completed);
+                    self.log_dkg_participation();
+                    if let Err(e) = self.signer_db.record_dkg_round(
+                        self.reward_cycle,
+                        self.coordinator.current_dkg_id,
+                        aggregate_key,
+                    ) {
+                        signer_warn!(self.logger, "Failed to record the DKG round: {e:?}");
+                    }
                     self.process_dkg(stacks_client, aggregate_key);
                 }
                 OperationResult::SignError(e) => {
                     crate::monitoring::increment_operation_results("sign_error");
-                    warn!("{self}: Received a Sign error: {e:?}");
+                    SignerCounters::increment(&mut self.counters.sign_rounds_failed);
+                    signer_warn!(self.logger, "Received a Sign error: {e:?}");
                     self.process_sign_error(e);
                 }
                 OperationResult::DkgError(e) => {
                     crate::monitoring::increment_operation_results("dkg_error");
-                    warn!("{self}: Received a DKG error: {e:?}");
+                    SignerCounters::increment(&mut self.counters.dkg_rounds_failed);
+                    signer_warn!(self.logger, "Received a DKG error: {e:?}");
+                    self.alert(AlertEvent::DkgFailure, format!("DKG round failed: {e:?}"));
+                    self.log_dkg_participation();
                     // TODO: process these errors and track malicious signers to report
                 }
             }
         }
     }
 
+    /// The fraction of the reward cycle's signers that sent their public shares in the latest
+    /// DKG round
+    pub fn compute_dkg_participation_rate(&self) -> f64 {
+        let total_signers = self.signer_addresses.len();
+        if total_signers == 0 {
+            return 0.0;
+        }
+        self.dkg_history.participants.len() as f64 / total_signers as f64
+    }
+
+    /// Log the participation of the signers in the latest DKG round, warning if too few took part
+    fn log_dkg_participation(&self) {
+        let participation_rate = self.compute_dkg_participation_rate();
+        if participation_rate < DKG_PARTICIPATION_WARN_THRESHOLD {
+            signer_warn!(
+                self.logger,
+                "Low signer participation in DKG round";
+                "dkg_id" => self.dkg_history.dkg_id,
+                "participation_rate" => participation_rate,
+                "participants" => ?self.dkg_history.participants,
+            );
+        } else {
+            signer_info!(
+                self.logger,
+                "DKG round participation";
+                "dkg_id" => self.dkg_history.dkg_id,
+                "participation_rate" => participation_rate,
+                "participants" => ?self.dkg_history.participants,
+            );
+        }
+    }
+
+    /// Whether the aggregate public key of a DKG round is a usable secp256k1 point: it must not
+    /// be the point at infinity and its compressed encoding must decode back to the same point.
+    pub fn validate_aggregate_public_key_curve(point: &Point) -> bool {
+        if *point == Point::default() {
+            return false;
+        }
+        Point::try_from(&point.compress()).is_ok_and(|decoded| decoded == *point)
+    }
+
     /// Process a dkg result by broadcasting a vote to the stacks node
     fn process_dkg(&mut self, stacks_client: &StacksClient, dkg_public_key: &Point) {
+        if !Self::validate_aggregate_public_key_curve(dkg_public_key) {
+            signer_error!(
+                self.logger,
+                "DKG produced a malformed aggregate public key. Not voting for it.";
+                "dkg_public_key" => %dkg_public_key
+            );
+            return;
+        }
+        if self.handle_aggregate_key_mismatch(stacks_client, dkg_public_key) {
+            return;
+        }
         let mut dkg_results_bytes = vec![];
-        debug!(
-            "{self}: Received DKG result. Broadcasting vote to the stacks node...";
+        signer_debug!(
+            self.logger,
+            "Received DKG result. Broadcasting vote to the stacks node...";
             "dkg_public_key" => %dkg_public_key
         );
         if let Err(e) = SignerMessage::serialize_dkg_result(
@@ -1180,14 +3766,20 @@ impl Signer {
             dkg_public_key,
             self.coordinator.party_polynomials.iter(),
         ) {
-            error!("{}: Failed to serialize DKGResults message for StackerDB, will continue operating.", self.signer_id;
-                   "error" => %e);
+            signer_error!(
+                self.logger,
+                "Failed to serialize DKGResults message for StackerDB, will continue operating.";
+                "error" => %e,
+            );
         } else if let Err(e) = self
             .stackerdb
             .send_message_bytes_with_retry(&MessageSlotID::DkgResults, dkg_results_bytes)
         {
-            error!("{}: Failed to send DKGResults message to StackerDB, will continue operating.", self.signer_id;
-                       "error" => %e);
+            signer_error!(
+                self.logger,
+                "Failed to send DKGResults message to StackerDB, will continue operating.";
+                "error" => %e,
+            );
         }
 
         // Get our current nonce from the stacks node and compare it against what we have sitting in the stackerdb instance
@@ -1195,10 +3787,10 @@ impl Signer {
         // Retreieve ALL account nonces as we may have transactions from other signers in our stackerdb slot that we care about
         let account_nonces = self.get_account_nonces(stacks_client, &self.signer_addresses);
         let account_nonce = account_nonces.get(signer_address).unwrap_or(&0);
-        let signer_transactions = self
+        let mut signer_transactions = self
             .get_signer_transactions(&account_nonces)
             .map_err(|e| {
-                error!("{self}: Unable to get signer transactions: {e:?}.");
+                signer_error!(self.logger, "Unable to get signer transactions: {e:?}.");
             })
             .unwrap_or_default();
         // If we have a transaction in the stackerdb slot, we need to increment the nonce hence the +1, else should use the account nonce
@@ -1209,7 +3801,25 @@ impl Signer {
         let epoch = stacks_client
             .get_node_epoch()
             .unwrap_or(StacksEpochId::Epoch24);
-        match self.build_dkg_vote(stacks_client, &epoch, next_nonce, *dkg_public_key) {
+        // In epoch 3.0, votes are only broadcast to stackerDB, where our pending votes can be
+        // replaced without bumping their fee
+        let pending_votes = if epoch >= StacksEpochId::Epoch30 {
+            self.take_pending_dkg_votes(signer_address, &mut signer_transactions)
+        } else {
+            None
+        };
+        let new_transaction = match pending_votes {
+            Some((nonce, mut votes)) => {
+                votes.insert(self.coordinator.current_dkg_id, *dkg_public_key);
+                signer_debug!(self.logger, "Voting for the unconfirmed DKG rounds at once";
+                    "voting_rounds" => ?votes.keys().collect::<Vec<_>>(),
+                    "nonce" => nonce
+                );
+                self.build_dkg_batch_vote(stacks_client, &epoch, nonce, votes)
+            }
+            None => self.build_dkg_vote(stacks_client, &epoch, next_nonce, *dkg_public_key),
+        };
+        match new_transaction {
             Ok(new_transaction) => {
                 if let Err(e) = self.broadcast_dkg_vote(
                     stacks_client,
@@ -1217,19 +3827,166 @@ impl Signer {
                     signer_transactions,
                     new_transaction,
                 ) {
-                    warn!(
-                        "{self}: Failed to broadcast DKG public key vote ({dkg_public_key:?}): {e:?}"
+                    signer_warn!(
+                        self.logger,
+                        "Failed to broadcast DKG public key vote ({dkg_public_key:?}): {e:?}"
                     );
                 }
             }
             Err(e) => {
-                warn!(
-                    "{self}: Failed to build DKG public key vote ({dkg_public_key:?}) transaction: {e:?}."
+                signer_warn!(
+                    self.logger,
+                    "Failed to build DKG public key vote ({dkg_public_key:?}) transaction: {e:?}."
                 );
             }
         }
     }
 
+    /// Compare the given DKG result against the aggregate key of the previous DKG round. If the keys
+    /// differ and both received votes on chain, the signer set is split between them (e.g. by a
+    /// network partition) and neither can be trusted to reach the threshold: broadcast a
+    /// `DkgConflict` and reset the DKG round so that a fresh one is triggered.
+    /// Returns whether a conflict was found.
+    pub fn handle_aggregate_key_mismatch(
+        &mut self,
+        stacks_client: &StacksClient,
+        dkg_public_key: &Point,
+    ) -> bool {
+        let round = self.coordinator.current_dkg_id;
+        let Some((previous_round, previous_key)) =
+            self.last_dkg_result.replace((round, *dkg_public_key))
+        else {
+            return false;
+        };
+        if previous_key == *dkg_public_key {
+            return false;
+        }
+        match self.are_both_keys_voted(
+            stacks_client,
+            (previous_round, &previous_key),
+            (round, dkg_public_key),
+        ) {
+            Ok(true) => {}
+            Ok(false) => {
+                signer_debug!(
+                    self.logger,
+                    "DKG produced a new aggregate key, but the previous one has no competing votes";
+                    "previous_round" => previous_round,
+                    "round" => round,
+                );
+                return false;
+            }
+            Err(e) => {
+                signer_warn!(
+                    self.logger,
+                    "Failed to check the votes for the aggregate keys of the latest DKG rounds";
+                    "error" => ?e,
+                );
+                return false;
+            }
+        }
+        signer_warn!(
+            self.logger,
+            "Two DKG rounds produced different aggregate keys that both received votes. Forcing a new DKG round.";
+            "previous_round" => previous_round,
+            "previous_key" => %previous_key,
+            "round" => round,
+            "aggregate_key" => %dkg_public_key,
+        );
+        self.alert(
+            AlertEvent::DkgConflict,
+            format!("DKG rounds {previous_round} and {round} produced different aggregate keys that both received votes"),
+        );
+        self.send_or_queue_message(SignerMessage::DkgConflict {
+            sender_id: self.signer_id,
+            reward_cycle: self.reward_cycle,
+            previous_round,
+            previous_key,
+            conflicting_round: round,
+            conflicting_key: *dkg_public_key,
+        });
+        self.reset_dkg_round();
+        true
+    }
+
+    /// Reset the DKG round after a conflict between aggregate keys was confirmed on chain by
+    /// another signer, unless a fresh round already started since
+    fn handle_dkg_conflict(
+        &mut self,
+        stacks_client: &StacksClient,
+        sender_id: u32,
+        reward_cycle: u64,
+        previous: (u64, Point),
+        conflicting: (u64, Point),
+    ) {
+        if sender_id == self.signer_id
+            || reward_cycle != self.reward_cycle
+            || self.approved_aggregate_public_key.is_some()
+            || self.coordinator.current_dkg_id > previous.0.max(conflicting.0)
+        {
+            return;
+        }
+        match self.are_both_keys_voted(
+            stacks_client,
+            (previous.0, &previous.1),
+            (conflicting.0, &conflicting.1),
+        ) {
+            Ok(true) => {
+                signer_warn!(
+                    self.logger,
+                    "A signer reported conflicting aggregate keys. Forcing a new DKG round.";
+                    "sender_id" => sender_id,
+                    "previous_round" => previous.0,
+                    "conflicting_round" => conflicting.0,
+                );
+                self.alert(
+                    AlertEvent::DkgConflict,
+                    format!(
+                        "Signer {sender_id} reported that DKG rounds {} and {} produced different aggregate keys that both received votes",
+                        previous.0, conflicting.0
+                    ),
+                );
+                self.reset_dkg_round();
+            }
+            Ok(false) => signer_warn!(
+                self.logger,
+                "Ignoring a DKG conflict report not backed by votes on chain";
+                "sender_id" => sender_id,
+            ),
+            Err(e) => signer_warn!(
+                self.logger,
+                "Failed to check the votes for the conflicting aggregate keys";
+                "sender_id" => sender_id,
+                "error" => ?e,
+            ),
+        }
+    }
+
+    /// Whether both of the given (round, aggregate key) pairs received votes on chain
+    fn are_both_keys_voted(
+        &self,
+        stacks_client: &StacksClient,
+        first: (u64, &Point),
+        second: (u64, &Point),
+    ) -> Result<bool, ClientError> {
+        for (round, aggregate_key) in [first, second] {
+            let tally =
+                stacks_client.get_aggregate_key_tally(self.reward_cycle, round, aggregate_key)?;
+            if !tally.is_some_and(|weight| weight > 0) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Forget the conflicting aggregate keys and reset the round id, so that no vote is resubmitted
+    /// for either key and the next DKG check finds no vote for the current round, triggering a fresh round
+    fn reset_dkg_round(&mut self) {
+        self.coordinator.set_aggregate_public_key(None);
+        self.coordinator.current_dkg_id = 0;
+        self.last_dkg_result = None;
+    }
+
     /// Build a signed DKG vote transaction
     fn build_dkg_vote(
         &mut self,
@@ -1238,20 +3995,56 @@ impl Signer {
         nonce: u64,
         dkg_public_key: Point,
     ) -> Result<StacksTransaction, ClientError> {
-        let mut unsigned_tx = stacks_client.build_unsigned_vote_for_aggregate_public_key(
+        let unsigned_tx = stacks_client.build_unsigned_vote_for_aggregate_public_key(
             self.stackerdb.get_signer_slot_id().0,
             self.coordinator.current_dkg_id,
             dkg_public_key,
             self.reward_cycle,
             nonce,
         )?;
+        self.sign_dkg_vote(stacks_client, epoch, unsigned_tx)
+    }
+
+    /// Build a signed DKG vote transaction voting for the aggregate public key of several rounds.
+    /// Only the latest rounds are voted for if there are more than a transaction can hold.
+    fn build_dkg_batch_vote(
+        &mut self,
+        stacks_client: &StacksClient,
+        epoch: &StacksEpochId,
+        nonce: u64,
+        votes: BTreeMap<u64, Point>,
+    ) -> Result<StacksTransaction, ClientError> {
+        let skipped_rounds = votes.len().saturating_sub(SIGNERS_VOTING_MAX_BATCH_VOTES);
+        let votes: Vec<_> = votes.into_iter().skip(skipped_rounds).collect();
+        let unsigned_tx = stacks_client.build_unsigned_vote_for_aggregate_public_keys(
+            self.stackerdb.get_signer_slot_id().0,
+            &votes,
+            self.reward_cycle,
+            nonce,
+        )?;
+        self.sign_dkg_vote(stacks_client, epoch, unsigned_tx)
+    }
+
+    /// Set the fee of a DKG vote transaction according to the current epoch and sign it
+    fn sign_dkg_vote(
+        &self,
+        stacks_client: &StacksClient,
+        epoch: &StacksEpochId,
+        mut unsigned_tx: StacksTransaction,
+    ) -> Result<StacksTransaction, ClientError> {
         let tx_fee = if epoch < &StacksEpochId::Epoch30 {
-            info!("{self}: in pre Epoch 3.0 cycles, must set a transaction fee for the DKG vote.");
+            signer_info!(
+                self.logger,
+                "in pre Epoch 3.0 cycles, must set a transaction fee for the DKG vote."
+            );
             let fee = if let Some(max_fee) = self.max_tx_fee_ustx {
                 let estimated_fee = stacks_client
                     .get_medium_estimated_fee_ustx(&unsigned_tx)
                     .map_err(|e| {
-                        warn!("{self}: unable to estimate fee for DKG vote transaction: {e:?}.");
+                        signer_warn!(
+                            self.logger,
+                            "unable to estimate fee for DKG vote transaction: {e:?}."
+                        );
                         e
                     })
                     .unwrap_or(self.tx_fee_ustx);
@@ -1259,7 +4052,10 @@ impl Signer {
             } else {
                 self.tx_fee_ustx
             };
-            debug!("{self}: Using a fee of {fee} uSTX for DKG vote transaction.");
+            signer_debug!(
+                self.logger,
+                "Using a fee of {fee} uSTX for DKG vote transaction."
+            );
             fee
         } else {
             0
@@ -1268,6 +4064,47 @@ impl Signer {
         stacks_client.sign_transaction(unsigned_tx)
     }
 
+    /// Take our own vote transactions for the reward cycle that are still pending in stackerDB out
+    /// of the signer transactions, if they vote for rounds other than the current one. Returns the
+    /// lowest nonce of these transactions, to be reused by a single transaction replacing them,
+    /// and the latest key voted for in each of their rounds but the current one. The miner only
+    /// includes one vote transaction per signer in a block, so each pending transaction would
+    /// otherwise delay the next one by a block.
+    pub fn take_pending_dkg_votes(
+        &self,
+        signer_address: &StacksAddress,
+        signer_transactions: &mut Vec<StacksTransaction>,
+    ) -> Option<(u64, BTreeMap<u64, Point>)> {
+        let is_pending_vote = |transaction: &StacksTransaction| {
+            transaction.origin_address() == *signer_address
+                && NakamotoSigners::parse_votes_for_aggregate_public_keys(transaction).is_some_and(
+                    |votes| {
+                        votes
+                            .iter()
+                            .all(|vote| vote.reward_cycle == self.reward_cycle)
+                    },
+                )
+        };
+        let mut pending_transactions: Vec<_> = signer_transactions
+            .iter()
+            .filter(|transaction| is_pending_vote(*transaction))
+            .collect();
+        pending_transactions.sort_by_key(|transaction| transaction.get_origin_nonce());
+        let nonce = pending_transactions.first()?.get_origin_nonce();
+        let mut votes: BTreeMap<_, _> = pending_transactions
+            .into_iter()
+            .filter_map(NakamotoSigners::parse_votes_for_aggregate_public_keys)
+            .flatten()
+            .map(|vote| (vote.voting_round, vote.aggregate_key))
+            .collect();
+        votes.remove(&self.coordinator.current_dkg_id);
+        if votes.is_empty() {
+            return None;
+        }
+        signer_transactions.retain(|transaction| !is_pending_vote(transaction));
+        Some((nonce, votes))
+    }
+
     // Get the account nonces for the provided list of signer addresses
     fn get_account_nonces(
         &self,
@@ -1277,7 +4114,10 @@ impl Signer {
         let mut account_nonces = std::collections::HashMap::with_capacity(signer_addresses.len());
         for address in signer_addresses {
             let Ok(account_nonce) = stacks_client.get_account_nonce(address) else {
-                warn!("{self}: Unable to get account nonce for address: {address}.");
+                signer_warn!(
+                    self.logger,
+                    "Unable to get account nonce for address: {address}."
+                );
                 continue;
             };
             account_nonces.insert(*address, account_nonce);
@@ -1295,28 +4135,38 @@ impl Signer {
     ) -> Result<(), ClientError> {
         let txid = new_transaction.txid();
         if self.approved_aggregate_public_key.is_some() {
-            // We do not enforce a block contain any transactions except the aggregate votes when it is NOT already set
-            info!(
-                "{self}: Already has an approved aggregate key. Do not broadcast the transaction ({txid:?})."
+            // The contract rejects any vote once a key is approved (ERR_CYCLE_ALREADY_HAS_KEY),
+            // so broadcasting the vote would only waste its fee
+            signer_info!(
+                self.logger,
+                "Already has an approved aggregate key. Do not broadcast the transaction ({txid:?})."
             );
             return Ok(());
         }
         if epoch >= StacksEpochId::Epoch30 {
-            debug!("{self}: Received a DKG result while in epoch 3.0. Broadcast the transaction only to stackerDB.");
+            signer_debug!(self.logger, "Received a DKG result while in epoch 3.0. Broadcast the transaction only to stackerDB.");
         } else if epoch == StacksEpochId::Epoch25 {
-            debug!("{self}: Received a DKG result while in epoch 2.5. Broadcast the transaction to the mempool.");
+            signer_debug!(self.logger, "Received a DKG result while in epoch 2.5. Broadcast the transaction to the mempool.");
             stacks_client.submit_transaction(&new_transaction)?;
-            info!("{self}: Submitted DKG vote transaction ({txid:?}) to the mempool");
+            signer_info!(
+                self.logger,
+                "Submitted DKG vote transaction ({txid:?}) to the mempool"
+            );
         } else {
-            debug!("{self}: Received a DKG result, but are in an unsupported epoch. Do not broadcast the transaction ({}).", new_transaction.txid());
+            signer_debug!(self.logger, "Received a DKG result, but are in an unsupported epoch. Do not broadcast the transaction ({}).", new_transaction.txid());
             return Ok(());
         }
         // For all Pox-4 epochs onwards, broadcast the results also to stackerDB for other signers/miners to observe
         signer_transactions.push(new_transaction);
         let signer_message = SignerMessage::Transactions(signer_transactions);
-        self.stackerdb.send_message_with_retry(signer_message)?;
+        self.stackerdb
+            .send_message_with_retry(signer_message)?
+            .into_ack()?;
         crate::monitoring::increment_dkg_votes_submitted();
-        info!("{self}: Broadcasted DKG vote transaction ({txid}) to stacker DB");
+        signer_info!(
+            self.logger,
+            "Broadcasted DKG vote transaction ({txid}) to stacker DB"
+        );
         Ok(())
     }
 
@@ -1326,10 +4176,18 @@ impl Signer {
         // Deserialize the signature result and broadcast an appropriate Reject or Approval message to stackerdb
         let message = self.coordinator.get_message();
         let Some(block_vote): Option<NakamotoBlockVote> = read_next(&mut &message[..]).ok() else {
-            debug!("{self}: Received a signature result for a non-block. Nothing to broadcast.");
+            signer_debug!(
+                self.logger,
+                "Received a signature result for a non-block. Nothing to broadcast."
+            );
             return;
         };
 
+        self.block_sign_deadline
+            .remove(&block_vote.signer_signature_hash);
+        if self.process_block_duplicate_vote(&block_vote.signer_signature_hash) {
+            return;
+        }
         let block_submission = if block_vote.rejected {
             crate::monitoring::increment_block_responses_sent(false);
             // We signed a rejection message. Return a rejection message
@@ -1341,17 +4199,95 @@ impl Signer {
         };
 
         // Submit signature result to miners to observe
-        info!("{self}: Submit block response: {block_submission}");
-        if let Err(e) = self
-            .stackerdb
-            .send_message_with_retry(block_submission.into())
+        signer_info!(self.logger, "Submit block response: {block_submission}");
+        self.send_or_queue_message(block_submission.into());
+        let mut sign_round_waits = std::mem::take(&mut self.sign_round_waits);
+        if let Some(block_info) =
+            self.update_block_info(&block_vote.signer_signature_hash, |block_info| {
+                block_info
+                    .timings
+                    .record(SignRoundStep::SignatureBroadcast, now_ms());
+                sign_round_waits.finish(&mut block_info.timings);
+                if !block_vote.rejected {
+                    block_info.signature = Some(ThresholdSignature(signature.clone()));
+                }
+            })
         {
-            warn!("{self}: Failed to send block submission to stacker-db: {e:?}");
+            let breakdown = block_info.timings.breakdown();
+            for (phase, latency) in &breakdown.phases {
+                crate::monitoring::record_sign_round_phase_latency(phase, *latency);
+            }
+            signer_info!(
+                self.logger,
+                "Signing round latency breakdown";
+                "signer_sighash" => %block_vote.signer_signature_hash,
+                "rejected" => block_vote.rejected,
+                "breakdown" => %breakdown,
+            );
+        }
+    }
+
+    /// Check whether we already broadcast a block response for the block, marking it as
+    /// broadcast otherwise. A re-run signing round, e.g. after a coordinator timeout, must not
+    /// broadcast a second and possibly conflicting response.
+    fn process_block_duplicate_vote(&mut self, signer_signature_hash: &Sha512Trunc256Sum) -> bool {
+        if self.broadcast_responses.insert(*signer_signature_hash) {
+            return false;
+        }
+        signer_warn!(
+            self.logger,
+            "Already broadcast a block response for the block. Not broadcasting another.";
+            "signer_sighash" => %signer_signature_hash,
+        );
+        true
+    }
+
+    /// Alert operators of a critical event, if an alert webhook is configured
+    fn alert(&self, event: AlertEvent, details: String) {
+        if let Some(alert_notifier) = &self.alert_notifier {
+            alert_notifier.notify(
+                event,
+                Some(self.signer_id),
+                Some(self.reward_cycle),
+                details,
+            );
         }
     }
 
+    /// Update the given block in the signer db, returning it.
+    /// Returns None if we never saw the block.
+    fn update_block_info(
+        &mut self,
+        signer_signature_hash: &Sha512Trunc256Sum,
+        update: impl FnOnce(&mut BlockInfo),
+    ) -> Option<BlockInfo> {
+        let mut block_info = self
+            .signer_db
+            .block_lookup(self.reward_cycle, signer_signature_hash)
+            .unwrap_or_else(|e| {
+                signer_warn!(
+                    self.logger,
+                    "Failed to lookup block in signer db";
+                    "signer_sighash" => %signer_signature_hash,
+                    "error" => ?e
+                );
+                None
+            })?;
+        update(&mut block_info);
+        if let Err(e) = self.signer_db.insert_block(&block_info) {
+            signer_warn!(
+                self.logger,
+                "Failed to update block in signer db";
+                "signer_sighash" => %signer_signature_hash,
+                "error" => ?e
+            );
+        }
+        Some(block_info)
+    }
+
     /// Process a sign error from a signing round, broadcasting a rejection message to stackerdb accordingly
     fn process_sign_error(&mut self, e: &SignError) {
+        self.sign_error_stats.record(e);
         let message = self.coordinator.get_message();
         // We do not sign across blocks, but across their hashes. however, the first sign request is always across the block
         // so we must handle this case first
@@ -1361,8 +4297,9 @@ impl Signer {
             let Some(block_vote): Option<NakamotoBlockVote> = read_next(&mut &message[..]).ok()
             else {
                 // This is not a block vote either. We cannot process this error
-                debug!(
-                    "{self}: Received a signature error for a non-block. Nothing to broadcast."
+                signer_debug!(
+                    self.logger,
+                    "Received a signature error for a non-block. Nothing to broadcast."
                 );
                 return;
             };
@@ -1371,23 +4308,24 @@ impl Signer {
                 .block_lookup(self.reward_cycle, &block_vote.signer_signature_hash)
                 .unwrap_or_else(|_| panic!("{self}: Failed to connect to signer DB"))
             else {
-                debug!(
-                    "{self}: Received a signature result for a block we have not seen before. Ignoring..."
+                signer_debug!(
+                    self.logger,
+                    "Received a signature result for a block we have not seen before. Ignoring..."
                 );
                 return;
             };
             block_info.block
         });
+        self.block_sign_deadline
+            .remove(&block.header.signer_signature_hash());
         let block_rejection =
             BlockRejection::new(block.header.signer_signature_hash(), RejectCode::from(e));
-        debug!("{self}: Broadcasting block rejection: {block_rejection:?}");
+        signer_debug!(
+            self.logger,
+            "Broadcasting block rejection: {block_rejection:?}"
+        );
         // Submit signature result to miners to observe
-        if let Err(e) = self
-            .stackerdb
-            .send_message_with_retry(block_rejection.into())
-        {
-            warn!("{self}: Failed to send block rejection submission to stacker-db: {e:?}");
-        }
+        self.send_or_queue_message(block_rejection.into());
     }
 
     /// Persist signer state in both SignerDB and StackerDB
@@ -1407,11 +4345,11 @@ impl Signer {
         let stackerdb_result = self.save_signer_state_in_stackerdb(encrypted_state);
 
         if let Err(err) = &signerdb_result {
-            warn!("{self}: Failed to persist state in SignerDB: {err}");
+            signer_warn!(self.logger, "Failed to persist state in SignerDB: {err}");
         }
 
         if let Err(err) = &stackerdb_result {
-            warn!("{self}: Failed to persist state in StackerDB: {err}");
+            signer_warn!(self.logger, "Failed to persist state in StackerDB: {err}");
 
             stackerdb_result
         } else {
@@ -1439,7 +4377,7 @@ impl Signer {
          * This is a no-op until the number of signer slots can be expanded to 14
          *
         let message = SignerMessage::EncryptedSignerState(encrypted_state);
-        self.stackerdb.send_message_with_retry(message)?;
+        self.stackerdb.send_message_with_retry(message)?.into_ack()?;
         */
         Ok(())
     }
@@ -1453,27 +4391,141 @@ impl Signer {
         let nmb_results = operation_results.len();
         match res.send(operation_results) {
             Ok(_) => {
-                debug!("{self}: Successfully sent {nmb_results} operation result(s)")
+                signer_debug!(
+                    self.logger,
+                    "Successfully sent {nmb_results} operation result(s)"
+                )
             }
             Err(e) => {
-                warn!("{self}: Failed to send {nmb_results} operation results: {e:?}");
+                signer_warn!(
+                    self.logger,
+                    "Failed to send {nmb_results} operation results: {e:?}"
+                );
             }
         }
     }
 
     /// Sending all provided packets through stackerdb with a retry
     fn send_outbound_messages(&mut self, outbound_messages: Vec<Packet>) {
-        debug!(
-            "{self}: Sending {} messages to other stacker-db instances.",
+        signer_debug!(
+            self.logger,
+            "Sending {} messages to other stacker-db instances.",
             outbound_messages.len()
         );
         for msg in outbound_messages {
-            let ack = self.stackerdb.send_message_with_retry(msg.into());
-            if let Ok(ack) = ack {
-                debug!("{self}: send outbound ACK: {ack:?}");
-            } else {
-                warn!("{self}: Failed to send message to stacker-db instance: {ack:?}");
+            self.send_or_queue_message(msg.into());
+        }
+    }
+
+    /// Send a message through stackerdb with a retry. If the stacks node stays unreachable,
+    /// queue the message to be sent again on a later event loop pass.
+    /// Messages are queued behind any undelivered ones to preserve their order.
+    fn send_or_queue_message(&mut self, message: SignerMessage) {
+        if let SignerMessage::Packet(Packet {
+            msg: Message::NonceRequest(request),
+            ..
+        }) = &message
+        {
+            // Responses to our own requests must not be mistaken for future-dated packets
+            self.record_sign_request(request.sign_id, request.sign_iter_id);
+        }
+        if let SignerMessage::BlockResponse(BlockResponse::Rejected(rejection)) = &message {
+            // Keep the reason around for operators searching past rejections
+            self.signer_db
+                .record_block_rejection(
+                    self.reward_cycle,
+                    &rejection.signer_signature_hash,
+                    &rejection.reason_code,
+                )
+                .unwrap_or_else(|e| {
+                    signer_warn!(self.logger, "Failed to record block rejection: {e:?}")
+                });
+        }
+        if let Some(recorder) = &self.event_recorder {
+            recorder.record(&RecordedEntry::Outbound {
+                reward_cycle: self.reward_cycle,
+                message: RecordedMessage::new(&message),
+            });
+        }
+        if !self.stackerdb.has_queued_messages() {
+            match self.stackerdb.send_message_with_retry(message.clone()) {
+                Ok(SendOutcome::Acked(ack)) => {
+                    SignerCounters::increment(&mut self.counters.stackerdb_sends_acked);
+                    signer_debug!(self.logger, "send outbound ACK: {ack:?}");
+                    return;
+                }
+                Ok(SendOutcome::Unknown) => {
+                    SignerCounters::increment(&mut self.counters.stackerdb_sends_unacknowledged);
+                    signer_warn!(self.logger, "Stacks node did not acknowledge the message. Queueing message to send later.");
+                }
+                Ok(SendOutcome::Rejected(reason)) => {
+                    SignerCounters::increment(&mut self.counters.stackerdb_sends_rejected);
+                    signer_warn!(self.logger, "Stacker-db rejected the message: {reason}");
+                    return;
+                }
+                Err(e) => {
+                    SignerCounters::increment(&mut self.counters.stackerdb_sends_failed);
+                    signer_warn!(
+                        self.logger,
+                        "Failed to send message to stacker-db instance: {e:?}"
+                    );
+                    return;
+                }
+            }
+        }
+        let expires_at =
+            get_epoch_time_secs().saturating_add(self.outbound_message_ttl(&message).as_secs());
+        let id = self
+            .signer_db
+            .insert_broadcast(self.reward_cycle, expires_at, &message)
+            .map_err(|e| signer_warn!(self.logger, "Failed to persist queued message: {e:?}"))
+            .ok();
+        let queued = QueuedMessage {
+            id,
+            message,
+            expires_at,
+        };
+        if let Some(dropped) = self.stackerdb.queue_message(queued) {
+            signer_warn!(
+                self.logger,
+                "Outbound queue is full. Dropping oldest message: {dropped:?}"
+            );
+            self.forget_queued_message(&dropped);
+        }
+    }
+
+    /// How long an undelivered message remains worth sending
+    fn outbound_message_ttl(&self, message: &SignerMessage) -> Duration {
+        match message {
+            SignerMessage::Packet(packet) if Self::is_dkg_message(&packet.msg) => {
+                self.dkg_round_timeout
             }
+            SignerMessage::Packet(_) => self.sign_round_timeout,
+            SignerMessage::BlockResponse(_) => OUTBOUND_BLOCK_RESPONSE_TTL,
+            _ => OUTBOUND_PACKET_DEFAULT_TTL,
+        }
+    }
+
+    /// Try to deliver the messages queued while the stacks node was unreachable
+    fn flush_outbound_queue(&mut self) {
+        if !self.stackerdb.has_queued_messages() {
+            return;
+        }
+        for queued in self.stackerdb.flush_outbound_queue(get_epoch_time_secs()) {
+            self.forget_queued_message(&queued);
+        }
+    }
+
+    /// Remove a message that left the outbound queue from the signer db
+    fn forget_queued_message(&self, queued: &QueuedMessage) {
+        let Some(id) = queued.id else {
+            return;
+        };
+        if let Err(e) = self.signer_db.remove_broadcast(id) {
+            signer_warn!(
+                self.logger,
+                "Failed to remove queued message from signer db: {e:?}"
+            );
         }
     }
 
@@ -1489,8 +4541,14 @@ impl Signer {
         if self.approved_aggregate_public_key.is_some() {
             return Ok(());
         }
+        let starting_up = self.state == State::Uninitialized;
         // Check stackerdb for any missed DKG messages to catch up our state.
         self.read_dkg_stackerdb_messages(stacks_client, res, current_reward_cycle)?;
+        if starting_up {
+            if let Err(e) = self.resubmit_missed_votes(stacks_client) {
+                signer_warn!(self.logger, "Failed to resubmit missed DKG votes: {e:?}");
+            }
+        }
         // Check if we should still queue DKG
         if !self.should_queue_dkg(stacks_client)? {
             return Ok(());
@@ -1502,10 +4560,81 @@ impl Signer {
             return Ok(());
         }
         if self.commands.front() != Some(&SignerCommand::Dkg) {
-            info!("{self} is the current coordinator and must trigger DKG. Queuing DKG command...");
+            signer_info!(
+                self.logger,
+                "Signer is the current coordinator and must trigger DKG. Queuing DKG command..."
+            );
             self.commands.push_front(SignerCommand::Dkg);
         } else {
-            debug!("{self}: DKG command already queued...");
+            signer_debug!(self.logger, "DKG command already queued...");
+        }
+        Ok(())
+    }
+
+    /// Re-broadcast our vote for the current DKG round if it is not confirmed on chain, e.g.
+    /// because its transaction was dropped from the mempool before the signer restarted.
+    /// A vote still pending in our stacker-db slot is resubmitted as is, otherwise a new vote is cast.
+    pub fn resubmit_missed_votes(
+        &mut self,
+        stacks_client: &StacksClient,
+    ) -> Result<(), ClientError> {
+        if self.approved_aggregate_public_key.is_some() {
+            return Ok(());
+        }
+        let Some(dkg_public_key) = self.coordinator.aggregate_public_key else {
+            signer_debug!(
+                self.logger,
+                "No DKG result to vote for. Nothing to resubmit."
+            );
+            return Ok(());
+        };
+        let voting_round = self.coordinator.current_dkg_id;
+        let signer_address = *stacks_client.get_signer_address();
+        if stacks_client
+            .get_vote_for_aggregate_public_key(voting_round, self.reward_cycle, signer_address)?
+            .is_some()
+        {
+            signer_debug!(self.logger, "DKG vote is confirmed. Nothing to resubmit.";
+                "voting_round" => voting_round
+            );
+            return Ok(());
+        }
+        let account_nonces = self.get_account_nonces(stacks_client, &[signer_address]);
+        let reward_cycle = self.reward_cycle;
+        let pending_vote = self
+            .get_signer_transactions(&account_nonces)?
+            .into_iter()
+            .find(|transaction| {
+                transaction.origin_address() == signer_address
+                    && NakamotoSigners::parse_votes_for_aggregate_public_keys(transaction)
+                        .is_some_and(|votes| {
+                            votes.iter().any(|params| {
+                                params.aggregate_key == dkg_public_key
+                                    && params.voting_round == voting_round
+                                    && params.reward_cycle == reward_cycle
+                            })
+                        })
+            });
+        let Some(pending_vote) = pending_vote else {
+            signer_info!(self.logger, "DKG vote is missing on chain. Voting again.";
+                "voting_round" => voting_round,
+                "aggregate_key" => %dkg_public_key
+            );
+            self.process_dkg(stacks_client, &dkg_public_key);
+            return Ok(());
+        };
+        let epoch = stacks_client.get_node_epoch()?;
+        if epoch == StacksEpochId::Epoch25 {
+            let txid = stacks_client.submit_transaction(&pending_vote)?;
+            signer_info!(self.logger, "Resubmitted unconfirmed DKG vote transaction ({txid}) to the mempool";
+                "voting_round" => voting_round,
+                "nonce" => pending_vote.get_origin_nonce()
+            );
+        } else {
+            signer_debug!(self.logger, "DKG vote is still pending in StackerDB. Nothing to resubmit in epoch {epoch}.";
+                "txid" => %pending_vote.txid(),
+                "voting_round" => voting_round
+            );
         }
         Ok(())
     }
@@ -1524,20 +4653,22 @@ impl Signer {
             // then overwrite our value accordingly. Otherwise, we will be locked out of the round and should not participate.
             let internal_dkg = self.coordinator.aggregate_public_key;
             if internal_dkg != self.approved_aggregate_public_key {
-                warn!("{self}: we do not support changing the internal DKG key yet. Expected {internal_dkg:?} got {:?}", self.approved_aggregate_public_key);
+                signer_warn!(self.logger, "we do not support changing the internal DKG key yet. Expected {internal_dkg:?} got {:?}", self.approved_aggregate_public_key);
             }
             self.coordinator
                 .set_aggregate_public_key(self.approved_aggregate_public_key);
             if old_dkg != self.approved_aggregate_public_key {
-                warn!(
-                    "{self}: updated DKG value from {old_dkg:?} to {:?}.",
+                signer_warn!(
+                    self.logger,
+                    "updated DKG value from {old_dkg:?} to {:?}.",
                     self.approved_aggregate_public_key
                 );
             }
             match self.state {
                 State::OperationInProgress(Operation::Dkg) => {
-                    debug!(
-                        "{self}: DKG has already been set. Aborting DKG operation {}.",
+                    signer_debug!(
+                        self.logger,
+                        "DKG has already been set. Aborting DKG operation {}.",
                         self.coordinator.current_dkg_id
                     );
                     self.finish_operation();
@@ -1554,6 +4685,57 @@ impl Signer {
         Ok(())
     }
 
+    /// Whether the vote weight of the given DKG round has not changed for longer than the stall timeout
+    fn is_dkg_round_stalled(&mut self, round: u64, votes_weight: u128) -> bool {
+        let now = Instant::now();
+        match self.dkg_round_progress {
+            Some((last_round, last_weight, since))
+                if last_round == round && last_weight == votes_weight =>
+            {
+                now.saturating_duration_since(since) >= self.dkg_round_stall_timeout
+            }
+            _ => {
+                self.dkg_round_progress = Some((round, votes_weight, now));
+                false
+            }
+        }
+    }
+
+    /// Retrieve the tally of the candidate key with the most votes in the given DKG round. The
+    /// candidates are the keys the signers of the reward cycle voted for, starting with ours.
+    fn get_best_dkg_candidate(
+        &self,
+        stacks_client: &StacksClient,
+        round: u64,
+        our_vote: Point,
+    ) -> Result<CandidateInfo, ClientError> {
+        let mut best_candidate =
+            stacks_client.get_candidate_info(self.reward_cycle, round, &our_vote)?;
+        let mut candidates = vec![our_vote];
+        for signer_address in &self.signer_addresses {
+            if signer_address == stacks_client.get_signer_address() {
+                continue;
+            }
+            let Some(vote) = stacks_client.get_vote_for_aggregate_public_key(
+                round,
+                self.reward_cycle,
+                *signer_address,
+            )?
+            else {
+                continue;
+            };
+            if candidates.contains(&vote) {
+                continue;
+            }
+            let candidate = stacks_client.get_candidate_info(self.reward_cycle, round, &vote)?;
+            if candidate.candidate_weight > best_candidate.candidate_weight {
+                best_candidate = candidate;
+            }
+            candidates.push(vote);
+        }
+        Ok(best_candidate)
+    }
+
     /// Should DKG be queued to the current signer's command queue
     /// This assumes that no key has been approved by the contract yet
     pub fn should_queue_dkg(&mut self, stacks_client: &StacksClient) -> Result<bool, ClientError> {
@@ -1566,17 +4748,24 @@ impl Signer {
         }
         let signer_address = stacks_client.get_signer_address();
         let account_nonces = self.get_account_nonces(stacks_client, &[*signer_address]);
-        let old_transactions = self.get_signer_transactions(&account_nonces).map_err(|e| {
-                warn!("{self}: Failed to get old signer transactions: {e:?}. May trigger DKG unnecessarily");
-            }).unwrap_or_default();
+        let old_transactions = self
+            .get_signer_transactions(&account_nonces)
+            .map_err(|e| {
+                signer_warn!(
+                    self.logger,
+                    "Failed to get old signer transactions: {e:?}. May trigger DKG unnecessarily"
+                );
+            })
+            .unwrap_or_default();
         // Check if we have an existing vote transaction for the same round and reward cycle
         for transaction in old_transactions.iter() {
-            let params =
-                    NakamotoSigners::parse_vote_for_aggregate_public_key(transaction).unwrap_or_else(|| panic!("BUG: {self}: Received an invalid {SIGNERS_VOTING_FUNCTION_NAME} transaction in an already filtered list: {transaction:?}"));
-            if Some(params.aggregate_key) == self.coordinator.aggregate_public_key
-                && params.voting_round == self.coordinator.current_dkg_id
-            {
-                debug!("{self}: Not triggering a DKG round. Already have a pending vote transaction.";
+            let votes =
+                    NakamotoSigners::parse_votes_for_aggregate_public_keys(transaction).unwrap_or_else(|| panic!("BUG: {self}: Received an invalid {SIGNERS_VOTING_FUNCTION_NAME} transaction in an already filtered list: {transaction:?}"));
+            if let Some(params) = votes.into_iter().find(|params| {
+                Some(params.aggregate_key) == self.coordinator.aggregate_public_key
+                    && params.voting_round == self.coordinator.current_dkg_id
+            }) {
+                signer_debug!(self.logger, "Not triggering a DKG round. Already have a pending vote transaction.";
                     "txid" => %transaction.txid(),
                     "aggregate_key" => %params.aggregate_key,
                     "voting_round" => params.voting_round
@@ -1589,28 +4778,61 @@ impl Signer {
             self.reward_cycle,
             *signer_address,
         )? {
-            let Some(round_weight) = stacks_client
-                .get_round_vote_weight(self.reward_cycle, self.coordinator.current_dkg_id)?
+            let Some(round_info) =
+                stacks_client.get_round_info(self.reward_cycle, self.coordinator.current_dkg_id)?
             else {
                 // This only will happen if somehow we registered as a signer and were granted no weight which should not really ever happen.
-                error!("{self}: already voted for DKG, but no round vote weight found. We either have no voting power or the contract is corrupted.";
+                signer_error!(self.logger, "already voted for DKG, but no round vote weight found. We either have no voting power or the contract is corrupted.";
                     "voting_round" => self.coordinator.current_dkg_id,
                     "aggregate_key" => %aggregate_key
                 );
                 return Ok(false);
             };
             let threshold_weight = stacks_client.get_vote_threshold_weight(self.reward_cycle)?;
-            if round_weight < threshold_weight {
-                // The threshold weight has not been met yet. We should wait for more votes to arrive.
-                // TODO: this should be on a timeout of some kind. We should not wait forever for the threshold to be met.
-                // See https://github.com/stacks-network/stacks-core/issues/4568
-                debug!("{self}: Not triggering a DKG round. Weight threshold has not been met yet. Waiting for more votes to arrive.";
-                    "voting_round" => self.coordinator.current_dkg_id,
-                    "aggregate_key" => %aggregate_key,
-                    "round_weight" => round_weight,
-                    "threshold_weight" => threshold_weight
-                );
-                return Ok(false);
+            let best_candidate = self.get_best_dkg_candidate(
+                stacks_client,
+                self.coordinator.current_dkg_id,
+                aggregate_key,
+            )?;
+            let stalled =
+                self.is_dkg_round_stalled(self.coordinator.current_dkg_id, round_info.votes_weight);
+            // The contract no longer accepts votes in an expired round, so waiting on it is futile
+            let expired = round_info.approved_aggregate_key.is_none()
+                && stacks_client
+                    .is_round_expired(self.reward_cycle, self.coordinator.current_dkg_id)?;
+            match DkgRoundDecision::from_round_info(
+                &round_info,
+                &best_candidate,
+                threshold_weight,
+                stalled || expired,
+            ) {
+                DkgRoundDecision::Approved => {
+                    signer_debug!(self.logger, "Not triggering a DKG round. A key has already reached the weight threshold.";
+                        "voting_round" => self.coordinator.current_dkg_id,
+                        "approved_aggregate_key" => ?round_info.approved_aggregate_key,
+                    );
+                    return Ok(false);
+                }
+                DkgRoundDecision::Wait => {
+                    signer_debug!(self.logger, "Not triggering a DKG round. Weight threshold has not been met yet. Waiting for more votes to arrive.";
+                        "voting_round" => self.coordinator.current_dkg_id,
+                        "aggregate_key" => %aggregate_key,
+                        "round_weight" => round_info.votes_weight,
+                        "threshold_weight" => threshold_weight
+                    );
+                    return Ok(false);
+                }
+                DkgRoundDecision::NewRound => {
+                    signer_info!(self.logger, "DKG round can no longer reach the weight threshold. Triggering a new round.";
+                        "voting_round" => self.coordinator.current_dkg_id,
+                        "round_weight" => round_info.votes_weight,
+                        "best_candidate_weight" => best_candidate.candidate_weight,
+                        "total_weight" => ?best_candidate.total_weight,
+                        "threshold_weight" => threshold_weight,
+                        "stalled" => stalled,
+                        "expired" => expired,
+                    );
+                }
             }
         } else {
             // Have I already voted, but the vote is still pending in StackerDB? Check stackerdb for the same round number and reward cycle vote transaction
@@ -1625,34 +4847,36 @@ impl Signer {
                 {
                     continue;
                 }
-                let Some(params) =
-                    NakamotoSigners::parse_vote_for_aggregate_public_key(transaction)
+                let Some(votes) =
+                    NakamotoSigners::parse_votes_for_aggregate_public_keys(transaction)
                 else {
                     continue;
                 };
                 let Some(dkg_public_key) = self.coordinator.aggregate_public_key else {
                     break;
                 };
-                if params.aggregate_key == dkg_public_key
-                    && params.voting_round == self.coordinator.current_dkg_id
-                    && params.reward_cycle == self.reward_cycle
-                {
-                    let origin_nonce = transaction.get_origin_nonce();
-                    if origin_nonce < account_nonce {
-                        // We have already voted, but our vote nonce is outdated. Resubmit vote with updated transaction
-                        warn!("{self}: DKG vote submitted with invalid nonce ({origin_nonce} < {account_nonce}). Resubmitting vote.");
-                        self.process_dkg(stacks_client, &dkg_public_key);
-                    } else {
-                        debug!("{self}: Already have a pending DKG vote in StackerDB. Waiting for it to be confirmed.";
-                            "txid" => %transaction.txid(),
-                            "aggregate_key" => %params.aggregate_key,
-                            "voting_round" => params.voting_round,
-                            "reward_cycle" => params.reward_cycle,
-                            "nonce" => origin_nonce
-                        );
-                    }
-                    return Ok(false);
+                let Some(params) = votes.into_iter().find(|params| {
+                    params.aggregate_key == dkg_public_key
+                        && params.voting_round == self.coordinator.current_dkg_id
+                        && params.reward_cycle == self.reward_cycle
+                }) else {
+                    continue;
+                };
+                let origin_nonce = transaction.get_origin_nonce();
+                if origin_nonce < account_nonce {
+                    // We have already voted, but our vote nonce is outdated. Resubmit vote with updated transaction
+                    signer_warn!(self.logger, "DKG vote submitted with invalid nonce ({origin_nonce} < {account_nonce}). Resubmitting vote.");
+                    self.process_dkg(stacks_client, &dkg_public_key);
+                } else {
+                    signer_debug!(self.logger, "Already have a pending DKG vote in StackerDB. Waiting for it to be confirmed.";
+                        "txid" => %transaction.txid(),
+                        "aggregate_key" => %params.aggregate_key,
+                        "voting_round" => params.voting_round,
+                        "reward_cycle" => params.reward_cycle,
+                        "nonce" => origin_nonce
+                    );
                 }
+                return Ok(false);
             }
         }
         Ok(true)
@@ -1725,6 +4949,14 @@ fn derive_encryption_key(private_key: &Scalar) -> Sha512Trunc256Sum {
     Sha512Trunc256Sum::from_data(&prefixed_key)
 }
 
+/// Error stemming from the signer itself
+#[derive(Debug, thiserror::Error)]
+pub enum SignerError {
+    /// The signer is not fit to take part in signing rounds
+    #[error("Self test failed: {0}")]
+    SelfTestFailed(String),
+}
+
 /// Error stemming from a persistence operation
 #[derive(Debug, thiserror::Error)]
 pub enum PersistenceError {
@@ -1753,21 +4985,2099 @@ pub enum EncryptionError {
     Decrypt,
 }
 
+/// The median fee in uSTX of the transactions in the block, or None if it has no transactions
+fn median_tx_fee(block: &NakamotoBlock) -> Option<u64> {
+    let mut fees = block
+        .txs
+        .iter()
+        .map(StacksTransaction::get_tx_fee)
+        .collect::<Vec<_>>();
+    if fees.is_empty() {
+        return None;
+    }
+    fees.sort_unstable();
+    let mid = fees.len() / 2;
+    if fees.len() % 2 == 1 {
+        return Some(fees[mid]);
+    }
+    let sum = u128::from(fees[mid - 1]) + u128::from(fees[mid]);
+    Some(u64::try_from(sum / 2).expect("infallible: the mean of two u64 values fits in a u64"))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread::spawn;
 
-    #[test]
-    fn encrypted_messages_should_be_possible_to_decrypt() {
-        let msg = "Nobody's gonna know".as_bytes();
-        let key = Scalar::random(&mut OsRng);
+    use blockstack_lib::chainstate::nakamoto::NakamotoBlockHeader;
+    use blockstack_lib::chainstate::stacks::boot::NakamotoSignerEntry;
+    use blockstack_lib::chainstate::stacks::{
+        CoinbasePayload, TenureChangeCause, TenureChangePayload, TokenTransferMemo,
+        TransactionAuth, TransactionPayload, TransactionVersion,
+    };
+    use blockstack_lib::net::api::postblock_proposal::{BlockValidateReject, ValidateRejectCode};
+    use stacks_common::types::chainstate::{StacksPrivateKey, StacksPublicKey};
+    use stacks_common::util::hash::Hash160;
+    use stacks_common::util::vrf::VRFProof;
+    use wsts::errors::AggregatorError;
+    use wsts::net::{DkgBegin, NonceResponse};
+    use wsts::state_machine::DkgError;
 
-        let encrypted = encrypt(&key, msg, &mut OsRng).unwrap();
+    use super::*;
+    use crate::alerting::tests::receive_alert;
+    use crate::alerting::AlertSeverity;
+    use crate::client::tests::{
+        build_account_nonce_response, build_get_aggregate_key_tally_response,
+        build_get_candidate_info_response, build_get_last_round_response,
+        build_get_peer_info_response, build_get_pox_data_response, build_get_reward_set_response,
+        build_get_vote_for_aggregate_key_response, generate_signer_config, mock_server_from_config,
+        mock_server_random, write_response,
+    };
+    use crate::config::GlobalConfig;
+    use crate::logging::json_logger;
+    use crate::monitoring::SignerMetricsServer;
 
-        assert_ne!(encrypted, msg);
+    fn nonce_response_packet(signer_id: u32, sign_iter_id: u64) -> Packet {
+        Packet {
+            msg: Message::NonceResponse(NonceResponse {
+                dkg_id: 1,
+                sign_id: 1,
+                sign_iter_id,
+                signer_id,
+                key_ids: vec![signer_id],
+                nonces: vec![],
+                message: vec![1, 2, 3],
+            }),
+            sig: vec![],
+        }
+    }
 
-        let decrypted = decrypt(&key, &encrypted).unwrap();
+    #[test]
+    fn duplicate_outbound_messages_should_be_dropped() {
+        // Our nonce response, produced once acting as a signer and again acting as the coordinator
+        let signer_messages = vec![nonce_response_packet(0, 1)];
+        let coordinator_messages = vec![
+            nonce_response_packet(0, 1),
+            nonce_response_packet(1, 1),
+            nonce_response_packet(0, 2),
+        ];
+        let deduplicated = Signer::deduplicate_outbound_messages(
+            signer_messages
+                .into_iter()
+                .chain(coordinator_messages)
+                .collect(),
+        );
+        assert_eq!(
+            deduplicated,
+            vec![
+                nonce_response_packet(0, 1),
+                nonce_response_packet(1, 1),
+                nonce_response_packet(0, 2),
+            ]
+        );
+    }
 
-        assert_eq!(decrypted, msg);
+    fn nonce_request(sign_id: u64, message: Vec<u8>) -> NonceRequest {
+        NonceRequest {
+            dkg_id: 1,
+            sign_id,
+            sign_iter_id: 1,
+            message,
+            is_taproot: false,
+            merkle_root: None,
+        }
+    }
+
+    #[test]
+    fn pending_nonce_requests_should_accumulate() {
+        let mut nonce_requests = vec![];
+        assert!(
+            push_pending_nonce_request(&mut nonce_requests, &nonce_request(1, vec![1])).is_none()
+        );
+        assert!(
+            push_pending_nonce_request(&mut nonce_requests, &nonce_request(2, vec![2])).is_none()
+        );
+        // A request for a round already cached replaces it instead of piling up
+        assert!(
+            push_pending_nonce_request(&mut nonce_requests, &nonce_request(1, vec![3])).is_none()
+        );
+        assert_eq!(
+            nonce_requests
+                .iter()
+                .map(|request| (request.sign_id, request.message.clone()))
+                .collect::<Vec<_>>(),
+            vec![(1, vec![3]), (2, vec![2])]
+        );
+
+        for sign_id in 3..=MAX_PENDING_NONCE_REQUESTS_PER_BLOCK as u64 {
+            assert!(push_pending_nonce_request(
+                &mut nonce_requests,
+                &nonce_request(sign_id, vec![])
+            )
+            .is_none());
+        }
+        let dropped = push_pending_nonce_request(&mut nonce_requests, &nonce_request(100, vec![]))
+            .expect("Expected the oldest request to be dropped");
+        assert_eq!(dropped.sign_id, 1);
+        assert_eq!(nonce_requests.len(), MAX_PENDING_NONCE_REQUESTS_PER_BLOCK);
+        assert_eq!(nonce_requests.last().unwrap().sign_id, 100);
+    }
+
+    #[test]
+    fn encrypted_messages_should_be_possible_to_decrypt() {
+        let msg = "Nobody's gonna know".as_bytes();
+        let key = Scalar::random(&mut OsRng);
+
+        let encrypted = encrypt(&key, msg, &mut OsRng).unwrap();
+
+        assert_ne!(encrypted, msg);
+
+        let decrypted = decrypt(&key, &encrypted).unwrap();
+
+        assert_eq!(decrypted, msg);
+    }
+
+    #[test]
+    fn dkg_round_decision_should_respect_threshold_and_stall() {
+        let threshold_weight = 70;
+        let mut round_info = RoundInfo {
+            votes_count: 5,
+            votes_weight: 50,
+            approved_aggregate_key: None,
+        };
+        let mut best_candidate = CandidateInfo {
+            candidate_weight: 30,
+            total_weight: Some(100),
+        };
+        assert_eq!(
+            DkgRoundDecision::from_round_info(
+                &round_info,
+                &best_candidate,
+                threshold_weight,
+                false
+            ),
+            DkgRoundDecision::Wait
+        );
+        assert_eq!(
+            DkgRoundDecision::from_round_info(&round_info, &best_candidate, threshold_weight, true),
+            DkgRoundDecision::NewRound
+        );
+
+        // The best candidate can still be approved with the votes of the remaining signers
+        round_info.votes_weight = 80;
+        best_candidate.candidate_weight = 55;
+        assert_eq!(
+            DkgRoundDecision::from_round_info(
+                &round_info,
+                &best_candidate,
+                threshold_weight,
+                false
+            ),
+            DkgRoundDecision::Wait
+        );
+
+        // The votes are split such that no candidate can be approved
+        best_candidate.candidate_weight = 40;
+        assert_eq!(
+            DkgRoundDecision::from_round_info(
+                &round_info,
+                &best_candidate,
+                threshold_weight,
+                false
+            ),
+            DkgRoundDecision::NewRound
+        );
+
+        // Without the total weight, the remaining signers may still approve the candidate
+        best_candidate.total_weight = None;
+        assert_eq!(
+            DkgRoundDecision::from_round_info(
+                &round_info,
+                &best_candidate,
+                threshold_weight,
+                false
+            ),
+            DkgRoundDecision::Wait
+        );
+
+        round_info.approved_aggregate_key = Some(Point::from(Scalar::random(&mut OsRng)));
+        assert_eq!(
+            DkgRoundDecision::from_round_info(&round_info, &best_candidate, threshold_weight, true),
+            DkgRoundDecision::Approved
+        );
+    }
+
+    #[test]
+    fn best_dkg_candidate_should_be_the_most_voted_key() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let stacks_client = StacksClient::from(&config);
+        let other_signers: Vec<_> = (0..3)
+            .map(|_| {
+                StacksAddress::p2pkh(
+                    false,
+                    &StacksPublicKey::from_private(&StacksPrivateKey::new()),
+                )
+            })
+            .collect();
+        signer.signer_addresses = vec![*stacks_client.get_signer_address()];
+        signer.signer_addresses.extend(other_signers);
+        let our_vote = Point::from(Scalar::random(&mut OsRng));
+        let other_vote = Point::from(Scalar::random(&mut OsRng));
+
+        let h = spawn(move || {
+            let responses = [
+                build_get_candidate_info_response(20, Some(100)),
+                // The first other signer voted for another key, which has more votes
+                build_get_vote_for_aggregate_key_response(Some(other_vote)),
+                build_get_candidate_info_response(45, Some(100)),
+                // The second did not vote
+                build_get_vote_for_aggregate_key_response(None),
+                // The third voted for the same key, whose tally is already known
+                build_get_vote_for_aggregate_key_response(Some(other_vote)),
+            ];
+            for response in responses {
+                write_response(mock_server_from_config(&config), response.as_bytes());
+            }
+        });
+        let best_candidate = signer
+            .get_best_dkg_candidate(&stacks_client, 1, our_vote)
+            .unwrap();
+        h.join().unwrap();
+        assert_eq!(
+            best_candidate,
+            CandidateInfo {
+                candidate_weight: 45,
+                total_weight: Some(100),
+            }
+        );
+    }
+
+    #[test]
+    fn sign_error_stats_should_count_each_error_type() {
+        let mut stats = SignErrorStats::default();
+        stats.record(&SignError::NonceTimeout(vec![0, 1], vec![2]));
+        stats.record(&SignError::NonceTimeout(vec![0], vec![]));
+        stats.record(&SignError::InsufficientSigners(vec![3]));
+        stats.record(&SignError::Aggregator(AggregatorError::BadGroupSig));
+        stats.record(&SignError::Aggregator(AggregatorError::BadGroupSig));
+        stats.record(&SignError::Aggregator(AggregatorError::BadGroupSig));
+        assert_eq!(
+            stats,
+            SignErrorStats {
+                nonce_timeouts: 2,
+                insufficient_signers: 1,
+                aggregator_errors: 3,
+            }
+        );
+    }
+
+    /// Build a block holding one transaction paying each of the given fees
+    fn block_with_fees(fees: &[u64]) -> NakamotoBlock {
+        let sk = StacksPrivateKey::new();
+        let txs = fees
+            .iter()
+            .map(|fee| {
+                let mut tx = StacksTransaction::new(
+                    TransactionVersion::Testnet,
+                    TransactionAuth::from_p2pkh(&sk).unwrap(),
+                    TransactionPayload::TokenTransfer(
+                        StacksAddress::burn_address(false).into(),
+                        1,
+                        TokenTransferMemo([0; 34]),
+                    ),
+                );
+                tx.set_tx_fee(*fee);
+                tx
+            })
+            .collect();
+        NakamotoBlock {
+            header: NakamotoBlockHeader::empty(),
+            txs,
+        }
+    }
+
+    #[test]
+    fn median_tx_fee_should_handle_odd_and_even_counts() {
+        assert_eq!(median_tx_fee(&block_with_fees(&[])), None);
+        assert_eq!(median_tx_fee(&block_with_fees(&[300, 100, 200])), Some(200));
+        assert_eq!(
+            median_tx_fee(&block_with_fees(&[100, 400, 200, 300])),
+            Some(250)
+        );
+        assert_eq!(
+            median_tx_fee(&block_with_fees(&[u64::MAX, u64::MAX])),
+            Some(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn signature_share_request_for_unvoted_block_should_be_nacked() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        // Point the signer at a node that is down so that the rejection ends up queued
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.stackerdb_write_retry_config = signer_config
+            .stackerdb_write_retry_config
+            .with_deadline(Duration::from_millis(200));
+        let mut signer = Signer::from(signer_config);
+
+        let block_proposal = BlockProposal {
+            block: block_with_fees(&[]),
+            burn_height: 7,
+            reward_cycle: signer.reward_cycle,
+        };
+        let block_info = BlockInfo::from(block_proposal);
+        assert!(block_info.vote.is_none());
+        signer.signer_db.insert_block(&block_info).unwrap();
+
+        let block_vote = NakamotoBlockVote {
+            signer_signature_hash: block_info.signer_signature_hash(),
+            rejected: false,
+        };
+        let mut request = SignatureShareRequest {
+            dkg_id: 1,
+            sign_id: 1,
+            sign_iter_id: 1,
+            nonce_responses: vec![],
+            message: block_vote.serialize_to_vec(),
+            is_taproot: false,
+            merkle_root: None,
+        };
+        assert!(!signer.validate_signature_share_request(&mut request));
+
+        let broadcasts = signer
+            .signer_db
+            .get_broadcasts(signer.reward_cycle)
+            .unwrap();
+        assert_eq!(broadcasts.len(), 1);
+        assert_eq!(
+            broadcasts[0].message,
+            SignerMessage::BlockResponse(BlockResponse::Rejected(BlockRejection::new(
+                block_info.signer_signature_hash(),
+                RejectCode::NoVote,
+            )))
+        );
+        let rejections = signer.signer_db.search_rejections("NoVote").unwrap();
+        assert_eq!(rejections.len(), 1);
+        assert_eq!(
+            rejections[0].signer_signature_hash,
+            block_info.signer_signature_hash()
+        );
+    }
+
+    #[test]
+    fn dkg_vote_should_be_submitted_to_the_mempool_only_in_epoch_2_5() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        // Point the signer at a node that is down so that stacker-db writes fail fast
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.stackerdb_write_retry_config = signer_config
+            .stackerdb_write_retry_config
+            .with_deadline(Duration::from_millis(200));
+        let mut signer = Signer::from(signer_config);
+
+        let (mempool_server, mempool_server_addr) = mock_server_random();
+        config.node_host = mempool_server_addr.to_string();
+        let stacks_client = StacksClient::from(&config);
+        let vote = stacks_client
+            .build_unsigned_vote_for_aggregate_public_key(
+                0,
+                0,
+                Point::from(Scalar::random(&mut OsRng)),
+                signer.reward_cycle,
+                0,
+            )
+            .unwrap();
+
+        // Before epoch 3.0, the vote is submitted to the mempool before it is written to stacker-db
+        let h = spawn(move || write_response(mempool_server, b"HTTP/1.1 200 OK\n\n"));
+        assert!(signer
+            .broadcast_dkg_vote(&stacks_client, StacksEpochId::Epoch25, vec![], vote.clone())
+            .is_err());
+        let request = h.join().unwrap();
+        assert!(request.starts_with(b"POST /v2/transactions"));
+
+        // From epoch 3.0, the vote is only written to stacker-db
+        let (mempool_server, mempool_server_addr) = mock_server_random();
+        mempool_server.set_nonblocking(true).unwrap();
+        config.node_host = mempool_server_addr.to_string();
+        let stacks_client = StacksClient::from(&config);
+        assert!(signer
+            .broadcast_dkg_vote(&stacks_client, StacksEpochId::Epoch30, vec![], vote)
+            .is_err());
+        assert_eq!(
+            mempool_server.accept().unwrap_err().kind(),
+            std::io::ErrorKind::WouldBlock
+        );
+    }
+
+    #[test]
+    fn pending_dkg_votes_should_be_taken_for_a_batch_vote() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        signer.coordinator.current_dkg_id = 2;
+        let stacks_client = StacksClient::from(&config);
+        let signer_address = *stacks_client.get_signer_address();
+        let other_config = GlobalConfig::load_from_file("./src/tests/conf/signer-1.toml").unwrap();
+        let other_stacks_client = StacksClient::from(&other_config);
+
+        let keys: Vec<_> = (0..3)
+            .map(|_| Point::from(Scalar::random(&mut OsRng)))
+            .collect();
+        let vote = |client: &StacksClient, round: u64, key: Point, nonce: u64| {
+            client
+                .build_unsigned_vote_for_aggregate_public_key(
+                    0,
+                    round,
+                    key,
+                    signer.reward_cycle,
+                    nonce,
+                )
+                .unwrap()
+        };
+        let other_vote = vote(&other_stacks_client, 0, keys[0], 7);
+
+        // Only the current round is pending: the new vote simply follows it
+        let mut signer_transactions = vec![vote(&stacks_client, 2, keys[2], 3), other_vote.clone()];
+        assert!(signer
+            .take_pending_dkg_votes(&signer_address, &mut signer_transactions)
+            .is_none());
+        assert_eq!(signer_transactions.len(), 2);
+
+        // Our votes for the unconfirmed rounds are replaced, keeping the latest vote per round
+        let mut signer_transactions = vec![
+            vote(&stacks_client, 1, keys[1], 5),
+            other_vote.clone(),
+            vote(&stacks_client, 0, keys[1], 3),
+            vote(&stacks_client, 2, keys[2], 6),
+            vote(&stacks_client, 0, keys[0], 4),
+        ];
+        let (nonce, votes) = signer
+            .take_pending_dkg_votes(&signer_address, &mut signer_transactions)
+            .unwrap();
+        assert_eq!(nonce, 3);
+        assert_eq!(
+            votes.into_iter().collect::<Vec<_>>(),
+            vec![(0, keys[0]), (1, keys[1])]
+        );
+        assert_eq!(signer_transactions, vec![other_vote]);
+    }
+
+    #[test]
+    fn malformed_aggregate_public_keys_should_be_rejected() {
+        assert!(!Signer::validate_aggregate_public_key_curve(
+            &Point::default()
+        ));
+        for _ in 0..10 {
+            let point = Point::from(Scalar::random(&mut OsRng));
+            assert!(Signer::validate_aggregate_public_key_curve(&point));
+        }
+    }
+
+    #[test]
+    fn dkg_participation_rate_should_count_public_shares_senders() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let public_shares = |dkg_id: u64, signer_id: u32| DkgPublicShares {
+            dkg_id,
+            signer_id,
+            comms: vec![],
+        };
+        assert_eq!(signer.compute_dkg_participation_rate(), 0.0);
+
+        for signer_id in [0, 1, 2, 1] {
+            signer
+                .dkg_history
+                .record_public_shares(&public_shares(1, signer_id));
+        }
+        assert_eq!(signer.dkg_history.participants, vec![0, 1, 2]);
+        assert_eq!(signer.compute_dkg_participation_rate(), 0.6);
+
+        // A new round starts over, and late shares of the previous round are ignored
+        signer
+            .dkg_history
+            .record_public_shares(&public_shares(2, 4));
+        signer
+            .dkg_history
+            .record_public_shares(&public_shares(1, 3));
+        assert_eq!(signer.dkg_history.dkg_id, 2);
+        assert_eq!(signer.dkg_history.participants, vec![4]);
+        assert_eq!(signer.compute_dkg_participation_rate(), 0.2);
+    }
+
+    #[test]
+    fn conflicting_block_proposals_should_be_detected() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let block_at_height = |chain_length: u64, fees: &[u64]| {
+            let mut block = block_with_fees(fees);
+            block.header.consensus_hash = ConsensusHash([0x01; 20]);
+            block.header.chain_length = chain_length;
+            block
+        };
+        let block = block_at_height(5, &[1]);
+        signer
+            .signer_db
+            .insert_block(&BlockInfo::from(BlockProposal {
+                block: block.clone(),
+                burn_height: 7,
+                reward_cycle: signer.reward_cycle,
+            }))
+            .unwrap();
+
+        // The same proposal seen again is not a conflict
+        assert!(!signer.detect_duplicate_block_proposals(&block));
+        assert!(!signer.detect_duplicate_block_proposals(&block_at_height(6, &[2])));
+        assert!(signer.detect_duplicate_block_proposals(&block_at_height(5, &[2])));
+
+        let mut other_tenure_block = block_at_height(5, &[2]);
+        other_tenure_block.header.consensus_hash = ConsensusHash([0x02; 20]);
+        assert!(!signer.detect_duplicate_block_proposals(&other_tenure_block));
+    }
+
+    #[test]
+    fn verify_block_fee_rate_should_reject_excessive_median_fee() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.max_acceptable_median_fee_rate = 1_000;
+        let signer = Signer::from(signer_config);
+
+        assert!(signer.verify_block_fee_rate(&block_with_fees(&[])));
+        assert!(signer.verify_block_fee_rate(&block_with_fees(&[10, 1_000, 1_000_000])));
+        assert!(!signer.verify_block_fee_rate(&block_with_fees(&[10, 1_001, 1_000_000])));
+    }
+
+    /// Build a block starting a tenure, signed by the given miner
+    fn tenure_start_block(
+        miner_sk: &StacksPrivateKey,
+        vrf_proof: Option<VRFProof>,
+    ) -> NakamotoBlock {
+        let sk = StacksPrivateKey::new();
+        let auth = TransactionAuth::from_p2pkh(&sk).unwrap();
+        let mut header = NakamotoBlockHeader::empty();
+        let tenure_change = TenureChangePayload {
+            tenure_consensus_hash: header.consensus_hash.clone(),
+            prev_tenure_consensus_hash: ConsensusHash([0x01; 20]),
+            burn_view_consensus_hash: header.consensus_hash.clone(),
+            previous_tenure_end: header.parent_block_id.clone(),
+            previous_tenure_blocks: 1,
+            cause: TenureChangeCause::BlockFound,
+            pubkey_hash: Hash160([0x02; 20]),
+        };
+        let txs = vec![
+            StacksTransaction::new(
+                TransactionVersion::Testnet,
+                auth.clone(),
+                TransactionPayload::TenureChange(tenure_change),
+            ),
+            StacksTransaction::new(
+                TransactionVersion::Testnet,
+                auth,
+                TransactionPayload::Coinbase(CoinbasePayload([0; 32]), None, vrf_proof),
+            ),
+        ];
+        header.sign_miner(miner_sk).unwrap();
+        NakamotoBlock { header, txs }
+    }
+
+    #[test]
+    fn validate_block_coinbase_tx_should_check_the_coinbase_and_the_miner() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let miner_sk = StacksPrivateKey::new();
+        let block = tenure_start_block(&miner_sk, Some(VRFProof::empty()));
+
+        // Blocks that do not start a tenure have no coinbase to check
+        assert!(signer.validate_block_coinbase_tx(&block_with_fees(&[1])));
+        // A coinbase without a VRF proof is not a Nakamoto coinbase
+        assert!(!signer.validate_block_coinbase_tx(&tenure_start_block(&miner_sk, None)));
+        // The miner can only be checked once it is known
+        assert!(signer.validate_block_coinbase_tx(&block));
+
+        let miner_pk = StacksPublicKey::from_private(&miner_sk);
+        signer.miner_key =
+            Some(PublicKey::try_from(miner_pk.to_bytes_compressed().as_slice()).unwrap());
+        assert!(signer.validate_block_coinbase_tx(&block));
+
+        let other_miner_pk = StacksPublicKey::from_private(&StacksPrivateKey::new());
+        signer.miner_key =
+            Some(PublicKey::try_from(other_miner_pk.to_bytes_compressed().as_slice()).unwrap());
+        assert!(!signer.validate_block_coinbase_tx(&block));
+    }
+
+    #[test]
+    fn blocks_should_be_rejected_if_not_mined_by_the_tenure_miner() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let miner_sk = StacksPrivateKey::new();
+        let other_miner_sk = StacksPrivateKey::new();
+        let tenure_start = tenure_start_block(&other_miner_sk, Some(VRFProof::empty()));
+        let tenure = tenure_start.header.consensus_hash.clone();
+        let other_tenure = ConsensusHash([0x02; 20]);
+        let mined_by = |miner_sk: &StacksPrivateKey, consensus_hash: &ConsensusHash| {
+            let mut block = block_with_fees(&[1]);
+            block.header.consensus_hash = consensus_hash.clone();
+            block.header.sign_miner(miner_sk).unwrap();
+            block
+        };
+
+        // The first block we see of a tenure sets its miner
+        assert!(!signer.reject_blocks_from_unknown_miner(&mined_by(&miner_sk, &tenure), 7));
+        assert!(!signer.reject_blocks_from_unknown_miner(&mined_by(&miner_sk, &tenure), 7));
+        assert!(signer.reject_blocks_from_unknown_miner(&mined_by(&other_miner_sk, &tenure), 7));
+        // Unsigned blocks have no miner
+        assert!(signer.reject_blocks_from_unknown_miner(&block_with_fees(&[1]), 7));
+
+        // Other tenures have their own miner
+        assert!(
+            !signer.reject_blocks_from_unknown_miner(&mined_by(&other_miner_sk, &other_tenure), 8)
+        );
+        assert!(signer.reject_blocks_from_unknown_miner(&mined_by(&miner_sk, &other_tenure), 8));
+        assert!(signer.reject_blocks_from_unknown_miner(&mined_by(&other_miner_sk, &tenure), 8));
+
+        // The block starting a tenure sets the miner of its own tenure only
+        assert!(!signer.reject_blocks_from_unknown_miner(&tenure_start, 8));
+        assert!(!signer.reject_blocks_from_unknown_miner(&mined_by(&other_miner_sk, &tenure), 8));
+        assert!(signer.reject_blocks_from_unknown_miner(&mined_by(&miner_sk, &tenure), 8));
+        assert!(signer.reject_blocks_from_unknown_miner(&mined_by(&miner_sk, &other_tenure), 8));
+        assert_eq!(
+            signer.tenure_miners.get(&tenure),
+            Some(&(
+                StacksAddress::p2pkh(
+                    signer.mainnet,
+                    &StacksPublicKey::from_private(&other_miner_sk)
+                ),
+                8
+            ))
+        );
+
+        // Tenures we have not seen blocks of for a while are forgotten on new burn blocks
+        signer.forget_stale_tenure_miners(7 + TENURE_MINER_RETENTION_BURN_BLOCKS);
+        assert_eq!(signer.tenure_miners.len(), 2);
+        signer.forget_stale_tenure_miners(8 + TENURE_MINER_RETENTION_BURN_BLOCKS);
+        assert!(signer.tenure_miners.is_empty());
+    }
+
+    #[test]
+    fn blocks_should_be_rejected_once_their_signing_deadline_passes() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        // Point the signer at a node that is down so that the rejection ends up queued
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.stackerdb_write_retry_config = signer_config
+            .stackerdb_write_retry_config
+            .with_deadline(Duration::from_millis(200));
+        let mut signer = Signer::from(signer_config);
+
+        let expired_block_info = BlockInfo::from(BlockProposal {
+            block: block_with_fees(&[1]),
+            burn_height: 7,
+            reward_cycle: signer.reward_cycle,
+        });
+        let pending_block_info = BlockInfo::from(BlockProposal {
+            block: block_with_fees(&[2]),
+            burn_height: 7,
+            reward_cycle: signer.reward_cycle,
+        });
+        let now = Instant::now();
+        for (block_info, deadline) in [
+            (&expired_block_info, now),
+            (&pending_block_info, now + Duration::from_secs(60)),
+        ] {
+            signer.signer_db.insert_block(block_info).unwrap();
+            signer
+                .block_sign_deadline
+                .insert(block_info.signer_signature_hash(), deadline);
+        }
+
+        signer.process_block_timeout();
+
+        let broadcasts = signer
+            .signer_db
+            .get_broadcasts(signer.reward_cycle)
+            .unwrap();
+        assert_eq!(broadcasts.len(), 1);
+        assert_eq!(
+            broadcasts[0].message,
+            SignerMessage::BlockResponse(BlockResponse::Rejected(BlockRejection::new(
+                expired_block_info.signer_signature_hash(),
+                RejectCode::SigningTimeout,
+            )))
+        );
+        assert!(signer
+            .signer_db
+            .block_lookup(
+                signer.reward_cycle,
+                &expired_block_info.signer_signature_hash()
+            )
+            .unwrap()
+            .is_none());
+        assert!(!signer
+            .block_sign_deadline
+            .contains_key(&expired_block_info.signer_signature_hash()));
+        assert!(signer
+            .block_sign_deadline
+            .contains_key(&pending_block_info.signer_signature_hash()));
+    }
+
+    #[test]
+    fn only_vote_transactions_of_the_signer_set_should_be_forwarded() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let stacks_client = StacksClient::from(&config);
+        let vote = |round: u64, nonce: u64| {
+            stacks_client
+                .build_unsigned_vote_for_aggregate_public_key(
+                    0,
+                    round,
+                    Point::from(Scalar::random(&mut OsRng)),
+                    signer.reward_cycle,
+                    nonce,
+                )
+                .unwrap()
+        };
+        let (older_vote, newer_vote) = (vote(0, 1), vote(1, 2));
+        let mut transfer = older_vote.clone();
+        transfer.payload = TransactionPayload::TokenTransfer(
+            StacksAddress::burn_address(false).into(),
+            1,
+            TokenTransferMemo([0; 34]),
+        );
+        let mut outsider_vote = vote(0, 1);
+        outsider_vote.auth = TransactionAuth::from_p2pkh(&StacksPrivateKey::new()).unwrap();
+
+        signer.cache_vote_transactions(&[newer_vote, transfer, outsider_vote, older_vote.clone()]);
+        assert_eq!(signer.known_vote_transactions.len(), 1);
+        assert_eq!(
+            signer.known_vote_transactions.get(&config.stacks_address),
+            Some(&older_vote)
+        );
+
+        // A block with every known vote transaction needs nothing forwarded, and the node is
+        // not even contacted
+        let mut block = block_with_fees(&[]);
+        block.txs.push(older_vote);
+        assert!(!signer.forward_transaction_to_peers(&stacks_client, &block));
+    }
+
+    #[test]
+    fn block_responses_should_be_broadcast_once_per_block() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let (first_hash, second_hash) = (Sha512Trunc256Sum([1; 32]), Sha512Trunc256Sum([2; 32]));
+
+        assert!(!signer.process_block_duplicate_vote(&first_hash));
+        assert!(signer.process_block_duplicate_vote(&first_hash));
+        assert!(!signer.process_block_duplicate_vote(&second_hash));
+        assert_eq!(signer.broadcast_responses.len(), 2);
+    }
+
+    #[test]
+    fn changed_votes_should_supersede_the_known_vote_transaction() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let stacks_client = StacksClient::from(&config);
+        let vote = |point: Point, nonce: u64| {
+            stacks_client
+                .build_unsigned_vote_for_aggregate_public_key(
+                    0,
+                    0,
+                    point,
+                    signer.reward_cycle,
+                    nonce,
+                )
+                .unwrap()
+        };
+        let (first_key, second_key) = (
+            Point::from(Scalar::random(&mut OsRng)),
+            Point::from(Scalar::random(&mut OsRng)),
+        );
+        let first_vote = vote(first_key, 1);
+        let changed_vote = vote(second_key, 2);
+
+        signer.cache_vote_transactions(&[first_vote.clone()]);
+        // Re-sending the same vote is still a duplicate
+        signer.cache_vote_transactions(&[vote(first_key, 2)]);
+        assert_eq!(
+            signer.known_vote_transactions.get(&config.stacks_address),
+            Some(&first_vote)
+        );
+
+        signer.cache_vote_transactions(&[changed_vote.clone()]);
+        assert_eq!(signer.known_vote_transactions.len(), 1);
+        assert_eq!(
+            signer.known_vote_transactions.get(&config.stacks_address),
+            Some(&changed_vote)
+        );
+    }
+
+    #[test]
+    fn metrics_export_prometheus_should_expose_every_signer_metric() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        signer.sign_error_stats.nonce_timeouts = 2;
+        signer
+            .block_sign_deadline
+            .insert(Sha512Trunc256Sum([1; 32]), Instant::now());
+
+        let export = signer.metrics_export_prometheus();
+        let labels = format!(
+            "signer_id=\"{}\",reward_cycle=\"{}\"",
+            signer.signer_id, signer.reward_cycle
+        );
+        let mut samples = HashMap::new();
+        for line in export.lines().filter(|line| !line.starts_with('#')) {
+            let (name, rest) = line.split_once('{').expect("sample without labels");
+            let (sample_labels, value) = rest.split_once("} ").expect("malformed sample");
+            assert_eq!(sample_labels, labels);
+            let value: f64 = value.parse().expect("sample value is not a number");
+            assert!(samples.insert(name.to_string(), value).is_none());
+        }
+        for name in [
+            "stacks_signer_pending_blocks",
+            "stacks_signer_blocks_pending_signature",
+            "stacks_signer_blocks_being_signed",
+            "stacks_signer_pending_nonce_requests",
+            "stacks_signer_queued_messages",
+            "stacks_signer_known_vote_transactions",
+            "stacks_signer_dkg_participation_rate",
+            "stacks_signer_sign_nonce_timeouts_total",
+            "stacks_signer_sign_insufficient_signers_total",
+            "stacks_signer_sign_aggregator_errors_total",
+            "stacks_signer_block_validation_events_total",
+            "stacks_signer_signer_message_events_total",
+            "stacks_signer_miner_message_events_total",
+            "stacks_signer_status_check_events_total",
+            "stacks_signer_new_burn_block_events_total",
+            "stacks_signer_packets_processed_total",
+            "stacks_signer_sign_rounds_started_total",
+            "stacks_signer_sign_rounds_completed_total",
+            "stacks_signer_sign_rounds_failed_total",
+            "stacks_signer_dkg_rounds_started_total",
+            "stacks_signer_dkg_rounds_completed_total",
+            "stacks_signer_dkg_rounds_failed_total",
+            "stacks_signer_block_validations_accepted_total",
+            "stacks_signer_block_validations_rejected_total",
+            "stacks_signer_block_validation_seconds_sum",
+            "stacks_signer_block_validation_seconds_count",
+            "stacks_signer_stackerdb_sends_acked_total",
+            "stacks_signer_stackerdb_sends_unacknowledged_total",
+            "stacks_signer_stackerdb_sends_rejected_total",
+            "stacks_signer_stackerdb_sends_failed_total",
+            "stacks_signer_nacks_sent_total",
+            "stacks_signer_nacks_received_total",
+        ] {
+            assert!(samples.contains_key(name), "missing metric {name}");
+            assert!(export.contains(&format!("# TYPE {name} ")));
+        }
+        assert_eq!(samples.len(), 32);
+        assert_eq!(samples["stacks_signer_sign_nonce_timeouts_total"], 2.0);
+        assert_eq!(samples["stacks_signer_blocks_being_signed"], 1.0);
+    }
+
+    #[test]
+    fn unconfirmed_dkg_votes_should_be_resubmitted() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let stacks_client = StacksClient::from(&config);
+
+        // Without a DKG result there is nothing to vote for, and the node is not contacted
+        signer.resubmit_missed_votes(&stacks_client).unwrap();
+
+        let dkg_public_key = Point::from(Scalar::random(&mut OsRng));
+        signer
+            .coordinator
+            .set_aggregate_public_key(Some(dkg_public_key));
+        let vote = stacks_client
+            .build_unsigned_vote_for_aggregate_public_key(
+                signer.stackerdb.get_signer_slot_id().0,
+                signer.coordinator.current_dkg_id,
+                dkg_public_key,
+                signer.reward_cycle,
+                1,
+            )
+            .unwrap();
+        let vote_txid = vote.txid();
+
+        let h = spawn(move || {
+            // The vote is not confirmed on chain...
+            let vote_response = build_get_vote_for_aggregate_key_response(None);
+            write_response(mock_server_from_config(&config), vote_response.as_bytes());
+            // ...but is still pending in our stacker-db slot
+            let nonce_response = build_account_nonce_response(1);
+            write_response(mock_server_from_config(&config), nonce_response.as_bytes());
+            let mut chunk_response = b"HTTP/1.1 200 OK\n\n".to_vec();
+            chunk_response.extend(SignerMessage::Transactions(vec![vote]).serialize_to_vec());
+            write_response(mock_server_from_config(&config), &chunk_response);
+            // In epoch 2.5 the pending vote goes to the mempool again
+            let pox_response = build_get_pox_data_response(None, None, Some(100), Some(200)).0;
+            write_response(mock_server_from_config(&config), pox_response.as_bytes());
+            let peer_response = build_get_peer_info_response(Some(150), None).0;
+            write_response(mock_server_from_config(&config), peer_response.as_bytes());
+            write_response(
+                mock_server_from_config(&config),
+                format!("HTTP/1.1 200 OK\n\n{vote_txid}").as_bytes(),
+            )
+        });
+        signer.resubmit_missed_votes(&stacks_client).unwrap();
+        let request_bytes = h.join().unwrap();
+        assert!(request_bytes.starts_with(b"POST /v2/transactions"));
+    }
+
+    #[test]
+    fn signer_address_ids_should_be_refreshed_once_per_reward_cycle() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let stacks_client = StacksClient::from(&config);
+        let reward_set: Vec<_> = (0..3)
+            .map(|_| NakamotoSignerEntry {
+                signing_key: StacksPublicKey::from_private(&StacksPrivateKey::new())
+                    .to_bytes_compressed()
+                    .try_into()
+                    .unwrap(),
+                stacked_amt: 1,
+                weight: 1,
+            })
+            .collect();
+        let signer_ids = SignerEntries::parse(false, &reward_set).unwrap().signer_ids;
+        let reward_cycle = signer.reward_cycle;
+
+        let response = build_get_reward_set_response(reward_set.clone());
+        let server_config = config.clone();
+        let h = spawn(move || {
+            write_response(mock_server_from_config(&server_config), response.as_bytes())
+        });
+        signer
+            .update_signer_address_ids(&stacks_client, reward_cycle)
+            .unwrap();
+        h.join().unwrap();
+        assert_eq!(signer.signer_address_ids, signer_ids);
+        assert_eq!(signer.signer_addresses.len(), 3);
+
+        // The reward set of the cycle is known, so the stacks node is not asked again
+        signer
+            .update_signer_address_ids(&stacks_client, reward_cycle)
+            .unwrap();
+
+        // An unchanged reward set leaves the ids as they are
+        let response = build_get_reward_set_response(reward_set);
+        let h =
+            spawn(move || write_response(mock_server_from_config(&config), response.as_bytes()));
+        signer
+            .update_signer_address_ids(&stacks_client, reward_cycle + 1)
+            .unwrap();
+        h.join().unwrap();
+        assert_eq!(signer.signer_address_ids, signer_ids);
+        assert_eq!(
+            signer.signer_address_ids_reward_cycle,
+            Some(reward_cycle + 1)
+        );
+    }
+
+    #[test]
+    fn blocks_confirmed_by_the_stacks_node_should_not_be_rebroadcast() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let stacks_client = StacksClient::from(&config);
+
+        let mut block_info = BlockInfo::new_with_vote(
+            BlockProposal {
+                block: block_with_fees(&[]),
+                burn_height: 7,
+                reward_cycle: signer.reward_cycle,
+            },
+            false,
+        );
+        block_info.signed_over = true;
+        block_info.proposed_at = Some(get_epoch_time_secs().saturating_sub(3600));
+        let signature = ThresholdSignature(Signature {
+            R: Point::from(Scalar::random(&mut OsRng)),
+            z: Scalar::random(&mut OsRng),
+        });
+        block_info.signature = Some(signature.clone());
+        signer.signer_db.insert_block(&block_info).unwrap();
+
+        // The stacks node serves the block with the aggregate signature applied
+        let mut signed_block = block_info.block.clone();
+        signed_block.header.signer_signature = signature;
+        let signed_block_id = signed_block.block_id();
+        assert_ne!(signed_block_id, block_info.block.block_id());
+        let mut response = b"HTTP/1.1 200 OK\n\n".to_vec();
+        response.extend(signed_block.serialize_to_vec());
+        let server_config = config.clone();
+        let h = spawn(move || write_response(mock_server_from_config(&server_config), &response));
+        signer.pass_time(BLOCK_REBROADCAST_INTERVAL);
+        signer.rebroadcast_unconfirmed_blocks(&stacks_client);
+        let request = h.join().unwrap();
+        assert!(String::from_utf8_lossy(&request)
+            .starts_with(&format!("GET /v3/blocks/{signed_block_id}")));
+
+        // The block is confirmed, so it is neither looked up nor broadcast again
+        assert!(signer
+            .signer_db
+            .get_blocks_needing_rebroadcast(signer.reward_cycle, BLOCK_REBROADCAST_MIN_AGE)
+            .unwrap()
+            .is_empty());
+        signer.pass_time(BLOCK_REBROADCAST_INTERVAL);
+        signer.rebroadcast_unconfirmed_blocks(&stacks_client);
+        assert!(!signer.stackerdb.has_queued_messages());
+    }
+
+    #[test]
+    fn self_test_should_check_the_key_the_registration_and_the_node() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let stacks_client = StacksClient::from(&config);
+
+        let h = spawn(move || {
+            let peer_response = build_get_peer_info_response(None, None).0;
+            write_response(mock_server_from_config(&config), peer_response.as_bytes());
+            // The node fails to serve its info
+            write_response(
+                mock_server_from_config(&config),
+                b"HTTP/1.1 404 Not Found\n\n",
+            );
+        });
+        signer.self_test(&stacks_client).unwrap();
+        assert!(matches!(
+            signer.self_test(&stacks_client),
+            Err(SignerError::SelfTestFailed(_))
+        ));
+        h.join().unwrap();
+
+        // The node is not contacted once the signer is found misconfigured
+        let private_key = signer.state_machine.network_private_key;
+        signer.state_machine.network_private_key = Scalar::random(&mut OsRng);
+        assert!(signer.self_test(&stacks_client).is_err());
+        signer.state_machine.network_private_key = private_key;
+
+        signer.signer_id = 1;
+        assert!(signer.self_test(&stacks_client).is_err());
+    }
+
+    #[test]
+    fn signers_failing_their_self_test_should_stay_uninitialized() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let stacks_client = StacksClient::from(&config);
+        let (res_send, _res_recv) = std::sync::mpsc::channel();
+        // The private key does not match the registered public key
+        signer.state_machine.network_private_key = Scalar::random(&mut OsRng);
+        signer.commands.push_back(SignerCommand::Dkg);
+
+        let reward_cycle = signer.reward_cycle;
+        signer.process_event(
+            &stacks_client,
+            Some(&SignerEvent::StatusCheck),
+            res_send,
+            reward_cycle,
+        );
+        assert!(!signer.self_test_passed);
+        assert_eq!(signer.state, State::Uninitialized);
+        signer.process_command(&stacks_client, reward_cycle, None);
+        assert_eq!(signer.state, State::Uninitialized);
+        assert_eq!(signer.commands.len(), 1);
+    }
+
+    #[test]
+    fn coordinator_config_drift_should_trigger_a_fresh_dkg() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let db_path = std::env::temp_dir().join(format!(
+            "stacks-signer-config-drift-{}.sqlite",
+            rand::random::<u64>()
+        ));
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.db_path = db_path.clone();
+        let signer = Signer::from(signer_config.clone());
+        assert!(signer.commands.is_empty());
+        // Restarting with the same signing parameters keeps the saved state
+        let signer = Signer::from(signer_config);
+        assert!(signer.commands.is_empty());
+
+        let mut signer_config = generate_signer_config(&config, 5, 25);
+        signer_config.db_path = db_path;
+        let signer = Signer::from(signer_config);
+        let is_dkg_coordinator = signer.get_coordinator_dkg().0 == signer.signer_id;
+        assert_eq!(
+            signer.commands.contains(&SignerCommand::Dkg),
+            is_dkg_coordinator
+        );
+        assert!(signer
+            .signer_db
+            .get_last_coordinator_config(signer.reward_cycle)
+            .unwrap()
+            .is_some_and(|snapshot| snapshot.num_keys == 25));
+    }
+
+    #[test]
+    fn missing_transaction_policy_should_decide_the_vote() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let missing = block_with_fees(&[1, 2]).txs;
+
+        assert!(!signer.should_reject_missing_transactions(&[]));
+        signer.missing_transaction_policy = MissingTransactionPolicy::Reject;
+        assert!(signer.should_reject_missing_transactions(&missing));
+        signer.missing_transaction_policy = MissingTransactionPolicy::AcceptAndWarn;
+        assert!(!signer.should_reject_missing_transactions(&missing));
+    }
+
+    #[test]
+    fn missing_transaction_policy_should_escalate_to_rejection() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.missing_transaction_policy = MissingTransactionPolicy::RejectAfterNBlocks(3);
+        let mut signer = Signer::from(signer_config);
+        let missing = block_with_fees(&[1, 2]).txs;
+
+        assert!(!signer.should_reject_missing_transactions(&missing));
+        assert!(!signer.should_reject_missing_transactions(&missing));
+        assert!(signer.should_reject_missing_transactions(&missing));
+        assert!(signer.should_reject_missing_transactions(&missing[..1]));
+
+        // A proposal including the transactions resets their streak
+        assert!(!signer.should_reject_missing_transactions(&[]));
+        assert!(!signer.should_reject_missing_transactions(&missing));
+    }
+
+    #[test]
+    fn unresponsive_coordinator_should_be_escalated() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let stacks_client = StacksClient::from(&config);
+        signer.coordinator.config.nonce_timeout = Some(Duration::from_millis(10));
+        let other_signer_id = (0..5).find(|id| *id != signer.signer_id).unwrap();
+        assert!(signer.coordinator_selector.set_coordinator(other_signer_id));
+
+        // Nothing to sign, so nothing to wait for
+        signer.escalate_coordinator(&stacks_client);
+        assert!(signer.coordinator_wait_start.is_none());
+
+        let mut block_info = BlockInfo::from(BlockProposal {
+            block: block_with_fees(&[1]),
+            burn_height: 7,
+            reward_cycle: signer.reward_cycle,
+        });
+        block_info.valid = Some(true);
+        signer.signer_db.insert_block(&block_info).unwrap();
+
+        signer.escalate_coordinator(&stacks_client);
+        assert!(signer.coordinator_wait_start.is_some());
+        assert_eq!(
+            signer.coordinator_selector.get_coordinator().0,
+            other_signer_id
+        );
+
+        std::thread::sleep(Duration::from_millis(25));
+        signer.escalate_coordinator(&stacks_client);
+        assert_ne!(
+            signer.coordinator_selector.get_coordinator().0,
+            other_signer_id
+        );
+        assert!(signer.coordinator_wait_start.is_none());
+    }
+
+    #[test]
+    fn blocks_should_be_rejected_once_their_validation_attempts_are_exhausted() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        // Point the signer at a node that is down so that every submission fails
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        config.retry_config = config
+            .retry_config
+            .with_deadline(Duration::from_millis(200));
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.max_validation_attempts = 2;
+        signer_config.stackerdb_write_retry_config = signer_config
+            .stackerdb_write_retry_config
+            .with_deadline(Duration::from_millis(200));
+        let mut signer = Signer::from(signer_config);
+        let stacks_client = StacksClient::from(&config);
+
+        let mut block_info = BlockInfo::from(BlockProposal {
+            block: block_with_fees(&[1]),
+            burn_height: 7,
+            reward_cycle: signer.reward_cycle,
+        });
+        for attempt in 1..=2 {
+            assert!(signer.submit_block_for_validation(&stacks_client, &mut block_info));
+            assert_eq!(block_info.validation_attempts, attempt);
+            // Failed submissions are retried on the next status check
+            assert!(block_info.proposed_at.is_none());
+            assert!(block_info.is_awaiting_validation());
+        }
+        signer.signer_db.insert_block(&block_info).unwrap();
+
+        assert!(!signer.submit_block_for_validation(&stacks_client, &mut block_info));
+        assert!(signer
+            .signer_db
+            .block_lookup(signer.reward_cycle, &block_info.signer_signature_hash())
+            .unwrap()
+            .is_none());
+        let broadcasts = signer
+            .signer_db
+            .get_broadcasts(signer.reward_cycle)
+            .unwrap();
+        assert_eq!(broadcasts.len(), 1);
+        assert_eq!(
+            broadcasts[0].message,
+            SignerMessage::BlockResponse(BlockResponse::Rejected(BlockRejection::new(
+                block_info.signer_signature_hash(),
+                RejectCode::ValidationAttemptsExceeded,
+            )))
+        );
+    }
+
+    /// Declines blocks with more than the given number of transactions
+    struct MaxTransactionsFilter(usize);
+
+    impl BlockProposalFilter for MaxTransactionsFilter {
+        fn should_accept(&self, block: &NakamotoBlock) -> bool {
+            block.txs.len() <= self.0
+        }
+    }
+
+    #[test]
+    fn blocks_declined_by_the_proposal_filter_should_be_rejected_without_validation() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        config.retry_config = config
+            .retry_config
+            .with_deadline(Duration::from_millis(200));
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.block_proposal_filter = Some(Arc::new(MaxTransactionsFilter(1)));
+        signer_config.stackerdb_write_retry_config = signer_config
+            .stackerdb_write_retry_config
+            .with_deadline(Duration::from_millis(200));
+        let mut signer = Signer::from(signer_config);
+        let stacks_client = StacksClient::from(&config);
+
+        let mut accepted_block_info = BlockInfo::from(BlockProposal {
+            block: block_with_fees(&[1]),
+            burn_height: 7,
+            reward_cycle: signer.reward_cycle,
+        });
+        assert!(signer.submit_block_for_validation(&stacks_client, &mut accepted_block_info));
+        assert_eq!(accepted_block_info.validation_attempts, 1);
+
+        let mut declined_block_info = BlockInfo::from(BlockProposal {
+            block: block_with_fees(&[1, 2]),
+            burn_height: 7,
+            reward_cycle: signer.reward_cycle,
+        });
+        assert!(!signer.submit_block_for_validation(&stacks_client, &mut declined_block_info));
+        assert_eq!(declined_block_info.validation_attempts, 0);
+        let broadcasts = signer
+            .signer_db
+            .get_broadcasts(signer.reward_cycle)
+            .unwrap();
+        assert_eq!(broadcasts.len(), 1);
+        assert_eq!(
+            broadcasts[0].message,
+            SignerMessage::BlockResponse(BlockResponse::Rejected(BlockRejection::new(
+                declined_block_info.signer_signature_hash(),
+                RejectCode::FilteredProposal,
+            )))
+        );
+    }
+
+    #[test]
+    fn parallel_validation_should_send_the_validation_requests_concurrently() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.parallel_validation = true;
+        let signer = Signer::from(signer_config);
+        let stacks_client = StacksClient::from(&config);
+
+        let mut block_infos: Vec<_> = (1..=PARALLEL_VALIDATION_MIN_BLOCKS as u64)
+            .map(|fee| {
+                BlockInfo::from(BlockProposal {
+                    block: block_with_fees(&[fee]),
+                    burn_height: 7,
+                    reward_cycle: signer.reward_cycle,
+                })
+            })
+            .collect();
+        // As many requests as there are worker threads should be in flight at once
+        let concurrent_requests = PARALLEL_VALIDATION_MIN_BLOCKS.min(rayon::current_num_threads());
+        let server = spawn(move || {
+            let accept_request = || {
+                let mut stream = mock_server.accept().unwrap().0;
+                let mut request_bytes = [0u8; 1024];
+                let _ = stream.read(&mut request_bytes).unwrap();
+                stream
+            };
+            let in_flight: Vec<_> = (0..concurrent_requests).map(|_| accept_request()).collect();
+            for mut stream in in_flight {
+                stream.write_all(b"HTTP/1.1 200 OK\n\n").unwrap();
+            }
+            for _ in concurrent_requests..PARALLEL_VALIDATION_MIN_BLOCKS {
+                accept_request().write_all(b"HTTP/1.1 200 OK\n\n").unwrap();
+            }
+        });
+        signer.send_validation_requests(&stacks_client, &mut block_infos);
+        server.join().unwrap();
+        assert!(block_infos
+            .iter()
+            .all(|block_info| block_info.proposed_at.is_some()));
+    }
+
+    #[test]
+    fn blocks_should_only_be_accepted_on_top_of_known_parents() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let stacks_client = StacksClient::from(&config);
+        let parent_block = NakamotoBlock {
+            header: NakamotoBlockHeader::empty(),
+            txs: vec![],
+        };
+        let mut block = block_with_fees(&[1]);
+        block.header.parent_block_id = parent_block.block_id();
+
+        let parent_config = config.clone();
+        let h = spawn(move || {
+            write_response(
+                mock_server_from_config(&parent_config),
+                b"HTTP/1.1 404 Not Found\n\n",
+            );
+            let mut parent_response = b"HTTP/1.1 200 OK\n\n".to_vec();
+            parent_response.extend(parent_block.serialize_to_vec());
+            write_response(mock_server_from_config(&parent_config), &parent_response)
+        });
+        assert!(!signer
+            .verify_block_ancestor(&stacks_client, &block)
+            .unwrap());
+        assert!(signer
+            .verify_block_ancestor(&stacks_client, &block)
+            .unwrap());
+        let request_bytes = h.join().unwrap();
+        assert!(request_bytes
+            .starts_with(format!("GET /v3/blocks/{}", block.header.parent_block_id).as_bytes()));
+    }
+
+    #[test]
+    fn repeated_bogus_signature_share_requests_should_nack_the_round_once() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        // Point the signer at a node that is down so that the NACK ends up queued
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.max_rejected_requests_per_round = 2;
+        signer_config.stackerdb_write_retry_config = signer_config
+            .stackerdb_write_retry_config
+            .with_deadline(Duration::from_millis(200));
+        let mut signer = Signer::from(signer_config);
+        let coordinator_id = signer.coordinator_selector.get_coordinator().0;
+
+        let unknown_block_vote = NakamotoBlockVote {
+            signer_signature_hash: Sha512Trunc256Sum([7; 32]),
+            rejected: false,
+        };
+        let mut requests = vec![
+            unknown_block_vote.serialize_to_vec(),
+            b"not a block vote".to_vec(),
+            unknown_block_vote.serialize_to_vec(),
+        ]
+        .into_iter()
+        .map(|message| SignatureShareRequest {
+            dkg_id: 1,
+            sign_id: 3,
+            sign_iter_id: 1,
+            nonce_responses: vec![],
+            message,
+            is_taproot: false,
+            merkle_root: None,
+        })
+        .collect::<Vec<_>>();
+        for request in requests.iter_mut() {
+            assert!(!signer.validate_signature_share_request(request));
+        }
+
+        let rejected_requests = signer
+            .signer_db
+            .get_rejected_requests(signer.reward_cycle)
+            .unwrap();
+        assert_eq!(
+            rejected_requests
+                .iter()
+                .map(|r| (r.sign_id, r.coordinator_id, r.message_hash, r.reason))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    3,
+                    coordinator_id,
+                    Sha512Trunc256Sum([7; 32]),
+                    NackReason::UnexpectedCoordinator
+                ),
+                (
+                    3,
+                    coordinator_id,
+                    Sha512Trunc256Sum::from_data(b"not a block vote"),
+                    NackReason::MalformedPacket
+                ),
+                (
+                    3,
+                    coordinator_id,
+                    Sha512Trunc256Sum([7; 32]),
+                    NackReason::UnexpectedCoordinator
+                ),
+            ]
+        );
+        let broadcasts = signer
+            .signer_db
+            .get_broadcasts(signer.reward_cycle)
+            .unwrap();
+        assert_eq!(broadcasts.len(), 1);
+        assert_eq!(
+            broadcasts[0].message,
+            SignerMessage::Nack(NackMessage {
+                signer_id: signer.signer_id,
+                sign_id: 3,
+                sign_iter_id: 1,
+                coordinator_id,
+                reason: NackReason::MalformedPacket,
+            })
+        );
+    }
+
+    #[test]
+    fn received_nacks_should_be_reported_once_per_interval() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        // Point the signer at a node that is down so that the reports end up queued
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.stale_node_nack_policy = Some(StaleNodeNackPolicy::default());
+        signer_config.stackerdb_write_retry_config = signer_config
+            .stackerdb_write_retry_config
+            .with_deadline(Duration::from_millis(200));
+        let mut signer = Signer::from(signer_config);
+        let policy = signer.stale_node_nack_policy.unwrap();
+        let coordinator_metadata = CoordinatorMetadata {
+            burn_block_height: 100,
+            burn_header_hash: None,
+            pox_consensus_hash: ConsensusHash([1; 20]),
+        };
+        signer.coordinator_metadata = Some(coordinator_metadata.clone());
+        let start = signer.last_nack_report;
+        signer.received_nacks.insert(1, start);
+        signer.received_nacks.insert(2, start);
+
+        // Not due yet
+        signer.aggregate_nack_report_at(start + policy.report_interval / 2);
+        assert!(signer
+            .signer_db
+            .get_broadcasts(signer.reward_cycle)
+            .unwrap()
+            .is_empty());
+
+        signer.aggregate_nack_report_at(start + policy.report_interval);
+        let broadcasts = signer
+            .signer_db
+            .get_broadcasts(signer.reward_cycle)
+            .unwrap();
+        assert_eq!(broadcasts.len(), 1);
+        assert_eq!(
+            broadcasts[0].message,
+            SignerMessage::NackReport {
+                sender_id: signer.signer_id,
+                coordinator_metadata,
+                nacked_signer_count: 2,
+            }
+        );
+
+        // Once the NACKs expire there is nothing left to report
+        signer.aggregate_nack_report_at(start + policy.report_interval * 2 + policy.nack_ttl);
+        assert!(signer.received_nacks.is_empty());
+        assert_eq!(
+            signer
+                .signer_db
+                .get_broadcasts(signer.reward_cycle)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn nack_reports_from_enough_signers_should_back_off() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.stale_node_nack_policy = Some(StaleNodeNackPolicy::default());
+        let mut signer = Signer::from(signer_config);
+        let policy = signer.stale_node_nack_policy.unwrap();
+        let now = Instant::now();
+
+        // 3 of the 5 signers is below the 70% threshold
+        signer.handle_nack_report(1, 3, now);
+        assert_eq!(signer.back_off_until, None);
+        // Our own reports do not count
+        signer.handle_nack_report(signer.signer_id, 5, now);
+        assert_eq!(signer.back_off_until, None);
+
+        signer.handle_nack_report(1, 4, now);
+        assert_eq!(signer.back_off_until, Some(now + policy.back_off_duration));
+        // A later report extends the back-off, an earlier one does not shorten it
+        let later = now + Duration::from_secs(1);
+        signer.handle_nack_report(2, 4, later);
+        assert_eq!(
+            signer.back_off_until,
+            Some(later + policy.back_off_duration)
+        );
+        signer.handle_nack_report(3, 5, now);
+        assert_eq!(
+            signer.back_off_until,
+            Some(later + policy.back_off_duration)
+        );
+    }
+
+    #[test]
+    fn dkg_conflicts_backed_by_votes_should_reset_the_dkg_round() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let webhook_url = format!("http://{}/alerts", listener.local_addr().unwrap());
+        signer_config.alert_notifier = Some(AlertNotifier::spawn(&webhook_url));
+        let mut signer = Signer::from(signer_config);
+        let stacks_client = StacksClient::from(&config);
+        let reward_cycle = signer.reward_cycle;
+        let previous = (1, Point::from(Scalar::random(&mut OsRng)));
+        let conflicting = (2, Point::from(Scalar::random(&mut OsRng)));
+        signer.coordinator.current_dkg_id = 2;
+        signer
+            .coordinator
+            .set_aggregate_public_key(Some(conflicting.1));
+        signer.last_dkg_result = Some(conflicting);
+
+        // Reports from another reward cycle, or of rounds older than ours, are ignored without contacting the node
+        signer.handle_dkg_conflict(&stacks_client, 1, reward_cycle + 1, previous, conflicting);
+        signer.coordinator.current_dkg_id = 3;
+        signer.handle_dkg_conflict(&stacks_client, 1, reward_cycle, previous, conflicting);
+        signer.coordinator.current_dkg_id = 2;
+
+        // Only one of the keys received votes
+        let h = spawn(move || {
+            for weight in [Some(10), None] {
+                let tally_response = build_get_aggregate_key_tally_response(weight);
+                write_response(mock_server_from_config(&config), tally_response.as_bytes());
+            }
+            config
+        });
+        signer.handle_dkg_conflict(&stacks_client, 1, reward_cycle, previous, conflicting);
+        let config = h.join().unwrap();
+        assert_eq!(signer.coordinator.current_dkg_id, 2);
+        assert_eq!(signer.last_dkg_result, Some(conflicting));
+
+        let h = spawn(move || {
+            for weight in [Some(10), Some(5)] {
+                let tally_response = build_get_aggregate_key_tally_response(weight);
+                write_response(mock_server_from_config(&config), tally_response.as_bytes());
+            }
+        });
+        signer.handle_dkg_conflict(&stacks_client, 1, reward_cycle, previous, conflicting);
+        h.join().unwrap();
+        assert_eq!(signer.coordinator.current_dkg_id, 0);
+        assert_eq!(signer.coordinator.aggregate_public_key, None);
+        assert_eq!(signer.last_dkg_result, None);
+        let alert = receive_alert(&listener, "200 OK");
+        assert_eq!(alert.event, AlertEvent::DkgConflict);
+        assert_eq!(alert.severity, AlertSeverity::Critical);
+        assert!(alert.details.contains("rounds 1 and 2"));
+    }
+
+    #[test]
+    fn critical_events_should_be_posted_to_the_alert_webhook() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.stale_node_nack_policy = Some(StaleNodeNackPolicy::default());
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let webhook_url = format!("http://{}/alerts", listener.local_addr().unwrap());
+        signer_config.alert_notifier = Some(AlertNotifier::spawn(&webhook_url));
+        let mut signer = Signer::from(signer_config);
+        let stacks_client = StacksClient::from(&config);
+
+        signer.process_operation_results(
+            &stacks_client,
+            &[OperationResult::DkgError(DkgError::DkgPublicTimeout(vec![
+                1,
+            ]))],
+        );
+        let alert = receive_alert(&listener, "200 OK");
+        assert_eq!(alert.event, AlertEvent::DkgFailure);
+        assert_eq!(alert.severity, AlertSeverity::Critical);
+        assert_eq!(alert.signer_id, Some(signer.signer_id));
+        assert_eq!(alert.reward_cycle, Some(signer.reward_cycle));
+        assert!(alert.details.contains("DkgPublicTimeout"));
+
+        signer.handle_nack_report(1, 4, Instant::now());
+        let alert = receive_alert(&listener, "200 OK");
+        assert_eq!(alert.event, AlertEvent::NackBackOff);
+        assert_eq!(alert.severity, AlertSeverity::Warning);
+        assert_eq!(alert.signer_id, Some(signer.signer_id));
+        assert!(alert.details.contains("4 of 5 signers"));
+    }
+
+    fn dkg_public_shares_packet(dkg_id: u64, signer_id: u32) -> Packet {
+        Packet {
+            msg: Message::DkgPublicShares(DkgPublicShares {
+                dkg_id,
+                signer_id,
+                comms: vec![],
+            }),
+            sig: vec![],
+        }
+    }
+
+    #[test]
+    fn old_round_packets_should_never_reach_the_state_machine() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let stacks_client = StacksClient::from(&config);
+        let (res_send, _res_recv) = std::sync::mpsc::channel();
+        signer.coordinator.current_dkg_id = 5;
+
+        // Shares of a completed round, replayed from stacker-db
+        signer.handle_packets(
+            &stacks_client,
+            res_send,
+            &[dkg_public_shares_packet(3, 1)],
+            signer.reward_cycle,
+        );
+        assert_eq!(signer.dkg_history, DkgHistory::default());
+        assert_eq!(signer.packet_round_guard.num_next_round_packets(), 0);
+    }
+
+    #[test]
+    fn next_round_packets_should_be_held_until_their_round_starts() {
+        let mut guard = PacketRoundGuard::default();
+        let now = Instant::now();
+        let dkg_begin_packet = |dkg_id| Packet {
+            msg: Message::DkgBegin(DkgBegin { dkg_id }),
+            sig: vec![],
+        };
+
+        assert_eq!(
+            guard.filter_packets(&[dkg_public_shares_packet(5, 1)], 5, now),
+            vec![dkg_public_shares_packet(5, 1)]
+        );
+        // Packets of the next round are held until its DkgBegin, and further ones are dropped
+        assert!(guard
+            .filter_packets(
+                &[
+                    dkg_public_shares_packet(6, 1),
+                    dkg_public_shares_packet(8, 1)
+                ],
+                5,
+                now
+            )
+            .is_empty());
+        assert_eq!(guard.num_next_round_packets(), 1);
+        assert_eq!(
+            guard.filter_packets(
+                &[dkg_begin_packet(6), dkg_public_shares_packet(6, 2)],
+                5,
+                now
+            ),
+            vec![
+                dkg_begin_packet(6),
+                dkg_public_shares_packet(6, 1),
+                dkg_public_shares_packet(6, 2)
+            ]
+        );
+        assert_eq!(guard.dkg_id, 6);
+        assert!(guard
+            .filter_packets(&[dkg_begin_packet(5)], 5, now)
+            .is_empty());
+
+        // Held packets expire
+        assert!(guard
+            .filter_packets(&[dkg_public_shares_packet(7, 1)], 5, now)
+            .is_empty());
+        assert!(guard
+            .filter_packets(&[], 5, now + NEXT_ROUND_PACKET_TTL)
+            .is_empty());
+        assert_eq!(guard.num_next_round_packets(), 0);
+
+        // The held packets are bounded
+        let packets: Vec<_> = (0..=MAX_NEXT_ROUND_PACKETS)
+            .map(|signer_id| dkg_public_shares_packet(7, signer_id as u32))
+            .collect();
+        assert!(guard.filter_packets(&packets, 5, now).is_empty());
+        assert_eq!(guard.num_next_round_packets(), MAX_NEXT_ROUND_PACKETS);
+
+        // Signing packets are checked against the round of the latest NonceRequest
+        let nonce_request_packet = Packet {
+            msg: Message::NonceRequest(nonce_request(3, vec![1])),
+            sig: vec![],
+        };
+        assert_eq!(
+            guard.packet_round(&nonce_response_packet(0, 1).msg),
+            PacketRound::Next
+        );
+        assert_eq!(
+            guard.filter_packets(&[nonce_request_packet.clone()], 6, now),
+            vec![nonce_request_packet]
+        );
+        assert_eq!(guard.sign_id, Some(3));
+        assert_eq!(
+            guard.packet_round(&nonce_response_packet(0, 1).msg),
+            PacketRound::Stale
+        );
+    }
+
+    #[test]
+    fn coordinator_selection_history_should_measure_flapping() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let initial_coordinator_id = signer.coordinator_selector.get_coordinator().0;
+        let other_coordinator_id = (initial_coordinator_id + 1) % 5;
+        let (_, start) = signer.coordinator_selection_log[0];
+        assert_eq!(signer.coordinator_selection_log.len(), 1);
+        assert_eq!(
+            signer.coordinator_changes_per_hour(start + Duration::from_secs(3600)),
+            0.0
+        );
+
+        // Selecting the same coordinator again is not a change
+        signer.record_coordinator_selection(start + Duration::from_secs(60));
+        assert_eq!(signer.coordinator_selection_log.len(), 1);
+        for (minutes, coordinator_id) in [
+            (10, other_coordinator_id),
+            (20, initial_coordinator_id),
+            (30, other_coordinator_id),
+        ] {
+            assert!(signer.coordinator_selector.set_coordinator(coordinator_id));
+            signer.record_coordinator_selection(start + Duration::from_secs(minutes * 60));
+        }
+        assert_eq!(signer.coordinator_selection_log.len(), 4);
+        assert_eq!(
+            signer.coordinator_changes_per_hour(start + Duration::from_secs(1800)),
+            6.0
+        );
+
+        // The log is bounded
+        for i in 0..MAX_COORDINATOR_SELECTION_LOG_LEN {
+            let coordinator_id = if i % 2 == 0 {
+                initial_coordinator_id
+            } else {
+                other_coordinator_id
+            };
+            assert!(signer.coordinator_selector.set_coordinator(coordinator_id));
+            signer.record_coordinator_selection(start + Duration::from_secs(3600));
+        }
+        assert_eq!(
+            signer.coordinator_selection_log.len(),
+            MAX_COORDINATOR_SELECTION_LOG_LEN
+        );
+    }
+
+    #[test]
+    fn metrics_endpoint_should_count_the_processed_events() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        // Point the signer at a node that is down so that the rejection ends up queued
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.stackerdb_write_retry_config = signer_config
+            .stackerdb_write_retry_config
+            .with_deadline(Duration::from_millis(200));
+        let mut signer = Signer::from(signer_config);
+        signer.self_test_passed = true;
+        let stacks_client = StacksClient::from(&config);
+        let (res_send, _res_recv) = std::sync::mpsc::channel();
+        signer.approved_aggregate_public_key = Some(Point::from(Scalar::random(&mut OsRng)));
+
+        let mut block_info = BlockInfo::from(BlockProposal {
+            block: block_with_fees(&[]),
+            burn_height: 7,
+            reward_cycle: signer.reward_cycle,
+        });
+        block_info.proposed_at = Some(get_epoch_time_secs().saturating_sub(3));
+        signer.signer_db.insert_block(&block_info).unwrap();
+        let reject = BlockValidateResponse::Reject(BlockValidateReject {
+            signer_signature_hash: block_info.signer_signature_hash(),
+            reason: "invalid".to_string(),
+            reason_code: ValidateRejectCode::InvalidBlock,
+        });
+        let reward_cycle = signer.reward_cycle;
+        signer.process_event(
+            &stacks_client,
+            Some(&SignerEvent::BlockValidationResponse(reject)),
+            res_send.clone(),
+            reward_cycle,
+        );
+        signer.process_event(
+            &stacks_client,
+            Some(&SignerEvent::NewBurnBlock(8)),
+            res_send,
+            reward_cycle,
+        );
+
+        let server = SignerMetricsServer::bind(0, Duration::from_secs(60)).unwrap();
+        let port = server.local_addr().unwrap().port();
+        *server.exported_metrics().lock().unwrap() = signer.metrics_export_prometheus();
+        server.spawn();
+        let mut stream = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+        std::io::Write::write_all(&mut stream, b"GET /metrics HTTP/1.1\r\n\r\n").unwrap();
+        let mut response = String::new();
+        std::io::Read::read_to_string(&mut stream, &mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+
+        let labels = format!(
+            "signer_id=\"{}\",reward_cycle=\"{}\"",
+            signer.signer_id, signer.reward_cycle
+        );
+        let sample = |name: &str| -> f64 {
+            let prefix = format!("{name}{{{labels}}} ");
+            response
+                .lines()
+                .find_map(|line| line.strip_prefix(&prefix))
+                .unwrap_or_else(|| panic!("missing metric {name}"))
+                .parse()
+                .unwrap()
+        };
+        assert_eq!(sample("stacks_signer_block_validation_events_total"), 1.0);
+        assert_eq!(sample("stacks_signer_new_burn_block_events_total"), 1.0);
+        assert_eq!(sample("stacks_signer_signer_message_events_total"), 0.0);
+        assert_eq!(
+            sample("stacks_signer_block_validations_rejected_total"),
+            1.0
+        );
+        assert_eq!(
+            sample("stacks_signer_block_validations_accepted_total"),
+            0.0
+        );
+        assert_eq!(sample("stacks_signer_block_validation_seconds_count"), 1.0);
+        assert!(sample("stacks_signer_block_validation_seconds_sum") >= 3.0);
+        assert_eq!(
+            sample("stacks_signer_stackerdb_sends_unacknowledged_total"),
+            1.0
+        );
+        assert_eq!(sample("stacks_signer_stackerdb_sends_acked_total"), 0.0);
+    }
+
+    /// A log writer whose output the test can read back
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn json_logs_should_carry_the_signer_fields() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        // Point the signer at a node that is down so that the rejection ends up queued
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        let mut signer_config = generate_signer_config(&config, 5, 20);
+        signer_config.stackerdb_write_retry_config = signer_config
+            .stackerdb_write_retry_config
+            .with_deadline(Duration::from_millis(200));
+        let mut signer = Signer::from(signer_config);
+        signer.self_test_passed = true;
+        let stacks_client = StacksClient::from(&config);
+        let (res_send, _res_recv) = std::sync::mpsc::channel();
+        let buffer = SharedBuffer::default();
+        signer.logger = SignerLogger::with_root(
+            &json_logger(buffer.clone()),
+            signer.signer_id,
+            signer.reward_cycle,
+        );
+
+        let block_info = BlockInfo::from(BlockProposal {
+            block: block_with_fees(&[]),
+            burn_height: 7,
+            reward_cycle: signer.reward_cycle,
+        });
+        signer.signer_db.insert_block(&block_info).unwrap();
+        let reject = BlockValidateResponse::Reject(BlockValidateReject {
+            signer_signature_hash: block_info.signer_signature_hash(),
+            reason: "invalid".to_string(),
+            reason_code: ValidateRejectCode::InvalidBlock,
+        });
+        let reward_cycle = signer.reward_cycle;
+        signer.process_event(
+            &stacks_client,
+            Some(&SignerEvent::BlockValidationResponse(reject)),
+            res_send,
+            reward_cycle,
+        );
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let records: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("log line is not JSON"))
+            .collect();
+        let rejection = records
+            .iter()
+            .find(|record| {
+                record["msg"]
+                    .as_str()
+                    .unwrap()
+                    .starts_with("Broadcasting a block rejection")
+            })
+            .expect("missing the block rejection record");
+        assert_eq!(rejection["signer_id"], signer.signer_id);
+        assert_eq!(rejection["reward_cycle"], signer.reward_cycle);
+        assert_eq!(
+            rejection["signer_sighash"],
+            block_info.signer_signature_hash().to_string()
+        );
+        assert_eq!(rejection["reason_code"], "InvalidBlock");
+    }
+
+    #[test]
+    fn verify_packet_timestamp_should_reject_replayed_and_future_packets() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        signer.packet_future_tolerance = Duration::from_secs(1);
+        let request = |sign_id, sign_iter_id| {
+            Message::NonceRequest(NonceRequest {
+                sign_iter_id,
+                ..nonce_request(sign_id, vec![1, 2, 3])
+            })
+        };
+        let response = |signer_id, sign_iter_id| nonce_response_packet(signer_id, sign_iter_id).msg;
+        let now = Instant::now();
+        let later = now + Duration::from_secs(2);
+
+        // Without a request, the round of a packet is left to the packet round guard
+        assert!(signer.verify_packet_timestamp(&response(0, 1), now));
+        assert!(signer.verify_packet_timestamp(&request(1, 2), now));
+        // Packets of an iteration before the round started are replayed
+        assert!(!signer.verify_packet_timestamp(&response(0, 1), now));
+        assert!(!signer.verify_packet_timestamp(&request(1, 1), now));
+        assert!(signer.verify_packet_timestamp(&response(0, 2), now));
+
+        // Packets of an iteration we were not asked to sign yet are tolerated for a while
+        assert!(signer.verify_packet_timestamp(&response(1, 3), now));
+        assert!(!signer.verify_packet_timestamp(&response(1, 3), later));
+        assert!(signer.verify_packet_timestamp(&request(1, 3), later));
+        assert!(signer.verify_packet_timestamp(&response(1, 3), later));
+        // A signer going back to an earlier iteration is replaying its packets
+        assert!(!signer.verify_packet_timestamp(&response(1, 2), later));
+        assert_eq!(signer.packet_window.get(&1), Some(&(3, now)));
+
+        // A request of another round starts a new one
+        assert!(signer.verify_packet_timestamp(&request(2, 1), later));
+        assert_eq!(
+            signer.sign_round_nonces,
+            Some(SignRoundNonces {
+                sign_id: 2,
+                first_nonce: 1,
+                latest_nonce: 1,
+            })
+        );
+        assert!(signer.packet_window.is_empty());
+        assert!(signer.verify_packet_timestamp(&response(1, 1), later));
+    }
+
+    #[test]
+    fn dkg_should_not_restart_for_a_completed_round() {
+        let mut config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let (mock_server, mock_server_addr) = mock_server_random();
+        config.node_host = mock_server_addr.to_string();
+        drop(mock_server);
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        let stacks_client = StacksClient::from(&config);
+        signer.state = State::Idle;
+        assert!(signer.validate_dkg_round_monotonicity(1));
+
+        signer.last_dkg_result = Some((3, Point::from(Scalar::random(&mut OsRng))));
+        signer.coordinator.current_dkg_id = 3;
+        assert!(!signer.validate_dkg_round_monotonicity(2));
+        assert!(!signer.validate_dkg_round_monotonicity(3));
+        assert!(signer.validate_dkg_round_monotonicity(4));
+
+        // Our vote for round 3 is not confirmed yet, so the contract's last round is still 2
+        let h = spawn(move || {
+            write_response(
+                mock_server_from_config(&config),
+                build_get_last_round_response(2).as_bytes(),
+            );
+        });
+        signer.execute_command(&stacks_client, &SignerCommand::Dkg);
+        h.join().unwrap();
+        assert_eq!(signer.state, State::Idle);
+        assert_eq!(signer.coordinator.current_dkg_id, 3);
+    }
+
+    #[test]
+    fn graceful_shutdown_should_wait_for_the_in_progress_operation() {
+        let config = GlobalConfig::load_from_file("./src/tests/conf/signer-0.toml").unwrap();
+        let stacks_client = StacksClient::from(&config);
+        let mut signer = Signer::from(generate_signer_config(&config, 5, 20));
+        signer.state = State::OperationInProgress(Operation::Sign);
+
+        assert!(!signer.graceful_shutdown(Duration::from_secs(60)));
+        assert!(signer.shutdown_started.is_some());
+        // No new operation is started while shutting down
+        signer.state = State::Idle;
+        signer.commands.push_back(SignerCommand::Dkg);
+        signer.process_next_command(&stacks_client, signer.reward_cycle);
+        assert_eq!(signer.state, State::Idle);
+        assert_eq!(signer.commands.len(), 1);
+
+        // The signer gives up waiting once the timeout elapsed, and saves its state
+        signer.state = State::OperationInProgress(Operation::Sign);
+        assert!(signer
+            .signer_db
+            .get_encrypted_signer_state(signer.reward_cycle)
+            .unwrap()
+            .is_none());
+        assert!(signer.graceful_shutdown(Duration::ZERO));
+        assert!(signer.shutdown_complete);
+        assert!(signer
+            .signer_db
+            .get_encrypted_signer_state(signer.reward_cycle)
+            .unwrap()
+            .is_some());
+
+        let mut idle_signer = Signer::from(generate_signer_config(&config, 5, 20));
+        assert!(idle_signer.graceful_shutdown(Duration::from_secs(60)));
     }
 }
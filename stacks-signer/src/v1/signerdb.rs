@@ -14,18 +14,116 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::fs;
 use std::path::Path;
+use std::time::Duration;
 
+use blockstack_lib::burnchains::Txid;
 use blockstack_lib::util_lib::db::{
-    query_row, sqlite_open, table_exists, u64_to_sql, Error as DBError,
+    query_count, query_row, query_rows, sqlite_open, table_exists, tx_begin_immediate,
+    tx_busy_handler, u64_to_sql, Error as DBError,
 };
+use libsigner::v1::messages::{
+    BlockResponse, NackReason, RejectCode, RejectCodeTypePrefix, SignerMessage,
+};
+use libsigner::BlockProposal;
+use r2d2::{Pool, PooledConnection};
+use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{params, Connection, Error as SqliteError, OpenFlags, NO_PARAMS};
-use slog::slog_debug;
-use stacks_common::debug;
+use serde_derive::Serialize;
+use slog::{slog_debug, slog_warn};
+use stacks_common::codec::{read_next, StacksMessageCodec};
+use stacks_common::types::chainstate::ConsensusHash;
+use stacks_common::util::get_epoch_time_secs;
 use stacks_common::util::hash::Sha512Trunc256Sum;
+use stacks_common::{debug, warn};
+use wsts::curve::point::Point;
+use wsts::state_machine::coordinator::Config as CoordinatorConfig;
 
+use crate::client::{ClientError, QueuedMessage, StacksClient};
 use crate::v1::signer::BlockInfo;
 
+/// The signing parameters the coordinator of a reward cycle was configured with when recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoordinatorConfigSnapshot {
+    /// The reward cycle of the coordinator
+    pub reward_cycle: u64,
+    /// The signing threshold
+    pub threshold: u32,
+    /// The number of signers in the reward set
+    pub num_signers: u32,
+    /// The number of keys in the reward set
+    pub num_keys: u32,
+    /// When the snapshot was recorded, in seconds since the Unix epoch
+    pub recorded_at: u64,
+}
+
+impl CoordinatorConfigSnapshot {
+    /// Whether the signing parameters of both snapshots differ
+    pub fn differs_from(&self, other: &Self) -> bool {
+        (self.threshold, self.num_signers, self.num_keys)
+            != (other.threshold, other.num_signers, other.num_keys)
+    }
+}
+
+/// A signature share request the signer rejected, kept as an audit record of the round
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedRequest {
+    /// The reward cycle of the signing round
+    pub reward_cycle: u64,
+    /// The id of the signing round
+    pub sign_id: u64,
+    /// The id of the coordinator that sent the request
+    pub coordinator_id: u32,
+    /// The hash of the message the coordinator requested signature shares for
+    pub message_hash: Sha512Trunc256Sum,
+    /// Why the request was rejected
+    pub reason: NackReason,
+    /// When the request was rejected, in seconds since the Unix epoch
+    pub rejected_at: u64,
+}
+
+/// A block the signer rejected, as found by searching the rejection reasons
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockSummary {
+    /// The reward cycle the block was proposed in
+    pub reward_cycle: u64,
+    /// The signer signature hash of the block
+    pub signer_signature_hash: Sha512Trunc256Sum,
+    /// The burn block height at which the block was proposed
+    pub burn_block_height: u64,
+    /// The name of the `RejectCode` variant the block was rejected with
+    pub rejection_reason: String,
+}
+
+/// A block stored by the signer, with the reason it was rejected for, if any
+#[derive(Debug, PartialEq)]
+pub struct BlockRecord {
+    /// The stored block
+    pub block_info: BlockInfo,
+    /// The name of the `RejectCode` variant the block was rejected with
+    pub rejection_reason: Option<String>,
+}
+
+/// A summary of the signing activity of a reward cycle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RewardCycleStats {
+    /// The number of blocks stored for the reward cycle
+    pub total_blocks_seen: u64,
+    /// The number of blocks validated by the stacks node and not rejected since
+    pub blocks_accepted: u64,
+    /// The number of blocks found invalid or rejected for another reason
+    pub blocks_rejected: u64,
+    /// The number of blocks we have yet to decide on
+    pub blocks_pending: u64,
+    /// The number of DKG rounds completed in the reward cycle
+    pub dkg_rounds: u64,
+    /// The burn block height at which the first block was proposed
+    pub first_block_at: Option<u64>,
+    /// The burn block height at which the last block was proposed
+    pub last_block_at: Option<u64>,
+}
+
 /// This struct manages a SQLite database connection
 /// for the signer.
 #[derive(Debug)]
@@ -40,15 +138,87 @@ CREATE TABLE IF NOT EXISTS blocks (
     signer_signature_hash TEXT NOT NULL,
     block_info TEXT NOT NULL,
     burn_block_height INTEGER NOT NULL,
+    tenure_consensus_hash TEXT,
+    valid INTEGER,
+    signed_over INTEGER NOT NULL DEFAULT 0,
+    rejection_reason TEXT,
     PRIMARY KEY (reward_cycle, signer_signature_hash)
 )";
 
+const ADD_BLOCKS_TENURE_CONSENSUS_HASH: &str = "
+ALTER TABLE blocks ADD COLUMN tenure_consensus_hash TEXT";
+
+const ADD_BLOCKS_VALID: &str = "
+ALTER TABLE blocks ADD COLUMN valid INTEGER";
+
+const ADD_BLOCKS_SIGNED_OVER: &str = "
+ALTER TABLE blocks ADD COLUMN signed_over INTEGER NOT NULL DEFAULT 0";
+
+const ADD_BLOCKS_REJECTION_REASON: &str = "
+ALTER TABLE blocks ADD COLUMN rejection_reason TEXT";
+
+const CREATE_BLOCKS_TENURE_INDEX: &str = "
+CREATE INDEX IF NOT EXISTS blocks_tenure_consensus_hash ON blocks(tenure_consensus_hash)";
+
+const CREATE_BLOCKS_STATUS_INDEX: &str = "
+CREATE INDEX IF NOT EXISTS idx_blocks_status ON blocks (reward_cycle, valid, signed_over)";
+
+const SELECT_BLOCKS_PENDING_SIGNATURE: &str = "
+SELECT block_info FROM blocks WHERE reward_cycle = ? AND valid = 1 AND signed_over = 0
+ORDER BY burn_block_height";
+
 const CREATE_SIGNER_STATE_TABLE: &str = "
 CREATE TABLE IF NOT EXISTS signer_states (
     reward_cycle INTEGER PRIMARY KEY,
     encrypted_state BLOB NOT NULL
 )";
 
+const CREATE_BROADCASTS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS broadcasts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    reward_cycle INTEGER NOT NULL,
+    expires_at INTEGER NOT NULL,
+    message BLOB NOT NULL
+)";
+
+const CREATE_MISSING_TRANSACTIONS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS missing_transactions (
+    reward_cycle INTEGER NOT NULL,
+    txid TEXT NOT NULL,
+    consecutive_proposals INTEGER NOT NULL,
+    PRIMARY KEY (reward_cycle, txid)
+)";
+
+const CREATE_REJECTED_REQUESTS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS rejected_requests (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    reward_cycle INTEGER NOT NULL,
+    sign_id INTEGER NOT NULL,
+    coordinator_id INTEGER NOT NULL,
+    message_hash TEXT NOT NULL,
+    reason INTEGER NOT NULL,
+    rejected_at INTEGER NOT NULL
+)";
+
+const CREATE_COORDINATOR_CONFIG_HISTORY_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS coordinator_config_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    reward_cycle INTEGER NOT NULL,
+    threshold INTEGER NOT NULL,
+    num_signers INTEGER NOT NULL,
+    num_keys INTEGER NOT NULL,
+    recorded_at INTEGER NOT NULL
+)";
+
+const CREATE_DKG_HISTORY_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS dkg_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    reward_cycle INTEGER NOT NULL,
+    dkg_id INTEGER NOT NULL,
+    aggregate_key TEXT NOT NULL,
+    completed_at INTEGER NOT NULL
+)";
+
 impl SignerDb {
     /// Create a new `SignerState` instance.
     /// This will create a new SQLite database at the given path
@@ -58,21 +228,21 @@ impl SignerDb {
 
         let signer_db = Self { db: connection };
 
-        signer_db.instantiate_db()?;
+        instantiate_db(&signer_db.db)?;
 
         Ok(signer_db)
     }
 
-    fn instantiate_db(&self) -> Result<(), DBError> {
-        if !table_exists(&self.db, "blocks")? {
-            self.db.execute(CREATE_BLOCKS_TABLE, NO_PARAMS)?;
-        }
-
-        if !table_exists(&self.db, "signer_states")? {
-            self.db.execute(CREATE_SIGNER_STATE_TABLE, NO_PARAMS)?;
-        }
+    /// Open the existing SQLite database at the given path without write access, so that it
+    /// can be inspected while a signer is running
+    pub fn readonly(db_path: impl AsRef<Path>) -> Result<Self, DBError> {
+        let connection = sqlite_open(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY, false)?;
+        Ok(Self { db: connection })
+    }
 
-        Ok(())
+    #[cfg(test)]
+    fn blocks_have_tenure_consensus_hash(&self) -> Result<bool, DBError> {
+        blocks_have_tenure_consensus_hash(&self.db)
     }
 
     fn connect(db_path: impl AsRef<Path>) -> Result<Connection, SqliteError> {
@@ -88,11 +258,7 @@ impl SignerDb {
         &self,
         reward_cycle: u64,
     ) -> Result<Option<Vec<u8>>, DBError> {
-        query_row(
-            &self.db,
-            "SELECT encrypted_state FROM signer_states WHERE reward_cycle = ?",
-            [u64_to_sql(reward_cycle)?],
-        )
+        get_encrypted_signer_state(&self.db, reward_cycle)
     }
 
     /// Insert the given state in the `signer_states` table for the given reward cycle
@@ -101,11 +267,62 @@ impl SignerDb {
         reward_cycle: u64,
         encrypted_signer_state: &[u8],
     ) -> Result<(), DBError> {
-        self.db.execute(
-            "INSERT OR REPLACE INTO signer_states (reward_cycle, encrypted_state) VALUES (?1, ?2)",
-            params![&u64_to_sql(reward_cycle)?, &encrypted_signer_state],
-        )?;
-        Ok(())
+        insert_encrypted_signer_state(&self.db, reward_cycle, encrypted_signer_state)
+    }
+
+    /// Persist a message that could not be written to stackerdb yet.
+    /// Returns the ID the message is stored under.
+    pub fn insert_broadcast(
+        &self,
+        reward_cycle: u64,
+        expires_at: u64,
+        message: &SignerMessage,
+    ) -> Result<i64, DBError> {
+        insert_broadcast(&self.db, reward_cycle, expires_at, message)
+    }
+
+    /// Remove a message that was delivered or is no longer worth sending
+    pub fn remove_broadcast(&self, id: i64) -> Result<(), DBError> {
+        remove_broadcast(&self.db, id)
+    }
+
+    /// Fetch the messages of the given reward cycle waiting to be written to stackerdb,
+    /// in the order they were queued
+    pub fn get_broadcasts(&self, reward_cycle: u64) -> Result<Vec<QueuedMessage>, DBError> {
+        get_broadcasts(&self.db, reward_cycle)
+    }
+
+    /// Record the signing parameters of the given coordinator configuration for the reward cycle.
+    /// Returns the previously recorded snapshot if the parameters drifted from it.
+    pub fn record_coordinator_config(
+        &self,
+        reward_cycle: u64,
+        config: &CoordinatorConfig,
+    ) -> Result<Option<CoordinatorConfigSnapshot>, DBError> {
+        record_coordinator_config(&self.db, reward_cycle, config)
+    }
+
+    /// Fetch the latest recorded coordinator configuration snapshot of the reward cycle
+    pub fn get_last_coordinator_config(
+        &self,
+        reward_cycle: u64,
+    ) -> Result<Option<CoordinatorConfigSnapshot>, DBError> {
+        get_last_coordinator_config(&self.db, reward_cycle)
+    }
+
+    /// Record the aggregate key produced by a completed DKG round of the reward cycle
+    pub fn record_dkg_round(
+        &self,
+        reward_cycle: u64,
+        dkg_id: u64,
+        aggregate_key: &Point,
+    ) -> Result<(), DBError> {
+        record_dkg_round(&self.db, reward_cycle, dkg_id, aggregate_key)
+    }
+
+    /// Summarize the blocks and DKG rounds of the reward cycle
+    pub fn get_reward_cycle_stats(&self, reward_cycle: u64) -> Result<RewardCycleStats, DBError> {
+        get_reward_cycle_stats(&self.db, reward_cycle)
     }
 
     /// Fetch a block from the database using the block's
@@ -115,210 +332,1792 @@ impl SignerDb {
         reward_cycle: u64,
         hash: &Sha512Trunc256Sum,
     ) -> Result<Option<BlockInfo>, DBError> {
-        let result: Option<String> = query_row(
-            &self.db,
-            "SELECT block_info FROM blocks WHERE reward_cycle = ? AND signer_signature_hash = ?",
-            params![&u64_to_sql(reward_cycle)?, hash.to_string()],
-        )?;
+        block_lookup(&self.db, reward_cycle, hash)
+    }
 
-        try_deserialize(result)
+    /// Fetch all blocks in the given reward cycle that were submitted to the stacks node
+    /// for validation and have yet to receive a response
+    pub fn get_blocks_awaiting_validation(
+        &self,
+        reward_cycle: u64,
+    ) -> Result<Vec<BlockInfo>, DBError> {
+        get_blocks_awaiting_validation(&self.db, reward_cycle)
     }
 
-    /// Insert a block into the database.
-    /// `hash` is the `signer_signature_hash` of the block.
-    pub fn insert_block(&mut self, block_info: &BlockInfo) -> Result<(), DBError> {
-        let block_json =
-            serde_json::to_string(&block_info).expect("Unable to serialize block info");
-        let hash = &block_info.signer_signature_hash();
-        let block_id = &block_info.block.block_id();
-        let signed_over = &block_info.signed_over;
-        let vote = block_info
-            .vote
-            .as_ref()
-            .map(|v| if v.rejected { "REJECT" } else { "ACCEPT" });
-
-        debug!("Inserting block_info.";
-            "reward_cycle" => %block_info.reward_cycle,
-            "burn_block_height" => %block_info.burn_block_height,
-            "sighash" => %hash,
-            "block_id" => %block_id,
-            "signed" => %signed_over,
-            "vote" => vote
-        );
-        self.db
-            .execute(
-                "INSERT OR REPLACE INTO blocks (reward_cycle, burn_block_height, signer_signature_hash, block_info) VALUES (?1, ?2, ?3, ?4)",
-                params![u64_to_sql(block_info.reward_cycle)?, u64_to_sql(block_info.burn_block_height)?, hash.to_string(), &block_json],
-            )?;
+    /// Fetch all valid blocks in the given reward cycle that no signing round has been started for,
+    /// ordered by burn block height
+    pub fn get_blocks_pending_signature(
+        &self,
+        reward_cycle: u64,
+    ) -> Result<Vec<BlockInfo>, DBError> {
+        get_blocks_pending_signature(&self.db, reward_cycle)
+    }
 
-        Ok(())
+    /// Fetch all blocks in the given reward cycle that we accepted and signed over, were
+    /// proposed more than `min_age` ago and are yet to be confirmed by the stacks node
+    pub fn get_blocks_needing_rebroadcast(
+        &self,
+        reward_cycle: u64,
+        min_age: Duration,
+    ) -> Result<Vec<BlockInfo>, DBError> {
+        get_blocks_needing_rebroadcast(&self.db, reward_cycle, min_age)
     }
-}
 
-fn try_deserialize<T>(s: Option<String>) -> Result<Option<T>, DBError>
-where
-    T: serde::de::DeserializeOwned,
-{
-    s.as_deref()
-        .map(serde_json::from_str)
-        .transpose()
-        .map_err(DBError::SerializationError)
-}
+    /// Fetch all blocks of the miner tenure with the given consensus hash that this signer signed over
+    pub fn get_blocks_signed_in_tenure(
+        &self,
+        consensus_hash: &ConsensusHash,
+    ) -> Result<Vec<BlockInfo>, DBError> {
+        get_blocks_signed_in_tenure(&self.db, consensus_hash)
+    }
 
-#[cfg(test)]
-pub fn test_signer_db(db_path: &str) -> SignerDb {
-    use std::fs;
+    /// Fetch all blocks proposed in the miner tenure with the given consensus hash
+    pub fn get_blocks_in_tenure(
+        &self,
+        consensus_hash: &ConsensusHash,
+    ) -> Result<Vec<BlockInfo>, DBError> {
+        get_blocks_in_tenure(&self.db, consensus_hash)
+    }
 
-    if fs::metadata(db_path).is_ok() {
-        fs::remove_file(db_path).unwrap();
+    /// Get every block of the given reward cycle, ordered by burn block height
+    pub fn get_blocks(&self, reward_cycle: u64) -> Result<Vec<BlockRecord>, DBError> {
+        get_blocks(&self.db, reward_cycle)
     }
-    SignerDb::new(db_path).expect("Failed to create signer db")
-}
 
-#[cfg(test)]
-mod tests {
-    use std::fs;
-    use std::path::PathBuf;
+    /// Get the blocks with the given signer signature hash, across all reward cycles
+    pub fn get_blocks_by_hash(&self, hash: &Sha512Trunc256Sum) -> Result<Vec<BlockInfo>, DBError> {
+        get_blocks_by_hash(&self.db, hash)
+    }
 
-    use blockstack_lib::chainstate::nakamoto::{
-        NakamotoBlock, NakamotoBlockHeader, NakamotoBlockVote,
-    };
-    use libsigner::BlockProposal;
+    /// Remove the blocks, signer states, broadcasts and signing round records of all but the
+    /// `keep` latest reward cycles with blocks. Returns the number of blocks removed.
+    pub fn prune_reward_cycles(&mut self, keep: u64) -> Result<usize, DBError> {
+        prune_reward_cycles(&mut self.db, keep)
+    }
 
-    use super::*;
+    /// Remove a block from the database.
+    /// `hash` is the `signer_signature_hash` of the block.
+    pub fn remove_block(
+        &mut self,
+        reward_cycle: u64,
+        hash: &Sha512Trunc256Sum,
+    ) -> Result<(), DBError> {
+        remove_block(&self.db, reward_cycle, hash)
+    }
 
-    fn _wipe_db(db_path: &PathBuf) {
-        if fs::metadata(db_path).is_ok() {
-            fs::remove_file(db_path).unwrap();
-        }
+    /// Insert a block into the database.
+    /// `hash` is the `signer_signature_hash` of the block.
+    pub fn insert_block(&mut self, block_info: &BlockInfo) -> Result<(), DBError> {
+        insert_block(&self.db, block_info)
     }
 
-    fn create_block_override(
-        overrides: impl FnOnce(&mut BlockProposal),
-    ) -> (BlockInfo, BlockProposal) {
-        let header = NakamotoBlockHeader::empty();
-        let block = NakamotoBlock {
-            header,
-            txs: vec![],
-        };
-        let mut block_proposal = BlockProposal {
-            block,
-            burn_height: 7,
-            reward_cycle: 42,
-        };
-        overrides(&mut block_proposal);
-        (BlockInfo::from(block_proposal.clone()), block_proposal)
+    /// Insert the given blocks of the given reward cycle into the database in a single
+    /// transaction, which is much faster than inserting them one at a time.
+    /// Either all of the blocks are inserted or none are. Returns the number of blocks inserted.
+    pub fn batch_insert_blocks(
+        &mut self,
+        reward_cycle: u64,
+        blocks: &[BlockInfo],
+    ) -> Result<usize, DBError> {
+        batch_insert_blocks(&mut self.db, reward_cycle, blocks)
     }
 
-    fn create_block() -> (BlockInfo, BlockProposal) {
-        create_block_override(|_| {})
+    /// Record the expected transactions missing from the latest block proposal of the reward
+    /// cycle, forgetting those the proposal included. Returns the largest number of consecutive
+    /// proposals any of the given transactions has been missing from, this one included.
+    pub fn record_missing_transactions(
+        &mut self,
+        reward_cycle: u64,
+        txids: &[Txid],
+    ) -> Result<u32, DBError> {
+        record_missing_transactions(&mut self.db, reward_cycle, txids)
     }
 
-    fn tmp_db_path() -> PathBuf {
-        std::env::temp_dir().join(format!(
-            "stacks-signer-test-{}.sqlite",
-            rand::random::<u64>()
-        ))
+    /// Record a rejected signature share request. Returns the number of requests from the same
+    /// coordinator rejected in the signing round, this one included.
+    pub fn record_rejected_request(&self, request: &RejectedRequest) -> Result<u32, DBError> {
+        record_rejected_request(&self.db, request)
     }
 
-    fn test_basic_signer_db_with_path(db_path: impl AsRef<Path>) {
-        let mut db = SignerDb::new(db_path).expect("Failed to create signer db");
-        let (block_info, block_proposal) = create_block();
-        let reward_cycle = block_info.reward_cycle;
-        db.insert_block(&block_info)
-            .expect("Unable to insert block into db");
-        let block_info = db
-            .block_lookup(
-                reward_cycle,
-                &block_proposal.block.header.signer_signature_hash(),
-            )
-            .unwrap()
-            .expect("Unable to get block from db");
+    /// Import the blocks of a legacy signer state dump: a JSON file holding an array of
+    /// `BlockInfo` objects, as written by signers that predate the signer database.
+    /// Entries that are not valid `BlockInfo` objects are reported and skipped, and the valid
+    /// ones are inserted in a single transaction. Returns the number of blocks imported.
+    pub fn migrate_from_json_state(&mut self, json_path: &Path) -> Result<usize, DBError> {
+        migrate_from_json_state(&mut self.db, json_path)
+    }
 
-        assert_eq!(BlockInfo::from(block_proposal.clone()), block_info);
+    /// Import the blocks of the given reward cycle that the signer set responded to in stacker-db,
+    /// so that a signer joining the signer set starts with its block history. Stacker-db only
+    /// holds the latest response of each signer and the latest proposal of each miner, so only
+    /// those blocks can be imported. Blocks already in the database are left untouched.
+    /// Returns the number of blocks imported.
+    pub fn import_block_history_from_stackerdb(
+        &mut self,
+        stacks_client: &StacksClient,
+        reward_cycle: u64,
+    ) -> Result<usize, DBError> {
+        let to_db_error = |e: ClientError| {
+            DBError::Other(format!(
+                "Failed to read the block history from stacker-db: {e}"
+            ))
+        };
+        let block_proposals = stacks_client
+            .get_pending_block_proposals(reward_cycle)
+            .map_err(to_db_error)?;
+        let block_responses = stacks_client
+            .get_block_responses(reward_cycle)
+            .map_err(to_db_error)?;
+        self.import_block_history(reward_cycle, block_proposals, &block_responses)
+    }
 
-        // Test looking up a block from a different reward cycle
-        let block_info = db
-            .block_lookup(
-                reward_cycle + 1,
-                &block_proposal.block.header.signer_signature_hash(),
-            )
-            .unwrap();
-        assert!(block_info.is_none());
+    /// Insert the proposed blocks of the reward cycle that have a response as accepted, if any
+    /// response accepts them, or else as rejected with the reason of the first rejection
+    fn import_block_history(
+        &mut self,
+        reward_cycle: u64,
+        block_proposals: Vec<BlockProposal>,
+        block_responses: &[BlockResponse],
+    ) -> Result<usize, DBError> {
+        let mut imported = 0;
+        for block_proposal in block_proposals {
+            let hash = block_proposal.block.header.signer_signature_hash();
+            if block_proposal.reward_cycle != reward_cycle
+                || self.block_lookup(reward_cycle, &hash)?.is_some()
+            {
+                continue;
+            }
+            let mut accepted = false;
+            let mut rejection = None;
+            for block_response in block_responses {
+                match block_response {
+                    BlockResponse::Accepted((accepted_hash, _)) if *accepted_hash == hash => {
+                        accepted = true;
+                    }
+                    BlockResponse::Rejected(block_rejection)
+                        if block_rejection.signer_signature_hash == hash =>
+                    {
+                        rejection.get_or_insert(block_rejection);
+                    }
+                    _ => {}
+                }
+            }
+            if !accepted && rejection.is_none() {
+                continue;
+            }
+            self.insert_block(&BlockInfo::new_with_vote(block_proposal, !accepted))?;
+            if let (false, Some(block_rejection)) = (accepted, rejection) {
+                self.record_block_rejection(reward_cycle, &hash, &block_rejection.reason_code)?;
+            }
+            imported += 1;
+        }
+        debug!("Imported {imported} blocks of reward cycle {reward_cycle} from stacker-db");
+        Ok(imported)
     }
 
-    #[test]
-    fn test_basic_signer_db() {
-        let db_path = tmp_db_path();
-        test_basic_signer_db_with_path(db_path)
+    /// Record the reason a stored block was rejected with
+    pub fn record_block_rejection(
+        &self,
+        reward_cycle: u64,
+        hash: &Sha512Trunc256Sum,
+        reject_code: &RejectCode,
+    ) -> Result<(), DBError> {
+        record_block_rejection(&self.db, reward_cycle, hash, reject_code)
     }
 
-    #[test]
-    fn test_basic_signer_db_in_memory() {
-        test_basic_signer_db_with_path(":memory:")
+    /// Find the rejected blocks of every reward cycle whose rejection reason contains the keyword,
+    /// e.g. `MissingTransactions`. Matching is case insensitive for ASCII letters.
+    pub fn search_rejections(&self, keyword: &str) -> Result<Vec<BlockSummary>, DBError> {
+        search_rejections(&self.db, keyword)
     }
 
-    #[test]
-    fn test_update_block() {
-        let db_path = tmp_db_path();
-        let mut db = SignerDb::new(db_path).expect("Failed to create signer db");
-        let (block_info, block_proposal) = create_block();
-        let reward_cycle = block_info.reward_cycle;
-        db.insert_block(&block_info)
-            .expect("Unable to insert block into db");
+    /// Fetch the signature share requests rejected in the given reward cycle, oldest first
+    pub fn get_rejected_requests(
+        &self,
+        reward_cycle: u64,
+    ) -> Result<Vec<RejectedRequest>, DBError> {
+        get_rejected_requests(&self.db, reward_cycle)
+    }
+}
 
-        let block_info = db
-            .block_lookup(
-                reward_cycle,
-                &block_proposal.block.header.signer_signature_hash(),
-            )
-            .unwrap()
-            .expect("Unable to get block from db");
+/// A pool of connections to the signer's SQLite database, for use from several threads.
+/// Exposes the same API as `SignerDb`, acquiring a connection from the pool for each call.
+#[derive(Clone)]
+pub struct SignerDbPool {
+    /// Pool of connections to the SQLite database
+    pool: Pool<SqliteConnectionManager>,
+}
 
-        assert_eq!(BlockInfo::from(block_proposal.clone()), block_info);
+impl SignerDbPool {
+    /// Create a new pool of at most `pool_size` connections to the SQLite database at the
+    /// given path, creating the database if it does not exist.
+    /// In-memory databases are not supported, as every pooled connection would open its own.
+    pub fn new(db_path: impl AsRef<Path>, pool_size: u32) -> Result<Self, DBError> {
+        let db_path = db_path.as_ref();
+        if db_path == Path::new(":memory:") {
+            return Err(DBError::Other(
+                "Cannot pool connections to an in-memory signer db".into(),
+            ));
+        }
+        let manager = SqliteConnectionManager::file(db_path)
+            .with_flags(OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE)
+            .with_init(|conn| {
+                conn.busy_handler(Some(tx_busy_handler))?;
+                conn.pragma_update(None, "journal_mode", &"WAL")?;
+                conn.pragma_update(None, "synchronous", &"NORMAL")
+            });
+        let pool = Pool::builder()
+            .max_size(pool_size)
+            .build(manager)
+            .map_err(|e| DBError::Other(format!("Failed to build signer db pool: {e}")))?;
 
-        let old_block_info = block_info;
-        let old_block_proposal = block_proposal;
+        let signer_db_pool = Self { pool };
 
-        let (mut block_info, block_proposal) = create_block_override(|b| {
-            b.block.header.signer_signature =
-                old_block_proposal.block.header.signer_signature.clone();
-        });
-        assert_eq!(
-            block_info.signer_signature_hash(),
-            old_block_info.signer_signature_hash()
-        );
-        let vote = NakamotoBlockVote {
-            signer_signature_hash: Sha512Trunc256Sum([0x01; 32]),
-            rejected: false,
-        };
-        block_info.vote = Some(vote.clone());
-        db.insert_block(&block_info)
-            .expect("Unable to insert block into db");
+        instantiate_db(&*signer_db_pool.connection()?)?;
 
-        let block_info = db
-            .block_lookup(
-                reward_cycle,
-                &block_proposal.block.header.signer_signature_hash(),
-            )
-            .unwrap()
-            .expect("Unable to get block from db");
+        Ok(signer_db_pool)
+    }
 
-        assert_ne!(old_block_info, block_info);
-        assert_eq!(block_info.vote, Some(vote));
+    /// Acquire a connection from the pool, waiting for one to be returned if they are all in use
+    fn connection(&self) -> Result<PooledConnection<SqliteConnectionManager>, DBError> {
+        self.pool
+            .get()
+            .map_err(|e| DBError::Other(format!("Failed to get signer db connection: {e}")))
     }
 
-    #[test]
-    fn test_write_signer_state() {
-        let db_path = tmp_db_path();
-        let db = SignerDb::new(db_path).expect("Failed to create signer db");
-        let state_0 = vec![0];
-        let state_1 = vec![1; 1024];
+    /// Get the signer state for the provided reward cycle if it exists in the database
+    pub fn get_encrypted_signer_state(
+        &self,
+        reward_cycle: u64,
+    ) -> Result<Option<Vec<u8>>, DBError> {
+        get_encrypted_signer_state(&*self.connection()?, reward_cycle)
+    }
 
-        db.insert_encrypted_signer_state(10, &state_0)
-            .expect("Failed to insert signer state");
+    /// Insert the given state in the `signer_states` table for the given reward cycle
+    pub fn insert_encrypted_signer_state(
+        &self,
+        reward_cycle: u64,
+        encrypted_signer_state: &[u8],
+    ) -> Result<(), DBError> {
+        insert_encrypted_signer_state(&*self.connection()?, reward_cycle, encrypted_signer_state)
+    }
 
-        db.insert_encrypted_signer_state(11, &state_1)
+    /// Persist a message that could not be written to stackerdb yet.
+    /// Returns the ID the message is stored under.
+    pub fn insert_broadcast(
+        &self,
+        reward_cycle: u64,
+        expires_at: u64,
+        message: &SignerMessage,
+    ) -> Result<i64, DBError> {
+        insert_broadcast(&*self.connection()?, reward_cycle, expires_at, message)
+    }
+
+    /// Remove a message that was delivered or is no longer worth sending
+    pub fn remove_broadcast(&self, id: i64) -> Result<(), DBError> {
+        remove_broadcast(&*self.connection()?, id)
+    }
+
+    /// Fetch the messages of the given reward cycle waiting to be written to stackerdb,
+    /// in the order they were queued
+    pub fn get_broadcasts(&self, reward_cycle: u64) -> Result<Vec<QueuedMessage>, DBError> {
+        get_broadcasts(&*self.connection()?, reward_cycle)
+    }
+
+    /// Record the signing parameters of the given coordinator configuration for the reward cycle.
+    /// Returns the previously recorded snapshot if the parameters drifted from it.
+    pub fn record_coordinator_config(
+        &self,
+        reward_cycle: u64,
+        config: &CoordinatorConfig,
+    ) -> Result<Option<CoordinatorConfigSnapshot>, DBError> {
+        record_coordinator_config(&*self.connection()?, reward_cycle, config)
+    }
+
+    /// Fetch the latest recorded coordinator configuration snapshot of the reward cycle
+    pub fn get_last_coordinator_config(
+        &self,
+        reward_cycle: u64,
+    ) -> Result<Option<CoordinatorConfigSnapshot>, DBError> {
+        get_last_coordinator_config(&*self.connection()?, reward_cycle)
+    }
+
+    /// Fetch a block from the database using the block's
+    /// `signer_signature_hash`
+    pub fn block_lookup(
+        &self,
+        reward_cycle: u64,
+        hash: &Sha512Trunc256Sum,
+    ) -> Result<Option<BlockInfo>, DBError> {
+        block_lookup(&*self.connection()?, reward_cycle, hash)
+    }
+
+    /// Fetch all blocks in the given reward cycle that were submitted to the stacks node
+    /// for validation and have yet to receive a response
+    pub fn get_blocks_awaiting_validation(
+        &self,
+        reward_cycle: u64,
+    ) -> Result<Vec<BlockInfo>, DBError> {
+        get_blocks_awaiting_validation(&*self.connection()?, reward_cycle)
+    }
+
+    /// Fetch all valid blocks in the given reward cycle that no signing round has been started for,
+    /// ordered by burn block height
+    pub fn get_blocks_pending_signature(
+        &self,
+        reward_cycle: u64,
+    ) -> Result<Vec<BlockInfo>, DBError> {
+        get_blocks_pending_signature(&*self.connection()?, reward_cycle)
+    }
+
+    /// Fetch all blocks in the given reward cycle that we accepted and signed over, were
+    /// proposed more than `min_age` ago and are yet to be confirmed by the stacks node
+    pub fn get_blocks_needing_rebroadcast(
+        &self,
+        reward_cycle: u64,
+        min_age: Duration,
+    ) -> Result<Vec<BlockInfo>, DBError> {
+        get_blocks_needing_rebroadcast(&*self.connection()?, reward_cycle, min_age)
+    }
+
+    /// Fetch all blocks of the miner tenure with the given consensus hash that this signer signed over
+    pub fn get_blocks_signed_in_tenure(
+        &self,
+        consensus_hash: &ConsensusHash,
+    ) -> Result<Vec<BlockInfo>, DBError> {
+        get_blocks_signed_in_tenure(&*self.connection()?, consensus_hash)
+    }
+
+    /// Fetch all blocks proposed in the miner tenure with the given consensus hash
+    pub fn get_blocks_in_tenure(
+        &self,
+        consensus_hash: &ConsensusHash,
+    ) -> Result<Vec<BlockInfo>, DBError> {
+        get_blocks_in_tenure(&*self.connection()?, consensus_hash)
+    }
+
+    /// Remove a block from the database.
+    /// `hash` is the `signer_signature_hash` of the block.
+    pub fn remove_block(&self, reward_cycle: u64, hash: &Sha512Trunc256Sum) -> Result<(), DBError> {
+        remove_block(&*self.connection()?, reward_cycle, hash)
+    }
+
+    /// Insert a block into the database.
+    /// `hash` is the `signer_signature_hash` of the block.
+    pub fn insert_block(&self, block_info: &BlockInfo) -> Result<(), DBError> {
+        insert_block(&*self.connection()?, block_info)
+    }
+
+    /// Insert the given blocks of the given reward cycle into the database in a single
+    /// transaction. Returns the number of blocks inserted.
+    pub fn batch_insert_blocks(
+        &self,
+        reward_cycle: u64,
+        blocks: &[BlockInfo],
+    ) -> Result<usize, DBError> {
+        batch_insert_blocks(&mut *self.connection()?, reward_cycle, blocks)
+    }
+
+    /// Record the expected transactions missing from the latest block proposal of the reward
+    /// cycle, forgetting those the proposal included. Returns the largest number of consecutive
+    /// proposals any of the given transactions has been missing from, this one included.
+    pub fn record_missing_transactions(
+        &self,
+        reward_cycle: u64,
+        txids: &[Txid],
+    ) -> Result<u32, DBError> {
+        record_missing_transactions(&mut *self.connection()?, reward_cycle, txids)
+    }
+
+    /// Record a rejected signature share request. Returns the number of requests from the same
+    /// coordinator rejected in the signing round, this one included.
+    pub fn record_rejected_request(&self, request: &RejectedRequest) -> Result<u32, DBError> {
+        record_rejected_request(&*self.connection()?, request)
+    }
+
+    /// Record the reason a stored block was rejected with
+    pub fn record_block_rejection(
+        &self,
+        reward_cycle: u64,
+        hash: &Sha512Trunc256Sum,
+        reject_code: &RejectCode,
+    ) -> Result<(), DBError> {
+        record_block_rejection(&*self.connection()?, reward_cycle, hash, reject_code)
+    }
+
+    /// Find the rejected blocks of every reward cycle whose rejection reason contains the keyword,
+    /// e.g. `MissingTransactions`. Matching is case insensitive for ASCII letters.
+    pub fn search_rejections(&self, keyword: &str) -> Result<Vec<BlockSummary>, DBError> {
+        search_rejections(&*self.connection()?, keyword)
+    }
+
+    /// Fetch the signature share requests rejected in the given reward cycle, oldest first
+    pub fn get_rejected_requests(
+        &self,
+        reward_cycle: u64,
+    ) -> Result<Vec<RejectedRequest>, DBError> {
+        get_rejected_requests(&*self.connection()?, reward_cycle)
+    }
+
+    /// Record the aggregate key produced by a completed DKG round of the reward cycle
+    pub fn record_dkg_round(
+        &self,
+        reward_cycle: u64,
+        dkg_id: u64,
+        aggregate_key: &Point,
+    ) -> Result<(), DBError> {
+        record_dkg_round(&*self.connection()?, reward_cycle, dkg_id, aggregate_key)
+    }
+
+    /// Summarize the blocks and DKG rounds of the reward cycle
+    pub fn get_reward_cycle_stats(&self, reward_cycle: u64) -> Result<RewardCycleStats, DBError> {
+        get_reward_cycle_stats(&*self.connection()?, reward_cycle)
+    }
+}
+
+fn instantiate_db(conn: &Connection) -> Result<(), DBError> {
+    if !table_exists(conn, "blocks")? {
+        conn.execute(CREATE_BLOCKS_TABLE, NO_PARAMS)?;
+    } else {
+        if !blocks_have_tenure_consensus_hash(conn)? {
+            // Blocks stored before the column existed have no tenure consensus hash
+            conn.execute(ADD_BLOCKS_TENURE_CONSENSUS_HASH, NO_PARAMS)?;
+        }
+        if !blocks_have_column(conn, "signed_over")? {
+            conn.execute(ADD_BLOCKS_VALID, NO_PARAMS)?;
+            conn.execute(ADD_BLOCKS_SIGNED_OVER, NO_PARAMS)?;
+            backfill_blocks_status(conn)?;
+        }
+        if !blocks_have_column(conn, "rejection_reason")? {
+            conn.execute(ADD_BLOCKS_REJECTION_REASON, NO_PARAMS)?;
+        }
+    }
+    conn.execute(CREATE_BLOCKS_TENURE_INDEX, NO_PARAMS)?;
+    conn.execute(CREATE_BLOCKS_STATUS_INDEX, NO_PARAMS)?;
+
+    if !table_exists(conn, "signer_states")? {
+        conn.execute(CREATE_SIGNER_STATE_TABLE, NO_PARAMS)?;
+    }
+
+    if !table_exists(conn, "broadcasts")? {
+        conn.execute(CREATE_BROADCASTS_TABLE, NO_PARAMS)?;
+    }
+
+    if !table_exists(conn, "missing_transactions")? {
+        conn.execute(CREATE_MISSING_TRANSACTIONS_TABLE, NO_PARAMS)?;
+    }
+
+    if !table_exists(conn, "rejected_requests")? {
+        conn.execute(CREATE_REJECTED_REQUESTS_TABLE, NO_PARAMS)?;
+    }
+
+    if !table_exists(conn, "coordinator_config_history")? {
+        conn.execute(CREATE_COORDINATOR_CONFIG_HISTORY_TABLE, NO_PARAMS)?;
+    }
+
+    if !table_exists(conn, "dkg_history")? {
+        conn.execute(CREATE_DKG_HISTORY_TABLE, NO_PARAMS)?;
+    }
+
+    Ok(())
+}
+
+fn blocks_have_tenure_consensus_hash(conn: &Connection) -> Result<bool, DBError> {
+    blocks_have_column(conn, "tenure_consensus_hash")
+}
+
+fn blocks_have_column(conn: &Connection, column: &str) -> Result<bool, DBError> {
+    let count = query_count(
+        conn,
+        "SELECT COUNT(*) FROM pragma_table_info('blocks') WHERE name = ?",
+        params![column],
+    )?;
+    Ok(count > 0)
+}
+
+/// Copy the validity and signing status of the blocks stored before they had their own columns
+/// out of their serialized block info
+fn backfill_blocks_status(conn: &Connection) -> Result<(), DBError> {
+    let results: Vec<String> = query_rows(conn, "SELECT block_info FROM blocks", NO_PARAMS)?;
+    for result in results {
+        let block_info: BlockInfo =
+            serde_json::from_str(&result).map_err(DBError::SerializationError)?;
+        conn.execute(
+            "UPDATE blocks SET valid = ?1, signed_over = ?2 WHERE reward_cycle = ?3 AND signer_signature_hash = ?4",
+            params![
+                block_info.valid(),
+                block_info.signed_over,
+                u64_to_sql(block_info.reward_cycle)?,
+                block_info.signer_signature_hash().to_string()
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+fn get_encrypted_signer_state(
+    conn: &Connection,
+    reward_cycle: u64,
+) -> Result<Option<Vec<u8>>, DBError> {
+    query_row(
+        conn,
+        "SELECT encrypted_state FROM signer_states WHERE reward_cycle = ?",
+        [u64_to_sql(reward_cycle)?],
+    )
+}
+
+fn insert_encrypted_signer_state(
+    conn: &Connection,
+    reward_cycle: u64,
+    encrypted_signer_state: &[u8],
+) -> Result<(), DBError> {
+    conn.execute(
+        "INSERT OR REPLACE INTO signer_states (reward_cycle, encrypted_state) VALUES (?1, ?2)",
+        params![&u64_to_sql(reward_cycle)?, &encrypted_signer_state],
+    )?;
+    Ok(())
+}
+
+fn insert_broadcast(
+    conn: &Connection,
+    reward_cycle: u64,
+    expires_at: u64,
+    message: &SignerMessage,
+) -> Result<i64, DBError> {
+    conn.execute(
+        "INSERT INTO broadcasts (reward_cycle, expires_at, message) VALUES (?1, ?2, ?3)",
+        params![
+            &u64_to_sql(reward_cycle)?,
+            &u64_to_sql(expires_at)?,
+            &message.serialize_to_vec()
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+fn remove_broadcast(conn: &Connection, id: i64) -> Result<(), DBError> {
+    conn.execute("DELETE FROM broadcasts WHERE id = ?", params![id])?;
+    Ok(())
+}
+
+fn get_broadcasts(conn: &Connection, reward_cycle: u64) -> Result<Vec<QueuedMessage>, DBError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, expires_at, message FROM broadcasts WHERE reward_cycle = ? ORDER BY id",
+    )?;
+    let rows = stmt.query_map(params![&u64_to_sql(reward_cycle)?], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, Vec<u8>>(2)?,
+        ))
+    })?;
+    let mut broadcasts = vec![];
+    for row in rows {
+        let (id, expires_at, message_bytes) = row?;
+        let message = read_next::<SignerMessage, _>(&mut &message_bytes[..])
+            .map_err(|_| DBError::ParseError)?;
+        broadcasts.push(QueuedMessage {
+            id: Some(id),
+            message,
+            expires_at: u64::try_from(expires_at).map_err(|_| DBError::ParseError)?,
+        });
+    }
+    Ok(broadcasts)
+}
+
+fn get_last_coordinator_config(
+    conn: &Connection,
+    reward_cycle: u64,
+) -> Result<Option<CoordinatorConfigSnapshot>, DBError> {
+    let mut stmt = conn.prepare(
+        "SELECT threshold, num_signers, num_keys, recorded_at FROM coordinator_config_history
+        WHERE reward_cycle = ? ORDER BY id DESC LIMIT 1",
+    )?;
+    let mut rows = stmt.query_map(params![&u64_to_sql(reward_cycle)?], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, i64>(3)?,
+        ))
+    })?;
+    let Some(row) = rows.next() else {
+        return Ok(None);
+    };
+    let (threshold, num_signers, num_keys, recorded_at) = row?;
+    Ok(Some(CoordinatorConfigSnapshot {
+        reward_cycle,
+        threshold: u32::try_from(threshold).map_err(|_| DBError::ParseError)?,
+        num_signers: u32::try_from(num_signers).map_err(|_| DBError::ParseError)?,
+        num_keys: u32::try_from(num_keys).map_err(|_| DBError::ParseError)?,
+        recorded_at: u64::try_from(recorded_at).map_err(|_| DBError::ParseError)?,
+    }))
+}
+
+fn record_coordinator_config(
+    conn: &Connection,
+    reward_cycle: u64,
+    config: &CoordinatorConfig,
+) -> Result<Option<CoordinatorConfigSnapshot>, DBError> {
+    let snapshot = CoordinatorConfigSnapshot {
+        reward_cycle,
+        threshold: config.threshold,
+        num_signers: config.num_signers,
+        num_keys: config.num_keys,
+        recorded_at: get_epoch_time_secs(),
+    };
+    let last_snapshot = get_last_coordinator_config(conn, reward_cycle)?;
+    if last_snapshot.is_some_and(|last_snapshot| !last_snapshot.differs_from(&snapshot)) {
+        // Only changes of the signing parameters are recorded
+        return Ok(None);
+    }
+    conn.execute(
+        "INSERT INTO coordinator_config_history (reward_cycle, threshold, num_signers, num_keys, recorded_at)
+        VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            &u64_to_sql(reward_cycle)?,
+            snapshot.threshold,
+            snapshot.num_signers,
+            snapshot.num_keys,
+            &u64_to_sql(snapshot.recorded_at)?
+        ],
+    )?;
+    Ok(last_snapshot)
+}
+
+fn record_dkg_round(
+    conn: &Connection,
+    reward_cycle: u64,
+    dkg_id: u64,
+    aggregate_key: &Point,
+) -> Result<(), DBError> {
+    conn.execute(
+        "INSERT INTO dkg_history (reward_cycle, dkg_id, aggregate_key, completed_at)
+        VALUES (?1, ?2, ?3, ?4)",
+        params![
+            &u64_to_sql(reward_cycle)?,
+            &u64_to_sql(dkg_id)?,
+            aggregate_key.to_string(),
+            &u64_to_sql(get_epoch_time_secs())?
+        ],
+    )?;
+    Ok(())
+}
+
+fn get_reward_cycle_stats(
+    conn: &Connection,
+    reward_cycle: u64,
+) -> Result<RewardCycleStats, DBError> {
+    // A block with a rejection reason counts as rejected, even if the stacks node validated it
+    let (total, accepted, rejected, first_block_at, last_block_at) = conn.query_row(
+        "SELECT COUNT(*),
+            COALESCE(SUM(CASE WHEN valid = 1 AND rejection_reason IS NULL THEN 1 ELSE 0 END), 0),
+            COALESCE(SUM(CASE WHEN valid = 0 OR rejection_reason IS NOT NULL THEN 1 ELSE 0 END), 0),
+            MIN(burn_block_height),
+            MAX(burn_block_height)
+        FROM blocks WHERE reward_cycle = ?",
+        params![&u64_to_sql(reward_cycle)?],
+        |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, Option<i64>>(3)?,
+                row.get::<_, Option<i64>>(4)?,
+            ))
+        },
+    )?;
+    let dkg_rounds = query_count(
+        conn,
+        "SELECT COUNT(*) FROM dkg_history WHERE reward_cycle = ?",
+        params![&u64_to_sql(reward_cycle)?],
+    )?;
+    let to_u64 = |count: i64| u64::try_from(count).map_err(|_| DBError::ParseError);
+    let total_blocks_seen = to_u64(total)?;
+    let blocks_accepted = to_u64(accepted)?;
+    let blocks_rejected = to_u64(rejected)?;
+    Ok(RewardCycleStats {
+        total_blocks_seen,
+        blocks_accepted,
+        blocks_rejected,
+        blocks_pending: total_blocks_seen - blocks_accepted - blocks_rejected,
+        dkg_rounds: to_u64(dkg_rounds)?,
+        first_block_at: first_block_at.map(to_u64).transpose()?,
+        last_block_at: last_block_at.map(to_u64).transpose()?,
+    })
+}
+
+fn block_lookup(
+    conn: &Connection,
+    reward_cycle: u64,
+    hash: &Sha512Trunc256Sum,
+) -> Result<Option<BlockInfo>, DBError> {
+    let result: Option<String> = query_row(
+        conn,
+        "SELECT block_info FROM blocks WHERE reward_cycle = ? AND signer_signature_hash = ?",
+        params![&u64_to_sql(reward_cycle)?, hash.to_string()],
+    )?;
+
+    try_deserialize(result)
+}
+
+fn get_blocks_awaiting_validation(
+    conn: &Connection,
+    reward_cycle: u64,
+) -> Result<Vec<BlockInfo>, DBError> {
+    let results: Vec<String> = query_rows(
+        conn,
+        "SELECT block_info FROM blocks WHERE reward_cycle = ? AND valid IS NULL",
+        params![&u64_to_sql(reward_cycle)?],
+    )?;
+    let mut blocks = Vec::with_capacity(results.len());
+    for result in results {
+        let block_info: BlockInfo =
+            serde_json::from_str(&result).map_err(DBError::SerializationError)?;
+        if block_info.is_awaiting_validation() {
+            blocks.push(block_info);
+        }
+    }
+    Ok(blocks)
+}
+
+fn get_blocks_pending_signature(
+    conn: &Connection,
+    reward_cycle: u64,
+) -> Result<Vec<BlockInfo>, DBError> {
+    let results: Vec<String> = query_rows(
+        conn,
+        SELECT_BLOCKS_PENDING_SIGNATURE,
+        params![&u64_to_sql(reward_cycle)?],
+    )?;
+    results
+        .iter()
+        .map(|result| serde_json::from_str(result).map_err(DBError::SerializationError))
+        .collect()
+}
+
+fn get_blocks_needing_rebroadcast(
+    conn: &Connection,
+    reward_cycle: u64,
+    min_age: Duration,
+) -> Result<Vec<BlockInfo>, DBError> {
+    let results: Vec<String> = query_rows(
+        conn,
+        "SELECT block_info FROM blocks WHERE reward_cycle = ? AND signed_over = 1 ORDER BY burn_block_height",
+        params![&u64_to_sql(reward_cycle)?],
+    )?;
+    let mut blocks = Vec::with_capacity(results.len());
+    for result in results {
+        let block_info: BlockInfo =
+            serde_json::from_str(&result).map_err(DBError::SerializationError)?;
+        if block_info.needs_rebroadcast(min_age) {
+            blocks.push(block_info);
+        }
+    }
+    Ok(blocks)
+}
+
+fn get_blocks_signed_in_tenure(
+    conn: &Connection,
+    consensus_hash: &ConsensusHash,
+) -> Result<Vec<BlockInfo>, DBError> {
+    let results: Vec<String> = query_rows(
+        conn,
+        "SELECT block_info FROM blocks WHERE tenure_consensus_hash = ? ORDER BY burn_block_height",
+        params![consensus_hash.to_string()],
+    )?;
+    let mut blocks = Vec::with_capacity(results.len());
+    for result in results {
+        let block_info: BlockInfo =
+            serde_json::from_str(&result).map_err(DBError::SerializationError)?;
+        if block_info.signed_over {
+            blocks.push(block_info);
+        }
+    }
+    Ok(blocks)
+}
+
+fn get_blocks_in_tenure(
+    conn: &Connection,
+    consensus_hash: &ConsensusHash,
+) -> Result<Vec<BlockInfo>, DBError> {
+    let results: Vec<String> = query_rows(
+        conn,
+        "SELECT block_info FROM blocks WHERE tenure_consensus_hash = ? ORDER BY burn_block_height",
+        params![consensus_hash.to_string()],
+    )?;
+    results
+        .iter()
+        .map(|result| serde_json::from_str(result).map_err(DBError::SerializationError))
+        .collect()
+}
+
+fn get_blocks(conn: &Connection, reward_cycle: u64) -> Result<Vec<BlockRecord>, DBError> {
+    let mut stmt = conn.prepare(
+        "SELECT block_info, rejection_reason FROM blocks WHERE reward_cycle = ?
+        ORDER BY burn_block_height, signer_signature_hash",
+    )?;
+    let rows = stmt.query_map(params![&u64_to_sql(reward_cycle)?], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+    })?;
+    let mut blocks = vec![];
+    for row in rows {
+        let (block_info, rejection_reason) = row?;
+        blocks.push(BlockRecord {
+            block_info: serde_json::from_str(&block_info).map_err(DBError::SerializationError)?,
+            rejection_reason,
+        });
+    }
+    Ok(blocks)
+}
+
+fn get_blocks_by_hash(
+    conn: &Connection,
+    hash: &Sha512Trunc256Sum,
+) -> Result<Vec<BlockInfo>, DBError> {
+    let results: Vec<String> = query_rows(
+        conn,
+        "SELECT block_info FROM blocks WHERE signer_signature_hash = ? ORDER BY reward_cycle",
+        params![hash.to_string()],
+    )?;
+    results
+        .iter()
+        .map(|result| serde_json::from_str(result).map_err(DBError::SerializationError))
+        .collect()
+}
+
+fn prune_reward_cycles(conn: &mut Connection, keep: u64) -> Result<usize, DBError> {
+    let tx = tx_begin_immediate(conn)?;
+    let reward_cycles: Vec<i64> = query_rows(
+        &tx,
+        "SELECT DISTINCT reward_cycle FROM blocks ORDER BY reward_cycle DESC",
+        NO_PARAMS,
+    )?;
+    // Everything older than the oldest reward cycle to keep is removed
+    let oldest_kept = if keep == 0 {
+        i64::MAX
+    } else {
+        let oldest_kept_idx = usize::try_from(keep - 1).ok();
+        match oldest_kept_idx.and_then(|idx| reward_cycles.get(idx)) {
+            Some(reward_cycle) => *reward_cycle,
+            // There are no more reward cycles than the ones to keep
+            None => return Ok(0),
+        }
+    };
+    let pruned_blocks = tx.execute(
+        "DELETE FROM blocks WHERE reward_cycle < ?",
+        params![oldest_kept],
+    )?;
+    for table in [
+        "signer_states",
+        "broadcasts",
+        "missing_transactions",
+        "rejected_requests",
+        "dkg_history",
+    ] {
+        tx.execute(
+            &format!("DELETE FROM {table} WHERE reward_cycle < ?"),
+            params![oldest_kept],
+        )?;
+    }
+    tx.commit()?;
+    Ok(pruned_blocks)
+}
+
+fn remove_block(
+    conn: &Connection,
+    reward_cycle: u64,
+    hash: &Sha512Trunc256Sum,
+) -> Result<(), DBError> {
+    conn.execute(
+        "DELETE FROM blocks WHERE reward_cycle = ?1 AND signer_signature_hash = ?2",
+        params![&u64_to_sql(reward_cycle)?, hash.to_string()],
+    )?;
+    Ok(())
+}
+
+fn insert_block(conn: &Connection, block_info: &BlockInfo) -> Result<(), DBError> {
+    let block_json = serde_json::to_string(&block_info).expect("Unable to serialize block info");
+    let hash = &block_info.signer_signature_hash();
+    let block_id = &block_info.block.block_id();
+    let tenure_consensus_hash = &block_info.block.header.consensus_hash;
+    let signed_over = &block_info.signed_over;
+    let vote = block_info
+        .vote
+        .as_ref()
+        .map(|v| if v.rejected { "REJECT" } else { "ACCEPT" });
+
+    debug!("Inserting block_info.";
+        "reward_cycle" => %block_info.reward_cycle,
+        "burn_block_height" => %block_info.burn_block_height,
+        "sighash" => %hash,
+        "block_id" => %block_id,
+        "signed" => %signed_over,
+        "vote" => vote
+    );
+    conn.execute(
+        "INSERT OR REPLACE INTO blocks (reward_cycle, burn_block_height, signer_signature_hash, block_info, tenure_consensus_hash, valid, signed_over, rejection_reason)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, (SELECT rejection_reason FROM blocks WHERE reward_cycle = ?1 AND signer_signature_hash = ?3))",
+        params![u64_to_sql(block_info.reward_cycle)?, u64_to_sql(block_info.burn_block_height)?, hash.to_string(), &block_json, tenure_consensus_hash.to_string(), block_info.valid(), signed_over],
+    )?;
+
+    Ok(())
+}
+
+fn batch_insert_blocks(
+    conn: &mut Connection,
+    reward_cycle: u64,
+    blocks: &[BlockInfo],
+) -> Result<usize, DBError> {
+    if let Some(block_info) = blocks
+        .iter()
+        .find(|block_info| block_info.reward_cycle != reward_cycle)
+    {
+        return Err(DBError::Other(format!(
+            "Cannot insert block {} of reward cycle {} in a batch of reward cycle {reward_cycle}",
+            block_info.signer_signature_hash(),
+            block_info.reward_cycle
+        )));
+    }
+    let tx = tx_begin_immediate(conn)?;
+    for block_info in blocks {
+        insert_block(&tx, block_info)?;
+    }
+    tx.commit()?;
+    Ok(blocks.len())
+}
+
+fn migrate_from_json_state(conn: &mut Connection, json_path: &Path) -> Result<usize, DBError> {
+    let json = fs::read_to_string(json_path).map_err(|e| {
+        DBError::Other(format!(
+            "Failed to read the JSON state dump {}: {e}",
+            json_path.display()
+        ))
+    })?;
+    let entries: Vec<serde_json::Value> =
+        serde_json::from_str(&json).map_err(DBError::SerializationError)?;
+    let total = entries.len();
+    let blocks: Vec<BlockInfo> = entries
+        .into_iter()
+        .enumerate()
+        .filter_map(
+            |(index, entry)| match serde_json::from_value::<BlockInfo>(entry) {
+                Ok(block_info) => Some(block_info),
+                Err(e) => {
+                    warn!(
+                        "Skipping malformed block in the JSON state dump";
+                        "path" => %json_path.display(),
+                        "index" => index,
+                        "error" => %e,
+                    );
+                    None
+                }
+            },
+        )
+        .collect();
+    let tx = tx_begin_immediate(conn)?;
+    for block_info in &blocks {
+        insert_block(&tx, block_info)?;
+    }
+    tx.commit()?;
+    debug!(
+        "Imported {} of the {total} blocks of the JSON state dump {}",
+        blocks.len(),
+        json_path.display()
+    );
+    Ok(blocks.len())
+}
+
+fn record_missing_transactions(
+    conn: &mut Connection,
+    reward_cycle: u64,
+    txids: &[Txid],
+) -> Result<u32, DBError> {
+    let tx = tx_begin_immediate(conn)?;
+    let mut streaks = vec![];
+    for txid in txids {
+        let txid = txid.to_string();
+        let consecutive_proposals = query_row::<i64, _>(
+            &tx,
+            "SELECT consecutive_proposals FROM missing_transactions WHERE reward_cycle = ?1 AND txid = ?2",
+            params![&u64_to_sql(reward_cycle)?, &txid],
+        )?
+        .unwrap_or(0)
+        .saturating_add(1);
+        streaks.push((txid, consecutive_proposals));
+    }
+    // Transactions the proposal included are no longer missing
+    tx.execute(
+        "DELETE FROM missing_transactions WHERE reward_cycle = ?",
+        params![&u64_to_sql(reward_cycle)?],
+    )?;
+    for (txid, consecutive_proposals) in &streaks {
+        tx.execute(
+            "INSERT INTO missing_transactions (reward_cycle, txid, consecutive_proposals) VALUES (?1, ?2, ?3)",
+            params![&u64_to_sql(reward_cycle)?, txid, consecutive_proposals],
+        )?;
+    }
+    tx.commit()?;
+    let longest_streak = streaks
+        .iter()
+        .map(|(_, consecutive_proposals)| *consecutive_proposals)
+        .max()
+        .unwrap_or(0);
+    Ok(u32::try_from(longest_streak).unwrap_or(u32::MAX))
+}
+
+fn record_rejected_request(conn: &Connection, request: &RejectedRequest) -> Result<u32, DBError> {
+    conn.execute(
+        "INSERT INTO rejected_requests (reward_cycle, sign_id, coordinator_id, message_hash, reason, rejected_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            &u64_to_sql(request.reward_cycle)?,
+            &u64_to_sql(request.sign_id)?,
+            request.coordinator_id,
+            request.message_hash.to_string(),
+            request.reason.to_u8(),
+            &u64_to_sql(request.rejected_at)?
+        ],
+    )?;
+    let rejections = query_count(
+        conn,
+        "SELECT COUNT(*) FROM rejected_requests WHERE reward_cycle = ?1 AND sign_id = ?2 AND coordinator_id = ?3",
+        params![
+            &u64_to_sql(request.reward_cycle)?,
+            &u64_to_sql(request.sign_id)?,
+            request.coordinator_id
+        ],
+    )?;
+    Ok(u32::try_from(rejections).unwrap_or(u32::MAX))
+}
+
+fn record_block_rejection(
+    conn: &Connection,
+    reward_cycle: u64,
+    hash: &Sha512Trunc256Sum,
+    reject_code: &RejectCode,
+) -> Result<(), DBError> {
+    let rejection_reason = format!("{:?}", RejectCodeTypePrefix::from(reject_code));
+    conn.execute(
+        "UPDATE blocks SET rejection_reason = ?1 WHERE reward_cycle = ?2 AND signer_signature_hash = ?3",
+        params![rejection_reason, &u64_to_sql(reward_cycle)?, hash.to_string()],
+    )?;
+    Ok(())
+}
+
+fn search_rejections(conn: &Connection, keyword: &str) -> Result<Vec<BlockSummary>, DBError> {
+    // Match the keyword literally rather than as a LIKE pattern
+    let escaped_keyword = keyword
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    let mut stmt = conn.prepare(
+        "SELECT reward_cycle, signer_signature_hash, burn_block_height, rejection_reason FROM blocks
+        WHERE rejection_reason LIKE ?1 ESCAPE '\\' ORDER BY reward_cycle, burn_block_height",
+    )?;
+    let rows = stmt.query_map(params![format!("%{escaped_keyword}%")], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, String>(3)?,
+        ))
+    })?;
+    let mut summaries = vec![];
+    for row in rows {
+        let (reward_cycle, signer_signature_hash, burn_block_height, rejection_reason) = row?;
+        summaries.push(BlockSummary {
+            reward_cycle: u64::try_from(reward_cycle).map_err(|_| DBError::ParseError)?,
+            signer_signature_hash: Sha512Trunc256Sum::from_hex(&signer_signature_hash)
+                .map_err(|_| DBError::ParseError)?,
+            burn_block_height: u64::try_from(burn_block_height).map_err(|_| DBError::ParseError)?,
+            rejection_reason,
+        });
+    }
+    Ok(summaries)
+}
+
+fn get_rejected_requests(
+    conn: &Connection,
+    reward_cycle: u64,
+) -> Result<Vec<RejectedRequest>, DBError> {
+    let mut stmt = conn.prepare(
+        "SELECT sign_id, coordinator_id, message_hash, reason, rejected_at FROM rejected_requests
+        WHERE reward_cycle = ? ORDER BY id",
+    )?;
+    let rows = stmt.query_map(params![&u64_to_sql(reward_cycle)?], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, u32>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, u8>(3)?,
+            row.get::<_, i64>(4)?,
+        ))
+    })?;
+    let mut rejected_requests = vec![];
+    for row in rows {
+        let (sign_id, coordinator_id, message_hash, reason, rejected_at) = row?;
+        rejected_requests.push(RejectedRequest {
+            reward_cycle,
+            sign_id: u64::try_from(sign_id).map_err(|_| DBError::ParseError)?,
+            coordinator_id,
+            message_hash: Sha512Trunc256Sum::from_hex(&message_hash)
+                .map_err(|_| DBError::ParseError)?,
+            reason: NackReason::from_u8(reason).ok_or(DBError::ParseError)?,
+            rejected_at: u64::try_from(rejected_at).map_err(|_| DBError::ParseError)?,
+        });
+    }
+    Ok(rejected_requests)
+}
+
+fn try_deserialize<T>(s: Option<String>) -> Result<Option<T>, DBError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    s.as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(DBError::SerializationError)
+}
+
+#[cfg(test)]
+pub fn test_signer_db(db_path: &str) -> SignerDb {
+    use std::fs;
+
+    if fs::metadata(db_path).is_ok() {
+        fs::remove_file(db_path).unwrap();
+    }
+    SignerDb::new(db_path).expect("Failed to create signer db")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use blockstack_lib::chainstate::nakamoto::{
+        NakamotoBlock, NakamotoBlockHeader, NakamotoBlockVote,
+    };
+    use blockstack_lib::net::api::postblock_proposal::ValidateRejectCode;
+    use libsigner::v1::messages::{BlockRejection, BlockResponse, RejectCode};
+    use libsigner::BlockProposal;
+    use wsts::common::Signature;
+    use wsts::curve::point::Point;
+    use wsts::curve::scalar::Scalar;
+
+    use super::*;
+
+    fn _wipe_db(db_path: &PathBuf) {
+        if fs::metadata(db_path).is_ok() {
+            fs::remove_file(db_path).unwrap();
+        }
+    }
+
+    fn create_block_override(
+        overrides: impl FnOnce(&mut BlockProposal),
+    ) -> (BlockInfo, BlockProposal) {
+        let header = NakamotoBlockHeader::empty();
+        let block = NakamotoBlock {
+            header,
+            txs: vec![],
+        };
+        let mut block_proposal = BlockProposal {
+            block,
+            burn_height: 7,
+            reward_cycle: 42,
+        };
+        overrides(&mut block_proposal);
+        (BlockInfo::from(block_proposal.clone()), block_proposal)
+    }
+
+    fn create_block() -> (BlockInfo, BlockProposal) {
+        create_block_override(|_| {})
+    }
+
+    fn tmp_db_path() -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "stacks-signer-test-{}.sqlite",
+            rand::random::<u64>()
+        ))
+    }
+
+    fn test_basic_signer_db_with_path(db_path: impl AsRef<Path>) {
+        let mut db = SignerDb::new(db_path).expect("Failed to create signer db");
+        let (block_info, block_proposal) = create_block();
+        let reward_cycle = block_info.reward_cycle;
+        db.insert_block(&block_info)
+            .expect("Unable to insert block into db");
+        let block_info = db
+            .block_lookup(
+                reward_cycle,
+                &block_proposal.block.header.signer_signature_hash(),
+            )
+            .unwrap()
+            .expect("Unable to get block from db");
+
+        assert_eq!(BlockInfo::from(block_proposal.clone()), block_info);
+
+        // Test looking up a block from a different reward cycle
+        let block_info = db
+            .block_lookup(
+                reward_cycle + 1,
+                &block_proposal.block.header.signer_signature_hash(),
+            )
+            .unwrap();
+        assert!(block_info.is_none());
+    }
+
+    #[test]
+    fn test_basic_signer_db() {
+        let db_path = tmp_db_path();
+        test_basic_signer_db_with_path(db_path)
+    }
+
+    #[test]
+    fn test_basic_signer_db_in_memory() {
+        test_basic_signer_db_with_path(":memory:")
+    }
+
+    #[test]
+    fn test_update_block() {
+        let db_path = tmp_db_path();
+        let mut db = SignerDb::new(db_path).expect("Failed to create signer db");
+        let (block_info, block_proposal) = create_block();
+        let reward_cycle = block_info.reward_cycle;
+        db.insert_block(&block_info)
+            .expect("Unable to insert block into db");
+
+        let block_info = db
+            .block_lookup(
+                reward_cycle,
+                &block_proposal.block.header.signer_signature_hash(),
+            )
+            .unwrap()
+            .expect("Unable to get block from db");
+
+        assert_eq!(BlockInfo::from(block_proposal.clone()), block_info);
+
+        let old_block_info = block_info;
+        let old_block_proposal = block_proposal;
+
+        let (mut block_info, block_proposal) = create_block_override(|b| {
+            b.block.header.signer_signature =
+                old_block_proposal.block.header.signer_signature.clone();
+        });
+        assert_eq!(
+            block_info.signer_signature_hash(),
+            old_block_info.signer_signature_hash()
+        );
+        let vote = NakamotoBlockVote {
+            signer_signature_hash: Sha512Trunc256Sum([0x01; 32]),
+            rejected: false,
+        };
+        block_info.vote = Some(vote.clone());
+        db.insert_block(&block_info)
+            .expect("Unable to insert block into db");
+
+        let block_info = db
+            .block_lookup(
+                reward_cycle,
+                &block_proposal.block.header.signer_signature_hash(),
+            )
+            .unwrap()
+            .expect("Unable to get block from db");
+
+        assert_ne!(old_block_info, block_info);
+        assert_eq!(block_info.vote, Some(vote));
+    }
+
+    #[test]
+    fn test_remove_block() {
+        let db_path = tmp_db_path();
+        let mut db = SignerDb::new(db_path).expect("Failed to create signer db");
+        let (block_info, block_proposal) = create_block();
+        let reward_cycle = block_info.reward_cycle;
+        let hash = block_proposal.block.header.signer_signature_hash();
+        db.insert_block(&block_info)
+            .expect("Unable to insert block into db");
+
+        // The block was never submitted for validation so it is not awaiting a response
+        assert!(db
+            .get_blocks_awaiting_validation(reward_cycle)
+            .expect("Failed to get blocks awaiting validation")
+            .is_empty());
+
+        db.remove_block(reward_cycle, &hash)
+            .expect("Unable to remove block from db");
+        assert!(db
+            .block_lookup(reward_cycle, &hash)
+            .expect("Failed to lookup block")
+            .is_none());
+    }
+
+    #[test]
+    fn test_get_blocks_signed_in_tenure() {
+        let db_path = tmp_db_path();
+        let mut db = SignerDb::new(db_path).expect("Failed to create signer db");
+        let tenure_a = ConsensusHash([0x01; 20]);
+        let tenure_b = ConsensusHash([0x02; 20]);
+
+        let (mut block_a1, _) = create_block_override(|b| {
+            b.block.header.consensus_hash = tenure_a;
+            b.block.header.chain_length = 1;
+        });
+        block_a1.signed_over = true;
+        let (mut block_a2, _) = create_block_override(|b| {
+            b.block.header.consensus_hash = tenure_a;
+            b.block.header.chain_length = 2;
+            b.burn_height = 8;
+        });
+        block_a2.signed_over = true;
+        // Seen but never signed over
+        let (block_a3, _) = create_block_override(|b| {
+            b.block.header.consensus_hash = tenure_a;
+            b.block.header.chain_length = 3;
+        });
+        let (mut block_b1, _) = create_block_override(|b| {
+            b.block.header.consensus_hash = tenure_b;
+        });
+        block_b1.signed_over = true;
+
+        for block_info in [&block_a2, &block_a1, &block_a3, &block_b1] {
+            db.insert_block(block_info)
+                .expect("Unable to insert block into db");
+        }
+
+        assert_eq!(
+            db.get_blocks_signed_in_tenure(&tenure_a)
+                .expect("Failed to get blocks signed in tenure"),
+            vec![block_a1, block_a2]
+        );
+        assert_eq!(
+            db.get_blocks_signed_in_tenure(&tenure_b)
+                .expect("Failed to get blocks signed in tenure"),
+            vec![block_b1]
+        );
+        assert!(db
+            .get_blocks_signed_in_tenure(&ConsensusHash([0x03; 20]))
+            .expect("Failed to get blocks signed in tenure")
+            .is_empty());
+
+        // Blocks we never signed over are still part of the tenure
+        let blocks_in_tenure = db
+            .get_blocks_in_tenure(&tenure_a)
+            .expect("Failed to get blocks in tenure");
+        assert_eq!(blocks_in_tenure.len(), 3);
+        assert!(blocks_in_tenure.contains(&block_a3));
+        assert_eq!(blocks_in_tenure.last(), Some(&block_a2));
+    }
+
+    /// Set the validation result of a block, which only the signer may otherwise set
+    fn with_validation_result(block_info: BlockInfo, valid: bool) -> BlockInfo {
+        let mut json = serde_json::to_value(block_info).expect("Failed to serialize block info");
+        json["valid"] = valid.into();
+        serde_json::from_value(json).expect("Failed to deserialize block info")
+    }
+
+    #[test]
+    fn test_get_blocks_pending_signature() {
+        let db_path = tmp_db_path();
+        let mut db = SignerDb::new(db_path).expect("Failed to create signer db");
+
+        let (block_1, _) = create_block_override(|b| {
+            b.block.header.chain_length = 1;
+            b.burn_height = 9;
+        });
+        let block_1 = with_validation_result(block_1, true);
+        let (block_2, _) = create_block_override(|b| {
+            b.block.header.chain_length = 2;
+        });
+        let block_2 = with_validation_result(block_2, true);
+        // Already being signed over
+        let (block_3, _) = create_block_override(|b| {
+            b.block.header.chain_length = 3;
+        });
+        let mut block_3 = with_validation_result(block_3, true);
+        block_3.signed_over = true;
+        // Invalid
+        let (block_4, _) = create_block_override(|b| {
+            b.block.header.chain_length = 4;
+        });
+        let block_4 = with_validation_result(block_4, false);
+        // Not validated yet
+        let (block_5, _) = create_block_override(|b| {
+            b.block.header.chain_length = 5;
+        });
+        // Other reward cycle
+        let (block_6, _) = create_block_override(|b| {
+            b.block.header.chain_length = 6;
+            b.reward_cycle = 43;
+        });
+        let block_6 = with_validation_result(block_6, true);
+
+        for block_info in [&block_1, &block_2, &block_3, &block_4, &block_5, &block_6] {
+            db.insert_block(block_info)
+                .expect("Unable to insert block into db");
+        }
+
+        assert_eq!(
+            db.get_blocks_pending_signature(42)
+                .expect("Failed to get blocks pending signature"),
+            vec![block_2, block_1]
+        );
+        assert_eq!(
+            db.get_blocks_pending_signature(43)
+                .expect("Failed to get blocks pending signature"),
+            vec![block_6]
+        );
+    }
+
+    fn with_proposed_at(block_info: BlockInfo, proposed_at: u64) -> BlockInfo {
+        let mut json = serde_json::to_value(block_info).expect("Failed to serialize block info");
+        json["proposed_at"] = proposed_at.into();
+        serde_json::from_value(json).expect("Failed to deserialize block info")
+    }
+
+    #[test]
+    fn test_get_blocks_needing_rebroadcast() {
+        let db_path = tmp_db_path();
+        let mut db = SignerDb::new(db_path).expect("Failed to create signer db");
+        let min_age = Duration::from_secs(60);
+        let old = get_epoch_time_secs() - 120;
+        let recent = get_epoch_time_secs();
+        let accepted_block = |chain_length, proposed_at, rejected| {
+            let (block_info, _) = create_block_override(|b| {
+                b.block.header.chain_length = chain_length;
+            });
+            let mut block_info = with_proposed_at(block_info, proposed_at);
+            block_info.vote = Some(NakamotoBlockVote {
+                signer_signature_hash: block_info.signer_signature_hash(),
+                rejected,
+            });
+            block_info.signed_over = true;
+            block_info
+        };
+
+        let block_1 = accepted_block(1, old, false);
+        // Rejected
+        let block_2 = accepted_block(2, old, true);
+        // Proposed too recently
+        let block_3 = accepted_block(3, recent, false);
+        // Not signed over
+        let mut block_4 = accepted_block(4, old, false);
+        block_4.signed_over = false;
+        // Already confirmed
+        let mut block_5 = accepted_block(5, old, false);
+        block_5.confirmed = true;
+
+        for block_info in [&block_1, &block_2, &block_3, &block_4, &block_5] {
+            db.insert_block(block_info)
+                .expect("Unable to insert block into db");
+        }
+
+        assert_eq!(
+            db.get_blocks_needing_rebroadcast(42, min_age)
+                .expect("Failed to get blocks needing rebroadcast"),
+            vec![block_1]
+        );
+        assert!(db
+            .get_blocks_needing_rebroadcast(43, min_age)
+            .expect("Failed to get blocks needing rebroadcast")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_blocks_table_migration() {
+        let db_path = tmp_db_path();
+        {
+            let conn = SignerDb::connect(&db_path).expect("Failed to open db");
+            conn.execute(
+                "CREATE TABLE blocks (
+                    reward_cycle INTEGER NOT NULL,
+                    signer_signature_hash TEXT NOT NULL,
+                    block_info TEXT NOT NULL,
+                    burn_block_height INTEGER NOT NULL,
+                    PRIMARY KEY (reward_cycle, signer_signature_hash)
+                )",
+                NO_PARAMS,
+            )
+            .expect("Failed to create old blocks table");
+        }
+        let mut db = SignerDb::new(&db_path).expect("Failed to open signer db");
+        assert!(db.blocks_have_tenure_consensus_hash().unwrap());
+
+        let (mut block_info, block_proposal) = create_block();
+        block_info.signed_over = true;
+        db.insert_block(&block_info)
+            .expect("Unable to insert block into db");
+        assert_eq!(
+            db.get_blocks_signed_in_tenure(&block_proposal.block.header.consensus_hash)
+                .unwrap(),
+            vec![block_info]
+        );
+    }
+
+    #[test]
+    fn test_blocks_status_migration() {
+        let db_path = tmp_db_path();
+        let (block_info, block_proposal) = create_block();
+        let block_info = with_validation_result(block_info, true);
+        {
+            let conn = SignerDb::connect(&db_path).expect("Failed to open db");
+            conn.execute(
+                "CREATE TABLE blocks (
+                    reward_cycle INTEGER NOT NULL,
+                    signer_signature_hash TEXT NOT NULL,
+                    block_info TEXT NOT NULL,
+                    burn_block_height INTEGER NOT NULL,
+                    tenure_consensus_hash TEXT,
+                    PRIMARY KEY (reward_cycle, signer_signature_hash)
+                )",
+                NO_PARAMS,
+            )
+            .expect("Failed to create old blocks table");
+            conn.execute(
+                "INSERT INTO blocks (reward_cycle, signer_signature_hash, block_info, burn_block_height) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    u64_to_sql(block_info.reward_cycle).unwrap(),
+                    block_proposal.block.header.signer_signature_hash().to_string(),
+                    serde_json::to_string(&block_info).unwrap(),
+                    u64_to_sql(block_info.burn_block_height).unwrap()
+                ],
+            )
+            .expect("Failed to insert block into old blocks table");
+        }
+        let db = SignerDb::new(&db_path).expect("Failed to open signer db");
+        assert_eq!(
+            db.get_blocks_pending_signature(block_info.reward_cycle)
+                .unwrap(),
+            vec![block_info]
+        );
+    }
+
+    #[test]
+    fn test_get_blocks_pending_signature_uses_status_index() {
+        let mut db = SignerDb::new(":memory:").expect("Failed to create signer db");
+        for chain_length in 0..10_000 {
+            let (block_info, _) = create_block_override(|b| {
+                b.block.header.chain_length = chain_length;
+            });
+            let mut block_info = with_validation_result(block_info, true);
+            // Only every 1000th block still awaits signing
+            block_info.signed_over = chain_length % 1_000 != 0;
+            db.insert_block(&block_info)
+                .expect("Unable to insert block into db");
+        }
+
+        let start = Instant::now();
+        let pending_blocks = db
+            .get_blocks_pending_signature(42)
+            .expect("Failed to get blocks pending signature");
+        let elapsed = start.elapsed();
+        assert_eq!(pending_blocks.len(), 10);
+        assert!(
+            elapsed < Duration::from_millis(10),
+            "Querying blocks pending signature took {elapsed:?}"
+        );
+
+        let query_plan: Vec<String> = db
+            .db
+            .prepare(&format!(
+                "EXPLAIN QUERY PLAN {SELECT_BLOCKS_PENDING_SIGNATURE}"
+            ))
+            .unwrap()
+            .query_map(params![42], |row| row.get(3))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(
+            query_plan
+                .iter()
+                .any(|detail| detail.contains("idx_blocks_status")),
+            "Query does not use the status index: {query_plan:?}"
+        );
+    }
+
+    #[test]
+    fn test_broadcasts() {
+        let db_path = tmp_db_path();
+        let db = SignerDb::new(&db_path).expect("Failed to create signer db");
+        let messages: Vec<_> = (0..3u8)
+            .map(|i| {
+                SignerMessage::BlockResponse(BlockResponse::Rejected(BlockRejection::new(
+                    Sha512Trunc256Sum([i; 32]),
+                    RejectCode::ConnectivityIssues,
+                )))
+            })
+            .collect();
+        let mut ids = vec![];
+        for (i, message) in messages.iter().enumerate() {
+            ids.push(
+                db.insert_broadcast(10, 100 + i as u64, message)
+                    .expect("Failed to insert broadcast"),
+            );
+        }
+        db.insert_broadcast(11, 100, &messages[0])
+            .expect("Failed to insert broadcast");
+        db.remove_broadcast(ids[1])
+            .expect("Failed to remove broadcast");
+
+        // Queued messages survive a restart, in order
+        drop(db);
+        let db = SignerDb::new(&db_path).expect("Failed to open signer db");
+        assert_eq!(
+            db.get_broadcasts(10).expect("Failed to get broadcasts"),
+            vec![
+                QueuedMessage {
+                    id: Some(ids[0]),
+                    message: messages[0].clone(),
+                    expires_at: 100,
+                },
+                QueuedMessage {
+                    id: Some(ids[2]),
+                    message: messages[2].clone(),
+                    expires_at: 102,
+                },
+            ]
+        );
+        assert_eq!(db.get_broadcasts(11).unwrap().len(), 1);
+        assert!(db.get_broadcasts(12).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_signer_db_pool_concurrent_access() {
+        let db_path = tmp_db_path();
+        let pool = SignerDbPool::new(&db_path, 4).expect("Failed to create signer db pool");
+        let handles: Vec<_> = (0..8u64)
+            .map(|i| {
+                let pool = pool.clone();
+                thread::spawn(move || {
+                    let (block_info, block_proposal) = create_block_override(|b| {
+                        b.block.header.chain_length = i;
+                    });
+                    pool.insert_block(&block_info)
+                        .expect("Unable to insert block into db");
+                    pool.insert_encrypted_signer_state(i, &[i as u8])
+                        .expect("Failed to insert signer state");
+                    let looked_up = pool
+                        .block_lookup(
+                            block_info.reward_cycle,
+                            &block_proposal.block.header.signer_signature_hash(),
+                        )
+                        .unwrap()
+                        .expect("Unable to get block from db");
+                    assert_eq!(looked_up, block_info);
+                    block_info
+                })
+            })
+            .collect();
+        let blocks: Vec<_> = handles
+            .into_iter()
+            .map(|handle| handle.join().expect("Thread panicked"))
+            .collect();
+
+        // Writes made through the pool are visible to a single connection
+        let db = SignerDb::new(&db_path).expect("Failed to open signer db");
+        for (i, block_info) in blocks.iter().enumerate() {
+            assert_eq!(
+                db.block_lookup(block_info.reward_cycle, &block_info.signer_signature_hash())
+                    .unwrap()
+                    .as_ref(),
+                Some(block_info)
+            );
+            assert_eq!(
+                db.get_encrypted_signer_state(i as u64).unwrap(),
+                Some(vec![i as u8])
+            );
+        }
+    }
+
+    fn create_blocks(reward_cycle: u64, count: u64) -> Vec<BlockInfo> {
+        (0..count)
+            .map(|i| {
+                create_block_override(|b| {
+                    b.reward_cycle = reward_cycle;
+                    b.block.header.chain_length = i;
+                })
+                .0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_batch_insert_blocks() {
+        let mut db = SignerDb::new(tmp_db_path()).expect("Failed to create signer db");
+        let blocks = create_blocks(42, 10);
+        assert_eq!(db.batch_insert_blocks(42, &blocks).unwrap(), 10);
+        for block_info in &blocks {
+            assert_eq!(
+                db.block_lookup(42, &block_info.signer_signature_hash())
+                    .unwrap()
+                    .as_ref(),
+                Some(block_info)
+            );
+        }
+
+        // A batch with a block of another reward cycle is rejected as a whole
+        let mut mixed_blocks = create_blocks(43, 2);
+        mixed_blocks.extend(create_blocks(44, 1));
+        assert!(db.batch_insert_blocks(43, &mixed_blocks).is_err());
+        assert!(db
+            .block_lookup(43, &mixed_blocks[0].signer_signature_hash())
+            .unwrap()
+            .is_none());
+
+        assert_eq!(db.batch_insert_blocks(43, &[]).unwrap(), 0);
+
+        let pool = SignerDbPool::new(tmp_db_path(), 2).expect("Failed to create signer db pool");
+        assert_eq!(pool.batch_insert_blocks(42, &blocks).unwrap(), 10);
+        assert_eq!(
+            pool.block_lookup(42, &blocks[9].signer_signature_hash())
+                .unwrap()
+                .as_ref(),
+            Some(&blocks[9])
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_batch_insert_blocks() {
+        let blocks = create_blocks(42, 1000);
+
+        let mut db = SignerDb::new(tmp_db_path()).expect("Failed to create signer db");
+        let start = Instant::now();
+        for block_info in &blocks {
+            db.insert_block(block_info).unwrap();
+        }
+        let sequential = start.elapsed();
+
+        let mut db = SignerDb::new(tmp_db_path()).expect("Failed to create signer db");
+        let start = Instant::now();
+        db.batch_insert_blocks(42, &blocks).unwrap();
+        let batched = start.elapsed();
+
+        println!(
+            "Inserted {} blocks: sequential={sequential:?}, batched={batched:?}",
+            blocks.len()
+        );
+        assert!(batched < sequential);
+    }
+
+    #[test]
+    fn test_signer_db_pool_rejects_in_memory_db() {
+        assert!(SignerDbPool::new(":memory:", 4).is_err());
+    }
+
+    #[test]
+    fn test_write_signer_state() {
+        let db_path = tmp_db_path();
+        let db = SignerDb::new(db_path).expect("Failed to create signer db");
+        let state_0 = vec![0];
+        let state_1 = vec![1; 1024];
+
+        db.insert_encrypted_signer_state(10, &state_0)
+            .expect("Failed to insert signer state");
+
+        db.insert_encrypted_signer_state(11, &state_1)
             .expect("Failed to insert signer state");
 
         assert_eq!(
@@ -342,4 +2141,409 @@ mod tests {
             .expect("Failed to get signer state")
             .is_none());
     }
+
+    fn coordinator_config(threshold: u32, num_signers: u32, num_keys: u32) -> CoordinatorConfig {
+        CoordinatorConfig {
+            threshold,
+            dkg_threshold: threshold,
+            num_signers,
+            num_keys,
+            message_private_key: Scalar::from(1),
+            dkg_public_timeout: None,
+            dkg_private_timeout: None,
+            dkg_end_timeout: None,
+            nonce_timeout: None,
+            sign_timeout: None,
+            signer_key_ids: Default::default(),
+            signer_public_keys: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_coordinator_config_drift() {
+        let db_path = tmp_db_path();
+        let db = SignerDb::new(&db_path).expect("Failed to create signer db");
+        assert!(db.get_last_coordinator_config(5).unwrap().is_none());
+
+        // The first configuration of a reward cycle is no drift
+        assert!(db
+            .record_coordinator_config(5, &coordinator_config(7, 5, 10))
+            .unwrap()
+            .is_none());
+        // Neither is restarting with the same signing parameters, nor another reward cycle's
+        let db = SignerDb::new(&db_path).expect("Failed to reopen signer db");
+        assert!(db
+            .record_coordinator_config(5, &coordinator_config(7, 5, 10))
+            .unwrap()
+            .is_none());
+        assert!(db
+            .record_coordinator_config(6, &coordinator_config(14, 10, 20))
+            .unwrap()
+            .is_none());
+
+        let previous = db
+            .record_coordinator_config(5, &coordinator_config(8, 5, 11))
+            .unwrap()
+            .expect("Changed signing parameters should be reported");
+        assert_eq!(
+            (previous.threshold, previous.num_signers, previous.num_keys),
+            (7, 5, 10)
+        );
+        let last = db.get_last_coordinator_config(5).unwrap().unwrap();
+        assert_eq!(
+            (
+                last.reward_cycle,
+                last.threshold,
+                last.num_signers,
+                last.num_keys
+            ),
+            (5, 8, 5, 11)
+        );
+        assert!(last.recorded_at >= previous.recorded_at);
+    }
+
+    #[test]
+    fn test_missing_transactions_streak() {
+        let db_path = tmp_db_path();
+        let mut db = SignerDb::new(db_path).expect("Failed to create signer db");
+        let (txid_1, txid_2) = (Txid([1; 32]), Txid([2; 32]));
+
+        assert_eq!(db.record_missing_transactions(5, &[]).unwrap(), 0);
+        assert_eq!(db.record_missing_transactions(5, &[txid_1]).unwrap(), 1);
+        assert_eq!(
+            db.record_missing_transactions(5, &[txid_1, txid_2])
+                .unwrap(),
+            2
+        );
+        // Streaks are tracked per reward cycle
+        assert_eq!(db.record_missing_transactions(6, &[txid_1]).unwrap(), 1);
+        assert_eq!(db.record_missing_transactions(5, &[txid_2]).unwrap(), 2);
+        // Including a transaction resets its streak
+        assert_eq!(db.record_missing_transactions(5, &[txid_1]).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_rejected_requests() {
+        let db_path = tmp_db_path();
+        let db = SignerDb::new(db_path).expect("Failed to create signer db");
+        let rejected_request = |sign_id, coordinator_id, reason| RejectedRequest {
+            reward_cycle: 5,
+            sign_id,
+            coordinator_id,
+            message_hash: Sha512Trunc256Sum([sign_id as u8; 32]),
+            reason,
+            rejected_at: 100 + sign_id,
+        };
+        assert!(db.get_rejected_requests(5).unwrap().is_empty());
+
+        let first = rejected_request(1, 0, NackReason::UnexpectedCoordinator);
+        assert_eq!(db.record_rejected_request(&first).unwrap(), 1);
+        let second = rejected_request(1, 0, NackReason::MalformedPacket);
+        assert_eq!(db.record_rejected_request(&second).unwrap(), 2);
+        // Rejections are counted per signing round and coordinator
+        let other_round = rejected_request(2, 0, NackReason::UnexpectedCoordinator);
+        assert_eq!(db.record_rejected_request(&other_round).unwrap(), 1);
+        let other_coordinator = rejected_request(1, 3, NackReason::UnexpectedCoordinator);
+        assert_eq!(db.record_rejected_request(&other_coordinator).unwrap(), 1);
+
+        assert_eq!(
+            db.get_rejected_requests(5).unwrap(),
+            vec![first, second, other_round, other_coordinator]
+        );
+        assert!(db.get_rejected_requests(6).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_rejections() {
+        let db_path = tmp_db_path();
+        let mut db = SignerDb::new(db_path).expect("Failed to create signer db");
+        let (block_info_1, _) = create_block_override(|b| {
+            b.block.header.chain_length = 1;
+            b.reward_cycle = 5;
+            b.burn_height = 10;
+        });
+        let (block_info_2, _) = create_block_override(|b| {
+            b.block.header.chain_length = 2;
+            b.reward_cycle = 6;
+            b.burn_height = 20;
+        });
+        let (block_info_3, _) = create_block_override(|b| {
+            b.block.header.chain_length = 3;
+            b.reward_cycle = 6;
+            b.burn_height = 21;
+        });
+        for block_info in [&block_info_1, &block_info_2, &block_info_3] {
+            db.insert_block(block_info).unwrap();
+        }
+        assert!(db
+            .search_rejections("MissingTransactions")
+            .unwrap()
+            .is_empty());
+
+        db.record_block_rejection(
+            5,
+            &block_info_1.signer_signature_hash(),
+            &RejectCode::MissingTransactions(vec![]),
+        )
+        .unwrap();
+        db.record_block_rejection(
+            6,
+            &block_info_2.signer_signature_hash(),
+            &RejectCode::ConnectivityIssues,
+        )
+        .unwrap();
+        db.record_block_rejection(
+            6,
+            &block_info_3.signer_signature_hash(),
+            &RejectCode::MissingTransactions(vec![]),
+        )
+        .unwrap();
+        // Updating a block keeps its rejection reason
+        db.insert_block(&block_info_3).unwrap();
+
+        let rejections = db.search_rejections("missingtransactions").unwrap();
+        assert_eq!(
+            rejections
+                .iter()
+                .map(|summary| (
+                    summary.reward_cycle,
+                    summary.signer_signature_hash,
+                    summary.burn_block_height
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (5, block_info_1.signer_signature_hash(), 10),
+                (6, block_info_3.signer_signature_hash(), 21),
+            ]
+        );
+        assert!(rejections
+            .iter()
+            .all(|summary| summary.rejection_reason == "MissingTransactions"));
+        assert_eq!(db.search_rejections("Connectivity").unwrap().len(), 1);
+        assert_eq!(db.search_rejections("").unwrap().len(), 3);
+        // LIKE wildcards in the keyword are matched literally
+        assert!(db.search_rejections("%").unwrap().is_empty());
+        assert!(db
+            .search_rejections("Missing_Transactions")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_get_reward_cycle_stats() {
+        let db_path = tmp_db_path();
+        let mut db = SignerDb::new(db_path).expect("Failed to create signer db");
+        assert_eq!(
+            db.get_reward_cycle_stats(42).unwrap(),
+            RewardCycleStats::default()
+        );
+
+        let mut blocks = [10, 11, 12, 13].into_iter().map(|burn_height| {
+            create_block_override(|b| {
+                b.block.header.chain_length = burn_height;
+                b.burn_height = burn_height;
+            })
+            .0
+        });
+        let accepted = with_validation_result(blocks.next().unwrap(), true);
+        let invalid = with_validation_result(blocks.next().unwrap(), false);
+        let rejected = with_validation_result(blocks.next().unwrap(), true);
+        let pending = blocks.next().unwrap();
+        let (other_cycle_block, _) = create_block_override(|b| b.reward_cycle = 43);
+        for block_info in [&accepted, &invalid, &rejected, &pending, &other_cycle_block] {
+            db.insert_block(block_info).unwrap();
+        }
+        db.record_block_rejection(
+            42,
+            &rejected.signer_signature_hash(),
+            &RejectCode::ConnectivityIssues,
+        )
+        .unwrap();
+        let aggregate_key = Point::from(Scalar::from(1));
+        db.record_dkg_round(42, 1, &aggregate_key).unwrap();
+        db.record_dkg_round(42, 2, &aggregate_key).unwrap();
+        db.record_dkg_round(43, 1, &aggregate_key).unwrap();
+
+        assert_eq!(
+            db.get_reward_cycle_stats(42).unwrap(),
+            RewardCycleStats {
+                total_blocks_seen: 4,
+                blocks_accepted: 1,
+                blocks_rejected: 2,
+                blocks_pending: 1,
+                dkg_rounds: 2,
+                first_block_at: Some(10),
+                last_block_at: Some(13),
+            }
+        );
+    }
+
+    #[test]
+    fn test_prune_reward_cycles() {
+        let db_path = tmp_db_path();
+        let mut db = SignerDb::new(&db_path).expect("Failed to create signer db");
+        let blocks: Vec<BlockInfo> = [4, 5, 5, 6]
+            .into_iter()
+            .enumerate()
+            .map(|(i, reward_cycle)| {
+                create_block_override(|b| {
+                    b.block.header.chain_length = i as u64;
+                    b.reward_cycle = reward_cycle;
+                })
+                .0
+            })
+            .collect();
+        for block_info in &blocks {
+            db.insert_block(block_info).unwrap();
+        }
+        db.insert_encrypted_signer_state(4, &[1]).unwrap();
+        db.insert_encrypted_signer_state(6, &[2]).unwrap();
+
+        // There are no more reward cycles than the ones to keep
+        assert_eq!(db.prune_reward_cycles(3).unwrap(), 0);
+        assert_eq!(db.prune_reward_cycles(2).unwrap(), 1);
+        assert!(db.get_blocks(4).unwrap().is_empty());
+        assert!(db.get_encrypted_signer_state(4).unwrap().is_none());
+        assert_eq!(db.get_encrypted_signer_state(6).unwrap(), Some(vec![2]));
+        let mut kept_hashes: Vec<_> = db
+            .get_blocks(5)
+            .unwrap()
+            .iter()
+            .map(|record| record.block_info.signer_signature_hash())
+            .collect();
+        kept_hashes.sort();
+        let mut expected_hashes: Vec<_> = blocks[1..3]
+            .iter()
+            .map(|block_info| block_info.signer_signature_hash())
+            .collect();
+        expected_hashes.sort();
+        assert_eq!(kept_hashes, expected_hashes);
+
+        // The read-only connection sees the blocks but cannot prune them
+        let mut readonly_db = SignerDb::readonly(&db_path).expect("Failed to open signer db");
+        assert_eq!(
+            readonly_db
+                .get_blocks_by_hash(&blocks[3].signer_signature_hash())
+                .unwrap(),
+            &blocks[3..]
+        );
+        assert!(readonly_db.prune_reward_cycles(1).is_err());
+        assert_eq!(db.prune_reward_cycles(1).unwrap(), 2);
+        assert_eq!(db.get_blocks(6).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_import_block_history() {
+        let db_path = tmp_db_path();
+        let mut db = SignerDb::new(&db_path).expect("Failed to create signer db");
+        let [accepted, rejected, unanswered, other_cycle, known] = [0, 1, 2, 3, 4].map(|i| {
+            create_block_override(|b| {
+                b.block.header.chain_length = i;
+                if i == 3 {
+                    b.reward_cycle = 43;
+                }
+            })
+        });
+        db.insert_block(&known.0).unwrap();
+        let signature = || Signature {
+            R: Point::new(),
+            z: Scalar::new(),
+        };
+        let rejection = |block_info: &BlockInfo| {
+            BlockResponse::Rejected(BlockRejection::new(
+                block_info.signer_signature_hash(),
+                RejectCode::ValidationFailed(ValidateRejectCode::InvalidBlock),
+            ))
+        };
+        let block_responses = vec![
+            rejection(&accepted.0),
+            BlockResponse::accepted(accepted.0.signer_signature_hash(), signature()),
+            rejection(&rejected.0),
+            BlockResponse::rejected(rejected.0.signer_signature_hash(), signature()),
+            BlockResponse::accepted(other_cycle.0.signer_signature_hash(), signature()),
+            BlockResponse::accepted(known.0.signer_signature_hash(), signature()),
+        ];
+        let block_proposals = vec![accepted.1, rejected.1, unanswered.1, other_cycle.1, known.1];
+        assert_eq!(
+            db.import_block_history(42, block_proposals, &block_responses)
+                .unwrap(),
+            2
+        );
+
+        let records = db.get_blocks(42).unwrap();
+        assert_eq!(records.len(), 3);
+        let record = |block_info: &BlockInfo| {
+            records
+                .iter()
+                .find(|record| {
+                    record.block_info.signer_signature_hash() == block_info.signer_signature_hash()
+                })
+                .unwrap()
+        };
+        let accepted_record = record(&accepted.0);
+        assert_eq!(
+            accepted_record
+                .block_info
+                .vote
+                .as_ref()
+                .map(|vote| vote.rejected),
+            Some(false)
+        );
+        assert!(accepted_record.block_info.signed_over);
+        assert_eq!(accepted_record.rejection_reason, None);
+        let rejected_record = record(&rejected.0);
+        assert_eq!(
+            rejected_record
+                .block_info
+                .vote
+                .as_ref()
+                .map(|vote| vote.rejected),
+            Some(true)
+        );
+        assert!(rejected_record.rejection_reason.is_some());
+        // The block already in the database is left untouched
+        assert_eq!(record(&known.0).block_info.vote, None);
+        assert!(db.get_blocks(43).unwrap().is_empty());
+
+        // Importing again finds nothing new
+        let (_, accepted_proposal) = create_block_override(|b| b.block.header.chain_length = 0);
+        assert_eq!(
+            db.import_block_history(42, vec![accepted_proposal], &block_responses)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_migrate_from_json_state() {
+        let db_path = tmp_db_path();
+        let mut db = SignerDb::new(&db_path).expect("Failed to create signer db");
+        let json_path = db_path.with_extension("json");
+        let (first, _) = create_block_override(|b| b.block.header.chain_length = 1);
+        let (second, _) = create_block_override(|b| {
+            b.block.header.chain_length = 2;
+            b.reward_cycle = 43;
+        });
+        let mut truncated = serde_json::to_value(&first).unwrap();
+        truncated.as_object_mut().unwrap().remove("block");
+        let dump = serde_json::json!([first, 7, { "reward_cycle": 42 }, truncated, second]);
+        fs::write(&json_path, dump.to_string()).unwrap();
+
+        assert_eq!(db.migrate_from_json_state(&json_path).unwrap(), 2);
+        assert_eq!(
+            db.block_lookup(42, &first.signer_signature_hash()).unwrap(),
+            Some(first)
+        );
+        assert_eq!(
+            db.block_lookup(43, &second.signer_signature_hash())
+                .unwrap(),
+            Some(second)
+        );
+
+        // A dump that is not an array of blocks is rejected as a whole
+        fs::write(&json_path, r#"{"blocks": []}"#).unwrap();
+        assert!(db.migrate_from_json_state(&json_path).is_err());
+        fs::remove_file(&json_path).unwrap();
+        assert!(db.migrate_from_json_state(&json_path).is_err());
+    }
 }
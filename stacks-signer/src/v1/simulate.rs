@@ -0,0 +1,344 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::time::{Duration, Instant};
+
+use blockstack_lib::chainstate::nakamoto::NakamotoBlockHeader;
+use blockstack_lib::chainstate::stacks::boot::NakamotoSignerEntry;
+use libsigner::SignerEntries;
+use rand_core::OsRng;
+use wsts::curve::ecdsa;
+use wsts::curve::point::Point;
+use wsts::curve::scalar::Scalar;
+use wsts::net::{Message, Packet};
+use wsts::state_machine::coordinator::fire::Coordinator as FireCoordinator;
+use wsts::state_machine::coordinator::Coordinator;
+use wsts::state_machine::signer::Signer as SignerStateMachine;
+use wsts::state_machine::OperationResult;
+use wsts::v2;
+
+use crate::v1::signer::{new_coordinator_config, new_state_machine};
+use crate::v1::timings::{
+    now_ms, SignRoundBreakdown, SignRoundStep, SignRoundTimings, SignRoundWaits,
+};
+
+/// The most message exchanges a round may take before the simulation gives up on it.
+/// A DKG round completes in three and a signing round in two.
+const MAX_EXCHANGES: usize = 16;
+
+/// The parameters of a simulated signer set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulationParams {
+    /// The number of signers
+    pub num_signers: u32,
+    /// The number of key IDs, split as evenly as possible across the signers
+    pub num_keys: u32,
+    /// The percentage of the key IDs needed to sign
+    pub threshold_percent: u32,
+}
+
+/// The outcome of a successful simulation
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationReport {
+    /// The number of key IDs needed to sign
+    pub threshold: u32,
+    /// The number of key IDs needed to complete DKG
+    pub dkg_threshold: u32,
+    /// How long the DKG round took
+    pub dkg_duration: Duration,
+    /// How long the signing round took
+    pub sign_duration: Duration,
+    /// Where the time of the signing round went
+    pub sign_breakdown: SignRoundBreakdown,
+    /// The aggregate public key produced by DKG
+    pub aggregate_key: Point,
+    /// The message that was signed
+    pub message: Vec<u8>,
+    /// Whether the signature verified against the aggregate key
+    pub signature_verified: bool,
+}
+
+/// Errors that abort a simulation
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum SimulationError {
+    /// The parameters cannot describe a working signer set
+    #[error("Invalid simulation parameters: {0}")]
+    InvalidParameters(String),
+    /// The DKG round failed
+    #[error("DKG round failed: {0}")]
+    Dkg(String),
+    /// The signing round failed
+    #[error("Signing round failed: {0}")]
+    Sign(String),
+}
+
+/// The coordinator and signers of a simulated signer set, all in memory
+struct SimulatedSignerSet {
+    coordinator: FireCoordinator<v2::Aggregator>,
+    signers: Vec<SignerStateMachine<v2::Signer>>,
+    /// When the signing round reached each step
+    sign_timings: SignRoundTimings,
+    /// How long the coordinator waited for the slowest nonce and share
+    sign_waits: SignRoundWaits,
+}
+
+impl SimulatedSignerSet {
+    /// Deliver the packet, and every packet sent in response to it, to all of the signers and
+    /// the coordinator until the coordinator produces a result
+    fn run_round(&mut self, packet: Packet) -> Result<Vec<OperationResult>, String> {
+        self.record_outbound(&packet);
+        let mut in_flight = vec![packet];
+        for _ in 0..MAX_EXCHANGES {
+            let mut signer_packets = vec![];
+            for signer in self.signers.iter_mut() {
+                let outbound = signer
+                    .process_inbound_messages(&in_flight)
+                    .map_err(|e| format!("signer {} failed: {e:?}", signer.signer_id))?;
+                signer_packets.extend(outbound);
+            }
+            let now = Instant::now();
+            for packet in &signer_packets {
+                self.sign_waits.record_inbound(&packet.msg, now);
+            }
+            let (coordinator_packets, results) = self
+                .coordinator
+                .process_inbound_messages(&signer_packets)
+                .map_err(|e| format!("coordinator failed: {e:?}"))?;
+            coordinator_packets
+                .iter()
+                .for_each(|packet| self.record_outbound(packet));
+            if !results.is_empty() {
+                return Ok(results);
+            }
+            if signer_packets.is_empty() && coordinator_packets.is_empty() {
+                return Err("the round stalled with no messages left to deliver".into());
+            }
+            in_flight = signer_packets;
+            in_flight.extend(coordinator_packets);
+        }
+        Err(format!(
+            "the round did not complete within {MAX_EXCHANGES} message exchanges"
+        ))
+    }
+
+    /// Record a request of the coordinator in the signing round timings
+    fn record_outbound(&mut self, packet: &Packet) {
+        self.sign_waits.record_outbound(&packet.msg, Instant::now());
+        match packet.msg {
+            Message::NonceRequest(_) => self
+                .sign_timings
+                .record(SignRoundStep::NonceRound, now_ms()),
+            Message::SignatureShareRequest(_) => self
+                .sign_timings
+                .record(SignRoundStep::ShareRound, now_ms()),
+            _ => {}
+        }
+    }
+}
+
+/// Check the parameters, returning the signing threshold they give
+fn signing_threshold(params: &SimulationParams) -> Result<u32, SimulationError> {
+    let SimulationParams {
+        num_signers,
+        num_keys,
+        threshold_percent,
+    } = *params;
+    if num_signers == 0 {
+        return Err(SimulationError::InvalidParameters(
+            "at least one signer is required".into(),
+        ));
+    }
+    if num_keys < num_signers {
+        return Err(SimulationError::InvalidParameters(format!(
+            "{num_keys} key IDs cannot be split across {num_signers} signers, as each signer needs at least one. Use --keys {num_signers} or more."
+        )));
+    }
+    if threshold_percent == 0 || threshold_percent > 100 {
+        return Err(SimulationError::InvalidParameters(format!(
+            "the threshold must be between 1 and 100 percent, got {threshold_percent}"
+        )));
+    }
+    let threshold = (u64::from(num_keys) * u64::from(threshold_percent) + 99) / 100;
+    Ok(u32::try_from(threshold).expect("threshold is at most the number of keys"))
+}
+
+/// The signing keys of the simulated signers, and the reward set entries giving each of them
+/// an even share of the key IDs
+fn signer_entries(params: &SimulationParams) -> (Vec<Scalar>, Vec<NakamotoSignerEntry>) {
+    (0..params.num_signers)
+        .map(|signer_id| {
+            let private_key = Scalar::random(&mut OsRng);
+            let public_key = ecdsa::PublicKey::new(&private_key)
+                .expect("Failed to create ecdsa public key")
+                .to_bytes();
+            let mut signing_key = [0u8; 33];
+            signing_key.copy_from_slice(&public_key);
+            let weight = params.num_keys / params.num_signers
+                + u32::from(signer_id < params.num_keys % params.num_signers);
+            let entry = NakamotoSignerEntry {
+                signing_key,
+                stacked_amt: u128::from(weight),
+                weight,
+            };
+            (private_key, entry)
+        })
+        .unzip()
+}
+
+/// Simulate a signer set: run a DKG round and then a signing round over a dummy block in memory,
+/// with the coordinator and signers built the same way as those of a running signer
+pub fn simulate(params: &SimulationParams) -> Result<SimulationReport, SimulationError> {
+    let threshold = signing_threshold(params)?;
+    let (private_keys, entries) = signer_entries(params);
+    let signer_entries = SignerEntries::parse(false, &entries)
+        .map_err(|e| SimulationError::InvalidParameters(format!("{e:?}")))?;
+    let dkg_threshold = signer_entries
+        .get_dkg_threshold()
+        .map_err(|e| SimulationError::InvalidParameters(format!("{e:?}")))?;
+    if threshold > dkg_threshold {
+        return Err(SimulationError::InvalidParameters(format!(
+            "a signing threshold of {threshold} key IDs is above the DKG threshold of {dkg_threshold}, so DKG could complete without enough keys to ever sign. Use a --threshold-percent of at most 90."
+        )));
+    }
+
+    let coordinator_config =
+        new_coordinator_config(&signer_entries, threshold, dkg_threshold, private_keys[0]);
+    let signers = private_keys
+        .iter()
+        .zip(0u32..)
+        .map(|(private_key, signer_id)| {
+            let key_ids = signer_entries
+                .signer_key_ids
+                .get(&signer_id)
+                .cloned()
+                .unwrap_or_default();
+            new_state_machine(&signer_entries, threshold, signer_id, key_ids, *private_key)
+        })
+        .collect();
+    let mut signer_set = SimulatedSignerSet {
+        coordinator: FireCoordinator::new(coordinator_config),
+        signers,
+        sign_timings: SignRoundTimings::default(),
+        sign_waits: SignRoundWaits::default(),
+    };
+
+    let dkg_start = Instant::now();
+    let packet = signer_set
+        .coordinator
+        .start_dkg_round()
+        .map_err(|e| SimulationError::Dkg(format!("{e:?}")))?;
+    let aggregate_key = match signer_set
+        .run_round(packet)
+        .map_err(SimulationError::Dkg)?
+        .as_slice()
+    {
+        [OperationResult::Dkg(aggregate_key)] => *aggregate_key,
+        results => {
+            return Err(SimulationError::Dkg(format!(
+                "unexpected results {results:?}"
+            )))
+        }
+    };
+    let dkg_duration = dkg_start.elapsed();
+
+    let message = NakamotoBlockHeader::empty()
+        .signer_signature_hash()
+        .0
+        .to_vec();
+    let sign_start = Instant::now();
+    let packet = signer_set
+        .coordinator
+        .start_signing_round(&message, false, None)
+        .map_err(|e| SimulationError::Sign(format!("{e:?}")))?;
+    let signature = match signer_set
+        .run_round(packet)
+        .map_err(SimulationError::Sign)?
+        .as_slice()
+    {
+        [OperationResult::Sign(signature)] => signature.clone(),
+        results => {
+            return Err(SimulationError::Sign(format!(
+                "unexpected results {results:?}"
+            )))
+        }
+    };
+    let sign_duration = sign_start.elapsed();
+    let mut sign_timings = signer_set.sign_timings;
+    sign_timings.record(SignRoundStep::SignatureBroadcast, now_ms());
+    signer_set.sign_waits.finish(&mut sign_timings);
+
+    Ok(SimulationReport {
+        threshold,
+        dkg_threshold,
+        dkg_duration,
+        sign_duration,
+        sign_breakdown: sign_timings.breakdown(),
+        aggregate_key,
+        signature_verified: signature.verify(&aggregate_key, &message),
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(num_signers: u32, num_keys: u32, threshold_percent: u32) -> SimulationParams {
+        SimulationParams {
+            num_signers,
+            num_keys,
+            threshold_percent,
+        }
+    }
+
+    #[test]
+    fn simulate_should_sign_with_valid_parameters() {
+        for (num_signers, num_keys, threshold_percent, threshold) in
+            [(1, 1, 70, 1), (5, 20, 70, 14), (3, 10, 90, 9), (4, 7, 1, 1)]
+        {
+            let report = simulate(&params(num_signers, num_keys, threshold_percent)).unwrap();
+            assert_eq!(report.threshold, threshold);
+            assert!(report.signature_verified);
+            for phase in [
+                "nonce_round",
+                "share_round",
+                "slowest_nonce",
+                "slowest_share",
+            ] {
+                assert!(
+                    report.sign_breakdown.get(phase).is_some(),
+                    "missing {phase}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn simulate_should_reject_impossible_parameters() {
+        for params in [
+            params(0, 10, 70),
+            params(5, 3, 70),
+            params(5, 20, 0),
+            params(5, 20, 101),
+            params(5, 20, 95),
+        ] {
+            assert!(matches!(
+                simulate(&params),
+                Err(SimulationError::InvalidParameters(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,318 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use serde_derive::{Deserialize, Serialize};
+use stacks_common::util::get_epoch_time_ms;
+use wsts::net::Message;
+
+/// The steps a block goes through on its way to a signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignRoundStep {
+    /// The block proposal was received
+    Received,
+    /// The stacks node answered the validation of the block
+    Validated,
+    /// The nonce round of the signing round started
+    NonceRound,
+    /// The signature share round of the signing round started
+    ShareRound,
+    /// The signature was broadcast
+    SignatureBroadcast,
+}
+
+/// The time, in milliseconds since the epoch, at which a block first reached each step of its
+/// signing round, along with the coordinator's waits for the slowest signers
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SignRoundTimings {
+    /// When the block proposal was received
+    #[serde(default)]
+    pub received_at_ms: Option<u64>,
+    /// When the stacks node answered the validation of the block
+    #[serde(default)]
+    pub validated_at_ms: Option<u64>,
+    /// When the nonce round started
+    #[serde(default)]
+    pub nonce_round_at_ms: Option<u64>,
+    /// When the signature share round started
+    #[serde(default)]
+    pub share_round_at_ms: Option<u64>,
+    /// When the signature was broadcast
+    #[serde(default)]
+    pub signature_broadcast_at_ms: Option<u64>,
+    /// How long the coordinator waited for the slowest nonce, if we coordinated the round
+    #[serde(default)]
+    pub slowest_nonce_wait_ms: Option<u64>,
+    /// How long the coordinator waited for the slowest signature share, if we coordinated the round
+    #[serde(default)]
+    pub slowest_share_wait_ms: Option<u64>,
+}
+
+/// The current time in milliseconds since the epoch
+pub fn now_ms() -> u64 {
+    u64::try_from(get_epoch_time_ms()).unwrap_or(u64::MAX)
+}
+
+impl SignRoundTimings {
+    /// Record that the given step was reached at the given time, unless it was reached before
+    pub fn record(&mut self, step: SignRoundStep, at_ms: u64) {
+        let timing = match step {
+            SignRoundStep::Received => &mut self.received_at_ms,
+            SignRoundStep::Validated => &mut self.validated_at_ms,
+            SignRoundStep::NonceRound => &mut self.nonce_round_at_ms,
+            SignRoundStep::ShareRound => &mut self.share_round_at_ms,
+            SignRoundStep::SignatureBroadcast => &mut self.signature_broadcast_at_ms,
+        };
+        timing.get_or_insert(at_ms);
+    }
+
+    /// The time spent in each phase of the signing round whose start and end were both recorded.
+    /// Steps may be reached out of order, e.g. a nonce request arriving before validation, in
+    /// which case the phase between them takes no time.
+    pub fn breakdown(&self) -> SignRoundBreakdown {
+        let phase = |start: Option<u64>, end: Option<u64>| {
+            Some(Duration::from_millis(end?.saturating_sub(start?)))
+        };
+        let phases = [
+            (
+                "validation",
+                phase(self.received_at_ms, self.validated_at_ms),
+            ),
+            (
+                "awaiting_nonce_round",
+                phase(self.validated_at_ms, self.nonce_round_at_ms),
+            ),
+            (
+                "nonce_round",
+                phase(self.nonce_round_at_ms, self.share_round_at_ms),
+            ),
+            (
+                "share_round",
+                phase(self.share_round_at_ms, self.signature_broadcast_at_ms),
+            ),
+            (
+                "total",
+                phase(self.received_at_ms, self.signature_broadcast_at_ms),
+            ),
+            (
+                "slowest_nonce",
+                self.slowest_nonce_wait_ms.map(Duration::from_millis),
+            ),
+            (
+                "slowest_share",
+                self.slowest_share_wait_ms.map(Duration::from_millis),
+            ),
+        ];
+        SignRoundBreakdown {
+            phases: phases
+                .into_iter()
+                .filter_map(|(name, duration)| Some((name, duration?)))
+                .collect(),
+        }
+    }
+}
+
+/// The time spent in each phase of a signing round
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignRoundBreakdown {
+    /// The phases, in the order they happen, and how long each took
+    pub phases: Vec<(&'static str, Duration)>,
+}
+
+impl SignRoundBreakdown {
+    /// How long the given phase took, if it was recorded
+    pub fn get(&self, phase: &str) -> Option<Duration> {
+        self.phases
+            .iter()
+            .find(|(name, _)| *name == phase)
+            .map(|(_, duration)| *duration)
+    }
+}
+
+impl Display for SignRoundBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let phases: Vec<_> = self
+            .phases
+            .iter()
+            .map(|(name, duration)| format!("{name}={}ms", duration.as_millis()))
+            .collect();
+        write!(f, "{}", phases.join(" "))
+    }
+}
+
+/// Tracks how long the coordinator waits for the nonces and signature shares of its signing round
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignRoundWaits {
+    /// When the nonces were requested
+    nonces_requested_at: Option<Instant>,
+    /// When the signature shares were requested
+    shares_requested_at: Option<Instant>,
+    /// The longest wait for a nonce so far
+    slowest_nonce: Option<Duration>,
+    /// The longest wait for a signature share so far
+    slowest_share: Option<Duration>,
+}
+
+impl SignRoundWaits {
+    /// Record a request sent by the coordinator. A nonce request starts a new round.
+    pub fn record_outbound(&mut self, msg: &Message, now: Instant) {
+        match msg {
+            Message::NonceRequest(_) => {
+                *self = Self {
+                    nonces_requested_at: Some(now),
+                    ..Self::default()
+                };
+            }
+            Message::SignatureShareRequest(_) if self.nonces_requested_at.is_some() => {
+                self.shares_requested_at = Some(now);
+            }
+            _ => {}
+        }
+    }
+
+    /// Record a response received by the coordinator. Nonces arriving once the shares were
+    /// requested are too late to count towards the round.
+    pub fn record_inbound(&mut self, msg: &Message, now: Instant) {
+        match (msg, self.nonces_requested_at, self.shares_requested_at) {
+            (Message::NonceResponse(_), Some(requested_at), None) => {
+                let wait = now.saturating_duration_since(requested_at);
+                self.slowest_nonce = self.slowest_nonce.max(Some(wait));
+            }
+            (Message::SignatureShareResponse(_), _, Some(requested_at)) => {
+                let wait = now.saturating_duration_since(requested_at);
+                self.slowest_share = self.slowest_share.max(Some(wait));
+            }
+            _ => {}
+        }
+    }
+
+    /// Store the slowest waits of the round in the given timings and start over
+    pub fn finish(&mut self, timings: &mut SignRoundTimings) {
+        let as_millis = |wait: Duration| u64::try_from(wait.as_millis()).unwrap_or(u64::MAX);
+        timings.slowest_nonce_wait_ms = self.slowest_nonce.map(as_millis);
+        timings.slowest_share_wait_ms = self.slowest_share.map(as_millis);
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wsts::net::{NonceRequest, NonceResponse, SignatureShareRequest, SignatureShareResponse};
+
+    use super::*;
+
+    fn nonce_request() -> Message {
+        Message::NonceRequest(NonceRequest {
+            dkg_id: 1,
+            sign_id: 1,
+            sign_iter_id: 1,
+            message: vec![],
+            is_taproot: false,
+            merkle_root: None,
+        })
+    }
+
+    fn nonce_response() -> Message {
+        Message::NonceResponse(NonceResponse {
+            dkg_id: 1,
+            sign_id: 1,
+            sign_iter_id: 1,
+            signer_id: 0,
+            key_ids: vec![],
+            nonces: vec![],
+            message: vec![],
+        })
+    }
+
+    fn share_request() -> Message {
+        Message::SignatureShareRequest(SignatureShareRequest {
+            dkg_id: 1,
+            sign_id: 1,
+            sign_iter_id: 1,
+            nonce_responses: vec![],
+            message: vec![],
+            is_taproot: false,
+            merkle_root: None,
+        })
+    }
+
+    fn share_response() -> Message {
+        Message::SignatureShareResponse(SignatureShareResponse {
+            dkg_id: 1,
+            sign_id: 1,
+            sign_iter_id: 1,
+            signer_id: 0,
+            signature_shares: vec![],
+        })
+    }
+
+    #[test]
+    fn breakdown_should_cover_recorded_phases() {
+        let mut timings = SignRoundTimings::default();
+        timings.record(SignRoundStep::Received, 1_000);
+        timings.record(SignRoundStep::Validated, 1_200);
+        timings.record(SignRoundStep::NonceRound, 1_100);
+        timings.record(SignRoundStep::ShareRound, 1_500);
+        // Only the first time a step is reached counts
+        timings.record(SignRoundStep::ShareRound, 1_600);
+        let breakdown = timings.breakdown();
+        assert_eq!(
+            breakdown.get("validation"),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(breakdown.get("awaiting_nonce_round"), Some(Duration::ZERO));
+        assert_eq!(
+            breakdown.get("nonce_round"),
+            Some(Duration::from_millis(400))
+        );
+        assert_eq!(breakdown.get("share_round"), None);
+        assert_eq!(breakdown.get("total"), None);
+
+        timings.record(SignRoundStep::SignatureBroadcast, 1_750);
+        let breakdown = timings.breakdown();
+        assert_eq!(
+            breakdown.get("share_round"),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(breakdown.get("total"), Some(Duration::from_millis(750)));
+        assert_eq!(
+            breakdown.to_string(),
+            "validation=200ms awaiting_nonce_round=0ms nonce_round=400ms share_round=250ms total=750ms"
+        );
+    }
+
+    #[test]
+    fn waits_should_track_the_slowest_response_of_each_round() {
+        let mut waits = SignRoundWaits::default();
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        waits.record_outbound(&nonce_request(), start);
+        waits.record_inbound(&nonce_response(), at(30));
+        waits.record_inbound(&nonce_response(), at(10));
+        waits.record_outbound(&share_request(), at(40));
+        // Too late to count towards the nonce round
+        waits.record_inbound(&nonce_response(), at(90));
+        waits.record_inbound(&share_response(), at(65));
+
+        let mut timings = SignRoundTimings::default();
+        waits.finish(&mut timings);
+        assert_eq!(timings.slowest_nonce_wait_ms, Some(30));
+        assert_eq!(timings.slowest_share_wait_ms, Some(25));
+        assert_eq!(waits, SignRoundWaits::default());
+    }
+}
@@ -0,0 +1,131 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2024 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use blockstack_lib::chainstate::stacks::boot::SignersVotingError;
+use clarity::vm::Value as ClarityValue;
+
+/// Why a DKG vote transaction failed, and whether voting again may succeed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteFailure {
+    /// The vote may succeed later, once the signer set or the voting rounds caught up
+    Retryable(SignersVotingError),
+    /// The vote will never succeed: it is ill-formed, not ours to cast or no longer needed
+    Permanent(SignersVotingError),
+}
+
+impl VoteFailure {
+    /// Classify the failure of a vote from the result of its transaction, or from the result of
+    /// one vote of a `vote-for-aggregate-public-keys` batch. Returns None if the vote succeeded.
+    pub fn from_result(result: &ClarityValue) -> Option<Self> {
+        SignersVotingError::try_from(result.clone())
+            .ok()
+            .map(Self::from)
+    }
+
+    /// Whether voting again may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Retryable(_))
+    }
+
+    /// The error returned by the contract
+    pub fn error(&self) -> SignersVotingError {
+        match self {
+            Self::Retryable(error) | Self::Permanent(error) => *error,
+        }
+    }
+}
+
+impl From<SignersVotingError> for VoteFailure {
+    fn from(error: SignersVotingError) -> Self {
+        match error {
+            // The signer set is computed at the start of the prepare phase
+            SignersVotingError::CycleNotSet
+            | SignersVotingError::FailedToRetrieveSigners
+            | SignersVotingError::OutOfVotingWindow
+            // The previous round has no vote yet
+            | SignersVotingError::InvalidRound => Self::Retryable(error),
+            // A new DKG round is needed for a new key or a new round
+            SignersVotingError::DuplicateAggregatePublicKey
+            | SignersVotingError::RoundExpired
+            // The vote was already counted, or a key is approved
+            | SignersVotingError::DuplicateVote
+            | SignersVotingError::CycleAlreadyHasKey
+            // The signer is misconfigured or the key is malformed
+            | SignersVotingError::SignerIndexMismatch
+            | SignersVotingError::InvalidSignerIndex
+            | SignersVotingError::IllFormedAggregatePublicKey
+            | SignersVotingError::Unknown(_) => Self::Permanent(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vote_results_should_be_classified() {
+        assert_eq!(VoteFailure::from_result(&ClarityValue::okay_true()), None);
+        assert_eq!(
+            VoteFailure::from_result(&ClarityValue::from(SignersVotingError::CycleNotSet)),
+            Some(VoteFailure::Retryable(SignersVotingError::CycleNotSet))
+        );
+        assert_eq!(
+            VoteFailure::from_result(&ClarityValue::err_uint(17)),
+            Some(VoteFailure::Retryable(SignersVotingError::InvalidRound))
+        );
+        let duplicate_vote =
+            VoteFailure::from_result(&ClarityValue::from(SignersVotingError::DuplicateVote))
+                .unwrap();
+        assert!(!duplicate_vote.is_retryable());
+        assert_eq!(duplicate_vote.error(), SignersVotingError::DuplicateVote);
+        assert_eq!(
+            VoteFailure::from_result(&ClarityValue::err_uint(99)),
+            Some(VoteFailure::Permanent(SignersVotingError::Unknown(99)))
+        );
+    }
+
+    #[test]
+    fn batch_vote_results_should_be_classified_per_vote() {
+        let result = ClarityValue::okay(
+            ClarityValue::cons_list_unsanitized(vec![
+                ClarityValue::okay_true(),
+                ClarityValue::from(SignersVotingError::RoundExpired),
+                ClarityValue::from(SignersVotingError::InvalidRound),
+            ])
+            .unwrap(),
+        )
+        .unwrap();
+        // The batch itself succeeded
+        assert_eq!(VoteFailure::from_result(&result), None);
+        let failures: Vec<_> = result
+            .expect_result_ok()
+            .unwrap()
+            .expect_list()
+            .unwrap()
+            .iter()
+            .map(VoteFailure::from_result)
+            .collect();
+        assert_eq!(
+            failures,
+            vec![
+                None,
+                Some(VoteFailure::Permanent(SignersVotingError::RoundExpired)),
+                Some(VoteFailure::Retryable(SignersVotingError::InvalidRound)),
+            ]
+        );
+    }
+}
@@ -190,18 +190,16 @@ pub fn make_all_signers_vote_for_aggregate_key(
         assert!(insert_res.is_none(), "Duplicate signer in signers list");
     }
 
-    // Build a map of the signers, their private keys, and their index
+    // Build a map of the signers, their private keys, and their index.
+    // Stackers whose stake fell below the stacking threshold are not signers.
     let mut signers = HashMap::new();
     for test_stacker in test_stackers {
         let addr = key_to_stacks_addr(&test_stacker.signer_private_key);
         let principal = PrincipalData::from(addr);
-        signers.insert(
-            addr,
-            (
-                test_stacker.signer_private_key,
-                signers_to_index[&principal],
-            ),
-        );
+        let Some(index) = signers_to_index.get(&principal) else {
+            continue;
+        };
+        signers.insert(addr, (test_stacker.signer_private_key, *index));
     }
 
     // Vote for the aggregate key for each signer
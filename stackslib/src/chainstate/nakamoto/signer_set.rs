@@ -63,7 +63,8 @@ use crate::chainstate::stacks::address::PoxAddress;
 use crate::chainstate::stacks::boot::{
     PoxVersions, RawRewardSetEntry, RewardSet, BOOT_TEST_POX_4_AGG_KEY_CONTRACT,
     BOOT_TEST_POX_4_AGG_KEY_FNAME, POX_4_NAME, SIGNERS_MAX_LIST_SIZE, SIGNERS_NAME, SIGNERS_PK_LEN,
-    SIGNERS_UPDATE_STATE, SIGNERS_VOTING_FUNCTION_NAME, SIGNERS_VOTING_NAME,
+    SIGNERS_UPDATE_STATE, SIGNERS_VOTING_BATCH_FUNCTION_NAME, SIGNERS_VOTING_FUNCTION_NAME,
+    SIGNERS_VOTING_MAX_BATCH_VOTES, SIGNERS_VOTING_NAME,
 };
 use crate::chainstate::stacks::db::{
     ChainstateTx, ClarityTx, DBConfig as ChainstateConfig, MinerPaymentSchedule,
@@ -517,7 +518,7 @@ impl NakamotoSigners {
             debug!("valid_vote_transaction: Received a transaction with an outdated nonce ({origin_nonce} < {account_nonce}).");
             return false;
         }
-        Self::parse_vote_for_aggregate_public_key(transaction).is_some()
+        Self::parse_votes_for_aggregate_public_keys(transaction).is_some()
     }
 
     pub fn parse_vote_for_aggregate_public_key(
@@ -539,10 +540,7 @@ impl NakamotoSigners {
         }
         let signer_index_value = payload.function_args.first()?;
         let signer_index = u64::try_from(signer_index_value.clone().expect_u128().ok()?).ok()?;
-        let point_value = payload.function_args.get(1)?;
-        let point_bytes = point_value.clone().expect_buff(33).ok()?;
-        let compressed_data = Compressed::try_from(point_bytes.as_slice()).ok()?;
-        let aggregate_key = Point::try_from(&compressed_data).ok()?;
+        let aggregate_key = Self::parse_aggregate_key(payload.function_args.get(1)?)?;
         let round_value = payload.function_args.get(2)?;
         let voting_round = u64::try_from(round_value.clone().expect_u128().ok()?).ok()?;
         let reward_cycle =
@@ -555,6 +553,57 @@ impl NakamotoSigners {
         })
     }
 
+    /// Parse the votes of a transaction voting either for a single round with
+    /// `vote-for-aggregate-public-key` or for several rounds with `vote-for-aggregate-public-keys`
+    pub fn parse_votes_for_aggregate_public_keys(
+        transaction: &StacksTransaction,
+    ) -> Option<Vec<AggregateKeyVoteParams>> {
+        if let Some(vote) = Self::parse_vote_for_aggregate_public_key(transaction) {
+            return Some(vec![vote]);
+        }
+        let TransactionPayload::ContractCall(payload) = &transaction.payload else {
+            return None;
+        };
+        if payload.contract_identifier()
+            != boot_code_id(SIGNERS_VOTING_NAME, transaction.is_mainnet())
+            || payload.function_name != SIGNERS_VOTING_BATCH_FUNCTION_NAME.into()
+        {
+            return None;
+        }
+        if payload.function_args.len() != 3 {
+            return None;
+        }
+        let signer_index_value = payload.function_args.first()?;
+        let signer_index = u64::try_from(signer_index_value.clone().expect_u128().ok()?).ok()?;
+        let votes = payload.function_args.get(1)?.clone().expect_list().ok()?;
+        if votes.is_empty() || votes.len() > SIGNERS_VOTING_MAX_BATCH_VOTES {
+            return None;
+        }
+        let reward_cycle =
+            u64::try_from(payload.function_args.get(2)?.clone().expect_u128().ok()?).ok()?;
+        votes
+            .into_iter()
+            .map(|vote| {
+                let vote = vote.expect_tuple().ok()?;
+                let round_value = vote.get("round").ok()?;
+                let voting_round = u64::try_from(round_value.clone().expect_u128().ok()?).ok()?;
+                let aggregate_key = Self::parse_aggregate_key(vote.get("key").ok()?)?;
+                Some(AggregateKeyVoteParams {
+                    signer_index,
+                    aggregate_key,
+                    voting_round,
+                    reward_cycle,
+                })
+            })
+            .collect()
+    }
+
+    fn parse_aggregate_key(value: &Value) -> Option<Point> {
+        let point_bytes = value.clone().expect_buff(33).ok()?;
+        let compressed_data = Compressed::try_from(point_bytes.as_slice()).ok()?;
+        Point::try_from(&compressed_data).ok()
+    }
+
     /// Update the map of filtered valid transactions, selecting one per address based first on lowest nonce, then txid
     pub fn update_filtered_transactions(
         filtered_transactions: &mut HashMap<StacksAddress, StacksTransaction>,
@@ -69,8 +69,12 @@ use crate::chainstate::nakamoto::{
     query_rows, NakamotoBlock, NakamotoBlockHeader, NakamotoChainState, SortitionHandle,
     FIRST_STACKS_BLOCK_ID,
 };
+use crate::chainstate::stacks::boot::test::{
+    make_signers_vote_for_aggregate_public_key, make_signers_vote_for_aggregate_public_keys,
+    make_signers_vote_for_aggregate_public_keys_value,
+};
 use crate::chainstate::stacks::boot::{
-    MINERS_NAME, SIGNERS_VOTING_FUNCTION_NAME, SIGNERS_VOTING_NAME,
+    MINERS_NAME, SIGNERS_VOTING_FUNCTION_NAME, SIGNERS_VOTING_MAX_BATCH_VOTES, SIGNERS_VOTING_NAME,
 };
 use crate::chainstate::stacks::db::{
     ChainStateBootData, ChainstateAccountBalance, ChainstateAccountLockup, ChainstateBNSName,
@@ -2384,6 +2388,72 @@ fn valid_vote_transaction() {
     ));
 }
 
+#[test]
+fn parse_votes_for_aggregate_public_keys() {
+    let signer_private_key = StacksPrivateKey::new();
+    let points: Vec<_> = (0..2)
+        .map(|_| Point::from(Scalar::random(&mut thread_rng())))
+        .collect();
+    let votes = vec![(3, points[0]), (4, points[1])];
+
+    let batch_tx =
+        make_signers_vote_for_aggregate_public_keys(&signer_private_key, 1, 2, &votes, 5);
+    let params = NakamotoSigners::parse_votes_for_aggregate_public_keys(&batch_tx).unwrap();
+    assert_eq!(params.len(), votes.len());
+    for (params, (round, point)) in params.iter().zip(votes.iter()) {
+        assert_eq!(params.signer_index, 2);
+        assert_eq!(params.aggregate_key, *point);
+        assert_eq!(u128::from(params.voting_round), *round);
+        assert_eq!(params.reward_cycle, 5);
+    }
+    assert!(NakamotoSigners::parse_vote_for_aggregate_public_key(&batch_tx).is_none());
+    let mut account_nonces = std::collections::HashMap::new();
+    account_nonces.insert(batch_tx.origin_address(), 1);
+    assert!(NakamotoSigners::valid_vote_transaction(
+        &account_nonces,
+        &batch_tx,
+        false
+    ));
+
+    // A single vote is a batch of one
+    let single_tx =
+        make_signers_vote_for_aggregate_public_key(&signer_private_key, 1, 2, &points[0], 3, 5);
+    let params = NakamotoSigners::parse_votes_for_aggregate_public_keys(&single_tx).unwrap();
+    assert_eq!(params.len(), 1);
+    assert_eq!(params[0].aggregate_key, points[0]);
+    assert_eq!(params[0].voting_round, 3);
+
+    let point_arg =
+        Value::buff_from(points[0].compress().data.to_vec()).expect("Failed to create buff");
+    let empty_batch_tx =
+        make_signers_vote_for_aggregate_public_keys_value(&signer_private_key, 1, 2, vec![], 5);
+    let oversized_batch_tx = make_signers_vote_for_aggregate_public_keys_value(
+        &signer_private_key,
+        1,
+        2,
+        (0..=SIGNERS_VOTING_MAX_BATCH_VOTES as u128)
+            .map(|round| (round, point_arg.clone()))
+            .collect(),
+        5,
+    );
+    let invalid_key_batch_tx = make_signers_vote_for_aggregate_public_keys_value(
+        &signer_private_key,
+        1,
+        2,
+        vec![(0, point_arg), (1, Value::buff_from(vec![1; 33]).unwrap())],
+        5,
+    );
+    for (i, tx) in [empty_batch_tx, oversized_batch_tx, invalid_key_batch_tx]
+        .iter()
+        .enumerate()
+    {
+        assert!(
+            NakamotoSigners::parse_votes_for_aggregate_public_keys(tx).is_none(),
+            "parsed the {i}th transaction: {tx:?}"
+        );
+    }
+}
+
 #[test]
 fn valid_vote_transaction_malformed_transactions() {
     let signer_private_key = StacksPrivateKey::new();
@@ -79,6 +79,9 @@ pub const POX_4_NAME: &'static str = "pox-4";
 pub const SIGNERS_NAME: &'static str = "signers";
 pub const SIGNERS_VOTING_NAME: &'static str = "signers-voting";
 pub const SIGNERS_VOTING_FUNCTION_NAME: &str = "vote-for-aggregate-public-key";
+pub const SIGNERS_VOTING_BATCH_FUNCTION_NAME: &str = "vote-for-aggregate-public-keys";
+/// The most votes a single `vote-for-aggregate-public-keys` call accepts
+pub const SIGNERS_VOTING_MAX_BATCH_VOTES: usize = 16;
 /// This is the name of a variable in the `.signers` contract which tracks the most recently updated
 /// reward cycle number.
 pub const SIGNERS_UPDATE_STATE: &'static str = "last-set-cycle";
@@ -182,6 +185,90 @@ define_named_enum!(PoxVersions {
     Pox4("pox-4"),
 });
 
+/// The errors of the `.signers-voting` contract, including the error of the `.signers` contract
+/// that it passes on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignersVotingError {
+    /// `ERR_CYCLE_NOT_SET` (from `.signers`): the signer set of the reward cycle is not set yet
+    CycleNotSet,
+    /// `ERR_SIGNER_INDEX_MISMATCH`: the signer index belongs to another signer
+    SignerIndexMismatch,
+    /// `ERR_INVALID_SIGNER_INDEX`: no signer has the signer index
+    InvalidSignerIndex,
+    /// `ERR_OUT_OF_VOTING_WINDOW`: the vote is cast outside of the voting window
+    OutOfVotingWindow,
+    /// `ERR_ILL_FORMED_AGGREGATE_PUBLIC_KEY`: the aggregate public key is not 33 bytes long
+    IllFormedAggregatePublicKey,
+    /// `ERR_DUPLICATE_AGGREGATE_PUBLIC_KEY`: the aggregate public key was used in another cycle
+    DuplicateAggregatePublicKey,
+    /// `ERR_DUPLICATE_VOTE`: the signer already voted for the key in the round
+    DuplicateVote,
+    /// `ERR_FAILED_TO_RETRIEVE_SIGNERS`: the signers of the reward cycle could not be read
+    FailedToRetrieveSigners,
+    /// `ERR_INVALID_ROUND`: the round is more than one round past the last round
+    InvalidRound,
+    /// `ERR_ROUND_EXPIRED`: the round no longer accepts votes
+    RoundExpired,
+    /// `ERR_CYCLE_ALREADY_HAS_KEY`: an aggregate public key is already approved for the cycle
+    CycleAlreadyHasKey,
+    /// An error code the contracts do not define
+    Unknown(u128),
+}
+
+impl SignersVotingError {
+    /// The error code returned by the contract
+    pub fn code(&self) -> u128 {
+        match self {
+            Self::CycleNotSet => 2,
+            Self::SignerIndexMismatch => 10,
+            Self::InvalidSignerIndex => 11,
+            Self::OutOfVotingWindow => 12,
+            Self::IllFormedAggregatePublicKey => 13,
+            Self::DuplicateAggregatePublicKey => 14,
+            Self::DuplicateVote => 15,
+            Self::FailedToRetrieveSigners => 16,
+            Self::InvalidRound => 17,
+            Self::RoundExpired => 18,
+            Self::CycleAlreadyHasKey => 19,
+            Self::Unknown(code) => *code,
+        }
+    }
+}
+
+impl From<u128> for SignersVotingError {
+    fn from(code: u128) -> Self {
+        match code {
+            2 => Self::CycleNotSet,
+            10 => Self::SignerIndexMismatch,
+            11 => Self::InvalidSignerIndex,
+            12 => Self::OutOfVotingWindow,
+            13 => Self::IllFormedAggregatePublicKey,
+            14 => Self::DuplicateAggregatePublicKey,
+            15 => Self::DuplicateVote,
+            16 => Self::FailedToRetrieveSigners,
+            17 => Self::InvalidRound,
+            18 => Self::RoundExpired,
+            19 => Self::CycleAlreadyHasKey,
+            code => Self::Unknown(code),
+        }
+    }
+}
+
+impl TryFrom<Value> for SignersVotingError {
+    type Error = VmError;
+
+    /// Read the error of an `(err uint)` contract call result
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        Ok(Self::from(value.expect_result_err()?.expect_u128()?))
+    }
+}
+
+impl From<SignersVotingError> for Value {
+    fn from(error: SignersVotingError) -> Self {
+        Value::err_uint(error.code())
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct PoxStartCycleInfo {
     /// This data contains the set of principals who missed a reward slot
@@ -2040,6 +2127,65 @@ pub mod test {
         make_tx(key, nonce, 1, payload)
     }
 
+    pub fn make_signers_vote_for_aggregate_public_keys(
+        key: &StacksPrivateKey,
+        nonce: u64,
+        signer_index: u128,
+        votes: &[(u128, Point)],
+        cycle: u128,
+    ) -> StacksTransaction {
+        let votes = votes
+            .iter()
+            .map(|(round, aggregate_public_key)| {
+                let aggregate_public_key_val =
+                    Value::buff_from(aggregate_public_key.compress().data.to_vec())
+                        .expect("Failed to serialize aggregate public key");
+                (*round, aggregate_public_key_val)
+            })
+            .collect();
+        make_signers_vote_for_aggregate_public_keys_value(key, nonce, signer_index, votes, cycle)
+    }
+
+    pub fn make_signers_vote_for_aggregate_public_keys_value(
+        key: &StacksPrivateKey,
+        nonce: u64,
+        signer_index: u128,
+        votes: Vec<(u128, Value)>,
+        cycle: u128,
+    ) -> StacksTransaction {
+        debug!(
+            "Vote for aggregate keys in cycle {}, {} rounds",
+            cycle,
+            votes.len()
+        );
+
+        let votes = votes
+            .into_iter()
+            .map(|(round, aggregate_public_key)| {
+                Value::Tuple(
+                    TupleData::from_data(vec![
+                        ("round".into(), Value::UInt(round)),
+                        ("key".into(), aggregate_public_key),
+                    ])
+                    .unwrap(),
+                )
+            })
+            .collect();
+        let payload = TransactionPayload::new_contract_call(
+            boot_code_test_addr(),
+            SIGNERS_VOTING_NAME,
+            SIGNERS_VOTING_BATCH_FUNCTION_NAME,
+            vec![
+                Value::UInt(signer_index),
+                Value::cons_list_unsanitized(votes).unwrap(),
+                Value::UInt(cycle),
+            ],
+        )
+        .unwrap();
+        // TODO set tx_fee back to 0 once these txs are free
+        make_tx(key, nonce, 1, payload)
+    }
+
     pub fn get_approved_aggregate_key(
         peer: &mut TestPeer<'_>,
         latest_block_id: StacksBlockId,
@@ -2061,6 +2207,85 @@ pub mod test {
         })
     }
 
+    /// Get the total weight voted in the given round, the aggregate key approved for the
+    /// reward cycle and the weight a key needs to be approved, or None if no vote was cast
+    pub fn get_round_summary(
+        peer: &mut TestPeer<'_>,
+        latest_block_id: StacksBlockId,
+        reward_cycle: u128,
+        round: u128,
+    ) -> Option<(u128, Option<Point>, u128)> {
+        let summary_opt = readonly_call(
+            peer,
+            &latest_block_id,
+            SIGNERS_VOTING_NAME.into(),
+            "get-round-summary".into(),
+            vec![Value::UInt(reward_cycle), Value::UInt(round)],
+        )
+        .expect_optional()
+        .unwrap();
+        summary_opt.map(|summary_value| {
+            let summary = summary_value.expect_tuple().unwrap();
+            let total_weight_voted = summary
+                .get("total-weight-voted")
+                .unwrap()
+                .clone()
+                .expect_u128()
+                .unwrap();
+            let approved_key = summary
+                .get("approved-key")
+                .unwrap()
+                .clone()
+                .expect_optional()
+                .unwrap()
+                .map(|key_value| {
+                    let data = key_value.expect_buff(33).unwrap();
+                    let compressed_data = Compressed::try_from(data.as_slice()).unwrap();
+                    Point::try_from(&compressed_data).unwrap()
+                });
+            let votes_needed = summary
+                .get("votes-needed")
+                .unwrap()
+                .clone()
+                .expect_u128()
+                .unwrap();
+            (total_weight_voted, approved_key, votes_needed)
+        })
+    }
+
+    /// The fields of an "approved-aggregate-public-key" event of the signers voting contract
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct AggregateKeyApproval {
+        pub key: Value,
+        pub reward_cycle: u128,
+        pub round: u128,
+        pub total_weight: u128,
+        pub threshold_weight: u128,
+    }
+
+    /// Decode the event if it is an "approved-aggregate-public-key" event of the signers
+    /// voting contract, or return None for any other event
+    pub fn decode_aggregate_key_approval(
+        event: &StacksTransactionEvent,
+    ) -> Option<AggregateKeyApproval> {
+        let StacksTransactionEvent::SmartContractEvent(contract_event) = event else {
+            return None;
+        };
+        let event_data = contract_event.value.clone().expect_tuple().ok()?;
+        let event_name = event_data.get("event").ok()?.clone().expect_ascii().ok()?;
+        if event_name != "approved-aggregate-public-key" {
+            return None;
+        }
+        let get_uint = |name: &str| event_data.get(name).unwrap().clone().expect_u128().unwrap();
+        Some(AggregateKeyApproval {
+            key: event_data.get("key").unwrap().clone(),
+            reward_cycle: get_uint("reward-cycle"),
+            round: get_uint("round"),
+            total_weight: get_uint("total-weight"),
+            threshold_weight: get_uint("threshold-weight"),
+        })
+    }
+
     pub fn make_pox_2_increase(
         key: &StacksPrivateKey,
         nonce: u64,
@@ -233,10 +233,17 @@ fn signers_get_signer_keys_from_stackerdb() {
     let stacker_1 = TestStacker::from_seed(&[3, 4]);
     let stacker_2 = TestStacker::from_seed(&[5, 6]);
 
-    let (mut peer, test_signers, latest_block_id, _) = prepare_signers_test(
+    let SignersTestSetup {
+        mut peer,
+        test_signers,
+        latest_block_id,
+        ..
+    } = prepare_signers_test(
         function_name!(),
-        vec![],
-        &[stacker_1.clone(), stacker_2.clone()],
+        vec![
+            (stacker_1.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (stacker_2.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+        ],
         None,
     );
 
@@ -285,10 +292,17 @@ fn signers_db_get_slots() {
     let stacker_1 = TestStacker::from_seed(&[3, 4]);
     let stacker_2 = TestStacker::from_seed(&[5, 6]);
 
-    let (mut peer, test_signers, latest_block_id, _) = prepare_signers_test(
+    let SignersTestSetup {
+        mut peer,
+        test_signers,
+        latest_block_id,
+        ..
+    } = prepare_signers_test(
         function_name!(),
-        vec![],
-        &[stacker_1.clone(), stacker_2.clone()],
+        vec![
+            (stacker_1.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (stacker_2.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+        ],
         None,
     );
 
@@ -343,19 +357,118 @@ fn signers_db_get_slots() {
     }
 }
 
+/// Five stackers stack uneven amounts. With only 4 reward slots, the two largest stakes
+/// become signers with weights 2 and 1, while the three smallest fall below the
+/// stacking threshold and are left out of the signer set.
+#[test]
+fn signers_with_uneven_weights() {
+    let amounts = [8, 4, 2, 1, 1].map(|units| units * TestStacker::DEFAULT_STACKER_AMOUNT);
+    let stackers = amounts
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| {
+            let seed = 3 + 2 * i as u8;
+            (TestStacker::from_seed(&[seed, seed + 1]), *amount)
+        })
+        .collect();
+
+    let SignersTestSetup {
+        mut peer,
+        latest_block_id,
+        current_reward_cycle,
+        stackers,
+        ..
+    } = prepare_signers_test(function_name!(), stackers, None);
+
+    let weights: Vec<_> = stackers.iter().map(|stacker| stacker.weight).collect();
+    assert_eq!(weights, vec![2, 1, 0, 0, 0]);
+
+    for SignersTestStacker {
+        stacker,
+        signer_index,
+        ..
+    } in stackers.iter()
+    {
+        let signer_address = key_to_stacks_addr(&stacker.signer_private_key);
+        assert_eq!(
+            find_signer_index(
+                &mut peer,
+                latest_block_id,
+                signer_address,
+                current_reward_cycle
+            ),
+            *signer_index
+        );
+        if let Some(signer_index) = signer_index {
+            assert_eq!(
+                get_signer_index(
+                    &mut peer,
+                    latest_block_id,
+                    signer_address,
+                    current_reward_cycle
+                ),
+                *signer_index
+            );
+        }
+    }
+
+    let mut signer_indices: Vec<_> = stackers
+        .iter()
+        .filter_map(|stacker| stacker.signer_index)
+        .collect();
+    signer_indices.sort();
+    assert_eq!(signer_indices, vec![0, 1]);
+}
+
+/// A stacker of a signers test, along with its place in the signer set voting for the next
+/// reward cycle
+#[derive(Debug, Clone)]
+pub struct SignersTestStacker {
+    pub stacker: TestStacker,
+    /// The signer index of the stacker's signing key, or None if its stake fell below the
+    /// stacking threshold
+    pub signer_index: Option<u128>,
+    /// The weight of the stacker's signing key, or 0 if it is not a signer
+    pub weight: u128,
+}
+
+/// The state of a peer booted into Nakamoto by `prepare_signers_test`
+pub struct SignersTestSetup<'a> {
+    pub peer: TestPeer<'a>,
+    pub test_signers: TestSigners,
+    pub latest_block_id: StacksBlockId,
+    pub current_reward_cycle: u128,
+    /// The stackers, in the order they were given
+    pub stackers: Vec<SignersTestStacker>,
+}
+
+/// Boot a peer into Nakamoto with each stacker stacking the paired amount, and fund each
+/// stacker's signer so that it can vote.
 pub fn prepare_signers_test<'a>(
     test_name: &str,
-    initial_balances: Vec<(PrincipalData, u64)>,
-    stackers: &[TestStacker],
+    stackers: Vec<(TestStacker, u128)>,
     observer: Option<&'a TestEventObserver>,
-) -> (TestPeer<'a>, TestSigners, StacksBlockId, u128) {
+) -> SignersTestSetup<'a> {
+    let stackers: Vec<_> = stackers
+        .into_iter()
+        .map(|(stacker, amount)| TestStacker { amount, ..stacker })
+        .collect();
+    let initial_balances = stackers
+        .iter()
+        .map(|stacker| {
+            (
+                PrincipalData::from(key_to_stacks_addr(&stacker.signer_private_key)),
+                1000,
+            )
+        })
+        .collect();
     let mut test_signers = TestSigners::default();
 
     let mut peer = boot_nakamoto(
         test_name,
         initial_balances,
         &mut test_signers,
-        stackers,
+        &stackers,
         observer,
     );
 
@@ -407,7 +520,39 @@ pub fn prepare_signers_test<'a>(
 
     assert_eq!(last_set_cycle, 7);
 
-    (peer, test_signers, latest_block_id, current_reward_cycle)
+    let stackers = stackers
+        .into_iter()
+        .map(|stacker| {
+            let signer_address = key_to_stacks_addr(&stacker.signer_private_key);
+            let signer_index = find_signer_index(
+                &mut peer,
+                latest_block_id,
+                signer_address,
+                current_reward_cycle,
+            );
+            let weight = signer_index.map_or(0, |signer_index| {
+                get_signer_weight(
+                    &mut peer,
+                    latest_block_id,
+                    signer_index,
+                    current_reward_cycle + 1,
+                )
+            });
+            SignersTestStacker {
+                stacker,
+                signer_index,
+                weight,
+            }
+        })
+        .collect();
+
+    SignersTestSetup {
+        peer,
+        test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        stackers,
+    }
 }
 
 fn advance_blocks(
@@ -512,6 +657,16 @@ pub fn get_signer_index(
     signer_address: StacksAddress,
     cycle_index: u128,
 ) -> u128 {
+    find_signer_index(peer, latest_block_id, signer_address, cycle_index).expect("signer not found")
+}
+
+/// Get the signer index of the given address, or None if it is not in the signer set
+pub fn find_signer_index(
+    peer: &mut TestPeer<'_>,
+    latest_block_id: StacksBlockId,
+    signer_address: StacksAddress,
+    cycle_index: u128,
+) -> Option<u128> {
     let cycle_mod = cycle_index % 2;
     let signers = readonly_call(
         peer,
@@ -539,5 +694,32 @@ pub fn get_signer_index(
                 .unwrap()
                 == signer_address.to_account_principal()
         })
-        .expect("signer not found") as u128
+        .map(|index| index as u128)
+}
+
+pub fn get_signer_weight(
+    peer: &mut TestPeer<'_>,
+    latest_block_id: StacksBlockId,
+    signer_index: u128,
+    reward_cycle: u128,
+) -> u128 {
+    readonly_call(
+        peer,
+        &latest_block_id,
+        SIGNERS_NAME.into(),
+        "get-signer-by-index".into(),
+        vec![Value::UInt(reward_cycle), Value::UInt(signer_index)],
+    )
+    .expect_result_ok()
+    .unwrap()
+    .expect_optional()
+    .unwrap()
+    .expect("Missing signer")
+    .expect_tuple()
+    .unwrap()
+    .get("weight")
+    .unwrap()
+    .clone()
+    .expect_u128()
+    .unwrap()
 }
@@ -15,6 +15,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::thread;
 
 use clarity::boot_util::boot_code_addr;
 use clarity::vm::clarity::ClarityConnection;
@@ -65,11 +66,11 @@ use crate::chainstate::stacks::boot::pox_4_tests::{
     assert_latest_was_burn, get_last_block_sender_transactions, get_tip, make_test_epochs_pox,
 };
 use crate::chainstate::stacks::boot::signers_tests::{
-    get_signer_index, prepare_signers_test, readonly_call,
+    get_signer_index, get_signer_weight, prepare_signers_test, readonly_call, SignersTestSetup,
 };
 use crate::chainstate::stacks::boot::{
-    BOOT_CODE_COST_VOTING_TESTNET as BOOT_CODE_COST_VOTING, BOOT_CODE_POX_TESTNET, SIGNERS_NAME,
-    SIGNERS_VOTING_NAME,
+    SignersVotingError, BOOT_CODE_COST_VOTING_TESTNET as BOOT_CODE_COST_VOTING,
+    BOOT_CODE_POX_TESTNET, SIGNERS_BODY, SIGNERS_NAME, SIGNERS_VOTING_BODY, SIGNERS_VOTING_NAME,
 };
 use crate::chainstate::stacks::db::{
     MinerPaymentSchedule, StacksChainState, StacksHeaderInfo, MINER_REWARD_MATURITY,
@@ -158,13 +159,18 @@ fn vote_for_aggregate_public_key_success() {
     let bob_address = key_to_stacks_addr(bob_key);
     let bob_principal = PrincipalData::from(bob_address);
 
-    let (mut peer, mut test_signers, latest_block_id, current_reward_cycle) = prepare_signers_test(
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
         function_name!(),
         vec![
-            (alice_principal.clone(), 1000),
-            (bob_principal.clone(), 1000),
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
         ],
-        &[alice.clone(), bob.clone()],
         Some(&observer),
     );
 
@@ -237,6 +243,7 @@ fn vote_for_aggregate_public_key_success() {
                 ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
                 ("round".into(), Value::UInt(0)),
                 ("signer".into(), Value::Principal(alice_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -264,6 +271,7 @@ fn vote_for_aggregate_public_key_success() {
                 ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
                 ("round".into(), Value::UInt(0)),
                 ("signer".into(), Value::Principal(bob_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -286,6 +294,8 @@ fn vote_for_aggregate_public_key_success() {
                 ("key".into(), aggregate_public_key.clone()),
                 ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
                 ("round".into(), Value::UInt(0)),
+                ("threshold-weight".into(), Value::UInt(3)),
+                ("total-weight".into(), Value::UInt(4)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -293,6 +303,20 @@ fn vote_for_aggregate_public_key_success() {
     } else {
         panic!("Expected SmartContractEvent, got {:?}", approve_event);
     }
+
+    // Only Bob's vote, which crossed the threshold, approves the key
+    assert_eq!(decode_aggregate_key_approval(alice_vote_event), None);
+    assert_eq!(decode_aggregate_key_approval(bob_vote_event), None);
+    assert_eq!(
+        decode_aggregate_key_approval(approve_event),
+        Some(AggregateKeyApproval {
+            key: aggregate_public_key.clone(),
+            reward_cycle: cycle_id + 1,
+            round: 0,
+            total_weight: 4,
+            threshold_weight: 3,
+        })
+    );
 }
 
 /// In this test case, Alice & Bob both successfully vote for the same key,
@@ -314,13 +338,18 @@ fn vote_for_aggregate_public_key_with_errors() {
     let bob_address = key_to_stacks_addr(bob_key);
     let bob_principal = PrincipalData::from(bob_address);
 
-    let (mut peer, mut test_signers, latest_block_id, current_reward_cycle) = prepare_signers_test(
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
         function_name!(),
         vec![
-            (alice_principal.clone(), 1000),
-            (bob_principal.clone(), 1000),
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
         ],
-        &[alice.clone(), bob.clone()],
         Some(&observer),
     );
 
@@ -433,7 +462,7 @@ fn vote_for_aggregate_public_key_with_errors() {
     let alice_first_vote_tx_result = alice_first_vote_tx.result.clone();
     assert_eq!(
         alice_first_vote_tx_result,
-        Value::err_uint(10) // ERR_SIGNER_INDEX_MISMATCH
+        Value::from(SignersVotingError::SignerIndexMismatch)
     );
     assert_eq!(alice_first_vote_tx.events.len(), 0);
 
@@ -442,7 +471,7 @@ fn vote_for_aggregate_public_key_with_errors() {
     let alice_second_vote_tx_result = alice_second_vote_tx.result.clone();
     assert_eq!(
         alice_second_vote_tx_result,
-        Value::err_uint(11) // ERR_INVALID_SIGNER_INDEX
+        Value::from(SignersVotingError::InvalidSignerIndex)
     );
     assert_eq!(alice_second_vote_tx.events.len(), 0);
 
@@ -451,7 +480,7 @@ fn vote_for_aggregate_public_key_with_errors() {
     let alice_third_vote_tx_result = alice_third_vote_tx.result.clone();
     assert_eq!(
         alice_third_vote_tx_result,
-        Value::err_uint(13) // ERR_ILL_FORMED_AGGREGATE_PUBLIC_KEY
+        Value::from(SignersVotingError::IllFormedAggregatePublicKey)
     );
     assert_eq!(alice_third_vote_tx.events.len(), 0);
 
@@ -460,7 +489,7 @@ fn vote_for_aggregate_public_key_with_errors() {
     let alice_fourth_vote_tx_result = alice_fourth_vote_tx.result.clone();
     assert_eq!(
         alice_fourth_vote_tx_result,
-        Value::err_uint(2) // ERR_CYCLE_NOT_SET
+        Value::from(SignersVotingError::CycleNotSet)
     );
     assert_eq!(alice_fourth_vote_tx.events.len(), 0);
 
@@ -483,6 +512,7 @@ fn vote_for_aggregate_public_key_with_errors() {
                 ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
                 ("round".into(), Value::UInt(0)),
                 ("signer".into(), Value::Principal(alice_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -496,7 +526,7 @@ fn vote_for_aggregate_public_key_with_errors() {
     let alice_sixth_vote_tx_result = alice_sixth_vote_tx.result.clone();
     assert_eq!(
         alice_sixth_vote_tx_result,
-        Value::err_uint(15) // ERR_DUPLICATE_VOTE
+        Value::from(SignersVotingError::DuplicateVote)
     );
     assert_eq!(alice_sixth_vote_tx.events.len(), 0);
 
@@ -505,7 +535,7 @@ fn vote_for_aggregate_public_key_with_errors() {
     let bob_first_vote_tx_result = bob_first_vote_tx.result.clone();
     assert_eq!(
         bob_first_vote_tx_result,
-        Value::err_uint(17) // ERR_INVALID_ROUND
+        Value::from(SignersVotingError::InvalidRound)
     );
     assert_eq!(bob_first_vote_tx.events.len(), 0);
 
@@ -528,6 +558,7 @@ fn vote_for_aggregate_public_key_with_errors() {
                 ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
                 ("round".into(), Value::UInt(0)),
                 ("signer".into(), Value::Principal(bob_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -550,6 +581,8 @@ fn vote_for_aggregate_public_key_with_errors() {
                 ("key".into(), aggregate_public_key.clone()),
                 ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
                 ("round".into(), Value::UInt(0)),
+                ("threshold-weight".into(), Value::UInt(3)),
+                ("total-weight".into(), Value::UInt(4)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -560,7 +593,7 @@ fn vote_for_aggregate_public_key_with_errors() {
 }
 
 /// 4 stackers vote for the same aggregate public key. The threshold is reached
-/// after the 3rd vote, so the 4th gets an "out of voting window" error.
+/// after the 3rd vote, so the 4th gets a "cycle already has key" error.
 #[test]
 fn vote_for_aggregate_public_key_out_of_window() {
     // Test setup
@@ -588,21 +621,20 @@ fn vote_for_aggregate_public_key_out_of_window() {
     // Signer 4
     let stacker4_key = &stacker4.signer_private_key;
     let stacker4_address = key_to_stacks_addr(stacker4_key);
-    let stacker4_principal = PrincipalData::from(stacker4_address);
 
-    let (mut peer, mut test_signers, latest_block_id, current_reward_cycle) = prepare_signers_test(
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
         function_name!(),
         vec![
-            (stacker1_principal.clone(), 1000),
-            (stacker2_principal.clone(), 1000),
-            (stacker3_principal.clone(), 1000),
-            (stacker4_principal.clone(), 1000),
-        ],
-        &[
-            stacker1.clone(),
-            stacker2.clone(),
-            stacker3.clone(),
-            stacker4.clone(),
+            (stacker1.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (stacker2.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (stacker3.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (stacker4.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
         ],
         Some(&observer),
     );
@@ -654,7 +686,7 @@ fn vote_for_aggregate_public_key_out_of_window() {
             0,
             cycle_id + 1,
         ),
-        // stacker4 casts vote correctly, but it will return an out of voting window error
+        // stacker4 casts vote correctly, but it will return a cycle already has key error
         make_signers_vote_for_aggregate_public_key_value(
             stacker4_key,
             stacker4_nonce,
@@ -699,6 +731,7 @@ fn vote_for_aggregate_public_key_out_of_window() {
                     "signer".into(),
                     Value::Principal(stacker1_principal.clone())
                 ),
+                ("signer-weight".into(), Value::UInt(1)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -729,6 +762,7 @@ fn vote_for_aggregate_public_key_out_of_window() {
                     "signer".into(),
                     Value::Principal(stacker2_principal.clone())
                 ),
+                ("signer-weight".into(), Value::UInt(1)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -759,6 +793,7 @@ fn vote_for_aggregate_public_key_out_of_window() {
                     "signer".into(),
                     Value::Principal(stacker3_principal.clone())
                 ),
+                ("signer-weight".into(), Value::UInt(1)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -781,6 +816,8 @@ fn vote_for_aggregate_public_key_out_of_window() {
                 ("key".into(), aggregate_public_key.clone()),
                 ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
                 ("round".into(), Value::UInt(0)),
+                ("threshold-weight".into(), Value::UInt(3)),
+                ("total-weight".into(), Value::UInt(3)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -789,11 +826,11 @@ fn vote_for_aggregate_public_key_out_of_window() {
         panic!("Expected SmartContractEvent, got {:?}", approve_event);
     }
 
-    // stacker4's vote should get an out of voting window error
+    // stacker4's vote should get a cycle already has key error
     let stacker4_vote_tx = &receipts[5];
     assert_eq!(
         stacker4_vote_tx.result,
-        Value::err_uint(12) // ERR_OUT_OF_VOTING_WINDOW
+        Value::from(SignersVotingError::CycleAlreadyHasKey)
     );
     assert_eq!(stacker4_vote_tx.events.len(), 0);
 }
@@ -809,10 +846,18 @@ fn vote_for_aggregate_public_key_in_first_block() {
 
     let signer = key_to_stacks_addr(&stacker_1.signer_private_key).to_account_principal();
 
-    let (mut peer, mut test_signers, latest_block_id, current_reward_cycle) = prepare_signers_test(
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
         function_name!(),
-        vec![(signer, 1000)],
-        &[stacker_1.clone(), stacker_2.clone()],
+        vec![
+            (stacker_1.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (stacker_2.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+        ],
         Some(&observer),
     );
 
@@ -869,7 +914,7 @@ fn vote_for_aggregate_public_key_in_first_block() {
     let alice_second_vote_tx = &receipts[3];
     assert_eq!(
         alice_second_vote_tx.result,
-        Value::err_uint(15) // ERR_DUPLICATE_VOTE
+        Value::from(SignersVotingError::DuplicateVote)
     );
     assert_eq!(alice_second_vote_tx.events.len(), 0);
 }
@@ -883,13 +928,18 @@ fn vote_for_aggregate_public_key_in_last_block() {
     let stacker_2 = TestStacker::from_seed(&[5, 6]);
     let observer = TestEventObserver::new();
 
-    let signer_1 = key_to_stacks_addr(&stacker_1.signer_private_key).to_account_principal();
-    let signer_2 = key_to_stacks_addr(&stacker_2.signer_private_key).to_account_principal();
-
-    let (mut peer, mut test_signers, latest_block_id, current_reward_cycle) = prepare_signers_test(
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
         function_name!(),
-        vec![(signer_1, 1000), (signer_2, 1000)],
-        &[stacker_1.clone(), stacker_2.clone()],
+        vec![
+            (stacker_1.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (stacker_2.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+        ],
         Some(&observer),
     );
 
@@ -998,7 +1048,7 @@ fn vote_for_aggregate_public_key_in_last_block() {
     let alice_second_vote_tx = &receipts[3];
     assert_eq!(
         alice_second_vote_tx.result,
-        Value::err_uint(15) // ERR_DUPLICATE_VOTE
+        Value::from(SignersVotingError::DuplicateVote)
     );
     assert_eq!(alice_second_vote_tx.events.len(), 0);
 
@@ -1016,6 +1066,114 @@ fn vote_for_aggregate_public_key_in_last_block() {
     assert_eq!(tx1_bob.result, Value::okay_true());
 }
 
+/// In this test case, Alice's stacker moves its unlocked STX in the first half
+/// of the prepare phase, and Alice votes in the second half.
+/// PoX-4 only unlocks stacked STX at the end of their lock period, so the
+/// signer set calculated at the start of the prepare phase cannot change
+/// mid-cycle: `get-signers` and `get-signer-by-index` must return the same
+/// set, and Alice's vote must be counted with the weight it records.
+#[test]
+fn vote_with_changing_signer_set_mid_cycle() {
+    let alice = TestStacker::from_seed(&[3, 4]);
+    let bob = TestStacker::from_seed(&[5, 6]);
+    let observer = TestEventObserver::new();
+
+    // Alice - Signer 1
+    let alice_key = &alice.signer_private_key;
+    let alice_address = key_to_stacks_addr(alice_key);
+    let alice_principal = PrincipalData::from(alice_address);
+
+    // Bob - Signer 2
+    let bob_address = key_to_stacks_addr(&bob.signer_private_key);
+
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
+        function_name!(),
+        vec![
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+        ],
+        Some(&observer),
+    );
+    let cycle_id = current_reward_cycle;
+    let alice_index = get_signer_index(&mut peer, latest_block_id, alice_address, cycle_id);
+    let signers_at_start = get_signers(&mut peer, latest_block_id, cycle_id + 1);
+    assert!(signers_at_start.is_some());
+
+    //
+    // Alice's stacker moves its unlocked STX in the first half of the prepare phase
+    //
+    let mut alice_stacker_nonce = 1; // Start at 1 because the stacker has already stacked
+    let transfer_tx = make_dummy_tx(
+        &mut peer,
+        &alice.stacker_private_key,
+        &mut alice_stacker_nonce,
+    );
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![vec![transfer_tx]]);
+    let mid_cycle_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
+    // The signer set is unchanged
+    assert_eq!(
+        get_signers(&mut peer, mid_cycle_block_id, cycle_id + 1),
+        signers_at_start
+    );
+    let alice_entry = get_signer_by_index(&mut peer, mid_cycle_block_id, cycle_id + 1, alice_index)
+        .expect("Alice should still be a signer")
+        .expect_tuple()
+        .unwrap();
+    assert_eq!(
+        alice_entry.get("signer").unwrap(),
+        &Value::Principal(alice_principal.clone())
+    );
+    let alice_weight = alice_entry.get("weight").unwrap().clone();
+
+    //
+    // Alice votes in the second half of the prepare phase
+    //
+    let mut signers = TestSigners::default();
+    let aggregate_key = signers.generate_aggregate_key(cycle_id as u64 + 1);
+    let aggregate_public_key = Value::buff_from(aggregate_key.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
+    let txs = vec![make_signers_vote_for_aggregate_public_key_value(
+        alice_key,
+        1, // Start at 1 because the signer has already voted once
+        alice_index,
+        aggregate_public_key.clone(),
+        0,
+        cycle_id + 1,
+    )];
+    nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 3);
+    // ignore tenure change tx
+    // ignore tenure coinbase tx
+
+    // Alice's vote should succeed with the weight of the signer set
+    let alice_vote_tx = &receipts[2];
+    assert_eq!(alice_vote_tx.result, Value::okay_true());
+    assert_eq!(alice_vote_tx.events.len(), 1);
+    let StacksTransactionEvent::SmartContractEvent(contract_event) = &alice_vote_tx.events[0]
+    else {
+        panic!(
+            "Expected SmartContractEvent, got {:?}",
+            alice_vote_tx.events[0]
+        );
+    };
+    let vote_event = contract_event.value.clone().expect_tuple().unwrap();
+    assert_eq!(vote_event.get("new-total").unwrap(), &alice_weight);
+    assert_eq!(
+        vote_event.get("signer").unwrap(),
+        &Value::Principal(alice_principal)
+    );
+}
+
 /// In this test case, Alice & Bob both successfully vote in cycle N, then
 /// Alice tries to vote for the same signature in cycle N+1, but fails with
 /// "duplicate aggregate public key" error.
@@ -1029,20 +1187,23 @@ fn vote_for_duplicate_aggregate_public_key() {
     // Alice - Signer 1
     let alice_key = &alice.signer_private_key;
     let alice_address = key_to_stacks_addr(alice_key);
-    let alice_principal = PrincipalData::from(alice_address);
 
     // Bob - Signer 2
     let bob_key = &bob.signer_private_key;
     let bob_address = key_to_stacks_addr(bob_key);
-    let bob_principal = PrincipalData::from(bob_address);
 
-    let (mut peer, mut test_signers, latest_block_id, current_reward_cycle) = prepare_signers_test(
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
         function_name!(),
         vec![
-            (alice_principal.clone(), 1000),
-            (bob_principal.clone(), 1000),
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
         ],
-        &[alice.clone(), bob.clone()],
         Some(&observer),
     );
 
@@ -1152,7 +1313,7 @@ fn vote_for_duplicate_aggregate_public_key() {
     let alice_vote_tx = &receipts[2];
     assert_eq!(
         alice_vote_tx.result,
-        Value::err_uint(14) // ERR_DUPLICATE_AGGREGATE_PUBLIC_KEY
+        Value::from(SignersVotingError::DuplicateAggregatePublicKey)
     );
     assert_eq!(alice_vote_tx.events.len(), 0);
 
@@ -1186,13 +1347,18 @@ fn vote_for_aggregate_public_key_two_rounds() {
     let bob_address = key_to_stacks_addr(bob_key);
     let bob_principal = PrincipalData::from(bob_address);
 
-    let (mut peer, mut test_signers, latest_block_id, current_reward_cycle) = prepare_signers_test(
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
         function_name!(),
         vec![
-            (alice_principal.clone(), 1000),
-            (bob_principal.clone(), 1000),
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
         ],
-        &[alice.clone(), bob.clone()],
         Some(&observer),
     );
 
@@ -1284,6 +1450,7 @@ fn vote_for_aggregate_public_key_two_rounds() {
                 ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
                 ("round".into(), Value::UInt(0)),
                 ("signer".into(), Value::Principal(alice_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -1310,6 +1477,7 @@ fn vote_for_aggregate_public_key_two_rounds() {
                 ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
                 ("round".into(), Value::UInt(0)),
                 ("signer".into(), Value::Principal(bob_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -1336,6 +1504,7 @@ fn vote_for_aggregate_public_key_two_rounds() {
                 ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
                 ("round".into(), Value::UInt(1)),
                 ("signer".into(), Value::Principal(alice_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -1362,6 +1531,7 @@ fn vote_for_aggregate_public_key_two_rounds() {
                 ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
                 ("round".into(), Value::UInt(1)),
                 ("signer".into(), Value::Principal(bob_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -1386,6 +1556,8 @@ fn vote_for_aggregate_public_key_two_rounds() {
                 ("key".into(), aggregate_public_key_1.clone()),
                 ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
                 ("round".into(), Value::UInt(1)),
+                ("threshold-weight".into(), Value::UInt(3)),
+                ("total-weight".into(), Value::UInt(4)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -1461,6 +1633,7 @@ fn vote_for_aggregate_public_key_two_rounds() {
                 ("reward-cycle".into(), Value::UInt(cycle_id + 2)),
                 ("round".into(), Value::UInt(0)),
                 ("signer".into(), Value::Principal(alice_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -1487,6 +1660,7 @@ fn vote_for_aggregate_public_key_two_rounds() {
                 ("reward-cycle".into(), Value::UInt(cycle_id + 2)),
                 ("round".into(), Value::UInt(0)),
                 ("signer".into(), Value::Principal(bob_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -1544,6 +1718,7 @@ fn vote_for_aggregate_public_key_two_rounds() {
                 ("reward-cycle".into(), Value::UInt(cycle_id + 2)),
                 ("round".into(), Value::UInt(1)),
                 ("signer".into(), Value::Principal(alice_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -1570,6 +1745,7 @@ fn vote_for_aggregate_public_key_two_rounds() {
                 ("reward-cycle".into(), Value::UInt(cycle_id + 2)),
                 ("round".into(), Value::UInt(1)),
                 ("signer".into(), Value::Principal(bob_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -1594,6 +1770,8 @@ fn vote_for_aggregate_public_key_two_rounds() {
                 ("key".into(), aggregate_public_key_1.clone()),
                 ("reward-cycle".into(), Value::UInt(cycle_id + 2)),
                 ("round".into(), Value::UInt(1)),
+                ("threshold-weight".into(), Value::UInt(3)),
+                ("total-weight".into(), Value::UInt(4)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -1603,11 +1781,12 @@ fn vote_for_aggregate_public_key_two_rounds() {
     }
 }
 
-/// In this test case, Alice & Bob both successfully vote for the same key in
-/// cycle N, then in cycle N + 1, Alice tries to vote before the prepare phase,
-/// but fails with a "cycle not set" error.
+/// In this test case, DKG fails in round 0 because only Alice votes, short of
+/// the threshold. Alice & Bob then both vote for the same key in round 1,
+/// which approves it. Alice's round 0 vote must not count toward round 1, and
+/// the contract must report round 1 as the last round.
 #[test]
-fn vote_for_aggregate_public_key_early() {
+fn vote_for_aggregate_public_key_with_multiple_rounds() {
     // Test setup
     let alice = TestStacker::from_seed(&[3, 4]);
     let bob = TestStacker::from_seed(&[5, 6]);
@@ -1623,13 +1802,18 @@ fn vote_for_aggregate_public_key_early() {
     let bob_address = key_to_stacks_addr(bob_key);
     let bob_principal = PrincipalData::from(bob_address);
 
-    let (mut peer, mut test_signers, latest_block_id, current_reward_cycle) = prepare_signers_test(
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
         function_name!(),
         vec![
-            (alice_principal.clone(), 1000),
-            (bob_principal.clone(), 1000),
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
         ],
-        &[alice.clone(), bob.clone()],
         Some(&observer),
     );
 
@@ -1648,113 +1832,79 @@ fn vote_for_aggregate_public_key_early() {
     let aggregate_public_key = Value::buff_from(aggregate_key.compress().data.to_vec())
         .expect("Failed to serialize aggregate public key");
 
-    let aggregate_public_key_ill_formed = Value::buff_from_byte(0x00);
-
-    let txs = vec![
-        // Alice casts vote correctly
-        make_signers_vote_for_aggregate_public_key_value(
-            alice_key,
-            alice_nonce,
-            alice_index,
-            aggregate_public_key.clone(),
-            0,
-            cycle_id + 1,
-        ),
-        // Bob casts a vote correctly
-        make_signers_vote_for_aggregate_public_key_value(
-            bob_key,
-            bob_nonce,
-            bob_index,
-            aggregate_public_key.clone(),
-            0,
-            cycle_id + 1,
-        ),
-    ];
-    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
-    let block = observer.get_blocks().last().unwrap().clone();
-    let receipts = block.receipts.as_slice();
-    assert_eq!(receipts.len(), 4);
-
-    // Both votes should succeed
-    let alice_vote_tx = &receipts[2];
-    assert_eq!(alice_vote_tx.result, Value::okay_true());
-    assert_eq!(alice_vote_tx.events.len(), 1);
-    let bob_vote_tx = &receipts[3];
-    assert_eq!(bob_vote_tx.result, Value::okay_true());
-    assert_eq!(bob_vote_tx.events.len(), 2);
-
-    // Proceed to the reward phase
-    let _ = nakamoto_tenure(&mut peer, &mut test_signers, Vec::new());
-    let _ = nakamoto_tenure(&mut peer, &mut test_signers, Vec::new());
-
-    // In this tenure, signers have not been set yet, so the vote should fail
-    let aggregate_public_key_point = signers.generate_aggregate_key(cycle_id as u64 + 2);
-    let aggregate_public_key =
-        Value::buff_from(aggregate_public_key_point.compress().data.to_vec())
-            .expect("Failed to serialize aggregate public key");
-
-    let txs = vec![
-        // Alice casts vote for key 0 in round 0
-        make_signers_vote_for_aggregate_public_key_value(
-            alice_key,
-            alice_nonce + 1,
-            alice_index,
-            aggregate_public_key.clone(),
-            0,
-            cycle_id + 2,
-        ),
-    ];
-
-    // vote before the prepare phase
+    // Round 0: only Alice votes, which is not enough to reach the threshold
+    let txs = vec![make_signers_vote_for_aggregate_public_key_value(
+        alice_key,
+        alice_nonce,
+        alice_index,
+        aggregate_public_key.clone(),
+        0,
+        cycle_id + 1,
+    )];
     let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
 
-    // check the last two txs in the last block
     let block = observer.get_blocks().last().unwrap().clone();
     let receipts = block.receipts.as_slice();
     assert_eq!(receipts.len(), 3);
     // ignore tenure change tx
     // ignore tenure coinbase tx
-
-    // Alice's vote should fail with a "cycle not set" error
     let alice_vote_tx = &receipts[2];
+    assert_eq!(alice_vote_tx.result, Value::okay_true());
+    // The vote is recorded, but the key is not approved
+    assert_eq!(alice_vote_tx.events.len(), 1);
+
+    assert!(get_threshold_weight(&mut peer, tip_block_id, cycle_id + 1) > 2);
     assert_eq!(
-        alice_vote_tx.result,
-        Value::err_uint(2) // ERR_CYCLE_NOT_SET
+        get_last_round(&mut peer, tip_block_id, cycle_id + 1),
+        Some(0)
     );
-    assert_eq!(alice_vote_tx.events.len(), 0);
-
-    // Proceed to the prepare phase
-    let _ = nakamoto_tenure(&mut peer, &mut test_signers, Vec::new());
-    let _ = nakamoto_tenure(&mut peer, &mut test_signers, Vec::new());
+    let round_info = get_round_info(&mut peer, tip_block_id, cycle_id + 1, 0)
+        .unwrap()
+        .expect_tuple()
+        .unwrap();
+    assert_eq!(round_info.get("votes-count").unwrap(), &Value::UInt(1));
+    assert_eq!(round_info.get("votes-weight").unwrap(), &Value::UInt(2));
+    let approved_key = readonly_call(
+        &mut peer,
+        &tip_block_id,
+        SIGNERS_VOTING_NAME.into(),
+        "get-approved-aggregate-key".into(),
+        vec![Value::UInt(cycle_id + 1)],
+    )
+    .expect_optional()
+    .unwrap();
+    assert!(approved_key.is_none());
 
+    // Round 1: DKG is retried, and both Alice & Bob vote for the key
     let txs = vec![
-        // Alice casts vote correctly
         make_signers_vote_for_aggregate_public_key_value(
             alice_key,
-            alice_nonce + 2,
+            alice_nonce + 1,
             alice_index,
             aggregate_public_key.clone(),
-            0,
-            cycle_id + 2,
+            1,
+            cycle_id + 1,
         ),
-        // Bob casts a vote correctly
         make_signers_vote_for_aggregate_public_key_value(
             bob_key,
-            bob_nonce + 1,
+            bob_nonce,
             bob_index,
             aggregate_public_key.clone(),
-            0,
-            cycle_id + 2,
+            1,
+            cycle_id + 1,
         ),
     ];
     let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
     let block = observer.get_blocks().last().unwrap().clone();
     let receipts = block.receipts.as_slice();
     assert_eq!(receipts.len(), 4);
     // ignore tenure change tx
     // ignore tenure coinbase tx
 
-    // This time, the votes should succeed and the key should be approved
+    // Alice's round 0 vote does not count toward round 1
     let alice_vote_tx = &receipts[2];
     assert_eq!(alice_vote_tx.result, Value::okay_true());
     assert_eq!(alice_vote_tx.events.len(), 1);
@@ -1770,9 +1920,10 @@ fn vote_for_aggregate_public_key_early() {
                 ),
                 ("key".into(), aggregate_public_key.clone()),
                 ("new-total".into(), Value::UInt(2)),
-                ("reward-cycle".into(), Value::UInt(cycle_id + 2)),
-                ("round".into(), Value::UInt(0)),
+                ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
+                ("round".into(), Value::UInt(1)),
                 ("signer".into(), Value::Principal(alice_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -1796,9 +1947,10 @@ fn vote_for_aggregate_public_key_early() {
                 ),
                 ("key".into(), aggregate_public_key.clone()),
                 ("new-total".into(), Value::UInt(4)),
-                ("reward-cycle".into(), Value::UInt(cycle_id + 2)),
-                ("round".into(), Value::UInt(0)),
+                ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
+                ("round".into(), Value::UInt(1)),
                 ("signer".into(), Value::Principal(bob_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -1821,8 +1973,10 @@ fn vote_for_aggregate_public_key_early() {
                     .expect("Failed to create string")
                 ),
                 ("key".into(), aggregate_public_key.clone()),
-                ("reward-cycle".into(), Value::UInt(cycle_id + 2)),
-                ("round".into(), Value::UInt(0)),
+                ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
+                ("round".into(), Value::UInt(1)),
+                ("threshold-weight".into(), Value::UInt(3)),
+                ("total-weight".into(), Value::UInt(4)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -1830,13 +1984,58 @@ fn vote_for_aggregate_public_key_early() {
     } else {
         panic!("Expected SmartContractEvent, got {:?}", approve_event);
     }
+
+    // The contract tracks each round separately, and round 1 is the last one
+    assert_eq!(
+        get_last_round(&mut peer, tip_block_id, cycle_id + 1),
+        Some(1)
+    );
+    for (round, votes_count, votes_weight) in [(0, 1, 2), (1, 2, 4)] {
+        let round_info = get_round_info(&mut peer, tip_block_id, cycle_id + 1, round)
+            .unwrap()
+            .expect_tuple()
+            .unwrap();
+        assert_eq!(
+            round_info.get("votes-count").unwrap(),
+            &Value::UInt(votes_count)
+        );
+        assert_eq!(
+            round_info.get("votes-weight").unwrap(),
+            &Value::UInt(votes_weight)
+        );
+        let tally = readonly_call(
+            &mut peer,
+            &tip_block_id,
+            SIGNERS_VOTING_NAME.into(),
+            "get-tally".into(),
+            vec![
+                Value::UInt(cycle_id + 1),
+                Value::UInt(round),
+                aggregate_public_key.clone(),
+            ],
+        )
+        .expect_optional()
+        .unwrap();
+        assert_eq!(tally, Some(Value::UInt(votes_weight)));
+    }
+    let approved_key = readonly_call(
+        &mut peer,
+        &tip_block_id,
+        SIGNERS_VOTING_NAME.into(),
+        "get-approved-aggregate-key".into(),
+        vec![Value::UInt(cycle_id + 1)],
+    )
+    .expect_optional()
+    .unwrap();
+    assert_eq!(approved_key, Some(aggregate_public_key));
 }
 
-/// In this test case, Alice votes in round 0 and Bob votes in round 1.
-/// Although they both voted for the same key, the key is not approved. In the
-/// next tenure, Bob votes in round 0, and the key is approved.
+/// In this test case, Alice votes for a key in round 0, then changes her vote
+/// to another key in the same round, which moves her weight to the new key.
+/// The key she voted for first stays reserved for the reward cycle. Bob then votes for the new key, approving it, after which Alice can no
+/// longer change her vote.
 #[test]
-fn vote_for_aggregate_public_key_mixed_rounds() {
+fn vote_for_aggregate_public_key_changed_before_approval() {
     // Test setup
     let alice = TestStacker::from_seed(&[3, 4]);
     let bob = TestStacker::from_seed(&[5, 6]);
@@ -1850,15 +2049,19 @@ fn vote_for_aggregate_public_key_mixed_rounds() {
     // Bob - Signer 2
     let bob_key = &bob.signer_private_key;
     let bob_address = key_to_stacks_addr(bob_key);
-    let bob_principal = PrincipalData::from(bob_address);
 
-    let (mut peer, mut test_signers, latest_block_id, current_reward_cycle) = prepare_signers_test(
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
         function_name!(),
         vec![
-            (alice_principal.clone(), 1000),
-            (bob_principal.clone(), 1000),
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
         ],
-        &[alice.clone(), bob.clone()],
         Some(&observer),
     );
 
@@ -1868,132 +2071,357 @@ fn vote_for_aggregate_public_key_mixed_rounds() {
 
     let cycle_id = current_reward_cycle;
 
-    // create vote txs
     let alice_index = get_signer_index(&mut peer, latest_block_id, alice_address, cycle_id);
     let bob_index = get_signer_index(&mut peer, latest_block_id, bob_address, cycle_id);
 
     let mut signers = TestSigners::default();
-    let aggregate_public_key_point = signers.generate_aggregate_key(0);
-    let aggregate_public_key =
-        Value::buff_from(aggregate_public_key_point.compress().data.to_vec())
-            .expect("Failed to serialize aggregate public key");
+    let first_key = signers.generate_aggregate_key(cycle_id as u64 + 1);
+    let first_key = Value::buff_from(first_key.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
+    let second_key = signers.generate_aggregate_key(cycle_id as u64 + 2);
+    let second_key = Value::buff_from(second_key.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
 
+    // Alice votes for the first key, then changes her vote to the second key
     let txs = vec![
-        // Alice casts vote for key 0 in round 0
         make_signers_vote_for_aggregate_public_key_value(
             alice_key,
             alice_nonce,
             alice_index,
-            aggregate_public_key.clone(),
+            first_key.clone(),
             0,
             cycle_id + 1,
         ),
-        // Bob casts a vote for key 0 in round 1
         make_signers_vote_for_aggregate_public_key_value(
-            bob_key,
-            bob_nonce,
-            bob_index,
-            aggregate_public_key.clone(),
-            1,
+            alice_key,
+            alice_nonce + 1,
+            alice_index,
+            second_key.clone(),
+            0,
             cycle_id + 1,
         ),
     ];
-
-    // vote in the first burn block of prepare phase
     let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
 
-    // check the last four txs in the last block
     let block = observer.get_blocks().last().unwrap().clone();
     let receipts = block.receipts.as_slice();
     assert_eq!(receipts.len(), 4);
     // ignore tenure change tx
     // ignore tenure coinbase tx
+    assert_eq!(receipts[2].result, Value::okay_true());
 
-    // All votes should succeed
-    let alice_vote_tx = &receipts[2];
-    assert_eq!(alice_vote_tx.result, Value::okay_true());
-    assert_eq!(alice_vote_tx.events.len(), 1);
-    let alice_vote_event = &alice_vote_tx.events[0];
-    if let StacksTransactionEvent::SmartContractEvent(contract_event) = alice_vote_event {
+    let alice_changed_vote_tx = &receipts[3];
+    assert_eq!(alice_changed_vote_tx.result, Value::okay_true());
+    assert_eq!(alice_changed_vote_tx.events.len(), 1);
+    let alice_changed_vote_event = &alice_changed_vote_tx.events[0];
+    if let StacksTransactionEvent::SmartContractEvent(contract_event) = alice_changed_vote_event {
         assert_eq!(
             contract_event.value,
             TupleData::from_data(vec![
                 (
                     "event".into(),
-                    Value::string_ascii_from_bytes("voted".as_bytes().to_vec())
+                    Value::string_ascii_from_bytes("vote-changed".as_bytes().to_vec())
                         .expect("Failed to create string")
                 ),
-                ("key".into(), aggregate_public_key.clone()),
+                ("key".into(), second_key.clone()),
                 ("new-total".into(), Value::UInt(2)),
+                ("old-key".into(), first_key.clone()),
                 ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
                 ("round".into(), Value::UInt(0)),
                 ("signer".into(), Value::Principal(alice_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
         );
     } else {
-        panic!("Expected SmartContractEvent, got {:?}", alice_vote_event);
+        panic!(
+            "Expected SmartContractEvent, got {:?}",
+            alice_changed_vote_event
+        );
     }
 
-    let bob_vote_tx = &receipts[3];
-    assert_eq!(bob_vote_tx.result, Value::okay_true());
-    assert_eq!(bob_vote_tx.events.len(), 1);
-    let bob_vote_event = &bob_vote_tx.events[0];
-    if let StacksTransactionEvent::SmartContractEvent(contract_event) = bob_vote_event {
-        assert_eq!(
-            contract_event.value,
-            TupleData::from_data(vec![
-                (
-                    "event".into(),
-                    Value::string_ascii_from_bytes("voted".as_bytes().to_vec())
-                        .expect("Failed to create string")
-                ),
-                ("key".into(), aggregate_public_key.clone()),
-                ("new-total".into(), Value::UInt(2)),
-                ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
-                ("round".into(), Value::UInt(1)),
-                ("signer".into(), Value::Principal(bob_principal.clone())),
-            ])
-            .expect("Failed to create tuple")
-            .into()
+    // Alice's weight moved from the first key to the second one, and still counts once in the round
+    assert_eq!(
+        get_tally(&mut peer, tip_block_id, cycle_id + 1, 0, first_key.clone()),
+        Some(0)
+    );
+    assert_eq!(
+        get_tally(&mut peer, tip_block_id, cycle_id + 1, 0, second_key.clone()),
+        Some(2)
+    );
+    let round_info = get_round_info(&mut peer, tip_block_id, cycle_id + 1, 0)
+        .unwrap()
+        .expect_tuple()
+        .unwrap();
+    assert_eq!(round_info.get("votes-count").unwrap(), &Value::UInt(1));
+    assert_eq!(round_info.get("votes-weight").unwrap(), &Value::UInt(2));
+
+    // The superseded key stays reserved for the reward cycle it was voted for
+    for (key, reward_cycle, is_novel) in [
+        (&first_key, cycle_id + 1, true),
+        (&first_key, cycle_id + 2, false),
+        (&second_key, cycle_id + 2, false),
+    ] {
+        let novel = readonly_call(
+            &mut peer,
+            &tip_block_id,
+            SIGNERS_VOTING_NAME.into(),
+            "is-novel-aggregate-public-key".into(),
+            vec![key.clone(), Value::UInt(reward_cycle)],
         );
-    } else {
-        panic!("Expected SmartContractEvent, got {:?}", bob_vote_event);
+        assert_eq!(novel, Value::Bool(is_novel));
     }
 
+    // Bob votes for the second key, approving it. Alice can then no longer change her vote.
     let txs = vec![
-        // Bob casts a vote for key 0 in round 0
         make_signers_vote_for_aggregate_public_key_value(
             bob_key,
-            bob_nonce + 1,
+            bob_nonce,
+            bob_index,
+            second_key.clone(),
+            0,
+            cycle_id + 1,
+        ),
+        make_signers_vote_for_aggregate_public_key_value(
+            alice_key,
+            alice_nonce + 2,
+            alice_index,
+            first_key.clone(),
+            0,
+            cycle_id + 1,
+        ),
+    ];
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 4);
+    let bob_vote_tx = &receipts[2];
+    assert_eq!(bob_vote_tx.result, Value::okay_true());
+    // The vote and the approval events
+    assert_eq!(bob_vote_tx.events.len(), 2);
+
+    let alice_late_change_tx = &receipts[3];
+    assert_eq!(
+        alice_late_change_tx.result,
+        Value::from(SignersVotingError::CycleAlreadyHasKey)
+    );
+    assert_eq!(alice_late_change_tx.events.len(), 0);
+
+    assert_eq!(
+        get_tally(&mut peer, tip_block_id, cycle_id + 1, 0, first_key),
+        Some(0)
+    );
+    assert_eq!(
+        get_tally(&mut peer, tip_block_id, cycle_id + 1, 0, second_key.clone()),
+        Some(4)
+    );
+    let round_info = get_round_info(&mut peer, tip_block_id, cycle_id + 1, 0)
+        .unwrap()
+        .expect_tuple()
+        .unwrap();
+    assert_eq!(round_info.get("votes-count").unwrap(), &Value::UInt(2));
+    assert_eq!(round_info.get("votes-weight").unwrap(), &Value::UInt(4));
+    let approved_key = readonly_call(
+        &mut peer,
+        &tip_block_id,
+        SIGNERS_VOTING_NAME.into(),
+        "get-approved-aggregate-key".into(),
+        vec![Value::UInt(cycle_id + 1)],
+    )
+    .expect_optional()
+    .unwrap();
+    assert_eq!(approved_key, Some(second_key));
+}
+
+/// In this test case, Alice & Bob both successfully vote for the same key in
+/// cycle N, then in cycle N + 1, Alice tries to vote before the prepare phase,
+/// but fails with a "cycle not set" error.
+#[test]
+fn vote_for_aggregate_public_key_early() {
+    // Test setup
+    let alice = TestStacker::from_seed(&[3, 4]);
+    let bob = TestStacker::from_seed(&[5, 6]);
+    let observer = TestEventObserver::new();
+
+    // Alice - Signer 1
+    let alice_key = &alice.signer_private_key;
+    let alice_address = key_to_stacks_addr(alice_key);
+    let alice_principal = PrincipalData::from(alice_address);
+
+    // Bob - Signer 2
+    let bob_key = &bob.signer_private_key;
+    let bob_address = key_to_stacks_addr(bob_key);
+    let bob_principal = PrincipalData::from(bob_address);
+
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
+        function_name!(),
+        vec![
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+        ],
+        Some(&observer),
+    );
+
+    // Alice and Bob will each have voted once while booting to Nakamoto
+    let alice_nonce = 1;
+    let bob_nonce = 1;
+
+    let cycle_id = current_reward_cycle;
+
+    // create vote txs
+    let alice_index = get_signer_index(&mut peer, latest_block_id, alice_address, cycle_id);
+    let bob_index = get_signer_index(&mut peer, latest_block_id, bob_address, cycle_id);
+
+    let mut signers = TestSigners::default();
+    let aggregate_key = signers.generate_aggregate_key(cycle_id as u64 + 1);
+    let aggregate_public_key = Value::buff_from(aggregate_key.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
+
+    let aggregate_public_key_ill_formed = Value::buff_from_byte(0x00);
+
+    let txs = vec![
+        // Alice casts vote correctly
+        make_signers_vote_for_aggregate_public_key_value(
+            alice_key,
+            alice_nonce,
+            alice_index,
+            aggregate_public_key.clone(),
+            0,
+            cycle_id + 1,
+        ),
+        // Bob casts a vote correctly
+        make_signers_vote_for_aggregate_public_key_value(
+            bob_key,
+            bob_nonce,
             bob_index,
             aggregate_public_key.clone(),
             0,
             cycle_id + 1,
         ),
-        // Alice casts vote for key 0 in round 1
+    ];
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 4);
+
+    // Both votes should succeed
+    let alice_vote_tx = &receipts[2];
+    assert_eq!(alice_vote_tx.result, Value::okay_true());
+    assert_eq!(alice_vote_tx.events.len(), 1);
+    let bob_vote_tx = &receipts[3];
+    assert_eq!(bob_vote_tx.result, Value::okay_true());
+    assert_eq!(bob_vote_tx.events.len(), 2);
+
+    // Proceed to the reward phase
+    let _ = nakamoto_tenure(&mut peer, &mut test_signers, Vec::new());
+    let _ = nakamoto_tenure(&mut peer, &mut test_signers, Vec::new());
+
+    // In this tenure, signers have not been set yet, so the vote should fail
+    let aggregate_public_key_point = signers.generate_aggregate_key(cycle_id as u64 + 2);
+    let aggregate_public_key =
+        Value::buff_from(aggregate_public_key_point.compress().data.to_vec())
+            .expect("Failed to serialize aggregate public key");
+
+    let txs = vec![
+        // Alice casts vote for key 0 in round 0
         make_signers_vote_for_aggregate_public_key_value(
             alice_key,
             alice_nonce + 1,
             alice_index,
             aggregate_public_key.clone(),
-            1,
-            cycle_id + 1,
+            0,
+            cycle_id + 2,
         ),
     ];
 
-    // vote again in the next block of prepare phase
+    // vote before the prepare phase
     let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
 
-    // check the last four txs in the last block
+    // check the last two txs in the last block
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 3);
+    // ignore tenure change tx
+    // ignore tenure coinbase tx
+
+    // Alice's vote should fail with a "cycle not set" error
+    let alice_vote_tx = &receipts[2];
+    assert_eq!(
+        alice_vote_tx.result,
+        Value::from(SignersVotingError::CycleNotSet)
+    );
+    assert_eq!(alice_vote_tx.events.len(), 0);
+
+    // Proceed to the prepare phase
+    let _ = nakamoto_tenure(&mut peer, &mut test_signers, Vec::new());
+    let _ = nakamoto_tenure(&mut peer, &mut test_signers, Vec::new());
+
+    let txs = vec![
+        // Alice casts vote correctly
+        make_signers_vote_for_aggregate_public_key_value(
+            alice_key,
+            alice_nonce + 2,
+            alice_index,
+            aggregate_public_key.clone(),
+            0,
+            cycle_id + 2,
+        ),
+        // Bob casts a vote correctly
+        make_signers_vote_for_aggregate_public_key_value(
+            bob_key,
+            bob_nonce + 1,
+            bob_index,
+            aggregate_public_key.clone(),
+            0,
+            cycle_id + 2,
+        ),
+    ];
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
     let block = observer.get_blocks().last().unwrap().clone();
     let receipts = block.receipts.as_slice();
     assert_eq!(receipts.len(), 4);
     // ignore tenure change tx
     // ignore tenure coinbase tx
 
-    let bob_vote_tx = &receipts[2];
+    // This time, the votes should succeed and the key should be approved
+    let alice_vote_tx = &receipts[2];
+    assert_eq!(alice_vote_tx.result, Value::okay_true());
+    assert_eq!(alice_vote_tx.events.len(), 1);
+    let alice_vote_event = &alice_vote_tx.events[0];
+    if let StacksTransactionEvent::SmartContractEvent(contract_event) = alice_vote_event {
+        assert_eq!(
+            contract_event.value,
+            TupleData::from_data(vec![
+                (
+                    "event".into(),
+                    Value::string_ascii_from_bytes("voted".as_bytes().to_vec())
+                        .expect("Failed to create string")
+                ),
+                ("key".into(), aggregate_public_key.clone()),
+                ("new-total".into(), Value::UInt(2)),
+                ("reward-cycle".into(), Value::UInt(cycle_id + 2)),
+                ("round".into(), Value::UInt(0)),
+                ("signer".into(), Value::Principal(alice_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
+            ])
+            .expect("Failed to create tuple")
+            .into()
+        );
+    } else {
+        panic!("Expected SmartContractEvent, got {:?}", alice_vote_event);
+    }
+
+    let bob_vote_tx = &receipts[3];
     assert_eq!(bob_vote_tx.result, Value::okay_true());
     assert_eq!(bob_vote_tx.events.len(), 2);
     let bob_vote_event = &bob_vote_tx.events[0];
@@ -2008,9 +2436,10 @@ fn vote_for_aggregate_public_key_mixed_rounds() {
                 ),
                 ("key".into(), aggregate_public_key.clone()),
                 ("new-total".into(), Value::UInt(4)),
-                ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
+                ("reward-cycle".into(), Value::UInt(cycle_id + 2)),
                 ("round".into(), Value::UInt(0)),
                 ("signer".into(), Value::Principal(bob_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -2019,7 +2448,7 @@ fn vote_for_aggregate_public_key_mixed_rounds() {
         panic!("Expected SmartContractEvent, got {:?}", bob_vote_event);
     }
 
-    // The aggregate key is approved in round 0
+    // The aggregate key is approved in round 1
     let approve_event = &bob_vote_tx.events[1];
     if let StacksTransactionEvent::SmartContractEvent(contract_event) = approve_event {
         assert_eq!(
@@ -2033,8 +2462,10 @@ fn vote_for_aggregate_public_key_mixed_rounds() {
                     .expect("Failed to create string")
                 ),
                 ("key".into(), aggregate_public_key.clone()),
-                ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
+                ("reward-cycle".into(), Value::UInt(cycle_id + 2)),
                 ("round".into(), Value::UInt(0)),
+                ("threshold-weight".into(), Value::UInt(3)),
+                ("total-weight".into(), Value::UInt(4)),
             ])
             .expect("Failed to create tuple")
             .into()
@@ -2042,18 +2473,13 @@ fn vote_for_aggregate_public_key_mixed_rounds() {
     } else {
         panic!("Expected SmartContractEvent, got {:?}", approve_event);
     }
-
-    // Alice's vote should fail with an "out of voting window" error, since the
-    // key is already set
-    let alice_vote_tx = &receipts[3];
-    assert_eq!(alice_vote_tx.result, Value::err_uint(12)); // ERR_OUT_OF_VOTING_WINDOW
-    assert_eq!(alice_vote_tx.events.len(), 0);
 }
 
-// In this test case, Alice & Bob advance through setup & check
-// the round info from the very first reward cycle & round.
+/// In this test case, Alice votes in round 0 and Bob votes in round 1.
+/// Although they both voted for the same key, the key is not approved. In the
+/// next tenure, Bob votes in round 0, and the key is approved.
 #[test]
-fn test_get_round_info() {
+fn vote_for_aggregate_public_key_mixed_rounds() {
     // Test setup
     let alice = TestStacker::from_seed(&[3, 4]);
     let bob = TestStacker::from_seed(&[5, 6]);
@@ -2069,49 +2495,1921 @@ fn test_get_round_info() {
     let bob_address = key_to_stacks_addr(bob_key);
     let bob_principal = PrincipalData::from(bob_address);
 
-    let (mut peer, test_signers, latest_block_id, current_reward_cycle) = prepare_signers_test(
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
         function_name!(),
         vec![
-            (alice_principal.clone(), 1000),
-            (bob_principal.clone(), 1000),
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
         ],
-        &[alice.clone(), bob.clone()],
         Some(&observer),
     );
 
-    // Get the current creward cycle
-    let cycle_id = current_reward_cycle;
+    // Alice and Bob will each have voted once while booting to Nakamoto
+    let alice_nonce = 1;
+    let bob_nonce = 1;
 
-    let round_info = get_round_info(&mut peer, latest_block_id, cycle_id, 0)
-        .unwrap()
-        .expect_tuple()
-        .unwrap();
-    let votes_count = round_info.get("votes-count").unwrap();
-    let votes_weight = round_info.get("votes-weight").unwrap();
+    let cycle_id = current_reward_cycle;
 
-    assert_eq!(votes_count, &Value::UInt(2));
-    assert_eq!(votes_weight, &Value::UInt(4));
-}
+    // create vote txs
+    let alice_index = get_signer_index(&mut peer, latest_block_id, alice_address, cycle_id);
+    let bob_index = get_signer_index(&mut peer, latest_block_id, bob_address, cycle_id);
 
-pub fn get_round_info(
-    peer: &mut TestPeer<'_>,
-    latest_block_id: StacksBlockId,
-    reward_cycle: u128,
-    round: u128,
-) -> Option<Value> {
-    let round_tuple = readonly_call(
-        peer,
-        &latest_block_id,
-        "signers-voting".into(),
-        "get-round-info".into(),
-        vec![Value::UInt(reward_cycle), Value::UInt(round)],
-    )
-    .expect_optional()
-    .unwrap();
-    round_tuple
-}
+    let mut signers = TestSigners::default();
+    let aggregate_public_key_point = signers.generate_aggregate_key(0);
+    let aggregate_public_key =
+        Value::buff_from(aggregate_public_key_point.compress().data.to_vec())
+            .expect("Failed to serialize aggregate public key");
 
-// In this test case, Alice & Bob advance through setup & check
+    let txs = vec![
+        // Alice casts vote for key 0 in round 0
+        make_signers_vote_for_aggregate_public_key_value(
+            alice_key,
+            alice_nonce,
+            alice_index,
+            aggregate_public_key.clone(),
+            0,
+            cycle_id + 1,
+        ),
+        // Bob casts a vote for key 0 in round 1
+        make_signers_vote_for_aggregate_public_key_value(
+            bob_key,
+            bob_nonce,
+            bob_index,
+            aggregate_public_key.clone(),
+            1,
+            cycle_id + 1,
+        ),
+    ];
+
+    // vote in the first burn block of prepare phase
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+
+    // check the last four txs in the last block
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 4);
+    // ignore tenure change tx
+    // ignore tenure coinbase tx
+
+    // All votes should succeed
+    let alice_vote_tx = &receipts[2];
+    assert_eq!(alice_vote_tx.result, Value::okay_true());
+    assert_eq!(alice_vote_tx.events.len(), 1);
+    let alice_vote_event = &alice_vote_tx.events[0];
+    if let StacksTransactionEvent::SmartContractEvent(contract_event) = alice_vote_event {
+        assert_eq!(
+            contract_event.value,
+            TupleData::from_data(vec![
+                (
+                    "event".into(),
+                    Value::string_ascii_from_bytes("voted".as_bytes().to_vec())
+                        .expect("Failed to create string")
+                ),
+                ("key".into(), aggregate_public_key.clone()),
+                ("new-total".into(), Value::UInt(2)),
+                ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
+                ("round".into(), Value::UInt(0)),
+                ("signer".into(), Value::Principal(alice_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
+            ])
+            .expect("Failed to create tuple")
+            .into()
+        );
+    } else {
+        panic!("Expected SmartContractEvent, got {:?}", alice_vote_event);
+    }
+
+    let bob_vote_tx = &receipts[3];
+    assert_eq!(bob_vote_tx.result, Value::okay_true());
+    assert_eq!(bob_vote_tx.events.len(), 1);
+    let bob_vote_event = &bob_vote_tx.events[0];
+    if let StacksTransactionEvent::SmartContractEvent(contract_event) = bob_vote_event {
+        assert_eq!(
+            contract_event.value,
+            TupleData::from_data(vec![
+                (
+                    "event".into(),
+                    Value::string_ascii_from_bytes("voted".as_bytes().to_vec())
+                        .expect("Failed to create string")
+                ),
+                ("key".into(), aggregate_public_key.clone()),
+                ("new-total".into(), Value::UInt(2)),
+                ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
+                ("round".into(), Value::UInt(1)),
+                ("signer".into(), Value::Principal(bob_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
+            ])
+            .expect("Failed to create tuple")
+            .into()
+        );
+    } else {
+        panic!("Expected SmartContractEvent, got {:?}", bob_vote_event);
+    }
+
+    let txs = vec![
+        // Bob casts a vote for key 0 in round 0
+        make_signers_vote_for_aggregate_public_key_value(
+            bob_key,
+            bob_nonce + 1,
+            bob_index,
+            aggregate_public_key.clone(),
+            0,
+            cycle_id + 1,
+        ),
+        // Alice casts vote for key 0 in round 1
+        make_signers_vote_for_aggregate_public_key_value(
+            alice_key,
+            alice_nonce + 1,
+            alice_index,
+            aggregate_public_key.clone(),
+            1,
+            cycle_id + 1,
+        ),
+    ];
+
+    // vote again in the next block of prepare phase
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+
+    // check the last four txs in the last block
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 4);
+    // ignore tenure change tx
+    // ignore tenure coinbase tx
+
+    let bob_vote_tx = &receipts[2];
+    assert_eq!(bob_vote_tx.result, Value::okay_true());
+    assert_eq!(bob_vote_tx.events.len(), 2);
+    let bob_vote_event = &bob_vote_tx.events[0];
+    if let StacksTransactionEvent::SmartContractEvent(contract_event) = bob_vote_event {
+        assert_eq!(
+            contract_event.value,
+            TupleData::from_data(vec![
+                (
+                    "event".into(),
+                    Value::string_ascii_from_bytes("voted".as_bytes().to_vec())
+                        .expect("Failed to create string")
+                ),
+                ("key".into(), aggregate_public_key.clone()),
+                ("new-total".into(), Value::UInt(4)),
+                ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
+                ("round".into(), Value::UInt(0)),
+                ("signer".into(), Value::Principal(bob_principal.clone())),
+                ("signer-weight".into(), Value::UInt(2)),
+            ])
+            .expect("Failed to create tuple")
+            .into()
+        );
+    } else {
+        panic!("Expected SmartContractEvent, got {:?}", bob_vote_event);
+    }
+
+    // The aggregate key is approved in round 0
+    let approve_event = &bob_vote_tx.events[1];
+    if let StacksTransactionEvent::SmartContractEvent(contract_event) = approve_event {
+        assert_eq!(
+            contract_event.value,
+            TupleData::from_data(vec![
+                (
+                    "event".into(),
+                    Value::string_ascii_from_bytes(
+                        "approved-aggregate-public-key".as_bytes().to_vec()
+                    )
+                    .expect("Failed to create string")
+                ),
+                ("key".into(), aggregate_public_key.clone()),
+                ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
+                ("round".into(), Value::UInt(0)),
+                ("threshold-weight".into(), Value::UInt(3)),
+                ("total-weight".into(), Value::UInt(4)),
+            ])
+            .expect("Failed to create tuple")
+            .into()
+        );
+    } else {
+        panic!("Expected SmartContractEvent, got {:?}", approve_event);
+    }
+
+    // Alice's vote should fail with a "cycle already has key" error, since the
+    // key is already set
+    let alice_vote_tx = &receipts[3];
+    assert_eq!(
+        alice_vote_tx.result,
+        Value::from(SignersVotingError::CycleAlreadyHasKey)
+    );
+    assert_eq!(alice_vote_tx.events.len(), 0);
+}
+
+/// In this test case, Alice and Bob approve a key in round 0. In the next
+/// block, Alice votes for another key in round 1 and Bob votes with an invalid
+/// signer index. Both votes are rejected with a "cycle already has key" error,
+/// before any other check, and leave no trace in the contract.
+#[test]
+fn vote_for_aggregate_public_key_after_approval() {
+    // Test setup
+    let alice = TestStacker::from_seed(&[3, 4]);
+    let bob = TestStacker::from_seed(&[5, 6]);
+    let observer = TestEventObserver::new();
+
+    // Alice - Signer 1
+    let alice_key = &alice.signer_private_key;
+    let alice_address = key_to_stacks_addr(alice_key);
+
+    // Bob - Signer 2
+    let bob_key = &bob.signer_private_key;
+    let bob_address = key_to_stacks_addr(bob_key);
+
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
+        function_name!(),
+        vec![
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+        ],
+        Some(&observer),
+    );
+
+    // Alice and Bob will each have voted once while booting to Nakamoto
+    let alice_nonce = 1;
+    let bob_nonce = 1;
+
+    let cycle_id = current_reward_cycle;
+
+    let alice_index = get_signer_index(&mut peer, latest_block_id, alice_address, cycle_id);
+    let bob_index = get_signer_index(&mut peer, latest_block_id, bob_address, cycle_id);
+
+    let mut signers = TestSigners::default();
+    let approved_key_point = signers.generate_aggregate_key(cycle_id as u64 + 1);
+    let approved_key = Value::buff_from(approved_key_point.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
+    let late_key_point = signers.generate_aggregate_key(cycle_id as u64 + 2);
+    let late_key = Value::buff_from(late_key_point.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
+
+    let txs = vec![
+        make_signers_vote_for_aggregate_public_key_value(
+            alice_key,
+            alice_nonce,
+            alice_index,
+            approved_key.clone(),
+            0,
+            cycle_id + 1,
+        ),
+        make_signers_vote_for_aggregate_public_key_value(
+            bob_key,
+            bob_nonce,
+            bob_index,
+            approved_key.clone(),
+            0,
+            cycle_id + 1,
+        ),
+    ];
+    nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 4);
+    assert_eq!(receipts[2].result, Value::okay_true());
+    assert_eq!(receipts[3].result, Value::okay_true());
+    assert!(decode_aggregate_key_approval(&receipts[3].events[1]).is_some());
+
+    let txs = vec![
+        // Alice votes for another key in the next round
+        make_signers_vote_for_aggregate_public_key_value(
+            alice_key,
+            alice_nonce + 1,
+            alice_index,
+            late_key.clone(),
+            1,
+            cycle_id + 1,
+        ),
+        // Bob votes with an invalid signer index
+        make_signers_vote_for_aggregate_public_key_value(
+            bob_key,
+            bob_nonce + 1,
+            alice_index,
+            late_key.clone(),
+            0,
+            cycle_id + 1,
+        ),
+    ];
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 4);
+    // ignore tenure change tx
+    // ignore tenure coinbase tx
+    for late_vote_tx in &receipts[2..] {
+        assert_eq!(
+            late_vote_tx.result,
+            Value::from(SignersVotingError::CycleAlreadyHasKey)
+        );
+        assert_eq!(late_vote_tx.events.len(), 0);
+    }
+
+    assert_eq!(
+        get_tally(&mut peer, tip_block_id, cycle_id + 1, 1, late_key.clone()),
+        None
+    );
+    assert_eq!(
+        get_tally(&mut peer, tip_block_id, cycle_id + 1, 0, late_key),
+        None
+    );
+    assert_eq!(
+        get_round_info(&mut peer, tip_block_id, cycle_id + 1, 1),
+        None
+    );
+    assert_eq!(
+        get_approved_aggregate_key(&mut peer, tip_block_id, cycle_id + 1),
+        Some(approved_key_point)
+    );
+}
+
+/// In this test case, Alice stacks three times as much as Bob, so her vote
+/// alone carries the threshold weight even though she is only one of the two
+/// signers. Votes are weighted by the reward slots each signer controls, not
+/// counted per signer.
+#[test]
+fn vote_for_aggregate_public_key_weighted() {
+    // Test setup
+    let alice = TestStacker::from_seed(&[3, 4]);
+    let bob = TestStacker::from_seed(&[5, 6]);
+    let observer = TestEventObserver::new();
+
+    // Alice - Signer 1
+    let alice_key = &alice.signer_private_key;
+    let alice_address = key_to_stacks_addr(alice_key);
+    let alice_principal = PrincipalData::from(alice_address);
+
+    // Bob - Signer 2
+    let bob_key = &bob.signer_private_key;
+    let bob_address = key_to_stacks_addr(bob_key);
+
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
+        function_name!(),
+        vec![
+            (alice.clone(), 3 * TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+        ],
+        Some(&observer),
+    );
+
+    // Alice and Bob will each have voted once while booting to Nakamoto
+    let alice_nonce = 1;
+    let bob_nonce = 1;
+
+    let cycle_id = current_reward_cycle;
+
+    let alice_index = get_signer_index(&mut peer, latest_block_id, alice_address, cycle_id);
+    let bob_index = get_signer_index(&mut peer, latest_block_id, bob_address, cycle_id);
+    let alice_weight = get_signer_weight(&mut peer, latest_block_id, alice_index, cycle_id + 1);
+    let bob_weight = get_signer_weight(&mut peer, latest_block_id, bob_index, cycle_id + 1);
+    assert_eq!(alice_weight, 3 * bob_weight);
+
+    let mut signers = TestSigners::default();
+    let aggregate_key = signers.generate_aggregate_key(cycle_id as u64 + 1);
+    let aggregate_public_key = Value::buff_from(aggregate_key.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
+
+    let txs = vec![
+        // Alice's vote alone reaches the threshold
+        make_signers_vote_for_aggregate_public_key_value(
+            alice_key,
+            alice_nonce,
+            alice_index,
+            aggregate_public_key.clone(),
+            0,
+            cycle_id + 1,
+        ),
+        // Bob's vote comes too late
+        make_signers_vote_for_aggregate_public_key_value(
+            bob_key,
+            bob_nonce,
+            bob_index,
+            aggregate_public_key.clone(),
+            0,
+            cycle_id + 1,
+        ),
+    ];
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
+    let threshold_weight = get_threshold_weight(&mut peer, tip_block_id, cycle_id + 1);
+    assert!(alice_weight >= threshold_weight);
+    assert!(bob_weight < threshold_weight);
+
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 4);
+    // ignore tenure change tx
+    // ignore tenure coinbase tx
+
+    let alice_vote_tx = &receipts[2];
+    assert_eq!(alice_vote_tx.result, Value::okay_true());
+    assert_eq!(alice_vote_tx.events.len(), 2);
+    let alice_vote_event = &alice_vote_tx.events[0];
+    if let StacksTransactionEvent::SmartContractEvent(contract_event) = alice_vote_event {
+        assert_eq!(
+            contract_event.value,
+            TupleData::from_data(vec![
+                (
+                    "event".into(),
+                    Value::string_ascii_from_bytes("voted".as_bytes().to_vec())
+                        .expect("Failed to create string")
+                ),
+                ("key".into(), aggregate_public_key.clone()),
+                ("new-total".into(), Value::UInt(alice_weight)),
+                ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
+                ("round".into(), Value::UInt(0)),
+                ("signer".into(), Value::Principal(alice_principal.clone())),
+                ("signer-weight".into(), Value::UInt(alice_weight)),
+            ])
+            .expect("Failed to create tuple")
+            .into()
+        );
+    } else {
+        panic!("Expected SmartContractEvent, got {:?}", alice_vote_event);
+    }
+    assert_eq!(
+        decode_aggregate_key_approval(&alice_vote_tx.events[1]),
+        Some(AggregateKeyApproval {
+            key: aggregate_public_key.clone(),
+            reward_cycle: cycle_id + 1,
+            round: 0,
+            total_weight: alice_weight,
+            threshold_weight,
+        })
+    );
+
+    let bob_vote_tx = &receipts[3];
+    assert_eq!(
+        bob_vote_tx.result,
+        Value::from(SignersVotingError::CycleAlreadyHasKey)
+    );
+    assert_eq!(bob_vote_tx.events.len(), 0);
+
+    assert_eq!(
+        get_approved_aggregate_key(&mut peer, tip_block_id, cycle_id + 1),
+        Some(aggregate_key)
+    );
+}
+
+/// In this test case, four signers build and sign their votes for the same
+/// key on separate threads. Only the transactions are built concurrently: the
+/// votes are then mined one after the other, each in its own block, with the
+/// blocks spread across the tenures of the prepare phase. The key should be
+/// approved exactly once, by the vote that reaches the threshold, any later
+/// votes should be rejected, and every accepted vote should be recorded in the
+/// contract.
+#[test]
+fn vote_for_aggregate_public_key_concurrency() {
+    // the test peer only has 4 reward slots, so more equal stackers would all get a weight of 0
+    const NUM_SIGNERS: usize = 4;
+    let observer = TestEventObserver::new();
+
+    let stackers: Vec<_> = (0..NUM_SIGNERS as u8)
+        .map(|i| TestStacker::from_seed(&[3 + 2 * i, 4 + 2 * i]))
+        .collect();
+    let signer_principals: Vec<_> = stackers
+        .iter()
+        .map(|stacker| PrincipalData::from(key_to_stacks_addr(&stacker.signer_private_key)))
+        .collect();
+
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        stackers: signer_stackers,
+    } = prepare_signers_test(
+        function_name!(),
+        stackers
+            .iter()
+            .map(|stacker| (stacker.clone(), TestStacker::DEFAULT_STACKER_AMOUNT))
+            .collect(),
+        Some(&observer),
+    );
+
+    let cycle_id = current_reward_cycle;
+    let mut signers = TestSigners::default();
+    let aggregate_key = signers.generate_aggregate_key(cycle_id as u64 + 1);
+    let aggregate_public_key = Value::buff_from(aggregate_key.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
+
+    // each signer builds and signs its vote on its own thread
+    let vote_handles: Vec<_> = stackers
+        .iter()
+        .map(|stacker| {
+            let signer_key = stacker.signer_private_key.clone();
+            let signer_address = key_to_stacks_addr(&signer_key);
+            let signer_index =
+                get_signer_index(&mut peer, latest_block_id, signer_address, cycle_id);
+            let aggregate_public_key = aggregate_public_key.clone();
+            thread::spawn(move || {
+                // Every signer will have voted once while booting to Nakamoto
+                make_signers_vote_for_aggregate_public_key_value(
+                    &signer_key,
+                    1,
+                    signer_index,
+                    aggregate_public_key,
+                    0,
+                    cycle_id + 1,
+                )
+            })
+        })
+        .collect();
+    let vote_blocks: Vec<_> = vote_handles
+        .into_iter()
+        .map(|handle| vec![handle.join().expect("Vote thread panicked")])
+        .collect();
+
+    //
+    // the prepare phase only has room for three tenures, so mine two votes per tenure
+    //
+    let mut tip_block_id = latest_block_id;
+    for txs_of_blocks in vote_blocks.chunks(2) {
+        let blocks_and_sizes =
+            nakamoto_tenure(&mut peer, &mut test_signers, txs_of_blocks.to_vec());
+        assert_eq!(blocks_and_sizes.len(), txs_of_blocks.len());
+        tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+    }
+
+    // each vote is the last tx of its block
+    let blocks = observer.get_blocks();
+    let vote_receipts: Vec<_> = blocks[blocks.len() - NUM_SIGNERS..]
+        .iter()
+        .map(|block| block.receipts.last().unwrap().clone())
+        .collect();
+
+    let mut approvals = 0;
+    let mut accepted_signers = vec![];
+    for ((signer_principal, signer_stacker), receipt) in signer_principals
+        .iter()
+        .zip(signer_stackers.iter())
+        .zip(vote_receipts.iter())
+    {
+        if approvals > 0 {
+            // the key is already set, so the vote should fail
+            assert_eq!(
+                receipt.result,
+                Value::from(SignersVotingError::CycleAlreadyHasKey)
+            );
+            assert_eq!(receipt.events.len(), 0);
+            continue;
+        }
+        assert_eq!(receipt.result, Value::okay_true());
+        accepted_signers.push((signer_principal.clone(), signer_stacker.weight));
+        for event in receipt.events.iter() {
+            if let Some(approval) = decode_aggregate_key_approval(event) {
+                assert_eq!(approval.key, aggregate_public_key);
+                assert!(approval.total_weight >= approval.threshold_weight);
+                approvals += 1;
+            }
+        }
+    }
+    // the key should be approved exactly once
+    assert_eq!(approvals, 1);
+
+    let approved_key = readonly_call(
+        &mut peer,
+        &tip_block_id,
+        SIGNERS_VOTING_NAME.into(),
+        "get-approved-aggregate-key".into(),
+        vec![Value::UInt(cycle_id + 1)],
+    )
+    .expect_optional()
+    .unwrap();
+    assert_eq!(approved_key, Some(aggregate_public_key.clone()));
+
+    // every accepted vote should be recorded
+    let mut recorded_weight = 0;
+    for (signer_principal, signer_weight) in accepted_signers.iter() {
+        let vote = get_vote(
+            &mut peer,
+            tip_block_id,
+            cycle_id + 1,
+            0,
+            signer_principal.clone(),
+        )
+        .expect("Accepted vote is missing from the contract");
+        assert_eq!(vote, aggregate_public_key);
+        recorded_weight += signer_weight;
+    }
+
+    let round_info = get_round_info(&mut peer, tip_block_id, cycle_id + 1, 0)
+        .unwrap()
+        .expect_tuple()
+        .unwrap();
+    assert_eq!(
+        round_info.get("votes-count").unwrap(),
+        &Value::UInt(accepted_signers.len() as u128)
+    );
+    assert_eq!(
+        round_info.get("votes-weight").unwrap(),
+        &Value::UInt(recorded_weight)
+    );
+    assert!(recorded_weight >= get_threshold_weight(&mut peer, tip_block_id, cycle_id + 1));
+}
+
+/// Check the approval boundary of the voting contract: a candidate key is only approved once
+/// its tally reaches `get-threshold-weight` (70% of the total signer weight, rounded up).
+///
+/// The test peer only has 4 reward slots, so four equal stackers are used, each with a weight
+/// of 1, which puts the threshold at 3 of 4 votes:
+/// * `threshold - 1` votes in round 0 do not approve the key
+/// * `threshold` votes in round 1 approve the key
+/// * the `threshold + 1` vote is rejected, since the key is already set
+#[test]
+fn vote_threshold_exactly_met() {
+    const NUM_SIGNERS: usize = 4;
+    let observer = TestEventObserver::new();
+
+    let stackers: Vec<_> = (0..NUM_SIGNERS as u8)
+        .map(|i| TestStacker::from_seed(&[3 + 2 * i, 4 + 2 * i]))
+        .collect();
+    let signer_principals: Vec<_> = stackers
+        .iter()
+        .map(|stacker| PrincipalData::from(key_to_stacks_addr(&stacker.signer_private_key)))
+        .collect();
+
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
+        function_name!(),
+        stackers
+            .iter()
+            .map(|stacker| (stacker.clone(), TestStacker::DEFAULT_STACKER_AMOUNT))
+            .collect(),
+        Some(&observer),
+    );
+
+    let cycle_id = current_reward_cycle;
+    let signer_indices: Vec<_> = stackers
+        .iter()
+        .map(|stacker| {
+            let signer_address = key_to_stacks_addr(&stacker.signer_private_key);
+            get_signer_index(&mut peer, latest_block_id, signer_address, cycle_id)
+        })
+        .collect();
+
+    let threshold = get_threshold_weight(&mut peer, latest_block_id, cycle_id);
+    assert_eq!(threshold, 3);
+
+    let mut signers = TestSigners::default();
+    let rejected_key_point = signers.generate_aggregate_key(cycle_id as u64 + 1);
+    let rejected_key = Value::buff_from(rejected_key_point.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
+    let approved_key_point = signers.generate_aggregate_key(cycle_id as u64 + 2);
+    let approved_key = Value::buff_from(approved_key_point.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
+    assert_ne!(rejected_key, approved_key);
+
+    let is_approval =
+        |event: &StacksTransactionEvent| decode_aggregate_key_approval(event).is_some();
+
+    //
+    // Round 0: threshold - 1 votes for the first key
+    //
+    // Every signer will have voted once while booting to Nakamoto
+    let txs: Vec<_> = (0..threshold as usize - 1)
+        .map(|i| {
+            make_signers_vote_for_aggregate_public_key_value(
+                &stackers[i].signer_private_key,
+                1,
+                signer_indices[i],
+                rejected_key.clone(),
+                0,
+                cycle_id + 1,
+            )
+        })
+        .collect();
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 2 + threshold as usize - 1);
+    // ignore tenure change tx
+    // ignore tenure coinbase tx
+    for receipt in receipts[2..].iter() {
+        assert_eq!(receipt.result, Value::okay_true());
+        // only the "voted" event
+        assert_eq!(receipt.events.len(), 1);
+        assert!(!is_approval(&receipt.events[0]));
+    }
+
+    let tally = readonly_call(
+        &mut peer,
+        &tip_block_id,
+        SIGNERS_VOTING_NAME.into(),
+        "get-tally".into(),
+        vec![
+            Value::UInt(cycle_id + 1),
+            Value::UInt(0),
+            rejected_key.clone(),
+        ],
+    )
+    .expect_optional()
+    .unwrap()
+    .expect("Missing tally for the round 0 key")
+    .expect_u128()
+    .unwrap();
+    assert_eq!(tally, threshold - 1);
+
+    let approved = readonly_call(
+        &mut peer,
+        &tip_block_id,
+        SIGNERS_VOTING_NAME.into(),
+        "get-approved-aggregate-key".into(),
+        vec![Value::UInt(cycle_id + 1)],
+    )
+    .expect_optional()
+    .unwrap();
+    assert_eq!(approved, None);
+
+    //
+    // Round 1: threshold votes for the second key, followed by one more vote
+    //
+    let txs: Vec<_> = (0..NUM_SIGNERS)
+        .map(|i| {
+            let nonce = if i < threshold as usize - 1 { 2 } else { 1 };
+            make_signers_vote_for_aggregate_public_key_value(
+                &stackers[i].signer_private_key,
+                nonce,
+                signer_indices[i],
+                approved_key.clone(),
+                1,
+                cycle_id + 1,
+            )
+        })
+        .collect();
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 2 + NUM_SIGNERS);
+    // ignore tenure change tx
+    // ignore tenure coinbase tx
+    let vote_receipts = &receipts[2..];
+
+    // threshold - 1 votes: still not approved
+    for receipt in vote_receipts[..threshold as usize - 1].iter() {
+        assert_eq!(receipt.result, Value::okay_true());
+        assert_eq!(receipt.events.len(), 1);
+        assert!(!is_approval(&receipt.events[0]));
+    }
+
+    // threshold votes: the key is approved
+    let threshold_vote_tx = &vote_receipts[threshold as usize - 1];
+    assert_eq!(threshold_vote_tx.result, Value::okay_true());
+    assert_eq!(threshold_vote_tx.events.len(), 2);
+    assert!(!is_approval(&threshold_vote_tx.events[0]));
+    let approve_event = &threshold_vote_tx.events[1];
+    if let StacksTransactionEvent::SmartContractEvent(contract_event) = approve_event {
+        assert_eq!(
+            contract_event.value,
+            TupleData::from_data(vec![
+                (
+                    "event".into(),
+                    Value::string_ascii_from_bytes(
+                        "approved-aggregate-public-key".as_bytes().to_vec()
+                    )
+                    .expect("Failed to create string")
+                ),
+                ("key".into(), approved_key.clone()),
+                ("reward-cycle".into(), Value::UInt(cycle_id + 1)),
+                ("round".into(), Value::UInt(1)),
+                ("threshold-weight".into(), Value::UInt(3)),
+                ("total-weight".into(), Value::UInt(3)),
+            ])
+            .expect("Failed to create tuple")
+            .into()
+        );
+    } else {
+        panic!("Expected SmartContractEvent, got {:?}", approve_event);
+    }
+
+    // threshold + 1 votes: the key is already set, so the vote should fail
+    let extra_vote_tx = &vote_receipts[threshold as usize];
+    assert_eq!(
+        extra_vote_tx.result,
+        Value::from(SignersVotingError::CycleAlreadyHasKey)
+    );
+    assert_eq!(extra_vote_tx.events.len(), 0);
+
+    let tally = readonly_call(
+        &mut peer,
+        &tip_block_id,
+        SIGNERS_VOTING_NAME.into(),
+        "get-tally".into(),
+        vec![
+            Value::UInt(cycle_id + 1),
+            Value::UInt(1),
+            approved_key.clone(),
+        ],
+    )
+    .expect_optional()
+    .unwrap()
+    .expect("Missing tally for the round 1 key")
+    .expect_u128()
+    .unwrap();
+    assert_eq!(tally, threshold);
+
+    let approved = readonly_call(
+        &mut peer,
+        &tip_block_id,
+        SIGNERS_VOTING_NAME.into(),
+        "get-approved-aggregate-key".into(),
+        vec![Value::UInt(cycle_id + 1)],
+    )
+    .expect_optional()
+    .unwrap();
+    assert_eq!(approved, Some(approved_key));
+}
+
+// In this test case, Alice & Bob advance through setup & check
+// the round info from the very first reward cycle & round.
+#[test]
+fn test_get_round_info() {
+    // Test setup
+    let alice = TestStacker::from_seed(&[3, 4]);
+    let bob = TestStacker::from_seed(&[5, 6]);
+    let observer = TestEventObserver::new();
+
+    // Alice - Signer 1
+    let alice_key = &alice.signer_private_key;
+    let alice_address = key_to_stacks_addr(alice_key);
+
+    // Bob - Signer 2
+    let bob_key = &bob.signer_private_key;
+    let bob_address = key_to_stacks_addr(bob_key);
+
+    let SignersTestSetup {
+        mut peer,
+        test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
+        function_name!(),
+        vec![
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+        ],
+        Some(&observer),
+    );
+
+    // Get the current creward cycle
+    let cycle_id = current_reward_cycle;
+
+    let round_info = get_round_info(&mut peer, latest_block_id, cycle_id, 0)
+        .unwrap()
+        .expect_tuple()
+        .unwrap();
+    let votes_count = round_info.get("votes-count").unwrap();
+    let votes_weight = round_info.get("votes-weight").unwrap();
+
+    assert_eq!(votes_count, &Value::UInt(2));
+    assert_eq!(votes_weight, &Value::UInt(4));
+}
+
+/// In this test case, the round summary of the next reward cycle is checked
+/// before any vote, after Alice votes alone, short of the threshold, and after
+/// Bob votes for the same key, which approves it.
+#[test]
+fn test_get_round_summary() {
+    // Test setup
+    let alice = TestStacker::from_seed(&[3, 4]);
+    let bob = TestStacker::from_seed(&[5, 6]);
+    let observer = TestEventObserver::new();
+
+    // Alice - Signer 1
+    let alice_key = &alice.signer_private_key;
+    let alice_address = key_to_stacks_addr(alice_key);
+
+    // Bob - Signer 2
+    let bob_key = &bob.signer_private_key;
+    let bob_address = key_to_stacks_addr(bob_key);
+
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
+        function_name!(),
+        vec![
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+        ],
+        Some(&observer),
+    );
+
+    // Alice and Bob will each have voted once while booting to Nakamoto
+    let alice_nonce = 1;
+    let bob_nonce = 1;
+
+    let cycle_id = current_reward_cycle;
+
+    // Empty round: no vote was cast yet
+    assert!(get_round_summary(&mut peer, latest_block_id, cycle_id + 1, 0).is_none());
+
+    let alice_index = get_signer_index(&mut peer, latest_block_id, alice_address, cycle_id);
+    let bob_index = get_signer_index(&mut peer, latest_block_id, bob_address, cycle_id);
+
+    let mut signers = TestSigners::default();
+    let aggregate_key = signers.generate_aggregate_key(cycle_id as u64 + 1);
+    let aggregate_public_key = Value::buff_from(aggregate_key.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
+
+    // Partial votes: only Alice votes, which is not enough to reach the threshold
+    let txs = vec![make_signers_vote_for_aggregate_public_key_value(
+        alice_key,
+        alice_nonce,
+        alice_index,
+        aggregate_public_key.clone(),
+        0,
+        cycle_id + 1,
+    )];
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
+    let threshold_weight = get_threshold_weight(&mut peer, tip_block_id, cycle_id + 1);
+    assert_eq!(
+        get_round_summary(&mut peer, tip_block_id, cycle_id + 1, 0),
+        Some((2, None, threshold_weight))
+    );
+
+    // Approved round: Bob votes for the same key
+    let txs = vec![make_signers_vote_for_aggregate_public_key_value(
+        bob_key,
+        bob_nonce,
+        bob_index,
+        aggregate_public_key,
+        0,
+        cycle_id + 1,
+    )];
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
+    assert_eq!(
+        get_round_summary(&mut peer, tip_block_id, cycle_id + 1, 0),
+        Some((4, Some(aggregate_key), threshold_weight))
+    );
+    // Other rounds of the reward cycle are still empty
+    assert!(get_round_summary(&mut peer, tip_block_id, cycle_id + 1, 1).is_none());
+}
+
+/// In this test case, DKG fails 20 times in a row: Alice votes alone in each
+/// round, for a new key, which is never enough to reach the threshold. The
+/// rounds are spread across the tenures of the prepare phase.
+/// * the last round only ever moves up, one round at a time
+/// * each round only tallies its own vote
+/// * skipping a round is rejected and does not move the last round
+/// * Bob's vote in the last round finally approves its key
+#[test]
+fn vote_round_advancement_stress_test() {
+    const NUM_ROUNDS: u64 = 20;
+    // the prepare phase only has room for three tenures: two for the failed rounds and one
+    // for the final votes
+    const ROUNDS_PER_TENURE: usize = 10;
+
+    // Test setup
+    let alice = TestStacker::from_seed(&[3, 4]);
+    let bob = TestStacker::from_seed(&[5, 6]);
+    let observer = TestEventObserver::new();
+
+    // Alice - Signer 1
+    let alice_key = &alice.signer_private_key;
+    let alice_address = key_to_stacks_addr(alice_key);
+
+    // Bob - Signer 2
+    let bob_key = &bob.signer_private_key;
+    let bob_address = key_to_stacks_addr(bob_key);
+
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
+        function_name!(),
+        vec![
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+        ],
+        Some(&observer),
+    );
+
+    // Alice and Bob will each have voted once while booting to Nakamoto
+    let mut alice_nonce = 1;
+    let bob_nonce = 1;
+
+    let cycle_id = current_reward_cycle;
+    let alice_index = get_signer_index(&mut peer, latest_block_id, alice_address, cycle_id);
+    let bob_index = get_signer_index(&mut peer, latest_block_id, bob_address, cycle_id);
+    let alice_weight = get_signer_weight(&mut peer, latest_block_id, alice_index, cycle_id + 1);
+    assert!(alice_weight < get_threshold_weight(&mut peer, latest_block_id, cycle_id + 1));
+
+    // a new key for every round
+    let mut signers = TestSigners::default();
+    let round_keys: Vec<_> = (0..NUM_ROUNDS)
+        .map(|round| {
+            let aggregate_key = signers.generate_aggregate_key(100 + round);
+            Value::buff_from(aggregate_key.compress().data.to_vec())
+                .expect("Failed to serialize aggregate public key")
+        })
+        .collect();
+
+    //
+    // Alice votes in each round, one vote per block
+    //
+    let mut tip_block_id = latest_block_id;
+    let mut last_round = None;
+    let rounds: Vec<_> = (0..NUM_ROUNDS).collect();
+    for tenure_rounds in rounds.chunks(ROUNDS_PER_TENURE) {
+        let txs_of_blocks: Vec<_> = tenure_rounds
+            .iter()
+            .map(|round| {
+                let tx = make_signers_vote_for_aggregate_public_key_value(
+                    alice_key,
+                    alice_nonce,
+                    alice_index,
+                    round_keys[*round as usize].clone(),
+                    *round,
+                    cycle_id + 1,
+                );
+                alice_nonce += 1;
+                vec![tx]
+            })
+            .collect();
+        let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, txs_of_blocks);
+        assert_eq!(blocks_and_sizes.len(), tenure_rounds.len());
+        tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
+        // each vote is the last tx of its block
+        let blocks = observer.get_blocks();
+        for block in blocks[blocks.len() - tenure_rounds.len()..].iter() {
+            let vote_tx = block.receipts.last().unwrap();
+            assert_eq!(vote_tx.result, Value::okay_true());
+        }
+
+        // the last round only moves up, to the round voted in by each block
+        for ((block, _, _), round) in blocks_and_sizes.iter().zip(tenure_rounds.iter()) {
+            let block_last_round = get_last_round(&mut peer, block.block_id(), cycle_id + 1);
+            assert!(block_last_round > last_round);
+            assert_eq!(block_last_round, Some(*round as u128));
+            last_round = block_last_round;
+        }
+    }
+
+    //
+    // Each round only tallies its own vote
+    //
+    for round in 0..NUM_ROUNDS {
+        let round_key = round_keys[round as usize].clone();
+        assert_eq!(
+            get_tally(
+                &mut peer,
+                tip_block_id,
+                cycle_id + 1,
+                round as u128,
+                round_key.clone()
+            ),
+            Some(alice_weight)
+        );
+        if round > 0 {
+            let previous_round_key = round_keys[round as usize - 1].clone();
+            assert_eq!(
+                get_tally(
+                    &mut peer,
+                    tip_block_id,
+                    cycle_id + 1,
+                    round as u128,
+                    previous_round_key
+                ),
+                None
+            );
+        }
+        let round_info = get_round_info(&mut peer, tip_block_id, cycle_id + 1, round as u128)
+            .unwrap()
+            .expect_tuple()
+            .unwrap();
+        assert_eq!(round_info.get("votes-count").unwrap(), &Value::UInt(1));
+        assert_eq!(
+            round_info.get("votes-weight").unwrap(),
+            &Value::UInt(alice_weight)
+        );
+    }
+    assert_eq!(
+        get_approved_aggregate_key(&mut peer, tip_block_id, cycle_id + 1),
+        None
+    );
+
+    //
+    // Alice skips a round, then Bob joins Alice in the last round
+    //
+    let last_round_key = round_keys[NUM_ROUNDS as usize - 1].clone();
+    let txs = vec![
+        make_signers_vote_for_aggregate_public_key_value(
+            alice_key,
+            alice_nonce,
+            alice_index,
+            last_round_key.clone(),
+            NUM_ROUNDS + 1,
+            cycle_id + 1,
+        ),
+        make_signers_vote_for_aggregate_public_key_value(
+            bob_key,
+            bob_nonce,
+            bob_index,
+            last_round_key.clone(),
+            NUM_ROUNDS - 1,
+            cycle_id + 1,
+        ),
+    ];
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 4);
+    assert_eq!(
+        receipts[2].result,
+        Value::from(SignersVotingError::InvalidRound)
+    );
+    assert_eq!(receipts[3].result, Value::okay_true());
+
+    assert_eq!(
+        get_last_round(&mut peer, tip_block_id, cycle_id + 1),
+        Some(NUM_ROUNDS as u128 - 1)
+    );
+    let approved_key = get_approved_aggregate_key(&mut peer, tip_block_id, cycle_id + 1)
+        .expect("Expected the last round's key to be approved");
+    assert_eq!(
+        Value::buff_from(approved_key.compress().data.to_vec()).unwrap(),
+        last_round_key
+    );
+}
+
+/// `get-vote` and `get-last-round` are checked before any vote of the next
+/// reward cycle, and after Alice votes alone in its first round.
+#[test]
+fn test_get_vote_and_last_round() {
+    // Test setup
+    let alice = TestStacker::from_seed(&[3, 4]);
+    let bob = TestStacker::from_seed(&[5, 6]);
+    let observer = TestEventObserver::new();
+
+    // Alice - Signer 1
+    let alice_key = &alice.signer_private_key;
+    let alice_address = key_to_stacks_addr(alice_key);
+    let alice_principal = PrincipalData::from(alice_address);
+
+    // Bob - Signer 2
+    let bob_key = &bob.signer_private_key;
+    let bob_address = key_to_stacks_addr(bob_key);
+    let bob_principal = PrincipalData::from(bob_address);
+
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
+        function_name!(),
+        vec![
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+        ],
+        Some(&observer),
+    );
+
+    // Alice will have voted once while booting to Nakamoto
+    let alice_nonce = 1;
+
+    let cycle_id = current_reward_cycle;
+
+    // No vote was cast for the next reward cycle yet
+    assert_eq!(
+        get_last_round(&mut peer, latest_block_id, cycle_id + 1),
+        None
+    );
+    assert_eq!(
+        get_vote(
+            &mut peer,
+            latest_block_id,
+            cycle_id + 1,
+            0,
+            alice_principal.clone()
+        ),
+        None
+    );
+
+    let alice_index = get_signer_index(&mut peer, latest_block_id, alice_address, cycle_id);
+
+    let mut signers = TestSigners::default();
+    let aggregate_key = signers.generate_aggregate_key(cycle_id as u64 + 1);
+    let aggregate_public_key = Value::buff_from(aggregate_key.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
+
+    let txs = vec![make_signers_vote_for_aggregate_public_key_value(
+        alice_key,
+        alice_nonce,
+        alice_index,
+        aggregate_public_key.clone(),
+        0,
+        cycle_id + 1,
+    )];
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
+    assert_eq!(
+        get_last_round(&mut peer, tip_block_id, cycle_id + 1),
+        Some(0)
+    );
+    assert_eq!(
+        get_vote(
+            &mut peer,
+            tip_block_id,
+            cycle_id + 1,
+            0,
+            alice_principal.clone()
+        ),
+        Some(aggregate_public_key)
+    );
+    // Bob did not vote, and Alice did not vote in any other round
+    assert_eq!(
+        get_vote(&mut peer, tip_block_id, cycle_id + 1, 0, bob_principal),
+        None
+    );
+    assert_eq!(
+        get_vote(&mut peer, tip_block_id, cycle_id + 1, 1, alice_principal),
+        None
+    );
+}
+
+/// A round records the burn height of its first vote, and expires
+/// `get-round-expiry-blocks` burn blocks later. Later votes in the round do not
+/// move its start, and rounds without votes never expire.
+#[test]
+fn test_round_expiry() {
+    // Test setup
+    let alice = TestStacker::from_seed(&[3, 4]);
+    let bob = TestStacker::from_seed(&[5, 6]);
+    let observer = TestEventObserver::new();
+
+    // Alice - Signer 1
+    let alice_key = &alice.signer_private_key;
+    let alice_address = key_to_stacks_addr(alice_key);
+
+    // Bob - Signer 2
+    let bob_key = &bob.signer_private_key;
+    let bob_address = key_to_stacks_addr(bob_key);
+
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
+        function_name!(),
+        vec![
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+        ],
+        Some(&observer),
+    );
+
+    // Alice and Bob will each have voted once while booting to Nakamoto
+    let alice_nonce = 1;
+    let bob_nonce = 1;
+
+    let cycle_id = current_reward_cycle;
+
+    let expiry_blocks = readonly_call(
+        &mut peer,
+        &latest_block_id,
+        SIGNERS_VOTING_NAME.into(),
+        "get-round-expiry-blocks".into(),
+        vec![],
+    )
+    .expect_u128()
+    .unwrap();
+    assert_eq!(expiry_blocks, 6);
+    assert_eq!(
+        get_round_start_height(&mut peer, latest_block_id, cycle_id + 1, 0),
+        None
+    );
+
+    let alice_index = get_signer_index(&mut peer, latest_block_id, alice_address, cycle_id);
+    let bob_index = get_signer_index(&mut peer, latest_block_id, bob_address, cycle_id);
+
+    let mut signers = TestSigners::default();
+    let first_key = signers.generate_aggregate_key(cycle_id as u64 + 1);
+    let first_key = Value::buff_from(first_key.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
+    let second_key = signers.generate_aggregate_key(cycle_id as u64 + 2);
+    let second_key = Value::buff_from(second_key.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
+
+    // Alice's vote starts round 0
+    let txs = vec![make_signers_vote_for_aggregate_public_key_value(
+        alice_key,
+        alice_nonce,
+        alice_index,
+        first_key,
+        0,
+        cycle_id + 1,
+    )];
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+    let start_height = u128::from(peer.get_burnchain_view().unwrap().burn_block_height);
+    assert_eq!(
+        get_round_start_height(&mut peer, tip_block_id, cycle_id + 1, 0),
+        Some(start_height)
+    );
+
+    // The round expires exactly `expiry_blocks` after its first vote
+    assert!(!is_round_expired(
+        &mut peer,
+        tip_block_id,
+        cycle_id + 1,
+        0,
+        start_height + expiry_blocks - 1
+    ));
+    assert!(is_round_expired(
+        &mut peer,
+        tip_block_id,
+        cycle_id + 1,
+        0,
+        start_height + expiry_blocks
+    ));
+    // A round without votes never expires
+    assert!(!is_round_expired(
+        &mut peer,
+        tip_block_id,
+        cycle_id + 1,
+        1,
+        start_height + expiry_blocks
+    ));
+
+    // Bob votes in the next burn block, before the round expires
+    let txs = vec![make_signers_vote_for_aggregate_public_key_value(
+        bob_key,
+        bob_nonce,
+        bob_index,
+        second_key,
+        0,
+        cycle_id + 1,
+    )];
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 3);
+    let bob_vote_tx = &receipts[2];
+    assert_eq!(bob_vote_tx.result, Value::okay_true());
+    assert_eq!(bob_vote_tx.events.len(), 1);
+
+    // The round still started with Alice's vote
+    assert_eq!(
+        get_round_start_height(&mut peer, tip_block_id, cycle_id + 1, 0),
+        Some(start_height)
+    );
+    assert!(!is_round_expired(
+        &mut peer,
+        tip_block_id,
+        cycle_id + 1,
+        0,
+        u128::from(peer.get_burnchain_view().unwrap().burn_block_height)
+    ));
+}
+
+/// Alice votes for several rounds at once, with a batch mixing valid votes, a
+/// duplicate vote, a skipped round and an ill-formed key: the valid votes are
+/// recorded and the others are reported without failing the call. Bob's batch
+/// with Alice's signer index is rejected as a whole. Bob then approves the key
+/// of round 0 with a batch, and his vote for round 1 fails as the cycle has a key.
+///
+/// A round expires 6 burn blocks after its first vote, which is longer than the
+/// reward cycles of the test chain: votes for an expired round cannot be cast
+/// here, and go through the same checks as the other per-round errors.
+#[test]
+fn vote_for_aggregate_public_keys() {
+    // Test setup
+    let alice = TestStacker::from_seed(&[3, 4]);
+    let bob = TestStacker::from_seed(&[5, 6]);
+    let observer = TestEventObserver::new();
+
+    // Alice - Signer 1
+    let alice_key = &alice.signer_private_key;
+    let alice_address = key_to_stacks_addr(alice_key);
+    let alice_principal = PrincipalData::from(alice_address);
+
+    // Bob - Signer 2
+    let bob_key = &bob.signer_private_key;
+    let bob_address = key_to_stacks_addr(bob_key);
+    let bob_principal = PrincipalData::from(bob_address);
+
+    let SignersTestSetup {
+        mut peer,
+        mut test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
+        function_name!(),
+        vec![
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+        ],
+        Some(&observer),
+    );
+
+    // Alice and Bob will each have voted once while booting to Nakamoto
+    let alice_nonce = 1;
+    let mut bob_nonce = 1;
+
+    let cycle_id = current_reward_cycle;
+
+    let alice_index = get_signer_index(&mut peer, latest_block_id, alice_address, cycle_id);
+    let bob_index = get_signer_index(&mut peer, latest_block_id, bob_address, cycle_id);
+
+    let mut signers = TestSigners::default();
+    let round_keys: Vec<_> = (0..3)
+        .map(|round| {
+            let key = signers.generate_aggregate_key(cycle_id as u64 + 1 + round);
+            Value::buff_from(key.compress().data.to_vec())
+                .expect("Failed to serialize aggregate public key")
+        })
+        .collect();
+    let ill_formed_key = Value::buff_from(vec![1; 32]).unwrap();
+
+    let txs = vec![
+        make_signers_vote_for_aggregate_public_keys_value(
+            alice_key,
+            alice_nonce,
+            alice_index,
+            vec![
+                (0, round_keys[0].clone()),
+                // duplicate of the previous vote
+                (0, round_keys[0].clone()),
+                // skips round 1
+                (2, round_keys[2].clone()),
+                (1, round_keys[1].clone()),
+                (1, ill_formed_key),
+            ],
+            cycle_id + 1,
+        ),
+        // Bob uses Alice's signer index
+        make_signers_vote_for_aggregate_public_keys_value(
+            bob_key,
+            bob_nonce,
+            alice_index,
+            vec![(0, round_keys[0].clone())],
+            cycle_id + 1,
+        ),
+    ];
+    bob_nonce += 1;
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 4);
+
+    let alice_vote_tx = &receipts[2];
+    assert_eq!(
+        alice_vote_tx
+            .result
+            .clone()
+            .expect_result_ok()
+            .unwrap()
+            .expect_list()
+            .unwrap(),
+        vec![
+            Value::okay_true(),
+            Value::from(SignersVotingError::DuplicateVote),
+            Value::from(SignersVotingError::InvalidRound),
+            Value::okay_true(),
+            Value::from(SignersVotingError::IllFormedAggregatePublicKey),
+        ]
+    );
+    assert_eq!(
+        get_vote_events(alice_vote_tx),
+        vec![("voted".to_string(), 0), ("voted".to_string(), 1)]
+    );
+
+    let bob_vote_tx = &receipts[3];
+    assert_eq!(
+        bob_vote_tx.result,
+        Value::from(SignersVotingError::SignerIndexMismatch)
+    );
+    assert!(bob_vote_tx.events.is_empty());
+
+    assert_eq!(
+        get_last_round(&mut peer, tip_block_id, cycle_id + 1),
+        Some(1)
+    );
+    for round in 0..2 {
+        assert_eq!(
+            get_vote(
+                &mut peer,
+                tip_block_id,
+                cycle_id + 1,
+                round,
+                alice_principal.clone()
+            ),
+            Some(round_keys[round as usize].clone())
+        );
+        assert_eq!(
+            get_vote(
+                &mut peer,
+                tip_block_id,
+                cycle_id + 1,
+                round,
+                bob_principal.clone()
+            ),
+            None
+        );
+    }
+    assert_eq!(
+        get_vote(&mut peer, tip_block_id, cycle_id + 1, 2, alice_principal),
+        None
+    );
+
+    // Bob approves the key of round 0, and can no longer vote for round 1
+    let txs = vec![make_signers_vote_for_aggregate_public_keys_value(
+        bob_key,
+        bob_nonce,
+        bob_index,
+        vec![(0, round_keys[0].clone()), (1, round_keys[1].clone())],
+        cycle_id + 1,
+    )];
+    let blocks_and_sizes = nakamoto_tenure(&mut peer, &mut test_signers, vec![txs]);
+    let tip_block_id = blocks_and_sizes.last().unwrap().0.block_id();
+
+    let block = observer.get_blocks().last().unwrap().clone();
+    let receipts = block.receipts.as_slice();
+    assert_eq!(receipts.len(), 3);
+    let bob_vote_tx = &receipts[2];
+    assert_eq!(
+        bob_vote_tx
+            .result
+            .clone()
+            .expect_result_ok()
+            .unwrap()
+            .expect_list()
+            .unwrap(),
+        vec![
+            Value::okay_true(),
+            Value::from(SignersVotingError::CycleAlreadyHasKey),
+        ]
+    );
+    assert_eq!(
+        get_vote_events(bob_vote_tx),
+        vec![
+            ("voted".to_string(), 0),
+            ("approved-aggregate-public-key".to_string(), 0)
+        ]
+    );
+    assert_eq!(
+        get_vote(&mut peer, tip_block_id, cycle_id + 1, 1, bob_principal),
+        None
+    );
+    let approved_key = get_approved_aggregate_key(&mut peer, tip_block_id, cycle_id + 1)
+        .expect("Expected an approved aggregate key");
+    assert_eq!(
+        Value::buff_from(approved_key.compress().data.to_vec()).unwrap(),
+        round_keys[0]
+    );
+}
+
+/// The name and round of each event printed by a vote transaction
+fn get_vote_events(receipt: &StacksTransactionReceipt) -> Vec<(String, u128)> {
+    receipt
+        .events
+        .iter()
+        .map(|event| {
+            let StacksTransactionEvent::SmartContractEvent(contract_event) = event else {
+                panic!("Expected SmartContractEvent, got {event:?}");
+            };
+            let event_data = contract_event.value.clone().expect_tuple().unwrap();
+            (
+                event_data
+                    .get("event")
+                    .unwrap()
+                    .clone()
+                    .expect_ascii()
+                    .unwrap(),
+                event_data
+                    .get("round")
+                    .unwrap()
+                    .clone()
+                    .expect_u128()
+                    .unwrap(),
+            )
+        })
+        .collect()
+}
+
+/// In this test case, Alice votes while the chain is still in Epoch 2.5, before any
+/// signer set has been computed for the cycle she votes for. The signers-voting
+/// contract has no notion of epochs, so the vote must fail because the signer set
+/// for the cycle is not yet set, and no vote must be recorded.
+#[test]
+fn vote_in_wrong_epoch() {
+    let observer = TestEventObserver::new();
+    let (burnchain, mut peer, keys, _, block_height, mut coinbase_nonce) =
+        prepare_pox4_test(function_name!(), Some(&observer));
+
+    let epoch = SortitionDB::get_stacks_epoch(peer.sortdb().conn(), block_height)
+        .unwrap()
+        .unwrap();
+    assert_eq!(epoch.epoch_id, StacksEpochId::Epoch25);
+
+    let alice_key = &keys[0];
+    let alice_address = key_to_stacks_addr(alice_key);
+    // No signer set is computed this far ahead, even in a later epoch
+    let cycle_id = get_current_reward_cycle(&peer, &burnchain) + 2;
+
+    let mut signers = TestSigners::default();
+    let aggregate_key = signers.generate_aggregate_key(cycle_id as u64);
+    let aggregate_public_key = Value::buff_from(aggregate_key.compress().data.to_vec())
+        .expect("Failed to serialize aggregate public key");
+
+    let txs = vec![make_signers_vote_for_aggregate_public_key_value(
+        alice_key,
+        0,
+        0,
+        aggregate_public_key.clone(),
+        0,
+        cycle_id,
+    )];
+    let latest_block = peer.tenure_with_txs(&txs, &mut coinbase_nonce);
+
+    let alice_txs = get_last_block_sender_transactions(&observer, alice_address);
+    assert_eq!(alice_txs.len(), 1);
+    let alice_vote_tx = &alice_txs[0];
+    // An error of the .signers contract
+    assert_eq!(
+        alice_vote_tx.result,
+        Value::from(SignersVotingError::CycleNotSet)
+    );
+    assert!(alice_vote_tx.events.is_empty());
+
+    let round_info = get_round_info(&mut peer, latest_block, cycle_id, 0);
+    assert!(round_info.is_none());
+}
+
+/// Every error constant of the contract has a `SignersVotingError` variant with the same code,
+/// and every variant but `Unknown` has a constant
+#[test]
+fn signers_voting_errors_match_the_contract() {
+    let mut constants = get_error_constants(SIGNERS_VOTING_BODY);
+    // The only error of the .signers contract that the voting contract passes on
+    constants.insert(
+        "ERR_CYCLE_NOT_SET",
+        get_error_constants(SIGNERS_BODY)["ERR_CYCLE_NOT_SET"],
+    );
+    for (name, code) in constants.iter() {
+        let error = SignersVotingError::from(*code);
+        assert_eq!(get_error_constant_name(error), *name);
+        assert_eq!(error.code(), *code);
+        assert_eq!(
+            SignersVotingError::try_from(Value::from(error)).unwrap(),
+            error
+        );
+    }
+    for code in 0..=u128::from(u8::MAX) {
+        let error = SignersVotingError::from(code);
+        if error != SignersVotingError::Unknown(code) {
+            assert_eq!(constants.get(get_error_constant_name(error)), Some(&code));
+        }
+    }
+    assert!(SignersVotingError::try_from(Value::okay_true()).is_err());
+}
+
+/// The `ERR_` constants defined by a contract, with their codes
+fn get_error_constants(contract: &str) -> HashMap<&str, u128> {
+    contract
+        .lines()
+        .filter_map(|line| {
+            let (name, code) = line
+                .strip_prefix("(define-constant ")?
+                .strip_suffix(")")?
+                .split_once(" u")?;
+            name.starts_with("ERR_")
+                .then(|| (name, code.parse().expect("Failed to parse error code")))
+        })
+        .collect()
+}
+
+/// The name of the contract constant of an error. The match fails to compile when a variant is
+/// added without a constant.
+fn get_error_constant_name(error: SignersVotingError) -> &'static str {
+    match error {
+        SignersVotingError::CycleNotSet => "ERR_CYCLE_NOT_SET",
+        SignersVotingError::SignerIndexMismatch => "ERR_SIGNER_INDEX_MISMATCH",
+        SignersVotingError::InvalidSignerIndex => "ERR_INVALID_SIGNER_INDEX",
+        SignersVotingError::OutOfVotingWindow => "ERR_OUT_OF_VOTING_WINDOW",
+        SignersVotingError::IllFormedAggregatePublicKey => "ERR_ILL_FORMED_AGGREGATE_PUBLIC_KEY",
+        SignersVotingError::DuplicateAggregatePublicKey => "ERR_DUPLICATE_AGGREGATE_PUBLIC_KEY",
+        SignersVotingError::DuplicateVote => "ERR_DUPLICATE_VOTE",
+        SignersVotingError::FailedToRetrieveSigners => "ERR_FAILED_TO_RETRIEVE_SIGNERS",
+        SignersVotingError::InvalidRound => "ERR_INVALID_ROUND",
+        SignersVotingError::RoundExpired => "ERR_ROUND_EXPIRED",
+        SignersVotingError::CycleAlreadyHasKey => "ERR_CYCLE_ALREADY_HAS_KEY",
+        SignersVotingError::Unknown(code) => panic!("No constant for error code {code}"),
+    }
+}
+
+pub fn get_last_round(
+    peer: &mut TestPeer<'_>,
+    latest_block_id: StacksBlockId,
+    reward_cycle: u128,
+) -> Option<u128> {
+    readonly_call(
+        peer,
+        &latest_block_id,
+        "signers-voting".into(),
+        "get-last-round".into(),
+        vec![Value::UInt(reward_cycle)],
+    )
+    .expect_optional()
+    .unwrap()
+    .map(|round| round.expect_u128().unwrap())
+}
+
+pub fn get_vote(
+    peer: &mut TestPeer<'_>,
+    latest_block_id: StacksBlockId,
+    reward_cycle: u128,
+    round: u128,
+    signer: PrincipalData,
+) -> Option<Value> {
+    readonly_call(
+        peer,
+        &latest_block_id,
+        SIGNERS_VOTING_NAME.into(),
+        "get-vote".into(),
+        vec![
+            Value::UInt(reward_cycle),
+            Value::UInt(round),
+            Value::Principal(signer),
+        ],
+    )
+    .expect_optional()
+    .unwrap()
+}
+
+pub fn get_tally(
+    peer: &mut TestPeer<'_>,
+    latest_block_id: StacksBlockId,
+    reward_cycle: u128,
+    round: u128,
+    aggregate_public_key: Value,
+) -> Option<u128> {
+    readonly_call(
+        peer,
+        &latest_block_id,
+        "signers-voting".into(),
+        "get-tally".into(),
+        vec![
+            Value::UInt(reward_cycle),
+            Value::UInt(round),
+            aggregate_public_key,
+        ],
+    )
+    .expect_optional()
+    .unwrap()
+    .map(|tally| tally.expect_u128().unwrap())
+}
+
+pub fn get_round_start_height(
+    peer: &mut TestPeer<'_>,
+    latest_block_id: StacksBlockId,
+    reward_cycle: u128,
+    round: u128,
+) -> Option<u128> {
+    readonly_call(
+        peer,
+        &latest_block_id,
+        "signers-voting".into(),
+        "get-round-start-height".into(),
+        vec![Value::UInt(reward_cycle), Value::UInt(round)],
+    )
+    .expect_optional()
+    .unwrap()
+    .map(|height| height.expect_u128().unwrap())
+}
+
+pub fn is_round_expired(
+    peer: &mut TestPeer<'_>,
+    latest_block_id: StacksBlockId,
+    reward_cycle: u128,
+    round: u128,
+    height: u128,
+) -> bool {
+    readonly_call(
+        peer,
+        &latest_block_id,
+        "signers-voting".into(),
+        "is-round-expired".into(),
+        vec![
+            Value::UInt(reward_cycle),
+            Value::UInt(round),
+            Value::UInt(height),
+        ],
+    )
+    .expect_bool()
+    .unwrap()
+}
+
+pub fn get_round_info(
+    peer: &mut TestPeer<'_>,
+    latest_block_id: StacksBlockId,
+    reward_cycle: u128,
+    round: u128,
+) -> Option<Value> {
+    let round_tuple = readonly_call(
+        peer,
+        &latest_block_id,
+        "signers-voting".into(),
+        "get-round-info".into(),
+        vec![Value::UInt(reward_cycle), Value::UInt(round)],
+    )
+    .expect_optional()
+    .unwrap();
+    round_tuple
+}
+
+pub fn get_signers(
+    peer: &mut TestPeer<'_>,
+    latest_block_id: StacksBlockId,
+    reward_cycle: u128,
+) -> Option<Value> {
+    readonly_call(
+        peer,
+        &latest_block_id,
+        SIGNERS_NAME.into(),
+        "get-signers".into(),
+        vec![Value::UInt(reward_cycle)],
+    )
+    .expect_optional()
+    .unwrap()
+}
+
+pub fn get_signer_by_index(
+    peer: &mut TestPeer<'_>,
+    latest_block_id: StacksBlockId,
+    reward_cycle: u128,
+    signer_index: u128,
+) -> Option<Value> {
+    readonly_call(
+        peer,
+        &latest_block_id,
+        SIGNERS_NAME.into(),
+        "get-signer-by-index".into(),
+        vec![Value::UInt(reward_cycle), Value::UInt(signer_index)],
+    )
+    .expect_result_ok()
+    .unwrap()
+    .expect_optional()
+    .unwrap()
+}
+
+// In this test case, Alice & Bob advance through setup & check
 // the weight threshold info from the very first reward cycle & round.
 #[test]
 fn test_get_threshold_weight() {
@@ -2130,13 +4428,18 @@ fn test_get_threshold_weight() {
     let bob_address = key_to_stacks_addr(bob_key);
     let bob_principal = PrincipalData::from(bob_address);
 
-    let (mut peer, test_signers, latest_block_id, current_reward_cycle) = prepare_signers_test(
+    let SignersTestSetup {
+        mut peer,
+        test_signers,
+        latest_block_id,
+        current_reward_cycle,
+        ..
+    } = prepare_signers_test(
         function_name!(),
         vec![
-            (alice_principal.clone(), 1000),
-            (bob_principal.clone(), 1000),
+            (alice.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
+            (bob.clone(), TestStacker::DEFAULT_STACKER_AMOUNT),
         ],
-        &[alice.clone(), bob.clone()],
         Some(&observer),
     );
 
@@ -2167,6 +4470,7 @@ pub fn get_threshold_weight(
     threshold_weight
 }
 
+/// Get the weight of the signer at the given index of the reward cycle's signer set
 pub fn nakamoto_tenure(
     peer: &mut TestPeer,
     test_signers: &mut TestSigners,
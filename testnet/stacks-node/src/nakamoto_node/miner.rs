@@ -21,6 +21,7 @@ use std::time::{Duration, Instant};
 use clarity::vm::clarity::ClarityConnection;
 use clarity::vm::types::{PrincipalData, QualifiedContractIdentifier};
 use hashbrown::HashSet;
+use libsigner::decode_chunk;
 use libsigner::v1::messages::{MessageSlotID, SignerMessage};
 use stacks::burnchains::Burnchain;
 use stacks::chainstate::burn::db::sortdb::SortitionDB;
@@ -393,7 +394,7 @@ impl BlockMinerThread {
             .zip(signer_chunks.into_iter())
             .filter_map(|(slot_id, chunk)| {
                 chunk.and_then(|chunk| {
-                    read_next::<SignerMessage, _>(&mut &chunk[..])
+                    decode_chunk::<SignerMessage>(&chunk)
                         .ok()
                         .map(|msg| (*slot_id, msg))
                 })
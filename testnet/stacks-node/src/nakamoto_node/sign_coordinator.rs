@@ -486,6 +486,10 @@ impl SignCoordinator {
                     SignerMessage::DkgResults { .. }
                     | SignerMessage::BlockResponse(_)
                     | SignerMessage::EncryptedSignerState(_)
+                    | SignerMessage::StackerDbSlotUpdate { .. }
+                    | SignerMessage::Nack(_)
+                    | SignerMessage::NackReport { .. }
+                    | SignerMessage::DkgConflict { .. }
                     | SignerMessage::Transactions(_) => None,
                     SignerMessage::Packet(packet) => {
                         debug!("Received signers packet: {packet:?}");
@@ -1487,6 +1487,7 @@ fn stackerdb_filter_bad_transactions() {
     // Submit transactions to stackerdb for the signers and miners to pick up during block verification
     stackerdb
         .send_message_with_retry(SignerMessage::Transactions(invalid_txs))
+        .and_then(|outcome| outcome.into_ack())
         .expect("Failed to write expected transactions to stackerdb");
 
     info!("------------------------- Verify Nakamoto Block Mined -------------------------");